@@ -0,0 +1,67 @@
+//! Build script: embeds the git commit hash as `VRCPULSE_GIT_HASH`
+//!
+//! Falls back to "unknown" when git or the repository is unavailable (e.g.
+//! building from a source tarball). Uncommitted changes append "-dirty".
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    for path in watched_git_paths() {
+        println!("cargo:rerun-if-changed={}", path);
+    }
+    println!("cargo:rerun-if-env-changed=VRCPULSE_GIT_HASH");
+
+    // Allow CI or container builds without .git to inject the hash
+    let hash = std::env::var("VRCPULSE_GIT_HASH")
+        .ok()
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(git_hash);
+
+    println!("cargo:rustc-env=VRCPULSE_GIT_HASH={}", hash);
+}
+
+/// Git files whose changes move `HEAD` or its dirty state
+///
+/// Committing on the current branch only rewrites the branch ref, not
+/// `.git/HEAD`, so the ref HEAD points to is watched too, along with
+/// `packed-refs` in case the ref is packed. `--git-path` resolves them for
+/// worktrees as well. Missing files are left out, since cargo would rerun
+/// the script on every build for them.
+fn watched_git_paths() -> Vec<String> {
+    let mut names = vec![
+        "HEAD".to_string(),
+        "index".to_string(),
+        "packed-refs".to_string(),
+    ];
+    if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        names.push(head_ref);
+    }
+    names
+        .iter()
+        .map(|name| {
+            git(&["rev-parse", "--git-path", name]).unwrap_or_else(|| format!(".git/{}", name))
+        })
+        .filter(|path| Path::new(path).exists())
+        .collect()
+}
+
+fn git_hash() -> String {
+    let Some(hash) = git(&["rev-parse", "--short=12", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+
+    // Detached HEAD still resolves to a commit; only the dirty state needs a suffix
+    match git(&["status", "--porcelain", "--untracked-files=no"]) {
+        Some(status) if !status.is_empty() => format!("{}-dirty", hash),
+        _ => hash,
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
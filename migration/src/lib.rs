@@ -2,6 +2,35 @@ pub use sea_orm_migration::prelude::*;
 
 mod m20260103_001_create_table;
 mod m20260108_001_add_language_column;
+mod m20260112_001_add_fallback_dm_column;
+mod m20260113_001_add_user_reports_guild_index;
+mod m20260114_001_create_alert_silences;
+mod m20260115_001_normalize_language_values;
+mod m20260116_001_create_vrc_builds;
+mod m20260117_001_add_paused_until_column;
+mod m20260118_001_add_user_reports_status_index;
+mod m20260119_001_create_alert_payloads;
+mod m20260120_001_add_alert_payload_communities;
+mod m20260121_001_create_leases;
+mod m20260122_001_create_report_daily_aggregates;
+mod m20260123_001_add_muted_incident_types_column;
+mod m20260124_001_create_guild_alert_filters;
+mod m20260125_001_add_alert_threshold_column;
+mod m20260126_001_add_mention_role_column;
+mod m20260127_001_seed_metric_retention;
+mod m20260128_001_create_user_notification_filters;
+mod m20260129_001_add_digest_enabled_column;
+mod m20260130_001_add_component_group_column;
+mod m20260131_001_add_alert_quiet_hours_columns;
+mod m20260201_001_create_guild_alert_channels;
+mod m20260202_001_add_channel_invalid_column;
+mod m20260203_001_add_send_failures_column;
+mod m20260204_001_add_user_disabled_reason_column;
+mod m20260205_001_create_guild_webhooks;
+mod m20260206_001_create_status_transitions;
+mod m20260207_001_add_report_role_column;
+mod m20260208_001_add_sent_alerts_recipient_index;
+mod m20260209_001_split_incident_alert_types;
 
 pub struct Migrator;
 
@@ -11,6 +40,35 @@ impl MigratorTrait for Migrator {
         vec![
             Box::new(m20260103_001_create_table::Migration),
             Box::new(m20260108_001_add_language_column::Migration),
+            Box::new(m20260112_001_add_fallback_dm_column::Migration),
+            Box::new(m20260113_001_add_user_reports_guild_index::Migration),
+            Box::new(m20260114_001_create_alert_silences::Migration),
+            Box::new(m20260115_001_normalize_language_values::Migration),
+            Box::new(m20260116_001_create_vrc_builds::Migration),
+            Box::new(m20260117_001_add_paused_until_column::Migration),
+            Box::new(m20260118_001_add_user_reports_status_index::Migration),
+            Box::new(m20260119_001_create_alert_payloads::Migration),
+            Box::new(m20260120_001_add_alert_payload_communities::Migration),
+            Box::new(m20260121_001_create_leases::Migration),
+            Box::new(m20260122_001_create_report_daily_aggregates::Migration),
+            Box::new(m20260123_001_add_muted_incident_types_column::Migration),
+            Box::new(m20260124_001_create_guild_alert_filters::Migration),
+            Box::new(m20260125_001_add_alert_threshold_column::Migration),
+            Box::new(m20260126_001_add_mention_role_column::Migration),
+            Box::new(m20260127_001_seed_metric_retention::Migration),
+            Box::new(m20260128_001_create_user_notification_filters::Migration),
+            Box::new(m20260129_001_add_digest_enabled_column::Migration),
+            Box::new(m20260130_001_add_component_group_column::Migration),
+            Box::new(m20260131_001_add_alert_quiet_hours_columns::Migration),
+            Box::new(m20260201_001_create_guild_alert_channels::Migration),
+            Box::new(m20260202_001_add_channel_invalid_column::Migration),
+            Box::new(m20260203_001_add_send_failures_column::Migration),
+            Box::new(m20260204_001_add_user_disabled_reason_column::Migration),
+            Box::new(m20260205_001_create_guild_webhooks::Migration),
+            Box::new(m20260206_001_create_status_transitions::Migration),
+            Box::new(m20260207_001_add_report_role_column::Migration),
+            Box::new(m20260208_001_add_sent_alerts_recipient_index::Migration),
+            Box::new(m20260209_001_split_incident_alert_types::Migration),
         ]
     }
 }
@@ -0,0 +1,43 @@
+//! Add fallback_dm column to guild_configs table
+//!
+//! Opt-in flag: when the alert channel is unusable, DM the guild owner instead.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .add_column(boolean(GuildConfigs::FallbackDm).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .drop_column(GuildConfigs::FallbackDm)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildConfigs {
+    Table,
+    FallbackDm,
+}
@@ -0,0 +1,47 @@
+//! Add user_reports(guild_id, created_at) index
+//!
+//! Supports per-guild report counts in /config show. sent_alerts needs no new
+//! index: idx_sent_alerts_lookup already leads with guild_id.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_reports_guild_created")
+                    .table(UserReports::Table)
+                    .col(UserReports::GuildId)
+                    .col(UserReports::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_user_reports_guild_created")
+                    .table(UserReports::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserReports {
+    Table,
+    GuildId,
+    CreatedAt,
+}
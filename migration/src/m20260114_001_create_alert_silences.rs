@@ -0,0 +1,62 @@
+//! Create alert_silences table
+//!
+//! Admin-scheduled windows during which threshold alerts are not delivered to
+//! a guild. `incident_type` NULL silences every type.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AlertSilences::Table)
+                    .if_not_exists()
+                    .col(pk_auto(AlertSilences::Id))
+                    .col(string(AlertSilences::GuildId))
+                    .col(string_null(AlertSilences::IncidentType))
+                    .col(timestamp(AlertSilences::Until))
+                    .col(string(AlertSilences::CreatedBy))
+                    .col(timestamp(AlertSilences::CreatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index: alert_silences(guild_id, until)
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_alert_silences_guild_until")
+                    .table(AlertSilences::Table)
+                    .col(AlertSilences::GuildId)
+                    .col(AlertSilences::Until)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AlertSilences::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AlertSilences {
+    Table,
+    Id,
+    GuildId,
+    IncidentType,
+    Until,
+    CreatedBy,
+    CreatedAt,
+}
@@ -0,0 +1,53 @@
+//! Normalize stored language preferences
+//!
+//! Earlier writes stored codes as given ("en-US", " KO", ...). Language codes
+//! are now normalized on write, so repair existing rows to match: trim,
+//! lowercase, reduce region variants to their primary subtag, and reset
+//! anything unsupported to NULL (auto-detect).
+//!
+//! This is a data repair; `down` is a no-op.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Must match `i18n::SUPPORTED_LOCALES` at the time of this migration
+const SUPPORTED: &str = "'en', 'ko'";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        for table in ["guild_configs", "user_configs"] {
+            // Trim, lowercase, and unify the subtag separator
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET language = replace(lower(trim(language)), '_', '-') \
+                 WHERE language IS NOT NULL"
+            ))
+            .await?;
+
+            // Reduce region variants to the primary subtag
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET language = substr(language, 1, instr(language, '-') - 1) \
+                 WHERE instr(language, '-') > 0"
+            ))
+            .await?;
+
+            // Reset unsupported (including empty) values to auto-detect
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET language = NULL \
+                 WHERE language IS NOT NULL AND language NOT IN ({SUPPORTED})"
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Original values are not recoverable
+        Ok(())
+    }
+}
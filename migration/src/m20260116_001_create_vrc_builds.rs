@@ -0,0 +1,46 @@
+//! Create vrc_builds table
+//!
+//! VRChat client build versions observed on the public config endpoint, with
+//! the time each was first seen. Threshold alerts mention a recent new build.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(VrcBuilds::Table)
+                    .if_not_exists()
+                    .col(pk_auto(VrcBuilds::Id))
+                    .col(string_uniq(VrcBuilds::Version))
+                    .col(string_null(VrcBuilds::DeploymentGroup))
+                    .col(timestamp(VrcBuilds::FirstSeenAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VrcBuilds::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum VrcBuilds {
+    Table,
+    Id,
+    Version,
+    DeploymentGroup,
+    FirstSeenAt,
+}
@@ -0,0 +1,44 @@
+//! Add paused_until column to guild_configs table
+//!
+//! Temporary pause from the /config unregister prompt: alerts are skipped
+//! while `paused_until` is in the future and resume on their own afterwards.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .add_column(timestamp_null(GuildConfigs::PausedUntil))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .drop_column(GuildConfigs::PausedUntil)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildConfigs {
+    Table,
+    PausedUntil,
+}
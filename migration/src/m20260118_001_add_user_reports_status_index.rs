@@ -0,0 +1,50 @@
+//! Add user_reports(user_id, status, created_at) index
+//!
+//! Reports that lose a submission race are now kept with status `duplicate`
+//! instead of being deleted, so the per-user cooldown query filters on status
+//! over a growing table.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_reports_user_status_created")
+                    .table(UserReports::Table)
+                    .col(UserReports::UserId)
+                    .col(UserReports::Status)
+                    .col(UserReports::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_user_reports_user_status_created")
+                    .table(UserReports::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserReports {
+    Table,
+    UserId,
+    Status,
+    CreatedAt,
+}
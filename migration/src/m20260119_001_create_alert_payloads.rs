@@ -0,0 +1,66 @@
+//! Create alert_payloads table
+//!
+//! The content of the latest threshold alert fan-out per reference ID, so a
+//! guild that registers during an ongoing alert can be sent the same alert.
+//! `recent_reports` is a JSON array of RFC 3339 report timestamps.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AlertPayloads::Table)
+                    .if_not_exists()
+                    .col(string(AlertPayloads::ReferenceId).primary_key())
+                    .col(string(AlertPayloads::IncidentType))
+                    .col(big_integer(AlertPayloads::ReportCount))
+                    .col(big_integer(AlertPayloads::IntervalMinutes))
+                    .col(text(AlertPayloads::RecentReports))
+                    .col(big_integer(AlertPayloads::RecentTotal))
+                    .col(timestamp(AlertPayloads::CreatedAt))
+                    .col(timestamp(AlertPayloads::UpdatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Index: alert_payloads(updated_at)
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_alert_payloads_updated")
+                    .table(AlertPayloads::Table)
+                    .col(AlertPayloads::UpdatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AlertPayloads::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AlertPayloads {
+    Table,
+    ReferenceId,
+    IncidentType,
+    ReportCount,
+    IntervalMinutes,
+    RecentReports,
+    RecentTotal,
+    CreatedAt,
+    UpdatedAt,
+}
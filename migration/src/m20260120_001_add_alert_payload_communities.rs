@@ -0,0 +1,45 @@
+//! Add communities column to alert_payloads table
+//!
+//! Number of communities (guilds, plus one for user-install reports) among
+//! the reports behind an alert, so replayed alerts show the same spread.
+//! NULL for payloads stored before the column existed.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AlertPayloads::Table)
+                    .add_column(big_integer_null(AlertPayloads::Communities))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AlertPayloads::Table)
+                    .drop_column(AlertPayloads::Communities)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AlertPayloads {
+    Table,
+    Communities,
+}
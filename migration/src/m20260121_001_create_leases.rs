@@ -0,0 +1,47 @@
+//! Create leases table
+//!
+//! Named leases used for leader election when several bot instances share
+//! one database (e.g. during a zero-downtime deploy). Only the holder of the
+//! "collector" lease runs the collector pollers and scheduled jobs.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Leases::Table)
+                    .if_not_exists()
+                    .col(string(Leases::Name).primary_key())
+                    .col(string(Leases::Holder))
+                    .col(timestamp(Leases::ExpiresAt))
+                    .col(timestamp(Leases::UpdatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Leases::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Leases {
+    Table,
+    Name,
+    Holder,
+    ExpiresAt,
+    UpdatedAt,
+}
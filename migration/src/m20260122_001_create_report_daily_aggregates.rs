@@ -0,0 +1,56 @@
+//! Create report_daily_aggregates table
+//!
+//! Per-day, per-type report counts, written for each completed UTC day
+//! before raw `user_reports` rows are pruned, so trends outlive the raw
+//! retention window. No user or guild IDs are kept.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReportDailyAggregates::Table)
+                    .if_not_exists()
+                    .col(date(ReportDailyAggregates::Day))
+                    .col(string(ReportDailyAggregates::IncidentType))
+                    .col(big_integer(ReportDailyAggregates::DistinctReporters))
+                    .col(big_integer(ReportDailyAggregates::TotalReports))
+                    .col(big_integer(ReportDailyAggregates::GuildCount))
+                    .col(timestamp(ReportDailyAggregates::UpdatedAt))
+                    .primary_key(
+                        Index::create()
+                            .col(ReportDailyAggregates::Day)
+                            .col(ReportDailyAggregates::IncidentType),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReportDailyAggregates::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ReportDailyAggregates {
+    Table,
+    Day,
+    IncidentType,
+    DistinctReporters,
+    TotalReports,
+    GuildCount,
+    UpdatedAt,
+}
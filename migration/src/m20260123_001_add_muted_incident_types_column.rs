@@ -0,0 +1,45 @@
+//! Add muted_incident_types column to guild_configs table
+//!
+//! Comma-separated incident types a guild has opted out of via
+//! /config alerts. NULL (the value for every existing guild) means all
+//! types are enabled, and types added later start enabled too.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .add_column(string_null(GuildConfigs::MutedIncidentTypes))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .drop_column(GuildConfigs::MutedIncidentTypes)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildConfigs {
+    Table,
+    MutedIncidentTypes,
+}
@@ -0,0 +1,51 @@
+//! Create guild_alert_filters table
+//!
+//! Per-guild opt-outs for whole alert categories (threshold alerts,
+//! maintenance reminders). A guild without a row for a category receives it.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GuildAlertFilters::Table)
+                    .if_not_exists()
+                    .col(string(GuildAlertFilters::GuildId))
+                    .col(string(GuildAlertFilters::AlertType))
+                    .col(boolean(GuildAlertFilters::Enabled))
+                    .col(timestamp(GuildAlertFilters::UpdatedAt))
+                    .primary_key(
+                        Index::create()
+                            .col(GuildAlertFilters::GuildId)
+                            .col(GuildAlertFilters::AlertType),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GuildAlertFilters::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildAlertFilters {
+    Table,
+    GuildId,
+    AlertType,
+    Enabled,
+    UpdatedAt,
+}
@@ -0,0 +1,44 @@
+//! Add alert_threshold column to guild_configs table
+//!
+//! Optional per-guild report threshold set with /config threshold. NULL
+//! means the guild follows the global `bot_config.report_threshold`.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .add_column(big_integer_null(GuildConfigs::AlertThreshold))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .drop_column(GuildConfigs::AlertThreshold)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildConfigs {
+    Table,
+    AlertThreshold,
+}
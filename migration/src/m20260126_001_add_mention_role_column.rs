@@ -0,0 +1,44 @@
+//! Add mention_role_id column to guild_configs table
+//!
+//! Role pinged in the message content of threshold alerts, set with
+//! /config mention. NULL means alerts ping nobody.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .add_column(string_null(GuildConfigs::MentionRoleId))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(GuildConfigs::Table)
+                    .drop_column(GuildConfigs::MentionRoleId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildConfigs {
+    Table,
+    MentionRoleId,
+}
@@ -0,0 +1,34 @@
+//! Seed the metric retention setting
+//!
+//! `retention.metric_days` controls how long `metric_logs`, `status_logs`,
+//! and `component_logs` rows are kept before the daily prune deletes them.
+//! Changed with /admin config retention.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT OR IGNORE INTO bot_config (key, value, updated_at) \
+                 VALUES ('retention.metric_days', '30', datetime('now'))",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DELETE FROM bot_config WHERE key = 'retention.metric_days'")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+//! Create user_notification_filters table
+//!
+//! Per-user opt-outs for whole alert categories delivered by DM (threshold
+//! alerts, maintenance reminders). A user without a row for a category
+//! receives it. The guild counterpart is `guild_alert_filters`.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserNotificationFilters::Table)
+                    .if_not_exists()
+                    .col(string(UserNotificationFilters::UserId))
+                    .col(string(UserNotificationFilters::AlertType))
+                    .col(boolean(UserNotificationFilters::Enabled))
+                    .col(timestamp(UserNotificationFilters::UpdatedAt))
+                    .primary_key(
+                        Index::create()
+                            .col(UserNotificationFilters::UserId)
+                            .col(UserNotificationFilters::AlertType),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(UserNotificationFilters::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserNotificationFilters {
+    Table,
+    UserId,
+    AlertType,
+    Enabled,
+    UpdatedAt,
+}
@@ -0,0 +1,45 @@
+//! Add group_id column to component_logs table
+//!
+//! Parent group of the component as reported by the status page, so
+//! /status components can group components without a hardcoded list.
+//! Rows collected before this migration keep NULL.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ComponentLogs::Table)
+                    .add_column(string_null(ComponentLogs::GroupId))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ComponentLogs::Table)
+                    .drop_column(ComponentLogs::GroupId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ComponentLogs {
+    Table,
+    GroupId,
+}
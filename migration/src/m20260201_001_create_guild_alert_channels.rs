@@ -0,0 +1,67 @@
+//! Create guild_alert_channels table
+//!
+//! Extra alert channels that receive threshold alerts for chosen incident
+//! types, added with `/config setup channel for`. `incident_types` is a
+//! comma-separated list of incident type keys. `guild_configs.channel_id`
+//! stays the main channel for every type not routed here.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GuildAlertChannels::Table)
+                    .if_not_exists()
+                    .col(pk_auto(GuildAlertChannels::Id))
+                    .col(string(GuildAlertChannels::GuildId))
+                    .col(string(GuildAlertChannels::ChannelId))
+                    .col(text(GuildAlertChannels::IncidentTypes))
+                    .col(boolean(GuildAlertChannels::Enabled).default(true))
+                    .col(timestamp(GuildAlertChannels::CreatedAt))
+                    .col(timestamp(GuildAlertChannels::UpdatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Unique: one row per channel in a guild
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_guild_alert_channels_guild_channel")
+                    .table(GuildAlertChannels::Table)
+                    .col(GuildAlertChannels::GuildId)
+                    .col(GuildAlertChannels::ChannelId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GuildAlertChannels::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildAlertChannels {
+    Table,
+    Id,
+    GuildId,
+    ChannelId,
+    IncidentTypes,
+    Enabled,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,45 @@
+//! Add disabled_reason column to user_configs table
+//!
+//! Set when the bot disables a registration itself, e.g. `dm_unreachable`
+//! after Discord refused to deliver a DM alert. NULL for registrations the
+//! user unregistered. Cleared by /config setup.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserConfigs::Table)
+                    .add_column(string_null(UserConfigs::DisabledReason))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserConfigs::Table)
+                    .drop_column(UserConfigs::DisabledReason)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserConfigs {
+    Table,
+    DisabledReason,
+}
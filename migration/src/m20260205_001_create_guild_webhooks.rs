@@ -0,0 +1,67 @@
+//! Create guild_webhooks table
+//!
+//! HTTP endpoints that receive threshold alerts as signed JSON, added with
+//! `/config webhook add`. `alert_types` is a comma-separated list of incident
+//! type keys; an empty list receives every type. `secret` signs each payload
+//! (HMAC-SHA256) so the receiver can verify it came from the bot.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GuildWebhooks::Table)
+                    .if_not_exists()
+                    .col(pk_auto(GuildWebhooks::Id))
+                    .col(string(GuildWebhooks::GuildId))
+                    .col(text(GuildWebhooks::Url))
+                    .col(string(GuildWebhooks::Secret))
+                    .col(text(GuildWebhooks::AlertTypes).default(""))
+                    .col(boolean(GuildWebhooks::Enabled).default(true))
+                    .col(timestamp(GuildWebhooks::CreatedAt))
+                    .col(timestamp(GuildWebhooks::UpdatedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Webhooks are always looked up per guild
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_guild_webhooks_guild")
+                    .table(GuildWebhooks::Table)
+                    .col(GuildWebhooks::GuildId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GuildWebhooks::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum GuildWebhooks {
+    Table,
+    Id,
+    GuildId,
+    Url,
+    Secret,
+    AlertTypes,
+    Enabled,
+    CreatedAt,
+    UpdatedAt,
+}
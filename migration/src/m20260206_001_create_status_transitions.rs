@@ -0,0 +1,59 @@
+//! Create status_transitions table
+//!
+//! One row each time the overall status page indicator changes (e.g. `none`
+//! to `major`), detected by the status poller when it stores a new
+//! `status_logs` snapshot. `transitioned_at` is the status page update time of
+//! the snapshot that showed the new indicator.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(StatusTransitions::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StatusTransitions::Id))
+                    .col(string(StatusTransitions::FromIndicator))
+                    .col(string(StatusTransitions::ToIndicator))
+                    .col(timestamp(StatusTransitions::TransitionedAt))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Transitions are read newest first or by time range
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_status_transitions_transitioned_at")
+                    .table(StatusTransitions::Table)
+                    .col(StatusTransitions::TransitionedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StatusTransitions::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum StatusTransitions {
+    Table,
+    Id,
+    FromIndicator,
+    ToIndicator,
+    TransitionedAt,
+}
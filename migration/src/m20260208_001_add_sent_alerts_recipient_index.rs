@@ -0,0 +1,54 @@
+//! Make sent_alerts deduplication hold for guild and DM rows
+//!
+//! `idx_sent_alerts_lookup` covers (guild_id, user_id, alert_type,
+//! reference_id), but every row leaves one of guild_id/user_id NULL and
+//! SQLite treats NULLs in a unique index as distinct, so the same alert could
+//! be recorded (and sent) twice. Remove existing duplicates, keeping the
+//! earliest row, and add a unique index over the recipient with NULLs folded
+//! to ''.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "DELETE FROM sent_alerts WHERE id NOT IN ( \
+                 SELECT MIN(id) FROM sent_alerts \
+                 GROUP BY IFNULL(guild_id, ''), IFNULL(user_id, ''), alert_type, reference_id \
+             )",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_sent_alerts_recipient ON sent_alerts \
+             (IFNULL(guild_id, ''), IFNULL(user_id, ''), alert_type, reference_id)",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_sent_alerts_recipient")
+                    .table(SentAlerts::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SentAlerts {
+    Table,
+}
@@ -0,0 +1,75 @@
+//! Split the `incident` alert type into `incident_opened` and `incident_resolved`
+//!
+//! Incident notices used to share one alert type, so a guild or user could
+//! only mute all of them at once. Existing sent_alerts rows are retagged by
+//! their reference ID (`incident_<id>_resolved` becomes `incident_resolved`,
+//! everything else `incident_opened`), and every filter row for `incident` is
+//! copied to both new types so current opt-outs keep applying.
+
+use sea_orm_migration::prelude::*;
+
+const FILTER_TABLES: [(&str, &str); 2] = [
+    ("guild_alert_filters", "guild_id"),
+    ("user_notification_filters", "user_id"),
+];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "UPDATE sent_alerts SET alert_type = 'incident_resolved' \
+             WHERE alert_type = 'incident' AND reference_id LIKE '%\\_resolved' ESCAPE '\\'",
+        )
+        .await?;
+        db.execute_unprepared(
+            "UPDATE sent_alerts SET alert_type = 'incident_opened' WHERE alert_type = 'incident'",
+        )
+        .await?;
+
+        for (table, owner) in FILTER_TABLES {
+            for alert_type in ["incident_opened", "incident_resolved"] {
+                db.execute_unprepared(&format!(
+                    "INSERT OR IGNORE INTO {table} ({owner}, alert_type, enabled, updated_at) \
+                     SELECT {owner}, '{alert_type}', enabled, updated_at FROM {table} \
+                     WHERE alert_type = 'incident'"
+                ))
+                .await?;
+            }
+            db.execute_unprepared(&format!(
+                "DELETE FROM {table} WHERE alert_type = 'incident'"
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "UPDATE sent_alerts SET alert_type = 'incident' \
+             WHERE alert_type IN ('incident_opened', 'incident_resolved')",
+        )
+        .await?;
+
+        for (table, _) in FILTER_TABLES {
+            db.execute_unprepared(&format!(
+                "UPDATE OR IGNORE {table} SET alert_type = 'incident' \
+                 WHERE alert_type = 'incident_opened'"
+            ))
+            .await?;
+            db.execute_unprepared(&format!(
+                "DELETE FROM {table} WHERE alert_type IN ('incident_opened', 'incident_resolved')"
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+}
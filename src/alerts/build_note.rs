@@ -0,0 +1,176 @@
+//! New VRChat build note for threshold alerts
+//!
+//! Report waves often follow a client release. When an alert fires shortly
+//! after a new build was first seen, the alert mentions the build so readers
+//! can make the connection; it does not claim the build is the cause.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use tracing::warn;
+
+use crate::entity::{bot_config, vrc_builds};
+use crate::repository::BuildRepository;
+
+/// `bot_config` key for how long after a new build alerts mention it
+pub const BUILD_NOTE_WINDOW_KEY: &str = "build_note_window_hours";
+
+/// Window when `build_note_window_hours` is not configured
+pub const DEFAULT_BUILD_NOTE_WINDOW_HOURS: i64 = 6;
+
+/// Load the configured window in hours; 0 disables the note
+pub async fn load_window_hours(db: &DatabaseConnection) -> i64 {
+    bot_config::Entity::find_by_id(BUILD_NOTE_WINDOW_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .unwrap_or(DEFAULT_BUILD_NOTE_WINDOW_HOURS)
+        .max(0)
+}
+
+/// Whether a build first seen at `first_seen` is within the note window at `now`
+pub fn is_within_window(first_seen: DateTime<Utc>, now: DateTime<Utc>, window_hours: i64) -> bool {
+    let age = now.signed_duration_since(first_seen);
+    window_hours > 0 && age >= Duration::zero() && age <= Duration::hours(window_hours)
+}
+
+/// Newest build to mention in an alert, if one was first seen within the window
+pub async fn recent_build(db: &DatabaseConnection) -> Option<vrc_builds::Model> {
+    let window_hours = load_window_hours(db).await;
+    if window_hours == 0 {
+        return None;
+    }
+
+    let build = match BuildRepository::new(Arc::new(db.clone()))
+        .latest_new_build()
+        .await
+    {
+        Ok(build) => build?,
+        Err(e) => {
+            warn!(error = %e, "Failed to load latest VRChat build");
+            return None;
+        }
+    };
+
+    is_within_window(build.first_seen_at, Utc::now(), window_hours).then_some(build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::TimeZone;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    async fn set_window(db: &DatabaseConnection, value: &str) {
+        bot_config::ActiveModel {
+            key: Set(BUILD_NOTE_WINDOW_KEY.to_string()),
+            value: Set(value.to_string()),
+            updated_at: Set(Utc::now()),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    /// Record a baseline build, then `version` first seen `age` ago
+    async fn new_build(db: &DatabaseConnection, version: &str, age: Duration) {
+        let repo = BuildRepository::new(Arc::new(db.clone()));
+        let now = Utc::now();
+        repo.record("baseline", None, now - Duration::days(30))
+            .await
+            .unwrap();
+        repo.record(version, None, now - age).await.unwrap();
+    }
+
+    #[test]
+    fn test_is_within_window() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let cases = [
+            (Duration::zero(), 6, true),
+            (Duration::hours(2), 6, true),
+            (Duration::hours(6), 6, true),
+            (Duration::hours(6) + Duration::seconds(1), 6, false),
+            (Duration::days(2), 6, false),
+            // First seen after `now` (clock skew)
+            (-Duration::seconds(1), 6, false),
+            // A zero window disables the note
+            (Duration::zero(), 0, false),
+            (Duration::hours(1), -1, false),
+        ];
+        for (age, window_hours, expected) in cases {
+            assert_eq!(
+                is_within_window(now - age, now, window_hours),
+                expected,
+                "{age} in {window_hours}h"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_window_hours() {
+        let db = connect_in_memory().await;
+        assert_eq!(
+            load_window_hours(&db).await,
+            DEFAULT_BUILD_NOTE_WINDOW_HOURS
+        );
+
+        let cases = [("12", 12), ("0", 0), ("-4", 0), ("soon", 6)];
+        for (value, expected) in cases {
+            let db = connect_in_memory().await;
+            set_window(&db, value).await;
+
+            assert_eq!(load_window_hours(&db).await, expected, "{value}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recent_build_within_window() {
+        let db = connect_in_memory().await;
+        new_build(&db, "build-1581", Duration::hours(2)).await;
+
+        let build = recent_build(&db).await.unwrap();
+
+        assert_eq!(build.version, "build-1581");
+    }
+
+    #[tokio::test]
+    async fn test_recent_build_outside_window() {
+        let db = connect_in_memory().await;
+        new_build(&db, "build-1581", Duration::hours(7)).await;
+
+        assert_eq!(recent_build(&db).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_recent_build_configured_window() {
+        let db = connect_in_memory().await;
+        new_build(&db, "build-1581", Duration::hours(7)).await;
+        set_window(&db, "8").await;
+
+        assert!(recent_build(&db).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_recent_build_disabled() {
+        let db = connect_in_memory().await;
+        new_build(&db, "build-1581", Duration::minutes(5)).await;
+        set_window(&db, "0").await;
+
+        assert_eq!(recent_build(&db).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_baseline_build_not_mentioned() {
+        let db = connect_in_memory().await;
+        BuildRepository::new(Arc::new(db.clone()))
+            .record("build-1580", None, Utc::now())
+            .await
+            .unwrap();
+
+        assert_eq!(recent_build(&db).await, None);
+    }
+}
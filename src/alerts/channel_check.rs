@@ -0,0 +1,101 @@
+//! Detection of alert channels the bot can no longer reach
+//!
+//! When a channel is deleted or the bot loses access to it, every alert to
+//! that guild fails and its `sent_alerts` row is rolled back. A job fetches
+//! each enabled guild's alert channel every 6 hours; channels that fail with
+//! a permanent error (Unknown Channel, Missing Access, 403/404) get their
+//! registration disabled with `guild_configs.channel_invalid` set. Transient
+//! failures leave the guild alone until the next check. `/admin config
+//! validate-channels` runs the same check on demand.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sea_orm::DatabaseConnection;
+use serenity::all::{ChannelId, GuildId, Http};
+use tracing::{debug, info, warn};
+
+use crate::repository::GuildConfigRepository;
+use crate::scheduler::{JobResult, Schedule, Scheduler};
+
+use super::delivery::is_permanent_delivery_error;
+
+/// How often alert channels are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Outcome of one channel check
+#[derive(Debug, Clone, Default)]
+pub struct ChannelCheckSummary {
+    /// Enabled guilds whose channel was fetched
+    pub checked: usize,
+    /// Guilds disabled because their channel is gone or inaccessible
+    pub invalidated: Vec<GuildId>,
+    /// Fetches that failed with a transient error, left for the next check
+    pub transient_failures: usize,
+}
+
+/// Register the channel check job
+pub fn register(scheduler: &mut Scheduler, http: Arc<Http>, db: Arc<DatabaseConnection>) {
+    scheduler.register(
+        "validate_channels",
+        Schedule::Interval(CHECK_INTERVAL),
+        move || run_check(http.clone(), db.clone()),
+    );
+}
+
+async fn run_check(http: Arc<Http>, db: Arc<DatabaseConnection>) -> JobResult {
+    let summary = validate_channels(&http, &db).await?;
+    match summary.invalidated.len() {
+        0 => debug!(checked = summary.checked, "All alert channels reachable"),
+        count => info!(
+            checked = summary.checked,
+            invalidated = count,
+            "Disabled guilds with unreachable alert channels"
+        ),
+    }
+    Ok(())
+}
+
+/// Fetch every enabled guild's alert channel and disable guilds whose
+/// channel is permanently unreachable
+pub async fn validate_channels(
+    http: &Http,
+    db: &Arc<DatabaseConnection>,
+) -> Result<ChannelCheckSummary, sea_orm::DbErr> {
+    let repo = GuildConfigRepository::new(db.clone());
+    let mut summary = ChannelCheckSummary::default();
+
+    for guild in repo.list_all_enabled().await? {
+        let (Ok(guild_id), Some(channel_id)) = (
+            guild.guild_id.parse::<u64>(),
+            guild
+                .channel_id
+                .as_deref()
+                .and_then(|id| id.parse::<u64>().ok()),
+        ) else {
+            continue;
+        };
+        let guild_id = GuildId::new(guild_id);
+        summary.checked += 1;
+
+        let Err(e) = http.get_channel(ChannelId::new(channel_id)).await else {
+            continue;
+        };
+        if !is_permanent_delivery_error(&e) {
+            debug!(guild_id = %guild_id, channel_id, error = %e, "Alert channel check failed, retrying next run");
+            summary.transient_failures += 1;
+            continue;
+        }
+
+        repo.mark_channel_invalid(guild_id).await?;
+        warn!(
+            guild_id = %guild_id,
+            channel_id,
+            error = %e,
+            "Alert channel unreachable, disabled guild registration"
+        );
+        summary.invalidated.push(guild_id);
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,300 @@
+//! Alerts for VRChat status page component changes
+//!
+//! The status poller compares each new `component_logs` snapshot with the
+//! previous one. When a component gets worse (e.g. `operational` to
+//! `major_outage`, or `partial_outage` to `major_outage`) registered guilds
+//! and DM users get a notice; when it is `operational` again, recipients that
+//! were told about the outage get a recovery notice. `under_maintenance` is
+//! left to the maintenance reminders.
+//!
+//! Notices go through `delivery`, like threshold alerts. Deliveries are
+//! deduplicated in `sent_alerts` (type `component`) with
+//! reference IDs `component_<id>_<new_status>_<hour>`, so a component that
+//! flaps within an hour alerts once. The owner can turn these alerts off
+//! globally with the `bot_config` key `alerts.component_enabled`.
+
+use chrono::{DateTime, Utc};
+use rust_i18n::t;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serenity::all::{Colour, CreateEmbed, CreateEmbedFooter, Timestamp};
+use tracing::{debug, warn};
+
+use crate::commands::shared::components::{component_name, status_emoji};
+use crate::commands::shared::{colors, links};
+use crate::entity::{bot_config, sent_alerts};
+
+use super::delivery::{self, AlertNotice, Notifier, get_registered_guilds, get_registered_users};
+use super::reference::block_start;
+use super::webhook::{self, WebhookNotice, WebhookPayload};
+
+/// `sent_alerts.alert_type` for component status notices
+pub(crate) const ALERT_TYPE_COMPONENT: &str = "component";
+
+/// `bot_config` key that turns component alerts off when set to "false"
+pub const COMPONENT_ENABLED_KEY: &str = "alerts.component_enabled";
+
+/// Length of the deduplication bucket in reference IDs
+const BUCKET_MINUTES: i64 = 60;
+
+/// Status of a component that is working normally
+const OPERATIONAL: &str = "operational";
+
+// =============================================================================
+// Change Detection
+// =============================================================================
+
+/// Direction of a component status change worth a notice
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Worse than before
+    Degraded,
+    /// Back to operational after an outage
+    Recovered,
+}
+
+/// One component whose status changed between two snapshots
+#[derive(Debug, Clone)]
+pub struct ComponentChange {
+    pub component_id: String,
+    /// Status page name (translated per recipient)
+    pub name: String,
+    pub old_status: String,
+    pub new_status: String,
+    /// Status page update time of the snapshot that showed the change
+    pub changed_at: DateTime<Utc>,
+    pub kind: ChangeKind,
+}
+
+/// Outage severity of a status; `None` for maintenance and unknown values
+fn severity(status: &str) -> Option<u8> {
+    match status {
+        "operational" => Some(0),
+        "degraded_performance" => Some(1),
+        "partial_outage" => Some(2),
+        "major_outage" => Some(3),
+        _ => None,
+    }
+}
+
+/// Whether going from `old` to `new` warrants a notice, and which one
+pub fn classify(old: &str, new: &str) -> Option<ChangeKind> {
+    let (old, new) = (severity(old)?, severity(new)?);
+    if new > old {
+        Some(ChangeKind::Degraded)
+    } else if new == 0 && old > 0 {
+        Some(ChangeKind::Recovered)
+    } else {
+        None
+    }
+}
+
+/// `sent_alerts.reference_id` for a change
+///
+/// Format: `component_<id>_<new_status>_<unix>`, where the time is the start
+/// of the hour the change was published in.
+pub fn reference_id(change: &ComponentChange) -> String {
+    format!(
+        "component_{}_{}_{}",
+        change.component_id,
+        change.new_status,
+        block_start(change.changed_at, BUCKET_MINUTES).timestamp()
+    )
+}
+
+/// Whether the owner left component alerts on (the default)
+pub async fn is_enabled(db: &DatabaseConnection) -> bool {
+    bot_config::Entity::find_by_id(COMPONENT_ENABLED_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .is_none_or(|c| c.value != "false")
+}
+
+// =============================================================================
+// Delivery
+// =============================================================================
+
+/// One change, as sent to every recipient
+struct ComponentNotice<'a> {
+    change: &'a ComponentChange,
+    reference_id: String,
+}
+
+impl AlertNotice for ComponentNotice<'_> {
+    fn alert_type(&self) -> &'static str {
+        ALERT_TYPE_COMPONENT
+    }
+
+    fn reference_id(&self) -> &str {
+        &self.reference_id
+    }
+
+    fn route(&self) -> &str {
+        ALERT_TYPE_COMPONENT
+    }
+
+    fn embed(&self, locale: &str, _with_attachment: bool) -> CreateEmbed {
+        build_notice_embed(self.change, locale)
+    }
+
+    fn webhook_payload(&self, guild_id: &str) -> WebhookPayload {
+        WebhookPayload::Notice(WebhookNotice {
+            event: webhook::EVENT_COMPONENT_NOTICE,
+            guild_id: guild_id.to_string(),
+            reference_id: self.reference_id.clone(),
+            title: self.change.name.clone(),
+            status: self.change.new_status.clone(),
+            url: links::status_page(),
+            sent_at: Utc::now(),
+        })
+    }
+}
+
+/// Send notices for `changes` in a background task, so a slow fan-out never
+/// delays the next poll
+pub fn spawn_notices(notifier: Notifier, db: DatabaseConnection, changes: Vec<ComponentChange>) {
+    if changes.is_empty() {
+        return;
+    }
+    let tasks = notifier.tasks.clone();
+    tasks.spawn(async move {
+        send_notices(&notifier, &db, &changes).await;
+    });
+}
+
+async fn send_notices(notifier: &Notifier, db: &DatabaseConnection, changes: &[ComponentChange]) {
+    if !is_enabled(db).await {
+        debug!(
+            changes = changes.len(),
+            "Component alerts disabled, skipping"
+        );
+        return;
+    }
+
+    let guilds = get_registered_guilds(db, ALERT_TYPE_COMPONENT).await;
+    let users = get_registered_users(db, ALERT_TYPE_COMPONENT).await;
+
+    for change in changes {
+        let notice = ComponentNotice {
+            change,
+            reference_id: reference_id(change),
+        };
+        if change.kind == ChangeKind::Degraded {
+            delivery::fan_out(notifier, db, &guilds, &users, &notice).await;
+            continue;
+        }
+
+        // Recovery notices only go to recipients that heard about the outage
+        let mut told_guilds = Vec::new();
+        for guild in &guilds {
+            if was_told_outage(db, Some(&guild.guild_id), None, &change.component_id).await {
+                told_guilds.push(guild.clone());
+            }
+        }
+        let mut told_users = Vec::new();
+        for user in &users {
+            if was_told_outage(db, None, Some(&user.user_id), &change.component_id).await {
+                told_users.push(user.clone());
+            }
+        }
+        delivery::fan_out(notifier, db, &told_guilds, &told_users, &notice).await;
+    }
+}
+
+/// Whether a recipient's last notice about a component was an outage
+///
+/// Recovery notices only go to recipients that heard about the outage.
+async fn was_told_outage(
+    db: &DatabaseConnection,
+    guild_id: Option<&str>,
+    user_id: Option<&str>,
+    component_id: &str,
+) -> bool {
+    let mut query = sent_alerts::Entity::find()
+        .filter(sent_alerts::Column::AlertType.eq(ALERT_TYPE_COMPONENT))
+        .filter(
+            sent_alerts::Column::ReferenceId.starts_with(format!("component_{}_", component_id)),
+        );
+    query = match (guild_id, user_id) {
+        (Some(id), _) => query.filter(sent_alerts::Column::GuildId.eq(id)),
+        (None, Some(id)) => query.filter(sent_alerts::Column::UserId.eq(id)),
+        (None, None) => return false,
+    };
+
+    match query
+        .order_by_desc(sent_alerts::Column::NotifiedAt)
+        .one(db)
+        .await
+    {
+        Ok(Some(last)) => !last
+            .reference_id
+            .starts_with(&format!("component_{}_{}_", component_id, OPERATIONAL)),
+        Ok(None) => false,
+        Err(e) => {
+            warn!(component_id, error = %e, "Failed to load previous component notice");
+            false
+        }
+    }
+}
+
+/// Localized label of a component status
+fn status_label(status: &str, locale: &str) -> String {
+    let key = format!("embeds.alerts.component.status.{}", status);
+    let translated = t!(&key, locale = locale);
+    // If translation key doesn't exist, rust-i18n returns the key itself
+    if translated.contains("embeds.alerts.component.status.") {
+        status.to_string()
+    } else {
+        translated.to_string()
+    }
+}
+
+/// Build the notice embed
+fn build_notice_embed(change: &ComponentChange, locale: &str) -> CreateEmbed {
+    let name = component_name(&change.name, locale);
+    let (title, color) = match change.kind {
+        ChangeKind::Degraded => (
+            t!(
+                "embeds.alerts.component.title_degraded",
+                name = name,
+                locale = locale
+            ),
+            match change.new_status.as_str() {
+                "major_outage" => colors::ERROR,
+                "partial_outage" => colors::MAJOR,
+                _ => colors::WARNING,
+            },
+        ),
+        ChangeKind::Recovered => (
+            t!(
+                "embeds.alerts.component.title_recovered",
+                name = name,
+                locale = locale
+            ),
+            colors::SUCCESS,
+        ),
+    };
+
+    let description = format!(
+        "{} {} → {} {}",
+        status_emoji(&change.old_status),
+        status_label(&change.old_status, locale),
+        status_emoji(&change.new_status),
+        status_label(&change.new_status, locale)
+    );
+
+    let mut embed = CreateEmbed::default()
+        .title(title)
+        .url(links::status_page())
+        .description(description)
+        .color(Colour::new(color))
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.alerts.component.footer",
+            locale = locale
+        )));
+    if let Ok(timestamp) = Timestamp::from_unix_timestamp(change.changed_at.timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+    embed
+}
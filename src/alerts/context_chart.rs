@@ -0,0 +1,286 @@
+//! Context charts attached to threshold alerts
+//!
+//! Some incident types have a matching CloudFront metric that gives readers
+//! immediate context (e.g. API error rate for "api" reports). The chart is
+//! rendered once per alert reference block and shared by every recipient of
+//! the fan-out, including re-triggers within the same block.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use plotters::style::RGBColor;
+use sea_orm::DatabaseConnection;
+use tracing::{debug, warn};
+
+use crate::visualization::query::{downsample, load_metric_raw, to_percent};
+use crate::visualization::theme::RED;
+use crate::visualization::{YAxisFormat, generate_single_chart};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Attachment filename referenced by the alert embed
+pub const CHART_FILENAME: &str = "context.png";
+
+/// Time range shown in the context chart
+const CHART_RANGE_HOURS: i64 = 2;
+
+/// Rendered charts kept (one per reference block)
+const MAX_CACHED_CHARTS: usize = 8;
+
+// =============================================================================
+// Incident Type Mapping
+// =============================================================================
+
+/// Metric chart shown for an incident type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContextMetric {
+    /// `metric_logs.metric_name`
+    pub metric: &'static str,
+    pub title: &'static str,
+    pub color: RGBColor,
+    /// Whether stored 0-1 values are shown as percentages
+    pub percent: bool,
+}
+
+const API_ERROR_RATE: ContextMetric = ContextMetric {
+    metric: "api_errors",
+    title: "API Error Rate (2h)",
+    color: RED,
+    percent: true,
+};
+
+/// Context metric for an incident type, if it has one
+pub fn context_metric(incident_type: &str) -> Option<ContextMetric> {
+    match incident_type {
+        "api" | "login" => Some(API_ERROR_RATE),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Render Cache
+// =============================================================================
+
+/// Rendered chart PNG, shared across recipients
+pub type ChartPng = Arc<Vec<u8>>;
+
+/// Rendered context charts keyed by alert reference ID
+///
+/// Failed or empty renders are cached as `None` so a fan-out does not retry
+/// rendering for every recipient.
+#[derive(Debug, Default)]
+pub struct ChartCache {
+    entries: Mutex<VecDeque<(String, Option<ChartPng>)>>,
+}
+
+impl ChartCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached render for a reference ID; outer `None` means not cached yet
+    pub fn get(&self, reference_id: &str) -> Option<Option<ChartPng>> {
+        let entries = self.entries.lock().expect("chart cache mutex poisoned");
+        entries
+            .iter()
+            .find(|(key, _)| key == reference_id)
+            .map(|(_, chart)| chart.clone())
+    }
+
+    /// Store a render result, evicting the oldest entry when full
+    pub fn insert(&self, reference_id: &str, chart: Option<ChartPng>) {
+        let mut entries = self.entries.lock().expect("chart cache mutex poisoned");
+        if entries.iter().any(|(key, _)| key == reference_id) {
+            return;
+        }
+        if entries.len() >= MAX_CACHED_CHARTS {
+            entries.pop_front();
+        }
+        entries.push_back((reference_id.to_string(), chart));
+    }
+}
+
+/// Get the context chart for an alert, rendering it on first use per block
+pub async fn chart_for_alert(
+    cache: &ChartCache,
+    db: &DatabaseConnection,
+    incident_type: &str,
+    reference_id: &str,
+) -> Option<ChartPng> {
+    let metric = context_metric(incident_type)?;
+
+    if let Some(chart) = cache.get(reference_id) {
+        debug!(reference_id, "Reusing cached alert context chart");
+        return chart;
+    }
+
+    let chart = render(db, metric).await.map(Arc::new);
+    cache.insert(reference_id, chart.clone());
+    chart
+}
+
+/// Render a metric chart; `None` if there is no data or rendering fails
+async fn render(db: &DatabaseConnection, metric: ContextMetric) -> Option<Vec<u8>> {
+    let now = Utc::now();
+    let start = now - Duration::hours(CHART_RANGE_HOURS);
+    let data = match load_metric_raw(db, metric.metric, start, now).await {
+        Ok(data) => downsample(data),
+        Err(e) => {
+            warn!(metric = metric.metric, error = %e, "Failed to load alert context metric");
+            return None;
+        }
+    };
+    if data.is_empty() {
+        return None;
+    }
+
+    let (data, y_format) = if metric.percent {
+        (to_percent(data), YAxisFormat::PercentAuto)
+    } else {
+        (data, YAxisFormat::Count)
+    };
+
+    // One render is shared by recipients of every locale; titles are English too
+    match generate_single_chart(metric.title, &data, metric.color, y_format, "en") {
+        Ok(png) => Some(png),
+        Err(e) => {
+            warn!(metric = metric.metric, error = %e, "Failed to render alert context chart");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::shared::incident_types::INCIDENT_TYPE_KEYS;
+    use crate::database::connect_in_memory;
+    use crate::entity::metric_logs;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    fn png(byte: u8) -> ChartPng {
+        Arc::new(vec![byte])
+    }
+
+    async fn add_metric(db: &DatabaseConnection, metric: &str, values: &[f64]) {
+        let now = Utc::now();
+        for (i, value) in values.iter().enumerate() {
+            let at = now - Duration::minutes(10 * (values.len() - i) as i64);
+            metric_logs::ActiveModel {
+                metric_name: Set(metric.to_string()),
+                value: Set(*value),
+                unit: Set("percent".to_string()),
+                interval_sec: Set(60),
+                timestamp: Set(at),
+                created_at: Set(at),
+                ..Default::default()
+            }
+            .insert(db)
+            .await
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_context_metric_for_every_incident_type() {
+        for &incident_type in INCIDENT_TYPE_KEYS {
+            let expected = match incident_type {
+                "api" | "login" => Some(API_ERROR_RATE),
+                _ => None,
+            };
+            assert_eq!(context_metric(incident_type), expected, "{incident_type}");
+        }
+        assert_eq!(context_metric(""), None);
+        assert_eq!(context_metric("API"), None);
+    }
+
+    #[test]
+    fn test_api_error_rate_shown_as_percent() {
+        let metric = context_metric("api").unwrap();
+        assert_eq!(metric.metric, "api_errors");
+        assert!(metric.percent);
+    }
+
+    #[test]
+    fn test_cache_keys_kept_apart() {
+        let cache = ChartCache::new();
+        cache.insert("threshold_api_1", Some(png(1)));
+        cache.insert("threshold_api_2", Some(png(2)));
+        cache.insert("threshold_login_1", None);
+
+        assert_eq!(cache.get("threshold_api_1"), Some(Some(png(1))));
+        assert_eq!(cache.get("threshold_api_2"), Some(Some(png(2))));
+        // A failed render is cached, unlike a key that was never rendered
+        assert_eq!(cache.get("threshold_login_1"), Some(None));
+        assert_eq!(cache.get("threshold_login_2"), None);
+    }
+
+    #[test]
+    fn test_cache_keeps_first_render() {
+        let cache = ChartCache::new();
+        cache.insert("threshold_api_1", Some(png(1)));
+        cache.insert("threshold_api_1", Some(png(2)));
+
+        assert_eq!(cache.get("threshold_api_1"), Some(Some(png(1))));
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest() {
+        let cache = ChartCache::new();
+        for i in 0..=MAX_CACHED_CHARTS {
+            cache.insert(&format!("ref_{i}"), Some(png(i as u8)));
+        }
+
+        assert_eq!(cache.get("ref_0"), None);
+        assert_eq!(cache.get("ref_1"), Some(Some(png(1))));
+        let newest = format!("ref_{MAX_CACHED_CHARTS}");
+        assert_eq!(cache.get(&newest), Some(Some(png(MAX_CACHED_CHARTS as u8))));
+    }
+
+    #[tokio::test]
+    async fn test_chart_for_type_without_metric() {
+        let db = connect_in_memory().await;
+        let cache = ChartCache::new();
+
+        let chart = chart_for_alert(&cache, &db, "instance", "threshold_instance_1").await;
+
+        assert_eq!(chart, None);
+        assert_eq!(cache.get("threshold_instance_1"), None);
+    }
+
+    #[tokio::test]
+    async fn test_chart_without_data_cached_as_none() {
+        let db = connect_in_memory().await;
+        let cache = ChartCache::new();
+
+        let chart = chart_for_alert(&cache, &db, "api", "threshold_api_1").await;
+
+        assert_eq!(chart, None);
+        assert_eq!(cache.get("threshold_api_1"), Some(None));
+    }
+
+    #[tokio::test]
+    async fn test_chart_rendered_once_per_reference() {
+        let db = connect_in_memory().await;
+        let cache = ChartCache::new();
+        add_metric(&db, "api_errors", &[0.01, 0.02, 0.2, 0.35, 0.1]).await;
+
+        let first = chart_for_alert(&cache, &db, "api", "threshold_api_1")
+            .await
+            .unwrap();
+        let login = chart_for_alert(&cache, &db, "login", "threshold_api_1")
+            .await
+            .unwrap();
+        let other_block = chart_for_alert(&cache, &db, "api", "threshold_api_2")
+            .await
+            .unwrap();
+
+        assert!(first.starts_with(b"\x89PNG"));
+        // The same block reuses the render; another block renders its own
+        assert!(Arc::ptr_eq(&first, &login));
+        assert!(!Arc::ptr_eq(&first, &other_block));
+    }
+}
@@ -0,0 +1,1227 @@
+//! Shared delivery path for alerts and notices
+//!
+//! Threshold alerts, incident and component notices, and maintenance
+//! reminders all reach guilds and DM users through this module, so every
+//! kind of alert gets the same gating and bookkeeping:
+//!
+//! - pause and quiet hours (quiet hours record the alert without sending it)
+//! - the hourly delivery cap in `safeguard`
+//! - deduplication in `sent_alerts` (record first, delete if nothing was sent)
+//! - extra alert channels routed to the alert's type, and the role mention
+//! - guild webhooks, which get the alert whether or not a channel took it
+//! - permanent failure tracking, owner DM fallback, and disabling users
+//!   whose DMs are unreachable
+//!
+//! Recipients are sent to [`FAN_OUT_CONCURRENCY`] at a time, so one slow
+//! channel or DM doesn't hold up the rest.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, stream};
+use rust_i18n::t;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use serenity::all::{
+    ChannelId, Colour, Context, CreateAllowedMentions, CreateAttachment, CreateEmbed,
+    CreateEmbedFooter, CreateMessage, GuildId, Http, HttpError, Mentionable, RoleId, UserId,
+};
+use tokio_util::task::TaskTracker;
+use tracing::{debug, error, info, warn};
+
+use crate::commands::shared::colors;
+use crate::entity::{guild_configs, sent_alerts, user_configs};
+use crate::i18n::{resolve_guild_locale_by_id, resolve_user_locale_by_id};
+use crate::repository::{
+    AlertChannelRepository, GuildConfigRepository, UserConfigRepository, WebhookRepository,
+    channels, config, filters, registration, webhooks,
+};
+use crate::state::AppStateKey;
+
+use super::safeguard::{self, AlertSafeguard, DeliveryLimiter};
+use super::webhook::{self, WebhookPayload};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// `sent_alerts.alert_type` for owner DM fallback notices
+pub(crate) const ALERT_TYPE_FALLBACK_DM: &str = "fallback_dm";
+
+/// Discord error codes that mean the alert channel can't be used until reconfigured
+/// (Unknown Channel, Missing Access, Missing Permissions)
+const PERMANENT_CHANNEL_ERROR_CODES: [isize; 3] = [10003, 50001, MISSING_PERMISSIONS_CODE];
+
+/// Discord error code for Missing Permissions
+const MISSING_PERMISSIONS_CODE: isize = 50013;
+
+/// Discord error code for Cannot Send Messages to This User (DMs closed or bot blocked)
+const CANNOT_MESSAGE_USER_CODE: isize = 50007;
+
+/// Alerts in a row that may fail permanently before the guild is disabled
+const MAX_CONSECUTIVE_SEND_FAILURES: i32 = 5;
+
+/// Recipients delivered to at the same time during a fan-out
+///
+/// The hourly cap is checked before each send and counted after it, so up to
+/// this many deliveries can overshoot it.
+pub const FAN_OUT_CONCURRENCY: usize = 8;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Handles a fan-out needs outside of an event handler
+#[derive(Clone)]
+pub struct Notifier {
+    /// Discord HTTP client
+    pub http: Arc<Http>,
+    /// Shared hourly delivery counter (`AppState::alert_limiter`)
+    pub limiter: Arc<DeliveryLimiter>,
+    /// Tracker that background fan-outs and webhook posts are spawned on
+    /// (`AppState::background_tasks`), so shutdown waits for them
+    pub tasks: TaskTracker,
+}
+
+impl Notifier {
+    /// Build from the handles stored in AppState
+    pub async fn from_context(ctx: &Context) -> Option<Self> {
+        let data = ctx.data.read().await;
+        let state = data.get::<AppStateKey>()?;
+        let state = state.read().await;
+        Some(Self {
+            http: ctx.http.clone(),
+            limiter: state.alert_limiter.clone(),
+            tasks: state.background_tasks.clone(),
+        })
+    }
+
+    /// Delivery safeguard for one fan-out, with the configured cap
+    pub async fn safeguard(&self, db: &DatabaseConnection) -> AlertSafeguard {
+        AlertSafeguard::new(self.limiter.clone(), safeguard::load_max_per_hour(db).await)
+    }
+}
+
+/// Content of one alert, shared by every recipient of a fan-out
+pub(crate) trait AlertNotice: Sync {
+    /// `sent_alerts.alert_type`
+    fn alert_type(&self) -> &'static str;
+
+    /// `sent_alerts.reference_id`, the same for every recipient
+    fn reference_id(&self) -> &str;
+
+    /// Type that extra alert channels and webhooks are matched against
+    ///
+    /// Threshold alerts use their incident type. Notices use their alert
+    /// type, which no extra channel takes, so they go to the main channel and
+    /// to webhooks that take every type.
+    fn route(&self) -> &str;
+
+    /// Embed in `locale`, showing the attachment if `with_attachment`
+    fn embed(&self, locale: &str, with_attachment: bool) -> CreateEmbed;
+
+    /// File sent with the embed, if any
+    fn attachment(&self) -> Option<CreateAttachment> {
+        None
+    }
+
+    /// Whether guild messages ping the guild's alert role
+    fn mentions_role(&self) -> bool {
+        false
+    }
+
+    /// JSON body posted to the guild's webhooks
+    fn webhook_payload(&self, guild_id: &str) -> WebhookPayload;
+}
+
+/// Outcome of one recipient's delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Delivery {
+    Sent,
+    /// Already in `sent_alerts` for this reference ID
+    Duplicate,
+    /// Not due (paused, silenced, below threshold, quiet hours, capped, ...)
+    Skipped,
+    Failed,
+}
+
+/// Delivery outcomes of one fan-out, logged when it finishes
+#[derive(Debug, Default)]
+pub(crate) struct FanOutTally {
+    pub sent: u32,
+    pub duplicate: u32,
+    pub skipped: u32,
+    pub failed: u32,
+}
+
+impl FanOutTally {
+    pub fn add(&mut self, delivery: Delivery) {
+        match delivery {
+            Delivery::Sent => self.sent += 1,
+            Delivery::Duplicate => self.duplicate += 1,
+            Delivery::Skipped => self.skipped += 1,
+            Delivery::Failed => self.failed += 1,
+        }
+    }
+}
+
+// =============================================================================
+// Fan-out
+// =============================================================================
+
+/// Deliver `notice` to `guilds`, then to `users`
+///
+/// Both lists should come from [`get_registered_guilds`] and
+/// [`get_registered_users`] for the notice's alert type.
+pub(crate) async fn fan_out(
+    notifier: &Notifier,
+    db: &DatabaseConnection,
+    guilds: &[guild_configs::Model],
+    users: &[user_configs::Model],
+    notice: &impl AlertNotice,
+) -> FanOutTally {
+    let safeguard = notifier.safeguard(db).await;
+    let mut tally = FanOutTally::default();
+
+    // Futures are built up front: a closure in the stream type trips the
+    // `Send` check of the spawned task that runs the fan-out
+    let sends: Vec<_> = guilds
+        .iter()
+        .map(|guild| send_to_guild(notifier, db, guild, notice, &safeguard))
+        .collect();
+    let mut deliveries = stream::iter(sends).buffer_unordered(FAN_OUT_CONCURRENCY);
+    while let Some(delivery) = deliveries.next().await {
+        tally.add(delivery);
+    }
+
+    let sends: Vec<_> = users
+        .iter()
+        .map(|user| send_to_user(notifier, db, user, notice, &safeguard))
+        .collect();
+    let mut deliveries = stream::iter(sends).buffer_unordered(FAN_OUT_CONCURRENCY);
+    while let Some(delivery) = deliveries.next().await {
+        tally.add(delivery);
+    }
+
+    info!(
+        alert_type = notice.alert_type(),
+        reference_id = notice.reference_id(),
+        sent = tally.sent,
+        duplicate = tally.duplicate,
+        skipped = tally.skipped,
+        failed = tally.failed,
+        "Alert fan-out finished"
+    );
+    tally
+}
+
+// =============================================================================
+// Database Queries
+// =============================================================================
+
+/// Guilds receiving alerts (see `registration`) that have not turned `alert_type` off
+pub(crate) async fn get_registered_guilds(
+    db: &DatabaseConnection,
+    alert_type: &str,
+) -> Vec<guild_configs::Model> {
+    guild_configs::Entity::find()
+        .filter(registration::guild_receives_alerts(Utc::now()))
+        .filter(
+            guild_configs::Column::GuildId.not_in_subquery(filters::disabled_guilds(alert_type)),
+        )
+        .all(db)
+        .await
+        .unwrap_or_else(|e| {
+            error!(error = %e, "Failed to fetch registered guilds for alerts");
+            vec![]
+        })
+}
+
+/// Users receiving alerts (see `registration`) that have not turned `alert_type` off
+pub(crate) async fn get_registered_users(
+    db: &DatabaseConnection,
+    alert_type: &str,
+) -> Vec<user_configs::Model> {
+    user_configs::Entity::find()
+        .filter(registration::user_receives_alerts())
+        .filter(user_configs::Column::UserId.not_in_subquery(filters::disabled_users(alert_type)))
+        .all(db)
+        .await
+        .unwrap_or_else(|e| {
+            error!(error = %e, "Failed to fetch registered users for alerts");
+            vec![]
+        })
+}
+
+/// Result of attempting to record a sent alert
+pub(crate) enum RecordAlertResult {
+    /// Alert was recorded, contains the record ID for potential rollback
+    Recorded(i64),
+    /// Alert was already sent (duplicate)
+    AlreadySent,
+    /// Database error occurred
+    Error,
+}
+
+/// Try to record a sent alert. Returns the record ID if successful, or indicates duplicate/error.
+/// Uses INSERT with unique constraint to prevent race conditions (TOCTOU).
+pub(crate) async fn try_record_sent_alert(
+    db: &DatabaseConnection,
+    guild_id: Option<String>,
+    user_id: Option<String>,
+    alert_type: &str,
+    reference_id: &str,
+) -> RecordAlertResult {
+    let now = Utc::now();
+    let alert = sent_alerts::ActiveModel {
+        guild_id: Set(guild_id),
+        user_id: Set(user_id),
+        alert_type: Set(alert_type.to_string()),
+        reference_id: Set(reference_id.to_string()),
+        notified_at: Set(now),
+        created_at: Set(now),
+        ..Default::default()
+    };
+
+    match alert.insert(db).await {
+        Ok(record) => RecordAlertResult::Recorded(record.id), // Successfully inserted
+        Err(e) => {
+            // Check if it's a unique constraint violation (already sent)
+            let err_str = e.to_string().to_lowercase();
+            if err_str.contains("unique") || err_str.contains("duplicate") {
+                RecordAlertResult::AlreadySent // Dedup working correctly
+            } else {
+                error!(error = %e, "Failed to record sent alert");
+                RecordAlertResult::Error // Don't send alert if we can't record it
+            }
+        }
+    }
+}
+
+/// Delete a sent alert record (used for rollback on send failure)
+pub(crate) async fn delete_sent_alert(db: &DatabaseConnection, record_id: i64) {
+    if let Err(e) = sent_alerts::Entity::delete_by_id(record_id).exec(db).await {
+        error!(record_id = record_id, error = %e, "Failed to delete sent_alert record for retry");
+    }
+}
+
+// =============================================================================
+// Guild Delivery
+// =============================================================================
+
+/// Deliver `notice` to a guild's alert channels and webhooks
+///
+/// Checks that are specific to one kind of alert (thresholds, silences) are
+/// left to the caller.
+pub(crate) async fn send_to_guild(
+    notifier: &Notifier,
+    db: &DatabaseConnection,
+    guild: &guild_configs::Model,
+    notice: &impl AlertNotice,
+    safeguard: &AlertSafeguard,
+) -> Delivery {
+    let alert_type = notice.alert_type();
+    let reference_id = notice.reference_id();
+
+    // Skip delivery while alerts are paused (resumes once paused_until passes)
+    if config::is_paused(guild, Utc::now()) {
+        debug!(guild_id = %guild.guild_id, alert_type, "Guild alerts paused, skipping");
+        return Delivery::Skipped;
+    }
+
+    // Inside the guild's quiet hours: record without sending, so the alert
+    // is not delivered late once the window ends
+    if config::is_quiet(guild, Utc::now()) {
+        return match try_record_sent_alert(
+            db,
+            Some(guild.guild_id.clone()),
+            None,
+            alert_type,
+            reference_id,
+        )
+        .await
+        {
+            RecordAlertResult::Recorded(_) => {
+                info!(
+                    guild_id = %guild.guild_id,
+                    reference_id,
+                    "Guild in quiet hours, alert recorded without sending"
+                );
+                Delivery::Skipped
+            }
+            RecordAlertResult::AlreadySent => Delivery::Duplicate,
+            RecordAlertResult::Error => Delivery::Failed,
+        };
+    }
+
+    // Skip delivery while the hourly cap is exceeded
+    if !safeguard.try_acquire(&notifier.http).await {
+        return Delivery::Skipped;
+    }
+
+    // Try to record first (atomic deduplication via unique constraint)
+    // If this fails due to duplicate, we skip sending
+    let record_id = match try_record_sent_alert(
+        db,
+        Some(guild.guild_id.clone()),
+        None,
+        alert_type,
+        reference_id,
+    )
+    .await
+    {
+        RecordAlertResult::Recorded(id) => id,
+        RecordAlertResult::AlreadySent => return Delivery::Duplicate,
+        RecordAlertResult::Error => return Delivery::Failed, // Can't record - don't send
+    };
+
+    // Resolve locale for this guild
+    let locale = resolve_guild_locale_by_id(db, &guild.guild_id).await;
+
+    // Extra channels routed to this type replace the main channel
+    let extra_channels = AlertChannelRepository::new(db)
+        .list_enabled(&guild.guild_id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(guild_id = %guild.guild_id, error = %e, "Failed to load alert channels, using main channel");
+            Vec::new()
+        });
+    let targets =
+        channels::route_channels(guild.channel_id.as_deref(), &extra_channels, notice.route());
+
+    let mention = if notice.mentions_role() {
+        config::mention_role(guild)
+    } else {
+        None
+    };
+    let mut delivered = false;
+    let mut last_error = None;
+    for target in targets {
+        let Ok(channel_id) = target.parse::<u64>() else {
+            warn!(guild_id = %guild.guild_id, channel_id = target, "Invalid channel ID");
+            continue;
+        };
+        match send_to_channel(
+            &notifier.http,
+            guild,
+            ChannelId::new(channel_id),
+            notice,
+            &locale,
+            mention,
+        )
+        .await
+        {
+            Ok(()) => delivered = true,
+            Err(e) => {
+                error!(
+                    guild_id = %guild.guild_id,
+                    channel_id,
+                    error = %e,
+                    "Failed to send alert to guild channel"
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    // Webhooks are an alternative to the channels, so they get the alert
+    // whether or not a channel took it
+    let webhooked = send_webhooks(notifier, db, guild, notice).await;
+
+    if delivered {
+        if guild.consecutive_send_failures > 0 {
+            reset_delivery_failures(db, guild).await;
+        }
+    } else if last_error.as_ref().is_some_and(is_permanent_delivery_error) {
+        // Channel is gone or unwritable - count it, and fall back to the
+        // owner's DMs if opted in. Transient errors (outages, rate limits)
+        // don't count.
+        record_delivery_failure(db, guild).await;
+        if guild.fallback_dm {
+            send_owner_fallback(notifier, db, guild, notice, &locale, safeguard).await;
+        }
+    }
+
+    if delivered || webhooked {
+        safeguard.record();
+        info!(
+            guild_id = %guild.guild_id,
+            alert_type,
+            reference_id,
+            channel = delivered,
+            webhook = webhooked,
+            "Sent alert to guild"
+        );
+        return Delivery::Sent;
+    }
+
+    // Nothing delivered: delete the record so the next trigger can retry
+    delete_sent_alert(db, record_id).await;
+    if last_error.is_none() {
+        debug!(guild_id = %guild.guild_id, "Guild has no alert channel or webhook for the alert");
+        return Delivery::Skipped;
+    }
+    warn!(guild_id = %guild.guild_id, "No alert channel accepted the alert, will retry on next trigger");
+    Delivery::Failed
+}
+
+/// Count a permanent delivery failure, disabling the guild once
+/// `MAX_CONSECUTIVE_SEND_FAILURES` alerts in a row have failed
+async fn record_delivery_failure(db: &DatabaseConnection, guild: &guild_configs::Model) {
+    let Ok(guild_id) = guild.guild_id.parse::<u64>() else {
+        return;
+    };
+    let guild_id = GuildId::new(guild_id);
+    let repo = GuildConfigRepository::new(Arc::new(db.clone()));
+
+    let failures = match repo.record_send_failure(guild_id).await {
+        Ok(failures) => failures,
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to record alert delivery failure");
+            return;
+        }
+    };
+    if failures < MAX_CONSECUTIVE_SEND_FAILURES {
+        return;
+    }
+
+    match repo.mark_channel_invalid(guild_id).await {
+        Ok(_) => warn!(
+            guild_id = %guild_id,
+            failures,
+            "Alert delivery failed repeatedly, disabled guild registration"
+        ),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to disable guild after delivery failures")
+        }
+    }
+}
+
+/// Clear the delivery failure count after a successful delivery
+async fn reset_delivery_failures(db: &DatabaseConnection, guild: &guild_configs::Model) {
+    let Ok(guild_id) = guild.guild_id.parse::<u64>() else {
+        return;
+    };
+    if let Err(e) = GuildConfigRepository::new(Arc::new(db.clone()))
+        .reset_send_failures(GuildId::new(guild_id))
+        .await
+    {
+        warn!(guild_id = %guild.guild_id, error = %e, "Failed to reset alert delivery failures");
+    }
+}
+
+/// Send the notice to one channel, retrying without the attachment if the
+/// channel does not allow attachments
+async fn send_to_channel(
+    http: &Http,
+    guild: &guild_configs::Model,
+    channel: ChannelId,
+    notice: &impl AlertNotice,
+    locale: &str,
+    mention: Option<RoleId>,
+) -> Result<(), serenity::Error> {
+    let attachment = notice.attachment();
+    let has_attachment = attachment.is_some();
+    match channel
+        .send_message(
+            http,
+            with_role_mention(build_message(notice, locale, attachment), mention),
+        )
+        .await
+    {
+        // Channel lacks Attach Files - retry without the attachment
+        Err(e) if has_attachment && is_missing_permissions(&e) => {
+            warn!(
+                guild_id = %guild.guild_id,
+                "Missing permission to attach alert file, sending embed only"
+            );
+            channel
+                .send_message(
+                    http,
+                    with_role_mention(build_message(notice, locale, None), mention),
+                )
+                .await
+                .map(|_| ())
+        }
+        result => result.map(|_| ()),
+    }
+}
+
+/// DM the guild owner when the alert channel is unusable
+///
+/// Sends at most one notice per guild per UTC day, deduplicated via `sent_alerts`.
+/// If the owner has DMs closed, the failure is logged and the alert is dropped.
+async fn send_owner_fallback(
+    notifier: &Notifier,
+    db: &DatabaseConnection,
+    guild: &guild_configs::Model,
+    notice: &impl AlertNotice,
+    locale: &str,
+    safeguard: &AlertSafeguard,
+) {
+    let Ok(guild_id) = guild.guild_id.parse::<u64>() else {
+        return;
+    };
+    let http: &Http = &notifier.http;
+
+    let Some(record_id) = claim_owner_fallback(db, &guild.guild_id, Utc::now()).await else {
+        return; // Owner already notified today
+    };
+
+    // Resolve guild owner and name
+    let partial_guild = match GuildId::new(guild_id).to_partial_guild(http).await {
+        Ok(g) => g,
+        Err(e) => {
+            warn!(guild_id = %guild.guild_id, error = %e, "Failed to fetch guild for owner DM fallback");
+            delete_sent_alert(db, record_id).await;
+            return;
+        }
+    };
+
+    let intro = CreateEmbed::default()
+        .title(t!("embeds.alerts.fallback_dm.title", locale = locale))
+        .description(t!(
+            "embeds.alerts.fallback_dm.description",
+            guild = partial_guild.name,
+            locale = locale
+        ))
+        .color(Colour::new(colors::WARNING))
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.alerts.fallback_dm.footer",
+            locale = locale
+        )));
+    // DMs always allow attachments, so include the file when there is one
+    let attachment = notice.attachment();
+    let alert_embed = notice.embed(locale, attachment.is_some());
+    let mut message = CreateMessage::new().embeds(vec![intro, alert_embed]);
+    if let Some(file) = attachment {
+        message = message.add_file(file);
+    }
+
+    let result = match partial_guild.owner_id.create_dm_channel(http).await {
+        Ok(channel) => channel.send_message(http, message).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+
+    // Keep the record on failure too: closed DMs won't open by retrying today
+    match result {
+        Ok(()) => {
+            safeguard.record();
+            info!(
+                guild_id = %guild.guild_id,
+                owner_id = %partial_guild.owner_id,
+                alert_type = notice.alert_type(),
+                "Sent alert to guild owner DM (fallback)"
+            );
+        }
+        Err(e) => {
+            warn!(
+                guild_id = %guild.guild_id,
+                owner_id = %partial_guild.owner_id,
+                error = %e,
+                "Failed to DM guild owner with fallback alert, giving up"
+            );
+        }
+    }
+}
+
+/// Record today's owner DM fallback for a guild
+///
+/// Returns the `sent_alerts` row id, or `None` when the owner was already
+/// notified on `now`'s UTC day (or the record couldn't be written).
+async fn claim_owner_fallback(
+    db: &DatabaseConnection,
+    guild_id: &str,
+    now: DateTime<Utc>,
+) -> Option<i64> {
+    let reference_id = format!("fallback_dm_{}", now.format("%Y-%m-%d"));
+    match try_record_sent_alert(
+        db,
+        Some(guild_id.to_string()),
+        None,
+        ALERT_TYPE_FALLBACK_DM,
+        &reference_id,
+    )
+    .await
+    {
+        RecordAlertResult::Recorded(id) => Some(id),
+        RecordAlertResult::AlreadySent | RecordAlertResult::Error => None,
+    }
+}
+
+/// Post the notice to the guild's webhooks that take its type
+///
+/// Returns whether any webhook takes it; the posts themselves run in the
+/// background.
+async fn send_webhooks(
+    notifier: &Notifier,
+    db: &DatabaseConnection,
+    guild: &guild_configs::Model,
+    notice: &impl AlertNotice,
+) -> bool {
+    let webhooks: Vec<_> = match WebhookRepository::new(Arc::new(db.clone()))
+        .list_enabled(&guild.guild_id)
+        .await
+    {
+        Ok(rows) => rows
+            .into_iter()
+            .filter(|row| webhooks::receives(row, notice.route()))
+            .collect(),
+        Err(e) => {
+            warn!(guild_id = %guild.guild_id, error = %e, "Failed to load alert webhooks");
+            return false;
+        }
+    };
+    if webhooks.is_empty() {
+        return false;
+    }
+    webhook::spawn_deliveries(
+        &notifier.tasks,
+        webhooks,
+        notice.webhook_payload(&guild.guild_id),
+    );
+    true
+}
+
+// =============================================================================
+// User Delivery
+// =============================================================================
+
+/// Deliver `notice` to a user's DMs
+pub(crate) async fn send_to_user(
+    notifier: &Notifier,
+    db: &DatabaseConnection,
+    user: &user_configs::Model,
+    notice: &impl AlertNotice,
+    safeguard: &AlertSafeguard,
+) -> Delivery {
+    // Parse user ID
+    let Ok(user_id) = user.user_id.parse::<u64>() else {
+        warn!(user_id = %user.user_id, "Invalid user ID");
+        return Delivery::Skipped;
+    };
+    let http: &Http = &notifier.http;
+
+    // Skip delivery while the hourly cap is exceeded
+    if !safeguard.try_acquire(http).await {
+        return Delivery::Skipped;
+    }
+
+    // Try to record first (atomic deduplication via unique constraint)
+    // If this fails due to duplicate, we skip sending
+    let record_id = match try_record_sent_alert(
+        db,
+        None,
+        Some(user.user_id.clone()),
+        notice.alert_type(),
+        notice.reference_id(),
+    )
+    .await
+    {
+        RecordAlertResult::Recorded(id) => id,
+        RecordAlertResult::AlreadySent => return Delivery::Duplicate,
+        RecordAlertResult::Error => return Delivery::Failed, // Can't record - don't send
+    };
+
+    let dm_channel = match UserId::new(user_id).create_dm_channel(http).await {
+        Ok(c) => c,
+        Err(e) if is_dm_unreachable(&e) => {
+            delete_sent_alert(db, record_id).await;
+            disable_unreachable_user(db, user, &e).await;
+            return Delivery::Failed;
+        }
+        Err(e) => {
+            error!(user_id = %user.user_id, error = %e, "Failed to create DM channel, will retry on next trigger");
+            delete_sent_alert(db, record_id).await;
+            return Delivery::Failed;
+        }
+    };
+
+    // Resolve locale for this user
+    let locale = resolve_user_locale_by_id(db, &user.user_id).await;
+
+    // DMs always allow attachments
+    let message = build_message(notice, &locale, notice.attachment());
+
+    match dm_channel.send_message(http, message).await {
+        Ok(_) => {
+            safeguard.record();
+            info!(
+                user_id = %user.user_id,
+                alert_type = notice.alert_type(),
+                reference_id = notice.reference_id(),
+                "Sent alert to user DM"
+            );
+            Delivery::Sent
+        }
+        // DMs closed or bot blocked - retrying can't succeed
+        Err(e) if is_dm_unreachable(&e) => {
+            delete_sent_alert(db, record_id).await;
+            disable_unreachable_user(db, user, &e).await;
+            Delivery::Failed
+        }
+        Err(e) => {
+            error!(
+                user_id = %user.user_id,
+                error = %e,
+                "Failed to send alert to user DM, will retry on next trigger"
+            );
+            // Delete the record so the next trigger can retry
+            delete_sent_alert(db, record_id).await;
+            Delivery::Failed
+        }
+    }
+}
+
+/// Disable a user whose DMs can't be delivered, so later alerts skip them
+///
+/// `/config show` tells the user why; `/config setup` re-enables them.
+async fn disable_unreachable_user(
+    db: &DatabaseConnection,
+    user: &user_configs::Model,
+    error: &serenity::Error,
+) {
+    let Ok(user_id) = user.user_id.parse::<u64>() else {
+        return;
+    };
+    match UserConfigRepository::new(Arc::new(db.clone()))
+        .mark_dm_unreachable(UserId::new(user_id))
+        .await
+    {
+        Ok(_) => warn!(
+            user_id = %user.user_id,
+            error = %error,
+            "User DMs unreachable, disabled user registration"
+        ),
+        Err(e) => error!(user_id = %user.user_id, error = %e, "Failed to disable unreachable user"),
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Whether a send error means the channel is unusable until reconfigured
+///
+/// Deleted channels and missing access/permissions qualify; rate limits and
+/// server errors are transient and left to the normal retry path.
+pub(crate) fn is_permanent_delivery_error(error: &serenity::Error) -> bool {
+    discord_error(error).is_some_and(|(status, code)| is_permanent_failure(status, code))
+}
+
+/// Whether a Discord response's HTTP status and JSON error code mean the
+/// channel is unusable until reconfigured
+fn is_permanent_failure(status: u16, code: isize) -> bool {
+    PERMANENT_CHANNEL_ERROR_CODES.contains(&code) || matches!(status, 403 | 404)
+}
+
+/// Whether a DM error means the user can't receive DMs from the bot (50007)
+fn is_dm_unreachable(error: &serenity::Error) -> bool {
+    discord_error(error).is_some_and(|(_, code)| code == CANNOT_MESSAGE_USER_CODE)
+}
+
+/// Whether a send error is Discord's Missing Permissions (50013)
+fn is_missing_permissions(error: &serenity::Error) -> bool {
+    discord_error(error).is_some_and(|(_, code)| code == MISSING_PERMISSIONS_CODE)
+}
+
+/// HTTP status and JSON error code of a request Discord rejected
+///
+/// `None` for errors that never got a Discord response (network, decoding).
+fn discord_error(error: &serenity::Error) -> Option<(u16, isize)> {
+    match error {
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response)) => {
+            Some((response.status_code.as_u16(), response.error.code))
+        }
+        _ => None,
+    }
+}
+
+/// Build the message, with `attachment` if given
+fn build_message(
+    notice: &impl AlertNotice,
+    locale: &str,
+    attachment: Option<CreateAttachment>,
+) -> CreateMessage {
+    let message = CreateMessage::new().embed(notice.embed(locale, attachment.is_some()));
+    match attachment {
+        Some(file) => message.add_file(file),
+        None => message,
+    }
+}
+
+/// Ping the guild's alert role in the message content
+///
+/// Allowed mentions are limited to that one role, so message content can
+/// never ping @everyone, @here, or users.
+fn with_role_mention(message: CreateMessage, role: Option<RoleId>) -> CreateMessage {
+    let Some(role) = role else {
+        return message;
+    };
+    message
+        .content(role.mention().to_string())
+        .allowed_mentions(CreateAllowedMentions::new().roles(vec![role]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::webhook::WebhookNotice;
+    use crate::database::connect_in_memory;
+    use chrono::TimeZone;
+    use sea_orm::{IntoActiveModel, PaginatorTrait};
+
+    const GUILD_ID: u64 = 1;
+
+    struct TestNotice;
+
+    impl AlertNotice for TestNotice {
+        fn alert_type(&self) -> &'static str {
+            "threshold"
+        }
+
+        fn reference_id(&self) -> &str {
+            "ref"
+        }
+
+        fn route(&self) -> &str {
+            "login"
+        }
+
+        fn embed(&self, _locale: &str, _with_attachment: bool) -> CreateEmbed {
+            CreateEmbed::default().title("Test alert")
+        }
+
+        fn webhook_payload(&self, guild_id: &str) -> WebhookPayload {
+            WebhookPayload::Notice(WebhookNotice {
+                event: webhook::EVENT_THRESHOLD_ALERT,
+                guild_id: guild_id.to_string(),
+                reference_id: self.reference_id().to_string(),
+                title: "Test alert".into(),
+                status: "investigating".into(),
+                url: "https://status.vrchat.com".into(),
+                sent_at: Utc::now(),
+            })
+        }
+    }
+
+    fn notifier() -> Notifier {
+        Notifier {
+            http: Arc::new(Http::new("")),
+            limiter: Arc::new(DeliveryLimiter::new()),
+            tasks: TaskTracker::new(),
+        }
+    }
+
+    /// Registered guild config, stored so failure bookkeeping finds it
+    async fn guild(db: &DatabaseConnection, channel_id: Option<&str>) -> guild_configs::Model {
+        let now = Utc::now();
+        guild_configs::Model {
+            guild_id: GUILD_ID.to_string(),
+            channel_id: channel_id.map(Into::into),
+            enabled: true,
+            language: None,
+            fallback_dm: false,
+            paused_until: None,
+            muted_incident_types: None,
+            alert_threshold: None,
+            report_role_id: None,
+            mention_role_id: None,
+            digest_enabled: false,
+            alert_quiet_start: None,
+            alert_quiet_end: None,
+            channel_invalid: false,
+            consecutive_send_failures: 0,
+            created_at: now,
+            updated_at: now,
+        }
+        .into_active_model()
+        .insert(db)
+        .await
+        .unwrap()
+    }
+
+    async fn add_webhook(db: &DatabaseConnection, alert_types: &str) {
+        WebhookRepository::new(Arc::new(db.clone()))
+            .add(
+                GuildId::new(GUILD_ID),
+                "https://hooks.example.invalid/vrcpulse",
+                "secret",
+                alert_types,
+            )
+            .await
+            .unwrap();
+    }
+
+    async fn send(db: &DatabaseConnection, guild: &guild_configs::Model) -> (Delivery, Notifier) {
+        let notifier = notifier();
+        let safeguard = AlertSafeguard::new(notifier.limiter.clone(), 100);
+        let delivery = send_to_guild(&notifier, db, guild, &TestNotice, &safeguard).await;
+        (delivery, notifier)
+    }
+
+    async fn sent_alert_count(db: &DatabaseConnection) -> u64 {
+        sent_alerts::Entity::find().count(db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_webhook_only_guild_receives_alert() {
+        let db = connect_in_memory().await;
+        let guild = guild(&db, None).await;
+        add_webhook(&db, "").await;
+
+        let (delivery, notifier) = send(&db, &guild).await;
+
+        assert_eq!(delivery, Delivery::Sent);
+        assert_eq!(sent_alert_count(&db).await, 1);
+        let snapshot = notifier.limiter.snapshot(Utc::now(), 100);
+        assert_eq!(snapshot.delivered_last_hour, 1);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_routed_to_alert_type() {
+        let db = connect_in_memory().await;
+        let guild = guild(&db, None).await;
+        add_webhook(&db, "login,api").await;
+
+        let (delivery, _) = send(&db, &guild).await;
+
+        assert_eq!(delivery, Delivery::Sent);
+    }
+
+    #[tokio::test]
+    async fn test_guild_without_channel_or_matching_webhook_skipped() {
+        let db = connect_in_memory().await;
+        let guild = guild(&db, None).await;
+        add_webhook(&db, "api").await;
+
+        let (delivery, notifier) = send(&db, &guild).await;
+
+        assert_eq!(delivery, Delivery::Skipped);
+        // The record is rolled back so a later channel or webhook can take it
+        assert_eq!(sent_alert_count(&db).await, 0);
+        let snapshot = notifier.limiter.snapshot(Utc::now(), 100);
+        assert_eq!(snapshot.delivered_last_hour, 0);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_only_guild_deduplicated() {
+        let db = connect_in_memory().await;
+        let guild = guild(&db, None).await;
+        add_webhook(&db, "").await;
+
+        let (first, _) = send(&db, &guild).await;
+        let (second, _) = send(&db, &guild).await;
+
+        assert_eq!(first, Delivery::Sent);
+        assert_eq!(second, Delivery::Duplicate);
+    }
+
+    #[tokio::test]
+    async fn test_record_sent_alert_dedupes_guild_rows() {
+        let db = connect_in_memory().await;
+        let first = try_record_sent_alert(&db, Some("1".into()), None, "threshold", "ref").await;
+        let second = try_record_sent_alert(&db, Some("1".into()), None, "threshold", "ref").await;
+
+        assert!(matches!(first, RecordAlertResult::Recorded(_)));
+        assert!(matches!(second, RecordAlertResult::AlreadySent));
+    }
+
+    #[tokio::test]
+    async fn test_record_sent_alert_dedupes_user_rows() {
+        let db = connect_in_memory().await;
+        let first = try_record_sent_alert(&db, None, Some("2".into()), "threshold", "ref").await;
+        let second = try_record_sent_alert(&db, None, Some("2".into()), "threshold", "ref").await;
+
+        assert!(matches!(first, RecordAlertResult::Recorded(_)));
+        assert!(matches!(second, RecordAlertResult::AlreadySent));
+    }
+
+    #[tokio::test]
+    async fn test_record_sent_alert_keeps_recipients_apart() {
+        let db = connect_in_memory().await;
+        let guild = try_record_sent_alert(&db, Some("1".into()), None, "threshold", "ref").await;
+        let user = try_record_sent_alert(&db, None, Some("1".into()), "threshold", "ref").await;
+        let other = try_record_sent_alert(&db, Some("1".into()), None, "threshold", "other").await;
+
+        assert!(matches!(guild, RecordAlertResult::Recorded(_)));
+        assert!(matches!(user, RecordAlertResult::Recorded(_)));
+        assert!(matches!(other, RecordAlertResult::Recorded(_)));
+    }
+
+    #[tokio::test]
+    async fn test_deleted_record_can_be_recorded_again() {
+        let db = connect_in_memory().await;
+        let RecordAlertResult::Recorded(id) =
+            try_record_sent_alert(&db, Some("1".into()), None, "threshold", "ref").await
+        else {
+            panic!("first record should succeed");
+        };
+        delete_sent_alert(&db, id).await;
+        let retry = try_record_sent_alert(&db, Some("1".into()), None, "threshold", "ref").await;
+
+        assert!(matches!(retry, RecordAlertResult::Recorded(_)));
+    }
+
+    #[tokio::test]
+    async fn test_owner_fallback_once_per_day() {
+        let db = connect_in_memory().await;
+        let morning = Utc.with_ymd_and_hms(2026, 3, 1, 0, 5, 0).unwrap();
+        let evening = Utc.with_ymd_and_hms(2026, 3, 1, 23, 55, 0).unwrap();
+        let next_day = evening + chrono::Duration::minutes(10);
+
+        let first = claim_owner_fallback(&db, "1", morning).await;
+        let same_day = claim_owner_fallback(&db, "1", evening).await;
+        let other_guild = claim_owner_fallback(&db, "2", evening).await;
+        let tomorrow = claim_owner_fallback(&db, "1", next_day).await;
+
+        assert!(first.is_some());
+        assert_eq!(same_day, None);
+        assert!(other_guild.is_some());
+        assert!(tomorrow.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_owner_fallback_apart_from_alert_dedup() {
+        let db = connect_in_memory().await;
+        let now = Utc::now();
+        try_record_sent_alert(&db, Some("1".into()), None, "threshold", "ref").await;
+
+        let claimed = claim_owner_fallback(&db, "1", now).await;
+
+        assert!(claimed.is_some());
+        let row = sent_alerts::Entity::find_by_id(claimed.unwrap())
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.alert_type, ALERT_TYPE_FALLBACK_DM);
+        assert_eq!(
+            row.reference_id,
+            format!("fallback_dm_{}", now.format("%Y-%m-%d"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_owner_fallback_retried_after_failed_lookup() {
+        let db = connect_in_memory().await;
+        let now = Utc::now();
+
+        // A failed guild lookup rolls the claim back
+        let id = claim_owner_fallback(&db, "1", now).await.unwrap();
+        delete_sent_alert(&db, id).await;
+
+        assert!(claim_owner_fallback(&db, "1", now).await.is_some());
+    }
+
+    #[test]
+    fn test_permanent_failure_classification() {
+        let cases = [
+            // Unknown Channel, Missing Access, Missing Permissions
+            (404, 10003, true),
+            (403, 50001, true),
+            (403, MISSING_PERMISSIONS_CODE, true),
+            // Codes Discord reports without the usual status
+            (400, 10003, true),
+            (400, MISSING_PERMISSIONS_CODE, true),
+            // Status alone is enough for an unrecognized code
+            (403, 0, true),
+            (404, 0, true),
+            // Transient: rate limits, server errors, bad requests
+            (429, 0, false),
+            (500, 0, false),
+            (502, 0, false),
+            (503, 0, false),
+            (400, 50035, false),
+            (401, 0, false),
+            // DMs closed is a user problem, not a channel one
+            (400, CANNOT_MESSAGE_USER_CODE, false),
+        ];
+        for (status, code, expected) in cases {
+            assert_eq!(
+                is_permanent_failure(status, code),
+                expected,
+                "{status}/{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors_without_response_are_transient() {
+        let errors = [
+            serenity::Error::Other("connection reset"),
+            serenity::Error::Http(HttpError::RateLimitUtf8),
+            serenity::Error::Http(HttpError::InvalidWebhook),
+        ];
+        for error in errors {
+            assert_eq!(discord_error(&error), None, "{error}");
+            assert!(!is_permanent_delivery_error(&error), "{error}");
+            assert!(!is_dm_unreachable(&error), "{error}");
+            assert!(!is_missing_permissions(&error), "{error}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_paused_guild_skipped() {
+        let db = connect_in_memory().await;
+        guild(&db, None).await;
+        add_webhook(&db, "").await;
+        let paused = GuildConfigRepository::new(Arc::new(db.clone()))
+            .pause(
+                GuildId::new(GUILD_ID),
+                Utc::now() + chrono::Duration::days(7),
+            )
+            .await
+            .unwrap();
+
+        let (delivery, notifier) = send(&db, &paused).await;
+
+        assert_eq!(delivery, Delivery::Skipped);
+        // Not recorded, so the alert isn't counted as delivered while paused
+        assert_eq!(sent_alert_count(&db).await, 0);
+        let snapshot = notifier.limiter.snapshot(Utc::now(), 100);
+        assert_eq!(snapshot.delivered_last_hour, 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_pause_resumes_delivery() {
+        let db = connect_in_memory().await;
+        guild(&db, None).await;
+        add_webhook(&db, "").await;
+        // A pause that ended a minute ago, never cleared
+        let expired = GuildConfigRepository::new(Arc::new(db.clone()))
+            .pause(
+                GuildId::new(GUILD_ID),
+                Utc::now() - chrono::Duration::minutes(1),
+            )
+            .await
+            .unwrap();
+        assert!(expired.paused_until.is_some());
+
+        let (delivery, _) = send(&db, &expired).await;
+
+        assert_eq!(delivery, Delivery::Sent);
+        assert_eq!(sent_alert_count(&db).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_resumed_guild_receives_alert() {
+        let db = connect_in_memory().await;
+        guild(&db, None).await;
+        add_webhook(&db, "").await;
+        let repo = GuildConfigRepository::new(Arc::new(db.clone()));
+        let paused = repo
+            .pause(
+                GuildId::new(GUILD_ID),
+                Utc::now() + chrono::Duration::days(7),
+            )
+            .await
+            .unwrap();
+        let (while_paused, _) = send(&db, &paused).await;
+
+        let resumed = repo.resume(GuildId::new(GUILD_ID)).await.unwrap();
+        let (after_resume, _) = send(&db, &resumed).await;
+
+        assert_eq!(while_paused, Delivery::Skipped);
+        assert_eq!(after_resume, Delivery::Sent);
+    }
+}
@@ -0,0 +1,328 @@
+//! Weekly status digest
+//!
+//! Guilds that turned the digest on with /config digest get a summary of the
+//! past 7 days in their alert channel: official incidents and their combined
+//! duration, average online users and API error rate, and the most reported
+//! incident types. A job checks hourly and posts on the configured weekday
+//! (`bot_config.digest.day_of_week`, UTC, Monday by default). Deliveries are
+//! deduplicated in `sent_alerts` (type `digest`) with one reference ID per
+//! ISO week, so a restart on digest day does not post twice.
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use rust_i18n::t;
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter};
+use serenity::all::{ChannelId, Colour, CreateEmbed, CreateEmbedFooter, CreateMessage, Http};
+use tracing::{debug, info, warn};
+
+use crate::bot::maintenance_mode::MaintenanceMode;
+use crate::commands::shared::{colors, incident_types, text};
+use crate::entity::{bot_config, guild_configs, incidents};
+use crate::i18n::resolve_guild_locale_by_id;
+use crate::repository::{GuildConfigRepository, ReportRepository, config};
+use crate::scheduler::{JobResult, Schedule, Scheduler};
+use crate::visualization::query::average_metric;
+
+use super::delivery::{RecordAlertResult, delete_sent_alert, try_record_sent_alert};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// `sent_alerts.alert_type` for weekly digests
+pub(crate) const ALERT_TYPE_DIGEST: &str = "digest";
+
+/// Config key for the weekday digests are posted on
+pub const DAY_OF_WEEK_KEY: &str = "digest.day_of_week";
+
+/// Digest weekday (used if config missing or invalid)
+pub const DEFAULT_DAY_OF_WEEK: Weekday = Weekday::Mon;
+
+/// How often the job checks whether a digest is due
+const CHECK_INTERVAL: StdDuration = StdDuration::from_secs(60 * 60);
+
+/// Days summarized by a digest
+const DIGEST_DAYS: i64 = 7;
+
+/// Most reported incident types listed
+const TOP_TYPES: u64 = 3;
+
+// =============================================================================
+// Digest
+// =============================================================================
+
+/// Summary of one week
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyDigest {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Official incidents active during the week
+    pub incidents: u64,
+    /// Time those incidents were open within the week, added up
+    pub incident_duration: Duration,
+    pub online_users_avg: Option<f64>,
+    /// Percent
+    pub api_error_rate_avg: Option<f64>,
+    /// Most reported incident types with their report counts, busiest first
+    pub top_types: Vec<(String, u64)>,
+}
+
+/// `sent_alerts.reference_id` for the digest of the ISO week containing `date`
+pub fn reference_id(date: NaiveDate) -> String {
+    let week = date.iso_week();
+    format!("digest_{}-W{:02}", week.year(), week.week())
+}
+
+/// Combined time incidents were open within `[since, until]`
+///
+/// Unresolved incidents count as open until `until`. Overlapping incidents
+/// are added up, not merged.
+pub fn total_incident_duration(
+    windows: &[(DateTime<Utc>, Option<DateTime<Utc>>)],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Duration {
+    windows
+        .iter()
+        .map(|(started_at, resolved_at)| {
+            let start = (*started_at).max(since);
+            let end = resolved_at.unwrap_or(until).min(until);
+            (end - start).max(Duration::zero())
+        })
+        .sum()
+}
+
+/// Weekday digests are posted on
+async fn digest_weekday(db: &DatabaseConnection) -> Weekday {
+    let value = match bot_config::Entity::find_by_id(DAY_OF_WEEK_KEY)
+        .one(db)
+        .await
+    {
+        Ok(Some(row)) => row.value,
+        Ok(None) => return DEFAULT_DAY_OF_WEEK,
+        Err(e) => {
+            warn!(error = %e, "Failed to load digest weekday, using default");
+            return DEFAULT_DAY_OF_WEEK;
+        }
+    };
+    value.trim().parse().unwrap_or_else(|_| {
+        warn!(value, "Invalid config '{}', using default", DAY_OF_WEEK_KEY);
+        DEFAULT_DAY_OF_WEEK
+    })
+}
+
+/// Compute the digest for the 7 days ending at `now`
+async fn load_digest(
+    db: &Arc<DatabaseConnection>,
+    now: DateTime<Utc>,
+) -> Result<WeeklyDigest, sea_orm::DbErr> {
+    let range = Duration::days(DIGEST_DAYS);
+    let since = now - range;
+
+    let windows: Vec<_> = incidents::Entity::find()
+        .filter(incidents::Column::StartedAt.lt(now))
+        .filter(
+            Condition::any()
+                .add(incidents::Column::ResolvedAt.is_null())
+                .add(incidents::Column::ResolvedAt.gte(since)),
+        )
+        .all(&**db)
+        .await?
+        .into_iter()
+        .map(|incident| (incident.started_at, incident.resolved_at))
+        .collect();
+
+    Ok(WeeklyDigest {
+        since,
+        until: now,
+        incidents: windows.len() as u64,
+        incident_duration: total_incident_duration(&windows, since, now),
+        online_users_avg: average_metric(db, "visits", range).await?,
+        api_error_rate_avg: average_metric(db, "api_errors", range)
+            .await?
+            .map(|rate| rate * 100.0),
+        top_types: ReportRepository::new(db.clone())
+            .top_types_since(since, TOP_TYPES)
+            .await?,
+    })
+}
+
+// =============================================================================
+// Job
+// =============================================================================
+
+/// Register the weekly digest job
+pub fn register(
+    scheduler: &mut Scheduler,
+    http: Arc<Http>,
+    db: Arc<DatabaseConnection>,
+    maintenance_mode: Arc<MaintenanceMode>,
+) {
+    scheduler.register(
+        "weekly_digest",
+        Schedule::Interval(CHECK_INTERVAL),
+        move || send_weekly_digest(http.clone(), db.clone(), maintenance_mode.clone()),
+    );
+}
+
+/// Post this week's digest to every opted-in guild that has not received it
+async fn send_weekly_digest(
+    http: Arc<Http>,
+    db: Arc<DatabaseConnection>,
+    maintenance_mode: Arc<MaintenanceMode>,
+) -> JobResult {
+    if maintenance_mode.is_enabled() {
+        debug!("Maintenance mode, skipping weekly digest");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    if now.weekday() != digest_weekday(&db).await {
+        return Ok(());
+    }
+
+    let guilds = GuildConfigRepository::new(db.clone())
+        .list_digest_enabled()
+        .await?;
+    if guilds.is_empty() {
+        return Ok(());
+    }
+
+    let reference_id = reference_id(now.date_naive());
+    let digest = load_digest(&db, now).await?;
+    for guild in &guilds {
+        send_guild_digest(&http, &db, guild, &digest, &reference_id).await;
+    }
+    Ok(())
+}
+
+async fn send_guild_digest(
+    http: &Http,
+    db: &DatabaseConnection,
+    guild: &guild_configs::Model,
+    digest: &WeeklyDigest,
+    reference_id: &str,
+) {
+    let Some(Ok(channel_id)) = guild.channel_id.as_ref().map(|id| id.parse::<u64>()) else {
+        return;
+    };
+    if config::is_paused(guild, Utc::now()) {
+        debug!(guild_id = %guild.guild_id, "Guild alerts paused, skipping weekly digest");
+        return;
+    }
+
+    let record_id = match try_record_sent_alert(
+        db,
+        Some(guild.guild_id.clone()),
+        None,
+        ALERT_TYPE_DIGEST,
+        reference_id,
+    )
+    .await
+    {
+        RecordAlertResult::Recorded(id) => id,
+        RecordAlertResult::AlreadySent | RecordAlertResult::Error => return,
+    };
+
+    let locale = resolve_guild_locale_by_id(db, &guild.guild_id).await;
+    let message = CreateMessage::new().embed(build_digest_embed(digest, &locale));
+    match ChannelId::new(channel_id).send_message(http, message).await {
+        Ok(_) => info!(
+            guild_id = %guild.guild_id,
+            reference_id,
+            "Sent weekly digest to guild"
+        ),
+        Err(e) => {
+            warn!(
+                guild_id = %guild.guild_id,
+                error = %e,
+                "Failed to send weekly digest to guild, will retry on next check"
+            );
+            delete_sent_alert(db, record_id).await;
+        }
+    }
+}
+
+// =============================================================================
+// Embed
+// =============================================================================
+
+/// Build the digest embed
+fn build_digest_embed(digest: &WeeklyDigest, locale: &str) -> CreateEmbed {
+    let no_data = t!("embeds.alerts.digest.no_data", locale = locale).to_string();
+
+    let online_users = match digest.online_users_avg {
+        Some(avg) if avg >= 1000.0 => format!("{:.1}k", avg / 1000.0),
+        Some(avg) => format!("{:.0}", avg),
+        None => no_data.clone(),
+    };
+    let api_error_rate = digest
+        .api_error_rate_avg
+        .map(|rate| format!("{:.4}%", rate))
+        .unwrap_or(no_data);
+
+    let top_types = if digest.top_types.is_empty() {
+        t!("embeds.alerts.digest.no_reports", locale = locale).to_string()
+    } else {
+        digest
+            .top_types
+            .iter()
+            .map(|(incident_type, count)| {
+                t!(
+                    "embeds.alerts.digest.top_type_line",
+                    incident_type = incident_types::display_name_localized(incident_type, locale),
+                    count = count,
+                    locale = locale
+                )
+                .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::default()
+        .title(t!("embeds.alerts.digest.title", locale = locale))
+        .description(t!(
+            "embeds.alerts.digest.description",
+            since = format!("<t:{}:D>", digest.since.timestamp()),
+            until = format!("<t:{}:D>", digest.until.timestamp()),
+            locale = locale
+        ))
+        .color(Colour::new(colors::BRAND))
+        .field(
+            t!("embeds.alerts.digest.field_incidents", locale = locale),
+            digest.incidents.to_string(),
+            true,
+        )
+        .field(
+            t!(
+                "embeds.alerts.digest.field_incident_duration",
+                locale = locale
+            ),
+            text::format_duration(digest.incident_duration, locale),
+            true,
+        )
+        .field("\u{200B}", "\u{200B}", true)
+        .field(
+            t!("embeds.alerts.digest.field_online_users", locale = locale),
+            online_users,
+            true,
+        )
+        .field(
+            t!("embeds.alerts.digest.field_api_error_rate", locale = locale),
+            api_error_rate,
+            true,
+        )
+        .field("\u{200B}", "\u{200B}", true)
+        .field(
+            t!("embeds.alerts.digest.field_top_types", locale = locale),
+            top_types,
+            false,
+        )
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.alerts.digest.footer",
+            locale = locale
+        )))
+}
@@ -0,0 +1,514 @@
+//! Alerts for official VRChat status page incidents
+//!
+//! The incident poller reports each incident it inserts and each incident
+//! whose status changes (e.g. `investigating` to `identified`, or to
+//! `resolved` once it leaves the unresolved list). Registered guilds and DM
+//! users get a notice with the title, impact, status, and latest update;
+//! resolved notices are green and say how long the incident lasted.
+//!
+//! Notices go through `delivery`, like threshold alerts, and are
+//! deduplicated in `sent_alerts` with reference IDs `incident_<id>_<status>`,
+//! so each status of an incident alerts once. Resolved notices have type
+//! `incident_resolved` and the others `incident_opened`, so recipients can
+//! turn either off on its own.
+//!
+//! Updates the status page posts later (e.g. a new `monitoring` note) go to
+//! the recipients that got a notice for the incident, once per update ID
+//! (type `incident_update`).
+
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serenity::all::{Colour, CreateEmbed, CreateEmbedFooter, Timestamp};
+use tracing::warn;
+
+use crate::commands::shared::components::label;
+use crate::commands::shared::{colors, links, text};
+use crate::commands::status::history::impact_style;
+use crate::entity::{incident_updates, incidents, sent_alerts};
+
+use super::delivery::{self, AlertNotice, Notifier, get_registered_guilds, get_registered_users};
+use super::webhook::{self, WebhookNotice, WebhookPayload};
+
+/// `sent_alerts.alert_type` for new incidents and their status changes
+pub(crate) const ALERT_TYPE_INCIDENT_OPENED: &str = "incident_opened";
+
+/// `sent_alerts.alert_type` for resolved incidents
+pub(crate) const ALERT_TYPE_INCIDENT_RESOLVED: &str = "incident_resolved";
+
+/// `sent_alerts.alert_type` for incident update notices
+pub(crate) const ALERT_TYPE_INCIDENT_UPDATE: &str = "incident_update";
+
+/// Longest incident title in the embed title
+const TITLE_CHARS: usize = 200;
+
+/// Longest latest-update body shown (embed field limit)
+const UPDATE_CHARS: usize = 1024;
+
+// =============================================================================
+// Change Detection
+// =============================================================================
+
+/// An incident that is new or whose status changed during a poll
+#[derive(Debug, Clone)]
+pub struct IncidentChange {
+    /// Incident as stored after the poll
+    pub incident: incidents::Model,
+    /// Status before the poll; `None` for a newly inserted incident
+    pub previous_status: Option<String>,
+    /// Newest stored update
+    pub latest_update: Option<incident_updates::Model>,
+}
+
+/// A new update of an incident that was already announced
+#[derive(Debug, Clone)]
+pub struct IncidentUpdateChange {
+    /// Incident the update belongs to
+    pub incident: incidents::Model,
+    /// The update as stored
+    pub update: incident_updates::Model,
+    /// Status of the update before it; `None` for the first update
+    pub previous_status: Option<String>,
+}
+
+/// `sent_alerts.reference_id` for a change
+///
+/// Format: `incident_<id>_<status>`.
+pub fn reference_id(change: &IncidentChange) -> String {
+    format!("incident_{}_{}", change.incident.id, change.incident.status)
+}
+
+/// `sent_alerts.alert_type` for a change
+pub fn alert_type(change: &IncidentChange) -> &'static str {
+    if change.incident.status == "resolved" {
+        ALERT_TYPE_INCIDENT_RESOLVED
+    } else {
+        ALERT_TYPE_INCIDENT_OPENED
+    }
+}
+
+// =============================================================================
+// Delivery
+// =============================================================================
+
+/// One change, as sent to every recipient
+struct IncidentNotice<'a> {
+    change: &'a IncidentChange,
+    reference_id: String,
+}
+
+impl AlertNotice for IncidentNotice<'_> {
+    fn alert_type(&self) -> &'static str {
+        alert_type(self.change)
+    }
+
+    fn reference_id(&self) -> &str {
+        &self.reference_id
+    }
+
+    fn route(&self) -> &str {
+        alert_type(self.change)
+    }
+
+    fn embed(&self, locale: &str, _with_attachment: bool) -> CreateEmbed {
+        build_notice_embed(self.change, locale)
+    }
+
+    fn webhook_payload(&self, guild_id: &str) -> WebhookPayload {
+        let incident = &self.change.incident;
+        WebhookPayload::Notice(WebhookNotice {
+            event: webhook::EVENT_INCIDENT_NOTICE,
+            guild_id: guild_id.to_string(),
+            reference_id: self.reference_id.clone(),
+            title: incident.title.clone(),
+            status: incident.status.clone(),
+            url: links::incident_url(&incident.id),
+            sent_at: Utc::now(),
+        })
+    }
+}
+
+/// One new update, as sent to every recipient that follows the incident
+struct UpdateNotice<'a> {
+    change: &'a IncidentUpdateChange,
+}
+
+impl AlertNotice for UpdateNotice<'_> {
+    fn alert_type(&self) -> &'static str {
+        ALERT_TYPE_INCIDENT_UPDATE
+    }
+
+    fn reference_id(&self) -> &str {
+        &self.change.update.id
+    }
+
+    fn route(&self) -> &str {
+        ALERT_TYPE_INCIDENT_OPENED
+    }
+
+    fn embed(&self, locale: &str, _with_attachment: bool) -> CreateEmbed {
+        build_update_embed(self.change, locale)
+    }
+
+    fn webhook_payload(&self, guild_id: &str) -> WebhookPayload {
+        let incident = &self.change.incident;
+        WebhookPayload::Notice(WebhookNotice {
+            event: webhook::EVENT_INCIDENT_NOTICE,
+            guild_id: guild_id.to_string(),
+            reference_id: self.change.update.id.clone(),
+            title: incident.title.clone(),
+            status: self.change.update.status.clone(),
+            url: links::incident_url(&incident.id),
+            sent_at: Utc::now(),
+        })
+    }
+}
+
+/// Send notices for `changes`, then for `updates`, in a background task, so a
+/// slow fan-out never delays the next poll
+pub fn spawn_notices(
+    notifier: Notifier,
+    db: DatabaseConnection,
+    changes: Vec<IncidentChange>,
+    updates: Vec<IncidentUpdateChange>,
+) {
+    if changes.is_empty() && updates.is_empty() {
+        return;
+    }
+    let tasks = notifier.tasks.clone();
+    tasks.spawn(async move {
+        send_notices(&notifier, &db, &changes, &updates).await;
+    });
+}
+
+async fn send_notices(
+    notifier: &Notifier,
+    db: &DatabaseConnection,
+    changes: &[IncidentChange],
+    updates: &[IncidentUpdateChange],
+) {
+    let guilds = get_registered_guilds(db, ALERT_TYPE_INCIDENT_OPENED).await;
+    let users = get_registered_users(db, ALERT_TYPE_INCIDENT_OPENED).await;
+    // Recipients can turn resolved notices off separately
+    let (resolved_guilds, resolved_users) = if changes
+        .iter()
+        .any(|change| alert_type(change) == ALERT_TYPE_INCIDENT_RESOLVED)
+    {
+        (
+            get_registered_guilds(db, ALERT_TYPE_INCIDENT_RESOLVED).await,
+            get_registered_users(db, ALERT_TYPE_INCIDENT_RESOLVED).await,
+        )
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    for change in changes {
+        let notice = IncidentNotice {
+            change,
+            reference_id: reference_id(change),
+        };
+        let (guilds, users) = if alert_type(change) == ALERT_TYPE_INCIDENT_RESOLVED {
+            (&resolved_guilds, &resolved_users)
+        } else {
+            (&guilds, &users)
+        };
+        delivery::fan_out(notifier, db, guilds, users, &notice).await;
+    }
+
+    // Updates belong to open incidents, and only go to recipients that heard
+    // about the incident
+    for change in updates {
+        let incident_id = &change.incident.id;
+        let mut following_guilds = Vec::new();
+        for guild in &guilds {
+            if follows_incident(db, Some(&guild.guild_id), None, incident_id).await {
+                following_guilds.push(guild.clone());
+            }
+        }
+        let mut following_users = Vec::new();
+        for user in &users {
+            if follows_incident(db, None, Some(&user.user_id), incident_id).await {
+                following_users.push(user.clone());
+            }
+        }
+        let notice = UpdateNotice { change };
+        delivery::fan_out(notifier, db, &following_guilds, &following_users, &notice).await;
+    }
+}
+
+/// Whether a recipient got a notice for the incident
+async fn follows_incident(
+    db: &DatabaseConnection,
+    guild_id: Option<&str>,
+    user_id: Option<&str>,
+    incident_id: &str,
+) -> bool {
+    let mut query = sent_alerts::Entity::find()
+        .filter(
+            sent_alerts::Column::AlertType
+                .is_in([ALERT_TYPE_INCIDENT_OPENED, ALERT_TYPE_INCIDENT_RESOLVED]),
+        )
+        .filter(sent_alerts::Column::ReferenceId.starts_with(format!("incident_{}_", incident_id)));
+    query = match (guild_id, user_id) {
+        (Some(id), _) => query.filter(sent_alerts::Column::GuildId.eq(id)),
+        (None, Some(id)) => query.filter(sent_alerts::Column::UserId.eq(id)),
+        (None, None) => return false,
+    };
+
+    match query.one(db).await {
+        Ok(found) => found.is_some(),
+        Err(e) => {
+            warn!(incident_id, error = %e, "Failed to load previous incident notice");
+            false
+        }
+    }
+}
+
+// =============================================================================
+// Embed
+// =============================================================================
+
+/// Build the notice embed, colored by impact (green once resolved)
+fn build_notice_embed(change: &IncidentChange, locale: &str) -> CreateEmbed {
+    let incident = &change.incident;
+    let (emoji, impact_color) = impact_style(&incident.impact);
+    let resolved = incident.status == "resolved";
+
+    let title_key = match (&change.previous_status, resolved) {
+        (_, true) => "embeds.alerts.incident.title_resolved",
+        (None, false) => "embeds.alerts.incident.title_new",
+        (Some(_), false) => "embeds.alerts.incident.title_updated",
+    };
+    let title = t!(
+        title_key,
+        title = text::truncate(&incident.title, TITLE_CHARS),
+        locale = locale
+    );
+
+    let status = label("embeds.incident.update_status", &incident.status, locale);
+    let status = match &change.previous_status {
+        Some(previous) => format!(
+            "{} → {}",
+            label("embeds.incident.update_status", previous, locale),
+            status
+        ),
+        None => status,
+    };
+    let mut description = format!(
+        "{}\n{}\n{}",
+        t!(
+            "embeds.incident.impact",
+            impact = format!(
+                "{} {}",
+                emoji,
+                label("embeds.incident.impact_level", &incident.impact, locale)
+            ),
+            locale = locale
+        ),
+        t!(
+            "embeds.alerts.incident.status",
+            status = status,
+            locale = locale
+        ),
+        t!(
+            "embeds.incident.started",
+            time = format!("<t:{}:f>", incident.started_at.timestamp()),
+            locale = locale
+        )
+    );
+    // Resolved notices say when and how long; the poller stamps `resolved_at`
+    // when an incident leaves the unresolved list
+    if resolved {
+        let resolved_at = incident.resolved_at.unwrap_or(incident.updated_at);
+        description = format!(
+            "{}\n{}\n{}",
+            description,
+            t!(
+                "embeds.incident.resolved",
+                time = format!("<t:{}:f>", resolved_at.timestamp()),
+                locale = locale
+            ),
+            t!(
+                "embeds.incident.duration",
+                duration = text::format_duration(resolved_at - incident.started_at, locale),
+                locale = locale
+            )
+        );
+    }
+
+    let mut embed = CreateEmbed::default()
+        .title(title)
+        .url(links::incident_url(&incident.id))
+        .description(description)
+        .color(Colour::new(if resolved {
+            colors::SUCCESS
+        } else {
+            impact_color
+        }))
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.alerts.incident.footer",
+            locale = locale
+        )));
+    if let Some(update) = &change.latest_update {
+        embed = embed.field(
+            t!(
+                "embeds.alerts.incident.field_latest_update",
+                locale = locale
+            ),
+            text::truncate(&update.body, UPDATE_CHARS),
+            false,
+        );
+    }
+    if let Ok(timestamp) = Timestamp::from_unix_timestamp(incident.updated_at.timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+    embed
+}
+
+/// Build the compact embed for a new update: status transition, update body,
+/// and time since the incident started
+fn build_update_embed(change: &IncidentUpdateChange, locale: &str) -> CreateEmbed {
+    let incident = &change.incident;
+    let update = &change.update;
+    let (_, impact_color) = impact_style(&incident.impact);
+
+    let status = label("embeds.incident.update_status", &update.status, locale);
+    let status = match &change.previous_status {
+        Some(previous) if *previous != update.status => format!(
+            "{} → {}",
+            label("embeds.incident.update_status", previous, locale),
+            status
+        ),
+        _ => status,
+    };
+
+    let mut embed = CreateEmbed::default()
+        .title(t!(
+            "embeds.alerts.incident.title_updated",
+            title = text::truncate(&incident.title, TITLE_CHARS),
+            locale = locale
+        ))
+        .url(links::incident_url(&incident.id))
+        .description(t!(
+            "embeds.alerts.incident.status",
+            status = status,
+            locale = locale
+        ))
+        .color(Colour::new(impact_color))
+        .field(
+            t!("embeds.alerts.incident.field_update", locale = locale),
+            text::truncate(&update.body, UPDATE_CHARS),
+            false,
+        )
+        .field(
+            t!("embeds.alerts.incident.field_elapsed", locale = locale),
+            text::format_duration(update.published_at - incident.started_at, locale),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.alerts.incident.footer",
+            locale = locale
+        )));
+    if let Ok(timestamp) = Timestamp::from_unix_timestamp(update.published_at.timestamp()) {
+        embed = embed.timestamp(timestamp);
+    }
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::delivery::{RecordAlertResult, try_record_sent_alert};
+    use crate::database::connect_in_memory;
+
+    fn change(status: &str, previous_status: Option<&str>) -> IncidentChange {
+        let now = chrono::Utc::now();
+        IncidentChange {
+            incident: incidents::Model {
+                id: "inc1".into(),
+                title: "Login issues".into(),
+                impact: "major".into(),
+                status: status.into(),
+                started_at: now,
+                resolved_at: None,
+                created_at: now,
+                updated_at: now,
+            },
+            previous_status: previous_status.map(Into::into),
+            latest_update: None,
+        }
+    }
+
+    #[test]
+    fn test_alert_type_by_status() {
+        let cases = [
+            ("investigating", None, ALERT_TYPE_INCIDENT_OPENED),
+            ("monitoring", Some("identified"), ALERT_TYPE_INCIDENT_OPENED),
+            ("resolved", Some("monitoring"), ALERT_TYPE_INCIDENT_RESOLVED),
+            ("resolved", None, ALERT_TYPE_INCIDENT_RESOLVED),
+        ];
+        for (status, previous, expected) in cases {
+            assert_eq!(alert_type(&change(status, previous)), expected, "{status}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follows_incident_after_notice() {
+        let db = connect_in_memory().await;
+        try_record_sent_alert(
+            &db,
+            Some("1".into()),
+            None,
+            ALERT_TYPE_INCIDENT_OPENED,
+            "incident_inc1_investigating",
+        )
+        .await;
+        try_record_sent_alert(
+            &db,
+            None,
+            Some("2".into()),
+            ALERT_TYPE_INCIDENT_RESOLVED,
+            "incident_inc1_resolved",
+        )
+        .await;
+
+        assert!(follows_incident(&db, Some("1"), None, "inc1").await);
+        assert!(follows_incident(&db, None, Some("2"), "inc1").await);
+        assert!(!follows_incident(&db, Some("3"), None, "inc1").await);
+        assert!(!follows_incident(&db, Some("1"), None, "inc10").await);
+        assert!(!follows_incident(&db, None, None, "inc1").await);
+    }
+
+    #[tokio::test]
+    async fn test_update_recorded_once_per_recipient() {
+        let db = connect_in_memory().await;
+        let first = try_record_sent_alert(
+            &db,
+            Some("1".into()),
+            None,
+            ALERT_TYPE_INCIDENT_UPDATE,
+            "upd1",
+        )
+        .await;
+        let repeat = try_record_sent_alert(
+            &db,
+            Some("1".into()),
+            None,
+            ALERT_TYPE_INCIDENT_UPDATE,
+            "upd1",
+        )
+        .await;
+        let next = try_record_sent_alert(
+            &db,
+            Some("1".into()),
+            None,
+            ALERT_TYPE_INCIDENT_UPDATE,
+            "upd2",
+        )
+        .await;
+
+        assert!(matches!(first, RecordAlertResult::Recorded(_)));
+        assert!(matches!(repeat, RecordAlertResult::AlreadySent));
+        assert!(matches!(next, RecordAlertResult::Recorded(_)));
+    }
+}
@@ -0,0 +1,296 @@
+//! Reminders for scheduled VRChat maintenance
+//!
+//! A scheduled job checks the `maintenances` table kept by the collector and
+//! notifies registered guilds and DM users 60 and 10 minutes before a window
+//! starts, and once when it is in progress. Notices go through `delivery`,
+//! like threshold alerts, and each is deduplicated in `sent_alerts` (type
+//! `maintenance`). Reminder reference IDs include the scheduled start, so a
+//! window moved after its reminder was sent gets a new reminder that
+//! mentions the new time.
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Utc};
+use rust_i18n::t;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter};
+use serenity::all::{Colour, CreateEmbed, CreateEmbedFooter};
+use tracing::debug;
+
+use crate::bot::maintenance_mode::MaintenanceMode;
+use crate::commands::shared::{colors, links};
+use crate::entity::{maintenances, sent_alerts};
+use crate::scheduler::{JobResult, Schedule, Scheduler};
+
+use super::delivery::{self, AlertNotice, Notifier, get_registered_guilds, get_registered_users};
+use super::webhook::{self, WebhookNotice, WebhookPayload};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// `sent_alerts.alert_type` for maintenance notices
+pub(crate) const ALERT_TYPE_MAINTENANCE: &str = "maintenance";
+
+/// How often upcoming maintenance is checked
+const CHECK_INTERVAL: StdDuration = StdDuration::from_secs(60);
+
+/// Lead time of the first reminder
+const EARLY_REMINDER_MINUTES: i64 = 60;
+
+/// Lead time of the final reminder
+const FINAL_REMINDER_MINUTES: i64 = 10;
+
+// =============================================================================
+// Reminder Stages
+// =============================================================================
+
+/// Notice due for a maintenance window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reminder {
+    /// Starts within 60 minutes
+    Early,
+    /// Starts within 10 minutes
+    Final,
+    /// In progress
+    Started,
+}
+
+impl Reminder {
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::Early => "60m",
+            Self::Final => "10m",
+            Self::Started => "started",
+        }
+    }
+}
+
+/// Notice due for a window at `now`, if any
+///
+/// Only the nearest reminder is due, so a window announced 5 minutes before
+/// it starts gets the final reminder alone. A window counts as started once
+/// the status page marks it in progress, until its scheduled end.
+pub fn due_reminder(
+    status: &str,
+    scheduled_for: DateTime<Utc>,
+    scheduled_until: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<Reminder> {
+    match status {
+        "in_progress" if now < scheduled_until => Some(Reminder::Started),
+        "scheduled" if scheduled_for > now => {
+            let until_start = scheduled_for - now;
+            if until_start <= Duration::minutes(FINAL_REMINDER_MINUTES) {
+                Some(Reminder::Final)
+            } else if until_start <= Duration::minutes(EARLY_REMINDER_MINUTES) {
+                Some(Reminder::Early)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `sent_alerts.reference_id` for a notice
+///
+/// Reminders carry the scheduled start (`maintenance_<id>_60m_<unix>`) so a
+/// rescheduled window is reminded again; the started notice is sent once.
+pub fn reference_id(
+    maintenance_id: &str,
+    reminder: Reminder,
+    scheduled_for: DateTime<Utc>,
+) -> String {
+    match reminder {
+        Reminder::Started => format!("maintenance_{}_{}", maintenance_id, reminder.suffix()),
+        _ => format!(
+            "maintenance_{}_{}_{}",
+            maintenance_id,
+            reminder.suffix(),
+            scheduled_for.timestamp()
+        ),
+    }
+}
+
+// =============================================================================
+// Job
+// =============================================================================
+
+/// Register the maintenance reminder job
+pub fn register(
+    scheduler: &mut Scheduler,
+    notifier: Notifier,
+    db: Arc<DatabaseConnection>,
+    maintenance_mode: Arc<MaintenanceMode>,
+) {
+    scheduler.register(
+        "maintenance_reminders",
+        Schedule::Interval(CHECK_INTERVAL),
+        move || send_due_reminders(notifier.clone(), db.clone(), maintenance_mode.clone()),
+    );
+}
+
+/// Send every notice that is due
+async fn send_due_reminders(
+    notifier: Notifier,
+    db: Arc<DatabaseConnection>,
+    maintenance_mode: Arc<MaintenanceMode>,
+) -> JobResult {
+    if maintenance_mode.is_enabled() {
+        debug!("Maintenance mode, skipping maintenance reminders");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let windows = maintenances::Entity::find()
+        .filter(maintenances::Column::Status.is_in(["scheduled", "in_progress"]))
+        .all(&*db)
+        .await?;
+
+    let due: Vec<_> = windows
+        .into_iter()
+        .filter_map(|window| {
+            let reminder = due_reminder(
+                &window.status,
+                window.scheduled_for,
+                window.scheduled_until,
+                now,
+            )?;
+            Some((window, reminder))
+        })
+        .collect();
+    if due.is_empty() {
+        return Ok(());
+    }
+
+    let guilds = get_registered_guilds(&db, ALERT_TYPE_MAINTENANCE).await;
+    let users = get_registered_users(&db, ALERT_TYPE_MAINTENANCE).await;
+
+    for (window, reminder) in due {
+        let notice = Notice {
+            rescheduled: reminder != Reminder::Started && was_rescheduled(&db, &window).await?,
+            reference_id: reference_id(&window.id, reminder, window.scheduled_for),
+            window: &window,
+            reminder,
+        };
+        delivery::fan_out(&notifier, &db, &guilds, &users, &notice).await;
+    }
+    Ok(())
+}
+
+/// Whether a reminder was already sent for an earlier start time of this window
+async fn was_rescheduled(
+    db: &DatabaseConnection,
+    window: &maintenances::Model,
+) -> Result<bool, sea_orm::DbErr> {
+    let start = window.scheduled_for.timestamp().to_string();
+    let earlier = sent_alerts::Entity::find()
+        .filter(sent_alerts::Column::AlertType.eq(ALERT_TYPE_MAINTENANCE))
+        .filter(sent_alerts::Column::ReferenceId.starts_with(format!("maintenance_{}_", window.id)))
+        .filter(sent_alerts::Column::ReferenceId.ne(format!("maintenance_{}_started", window.id)))
+        .filter(sent_alerts::Column::ReferenceId.ends_with(&start).not())
+        .count(db)
+        .await?;
+    Ok(earlier > 0)
+}
+
+// =============================================================================
+// Delivery
+// =============================================================================
+
+/// One notice shared by every recipient
+struct Notice<'a> {
+    window: &'a maintenances::Model,
+    reminder: Reminder,
+    reference_id: String,
+    /// An earlier reminder announced a different start time
+    rescheduled: bool,
+}
+
+impl AlertNotice for Notice<'_> {
+    fn alert_type(&self) -> &'static str {
+        ALERT_TYPE_MAINTENANCE
+    }
+
+    fn reference_id(&self) -> &str {
+        &self.reference_id
+    }
+
+    fn route(&self) -> &str {
+        ALERT_TYPE_MAINTENANCE
+    }
+
+    fn embed(&self, locale: &str, _with_attachment: bool) -> CreateEmbed {
+        build_notice_embed(self, locale)
+    }
+
+    fn webhook_payload(&self, guild_id: &str) -> WebhookPayload {
+        WebhookPayload::Notice(WebhookNotice {
+            event: webhook::EVENT_MAINTENANCE_NOTICE,
+            guild_id: guild_id.to_string(),
+            reference_id: self.reference_id.clone(),
+            title: self.window.title.clone(),
+            status: self.window.status.clone(),
+            url: links::incident_url(&self.window.id),
+            sent_at: Utc::now(),
+        })
+    }
+}
+
+/// Build the notice embed
+fn build_notice_embed(notice: &Notice<'_>, locale: &str) -> CreateEmbed {
+    let (title, color) = match notice.reminder {
+        Reminder::Early => (
+            t!(
+                "embeds.alerts.maintenance.title_soon",
+                minutes = EARLY_REMINDER_MINUTES,
+                locale = locale
+            ),
+            colors::WARNING,
+        ),
+        Reminder::Final => (
+            t!(
+                "embeds.alerts.maintenance.title_soon",
+                minutes = FINAL_REMINDER_MINUTES,
+                locale = locale
+            ),
+            colors::WARNING,
+        ),
+        Reminder::Started => (
+            t!("embeds.alerts.maintenance.title_started", locale = locale),
+            colors::MAJOR,
+        ),
+    };
+
+    let window = notice.window;
+    let mut description = format!("**{}**", window.title);
+    if notice.rescheduled {
+        description.push_str("\n\n");
+        description.push_str(&t!(
+            "embeds.alerts.maintenance.rescheduled",
+            time = format!("<t:{}:f>", window.scheduled_for.timestamp()),
+            locale = locale
+        ));
+    }
+
+    CreateEmbed::default()
+        .title(title)
+        .url(links::incident_url(&window.id))
+        .description(description)
+        .color(Colour::new(color))
+        .field(
+            t!("embeds.alerts.maintenance.field_start", locale = locale),
+            format!("<t:{0}:f> (<t:{0}:R>)", window.scheduled_for.timestamp()),
+            true,
+        )
+        .field(
+            t!("embeds.alerts.maintenance.field_end", locale = locale),
+            format!("<t:{0}:f> (<t:{0}:R>)", window.scheduled_until.timestamp()),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.alerts.maintenance.footer",
+            locale = locale
+        )))
+}
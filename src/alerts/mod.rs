@@ -1,17 +1,64 @@
 //! Alert system for VRCPulse
 //!
 //! Handles threshold-based alerts when multiple users report the same issue.
+//! Every alert and notice reaches guilds and DM users through `delivery`.
+//! Deliveries are capped per rolling hour by the safeguard in `safeguard`.
+//! Alerts for some incident types carry a metric chart from `context_chart`.
+//! The "Recent Reports" field is summarized by `recent`, and `build_note`
+//! mentions a VRChat build released shortly before the alert. `spread` counts
+//! the communities the reports came from. Deduplication reference IDs come
+//! from `reference`. `maintenance_reminder` notifies the same recipients
+//! before and when scheduled VRChat maintenance starts, `incident` announces
+//! new status page incidents and their status changes, and `digest` posts a
+//! weekly status summary to guilds that opted in. `channel_check` disables
+//! guilds whose alert channel is gone, and `webhook` posts delivered alerts
+//! to a guild's registered HTTP endpoints. `component` tells recipients when
+//! a status page component degrades or recovers.
 //!
 //! ## Status Field Lifecycle
 //!
 //! The `user_reports.status` field uses the following values:
 //! - `active`: Report is within the time window, can contribute to threshold alerts
-//! - `counted`: Report was included in a threshold alert (future use)
-//! - `expired`: Time window passed without triggering alert (future use)
+//! - `counted`: Report was included in a threshold alert; set in one batch
+//!   by `check_and_send_alerts` when the threshold is reached. Counted reports
+//!   keep counting toward thresholds until their window passes
+//! - `expired`: Time window passed without triggering an alert; set by the
+//!   `expire_reports` scheduled job (`crate::maintenance`)
+//! - `duplicate`: Lost a submission race within the cooldown window; kept for
+//!   auditing and excluded from every count
+//! - `withdrawn`: Retracted by the reporter with `/report withdraw` within the
+//!   cooldown window; excluded from every count and no longer holds the cooldown
 //!
-//! Currently, only `active` is used. Status transitions (`counted`, `expired`)
-//! are reserved for future implementation of report lifecycle management.
+//! Threshold counts, similar-report counts, and the cooldown read `active`
+//! and `counted` reports (`COUNTING_STATUSES`). Statistics include every
+//! status except `duplicate` and `withdrawn`.
 
+pub mod build_note;
+pub mod channel_check;
+pub mod component;
+pub mod context_chart;
+pub mod delivery;
+pub mod digest;
+pub mod incident;
+pub mod maintenance_reminder;
+pub mod recent;
+pub mod reference;
+pub mod safeguard;
+pub mod spread;
 pub mod threshold;
+pub mod webhook;
 
 pub use threshold::check_and_send_alerts;
+
+/// Alert categories a guild can turn off with /config alerts disable, and a
+/// user with /config notifications set
+///
+/// Values match `sent_alerts.alert_type`, `guild_alert_filters.alert_type`,
+/// and `user_notification_filters.alert_type`.
+pub const FILTERABLE_ALERT_TYPES: &[&str] = &[
+    threshold::ALERT_TYPE_THRESHOLD,
+    maintenance_reminder::ALERT_TYPE_MAINTENANCE,
+    component::ALERT_TYPE_COMPONENT,
+    incident::ALERT_TYPE_INCIDENT_OPENED,
+    incident::ALERT_TYPE_INCIDENT_RESOLVED,
+];
@@ -0,0 +1,204 @@
+//! "Recent Reports" summary for threshold alerts
+//!
+//! Turns the newest report timestamps into display lines: oldest first,
+//! reports with the same relative time merged into one line, and a final
+//! "and N more" line when the window holds more reports than are listed.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait};
+
+use crate::entity::bot_config;
+
+/// `bot_config` key for the number of report timestamps listed in an alert
+pub const MAX_RECENT_REPORTS_KEY: &str = "max_recent_reports";
+
+/// Listed timestamps when `max_recent_reports` is not configured
+pub const DEFAULT_MAX_RECENT_REPORTS: u64 = 5;
+
+/// Upper bound on listed timestamps (keeps the embed field under 1024 chars)
+const MAX_RECENT_REPORTS_LIMIT: u64 = 20;
+
+/// Report timestamps listed in an alert
+#[derive(Debug, Clone, Default)]
+pub struct RecentReports {
+    /// Newest reports in the window, oldest first
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// All reports in the window, including those not listed
+    pub total: u64,
+}
+
+/// One display line of the summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecentLine {
+    /// `count` reports made `minutes` whole minutes ago
+    Reports { minutes: i64, count: usize },
+    /// Reports in the window that were not listed
+    More(u64),
+}
+
+/// Load the configured number of listed timestamps, clamped to 1..=20
+pub async fn load_max_recent_reports(db: &DatabaseConnection) -> u64 {
+    bot_config::Entity::find_by_id(MAX_RECENT_REPORTS_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_RECENT_REPORTS)
+        .clamp(1, MAX_RECENT_REPORTS_LIMIT)
+}
+
+/// Summarize report ages for display
+///
+/// `ages` are whole minutes since each listed report, oldest report first
+/// (descending). Adjacent equal ages merge into one line; `total` above the
+/// number of listed ages adds a trailing [`RecentLine::More`].
+pub fn summarize(ages: &[i64], total: u64) -> Vec<RecentLine> {
+    let mut lines: Vec<RecentLine> = Vec::new();
+
+    for &minutes in ages {
+        match lines.last_mut() {
+            Some(RecentLine::Reports {
+                minutes: last,
+                count,
+            }) if *last == minutes => *count += 1,
+            _ => lines.push(RecentLine::Reports { minutes, count: 1 }),
+        }
+    }
+
+    let hidden = total.saturating_sub(ages.len() as u64);
+    if hidden > 0 {
+        lines.push(RecentLine::More(hidden));
+    }
+
+    lines
+}
+
+/// Whole minutes since each timestamp, clamped at zero for clock skew
+pub fn ages_in_minutes(timestamps: &[DateTime<Utc>], now: DateTime<Utc>) -> Vec<i64> {
+    timestamps
+        .iter()
+        .map(|ts| now.signed_duration_since(*ts).num_minutes().max(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::{Duration, TimeZone};
+    use sea_orm::{ActiveModelTrait, Set};
+
+    fn reports(minutes: i64, count: usize) -> RecentLine {
+        RecentLine::Reports { minutes, count }
+    }
+
+    async fn set_max(db: &DatabaseConnection, value: &str) {
+        bot_config::ActiveModel {
+            key: Set(MAX_RECENT_REPORTS_KEY.to_string()),
+            value: Set(value.to_string()),
+            updated_at: Set(Utc::now()),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn test_summarize_empty() {
+        assert_eq!(summarize(&[], 0), vec![]);
+    }
+
+    #[test]
+    fn test_summarize_distinct_ages() {
+        assert_eq!(
+            summarize(&[9, 4, 1, 0], 4),
+            vec![reports(9, 1), reports(4, 1), reports(1, 1), reports(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_summarize_merges_equal_ages() {
+        assert_eq!(
+            summarize(&[5, 5, 3, 0, 0, 0], 6),
+            vec![reports(5, 2), reports(3, 1), reports(0, 3)]
+        );
+        assert_eq!(summarize(&[2, 2, 2, 2, 2], 5), vec![reports(2, 5)]);
+    }
+
+    #[test]
+    fn test_summarize_merges_only_adjacent_ages() {
+        // Ages are sorted in practice; out-of-order input isn't regrouped
+        assert_eq!(
+            summarize(&[3, 1, 3], 3),
+            vec![reports(3, 1), reports(1, 1), reports(3, 1)]
+        );
+    }
+
+    #[test]
+    fn test_summarize_more_boundary() {
+        let ages = [4, 2, 2, 1, 0];
+
+        // Everything listed: no overflow line
+        assert_eq!(summarize(&ages, 5).last(), Some(&reports(0, 1)));
+        // One past the listed count
+        assert_eq!(summarize(&ages, 6).last(), Some(&RecentLine::More(1)));
+        assert_eq!(summarize(&ages, 7).last(), Some(&RecentLine::More(2)));
+        assert_eq!(summarize(&ages, 6).len(), 5);
+    }
+
+    #[test]
+    fn test_summarize_total_below_listed() {
+        // A failed count falls back to fewer than listed; never "and -1 more"
+        assert_eq!(summarize(&[1, 0], 0), vec![reports(1, 1), reports(0, 1)]);
+    }
+
+    #[test]
+    fn test_summarize_only_hidden_reports() {
+        assert_eq!(summarize(&[], 3), vec![RecentLine::More(3)]);
+    }
+
+    #[test]
+    fn test_ages_in_minutes() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        let timestamps = [
+            now - Duration::minutes(10),
+            now - Duration::seconds(119),
+            now - Duration::seconds(59),
+            now,
+            // Clock skew: a report stamped after `now`
+            now + Duration::seconds(30),
+        ];
+
+        assert_eq!(ages_in_minutes(&timestamps, now), vec![10, 1, 0, 0, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_max_recent_reports_default() {
+        let db = connect_in_memory().await;
+
+        assert_eq!(
+            load_max_recent_reports(&db).await,
+            DEFAULT_MAX_RECENT_REPORTS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_recent_reports_configured() {
+        let cases = [
+            ("8", 8),
+            ("1", 1),
+            ("20", 20),
+            ("0", 1),
+            ("50", MAX_RECENT_REPORTS_LIMIT),
+            ("-3", DEFAULT_MAX_RECENT_REPORTS),
+            ("many", DEFAULT_MAX_RECENT_REPORTS),
+        ];
+        for (value, expected) in cases {
+            let db = connect_in_memory().await;
+            set_max(&db, value).await;
+
+            assert_eq!(load_max_recent_reports(&db).await, expected, "{value}");
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Reference IDs for threshold alert deduplication
+//!
+//! Every check within the same time block must produce a byte-identical ID so
+//! the `sent_alerts` unique constraint blocks repeat deliveries. Blocks are
+//! aligned to the Unix epoch in UTC, so they roll over cleanly at midnight,
+//! month, and year boundaries.
+//!
+//! The block length is the alert expiry: once it passes, a still-ongoing
+//! incident alerts again. It is stored in `bot_config` and changed with
+//! `/admin config alert-expiry`.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+use crate::entity::bot_config;
+
+/// bot_config key for the deduplication block length
+pub const ALERT_EXPIRY_KEY: &str = "report_alert_expiry_minutes";
+
+/// Block length when unset or out of range
+pub const DEFAULT_ALERT_EXPIRY_MINUTES: i64 = 60;
+
+/// Allowed block length range
+pub const MIN_ALERT_EXPIRY_MINUTES: i64 = 15;
+pub const MAX_ALERT_EXPIRY_MINUTES: i64 = 240;
+
+/// Load the deduplication block length, falls back to default if missing or
+/// out of range
+pub async fn get_alert_expiry_minutes(db: &DatabaseConnection) -> i64 {
+    bot_config::Entity::find_by_id(ALERT_EXPIRY_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .filter(|minutes| (MIN_ALERT_EXPIRY_MINUTES..=MAX_ALERT_EXPIRY_MINUTES).contains(minutes))
+        .unwrap_or(DEFAULT_ALERT_EXPIRY_MINUTES)
+}
+
+/// Store the deduplication block length
+pub async fn set_alert_expiry_minutes(
+    db: &DatabaseConnection,
+    minutes: i64,
+) -> Result<(), sea_orm::DbErr> {
+    match bot_config::Entity::find_by_id(ALERT_EXPIRY_KEY)
+        .one(db)
+        .await?
+    {
+        Some(existing) => {
+            let mut active: bot_config::ActiveModel = existing.into();
+            active.value = Set(minutes.to_string());
+            active.updated_at = Set(Utc::now());
+            active.update(db).await?;
+        }
+        None => {
+            let config = bot_config::ActiveModel {
+                key: Set(ALERT_EXPIRY_KEY.to_string()),
+                value: Set(minutes.to_string()),
+                updated_at: Set(Utc::now()),
+            };
+            config.insert(db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Reference ID for an incident type in the current block
+pub fn current_reference_id(incident_type: &str, block_minutes: i64) -> String {
+    reference_id(incident_type, Utc::now(), block_minutes)
+}
+
+/// Reference ID for an incident type in the block containing `at`
+///
+/// Format: `threshold_{incident_type}_{YYYY-MM-DD}T{HH}:{MM}`, where the time
+/// is the UTC start of the block. The timestamp suffix has a fixed width, so
+/// types containing underscores or digits cannot produce the same ID as
+/// another type. Block sizes below one minute are treated as one minute.
+pub fn reference_id(incident_type: &str, at: DateTime<Utc>, block_minutes: i64) -> String {
+    let start = block_start(at, block_minutes);
+    format!(
+        "threshold_{incident_type}_{timestamp}",
+        timestamp = start.format("%Y-%m-%dT%H:%M")
+    )
+}
+
+/// UTC start of the block containing `at`
+pub fn block_start(at: DateTime<Utc>, block_minutes: i64) -> DateTime<Utc> {
+    let block_secs = block_minutes.max(1).saturating_mul(60);
+    let start = at.timestamp().div_euclid(block_secs) * block_secs;
+    DateTime::from_timestamp(start, 0).unwrap_or(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate, TimeZone};
+    use proptest::prelude::*;
+
+    /// Block lengths `/admin config alert-expiry` accepts
+    fn block_minutes() -> impl Strategy<Value = i64> {
+        MIN_ALERT_EXPIRY_MINUTES..=MAX_ALERT_EXPIRY_MINUTES
+    }
+
+    /// Accepted block lengths that divide a day, so blocks start at midnight
+    fn day_aligned_block_minutes() -> impl Strategy<Value = i64> {
+        block_minutes().prop_filter("must divide a day", |minutes| 1440 % minutes == 0)
+    }
+
+    /// Times between 1970 and 2100
+    fn instant() -> impl Strategy<Value = DateTime<Utc>> {
+        (0i64..4_102_444_800).prop_map(|secs| DateTime::from_timestamp(secs, 0).unwrap())
+    }
+
+    fn incident_type() -> impl Strategy<Value = String> {
+        "[a-z0-9_]{1,24}"
+    }
+
+    fn midnight(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.from_utc_datetime(
+            &NaiveDate::from_ymd_opt(year, month, day)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_same_block_same_id(
+            incident_type in incident_type(),
+            at in instant(),
+            block in block_minutes(),
+            fraction in 0.0f64..1.0,
+        ) {
+            let start = block_start(at, block);
+            let offset = ((block * 60) as f64 * fraction) as i64;
+            prop_assert_eq!(
+                reference_id(&incident_type, start, block),
+                reference_id(&incident_type, start + Duration::seconds(offset), block)
+            );
+            prop_assert_eq!(
+                reference_id(&incident_type, at, block),
+                reference_id(&incident_type, start, block)
+            );
+        }
+
+        #[test]
+        fn test_adjacent_blocks_differ(
+            incident_type in incident_type(),
+            at in instant(),
+            block in block_minutes(),
+        ) {
+            let start = block_start(at, block);
+            let last_second = start + Duration::minutes(block) - Duration::seconds(1);
+            let next = start + Duration::minutes(block);
+            prop_assert_eq!(
+                reference_id(&incident_type, start, block),
+                reference_id(&incident_type, last_second, block)
+            );
+            prop_assert_ne!(
+                reference_id(&incident_type, last_second, block),
+                reference_id(&incident_type, next, block)
+            );
+            prop_assert_ne!(
+                reference_id(&incident_type, start - Duration::seconds(1), block),
+                reference_id(&incident_type, start, block)
+            );
+        }
+
+        #[test]
+        fn test_day_rollover(
+            day in 0i64..47_000,
+            block in day_aligned_block_minutes(),
+        ) {
+            let midnight = DateTime::from_timestamp(day * 86_400, 0).unwrap();
+            let before = reference_id("api", midnight - Duration::seconds(1), block);
+            let after = reference_id("api", midnight, block);
+            let previous_day = (midnight - Duration::days(1)).format("%Y-%m-%d").to_string();
+            let expected_after = format!("threshold_api_{}T00:00", midnight.format("%Y-%m-%d"));
+
+            prop_assert!(before.contains(&previous_day));
+            prop_assert_eq!(&after, &expected_after);
+            prop_assert_ne!(before, after);
+        }
+
+        #[test]
+        fn test_month_rollover(
+            year in 1971i32..2100,
+            month in 1u32..=12,
+            block in day_aligned_block_minutes(),
+        ) {
+            let first = midnight(year, month, 1);
+            let before = reference_id("login", first - Duration::seconds(1), block);
+            let after = reference_id("login", first, block);
+            let last_month = (first - Duration::days(1)).format("%Y-%m").to_string();
+
+            let expected_prefix = format!("{last_month}-");
+            prop_assert!(before.contains(&expected_prefix));
+            prop_assert_eq!(after, format!("threshold_login_{year:04}-{month:02}-01T00:00"));
+        }
+
+        #[test]
+        fn test_year_rollover(
+            year in 1971i32..2100,
+            block in day_aligned_block_minutes(),
+        ) {
+            let new_year = midnight(year, 1, 1);
+            let before = reference_id("instance", new_year - Duration::seconds(1), block);
+            let after = reference_id("instance", new_year, block);
+
+            let expected_prefix = format!("{}-12-31T", year - 1);
+            prop_assert!(before.contains(&expected_prefix));
+            prop_assert_eq!(after, format!("threshold_instance_{year:04}-01-01T00:00"));
+        }
+
+        #[test]
+        fn test_different_types_never_collide(
+            first in incident_type(),
+            second in incident_type(),
+            first_at in instant(),
+            second_at in instant(),
+            first_block in block_minutes(),
+            second_block in block_minutes(),
+        ) {
+            prop_assume!(first != second);
+            prop_assert_ne!(
+                reference_id(&first, first_at, first_block),
+                reference_id(&second, second_at, second_block)
+            );
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! Alert delivery safeguard
+//!
+//! Caps the number of alert deliveries per rolling hour so that a misconfigured
+//! threshold cannot flood every registered guild and user. When the cap is hit,
+//! further deliveries are skipped and the bot owner is notified once per episode.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serenity::all::Http;
+use tracing::{error, info, warn};
+
+use crate::bot::owner;
+use crate::entity::bot_config;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Config key for the hourly delivery cap
+pub const MAX_RECIPIENTS_KEY: &str = "max_alert_recipients_per_hour";
+
+/// Default hourly delivery cap (used if config missing)
+pub const DEFAULT_MAX_RECIPIENTS_PER_HOUR: u64 = 5000;
+
+/// Number of one-minute buckets in the sliding window (1 hour)
+const BUCKET_COUNT: usize = 60;
+
+// =============================================================================
+// Sliding Counter
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    /// Minute index (unix seconds / 60) this bucket belongs to
+    minute: i64,
+    count: u64,
+}
+
+/// Active suppression episode
+#[derive(Debug, Clone, Copy)]
+pub struct Suppression {
+    /// When the cap was first hit in this episode
+    pub since: DateTime<Utc>,
+    /// Deliveries skipped since the episode started
+    pub skipped: u64,
+}
+
+/// Outcome of a delivery check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// Delivery may proceed
+    Allow,
+    /// Delivery must be skipped; `new_episode` is true for the first skip of an episode
+    Suppress { new_episode: bool },
+}
+
+/// Point-in-time view of the limiter for display
+#[derive(Debug, Clone, Copy)]
+pub struct LimiterSnapshot {
+    pub delivered_last_hour: u64,
+    pub suppression: Option<Suppression>,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    buckets: [Bucket; BUCKET_COUNT],
+    suppression: Option<Suppression>,
+}
+
+/// Sliding one-hour delivery counter backed by a ring of one-minute buckets
+#[derive(Debug)]
+pub struct DeliveryLimiter {
+    state: Mutex<LimiterState>,
+}
+
+impl Default for DeliveryLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeliveryLimiter {
+    /// Create an empty limiter
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(LimiterState {
+                buckets: [Bucket::default(); BUCKET_COUNT],
+                suppression: None,
+            }),
+        }
+    }
+
+    /// Record one successful delivery at `now`
+    pub fn record(&self, now: DateTime<Utc>) {
+        let minute = minute_index(now);
+        let slot = minute.rem_euclid(BUCKET_COUNT as i64) as usize;
+
+        let mut state = self.state.lock().expect("limiter mutex poisoned");
+        let bucket = &mut state.buckets[slot];
+        if bucket.minute != minute {
+            // Slot belongs to an older hour - recycle it
+            *bucket = Bucket { minute, count: 0 };
+        }
+        bucket.count += 1;
+    }
+
+    /// Decide whether another delivery is allowed under `max_per_hour`
+    ///
+    /// Starts a suppression episode when the cap is reached and ends it once the
+    /// rolling count drops below the cap again.
+    pub fn check(&self, now: DateTime<Utc>, max_per_hour: u64) -> Decision {
+        let mut state = self.state.lock().expect("limiter mutex poisoned");
+        let delivered = count_in_window(&state.buckets, minute_index(now));
+
+        if delivered < max_per_hour {
+            state.suppression = None;
+            return Decision::Allow;
+        }
+
+        match state.suppression.as_mut() {
+            Some(suppression) => {
+                suppression.skipped += 1;
+                Decision::Suppress { new_episode: false }
+            }
+            None => {
+                state.suppression = Some(Suppression {
+                    since: now,
+                    skipped: 1,
+                });
+                Decision::Suppress { new_episode: true }
+            }
+        }
+    }
+
+    /// Snapshot the current counter and suppression state
+    ///
+    /// A suppression episode is reported as over once the rolling count has
+    /// dropped below `max_per_hour`, even if no delivery was attempted since.
+    pub fn snapshot(&self, now: DateTime<Utc>, max_per_hour: u64) -> LimiterSnapshot {
+        let state = self.state.lock().expect("limiter mutex poisoned");
+        let delivered_last_hour = count_in_window(&state.buckets, minute_index(now));
+        let suppression = state
+            .suppression
+            .filter(|_| delivered_last_hour >= max_per_hour);
+
+        LimiterSnapshot {
+            delivered_last_hour,
+            suppression,
+        }
+    }
+}
+
+fn minute_index(now: DateTime<Utc>) -> i64 {
+    now.timestamp().div_euclid(60)
+}
+
+/// Sum deliveries whose bucket falls within the last `BUCKET_COUNT` minutes
+fn count_in_window(buckets: &[Bucket; BUCKET_COUNT], current_minute: i64) -> u64 {
+    buckets
+        .iter()
+        .filter(|b| {
+            let age = current_minute - b.minute;
+            (0..BUCKET_COUNT as i64).contains(&age)
+        })
+        .map(|b| b.count)
+        .sum()
+}
+
+// =============================================================================
+// Safeguard (limiter + configured cap)
+// =============================================================================
+
+/// Load the hourly delivery cap from `bot_config`, falls back to default if missing
+pub async fn load_max_per_hour(db: &DatabaseConnection) -> u64 {
+    bot_config::Entity::find_by_id(MAX_RECIPIENTS_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_RECIPIENTS_PER_HOUR)
+}
+
+/// Per-fan-out handle combining the shared limiter with the configured cap
+pub struct AlertSafeguard {
+    limiter: Arc<DeliveryLimiter>,
+    max_per_hour: u64,
+}
+
+impl AlertSafeguard {
+    pub fn new(limiter: Arc<DeliveryLimiter>, max_per_hour: u64) -> Self {
+        Self {
+            limiter,
+            max_per_hour,
+        }
+    }
+
+    /// Check whether a delivery may proceed
+    ///
+    /// On the first skip of a suppression episode, logs a warning and DMs the
+    /// bot owner. Later skips in the same episode are silent.
+    pub async fn try_acquire(&self, http: &Http) -> bool {
+        let now = Utc::now();
+        match self.limiter.check(now, self.max_per_hour) {
+            Decision::Allow => true,
+            Decision::Suppress { new_episode: false } => false,
+            Decision::Suppress { new_episode: true } => {
+                warn!(
+                    max_per_hour = self.max_per_hour,
+                    "Alert delivery cap reached, suppressing further alerts this hour"
+                );
+                notify_owner(http, self.max_per_hour, now).await;
+                false
+            }
+        }
+    }
+
+    /// Record a successful delivery
+    pub fn record(&self) {
+        self.limiter.record(Utc::now());
+    }
+}
+
+/// DM the bot owner about a new suppression episode
+async fn notify_owner(http: &Http, max_per_hour: u64, since: DateTime<Utc>) {
+    let content = format!(
+        "**Alert delivery suppressed**\n\
+         {} alert deliveries were sent in the last hour, reaching the \
+         `{}` limit. Further alerts are skipped until the rolling count drops \
+         below the limit.\n\
+         Suppressed since <t:{}:F>. Check `report_threshold` and `/admin show`.",
+        max_per_hour,
+        MAX_RECIPIENTS_KEY,
+        since.timestamp()
+    );
+
+    match owner::dm_owner(http, content).await {
+        Ok(Some(owner_id)) => info!(owner_id = %owner_id, "Sent alert suppression notice to owner"),
+        Ok(None) => warn!("Application has no owner, skipping suppression notice"),
+        Err(e) => error!(error = %e, "Failed to DM owner about alert suppression"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap()
+    }
+
+    /// Record `count` deliveries at `at`, checking each against `max`
+    fn deliver(limiter: &DeliveryLimiter, at: DateTime<Utc>, count: u64, max: u64) {
+        for _ in 0..count {
+            assert_eq!(limiter.check(at, max), Decision::Allow);
+            limiter.record(at);
+        }
+    }
+
+    #[test]
+    fn test_allows_up_to_limit() {
+        let limiter = DeliveryLimiter::new();
+        deliver(&limiter, start(), 3, 3);
+
+        let snapshot = limiter.snapshot(start(), 3);
+        assert_eq!(snapshot.delivered_last_hour, 3);
+        assert!(snapshot.suppression.is_none());
+    }
+
+    #[test]
+    fn test_rejects_at_limit() {
+        let limiter = DeliveryLimiter::new();
+        deliver(&limiter, start(), 3, 3);
+
+        assert_eq!(
+            limiter.check(start(), 3),
+            Decision::Suppress { new_episode: true }
+        );
+        assert_eq!(
+            limiter.check(start() + Duration::minutes(1), 3),
+            Decision::Suppress { new_episode: false }
+        );
+
+        let suppression = limiter.snapshot(start(), 3).suppression.unwrap();
+        assert_eq!(suppression.since, start());
+        assert_eq!(suppression.skipped, 2);
+    }
+
+    #[test]
+    fn test_counts_deliveries_across_minutes() {
+        let limiter = DeliveryLimiter::new();
+        deliver(&limiter, start(), 2, 3);
+        deliver(&limiter, start() + Duration::minutes(30), 1, 3);
+
+        assert_eq!(
+            limiter.check(start() + Duration::minutes(59), 3),
+            Decision::Suppress { new_episode: true }
+        );
+    }
+
+    #[test]
+    fn test_window_expiry() {
+        let limiter = DeliveryLimiter::new();
+        deliver(&limiter, start(), 2, 3);
+        deliver(&limiter, start() + Duration::minutes(30), 1, 3);
+        assert!(matches!(
+            limiter.check(start() + Duration::minutes(59), 3),
+            Decision::Suppress { .. }
+        ));
+
+        // The first two deliveries leave the window after an hour
+        let later = start() + Duration::minutes(60);
+        assert_eq!(limiter.snapshot(later, 3).delivered_last_hour, 1);
+        assert!(limiter.snapshot(later, 3).suppression.is_none());
+        assert_eq!(limiter.check(later, 3), Decision::Allow);
+
+        // Everything has expired after another half hour
+        let much_later = start() + Duration::minutes(90);
+        assert_eq!(limiter.snapshot(much_later, 3).delivered_last_hour, 0);
+    }
+
+    #[test]
+    fn test_recycled_bucket_starts_empty() {
+        let limiter = DeliveryLimiter::new();
+        deliver(&limiter, start(), 3, 10);
+
+        // Same ring slot, two hours later
+        let later = start() + Duration::hours(2);
+        limiter.record(later);
+
+        assert_eq!(limiter.snapshot(later, 10).delivered_last_hour, 1);
+    }
+
+    #[test]
+    fn test_new_episode_after_recovery() {
+        let limiter = DeliveryLimiter::new();
+        deliver(&limiter, start(), 1, 1);
+        assert_eq!(
+            limiter.check(start(), 1),
+            Decision::Suppress { new_episode: true }
+        );
+
+        let later = start() + Duration::hours(1);
+        deliver(&limiter, later, 1, 1);
+        assert_eq!(
+            limiter.check(later, 1),
+            Decision::Suppress { new_episode: true }
+        );
+    }
+}
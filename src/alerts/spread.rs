@@ -0,0 +1,119 @@
+//! Community spread of the reports behind a threshold alert
+//!
+//! Admins want to know whether "12 users reported" means one busy Discord or
+//! a wider problem. Only the number of communities leaves this module; guild
+//! IDs are never shown. Each guild is one community, and all user-install
+//! reports (no guild) together count as one more.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use rust_i18n::t;
+use sea_orm::DatabaseConnection;
+use tracing::warn;
+
+use crate::repository::ReportRepository;
+
+/// Number of communities among report sources
+///
+/// `guild_ids` has one entry per report or per distinct source; `None` is a
+/// user-install report. Repeated guilds count once.
+pub fn community_count<'a, I>(guild_ids: I) -> u64
+where
+    I: IntoIterator<Item = Option<&'a str>>,
+{
+    let mut guilds = HashSet::new();
+    let mut user_installs = false;
+    for guild_id in guild_ids {
+        match guild_id {
+            Some(id) => {
+                guilds.insert(id);
+            }
+            None => user_installs = true,
+        }
+    }
+    guilds.len() as u64 + u64::from(user_installs)
+}
+
+/// Alert description line for the community count
+///
+/// A single community reads differently from several; no line when the
+/// count is unknown or zero.
+pub fn communities_line(communities: Option<u64>, locale: &str) -> Option<String> {
+    match communities? {
+        0 => None,
+        1 => Some(t!("embeds.alerts.threshold.communities_one", locale = locale).to_string()),
+        n => Some(
+            t!(
+                "embeds.alerts.threshold.communities_many",
+                n = n,
+                locale = locale
+            )
+            .to_string(),
+        ),
+    }
+}
+
+/// Communities among active reports of an incident type in the last `interval` minutes
+///
+/// `None` if the lookup fails; the alert is sent without the spread line.
+pub async fn load_communities(
+    db: &DatabaseConnection,
+    incident_type: &str,
+    interval: i64,
+) -> Option<u64> {
+    let since = Utc::now() - Duration::minutes(interval);
+    match ReportRepository::new(Arc::new(db.clone()))
+        .active_guilds_since(incident_type, since)
+        .await
+    {
+        Ok(guild_ids) => Some(community_count(guild_ids.iter().map(Option::as_deref))),
+        Err(e) => {
+            warn!(error = %e, incident_type, "Failed to count reporting communities");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_guilds_count_once() {
+        let ids = [Some("1"), Some("2"), Some("1"), Some("2"), Some("3")];
+
+        assert_eq!(community_count(ids), 3);
+    }
+
+    #[test]
+    fn test_user_installs_count_as_one_community() {
+        assert_eq!(community_count([None, None, None]), 1);
+        assert_eq!(community_count([Some("1"), None, Some("1"), None]), 2);
+    }
+
+    #[test]
+    fn test_no_sources() {
+        assert_eq!(community_count(std::iter::empty()), 0);
+    }
+
+    #[test]
+    fn test_single_community_phrased_differently() {
+        let one = communities_line(Some(1), "en").unwrap();
+        let many = communities_line(Some(2), "en").unwrap();
+
+        assert_eq!(one, "All reports came from a single community.");
+        assert_eq!(many, "Reported across **2** communities.");
+        assert_eq!(
+            communities_line(Some(community_count([Some("1"), Some("1")])), "en").as_deref(),
+            Some(one.as_str())
+        );
+    }
+
+    #[test]
+    fn test_no_line_without_communities() {
+        assert_eq!(communities_line(None, "en"), None);
+        assert_eq!(communities_line(Some(0), "en"), None);
+    }
+}
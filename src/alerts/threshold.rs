@@ -1,25 +1,73 @@
 //! Threshold-based alert system
 //!
 //! Monitors user reports and sends alerts when the count exceeds the configured threshold.
+//! The content of each fan-out is stored in `alert_payloads`, so a guild that
+//! registers during an ongoing alert can be sent it right away. Delivery to
+//! each recipient goes through `delivery`, after the guild's own threshold
+//! and silences are checked here.
 
-use chrono::{Duration, Utc};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use futures::{StreamExt, stream};
 use rust_i18n::t;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
-};
-use serenity::all::{ChannelId, Colour, Context, CreateEmbed, CreateEmbedFooter, CreateMessage};
-use tracing::{error, info, warn};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serenity::all::{Colour, Context, CreateAttachment, CreateEmbed, CreateEmbedFooter, GuildId};
+use tracing::{debug, error, info, warn};
 
 use crate::commands::shared::{colors, incident_types};
-use crate::entity::{bot_config, guild_configs, sent_alerts, user_configs, user_reports};
-use crate::i18n::{resolve_guild_locale_by_id, resolve_user_locale_by_id};
+use crate::entity::{bot_config, guild_configs, user_reports, vrc_builds};
+use crate::i18n;
+use crate::repository::reports::COUNTING_STATUSES;
+use crate::repository::{
+    AlertFilterRepository, AlertPayload, GuildConfigRepository, PayloadRepository,
+    ReportRepository, SilenceRepository, config,
+};
+use crate::state::AppStateKey;
+
+use super::build_note;
+use super::context_chart::{self, CHART_FILENAME, ChartCache, ChartPng};
+use super::delivery::{
+    self, AlertNotice, Delivery, FAN_OUT_CONCURRENCY, FanOutTally, Notifier, get_registered_guilds,
+    get_registered_users,
+};
+use super::recent::{self, RecentLine, RecentReports};
+use super::reference;
+use super::safeguard::AlertSafeguard;
+use super::spread;
+use super::webhook::{self, WebhookAlert, WebhookPayload};
 
 // =============================================================================
 // Constants
 // =============================================================================
 
-/// Maximum number of recent report timestamps to show in alert
-const MAX_RECENT_REPORTS: u64 = 5;
+/// `sent_alerts.alert_type` for threshold alerts
+pub(crate) const ALERT_TYPE_THRESHOLD: &str = "threshold";
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Threshold alert details shared by every recipient of one fan-out
+struct ThresholdAlert<'a> {
+    incident_type: &'a str,
+    count: i64,
+    /// Global `report_threshold`, used by guilds without their own
+    threshold: i64,
+    interval: i64,
+    /// Communities the reports came from (guilds, plus one for user installs)
+    communities: Option<u64>,
+    recent_reports: &'a RecentReports,
+    reference_id: &'a str,
+    /// Context chart PNG, attached as `context_chart::CHART_FILENAME`
+    chart: Option<ChartPng>,
+    /// VRChat build first seen shortly before this alert
+    new_build: Option<vrc_builds::Model>,
+    /// "Learn more" documentation link for the incident type
+    link: Option<String>,
+    /// Time of the original fan-out, when replaying to a newly registered guild
+    replay_of: Option<DateTime<Utc>>,
+}
 
 // =============================================================================
 // Public API
@@ -28,8 +76,16 @@ const MAX_RECENT_REPORTS: u64 = 5;
 /// Check if threshold is reached and send alerts to all registered recipients
 ///
 /// Called after a new report is inserted. Checks the global report count
-/// for the given incident type and sends alerts if threshold is exceeded.
+/// for the given incident type and sends alerts to the guilds whose
+/// threshold (their own, or the global one) it reaches; DM users follow the
+/// global threshold.
 pub async fn check_and_send_alerts(ctx: &Context, db: &DatabaseConnection, incident_type: &str) {
+    // No fan-out while the bot is in maintenance mode
+    if is_maintenance_mode(ctx).await {
+        info!(incident_type, "Maintenance mode, skipping alert check");
+        return;
+    }
+
     // Get config values (required - seeded in migration)
     let Some(threshold) = get_config_value(db, "report_threshold").await else {
         error!("Missing required config: report_threshold");
@@ -50,54 +106,264 @@ pub async fn check_and_send_alerts(ctx: &Context, db: &DatabaseConnection, incid
         "Checking alert threshold"
     );
 
-    if count < threshold {
+    // A guild's own threshold (`/config threshold`) replaces the global one
+    // for that guild, so the fan-out starts at the lowest threshold in use
+    let guilds: Vec<_> = get_registered_guilds(db, ALERT_TYPE_THRESHOLD)
+        .await
+        .into_iter()
+        .filter(|guild| config::is_subscribed(guild, incident_type))
+        .collect();
+    let global_due = count >= threshold;
+    if !global_due
+        && !guilds
+            .iter()
+            .any(|guild| config::meets_guild_threshold(guild, count, threshold))
+    {
         return;
     }
 
     // Threshold reached - get recent report timestamps for the alert message
-    let recent_reports = get_recent_reports(db, incident_type, interval, MAX_RECENT_REPORTS).await;
-
-    // Generate reference ID for deduplication (15-minute blocks)
-    let reference_id = generate_reference_id(incident_type);
-
-    // Get all registered guilds
-    let guilds = get_registered_guilds(db).await;
-    for guild in guilds {
-        send_guild_alert(
-            ctx,
-            db,
-            &guild,
-            incident_type,
-            count,
-            interval,
-            &recent_reports,
-            &reference_id,
-        )
-        .await;
+    let max_recent = recent::load_max_recent_reports(db).await;
+    let recent_reports = get_recent_reports(db, incident_type, interval, max_recent).await;
+
+    // Reference ID for deduplication (one alert per type per block)
+    let expiry = reference::get_alert_expiry_minutes(db).await;
+    let reference_id = reference::current_reference_id(incident_type, expiry);
+
+    // Context chart for types with a matching metric (rendered once per block)
+    let chart = match load_chart_cache(ctx).await {
+        Some(cache) => {
+            context_chart::chart_for_alert(&cache, db, incident_type, &reference_id).await
+        }
+        None => None,
+    };
+
+    let alert = ThresholdAlert {
+        incident_type,
+        count,
+        threshold,
+        interval,
+        communities: spread::load_communities(db, incident_type, interval).await,
+        recent_reports: &recent_reports,
+        reference_id: &reference_id,
+        chart,
+        new_build: build_note::recent_build(db).await,
+        link: incident_types::link(ctx, db, incident_type).await,
+        replay_of: None,
+    };
+
+    // Keep the content for guilds that register while this block is ongoing
+    let payload = AlertPayload {
+        reference_id: reference_id.clone(),
+        incident_type: incident_type.to_string(),
+        report_count: count,
+        interval_minutes: interval,
+        recent_reports: recent_reports.timestamps.clone(),
+        recent_total: recent_reports.total,
+        communities: alert.communities,
+        updated_at: Utc::now(),
+    };
+    if let Err(e) = PayloadRepository::new(Arc::new(db.clone()))
+        .save(&payload)
+        .await
+    {
+        warn!(error = %e, reference_id, "Failed to store alert payload");
     }
 
-    // Get all registered users (for DM alerts)
-    let users = get_registered_users(db).await;
-    for user in users {
-        send_user_alert(
-            ctx,
-            db,
-            &user,
-            incident_type,
-            count,
-            interval,
-            &recent_reports,
-            &reference_id,
-        )
-        .await;
+    // The reports behind this alert are now counted; they keep counting until
+    // their window passes
+    match ReportRepository::new(Arc::new(db.clone()))
+        .mark_counted(incident_type, Utc::now() - Duration::minutes(interval))
+        .await
+    {
+        Ok(marked) => debug!(incident_type, marked, "Marked reports as counted"),
+        Err(e) => warn!(error = %e, incident_type, "Failed to mark reports as counted"),
+    }
+
+    let Some(notifier) = Notifier::from_context(ctx).await else {
+        error!("AppState not found, skipping alert delivery");
+        return;
+    };
+    // Delivery cap safeguard (protects against alert storms from misconfiguration)
+    let safeguard = notifier.safeguard(db).await;
+
+    // Guilds subscribed to this incident type; each checks its own threshold
+    let mut tally = FanOutTally::default();
+    // Futures are built up front: a closure in the stream type trips the
+    // `Send` check of the spawned task that runs this check
+    let sends: Vec<_> = guilds
+        .iter()
+        .map(|guild| send_guild_alert(&notifier, db, guild, &alert, &safeguard))
+        .collect();
+    let mut deliveries = stream::iter(sends).buffer_unordered(FAN_OUT_CONCURRENCY);
+    while let Some(delivery) = deliveries.next().await {
+        tally.add(delivery);
+    }
+
+    // Registered users (for DM alerts) always follow the global threshold
+    if global_due {
+        let users = get_registered_users(db, ALERT_TYPE_THRESHOLD).await;
+        let sends: Vec<_> = users
+            .iter()
+            .map(|user| delivery::send_to_user(&notifier, db, user, &alert, &safeguard))
+            .collect();
+        let mut deliveries = stream::iter(sends).buffer_unordered(FAN_OUT_CONCURRENCY);
+        while let Some(delivery) = deliveries.next().await {
+            tally.add(delivery);
+        }
     }
+
+    info!(
+        incident_type,
+        reference_id,
+        sent = tally.sent,
+        duplicate = tally.duplicate,
+        skipped = tally.skipped,
+        failed = tally.failed,
+        "Threshold alert fan-out finished"
+    );
+}
+
+/// Send the latest ongoing alert to a guild that just registered
+///
+/// Looks for a fan-out within the last dedup block. The delivery goes through
+/// the normal guild path, so it is recorded in `sent_alerts` under the
+/// original reference ID and a guild that already received it gets nothing.
+pub async fn replay_to_new_guild(ctx: &Context, db: &DatabaseConnection, guild_id: GuildId) {
+    if is_maintenance_mode(ctx).await {
+        return;
+    }
+
+    let Some(replay) = pending_replay(db, guild_id).await else {
+        return;
+    };
+    let Some(notifier) = Notifier::from_context(ctx).await else {
+        error!("AppState not found, skipping alert replay");
+        return;
+    };
+
+    let incident_type = replay.payload.incident_type.as_str();
+    let chart = match load_chart_cache(ctx).await {
+        Some(cache) => {
+            context_chart::chart_for_alert(&cache, db, incident_type, &replay.payload.reference_id)
+                .await
+        }
+        None => None,
+    };
+    let link = incident_types::link(ctx, db, incident_type).await;
+    send_replay(&notifier, db, &replay, chart, link).await;
+}
+
+/// Ongoing alert to replay to a newly registered guild
+struct Replay {
+    guild: guild_configs::Model,
+    payload: AlertPayload,
+    /// Global `report_threshold`
+    threshold: i64,
+}
+
+/// The latest fan-out within the dedup block, if the guild takes it
+async fn pending_replay(db: &DatabaseConnection, guild_id: GuildId) -> Option<Replay> {
+    let expiry = reference::get_alert_expiry_minutes(db).await;
+    let since = Utc::now() - Duration::minutes(expiry);
+    let payload = match PayloadRepository::new(Arc::new(db.clone()))
+        .latest_since(since)
+        .await
+    {
+        Ok(payload) => payload?,
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to load alert payload for replay");
+            return None;
+        }
+    };
+
+    let guild = GuildConfigRepository::new(Arc::new(db.clone()))
+        .get(guild_id)
+        .await?;
+    if !config::is_subscribed(&guild, &payload.incident_type) {
+        return None;
+    }
+    match AlertFilterRepository::new(Arc::new(db.clone()))
+        .disabled_types(guild_id)
+        .await
+    {
+        Ok(disabled) if disabled.iter().any(|t| t == ALERT_TYPE_THRESHOLD) => return None,
+        Ok(_) => {}
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to load alert filters for replay");
+            return None;
+        }
+    }
+    let Some(threshold) = get_config_value(db, "report_threshold").await else {
+        error!("Missing required config: report_threshold");
+        return None;
+    };
+
+    Some(Replay {
+        guild,
+        payload,
+        threshold,
+    })
+}
+
+/// Deliver a replay through the normal guild path
+async fn send_replay(
+    notifier: &Notifier,
+    db: &DatabaseConnection,
+    replay: &Replay,
+    chart: Option<ChartPng>,
+    link: Option<String>,
+) -> Delivery {
+    let payload = &replay.payload;
+    let safeguard = notifier.safeguard(db).await;
+    let recent_reports = RecentReports {
+        timestamps: payload.recent_reports.clone(),
+        total: payload.recent_total,
+    };
+
+    let alert = ThresholdAlert {
+        incident_type: &payload.incident_type,
+        count: payload.report_count,
+        threshold: replay.threshold,
+        interval: payload.interval_minutes,
+        communities: payload.communities,
+        recent_reports: &recent_reports,
+        reference_id: &payload.reference_id,
+        chart,
+        new_build: build_note::recent_build(db).await,
+        link,
+        replay_of: Some(payload.updated_at),
+    };
+
+    info!(
+        guild_id = %replay.guild.guild_id,
+        reference_id = %payload.reference_id,
+        "Replaying ongoing alert to newly registered guild"
+    );
+    send_guild_alert(notifier, db, &replay.guild, &alert, &safeguard).await
+}
+
+/// Whether the bot is in maintenance mode (cached flag in AppState)
+async fn is_maintenance_mode(ctx: &Context) -> bool {
+    let data = ctx.data.read().await;
+    match data.get::<AppStateKey>() {
+        Some(state) => state.read().await.maintenance.is_enabled(),
+        None => false,
+    }
+}
+
+/// Shared context chart cache from AppState
+async fn load_chart_cache(ctx: &Context) -> Option<Arc<ChartCache>> {
+    let data = ctx.data.read().await;
+    let state = data.get::<AppStateKey>()?;
+    Some(state.read().await.alert_charts.clone())
 }
 
 // =============================================================================
 // Database Queries
 // =============================================================================
 
-async fn get_config_value(db: &DatabaseConnection, key: &str) -> Option<i64> {
+pub(crate) async fn get_config_value(db: &DatabaseConnection, key: &str) -> Option<i64> {
     bot_config::Entity::find_by_id(key)
         .one(db)
         .await
@@ -108,15 +374,25 @@ async fn get_config_value(db: &DatabaseConnection, key: &str) -> Option<i64> {
 
 /// Count unique users who reported this incident type within the interval
 async fn count_active_reports(db: &DatabaseConnection, incident_type: &str, interval: i64) -> i64 {
-    use sea_orm::{QuerySelect, sea_query::Expr};
+    let now = Utc::now();
+    count_reporters_between(db, incident_type, now - Duration::minutes(interval), now).await
+}
 
-    let cutoff = Utc::now() - Duration::minutes(interval);
+/// Count unique users who reported this incident type in `(from, until]`
+async fn count_reporters_between(
+    db: &DatabaseConnection,
+    incident_type: &str,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> i64 {
+    use sea_orm::{QuerySelect, sea_query::Expr};
 
     // Count distinct users, not total reports
     let result = user_reports::Entity::find()
         .filter(user_reports::Column::IncidentType.eq(incident_type))
-        .filter(user_reports::Column::Status.eq("active"))
-        .filter(user_reports::Column::CreatedAt.gt(cutoff))
+        .filter(user_reports::Column::Status.is_in(COUNTING_STATUSES))
+        .filter(user_reports::Column::CreatedAt.gt(from))
+        .filter(user_reports::Column::CreatedAt.lte(until))
         .select_only()
         .column_as(
             Expr::col(user_reports::Column::UserId).count_distinct(),
@@ -129,20 +405,64 @@ async fn count_active_reports(db: &DatabaseConnection, incident_type: &str, inte
     result.ok().flatten().unwrap_or(0)
 }
 
+/// Direction of reports for an incident type within the interval window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trending {
+    /// More users reported in the second half of the window than the first
+    Up,
+    /// Fewer users reported in the second half of the window
+    Down,
+    /// Both halves had the same number of reporters
+    Stable,
+}
+
+impl Trending {
+    /// Arrow shown next to a report count
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Self::Up => "↑",
+            Self::Down => "↓",
+            Self::Stable => "→",
+        }
+    }
+}
+
+/// Compare unique reporters in the older and newer half of the last `interval` minutes
+pub async fn trend_direction(
+    db: &DatabaseConnection,
+    incident_type: &str,
+    interval: i64,
+) -> Trending {
+    let now = Utc::now();
+    let start = now - Duration::minutes(interval);
+    let midpoint = start + (now - start) / 2;
+
+    let older = count_reporters_between(db, incident_type, start, midpoint).await;
+    let newer = count_reporters_between(db, incident_type, midpoint, now).await;
+    match newer.cmp(&older) {
+        std::cmp::Ordering::Greater => Trending::Up,
+        std::cmp::Ordering::Less => Trending::Down,
+        std::cmp::Ordering::Equal => Trending::Stable,
+    }
+}
+
+/// Newest `limit` active reports in the window (oldest first) and the window total
 async fn get_recent_reports(
     db: &DatabaseConnection,
     incident_type: &str,
     interval: i64,
     limit: u64,
-) -> Vec<chrono::DateTime<Utc>> {
-    use sea_orm::QuerySelect;
+) -> RecentReports {
+    use sea_orm::{PaginatorTrait, QuerySelect};
 
     let cutoff = Utc::now() - Duration::minutes(interval);
-
-    let reports = user_reports::Entity::find()
+    let query = user_reports::Entity::find()
         .filter(user_reports::Column::IncidentType.eq(incident_type))
-        .filter(user_reports::Column::Status.eq("active"))
-        .filter(user_reports::Column::CreatedAt.gt(cutoff))
+        .filter(user_reports::Column::Status.is_in(COUNTING_STATUSES))
+        .filter(user_reports::Column::CreatedAt.gt(cutoff));
+
+    let reports = query
+        .clone()
         .order_by_desc(user_reports::Column::CreatedAt)
         .limit(limit)
         .all(db)
@@ -152,214 +472,126 @@ async fn get_recent_reports(
             vec![]
         });
 
-    reports.into_iter().map(|r| r.created_at).collect()
-}
+    let mut timestamps: Vec<_> = reports.into_iter().map(|r| r.created_at).collect();
+    timestamps.reverse();
 
-async fn get_registered_guilds(db: &DatabaseConnection) -> Vec<guild_configs::Model> {
-    guild_configs::Entity::find()
-        .filter(guild_configs::Column::Enabled.eq(true))
-        .filter(guild_configs::Column::ChannelId.is_not_null())
-        .all(db)
+    // Fall back to the listed count so a failed count never claims fewer reports
+    let total = query
+        .count(db)
         .await
         .unwrap_or_else(|e| {
-            error!(error = %e, "Failed to fetch registered guilds for alerts");
-            vec![]
+            error!(error = %e, "Failed to count recent reports");
+            0
         })
-}
+        .max(timestamps.len() as u64);
 
-async fn get_registered_users(db: &DatabaseConnection) -> Vec<user_configs::Model> {
-    user_configs::Entity::find()
-        .filter(user_configs::Column::Enabled.eq(true))
-        .all(db)
-        .await
-        .unwrap_or_else(|e| {
-            error!(error = %e, "Failed to fetch registered users for alerts");
-            vec![]
-        })
-}
-
-/// Result of attempting to record a sent alert
-enum RecordAlertResult {
-    /// Alert was recorded, contains the record ID for potential rollback
-    Recorded(i64),
-    /// Alert was already sent (duplicate)
-    AlreadySent,
-    /// Database error occurred
-    Error,
+    RecentReports { timestamps, total }
 }
 
-/// Try to record a sent alert. Returns the record ID if successful, or indicates duplicate/error.
-/// Uses INSERT with unique constraint to prevent race conditions (TOCTOU).
-async fn try_record_sent_alert(
+/// Check for an active silence; delivery proceeds if the lookup fails
+async fn is_guild_silenced(
     db: &DatabaseConnection,
-    guild_id: Option<String>,
-    user_id: Option<String>,
-    reference_id: &str,
-) -> RecordAlertResult {
-    let now = Utc::now();
-    let alert = sent_alerts::ActiveModel {
-        guild_id: Set(guild_id),
-        user_id: Set(user_id),
-        alert_type: Set("threshold".to_string()),
-        reference_id: Set(reference_id.to_string()),
-        notified_at: Set(now),
-        created_at: Set(now),
-        ..Default::default()
+    guild: &guild_configs::Model,
+    incident_type: &str,
+) -> bool {
+    let Ok(guild_id) = guild.guild_id.parse::<u64>() else {
+        return false;
     };
 
-    match alert.insert(db).await {
-        Ok(record) => RecordAlertResult::Recorded(record.id), // Successfully inserted
+    let repo = SilenceRepository::new(Arc::new(db.clone()));
+    match repo
+        .is_silenced(GuildId::new(guild_id), incident_type, Utc::now())
+        .await
+    {
+        Ok(true) => {
+            info!(
+                guild_id = %guild.guild_id,
+                incident_type,
+                "Guild has an active silence, skipping alert"
+            );
+            true
+        }
+        Ok(false) => false,
         Err(e) => {
-            // Check if it's a unique constraint violation (already sent)
-            let err_str = e.to_string().to_lowercase();
-            if err_str.contains("unique") || err_str.contains("duplicate") {
-                RecordAlertResult::AlreadySent // Dedup working correctly
-            } else {
-                error!(error = %e, "Failed to record sent alert");
-                RecordAlertResult::Error // Don't send alert if we can't record it
-            }
+            error!(guild_id = %guild.guild_id, error = %e, "Failed to check alert silences");
+            false
         }
     }
 }
 
-/// Delete a sent alert record (used for rollback on send failure)
-async fn delete_sent_alert(db: &DatabaseConnection, record_id: i64) {
-    if let Err(e) = sent_alerts::Entity::delete_by_id(record_id).exec(db).await {
-        error!(record_id = record_id, error = %e, "Failed to delete sent_alert record for retry");
-    }
-}
-
 // =============================================================================
 // Alert Sending
 // =============================================================================
 
+/// Deliver to a guild once its own checks pass; the rest of the path is in
+/// `delivery`
 async fn send_guild_alert(
-    ctx: &Context,
+    notifier: &Notifier,
     db: &DatabaseConnection,
     guild: &guild_configs::Model,
-    incident_type: &str,
-    count: i64,
-    interval: i64,
-    recent_reports: &[chrono::DateTime<Utc>],
-    reference_id: &str,
-) {
-    // Get channel ID
-    let Some(channel_id_str) = &guild.channel_id else {
-        return;
-    };
-
-    let Ok(channel_id) = channel_id_str.parse::<u64>() else {
-        warn!(guild_id = %guild.guild_id, "Invalid channel ID");
-        return;
-    };
-
-    // Try to record first (atomic deduplication via unique constraint)
-    // If this fails due to duplicate, we skip sending
-    let record_id =
-        match try_record_sent_alert(db, Some(guild.guild_id.clone()), None, reference_id).await {
-            RecordAlertResult::Recorded(id) => id,
-            RecordAlertResult::AlreadySent => return, // Already sent - skip
-            RecordAlertResult::Error => return,       // Can't record - don't send
-        };
-
-    // Resolve locale for this guild
-    let locale = resolve_guild_locale_by_id(db, &guild.guild_id).await;
-
-    // Build and send embed
-    let embed = build_alert_embed(incident_type, count, interval, recent_reports, &locale);
-    let message = CreateMessage::new().embed(embed);
+    alert: &ThresholdAlert<'_>,
+    safeguard: &AlertSafeguard,
+) -> Delivery {
+    // Skip delivery until the guild's threshold (own or global) is reached
+    if !config::meets_guild_threshold(guild, alert.count, alert.threshold) {
+        debug!(
+            guild_id = %guild.guild_id,
+            count = alert.count,
+            threshold = guild.alert_threshold.unwrap_or(alert.threshold),
+            "Below guild threshold, skipping"
+        );
+        return Delivery::Skipped;
+    }
 
-    let channel = ChannelId::new(channel_id);
-    match channel.send_message(&ctx.http, message).await {
-        Ok(_) => {
-            info!(
-                guild_id = %guild.guild_id,
-                incident_type = incident_type,
-                count = count,
-                "Sent threshold alert to guild"
-            );
-        }
-        Err(e) => {
-            error!(
-                guild_id = %guild.guild_id,
-                error = %e,
-                "Failed to send alert to guild channel, will retry on next trigger"
-            );
-            // Delete the record so we can retry on the next report
-            delete_sent_alert(db, record_id).await;
-        }
+    // Skip delivery while an admin-scheduled silence covers this incident type
+    if is_guild_silenced(db, guild, alert.incident_type).await {
+        return Delivery::Skipped;
     }
+
+    delivery::send_to_guild(notifier, db, guild, alert, safeguard).await
 }
 
-async fn send_user_alert(
-    ctx: &Context,
-    db: &DatabaseConnection,
-    user: &user_configs::Model,
-    incident_type: &str,
-    count: i64,
-    interval: i64,
-    recent_reports: &[chrono::DateTime<Utc>],
-    reference_id: &str,
-) {
-    // Parse user ID
-    let Ok(user_id) = user.user_id.parse::<u64>() else {
-        warn!(user_id = %user.user_id, "Invalid user ID");
-        return;
-    };
+impl AlertNotice for ThresholdAlert<'_> {
+    fn alert_type(&self) -> &'static str {
+        ALERT_TYPE_THRESHOLD
+    }
 
-    // Try to record first (atomic deduplication via unique constraint)
-    // If this fails due to duplicate, we skip sending
-    let record_id =
-        match try_record_sent_alert(db, None, Some(user.user_id.clone()), reference_id).await {
-            RecordAlertResult::Recorded(id) => id,
-            RecordAlertResult::AlreadySent => return, // Already sent - skip
-            RecordAlertResult::Error => return,       // Can't record - don't send
-        };
+    fn reference_id(&self) -> &str {
+        self.reference_id
+    }
 
-    // Get user and create DM channel
-    let user_obj = match serenity::all::UserId::new(user_id).to_user(&ctx.http).await {
-        Ok(u) => u,
-        Err(e) => {
-            error!(user_id = %user.user_id, error = %e, "Failed to get user, will retry on next trigger");
-            delete_sent_alert(db, record_id).await;
-            return;
-        }
-    };
+    fn route(&self) -> &str {
+        self.incident_type
+    }
 
-    let dm_channel = match user_obj.create_dm_channel(&ctx.http).await {
-        Ok(c) => c,
-        Err(e) => {
-            error!(user_id = %user.user_id, error = %e, "Failed to create DM channel, will retry on next trigger");
-            delete_sent_alert(db, record_id).await;
-            return;
-        }
-    };
+    fn embed(&self, locale: &str, with_attachment: bool) -> CreateEmbed {
+        build_alert_embed(self, locale, with_attachment)
+    }
 
-    // Resolve locale for this user
-    let locale = resolve_user_locale_by_id(db, &user.user_id).await;
+    fn attachment(&self) -> Option<CreateAttachment> {
+        self.chart
+            .as_ref()
+            .map(|png| CreateAttachment::bytes(png.as_slice(), CHART_FILENAME))
+    }
 
-    // Build and send embed
-    let embed = build_alert_embed(incident_type, count, interval, recent_reports, &locale);
-    let message = CreateMessage::new().embed(embed);
+    fn mentions_role(&self) -> bool {
+        true
+    }
 
-    match dm_channel.send_message(&ctx.http, message).await {
-        Ok(_) => {
-            info!(
-                user_id = %user.user_id,
-                incident_type = incident_type,
-                count = count,
-                "Sent threshold alert to user DM"
-            );
-        }
-        Err(e) => {
-            error!(
-                user_id = %user.user_id,
-                error = %e,
-                "Failed to send alert to user DM, will retry on next trigger"
-            );
-            // Delete the record so we can retry on the next report
-            delete_sent_alert(db, record_id).await;
-        }
+    fn webhook_payload(&self, guild_id: &str) -> WebhookPayload {
+        WebhookPayload::Alert(WebhookAlert {
+            event: webhook::EVENT_THRESHOLD_ALERT,
+            guild_id: guild_id.to_string(),
+            reference_id: self.reference_id.to_string(),
+            incident_type: self.incident_type.to_string(),
+            incident_name: incident_types::display_name(self.incident_type),
+            report_count: self.count,
+            interval_minutes: self.interval,
+            communities: self.communities,
+            recent_reports: self.recent_reports.timestamps.clone(),
+            replay_of: self.replay_of,
+            sent_at: Utc::now(),
+        })
     }
 }
 
@@ -367,65 +599,380 @@ async fn send_user_alert(
 // Helpers
 // =============================================================================
 
-fn generate_reference_id(incident_type: &str) -> String {
-    let now = Utc::now();
-    // Round down to 15-minute block
-    let minutes = now.format("%M").to_string().parse::<i32>().unwrap_or(0);
-    let block = (minutes / 15) * 15;
-    let timestamp = now.format("%Y-%m-%dT%H").to_string();
-    format!("threshold_{}_{timestamp}:{block:02}", incident_type)
+/// Localize one line of the "Recent Reports" field
+fn format_recent_line(line: RecentLine, locale: &str) -> String {
+    match line {
+        RecentLine::Reports { minutes, count: 1 } => {
+            format!(
+                "- {}",
+                i18n::relative_time(Duration::minutes(minutes), locale)
+            )
+        }
+        RecentLine::Reports { minutes, count } => {
+            let text = match minutes {
+                0 => t!(
+                    "embeds.alerts.threshold.recent_group_just_now",
+                    count = count,
+                    locale = locale
+                ),
+                1 => t!(
+                    "embeds.alerts.threshold.recent_group_one",
+                    count = count,
+                    locale = locale
+                ),
+                n => t!(
+                    "embeds.alerts.threshold.recent_group_many",
+                    count = count,
+                    n = n,
+                    locale = locale
+                ),
+            };
+            format!("- {}", text)
+        }
+        RecentLine::More(1) => {
+            t!("embeds.alerts.threshold.recent_more_one", locale = locale).to_string()
+        }
+        RecentLine::More(n) => t!(
+            "embeds.alerts.threshold.recent_more_many",
+            n = n,
+            locale = locale
+        )
+        .to_string(),
+    }
 }
 
-fn build_alert_embed(
-    incident_type: &str,
-    count: i64,
-    interval: i64,
-    recent_reports: &[chrono::DateTime<Utc>],
-    locale: &str,
-) -> CreateEmbed {
-    let display_name = incident_types::display_name_localized(incident_type, locale);
+fn build_alert_embed(alert: &ThresholdAlert<'_>, locale: &str, with_chart: bool) -> CreateEmbed {
+    let display_name = incident_types::display_name_localized(alert.incident_type, locale);
     let now = Utc::now();
 
-    // Format recent reports as relative timestamps
-    let recent_text = if recent_reports.is_empty() {
+    // Format recent reports as relative timestamps, oldest first
+    let recent_text = if alert.recent_reports.timestamps.is_empty() {
         t!("embeds.alerts.threshold.no_recent_reports", locale = locale).to_string()
     } else {
-        recent_reports
-            .iter()
-            .map(|ts| {
-                let diff = now.signed_duration_since(*ts);
-                let mins = diff.num_minutes();
-                if mins < 1 {
-                    format!("- {}", t!("time.just_now", locale = locale))
-                } else if mins == 1 {
-                    format!("- {}", t!("time.min_ago_one", locale = locale))
-                } else {
-                    format!("- {}", t!("time.min_ago_many", n = mins, locale = locale))
-                }
-            })
+        let ages = recent::ages_in_minutes(&alert.recent_reports.timestamps, now);
+        recent::summarize(&ages, alert.recent_reports.total)
+            .into_iter()
+            .map(|line| format_recent_line(line, locale))
             .collect::<Vec<_>>()
             .join("\n")
     };
 
     let title = t!("embeds.alerts.threshold.title", locale = locale);
-    let description = t!(
+    let mut description = match alert.replay_of {
+        Some(sent_at) => {
+            let mut note = t!(
+                "embeds.alerts.threshold.replay_note",
+                time = format!("<t:{}:R>", sent_at.timestamp()),
+                locale = locale
+            )
+            .to_string();
+            note.push_str("\n\n");
+            note
+        }
+        None => String::new(),
+    };
+    description.push_str(&t!(
         "embeds.alerts.threshold.description",
-        count = count,
+        count = alert.count,
         incident_type = display_name,
-        interval = interval,
+        interval = alert.interval,
         locale = locale
-    );
+    ));
+    if let Some(line) = spread::communities_line(alert.communities, locale) {
+        description.push('\n');
+        description.push_str(&line);
+    }
+    if let Some(url) = &alert.link {
+        description.push_str("\n\n");
+        description.push_str(&t!(
+            "embeds.alerts.threshold.learn_more",
+            url = url,
+            locale = locale
+        ));
+    }
     let field_name = t!(
         "embeds.alerts.threshold.field_recent_reports",
         locale = locale
     );
     let footer = t!("embeds.alerts.threshold.footer", locale = locale);
 
-    CreateEmbed::default()
+    let mut embed = CreateEmbed::default()
         .title(title)
         .description(description)
         .color(Colour::new(colors::MAJOR))
-        .field(field_name, recent_text, false)
+        .field(field_name, recent_text, false);
+
+    if let Some(build) = &alert.new_build {
+        embed = embed.field(
+            t!("embeds.alerts.threshold.field_new_build", locale = locale),
+            t!(
+                "embeds.alerts.threshold.new_build_note",
+                version = build.version,
+                time = format!("<t:{}:R>", build.first_seen_at.timestamp()),
+                locale = locale
+            ),
+            false,
+        );
+    }
+
+    let embed = embed
         .footer(CreateEmbedFooter::new(footer))
-        .timestamp(serenity::all::Timestamp::now())
+        .timestamp(serenity::all::Timestamp::now());
+
+    if with_chart {
+        embed.image(format!("attachment://{}", CHART_FILENAME))
+    } else {
+        embed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alerts::safeguard::DeliveryLimiter;
+    use crate::database::connect_in_memory;
+    use crate::entity::sent_alerts;
+    use crate::repository::WebhookRepository;
+    use sea_orm::{ActiveModelTrait, IntoActiveModel, PaginatorTrait, Set};
+    use serenity::all::{ChannelId, Http, UserId};
+    use tokio_util::task::TaskTracker;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const ADMIN: UserId = UserId::new(2);
+
+    /// Registered guild that only takes alerts by webhook, so delivery
+    /// succeeds without reaching Discord
+    async fn webhook_guild(db: &DatabaseConnection) -> guild_configs::Model {
+        let guild = GuildConfigRepository::new(Arc::new(db.clone()))
+            .create(GUILD, ChannelId::new(3))
+            .await
+            .unwrap();
+        let mut guild = guild.into_active_model();
+        guild.channel_id = Set(None);
+        let guild = guild.update(db).await.unwrap();
+
+        WebhookRepository::new(Arc::new(db.clone()))
+            .add(
+                GUILD,
+                "https://hooks.example.invalid/vrcpulse",
+                "secret",
+                "",
+            )
+            .await
+            .unwrap();
+        guild
+    }
+
+    async fn silence(db: &DatabaseConnection, incident_type: Option<&str>, until: DateTime<Utc>) {
+        SilenceRepository::new(Arc::new(db.clone()))
+            .create(GUILD, incident_type.map(Into::into), until, ADMIN)
+            .await
+            .unwrap();
+    }
+
+    /// Send a threshold alert for `incident_type` to `guild`
+    async fn send(
+        db: &DatabaseConnection,
+        guild: &guild_configs::Model,
+        incident_type: &str,
+    ) -> Delivery {
+        let notifier = Notifier {
+            http: Arc::new(Http::new("")),
+            limiter: Arc::new(DeliveryLimiter::new()),
+            tasks: TaskTracker::new(),
+        };
+        let safeguard = AlertSafeguard::new(notifier.limiter.clone(), 100);
+        let recent_reports = RecentReports::default();
+        let reference_id = format!("threshold_{}", incident_type);
+        let alert = ThresholdAlert {
+            incident_type,
+            count: 5,
+            threshold: 5,
+            interval: 60,
+            communities: None,
+            recent_reports: &recent_reports,
+            reference_id: &reference_id,
+            chart: None,
+            new_build: None,
+            link: None,
+            replay_of: None,
+        };
+        send_guild_alert(&notifier, db, guild, &alert, &safeguard).await
+    }
+
+    async fn sent_alert_count(db: &DatabaseConnection) -> u64 {
+        sent_alerts::Entity::find().count(db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unsilenced_guild_receives_alert() {
+        let db = connect_in_memory().await;
+        let guild = webhook_guild(&db).await;
+
+        assert_eq!(send(&db, &guild, "login").await, Delivery::Sent);
+        assert_eq!(sent_alert_count(&db).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_silenced_guild_skipped() {
+        let db = connect_in_memory().await;
+        let guild = webhook_guild(&db).await;
+        silence(&db, None, Utc::now() + Duration::hours(2)).await;
+
+        assert_eq!(send(&db, &guild, "login").await, Delivery::Skipped);
+        assert_eq!(send(&db, &guild, "api").await, Delivery::Skipped);
+        // Nothing recorded, so the alert can still go out once the silence ends
+        assert_eq!(sent_alert_count(&db).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_silence_delivers() {
+        let db = connect_in_memory().await;
+        let guild = webhook_guild(&db).await;
+        silence(&db, None, Utc::now() - Duration::minutes(1)).await;
+
+        assert_eq!(send(&db, &guild, "login").await, Delivery::Sent);
+    }
+
+    #[tokio::test]
+    async fn test_type_scoped_silence() {
+        let db = connect_in_memory().await;
+        let guild = webhook_guild(&db).await;
+        silence(&db, Some("login"), Utc::now() + Duration::hours(2)).await;
+
+        assert_eq!(send(&db, &guild, "login").await, Delivery::Skipped);
+        assert_eq!(send(&db, &guild, "api").await, Delivery::Sent);
+    }
+
+    /// Store an alert fan-out that finished `age` ago
+    async fn fan_out(db: &DatabaseConnection, age: Duration) {
+        PayloadRepository::new(Arc::new(db.clone()))
+            .save(&AlertPayload {
+                reference_id: "threshold_login_1".to_string(),
+                incident_type: "login".to_string(),
+                report_count: 7,
+                interval_minutes: 60,
+                recent_reports: vec![Utc::now() - age],
+                recent_total: 7,
+                communities: Some(2),
+                updated_at: Utc::now() - age,
+            })
+            .await
+            .unwrap();
+    }
+
+    /// Run the replay for a newly registered [`GUILD`], as `/config setup` does
+    async fn replay(db: &DatabaseConnection) -> Option<Delivery> {
+        let replay = pending_replay(db, GUILD).await?;
+        let notifier = Notifier {
+            http: Arc::new(Http::new("")),
+            limiter: Arc::new(DeliveryLimiter::new()),
+            tasks: TaskTracker::new(),
+        };
+        Some(send_replay(&notifier, db, &replay, None, None).await)
+    }
+
+    #[tokio::test]
+    async fn test_guild_registered_during_block_gets_alert_once() {
+        let db = connect_in_memory().await;
+        fan_out(&db, Duration::minutes(10)).await;
+        webhook_guild(&db).await;
+
+        assert_eq!(replay(&db).await, Some(Delivery::Sent));
+        // Registering again within the block does not resend
+        assert_eq!(replay(&db).await, Some(Delivery::Duplicate));
+        let sent = sent_alerts::Entity::find().all(&db).await.unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].reference_id, "threshold_login_1");
+        assert_eq!(sent[0].guild_id.as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn test_guild_registered_after_block_gets_nothing() {
+        let db = connect_in_memory().await;
+        fan_out(
+            &db,
+            Duration::minutes(reference::DEFAULT_ALERT_EXPIRY_MINUTES + 1),
+        )
+        .await;
+        webhook_guild(&db).await;
+
+        assert_eq!(replay(&db).await, None);
+        assert_eq!(sent_alert_count(&db).await, 0);
+    }
+
+    #[test]
+    fn test_format_recent_lines() {
+        let cases = [
+            (
+                RecentLine::Reports {
+                    minutes: 0,
+                    count: 1,
+                },
+                "- Just now",
+            ),
+            (
+                RecentLine::Reports {
+                    minutes: 3,
+                    count: 1,
+                },
+                "- 3 min ago",
+            ),
+            (
+                RecentLine::Reports {
+                    minutes: 0,
+                    count: 3,
+                },
+                "- 3 reports in the last minute",
+            ),
+            (
+                RecentLine::Reports {
+                    minutes: 1,
+                    count: 2,
+                },
+                "- 2 reports 1 min ago",
+            ),
+            (
+                RecentLine::Reports {
+                    minutes: 7,
+                    count: 4,
+                },
+                "- 4 reports 7 min ago",
+            ),
+            (RecentLine::More(1), "...and 1 more report"),
+            (RecentLine::More(12), "...and 12 more reports"),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(format_recent_line(line, "en"), expected, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn test_learn_more_line_only_with_link() {
+        let recent_reports = RecentReports::default();
+        let mut alert = ThresholdAlert {
+            incident_type: "auth",
+            count: 5,
+            threshold: 5,
+            interval: 60,
+            communities: None,
+            recent_reports: &recent_reports,
+            reference_id: "threshold_auth",
+            chart: None,
+            new_build: None,
+            link: Some("https://docs.example.com/vrchat-auth".to_string()),
+            replay_of: None,
+        };
+        let description = |alert: &ThresholdAlert<'_>| {
+            let embed = serde_json::to_value(build_alert_embed(alert, "en", false)).unwrap();
+            embed["description"].as_str().unwrap().to_string()
+        };
+
+        let with_link = description(&alert);
+        alert.link = None;
+        let without_link = description(&alert);
+
+        assert!(with_link.ends_with("[Learn more](https://docs.example.com/vrchat-auth)"));
+        assert!(!without_link.contains("Learn more"));
+    }
 }
@@ -0,0 +1,406 @@
+//! Webhook delivery of alerts
+//!
+//! Guilds can register HTTP endpoints with `/config webhook add` to receive
+//! alerts in external systems, alongside or instead of Discord channels.
+//! Each of a guild's webhooks that takes an alert's type gets it as a JSON
+//! POST, whether or not a channel accepted it: threshold alerts as
+//! [`WebhookAlert`], incident, component, and maintenance notices as
+//! [`WebhookNotice`] (only webhooks that take every type receive notices).
+//! The body is signed with the webhook's secret (HMAC-SHA256, hex) in the
+//! `X-VRCPulse-Signature` header as `sha256=<hex>`, so receivers can verify
+//! it came from the bot.
+//!
+//! Posts run in a background task and are not retried: a slow or failing
+//! endpoint never holds up the Discord fan-out, and `sent_alerts` already
+//! guarantees one delivery per guild and alert.
+//!
+//! Webhook URLs are chosen by guild admins, so they may only reach public
+//! addresses: `/config webhook add` rejects local names and private, loopback,
+//! and link-local addresses, and the delivery client checks every address
+//! again when it connects and never follows redirects.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use reqwest::Url;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect;
+use serde::Serialize;
+use sha2::Sha256;
+use tokio_util::task::TaskTracker;
+use tracing::{debug, warn};
+
+use crate::entity::guild_webhooks;
+
+/// Header carrying the payload signature
+pub const SIGNATURE_HEADER: &str = "X-VRCPulse-Signature";
+
+/// Header naming the event, so receivers can route without parsing the body
+pub const EVENT_HEADER: &str = "X-VRCPulse-Event";
+
+/// Event name of threshold alerts
+pub const EVENT_THRESHOLD_ALERT: &str = "threshold_alert";
+
+/// Event name of status page incident notices
+pub const EVENT_INCIDENT_NOTICE: &str = "incident_notice";
+
+/// Event name of status page component notices
+pub const EVENT_COMPONENT_NOTICE: &str = "component_notice";
+
+/// Event name of scheduled maintenance reminders
+pub const EVENT_MAINTENANCE_NOTICE: &str = "maintenance_notice";
+
+/// Per-request timeout, so a stalled endpoint is dropped quickly
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connect timeout of the delivery client
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Client used for every webhook post
+///
+/// Separate from the shared client: it doesn't follow redirects, and its
+/// resolver drops addresses that aren't public.
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .user_agent(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .redirect(redirect::Policy::none())
+        .timeout(REQUEST_TIMEOUT)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .dns_resolver(PublicResolver)
+        .build()
+        .expect("Failed to create webhook HTTP client")
+});
+
+/// Random bytes in a generated secret (64 hex characters)
+const SECRET_BYTES: usize = 32;
+
+/// JSON body of a threshold alert webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookAlert {
+    pub event: &'static str,
+    pub guild_id: String,
+    /// Deduplication reference, the same for every recipient of one alert
+    pub reference_id: String,
+    pub incident_type: String,
+    /// English display name of the incident type
+    pub incident_name: String,
+    /// Unique users who reported within the interval
+    pub report_count: i64,
+    pub interval_minutes: i64,
+    /// Communities the reports came from, if known
+    pub communities: Option<u64>,
+    /// Newest report times, oldest first
+    pub recent_reports: Vec<DateTime<Utc>>,
+    /// Original fan-out time when replayed to a newly registered guild
+    pub replay_of: Option<DateTime<Utc>>,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// JSON body of an incident, component, or maintenance notice webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookNotice {
+    pub event: &'static str,
+    pub guild_id: String,
+    /// Deduplication reference, the same for every recipient of one notice
+    pub reference_id: String,
+    /// Incident title, component name, or maintenance title from the status page
+    pub title: String,
+    /// Status after the change (e.g. `identified`, `major_outage`, `in_progress`)
+    pub status: String,
+    /// Status page link
+    pub url: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// Body of one webhook post
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum WebhookPayload {
+    Alert(WebhookAlert),
+    Notice(WebhookNotice),
+}
+
+impl WebhookPayload {
+    /// Event name, also sent in `EVENT_HEADER`
+    pub fn event(&self) -> &'static str {
+        match self {
+            Self::Alert(alert) => alert.event,
+            Self::Notice(notice) => notice.event,
+        }
+    }
+
+    fn guild_id(&self) -> &str {
+        match self {
+            Self::Alert(alert) => &alert.guild_id,
+            Self::Notice(notice) => &notice.guild_id,
+        }
+    }
+}
+
+/// Generate a new webhook secret
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Signature header value for `body` (`sha256=<hex HMAC-SHA256>`)
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+// =============================================================================
+// Address Checks
+// =============================================================================
+
+/// Whether `ip` is reachable on the public internet
+///
+/// Rejects loopback, private (RFC 1918), carrier-grade NAT, link-local
+/// (including the 169.254.169.254 metadata service), unspecified, broadcast,
+/// multicast, and documentation addresses, and their IPv6 counterparts.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // Shared address space (100.64.0.0/10)
+                || (a == 100 && (64..128).contains(&b))
+                // "This network" (0.0.0.0/8)
+                || a == 0)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ip(IpAddr::V4(mapped)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// Whether `name` refers to this machine (`localhost` or `*.localhost`)
+fn is_local_name(name: &str) -> bool {
+    let name = name.trim_end_matches('.').to_ascii_lowercase();
+    name == "localhost" || name.ends_with(".localhost")
+}
+
+/// Whether `url` may be registered: HTTPS, with a host that is not a local
+/// name and not a non-public IP address
+///
+/// Host names are checked again by [`resolves_to_public`] and on delivery.
+pub fn is_allowed_url(url: &Url) -> bool {
+    if url.scheme() != "https" {
+        return false;
+    }
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    // IPv6 literals come bracketed
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match host.parse::<IpAddr>() {
+        Ok(ip) => is_public_ip(ip),
+        Err(_) => !is_local_name(host),
+    }
+}
+
+/// Whether the URL's host resolves, and only to public addresses
+pub async fn resolves_to_public(url: &Url) -> bool {
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        return false;
+    };
+    // IPv6 literals come bracketed
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match tokio::net::lookup_host((host, port)).await {
+        Ok(addrs) => {
+            let addrs: Vec<SocketAddr> = addrs.collect();
+            !addrs.is_empty() && addrs.iter().all(|addr| is_public_ip(addr.ip()))
+        }
+        Err(e) => {
+            debug!(host, error = %e, "Failed to resolve webhook host");
+            false
+        }
+    }
+}
+
+/// DNS resolver of the delivery client that drops non-public addresses
+///
+/// Checking at connect time keeps a host that was public when the webhook
+/// was added from later resolving to an internal address.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} has no public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+// =============================================================================
+// Delivery
+// =============================================================================
+
+/// POST `payload` to each webhook in a background task on `tasks`
+pub fn spawn_deliveries(
+    tasks: &TaskTracker,
+    webhooks: Vec<guild_webhooks::Model>,
+    payload: WebhookPayload,
+) {
+    if webhooks.is_empty() {
+        return;
+    }
+    let body = match serde_json::to_vec(&payload) {
+        Ok(body) => body,
+        Err(e) => {
+            warn!(guild_id = %payload.guild_id(), error = %e, "Failed to serialize webhook alert");
+            return;
+        }
+    };
+
+    let event = payload.event();
+    tasks.spawn(async move {
+        for webhook in webhooks {
+            deliver(&webhook, event, &body).await;
+        }
+    });
+}
+
+/// POST one signed body, logging the outcome
+async fn deliver(webhook: &guild_webhooks::Model, event: &str, body: &[u8]) {
+    // IP literals skip the resolver, so they are checked here; rows added
+    // before these checks existed are covered too
+    if !Url::parse(&webhook.url).is_ok_and(|url| is_allowed_url(&url)) {
+        warn!(
+            guild_id = %webhook.guild_id,
+            webhook_id = webhook.id,
+            "Webhook URL is not a public HTTPS address, skipping"
+        );
+        return;
+    }
+
+    let result = CLIENT
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event)
+        .header(SIGNATURE_HEADER, sign(&webhook.secret, body))
+        .body(body.to_vec())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+
+    match result {
+        Ok(response) => debug!(
+            guild_id = %webhook.guild_id,
+            webhook_id = webhook.id,
+            status = %response.status(),
+            "Delivered alert webhook"
+        ),
+        Err(e) => warn!(
+            guild_id = %webhook.guild_id,
+            webhook_id = webhook.id,
+            error = %e,
+            "Failed to deliver alert webhook"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(url: &str) -> bool {
+        Url::parse(url).is_ok_and(|url| is_allowed_url(&url))
+    }
+
+    #[test]
+    fn test_public_addresses_allowed() {
+        assert!(is_public_ip("1.1.1.1".parse().unwrap()));
+        assert!(is_public_ip("2606:4700:4700::1111".parse().unwrap()));
+        assert!(allowed("https://example.com/hook"));
+        assert!(allowed("https://203.0.114.1/hook"));
+    }
+
+    #[test]
+    fn test_private_ipv4_rejected() {
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.5.4",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "224.0.0.1",
+        ] {
+            assert!(
+                !is_public_ip(ip.parse().unwrap()),
+                "{ip} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_private_ipv6_rejected() {
+        for ip in ["::1", "::", "fc00::1", "fd12:3456::1", "fe80::1", "ff02::1"] {
+            assert!(
+                !is_public_ip(ip.parse().unwrap()),
+                "{ip} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ipv4_mapped_ipv6_checked_as_ipv4() {
+        assert!(!is_public_ip("::ffff:127.0.0.1".parse().unwrap()));
+        assert!(!is_public_ip("::ffff:169.254.169.254".parse().unwrap()));
+        assert!(is_public_ip("::ffff:1.1.1.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_local_urls_rejected() {
+        assert!(!allowed("https://localhost/hook"));
+        assert!(!allowed("https://LOCALHOST./hook"));
+        assert!(!allowed("https://api.localhost/hook"));
+        assert!(!allowed("https://127.0.0.1:8443/hook"));
+        assert!(!allowed("https://[::1]/hook"));
+        assert!(!allowed("https://169.254.169.254/latest/meta-data"));
+        // Alternate IPv4 spellings are normalized by the URL parser
+        assert!(!allowed("https://0x7f.1/hook"));
+        assert!(!allowed("https://2130706433/hook"));
+    }
+
+    #[test]
+    fn test_non_https_rejected() {
+        assert!(!allowed("http://example.com/hook"));
+        assert!(!allowed("ftp://example.com/hook"));
+    }
+}
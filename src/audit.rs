@@ -2,16 +2,26 @@
 //!
 //! Logs command execution to console and database for auditing purposes.
 
+use std::sync::Arc;
+
 use chrono::Utc;
-use sea_orm::{ActiveModelTrait, Set};
-use serenity::all::{CommandDataOptionValue, CommandInteraction, Context};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use serenity::all::{CommandDataOptionValue, CommandInteraction};
+use tokio_util::task::TaskTracker;
 use tracing::{error, info};
 
-use crate::database;
 use crate::entity::command_logs;
 
 /// Log command execution to console and database (non-blocking)
-pub fn log_command(ctx: &Context, command: &CommandInteraction) {
+///
+/// `guild_name` is only shown in the console log. The database write runs on
+/// `tasks`, so it is drained on shutdown.
+pub fn log_command(
+    db: Arc<DatabaseConnection>,
+    tasks: &TaskTracker,
+    command: &CommandInteraction,
+    guild_name: Option<String>,
+) {
     let command_name = &command.data.name;
     let user_id = command.user.id;
     let guild_id = command.guild_id;
@@ -29,11 +39,6 @@ pub fn log_command(ctx: &Context, command: &CommandInteraction) {
             _ => None,
         });
 
-    // Get guild name if available
-    let guild_name = command
-        .guild_id
-        .and_then(|id| ctx.cache.guild(id).map(|g| g.name.clone()));
-
     // Console log (sync, fast)
     info!(
         command = command_name,
@@ -52,23 +57,20 @@ pub fn log_command(ctx: &Context, command: &CommandInteraction) {
     let user_id_str = user_id.to_string();
     let guild_id_str = guild_id.map(|g| g.to_string());
     let channel_id_str = channel_id.to_string();
-    let ctx = ctx.clone();
 
-    tokio::spawn(async move {
-        if let Some(db) = database::try_get_db(&ctx).await {
-            let log = command_logs::ActiveModel {
-                command_name: Set(command_name),
-                subcommand: Set(subcommand),
-                user_id: Set(user_id_str),
-                guild_id: Set(guild_id_str),
-                channel_id: Set(Some(channel_id_str)),
-                executed_at: Set(Utc::now()),
-                ..Default::default()
-            };
+    tasks.spawn(async move {
+        let log = command_logs::ActiveModel {
+            command_name: Set(command_name),
+            subcommand: Set(subcommand),
+            user_id: Set(user_id_str),
+            guild_id: Set(guild_id_str),
+            channel_id: Set(Some(channel_id_str)),
+            executed_at: Set(Utc::now()),
+            ..Default::default()
+        };
 
-            if let Err(e) = log.insert(&*db).await {
-                error!(error = %e, "Failed to insert command log");
-            }
+        if let Err(e) = log.insert(&*db).await {
+            error!(error = %e, "Failed to insert command log");
         }
     });
 }
@@ -0,0 +1,155 @@
+//! Interaction deduplication
+//!
+//! Discord can redeliver the same interaction after gateway reconnects. This
+//! bounded cache remembers recently processed interaction IDs so the dispatcher
+//! can skip redeliveries before they reach audit logging or command handlers.
+
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use serenity::all::InteractionId;
+
+/// Maximum number of interaction IDs remembered
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// How long an interaction ID is remembered
+pub const DEFAULT_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Bounded, TTL-based set of recently seen interaction IDs
+///
+/// IDs are evicted oldest-first when the capacity is exceeded or their TTL expires.
+/// Insertion order equals expiry order, so a queue is enough for eviction.
+#[derive(Debug)]
+pub struct SeenInteractions {
+    capacity: usize,
+    ttl: Duration,
+    ids: HashSet<InteractionId>,
+    order: VecDeque<(InteractionId, Instant)>,
+}
+
+impl SeenInteractions {
+    /// Create a cache with the given capacity and TTL
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            ids: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Mark an interaction as seen at `now`
+    ///
+    /// Returns true if this is the first time (should process).
+    /// Returns false if the ID was already seen within the TTL (skip).
+    pub fn try_mark(&mut self, id: InteractionId, now: Instant) -> bool {
+        self.evict_expired(now);
+
+        if self.ids.contains(&id) {
+            return false;
+        }
+
+        self.ids.insert(id);
+        self.order.push_back((id, now));
+
+        while self.order.len() > self.capacity {
+            if let Some((oldest, _)) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+
+        true
+    }
+
+    fn evict_expired(&mut self, now: Instant) {
+        while let Some((id, seen_at)) = self.order.front().copied() {
+            if now.duration_since(seen_at) < self.ttl {
+                break;
+            }
+            self.order.pop_front();
+            self.ids.remove(&id);
+        }
+    }
+}
+
+impl Default for SeenInteractions {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, DEFAULT_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u64) -> InteractionId {
+        InteractionId::new(n)
+    }
+
+    #[test]
+    fn test_first_delivery_processed() {
+        let mut seen = SeenInteractions::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(seen.try_mark(id(1), now));
+        assert!(seen.try_mark(id(2), now));
+    }
+
+    #[test]
+    fn test_redelivery_within_window_skipped() {
+        let mut seen = SeenInteractions::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+        assert!(seen.try_mark(id(1), now));
+
+        assert!(!seen.try_mark(id(1), now));
+        assert!(!seen.try_mark(id(1), now + Duration::from_secs(59)));
+    }
+
+    #[test]
+    fn test_redelivery_after_window_processed() {
+        let mut seen = SeenInteractions::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+        assert!(seen.try_mark(id(1), now));
+
+        assert!(seen.try_mark(id(1), now + Duration::from_secs(60)));
+        assert!(!seen.try_mark(id(1), now + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn test_expiry_evicts_oldest_only() {
+        let mut seen = SeenInteractions::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+        assert!(seen.try_mark(id(1), now));
+        assert!(seen.try_mark(id(2), now + Duration::from_secs(30)));
+
+        let later = now + Duration::from_secs(70);
+        assert!(seen.try_mark(id(1), later));
+        assert!(!seen.try_mark(id(2), later));
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let mut seen = SeenInteractions::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+        assert!(seen.try_mark(id(1), now));
+        assert!(seen.try_mark(id(2), now));
+        assert!(seen.try_mark(id(3), now));
+
+        assert_eq!(seen.order.len(), 2);
+        assert!(!seen.try_mark(id(3), now));
+        assert!(!seen.try_mark(id(2), now));
+        // Evicted to make room for 3, so it is processed again
+        assert!(seen.try_mark(id(1), now));
+    }
+
+    #[test]
+    fn test_ids_and_order_stay_in_sync() {
+        let mut seen = SeenInteractions::new(3, Duration::from_secs(60));
+        let now = Instant::now();
+        for n in 1..=10 {
+            seen.try_mark(id(n), now + Duration::from_secs(n * 10));
+            assert_eq!(seen.ids.len(), seen.order.len());
+            assert!(seen.order.len() <= 3);
+        }
+    }
+}
@@ -2,22 +2,29 @@
 //!
 //! Handles all Discord gateway events (ready, interactions, guild joins, etc.)
 
-use chrono::Utc;
-use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use std::time::Instant;
+
 use serenity::all::{
-    ActivityData, ComponentInteraction, EventHandler, Guild, Interaction, Permissions, Ready,
+    ChannelId, ComponentInteraction, EventHandler, Guild, GuildId, Interaction, Permissions, Ready,
+    UnavailableGuild,
 };
-use tracing::{error, info, warn};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
 
+use crate::audit;
 use crate::commands;
-use crate::entity::guild_configs;
+use crate::database;
 use crate::error::Result;
-use crate::state::AppStateKey;
+use crate::i18n::normalize_locale;
+use crate::repository::{GuildConfigRepository, registration};
+use crate::state::{AppState, AppStateKey};
 
 use super::intro::{
     BUTTON_SET_KOREAN, BUTTON_VIEW_KOREAN, create_admin_only_error_response, create_intro_message,
-    create_korean_intro_response, create_set_korean_success_response,
+    create_korean_intro_response, create_set_korean_success_response, save_korean_preference,
 };
+use super::maintenance_mode::{self, MaintenanceState};
+use super::onboarding::send_onboarding_dm;
 
 /// Serenity event handler
 pub struct Handler {
@@ -31,16 +38,19 @@ impl EventHandler for Handler {
     async fn ready(&self, ctx: serenity::all::Context, ready: Ready) {
         info!("{} is connected!", ready.user.name);
 
-        // Set bot activity status
-        ctx.set_activity(Some(ActivityData::watching("VRChat Status")));
+        // Set bot activity status (maintenance mode persists across restarts)
+        let maintenance = maintenance_state(&ctx).await.is_some();
+        ctx.set_activity(Some(maintenance_mode::activity(maintenance)));
 
         // Register slash commands
         match self.test_guild_id {
             Some(guild_id) => {
                 // Development: register all commands including admin to test guild
                 let guild_id = serenity::all::GuildId::new(guild_id);
-                let mut cmds = commands::all();
-                cmds.extend(commands::admin::all());
+                let cmds = commands::COMMANDS
+                    .iter()
+                    .map(|spec| (spec.register)())
+                    .collect::<Vec<_>>();
 
                 match guild_id.set_commands(&ctx.http, cmds).await {
                     Ok(registered) => {
@@ -62,63 +72,70 @@ impl EventHandler for Handler {
         }
     }
 
-    /// Handle interactions (slash commands and buttons)
+    /// Handle interactions (slash commands, buttons, and autocomplete)
     async fn interaction_create(&self, ctx: serenity::all::Context, interaction: Interaction) {
+        // Skip redelivered interactions before audit logging or dispatch
+        if !admit_interaction(&ctx, &interaction).await {
+            debug!(
+                interaction_id = %interaction.id(),
+                "Skipping already processed interaction"
+            );
+            return;
+        }
+
         match interaction {
             Interaction::Command(command) => {
-                // Log command request (fire-and-forget, don't block command handling)
-                crate::audit::log_command(&ctx, &command);
-
-                // Check if this guild has a pending intro (but don't send yet)
-                let pending_intro = if let Some(guild_id) = command.guild_id {
-                    let data = ctx.data.read().await;
-                    if let Some(state) = data.get::<AppStateKey>() {
-                        if state.write().await.remove_pending_intro(guild_id) {
-                            // Get guild's preferred locale from cache
-                            let locale = guild_id
-                                .to_guild_cached(&ctx.cache)
-                                .map(|g| g.preferred_locale.clone())
-                                .unwrap_or_else(|| "en-US".to_string());
-                            Some((guild_id, command.channel_id, locale))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
+                // Maintenance mode: answer everything except /admin with a notice
+                if let Some(maintenance) = maintenance_state(&ctx).await
+                    && maintenance_mode::blocks_command(maintenance.enabled, &command.data.name)
+                {
+                    let locale = normalize_locale(&command.locale).unwrap_or("en");
+                    let response = maintenance_mode::maintenance_response(&maintenance, locale);
+                    if let Err(e) = command.create_response(&ctx.http, response).await {
+                        error!("Maintenance notice error: {:?}", e);
                     }
-                } else {
-                    None
-                };
+                    return;
+                }
 
-                // Run the command first
-                let result = match command.data.name.as_str() {
-                    "hello" => commands::hello::run(&ctx, &command).await,
-                    "admin" => commands::admin::config::run(&ctx, &command).await,
-                    "config" => commands::config::run(&ctx, &command).await,
-                    "report" => commands::report::run(&ctx, &command).await,
-                    "status" => commands::status::run(&ctx, &command).await,
-                    _ => Ok(()),
+                // Claim this guild's pending intro (but don't send yet)
+                let pending_intro = match command.guild_id {
+                    Some(guild_id) if take_pending_intro(&ctx, guild_id).await => {
+                        // Get guild's preferred locale from cache
+                        let locale = guild_id
+                            .to_guild_cached(&ctx.cache)
+                            .map(|g| g.preferred_locale.clone())
+                            .unwrap_or_else(|| "en-US".to_string());
+                        Some((guild_id, command.channel_id, locale))
+                    }
+                    _ => None,
                 };
 
-                if let Err(e) = result {
+                // Run the command first
+                if let Err(e) = commands::dispatch(&ctx, &command).await {
                     error!("Command error: {:?}", e);
                 }
 
-                // Send pending intro AFTER command completes
+                // Send pending intro AFTER the command has responded, in the background
                 if let Some((guild_id, channel_id, locale)) = pending_intro {
-                    let message = create_intro_message(&locale);
-                    if let Err(e) = channel_id.send_message(&ctx.http, message).await {
-                        warn!(
-                            guild_id = %guild_id,
-                            error = %e,
-                            "Failed to send pending intro message"
-                        );
-                    } else {
-                        info!(guild_id = %guild_id, locale = %locale, "Sent pending intro message");
-                    }
+                    spawn_pending_intro(&ctx, guild_id, channel_id, locale);
                 }
             }
             Interaction::Component(component) => {
+                // Buttons write to the database too; hold them during maintenance
+                if let Some(maintenance) = maintenance_state(&ctx).await
+                    && maintenance_mode::blocks_component(
+                        maintenance.enabled,
+                        &component.data.custom_id,
+                    )
+                {
+                    let locale = normalize_locale(&component.locale).unwrap_or("en");
+                    let response = maintenance_mode::maintenance_response(&maintenance, locale);
+                    if let Err(e) = component.create_response(&ctx.http, response).await {
+                        error!("Maintenance notice error: {:?}", e);
+                    }
+                    return;
+                }
+
                 // Handle intro button interactions
                 if component.data.custom_id.starts_with("intro_") {
                     if let Err(e) = handle_intro_button(&ctx, &component).await {
@@ -127,6 +144,31 @@ impl EventHandler for Handler {
                     return;
                 }
 
+                // Handle page buttons for /status components
+                if component.data.custom_id.starts_with("status_") {
+                    if let Err(e) = commands::status::handle_button(&ctx, &component).await {
+                        error!("Status button error: {:?}", e);
+                    }
+                    return;
+                }
+
+                // Handle page buttons for /incidents
+                if commands::incidents::is_page_button(&component.data.custom_id) {
+                    if let Err(e) = commands::incidents::handle_page_button(&ctx, &component).await
+                    {
+                        error!("Incidents button error: {:?}", e);
+                    }
+                    return;
+                }
+
+                // Handle /admin broadcast confirmation buttons
+                if component.data.custom_id.starts_with("admin_") {
+                    if let Err(e) = commands::admin::handle_button(&ctx, &component).await {
+                        error!("Admin button error: {:?}", e);
+                    }
+                    return;
+                }
+
                 // Handle button interactions for /config unregister
                 if component.data.custom_id.starts_with("config_")
                     && let Err(e) = commands::config::handle_button(&ctx, &component).await
@@ -134,6 +176,12 @@ impl EventHandler for Handler {
                     error!("Button interaction error: {:?}", e);
                 }
             }
+            Interaction::Autocomplete(autocomplete) => {
+                // Suggestions only read the database, so they run during maintenance too
+                if let Err(e) = commands::autocomplete(&ctx, &autocomplete).await {
+                    error!("Autocomplete error: {:?}", e);
+                }
+            }
             _ => {}
         }
     }
@@ -195,16 +243,151 @@ impl EventHandler for Handler {
         };
 
         // If intro wasn't sent, add to pending intros for first command
-        if !intro_sent {
+        let database = {
             let data = ctx.data.read().await;
-            if let Some(state) = data.get::<AppStateKey>() {
-                state.write().await.add_pending_intro(guild.id);
-                info!(guild_id = %guild.id, "Added to pending intros");
+            match data.get::<AppStateKey>() {
+                Some(state) => {
+                    let mut state = state.write().await;
+                    if !intro_sent {
+                        state.add_pending_intro(guild.id);
+                        info!(guild_id = %guild.id, "Added to pending intros");
+                    }
+                    Some(state.database.clone())
+                }
+                None => None,
             }
+        };
+
+        // Also DM the admin who added the bot (system channels are often disabled)
+        if let Some(db) = database {
+            send_onboarding_dm(&ctx, &db, &guild).await;
+        }
+    }
+
+    /// Called when the bot is removed from a guild or the guild goes down
+    async fn guild_delete(
+        &self,
+        ctx: serenity::all::Context,
+        incomplete: UnavailableGuild,
+        _full: Option<Guild>,
+    ) {
+        // An outage only marks the guild unavailable; it comes back on its own
+        if incomplete.unavailable {
+            debug!(guild_id = %incomplete.id, "Guild unavailable (outage), keeping registration");
+            return;
         }
+
+        disable_removed_guild(&ctx, incomplete.id).await;
     }
 }
 
+/// Disable the alert registration of a guild the bot was removed from
+///
+/// Alerts to it can only fail from now on. Settings are kept, so a
+/// `/config setup` after the bot is added back restores them.
+async fn disable_removed_guild(ctx: &serenity::all::Context, guild_id: GuildId) {
+    let db = database::get_db(ctx).await;
+    if !registration::guild_status(&db, guild_id)
+        .await
+        .is_registered()
+    {
+        return;
+    }
+
+    let repo = GuildConfigRepository::new(db);
+
+    match repo.disable(guild_id).await {
+        Ok(_) => info!(guild_id = %guild_id, "Removed from guild, disabled registration"),
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to disable registration of removed guild")
+        }
+    }
+}
+
+/// Claim a guild's pending intro; true for only one of concurrent commands
+async fn take_pending_intro(ctx: &serenity::all::Context, guild_id: GuildId) -> bool {
+    let data = ctx.data.read().await;
+    match data.get::<AppStateKey>() {
+        Some(state) => state
+            .write()
+            .await
+            .take_pending_intro(guild_id, Instant::now()),
+        None => false,
+    }
+}
+
+/// Send a claimed pending intro without holding up the command handler
+fn spawn_pending_intro(
+    ctx: &serenity::all::Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    locale: String,
+) {
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let message = create_intro_message(&locale);
+        if let Err(e) = channel_id.send_message(&ctx.http, message).await {
+            warn!(
+                guild_id = %guild_id,
+                error = %e,
+                "Failed to send pending intro message"
+            );
+        } else {
+            info!(guild_id = %guild_id, locale = %locale, "Sent pending intro message");
+        }
+
+        let data = ctx.data.read().await;
+        if let Some(state) = data.get::<AppStateKey>() {
+            state.write().await.finish_pending_intro(guild_id);
+        }
+    });
+}
+
+/// Current maintenance state, if maintenance mode is on
+async fn maintenance_state(ctx: &serenity::all::Context) -> Option<MaintenanceState> {
+    let data = ctx.data.read().await;
+    let state = data.get::<AppStateKey>()?.read().await.maintenance.state();
+    state.enabled.then_some(state)
+}
+
+/// Record the interaction as processed and audit-log commands
+///
+/// Returns false if it was already processed (Discord redelivery).
+async fn admit_interaction(ctx: &serenity::all::Context, interaction: &Interaction) -> bool {
+    let guild_name = interaction
+        .guild_id()
+        .and_then(|id| ctx.cache.guild(id).map(|g| g.name.clone()));
+    let data = ctx.data.read().await;
+    match data.get::<AppStateKey>() {
+        Some(state) => admit(state, interaction, guild_name).await,
+        None => true, // No state, proceed (shouldn't happen)
+    }
+}
+
+/// Dispatch guard: mark the interaction as seen, then log it if it is a command
+///
+/// A redelivery is neither logged nor dispatched, so each command gets one
+/// `command_logs` row. The log write runs in the background.
+async fn admit(
+    state: &RwLock<AppState>,
+    interaction: &Interaction,
+    guild_name: Option<String>,
+) -> bool {
+    let mut state = state.write().await;
+    if !state.try_mark_interaction_seen(interaction.id()) {
+        return false;
+    }
+    if let Interaction::Command(command) = interaction {
+        audit::log_command(
+            state.database.clone(),
+            &state.background_tasks,
+            command,
+            guild_name,
+        );
+    }
+    true
+}
+
 /// Handle intro button interactions
 async fn handle_intro_button(
     ctx: &serenity::all::Context,
@@ -212,8 +395,14 @@ async fn handle_intro_button(
 ) -> Result<()> {
     match component.data.custom_id.as_str() {
         BUTTON_VIEW_KOREAN => {
+            // Remember Korean as the clicking user's personal language
+            let language_saved = match database::try_get_db(ctx).await {
+                Some(db) => save_korean_preference(db, component.user.id).await,
+                None => false,
+            };
+
             // Send Korean intro with "set language" button (ephemeral)
-            let response = create_korean_intro_response();
+            let response = create_korean_intro_response(language_saved);
             component.create_response(&ctx.http, response).await?;
         }
         BUTTON_SET_KOREAN => {
@@ -234,37 +423,12 @@ async fn handle_intro_button(
                 return Ok(());
             };
 
-            // Update guild config to set language to Korean
-            let data = ctx.data.read().await;
-            if let Some(state) = data.get::<AppStateKey>() {
-                let db = &*state.read().await.database;
-
-                // Upsert guild config with language = "ko"
-                let existing = guild_configs::Entity::find_by_id(guild_id.to_string())
-                    .one(db)
+            // Same validated write as /config language; creates a disabled
+            // config if the guild has none
+            if let Some(db) = database::try_get_db(ctx).await {
+                GuildConfigRepository::new(db)
+                    .upsert_language(guild_id, "ko")
                     .await?;
-
-                let now = Utc::now();
-                match existing {
-                    Some(config) => {
-                        let mut active: guild_configs::ActiveModel = config.into();
-                        active.language = Set(Some("ko".to_string()));
-                        active.updated_at = Set(now);
-                        active.update(db).await?;
-                    }
-                    None => {
-                        let active = guild_configs::ActiveModel {
-                            guild_id: Set(guild_id.to_string()),
-                            language: Set(Some("ko".to_string())),
-                            enabled: Set(false),
-                            created_at: Set(now),
-                            updated_at: Set(now),
-                            ..Default::default()
-                        };
-                        active.insert(db).await?;
-                    }
-                }
-
                 info!(guild_id = %guild_id, "Set guild language to Korean via intro button");
             }
 
@@ -277,3 +441,61 @@ async fn handle_intro_button(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use crate::entity::command_logs;
+    use sea_orm::{EntityTrait, PaginatorTrait};
+
+    fn command(id: u64) -> Interaction {
+        serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "application_id": "2",
+            "type": 2,
+            "token": "token",
+            "version": 1,
+            "channel_id": "3",
+            "locale": "en-US",
+            "data": { "id": "4", "name": "status", "type": 1 },
+            "user": { "id": "5", "username": "tester", "discriminator": "0", "avatar": null },
+            "app_permissions": "0",
+            "attachment_size_limit": 8388608,
+            "entitlements": [],
+            "authorizing_integration_owners": {}
+        }))
+        .unwrap()
+    }
+
+    async fn command_log_count(state: &RwLock<AppState>) -> u64 {
+        let (db, tasks) = {
+            let state = state.read().await;
+            (state.database.clone(), state.background_tasks.clone())
+        };
+        tasks.close();
+        tasks.wait().await;
+        command_logs::Entity::find().count(&*db).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_command_logged_once() {
+        let state = RwLock::new(AppState::for_test(connect_in_memory().await).await);
+        let interaction = command(100);
+
+        assert!(admit(&state, &interaction, None).await);
+        assert!(!admit(&state, &interaction, None).await);
+
+        assert_eq!(command_log_count(&state).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_distinct_commands_each_logged() {
+        let state = RwLock::new(AppState::for_test(connect_in_memory().await).await);
+
+        assert!(admit(&state, &command(100), None).await);
+        assert!(admit(&state, &command(101), None).await);
+
+        assert_eq!(command_log_count(&state).await, 2);
+    }
+}
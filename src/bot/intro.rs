@@ -1,14 +1,20 @@
 //! Guild introduction embed
 //!
-//! Creates the welcome embed sent when the bot joins a new guild.
+//! Creates the welcome embed sent when the bot joins a new guild, and saves
+//! the language choice made with its "view in Korean" button.
+
+use std::sync::Arc;
 
 use rust_i18n::t;
+use sea_orm::DatabaseConnection;
 use serenity::all::{
     ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
-    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, UserId,
 };
+use tracing::{info, warn};
 
 use crate::commands::shared::colors;
+use crate::repository::UserConfigRepository;
 
 /// Button custom IDs
 pub const BUTTON_VIEW_KOREAN: &str = "intro_view_korean";
@@ -91,15 +97,23 @@ pub fn create_intro_message(discord_locale: &str) -> CreateMessage {
 /// Create the Korean intro response with "Set language to Korean" button
 ///
 /// Used when user clicks "한국어 설명 보기" button.
-/// Returns a public response.
-pub fn create_korean_intro_response() -> CreateInteractionResponse {
+/// Returns an ephemeral response; `language_saved` adds a note that the
+/// clicking user's personal language was set to Korean.
+pub fn create_korean_intro_response(language_saved: bool) -> CreateInteractionResponse {
     let embed = create_intro_embed("ko");
     let button = create_set_korean_button();
     let action_row = CreateActionRow::Buttons(vec![button]);
 
-    let message = CreateInteractionResponseMessage::new()
+    let mut message = CreateInteractionResponseMessage::new()
         .embed(embed)
-        .components(vec![action_row]);
+        .components(vec![action_row])
+        .ephemeral(true);
+    if language_saved {
+        message = message.content(
+            "개인 언어가 한국어로 저장되었습니다. `/설정 언어`로 변경할 수 있습니다. \
+             서버 언어가 설정된 경우 서버에서는 서버 언어가 우선 적용됩니다.",
+        );
+    }
 
     CreateInteractionResponse::Message(message)
 }
@@ -124,3 +138,72 @@ pub fn create_admin_only_error_response() -> CreateInteractionResponse {
 
     CreateInteractionResponse::Message(message)
 }
+
+/// Store Korean as a user's personal language after a "view in Korean" click
+///
+/// Creates a disabled user config if the user has none. Returns whether the
+/// choice was saved.
+pub async fn save_korean_preference(db: Arc<DatabaseConnection>, user_id: UserId) -> bool {
+    match UserConfigRepository::new(db)
+        .upsert_language(user_id, "ko")
+        .await
+    {
+        Ok(_) => {
+            info!(user_id = %user_id, "Set user language to Korean via intro button");
+            true
+        }
+        Err(e) => {
+            warn!(user_id = %user_id, error = %e, "Failed to save user language");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use crate::entity::user_configs;
+    use crate::i18n::resolve_user_locale;
+    use sea_orm::EntityTrait;
+
+    async fn stored(db: &DatabaseConnection, user_id: UserId) -> Option<user_configs::Model> {
+        user_configs::Entity::find_by_id(user_id.to_string())
+            .one(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_click_stores_korean_for_new_user() {
+        let db = Arc::new(connect_in_memory().await);
+        // IDs are unique per test: the locale cache is process-wide
+        let user_id = UserId::new(740_001);
+        assert_eq!(resolve_user_locale(&db, user_id).await, "en");
+
+        assert!(save_korean_preference(db.clone(), user_id).await);
+
+        let row = stored(&db, user_id).await.unwrap();
+        assert_eq!(row.language.as_deref(), Some("ko"));
+        // Choosing a language does not subscribe the user to DM alerts
+        assert!(!row.enabled);
+        assert_eq!(resolve_user_locale(&db, user_id).await, "ko");
+    }
+
+    #[tokio::test]
+    async fn test_click_updates_existing_user() {
+        let db = Arc::new(connect_in_memory().await);
+        let user_id = UserId::new(740_002);
+        let repo = UserConfigRepository::new(db.clone());
+        repo.upsert_language(user_id, "ja").await.unwrap();
+        assert_eq!(resolve_user_locale(&db, user_id).await, "ja");
+
+        assert!(save_korean_preference(db.clone(), user_id).await);
+
+        assert_eq!(
+            stored(&db, user_id).await.unwrap().language.as_deref(),
+            Some("ko")
+        );
+        assert_eq!(resolve_user_locale(&db, user_id).await, "ko");
+    }
+}
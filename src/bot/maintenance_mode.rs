@@ -0,0 +1,281 @@
+//! Maintenance mode for the bot itself
+//!
+//! The owner turns it on with `/admin maintenance on` during deploys or
+//! database migrations. While on, every command except `/admin` gets an
+//! ephemeral "undergoing maintenance" reply, threshold alerts are not sent,
+//! and the collector skips its polls. The flag lives in `bot_config` so it
+//! survives restarts, and is cached in `AppState` for the hot paths.
+
+use std::sync::RwLock;
+
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use serenity::all::{
+    ActivityData, Colour, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+
+use crate::commands::shared::colors;
+use crate::entity::bot_config;
+
+/// `bot_config` key for the maintenance flag ("true"/"false")
+pub const MAINTENANCE_MODE_KEY: &str = "maintenance_mode";
+
+/// `bot_config` key for the optional message shown to users
+pub const MAINTENANCE_MESSAGE_KEY: &str = "maintenance_message";
+
+/// Commands that keep working during maintenance
+const EXEMPT_COMMANDS: &[&str] = &["admin"];
+
+/// Presence shown while maintenance mode is on
+const MAINTENANCE_ACTIVITY: &str = "🔧 Maintenance";
+
+/// Presence shown during normal operation
+const NORMAL_ACTIVITY: &str = "VRChat Status";
+
+/// Maintenance flag and message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaintenanceState {
+    pub enabled: bool,
+    /// Custom message shown to users, if the owner set one
+    pub message: Option<String>,
+}
+
+/// Cached maintenance state, shared through `AppState`
+#[derive(Debug, Default)]
+pub struct MaintenanceMode {
+    state: RwLock<MaintenanceState>,
+}
+
+impl MaintenanceMode {
+    pub fn new(state: MaintenanceState) -> Self {
+        Self {
+            state: RwLock::new(state),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state
+            .read()
+            .expect("maintenance mode lock poisoned")
+            .enabled
+    }
+
+    pub fn state(&self) -> MaintenanceState {
+        self.state
+            .read()
+            .expect("maintenance mode lock poisoned")
+            .clone()
+    }
+
+    /// Persist a new state, then update the cache
+    ///
+    /// The cache only changes once both keys are saved, so a failed write
+    /// leaves the bot in its previous mode.
+    pub async fn set(
+        &self,
+        db: &DatabaseConnection,
+        state: MaintenanceState,
+    ) -> Result<(), sea_orm::DbErr> {
+        save_value(db, MAINTENANCE_MODE_KEY, &state.enabled.to_string()).await?;
+        save_value(
+            db,
+            MAINTENANCE_MESSAGE_KEY,
+            state.message.as_deref().unwrap_or_default(),
+        )
+        .await?;
+
+        *self.state.write().expect("maintenance mode lock poisoned") = state;
+        Ok(())
+    }
+}
+
+/// Load the persisted maintenance state; off if missing or unreadable
+pub async fn load(db: &DatabaseConnection) -> MaintenanceState {
+    let enabled = load_value(db, MAINTENANCE_MODE_KEY)
+        .await
+        .is_some_and(|value| value == "true");
+    let message = load_value(db, MAINTENANCE_MESSAGE_KEY)
+        .await
+        .filter(|message| !message.is_empty());
+
+    MaintenanceState { enabled, message }
+}
+
+async fn load_value(db: &DatabaseConnection, key: &str) -> Option<String> {
+    bot_config::Entity::find_by_id(key)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .map(|c| c.value)
+}
+
+async fn save_value(db: &DatabaseConnection, key: &str, value: &str) -> Result<(), sea_orm::DbErr> {
+    match bot_config::Entity::find_by_id(key).one(db).await? {
+        Some(existing) => {
+            let mut active: bot_config::ActiveModel = existing.into();
+            active.value = Set(value.to_string());
+            active.updated_at = Set(Utc::now());
+            active.update(db).await?;
+        }
+        None => {
+            let config = bot_config::ActiveModel {
+                key: Set(key.to_string()),
+                value: Set(value.to_string()),
+                updated_at: Set(Utc::now()),
+            };
+            config.insert(db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the dispatcher should answer a command with the maintenance notice
+pub fn blocks_command(enabled: bool, command_name: &str) -> bool {
+    enabled && !EXEMPT_COMMANDS.contains(&command_name)
+}
+
+/// Whether the dispatcher should answer a button with the maintenance notice
+///
+/// Buttons of exempt commands use the command name as their ID prefix.
+pub fn blocks_component(enabled: bool, custom_id: &str) -> bool {
+    enabled
+        && !EXEMPT_COMMANDS.iter().any(|name| {
+            custom_id
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.starts_with('_'))
+        })
+}
+
+/// Bot presence for the current mode
+pub fn activity(enabled: bool) -> ActivityData {
+    if enabled {
+        ActivityData::custom(MAINTENANCE_ACTIVITY)
+    } else {
+        ActivityData::watching(NORMAL_ACTIVITY)
+    }
+}
+
+/// Ephemeral interaction response shown instead of running a command
+pub fn maintenance_response(state: &MaintenanceState, locale: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(maintenance_embed(state.message.as_deref(), locale))
+            .ephemeral(true),
+    )
+}
+
+/// Notice shown instead of running a command
+pub fn maintenance_embed(message: Option<&str>, locale: &str) -> CreateEmbed {
+    let mut embed = CreateEmbed::default()
+        .title(t!("embeds.maintenance_mode.title", locale = locale))
+        .description(t!("embeds.maintenance_mode.description", locale = locale))
+        .color(Colour::new(colors::WARNING));
+
+    if let Some(message) = message {
+        embed = embed.field(
+            t!("embeds.maintenance_mode.field_message", locale = locale),
+            message,
+            false,
+        );
+    }
+
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+
+    #[test]
+    fn test_blocks_command() {
+        let cases = [
+            (true, "status", true),
+            (true, "config", true),
+            (true, "report", true),
+            (true, "admin", false),
+            (false, "status", false),
+            (false, "admin", false),
+        ];
+        for (enabled, name, expected) in cases {
+            assert_eq!(
+                blocks_command(enabled, name),
+                expected,
+                "{name} ({enabled})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_blocks_component() {
+        let cases = [
+            ("admin_maintenance_off", false),
+            ("admin_", false),
+            ("intro_ko", true),
+            ("report_confirm", true),
+            // Only the exact command name followed by `_` is exempt
+            ("administrator_x", true),
+            ("admin", true),
+            ("adminx", true),
+        ];
+        for (custom_id, expected) in cases {
+            assert_eq!(blocks_component(true, custom_id), expected, "{custom_id}");
+            assert!(!blocks_component(false, custom_id), "{custom_id}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_defaults_to_off() {
+        let db = connect_in_memory().await;
+
+        assert_eq!(load(&db).await, MaintenanceState::default());
+    }
+
+    #[tokio::test]
+    async fn test_set_updates_cache_and_survives_restart() {
+        let db = connect_in_memory().await;
+        let mode = MaintenanceMode::default();
+        let on = MaintenanceState {
+            enabled: true,
+            message: Some("Back at 12:00 UTC".to_string()),
+        };
+
+        mode.set(&db, on.clone()).await.unwrap();
+
+        assert!(mode.is_enabled());
+        assert_eq!(mode.state(), on);
+        // A restart reads the flag back from bot_config
+        let restarted = MaintenanceMode::new(load(&db).await);
+        assert_eq!(restarted.state(), on);
+    }
+
+    #[tokio::test]
+    async fn test_turning_off_clears_message() {
+        let db = connect_in_memory().await;
+        let mode = MaintenanceMode::default();
+        mode.set(
+            &db,
+            MaintenanceState {
+                enabled: true,
+                message: Some("Migrating".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        mode.set(&db, MaintenanceState::default()).await.unwrap();
+
+        assert!(!mode.is_enabled());
+        assert_eq!(load(&db).await, MaintenanceState::default());
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_flag_is_off() {
+        let db = connect_in_memory().await;
+        save_value(&db, MAINTENANCE_MODE_KEY, "yes").await.unwrap();
+
+        assert!(!load(&db).await.enabled);
+    }
+}
@@ -2,36 +2,55 @@
 //!
 //! This module encapsulates all Discord-specific initialization and event handling.
 
+pub mod dedup;
 mod handler;
 pub mod intro;
+pub mod maintenance_mode;
+mod onboarding;
+pub mod owner;
+mod shutdown;
 
 pub use handler::Handler;
+pub use shutdown::Shutdown;
+
+use maintenance_mode::MaintenanceMode;
 
 use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection};
 use serenity::all::{Client, GatewayIntents};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::info;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
 
-use crate::collector;
+use crate::alerts;
+use crate::collector::{self, CollectorHandles, CollectorHealth, SchemaMonitor};
 use crate::config::Config;
 use crate::error::Result;
+use crate::leader::{self, Leadership};
+use crate::maintenance;
+use crate::repository::{GuildConfigRepository, UserConfigRepository};
+use crate::scheduler::{Scheduler, SchedulerStatus};
 use crate::state::{AppState, AppStateKey};
+use crate::version::{self, StartupInfo};
 
 /// Set up and configure the Discord bot client
 ///
 /// This function handles all initialization:
 /// - Database connection
 /// - Collector config initialization
-/// - HTTP client creation
-/// - Background collector task spawning
 /// - Discord client configuration
+/// - HTTP client creation
+/// - Scheduled jobs and collector task spawning, gated on the collector lease
+/// - Ctrl+C / SIGTERM handling
 ///
-/// Returns a configured `Client` ready to be started.
-pub async fn setup(config: &Config) -> Result<Client> {
+/// Returns a configured `Client` ready to be started, and the `Shutdown`
+/// handle that stops the background tasks once it returns.
+pub async fn setup(config: &Config) -> Result<(Client, Shutdown)> {
     // 1. Connect to database with optimized settings for SQLite
     let database = connect_database(&config.database_url).await?;
     info!("Database connected (WAL mode enabled)");
+    log_startup_banner(&database, &config.database_url).await;
+    warn_invalid_languages(&database).await;
 
     // 2. Initialize collector config
     let (config_tx, config_rx) = collector::config::init(&database)
@@ -39,14 +58,7 @@ pub async fn setup(config: &Config) -> Result<Client> {
         .expect("Failed to load collector config from database");
     info!("Collector config loaded");
 
-    // 3. Create AppState
-    let app_state = Arc::new(RwLock::new(AppState::new(database.clone(), config_tx)));
-
-    // 4. Start data collector in background
-    let http_client = create_http_client();
-    tokio::spawn(collector::start(http_client, database, config_rx));
-
-    // 5. Configure Discord client
+    // 3. Configure Discord client
     let intents = GatewayIntents::GUILDS
         | GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::GUILD_PRESENCES
@@ -60,13 +72,183 @@ pub async fn setup(config: &Config) -> Result<Client> {
         .event_handler(handler)
         .await?;
 
-    // 6. Store AppState in TypeMap
+    // 4. Create AppState (schema monitor DMs the owner via the Discord HTTP client)
+    let maintenance = Arc::new(MaintenanceMode::new(
+        maintenance_mode::load(&database).await,
+    ));
+    if maintenance.is_enabled() {
+        warn!("Maintenance mode is on, commands and alerts are paused");
+    }
+    let schema_monitor = Arc::new(SchemaMonitor::new(client.http.clone()));
+    let collector_health = Arc::new(CollectorHealth::new());
+    let scheduler_status = Arc::new(SchedulerStatus::new());
+    let http_client = create_http_client();
+    let state = AppState::new(
+        database.clone(),
+        config_tx,
+        schema_monitor.clone(),
+        collector_health.clone(),
+        scheduler_status.clone(),
+        maintenance.clone(),
+    );
+    // The collector drops cached chart series when it stores new points
+    let metric_cache = state.metric_cache.clone();
+    // Notices sent outside event handlers share the alert delivery cap
+    let notifier = alerts::delivery::Notifier {
+        http: client.http.clone(),
+        limiter: state.alert_limiter.clone(),
+        tasks: state.background_tasks.clone(),
+    };
+    let app_state = Arc::new(RwLock::new(state));
+
+    // 5. Store AppState in TypeMap
     {
         let mut data = client.data.write().await;
         data.insert::<AppStateKey>(app_state);
     }
 
-    Ok(client)
+    // 6. Start leader election; only the lease holder runs jobs and the collector
+    let token = CancellationToken::new();
+    let mut shutdown = Shutdown::new(
+        token.clone(),
+        client.shard_manager.clone(),
+        database.clone(),
+        notifier.tasks.clone(),
+    );
+    let leadership = Arc::new(Leadership::new(leader::instance_id()));
+    shutdown.track(
+        "leader",
+        tokio::spawn(leader::run(
+            Arc::new(database.clone()),
+            leadership.clone(),
+            token.clone(),
+        )),
+    );
+
+    // 7. Register scheduled jobs and start the scheduler in background
+    let mut scheduler = Scheduler::new(scheduler_status);
+    maintenance::register(&mut scheduler, Arc::new(database.clone()));
+    alerts::maintenance_reminder::register(
+        &mut scheduler,
+        notifier.clone(),
+        Arc::new(database.clone()),
+        maintenance.clone(),
+    );
+    alerts::digest::register(
+        &mut scheduler,
+        client.http.clone(),
+        Arc::new(database.clone()),
+        maintenance.clone(),
+    );
+    alerts::channel_check::register(
+        &mut scheduler,
+        client.http.clone(),
+        Arc::new(database.clone()),
+    );
+    collector::builds::register(
+        &mut scheduler,
+        http_client.clone(),
+        Arc::new(database.clone()),
+    );
+    shutdown.track(
+        "scheduler",
+        tokio::spawn(scheduler.run(token.clone(), leadership.clone())),
+    );
+
+    // 8. Start data collector in background (polls only while leader)
+    shutdown.track(
+        "collector",
+        tokio::spawn(collector::start(
+            http_client,
+            database,
+            config_rx,
+            CollectorHandles {
+                monitor: schema_monitor,
+                health: collector_health,
+                maintenance,
+                leadership,
+                notifier,
+                metric_cache,
+                shutdown: token,
+            },
+        )),
+    );
+    shutdown.listen_for_signals();
+
+    Ok((client, shutdown))
+}
+
+/// Log version, git hash, migration status, database file, and locales
+async fn log_startup_banner(database: &DatabaseConnection, database_url: &str) {
+    let migrations = match version::migration_status(database).await {
+        Ok(status) => {
+            if status.is_pending() {
+                warn!(
+                    applied = status.applied,
+                    available = status.available,
+                    "Database has pending migrations, run `sea-orm-cli migrate up`"
+                );
+            }
+            Some(status)
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to read migration status");
+            None
+        }
+    };
+
+    let database_path = version::sqlite_path(database_url);
+    let database_size = database_path
+        .as_ref()
+        .and_then(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len());
+    let database_path = database_path.map(|path| path.display().to_string());
+    let locales = rust_i18n::available_locales!();
+
+    let banner = version::banner(&StartupInfo {
+        version: version::VERSION,
+        git_hash: version::GIT_HASH,
+        migrations: migrations.as_ref(),
+        database_path: database_path.as_deref(),
+        database_size,
+        locales: &locales,
+    });
+    info!(
+        version = version::VERSION,
+        git_hash = version::GIT_HASH,
+        "Startup\n{}",
+        banner
+    );
+}
+
+/// Log config rows whose language is not a supported locale
+///
+/// SQLite cannot add a CHECK constraint to an existing column, so this scan
+/// stands in for one: writes are normalized by the repositories and the
+/// normalize migration repairs old rows, so anything found here is a bug.
+async fn warn_invalid_languages(database: &DatabaseConnection) {
+    let db = Arc::new(database.clone());
+
+    match GuildConfigRepository::new(db.clone())
+        .find_invalid_languages()
+        .await
+    {
+        Ok(rows) => {
+            for row in rows {
+                warn!(guild_id = %row.guild_id, language = ?row.language, "Guild config has unsupported language");
+            }
+        }
+        Err(e) => warn!(error = %e, "Failed to scan guild languages"),
+    }
+
+    match UserConfigRepository::new(db).find_invalid_languages().await {
+        Ok(rows) => {
+            for row in rows {
+                warn!(user_id = %row.user_id, language = ?row.language, "User config has unsupported language");
+            }
+        }
+        Err(e) => warn!(error = %e, "Failed to scan user languages"),
+    }
 }
 
 /// Connect to database with optimized settings for SQLite
@@ -97,6 +279,8 @@ fn create_http_client() -> reqwest::Client {
             "/",
             env!("CARGO_PKG_VERSION")
         ))
+        // Bound each poll so a stalled request surfaces as a timeout
+        .timeout(std::time::Duration::from_secs(30))
         .build()
         .expect("Failed to create HTTP client")
 }
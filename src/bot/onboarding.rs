@@ -0,0 +1,330 @@
+//! Onboarding DM to the installing admin
+//!
+//! The guild intro goes to the system channel, which many servers disable.
+//! On join we also look up who added the bot in the audit log (`BOT_ADD`) and
+//! DM them a quick-start guide. `guild_create` does not carry the installer,
+//! so this needs the View Audit Log permission; without it the DM is skipped.
+//!
+//! Each guild gets the DM at most once, deduplicated via `sent_alerts`
+//! (`alert_type` "onboarding_dm", reference = guild ID).
+
+use chrono::{DateTime, Duration, Utc};
+use rust_i18n::t;
+use sea_orm::DatabaseConnection;
+use serenity::all::{
+    AuditLogEntry, Colour, Context, CreateEmbed, CreateEmbedFooter, CreateMessage, Guild, GuildId,
+    HttpError, UserId,
+};
+use serenity::model::guild::audit_log::{Action, MemberAction};
+use tracing::{debug, info, warn};
+
+use crate::alerts::delivery::{RecordAlertResult, try_record_sent_alert};
+use crate::commands::shared::colors;
+use crate::i18n::normalize_locale;
+
+/// `sent_alerts.alert_type` for onboarding DMs
+pub const ALERT_TYPE_ONBOARDING_DM: &str = "onboarding_dm";
+
+/// `BOT_ADD` entries fetched from the audit log
+const AUDIT_LOG_LIMIT: u8 = 10;
+
+/// Oldest `BOT_ADD` entry that still counts as this join
+///
+/// Older entries belong to an earlier install (bot kicked and re-added).
+const MAX_ENTRY_AGE_MINUTES: i64 = 15;
+
+/// Discord error code for Missing Permissions
+const MISSING_PERMISSIONS_CODE: isize = 50013;
+
+/// Find the user who added the bot from a guild's audit log entries
+///
+/// Picks the newest `BOT_ADD` entry targeting `bot_id` that was created within
+/// [`MAX_ENTRY_AGE_MINUTES`] of `now`. Entry order does not matter.
+pub fn find_installer(
+    entries: &[AuditLogEntry],
+    bot_id: UserId,
+    now: DateTime<Utc>,
+) -> Option<UserId> {
+    let oldest = now - Duration::minutes(MAX_ENTRY_AGE_MINUTES);
+
+    entries
+        .iter()
+        .filter(|entry| matches!(entry.action, Action::Member(MemberAction::BotAdd)))
+        .filter(|entry| entry.target_id.is_some_and(|id| id.get() == bot_id.get()))
+        .filter(|entry| entry_time(entry) >= oldest)
+        .max_by_key(|entry| entry.id)
+        .map(|entry| entry.user_id)
+}
+
+/// Creation time of an audit log entry, from its snowflake
+fn entry_time(entry: &AuditLogEntry) -> DateTime<Utc> {
+    *entry.id.created_at()
+}
+
+/// DM the installing admin a quick-start guide, once per guild
+///
+/// Failures (missing audit log access, closed DMs) are logged and skipped.
+pub async fn send_onboarding_dm(ctx: &Context, db: &DatabaseConnection, guild: &Guild) {
+    let bot_id = ctx.cache.current_user().id;
+
+    let entries = match guild
+        .id
+        .audit_logs(
+            &ctx.http,
+            Some(Action::Member(MemberAction::BotAdd)),
+            None,
+            None,
+            Some(AUDIT_LOG_LIMIT),
+        )
+        .await
+    {
+        Ok(logs) => logs.entries,
+        Err(e) if is_missing_permissions(&e) => {
+            info!(
+                guild_id = %guild.id,
+                "No View Audit Log permission, skipping onboarding DM"
+            );
+            return;
+        }
+        Err(e) => {
+            warn!(guild_id = %guild.id, error = %e, "Failed to fetch audit log for onboarding DM");
+            return;
+        }
+    };
+
+    let Some(installer) = find_installer(&entries, bot_id, Utc::now()) else {
+        debug!(guild_id = %guild.id, "No recent BOT_ADD entry, skipping onboarding DM");
+        return;
+    };
+
+    if !claim_onboarding_dm(db, guild.id).await {
+        return;
+    }
+
+    let locale = onboarding_locale(&guild.preferred_locale);
+    let message = CreateMessage::new().embed(create_onboarding_embed(&guild.name, locale));
+
+    let result = match installer.create_dm_channel(&ctx.http).await {
+        Ok(channel) => channel.send_message(&ctx.http, message).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+
+    // Keep the record on failure too: closed DMs won't open by retrying
+    match result {
+        Ok(()) => {
+            info!(guild_id = %guild.id, user_id = %installer, locale, "Sent onboarding DM");
+        }
+        Err(e) => {
+            warn!(
+                guild_id = %guild.id,
+                user_id = %installer,
+                error = %e,
+                "Failed to send onboarding DM, giving up"
+            );
+        }
+    }
+}
+
+/// Record the onboarding DM for a guild; `false` if it was already sent
+/// (or the record couldn't be written)
+async fn claim_onboarding_dm(db: &DatabaseConnection, guild_id: GuildId) -> bool {
+    let reference_id = guild_id.to_string();
+    match try_record_sent_alert(
+        db,
+        Some(reference_id.clone()),
+        None,
+        ALERT_TYPE_ONBOARDING_DM,
+        &reference_id,
+    )
+    .await
+    {
+        RecordAlertResult::Recorded(_) => true,
+        RecordAlertResult::AlreadySent => {
+            debug!(guild_id = %guild_id, "Onboarding DM already sent");
+            false
+        }
+        RecordAlertResult::Error => false,
+    }
+}
+
+/// Locale for the onboarding DM
+///
+/// The installer's own locale is unknown here, so the guild's preferred
+/// locale is used, falling back to English.
+fn onboarding_locale(preferred_locale: &str) -> &'static str {
+    normalize_locale(preferred_locale).unwrap_or("en")
+}
+
+/// Create the quick-start embed sent to the installing admin
+pub fn create_onboarding_embed(guild_name: &str, locale: &str) -> CreateEmbed {
+    CreateEmbed::default()
+        .title(t!("embeds.intro.onboarding_dm.title", locale = locale))
+        .description(t!(
+            "embeds.intro.onboarding_dm.description",
+            guild = guild_name,
+            locale = locale
+        ))
+        .color(Colour::new(colors::BRAND))
+        .field(
+            t!("embeds.intro.onboarding_dm.field_setup", locale = locale),
+            t!(
+                "embeds.intro.onboarding_dm.field_setup_value",
+                locale = locale
+            ),
+            false,
+        )
+        .field(
+            t!(
+                "embeds.intro.onboarding_dm.field_permissions",
+                locale = locale
+            ),
+            t!(
+                "embeds.intro.onboarding_dm.field_permissions_value",
+                locale = locale
+            ),
+            false,
+        )
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.intro.onboarding_dm.footer",
+            locale = locale
+        )))
+}
+
+/// Whether an error is Discord's Missing Permissions (50013)
+fn is_missing_permissions(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(HttpError::UnsuccessfulRequest(response))
+            if response.error.code == MISSING_PERMISSIONS_CODE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    const BOT: UserId = UserId::new(500);
+    const ADMIN: UserId = UserId::new(600);
+    const OTHER_ADMIN: UserId = UserId::new(601);
+
+    /// Discord epoch (2015-01-01) in Unix milliseconds
+    const DISCORD_EPOCH_MS: i64 = 1_420_070_400_000;
+
+    /// `MEMBER_BOT_ADD` audit log action type
+    const BOT_ADD: u8 = 28;
+    /// `MEMBER_KICK` audit log action type
+    const MEMBER_KICK: u8 = 20;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    /// Synthetic audit log entry created `age` before [`now`]
+    fn entry(action_type: u8, user: UserId, target: UserId, age: Duration) -> AuditLogEntry {
+        let created_ms = (now() - age).timestamp_millis() - DISCORD_EPOCH_MS;
+        // Low bits keep entries created in the same millisecond distinct
+        let id = ((created_ms as u64) << 22) | (user.get() & 0xfff);
+        serde_json::from_value(json!({
+            "id": id.to_string(),
+            "action_type": action_type,
+            "user_id": user.to_string(),
+            "target_id": target.to_string(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_entry_time_from_snowflake() {
+        let entry = entry(BOT_ADD, ADMIN, BOT, Duration::minutes(3));
+
+        assert_eq!(entry_time(&entry), now() - Duration::minutes(3));
+    }
+
+    #[test]
+    fn test_find_installer() {
+        let entries = [entry(BOT_ADD, ADMIN, BOT, Duration::minutes(1))];
+
+        assert_eq!(find_installer(&entries, BOT, now()), Some(ADMIN));
+    }
+
+    #[test]
+    fn test_find_installer_none() {
+        assert_eq!(find_installer(&[], BOT, now()), None);
+    }
+
+    #[test]
+    fn test_find_installer_ignores_other_entries() {
+        let entries = [
+            // Another bot added by someone else
+            entry(BOT_ADD, OTHER_ADMIN, UserId::new(700), Duration::minutes(1)),
+            // A different action targeting this bot
+            entry(MEMBER_KICK, OTHER_ADMIN, BOT, Duration::minutes(1)),
+            entry(BOT_ADD, ADMIN, BOT, Duration::minutes(2)),
+        ];
+
+        assert_eq!(find_installer(&entries, BOT, now()), Some(ADMIN));
+    }
+
+    #[test]
+    fn test_find_installer_prefers_newest_in_any_order() {
+        let older = entry(BOT_ADD, OTHER_ADMIN, BOT, Duration::minutes(10));
+        let newer = entry(BOT_ADD, ADMIN, BOT, Duration::minutes(1));
+
+        assert_eq!(
+            find_installer(&[older.clone(), newer.clone()], BOT, now()),
+            Some(ADMIN)
+        );
+        assert_eq!(find_installer(&[newer, older], BOT, now()), Some(ADMIN));
+    }
+
+    #[test]
+    fn test_find_installer_age_boundary() {
+        let max_age = Duration::minutes(MAX_ENTRY_AGE_MINUTES);
+        let at_limit = [entry(BOT_ADD, ADMIN, BOT, max_age)];
+        let past_limit = [entry(BOT_ADD, ADMIN, BOT, max_age + Duration::seconds(1))];
+
+        assert_eq!(find_installer(&at_limit, BOT, now()), Some(ADMIN));
+        // An earlier install (bot kicked and re-added) doesn't count
+        assert_eq!(find_installer(&past_limit, BOT, now()), None);
+    }
+
+    #[tokio::test]
+    async fn test_onboarding_dm_claimed_once_per_guild() {
+        let db = connect_in_memory().await;
+
+        let first = claim_onboarding_dm(&db, GuildId::new(1)).await;
+        let again = claim_onboarding_dm(&db, GuildId::new(1)).await;
+        let other_guild = claim_onboarding_dm(&db, GuildId::new(2)).await;
+
+        assert!(first);
+        assert!(!again);
+        assert!(other_guild);
+    }
+
+    #[tokio::test]
+    async fn test_onboarding_dm_apart_from_alerts() {
+        let db = connect_in_memory().await;
+        try_record_sent_alert(&db, Some("1".into()), None, "threshold", "1").await;
+
+        assert!(claim_onboarding_dm(&db, GuildId::new(1)).await);
+    }
+
+    #[test]
+    fn test_onboarding_locale() {
+        let cases = [
+            ("ko", "ko"),
+            ("ja", "ja"),
+            ("en-US", "en"),
+            ("en-GB", "en"),
+            ("fr", "en"),
+            ("zh-TW", "en"),
+            ("", "en"),
+        ];
+        for (preferred, expected) in cases {
+            assert_eq!(onboarding_locale(preferred), expected, "{preferred}");
+        }
+    }
+}
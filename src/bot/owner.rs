@@ -0,0 +1,24 @@
+//! Bot owner notifications
+//!
+//! Operator-facing notices (alert suppression, upstream schema drift) are sent
+//! as plain DMs to the application owner.
+
+use serenity::all::{CreateMessage, Http, UserId};
+
+/// DM the application owner
+///
+/// Returns the owner's ID, or `None` if the application has no owner
+/// (e.g. team-owned applications).
+pub async fn dm_owner(http: &Http, content: String) -> Result<Option<UserId>, serenity::Error> {
+    let Some(owner) = http.get_current_application_info().await?.owner else {
+        return Ok(None);
+    };
+
+    owner
+        .create_dm_channel(http)
+        .await?
+        .send_message(http, CreateMessage::new().content(content))
+        .await?;
+
+    Ok(Some(owner.id))
+}
@@ -0,0 +1,204 @@
+//! Graceful shutdown on Ctrl+C or SIGTERM
+//!
+//! `bot::setup` hands back a [`Shutdown`] alongside the client. The signal
+//! listener cancels the shared token and disconnects all shards, which makes
+//! `Client::start` return; `main` then calls [`Shutdown::finish`] to wait for
+//! the background tasks and close the database.
+//!
+//! On cancellation the lease task releases the collector lease, poll loops
+//! exit after any poll in progress, and the scheduler exits after any job in
+//! progress. Alert fan-outs, webhook posts, and audit writes spawned on
+//! `AppState::background_tasks` are waited for as well, so none of them is
+//! still writing when the database closes. Tasks still running after
+//! [`DRAIN_TIMEOUT`] are abandoned.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use sea_orm::DatabaseConnection;
+use serenity::all::ShardManager;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{error, info, warn};
+
+/// Longest wait for background tasks to stop before closing the database
+pub const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Handles needed to stop the bot cleanly
+pub struct Shutdown {
+    token: CancellationToken,
+    shard_manager: Arc<ShardManager>,
+    database: DatabaseConnection,
+    /// Background tasks that stop when the token is cancelled
+    tasks: Vec<(&'static str, JoinHandle<()>)>,
+    /// Short-lived tasks spawned off the request path
+    background: TaskTracker,
+}
+
+impl Shutdown {
+    pub(super) fn new(
+        token: CancellationToken,
+        shard_manager: Arc<ShardManager>,
+        database: DatabaseConnection,
+        background: TaskTracker,
+    ) -> Self {
+        Self {
+            token,
+            shard_manager,
+            database,
+            tasks: Vec::new(),
+            background,
+        }
+    }
+
+    /// Track a background task to wait for on shutdown
+    pub(super) fn track(&mut self, name: &'static str, task: JoinHandle<()>) {
+        self.tasks.push((name, task));
+    }
+
+    /// Cancel background tasks and disconnect shards on Ctrl+C or SIGTERM
+    pub fn listen_for_signals(&self) {
+        let token = self.token.clone();
+        let shard_manager = self.shard_manager.clone();
+        tokio::spawn(async move {
+            if !wait_for_signal().await {
+                return;
+            }
+            token.cancel();
+            info!("Disconnecting shards");
+            shard_manager.shutdown_all().await;
+        });
+    }
+
+    /// Wait for background tasks to stop, then close the database
+    ///
+    /// Also cancels the token, so this works after the client stopped on its
+    /// own (e.g. a gateway error) as well as after a signal.
+    pub async fn finish(self) {
+        self.token.cancel();
+
+        info!(
+            tasks = self.tasks.len(),
+            in_flight = self.background.len(),
+            "Waiting for background tasks"
+        );
+        if tokio::time::timeout(DRAIN_TIMEOUT, drain(self.tasks, &self.background))
+            .await
+            .is_err()
+        {
+            warn!(
+                timeout_secs = DRAIN_TIMEOUT.as_secs(),
+                "Background tasks did not stop in time, closing anyway"
+            );
+        }
+
+        match self.database.close().await {
+            Ok(()) => info!("Database connection closed"),
+            Err(e) => error!(error = %e, "Failed to close database connection"),
+        }
+        info!("Shutdown complete");
+    }
+}
+
+/// Wait for the named tasks, then for every task spawned on `background`
+///
+/// `background` is closed first, so it is done once its running tasks finish;
+/// tasks they spawn on it in the meantime are waited for too.
+async fn drain(tasks: Vec<(&'static str, JoinHandle<()>)>, background: &TaskTracker) {
+    for (name, task) in tasks {
+        match task.await {
+            Ok(()) => info!(task = name, "Background task stopped"),
+            Err(e) => warn!(task = name, error = %e, "Background task failed"),
+        }
+    }
+    background.close();
+    background.wait().await;
+    info!("In-flight alert and audit tasks finished");
+}
+
+/// Wait for Ctrl+C or SIGTERM, returning `false` if no signal can be received
+async fn wait_for_signal() -> bool {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut sigterm = match signal(SignalKind::terminate()) {
+            Ok(sigterm) => sigterm,
+            Err(e) => {
+                warn!(error = %e, "Failed to listen for SIGTERM");
+                return wait_for_ctrl_c().await;
+            }
+        };
+        tokio::select! {
+            // A failed Ctrl+C listener leaves SIGTERM to wait on
+            true = wait_for_ctrl_c() => true,
+            _ = sigterm.recv() => {
+                info!("Shutdown signal received (SIGTERM)");
+                true
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        wait_for_ctrl_c().await
+    }
+}
+
+async fn wait_for_ctrl_c() -> bool {
+    match tokio::signal::ctrl_c().await {
+        Ok(()) => {
+            info!("Shutdown signal received (Ctrl+C)");
+            true
+        }
+        Err(e) => {
+            warn!(error = %e, "Failed to listen for Ctrl+C");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn finish_later(done: Arc<AtomicUsize>) {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        done.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_named_and_background_tasks() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let background = TaskTracker::new();
+        let tasks = vec![("named", tokio::spawn(finish_later(done.clone())))];
+        background.spawn(finish_later(done.clone()));
+        background.spawn(finish_later(done.clone()));
+
+        drain(tasks, &background).await;
+
+        assert_eq!(done.load(Ordering::SeqCst), 3);
+        assert!(background.is_closed() && background.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_tasks_spawned_while_draining() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let background = TaskTracker::new();
+        // A fan-out that starts a webhook post once it is already draining
+        background.spawn({
+            let background = background.clone();
+            let done = done.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                background.spawn(finish_later(done));
+            }
+        });
+
+        drain(Vec::new(), &background).await;
+
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,112 @@
+//! In-memory cache of chart metric queries
+//!
+//! `/status` and `/status metric` load the same few metric series on every
+//! invocation. Each loaded series is kept for a short TTL (`bot_config` key
+//! `metric_cache_ttl_seconds`, 30 seconds by default, 0 disables caching), and
+//! the collector drops a metric's entries as soon as it inserts new
+//! `metric_logs` rows for it, so charts never lag behind a poll on the
+//! collecting instance. Other instances see new points once the TTL expires.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Duration as ChronoDuration;
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serenity::all::Context;
+use tokio::sync::RwLock;
+
+use crate::entity::bot_config;
+use crate::state::AppStateKey;
+use crate::visualization::query::MetricData;
+
+/// `bot_config` key for the metric cache TTL in seconds
+pub const METRIC_CACHE_TTL_KEY: &str = "metric_cache_ttl_seconds";
+
+/// TTL when `metric_cache_ttl_seconds` is not configured
+pub const DEFAULT_METRIC_CACHE_TTL_SECS: u64 = 30;
+
+/// Longest accepted TTL (newer points must show up within one poll or two)
+const MAX_METRIC_CACHE_TTL_SECS: u64 = 10 * 60;
+
+/// Loaded metric series keyed by metric, scale, and range, shared through `AppState`
+#[derive(Default)]
+pub struct MetricCache {
+    entries: RwLock<HashMap<String, (MetricData, Instant)>>,
+}
+
+impl MetricCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache key of a series (`<metric_name>:<raw|percent>:<range seconds>`)
+    pub fn key(metric_name: &str, percent: bool, range: ChronoDuration) -> String {
+        let scale = if percent { "percent" } else { "raw" };
+        format!("{}:{}:{}", metric_name, scale, range.num_seconds())
+    }
+
+    /// Cached series if younger than `ttl`, otherwise the result of `load`
+    ///
+    /// A failed load is not cached, and a zero `ttl` always loads.
+    pub async fn get_or_load<F, Fut>(
+        &self,
+        key: String,
+        ttl: Duration,
+        load: F,
+    ) -> Result<MetricData, sea_orm::DbErr>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<MetricData, sea_orm::DbErr>>,
+    {
+        if let Some((data, loaded_at)) = self.entries.read().await.get(&key)
+            && loaded_at.elapsed() < ttl
+        {
+            return Ok(data.clone());
+        }
+
+        let data = load().await?;
+        if !ttl.is_zero() {
+            self.entries
+                .write()
+                .await
+                .insert(key, (data.clone(), Instant::now()));
+        }
+        Ok(data)
+    }
+
+    /// Drop every cached series of a metric
+    pub async fn invalidate(&self, metric_name: &str) {
+        let prefix = format!("{}:", metric_name);
+        self.entries
+            .write()
+            .await
+            .retain(|key, _| !key.starts_with(&prefix));
+    }
+}
+
+/// Load the configured TTL, clamped to 0..=600 seconds
+pub async fn load_ttl(db: &DatabaseConnection) -> Duration {
+    let secs = bot_config::Entity::find_by_id(METRIC_CACHE_TTL_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .unwrap_or(DEFAULT_METRIC_CACHE_TTL_SECS)
+        .min(MAX_METRIC_CACHE_TTL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Get the metric cache from Serenity context
+///
+/// # Panics
+/// Panics if AppState is not found in TypeMap (should never happen after bot initialization)
+pub async fn get_metric_cache(ctx: &Context) -> Arc<MetricCache> {
+    let data = ctx.data.read().await;
+    let state = data
+        .get::<AppStateKey>()
+        .expect("AppState not found in TypeMap");
+    state.read().await.metric_cache.clone()
+}
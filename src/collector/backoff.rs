@@ -0,0 +1,88 @@
+//! Poll retry policy
+//!
+//! Decides how long a poller waits after a failed poll, based on the kind of
+//! failure. Rate limits honor `Retry-After`, repeated client errors (endpoint
+//! moved or removed) back off for a long time, and transient network or server
+//! failures back off exponentially.
+
+use std::time::Duration;
+
+use super::client::ErrorKind;
+
+/// Upper bound for exponential backoff on transient failures
+pub const MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+
+/// Delay after repeated client errors (4xx other than 429)
+pub const LONG_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
+/// Consecutive client errors before switching to `LONG_BACKOFF`
+pub const CLIENT_ERROR_THRESHOLD: u32 = 3;
+
+/// Consecutive failure tracking for one poller
+#[derive(Debug, Default)]
+pub struct Backoff {
+    /// Consecutive transient failures (network, 5xx)
+    transient_failures: u32,
+    /// Consecutive client errors (4xx other than 429)
+    client_errors: u32,
+}
+
+impl Backoff {
+    /// Reset after a successful poll
+    pub fn on_success(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Record a failure and return the delay before the next poll
+    ///
+    /// Never returns less than `interval`, so a failure cannot speed up polling.
+    pub fn on_failure(
+        &mut self,
+        kind: ErrorKind,
+        retry_after: Option<Duration>,
+        interval: Duration,
+    ) -> Duration {
+        if !matches!(kind, ErrorKind::Status(code) if is_client_error(code)) {
+            self.client_errors = 0;
+        }
+        if !is_transient(kind) {
+            self.transient_failures = 0;
+        }
+
+        let delay = match kind {
+            ErrorKind::Status(429) => retry_after.unwrap_or(interval),
+            ErrorKind::Status(code) if is_client_error(code) => {
+                self.client_errors += 1;
+                if self.client_errors >= CLIENT_ERROR_THRESHOLD {
+                    LONG_BACKOFF
+                } else {
+                    interval
+                }
+            }
+            kind if is_transient(kind) => {
+                self.transient_failures += 1;
+                let exponent = (self.transient_failures - 1).min(16);
+                interval
+                    .saturating_mul(1 << exponent)
+                    .min(MAX_BACKOFF.max(interval))
+            }
+            // Timeouts retry on the next tick; decode and database failures
+            // won't resolve faster by waiting longer
+            _ => interval,
+        };
+
+        delay.max(interval)
+    }
+}
+
+fn is_client_error(code: u16) -> bool {
+    (400..500).contains(&code) && code != 429
+}
+
+fn is_transient(kind: ErrorKind) -> bool {
+    match kind {
+        ErrorKind::Network => true,
+        ErrorKind::Status(code) => code >= 500,
+        _ => false,
+    }
+}
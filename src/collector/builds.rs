@@ -0,0 +1,109 @@
+//! VRChat client build tracking
+//!
+//! Polls the public VRChat API config and records each client build version
+//! the first time it is seen. Runs as a scheduled job rather than a poll loop:
+//! builds change at most a few times a week.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use reqwest::Client;
+use sea_orm::DatabaseConnection;
+use tracing::{debug, info};
+
+use crate::repository::BuildRepository;
+use crate::scheduler::{JobResult, Schedule, Scheduler};
+
+use super::client::{fetch_json, vrchat_api_url};
+use super::models::RemoteConfig;
+
+/// How often the config endpoint is checked
+const BUILD_POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Register the build tracking job
+pub fn register(scheduler: &mut Scheduler, client: Client, db: Arc<DatabaseConnection>) {
+    scheduler.register(
+        "vrchat_builds",
+        Schedule::Interval(BUILD_POLL_INTERVAL),
+        move || poll(client.clone(), db.clone()),
+    );
+}
+
+/// Fetch /config and record the build version if new
+async fn poll(client: Client, db: Arc<DatabaseConnection>) -> JobResult {
+    let config: RemoteConfig = fetch_json(&client, &vrchat_api_url("/config")).await?;
+
+    let is_new = BuildRepository::new(db)
+        .record(
+            &config.build_version_tag,
+            config.deployment_group.clone(),
+            Utc::now(),
+        )
+        .await?;
+
+    if is_new {
+        info!(
+            version = %config.build_version_tag,
+            deployment_group = ?config.deployment_group,
+            "New VRChat build seen"
+        );
+    } else {
+        debug!(version = %config.build_version_tag, "VRChat build unchanged");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::client::{FetchError, decode};
+
+    /// Trimmed /config response; the real one has a few hundred more fields
+    const CONFIG: &str = r#"{
+        "address": "",
+        "announcements": [],
+        "appName": "VrChat",
+        "buildVersionTag": "public-standalone-1580-release",
+        "clientApiKey": "key",
+        "deploymentGroup": "blue",
+        "downloadUrls": { "sdk3-worlds": "https://example.invalid" },
+        "serverName": "prod-api-blue-abc"
+    }"#;
+
+    #[test]
+    fn test_parse_remote_config() {
+        let config: RemoteConfig = decode(CONFIG.as_bytes()).unwrap();
+
+        assert_eq!(config.build_version_tag, "public-standalone-1580-release");
+        assert_eq!(config.deployment_group.as_deref(), Some("blue"));
+    }
+
+    #[test]
+    fn test_parse_remote_config_without_deployment_group() {
+        let mut raw: serde_json::Value = serde_json::from_str(CONFIG).unwrap();
+        raw.as_object_mut().unwrap().remove("deploymentGroup");
+
+        let config: RemoteConfig = decode(raw.to_string().as_bytes()).unwrap();
+
+        assert_eq!(config.deployment_group, None);
+    }
+
+    #[test]
+    fn test_parse_remote_config_without_build() {
+        let mut raw: serde_json::Value = serde_json::from_str(CONFIG).unwrap();
+        raw.as_object_mut().unwrap().remove("buildVersionTag");
+
+        let error = decode::<RemoteConfig>(raw.to_string().as_bytes()).unwrap_err();
+
+        assert!(matches!(error, FetchError::Decode { .. }), "{error}");
+    }
+
+    #[test]
+    fn test_parse_remote_config_snake_case_rejected() {
+        let raw = r#"{ "build_version_tag": "public-standalone-1580-release" }"#;
+
+        assert!(decode::<RemoteConfig>(raw.as_bytes()).is_err());
+    }
+}
@@ -1,29 +1,255 @@
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
 use reqwest::Client;
+use reqwest::header::RETRY_AFTER;
 use serde::de::DeserializeOwned;
 use thiserror::Error;
+use tracing::{info, warn};
+
+/// VRChat status page base URL
+pub const VRCHAT_STATUS_PAGE_BASE: &str = "https://status.vrchat.com";
 
 /// VRChat Status API base URL
 pub const VRCHAT_STATUS_API_BASE: &str = "https://status.vrchat.com/api/v2";
 
+/// VRChat API base URL (public, unauthenticated endpoints only)
+pub const VRCHAT_API_BASE: &str = "https://api.vrchat.cloud/api/1";
+
 /// CloudFront Metrics API base URL
 pub const CLOUDFRONT_METRICS_BASE: &str = "https://d31qqo63tn8lj0.cloudfront.net";
 
+/// Per-request timeout, shorter than the shared client's so a stalled
+/// attempt leaves time for a retry within the poll
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Retries of a transient failure before the poll gives up
+pub const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry, doubled for each further one (1s, 2s, 4s)
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound of the random delay added to each retry
+const RETRY_JITTER_MS: u64 = 250;
+
+/// Failure while fetching and decoding a remote endpoint
+#[derive(Debug, Error)]
+pub enum FetchError {
+    /// Connection, TLS, or body transfer failure
+    #[error("Network error: {0}")]
+    Network(#[source] reqwest::Error),
+
+    /// Request did not complete within [`REQUEST_TIMEOUT`]
+    #[error("Request timed out")]
+    Timeout,
+
+    /// Non-success HTTP status
+    #[error("HTTP status {code}")]
+    Status {
+        code: u16,
+        retry_after: Option<Duration>,
+    },
+
+    /// Response body did not match the expected shape
+    #[error("Response did not match expected schema at `{path}`: {source}")]
+    Decode {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+impl FetchError {
+    /// Classify a reqwest error
+    pub fn from_reqwest(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            return Self::Timeout;
+        }
+        match error.status() {
+            Some(status) => Self::Status {
+                code: status.as_u16(),
+                retry_after: None,
+            },
+            None => Self::Network(error),
+        }
+    }
+}
+
+impl FetchError {
+    /// Whether retrying the request may succeed
+    ///
+    /// Network failures, timeouts, and 5xx responses are transient. 4xx
+    /// responses (including 429, which the poll loop backs off from) and
+    /// decode failures will not change on an immediate retry.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network(_) | Self::Timeout => true,
+            Self::Status { code, .. } => *code >= 500,
+            Self::Decode { .. } => false,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum CollectorError {
-    #[error("HTTP request failed: {0}")]
-    Http(#[from] reqwest::Error),
+    #[error("{0}")]
+    Fetch(#[from] FetchError),
 
     #[error("Database error: {0}")]
     Database(#[from] sea_orm::DbErr),
 }
 
+impl CollectorError {
+    /// Error category used for backoff decisions and health reporting
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Fetch(FetchError::Network(_)) => ErrorKind::Network,
+            Self::Fetch(FetchError::Timeout) => ErrorKind::Timeout,
+            Self::Fetch(FetchError::Status { code, .. }) => ErrorKind::Status(*code),
+            Self::Fetch(FetchError::Decode { .. }) => ErrorKind::Decode,
+            Self::Database(_) => ErrorKind::Database,
+        }
+    }
+
+    /// Server-requested delay from a `Retry-After` header, if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::Fetch(FetchError::Status { retry_after, .. }) => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+/// Category of a collector failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Network,
+    Timeout,
+    Status(u16),
+    Decode,
+    Database,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network => f.write_str("network"),
+            Self::Timeout => f.write_str("timeout"),
+            Self::Status(code) => write!(f, "HTTP {}", code),
+            Self::Decode => f.write_str("decode"),
+            Self::Database => f.write_str("database"),
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, CollectorError>;
 
 /// Fetch JSON from a URL and deserialize to type T
-pub async fn fetch_json<T: DeserializeOwned>(client: &Client, url: &str) -> Result<T> {
-    let response = client.get(url).send().await?.error_for_status()?;
-    let data = response.json::<T>().await?;
-    Ok(data)
+pub async fn fetch_json<T: DeserializeOwned>(
+    client: &Client,
+    url: &str,
+) -> std::result::Result<T, FetchError> {
+    let body = fetch_body(client, url).await?;
+    decode(&body)
+}
+
+/// Fetch a response body, retrying transient failures
+///
+/// Up to [`MAX_RETRIES`] retries with exponential backoff and jitter; the
+/// last error is returned once they are used up.
+pub async fn fetch_body(client: &Client, url: &str) -> std::result::Result<Vec<u8>, FetchError> {
+    let mut retries = 0;
+    loop {
+        match fetch_once(client, url, REQUEST_TIMEOUT).await {
+            Ok(body) => {
+                if retries > 0 {
+                    info!(url, retries, "Request succeeded after retries");
+                }
+                return Ok(body);
+            }
+            Err(e) if e.is_retryable() && retries < MAX_RETRIES => {
+                let delay = retry_delay(retries);
+                retries += 1;
+                warn!(
+                    url,
+                    retries,
+                    error = %e,
+                    retry_in_ms = delay.as_millis() as u64,
+                    "Request failed, retrying"
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                if retries > 0 {
+                    warn!(url, retries, error = %e, "Request failed after retries");
+                }
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Backoff before retry number `retries + 1`, with jitter
+fn retry_delay(retries: u32) -> Duration {
+    let jitter = Duration::from_millis(rand::rng().random_range(0..=RETRY_JITTER_MS));
+    RETRY_BASE_DELAY * 2u32.pow(retries) + jitter
+}
+
+/// Fetch a response body once, classifying non-success statuses
+async fn fetch_once(
+    client: &Client,
+    url: &str,
+    timeout: Duration,
+) -> std::result::Result<Vec<u8>, FetchError> {
+    let response = client
+        .get(url)
+        .timeout(timeout)
+        .send()
+        .await
+        .map_err(FetchError::from_reqwest)?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()));
+        return Err(FetchError::Status {
+            code: status.as_u16(),
+            retry_after,
+        });
+    }
+
+    let body = response.bytes().await.map_err(FetchError::from_reqwest)?;
+    Ok(body.to_vec())
+}
+
+/// Deserialize a JSON body, recording the path of the first mismatch
+pub fn decode<T: DeserializeOwned>(body: &[u8]) -> std::result::Result<T, FetchError> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| FetchError::Decode {
+        path: e.path().to_string(),
+        source: e.into_inner(),
+    })
+}
+
+/// Parse a `Retry-After` header value (delay in seconds or an HTTP date)
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means "retry now"
+    Some(
+        (at.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
 }
 
 /// Build full URL for VRChat Status API endpoint
@@ -31,7 +257,303 @@ pub fn status_api_url(endpoint: &str) -> String {
     format!("{}{}", VRCHAT_STATUS_API_BASE, endpoint)
 }
 
+/// Build full URL for a page of the public VRChat status page
+pub fn status_page_url(path: &str) -> String {
+    format!("{}{}", VRCHAT_STATUS_PAGE_BASE, path)
+}
+
 /// Build full URL for CloudFront Metrics API endpoint
 pub fn metrics_api_url(endpoint: &str) -> String {
     format!("{}{}", CLOUDFRONT_METRICS_BASE, endpoint)
 }
+
+/// Build full URL for VRChat API endpoint
+pub fn vrchat_api_url(endpoint: &str) -> String {
+    format!("{}{}", VRCHAT_API_BASE, endpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serde::Deserialize;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Timeout for requests to the mock server
+    const TEST_TIMEOUT: Duration = Duration::from_millis(200);
+
+    #[derive(Debug, Deserialize)]
+    struct Body {
+        ok: bool,
+    }
+
+    /// Minimal HTTP server answering each request by path
+    ///
+    /// `respond` returns the raw status line and headers to send (a JSON body
+    /// is appended for 200s), or `None` to never answer.
+    struct MockServer {
+        base: String,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl MockServer {
+        async fn start(respond: fn(&str) -> Option<&'static str>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let base = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(AtomicUsize::new(0));
+            let counter = requests.clone();
+            tokio::spawn(async move {
+                loop {
+                    let Ok((mut socket, _)) = listener.accept().await else {
+                        return;
+                    };
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        let mut request = Vec::new();
+                        let mut buf = [0; 1024];
+                        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                            match socket.read(&mut buf).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => request.extend_from_slice(&buf[..n]),
+                            }
+                        }
+                        let request = String::from_utf8_lossy(&request);
+                        let path = request.split_whitespace().nth(1).unwrap_or("/");
+                        let Some(head) = respond(path) else {
+                            // Hold the connection open without answering
+                            tokio::time::sleep(Duration::from_secs(60)).await;
+                            return;
+                        };
+                        let body = if head.starts_with("HTTP/1.1 200") {
+                            r#"{"ok":true}"#
+                        } else {
+                            ""
+                        };
+                        let response = format!(
+                            "{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                            head,
+                            body.len(),
+                            body
+                        );
+                        let _ = socket.write_all(response.as_bytes()).await;
+                    });
+                }
+            });
+            Self { base, requests }
+        }
+
+        fn url(&self, path: &str) -> String {
+            format!("{}{}", self.base, path)
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    fn routes(path: &str) -> Option<&'static str> {
+        match path {
+            "/ok" => Some("HTTP/1.1 200 OK\r\nContent-Type: application/json"),
+            "/missing" => Some("HTTP/1.1 404 Not Found"),
+            "/forbidden" => Some("HTTP/1.1 403 Forbidden"),
+            "/limited" => Some("HTTP/1.1 429 Too Many Requests\r\nRetry-After: 30"),
+            "/limited-no-header" => Some("HTTP/1.1 429 Too Many Requests"),
+            "/error" => Some("HTTP/1.1 500 Internal Server Error"),
+            "/unavailable" => Some("HTTP/1.1 503 Service Unavailable\r\nRetry-After: 5"),
+            "/moved" => Some("HTTP/1.1 301 Moved Permanently\r\nLocation: /ok"),
+            "/moved-missing" => Some("HTTP/1.1 302 Found\r\nLocation: /missing"),
+            "/loop" => Some("HTTP/1.1 302 Found\r\nLocation: /loop"),
+            "/no-location" => Some("HTTP/1.1 302 Found"),
+            _ => None,
+        }
+    }
+
+    async fn fetch(server: &MockServer, path: &str) -> std::result::Result<Vec<u8>, FetchError> {
+        fetch_once(&Client::new(), &server.url(path), TEST_TIMEOUT).await
+    }
+
+    #[tokio::test]
+    async fn test_success() {
+        let server = MockServer::start(routes).await;
+
+        let body = fetch(&server, "/ok").await.unwrap();
+
+        assert_eq!(body, br#"{"ok":true}"#);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_classified_as_status() {
+        let server = MockServer::start(routes).await;
+        let cases = [
+            ("/missing", 404, None),
+            ("/forbidden", 403, None),
+            ("/limited", 429, Some(Duration::from_secs(30))),
+            ("/limited-no-header", 429, None),
+        ];
+        for (path, expected_code, expected_retry_after) in cases {
+            let error = fetch(&server, path).await.unwrap_err();
+
+            assert!(!error.is_retryable(), "{path}");
+            let FetchError::Status { code, retry_after } = error else {
+                panic!("{path}: expected a status error, got {error:?}");
+            };
+            assert_eq!(code, expected_code, "{path}");
+            assert_eq!(retry_after, expected_retry_after, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_classified_as_retryable_status() {
+        let server = MockServer::start(routes).await;
+        let cases = [
+            ("/error", 500, None),
+            ("/unavailable", 503, Some(Duration::from_secs(5))),
+        ];
+        for (path, expected_code, expected_retry_after) in cases {
+            let error = fetch(&server, path).await.unwrap_err();
+
+            assert!(error.is_retryable(), "{path}");
+            let FetchError::Status { code, retry_after } = error else {
+                panic!("{path}: expected a status error, got {error:?}");
+            };
+            assert_eq!(code, expected_code, "{path}");
+            assert_eq!(retry_after, expected_retry_after, "{path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_classified() {
+        let server = MockServer::start(routes).await;
+
+        let error = fetch(&server, "/hang").await.unwrap_err();
+
+        assert!(matches!(error, FetchError::Timeout), "{error:?}");
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_connection_refused_classified_as_network() {
+        // Bind then drop to get a port nothing listens on
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/ok", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = fetch_once(&Client::new(), &url, TEST_TIMEOUT)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, FetchError::Network(_)), "{error:?}");
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_redirects_followed() {
+        let server = MockServer::start(routes).await;
+
+        let body = fetch(&server, "/moved").await.unwrap();
+        let error = fetch(&server, "/moved-missing").await.unwrap_err();
+
+        assert_eq!(body, br#"{"ok":true}"#);
+        assert!(
+            matches!(error, FetchError::Status { code: 404, .. }),
+            "{error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirect_loop_classified_as_network() {
+        let server = MockServer::start(routes).await;
+
+        let error = fetch(&server, "/loop").await.unwrap_err();
+
+        assert!(matches!(error, FetchError::Network(_)), "{error:?}");
+    }
+
+    #[tokio::test]
+    async fn test_redirect_without_location_classified_as_status() {
+        let server = MockServer::start(routes).await;
+
+        let error = fetch(&server, "/no-location").await.unwrap_err();
+
+        assert!(
+            matches!(error, FetchError::Status { code: 302, .. }),
+            "{error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_error_not_retried() {
+        let server = MockServer::start(routes).await;
+
+        let error = fetch_body(&Client::new(), &server.url("/missing"))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, FetchError::Status { code: 404, .. }));
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_json_decode_error() {
+        let server = MockServer::start(routes).await;
+
+        let body: Body = fetch_json(&Client::new(), &server.url("/ok"))
+            .await
+            .unwrap();
+        let error = fetch_json::<Vec<Body>>(&Client::new(), &server.url("/ok"))
+            .await
+            .unwrap_err();
+
+        assert!(body.ok);
+        assert!(matches!(error, FetchError::Decode { .. }), "{error:?}");
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_collector_error_kind() {
+        let cases = [
+            (FetchError::Timeout.into(), ErrorKind::Timeout),
+            (
+                FetchError::Status {
+                    code: 429,
+                    retry_after: Some(Duration::from_secs(3)),
+                }
+                .into(),
+                ErrorKind::Status(429),
+            ),
+            (
+                CollectorError::Database(sea_orm::DbErr::Custom("locked".into())),
+                ErrorKind::Database,
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(error.kind(), expected, "{error}");
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc2822("Sun, 01 Mar 2026 12:00:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_retry_after(" 0 ", now), Some(Duration::ZERO));
+        assert_eq!(
+            parse_retry_after("Sun, 01 Mar 2026 12:01:30 GMT", now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_retry_after("Sun, 01 Mar 2026 11:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(parse_retry_after("-5", now), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Per-poller health tracking
+//!
+//! The poll loops record each outcome here so `/admin show` can display which
+//! pollers are failing, why, and when they will next retry.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::client::ErrorKind;
+
+/// Most recent failure of a poller
+#[derive(Debug, Clone, Copy)]
+pub struct PollFailure {
+    pub at: DateTime<Utc>,
+    pub kind: ErrorKind,
+}
+
+/// Health of a single poller
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PollerHealth {
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_failure: Option<PollFailure>,
+    /// Failures since the last success
+    pub consecutive_failures: u32,
+    /// When the next poll is due, if delayed by backoff
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+impl PollerHealth {
+    /// Whether the most recent poll failed
+    pub fn is_failing(&self) -> bool {
+        self.consecutive_failures > 0
+    }
+}
+
+/// Health of all pollers, keyed by poller name
+#[derive(Debug, Default)]
+pub struct CollectorHealth {
+    pollers: Mutex<BTreeMap<&'static str, PollerHealth>>,
+}
+
+impl CollectorHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a successful poll, returning the failures it ended
+    pub fn record_success(&self, poller: &'static str, now: DateTime<Utc>) -> u32 {
+        let mut pollers = self
+            .pollers
+            .lock()
+            .expect("collector health mutex poisoned");
+        let health = pollers.entry(poller).or_default();
+        let failures = health.consecutive_failures;
+        health.last_success = Some(now);
+        health.consecutive_failures = 0;
+        health.retry_at = None;
+        failures
+    }
+
+    /// Record a failed poll and the delay before the next attempt, returning
+    /// the failures since the last success
+    pub fn record_failure(
+        &self,
+        poller: &'static str,
+        now: DateTime<Utc>,
+        kind: ErrorKind,
+        delay: Duration,
+    ) -> u32 {
+        let mut pollers = self
+            .pollers
+            .lock()
+            .expect("collector health mutex poisoned");
+        let health = pollers.entry(poller).or_default();
+        health.last_failure = Some(PollFailure { at: now, kind });
+        health.consecutive_failures += 1;
+        health.retry_at = chrono::Duration::from_std(delay).ok().map(|d| now + d);
+        health.consecutive_failures
+    }
+
+    /// Snapshot of all pollers that have completed at least one poll, by name
+    pub fn snapshot(&self) -> Vec<(&'static str, PollerHealth)> {
+        let pollers = self
+            .pollers
+            .lock()
+            .expect("collector health mutex poisoned");
+        pollers
+            .iter()
+            .map(|(name, health)| (*name, *health))
+            .collect()
+    }
+}
@@ -2,26 +2,42 @@ use std::collections::HashSet;
 
 use chrono::Utc;
 use reqwest::Client;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
 use tracing::{debug, info, warn};
 
+use crate::alerts::delivery::Notifier;
+use crate::alerts::incident::{self as incident_alert, IncidentChange, IncidentUpdateChange};
 use crate::entity::{incident_updates, incidents};
 
-use super::client::{Result, fetch_json, status_api_url};
+use super::client::{Result, status_api_url};
 use super::models::UnresolvedIncidentsResponse;
+use super::schema::{SchemaMonitor, fetch_checked};
 
 /// Poll /incidents/unresolved.json and handle incident resolution detection
-pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
-    let url = status_api_url("/incidents/unresolved.json");
+///
+/// New incidents and status changes (including resolution) are handed to
+/// `alerts::incident` for notices, along with updates posted to incidents
+/// that were already announced (see `updates_to_stream`).
+pub async fn poll(
+    client: &Client,
+    db: &DatabaseConnection,
+    monitor: &SchemaMonitor,
+    notifier: &Notifier,
+) -> Result<()> {
+    let endpoint = "/incidents/unresolved.json";
+    let url = status_api_url(endpoint);
 
     // Fetch API - abort on failure (do NOT modify DB on failure)
-    let response: UnresolvedIncidentsResponse = match fetch_json(client, &url).await {
-        Ok(r) => r,
-        Err(e) => {
-            warn!("API fetch failed, skipping resolution detection: {}", e);
-            return Err(e);
-        }
-    };
+    let response: UnresolvedIncidentsResponse =
+        match fetch_checked(client, &url, endpoint, monitor).await {
+            Ok(r) => r,
+            Err(e) => {
+                warn!("API fetch failed, skipping resolution detection: {}", e);
+                return Err(e);
+            }
+        };
 
     let api_ids: HashSet<_> = response.incidents.iter().map(|i| i.id.as_str()).collect();
     let now = Utc::now();
@@ -32,54 +48,150 @@ pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
         .all(db)
         .await?;
 
+    // (incident, status before this poll) for each new or changed incident
+    let mut changed = Vec::new();
+
     // Mark missing incidents as resolved
     for incident in unresolved_in_db {
         if !api_ids.contains(incident.id.as_str()) {
             let incident_id = incident.id.clone();
+            let previous_status = incident.status.clone();
             let mut active: incidents::ActiveModel = incident.into();
             active.status = Set("resolved".to_string());
             active.resolved_at = Set(Some(now));
             active.updated_at = Set(now);
-            active.update(db).await?;
+            let resolved = active.update(db).await?;
             info!(incident_id = %incident_id, "Marked incident as resolved");
+            changed.push((resolved, Some(previous_status)));
         }
     }
 
     // Upsert API response
+    let mut new_updates = Vec::new();
     for incident in response.incidents {
-        upsert_incident(db, &incident).await?;
+        if let Some(change) = upsert_incident(db, &incident).await? {
+            changed.push(change);
+        }
 
         // Process incident updates
         for update in &incident.incident_updates {
-            upsert_incident_update(db, &incident.id, update).await?;
+            if let Some(inserted) = upsert_incident_update(db, &incident.id, update).await? {
+                new_updates.push(inserted);
+            }
         }
     }
 
+    // Attach latest updates once all of them are stored
+    let mut changes = Vec::with_capacity(changed.len());
+    for (incident, previous_status) in changed {
+        let latest_update = latest_update(db, &incident.id).await?;
+        changes.push(IncidentChange {
+            incident,
+            previous_status,
+            latest_update,
+        });
+    }
+
+    let mut updates = Vec::new();
+    for update in updates_to_stream(new_updates, &changes) {
+        let Some(incident) = incidents::Entity::find_by_id(&update.incident_id)
+            .one(db)
+            .await?
+        else {
+            continue;
+        };
+        let previous_status = previous_update_status(db, &update).await?;
+        updates.push(IncidentUpdateChange {
+            incident,
+            update,
+            previous_status,
+        });
+    }
+    incident_alert::spawn_notices(notifier.clone(), db.clone(), changes, updates);
+
     Ok(())
 }
 
+/// New updates that need a notice of their own
+///
+/// A new incident's notice already covers the updates it arrived with, and a
+/// status change notice carries the incident's latest update.
+fn updates_to_stream(
+    new_updates: Vec<incident_updates::Model>,
+    changes: &[IncidentChange],
+) -> Vec<incident_updates::Model> {
+    new_updates
+        .into_iter()
+        .filter(|update| {
+            !changes.iter().any(|change| {
+                change.incident.id == update.incident_id
+                    && (change.previous_status.is_none()
+                        || change
+                            .latest_update
+                            .as_ref()
+                            .is_some_and(|latest| latest.id == update.id))
+            })
+        })
+        .collect()
+}
+
+/// Newest stored update of an incident
+async fn latest_update(
+    db: &DatabaseConnection,
+    incident_id: &str,
+) -> Result<Option<incident_updates::Model>> {
+    let latest = incident_updates::Entity::find()
+        .filter(incident_updates::Column::IncidentId.eq(incident_id))
+        .order_by_desc(incident_updates::Column::PublishedAt)
+        .one(db)
+        .await?;
+    Ok(latest)
+}
+
+/// Status of the update published before `update`, if any
+async fn previous_update_status(
+    db: &DatabaseConnection,
+    update: &incident_updates::Model,
+) -> Result<Option<String>> {
+    let previous = incident_updates::Entity::find()
+        .filter(incident_updates::Column::IncidentId.eq(&update.incident_id))
+        .filter(incident_updates::Column::PublishedAt.lt(update.published_at))
+        .order_by_desc(incident_updates::Column::PublishedAt)
+        .one(db)
+        .await?;
+    Ok(previous.map(|previous| previous.status))
+}
+
+/// Insert or update an incident
+///
+/// Returns the stored incident and its previous status (`None` if new) when
+/// it was inserted or its status changed.
 async fn upsert_incident(
     db: &DatabaseConnection,
     incident: &super::models::Incident,
-) -> Result<()> {
+) -> Result<Option<(incidents::Model, Option<String>)>> {
     let existing = incidents::Entity::find_by_id(&incident.id).one(db).await?;
 
     match existing {
         Some(existing) => {
             // Update if status, impact, title, or updated_at changed
-            let needs_update = existing.status != incident.status
-                || existing.impact != incident.impact
+            let needs_update = existing.status != incident.status.as_str()
+                || existing.impact != incident.impact.as_str()
                 || existing.title != incident.name
                 || existing.updated_at != incident.updated_at;
 
             if needs_update {
+                let previous_status = existing.status.clone();
                 let mut active: incidents::ActiveModel = existing.into();
                 active.title = Set(incident.name.clone());
-                active.impact = Set(incident.impact.clone());
-                active.status = Set(incident.status.clone());
+                active.impact = Set(incident.impact.to_string());
+                active.status = Set(incident.status.to_string());
                 active.updated_at = Set(incident.updated_at);
-                active.update(db).await?;
+                let updated = active.update(db).await?;
                 debug!(incident_id = %incident.id, "Updated incident");
+                if updated.status != previous_status {
+                    return Ok(Some((updated, Some(previous_status))));
+                }
             }
         }
         None => {
@@ -87,26 +199,30 @@ async fn upsert_incident(
             let active = incidents::ActiveModel {
                 id: Set(incident.id.clone()),
                 title: Set(incident.name.clone()),
-                impact: Set(incident.impact.clone()),
-                status: Set(incident.status.clone()),
+                impact: Set(incident.impact.to_string()),
+                status: Set(incident.status.to_string()),
                 started_at: Set(incident.created_at),
                 resolved_at: Set(None),
                 created_at: Set(incident.created_at),
                 updated_at: Set(incident.updated_at),
             };
-            active.insert(db).await?;
+            let inserted = active.insert(db).await?;
             info!(incident_id = %incident.id, title = %incident.name, "Inserted new incident");
+            return Ok(Some((inserted, None)));
         }
     }
 
-    Ok(())
+    Ok(None)
 }
 
+/// Insert an incident update unless it is already stored
+///
+/// Returns the update when it was inserted.
 async fn upsert_incident_update(
     db: &DatabaseConnection,
     incident_id: &str,
     update: &super::models::IncidentUpdate,
-) -> Result<()> {
+) -> Result<Option<incident_updates::Model>> {
     // Incident updates are immutable - skip if exists
     let existing = incident_updates::Entity::find_by_id(&update.id)
         .one(db)
@@ -117,14 +233,176 @@ async fn upsert_incident_update(
             id: Set(update.id.clone()),
             incident_id: Set(incident_id.to_string()),
             body: Set(update.body.clone()),
-            status: Set(update.status.clone()),
+            status: Set(update.status.to_string()),
             published_at: Set(update.created_at),
             created_at: Set(update.created_at),
             updated_at: Set(update.created_at),
         };
-        active.insert(db).await?;
+        let inserted = active.insert(db).await?;
         debug!(update_id = %update.id, "Inserted incident update");
+        return Ok(Some(inserted));
     }
 
-    Ok(())
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::models::Incident;
+    use crate::database::connect_in_memory;
+    use chrono::{DateTime, TimeZone};
+
+    const FIXTURE: &str = r#"{
+        "id": "inc1",
+        "name": "Login issues",
+        "status": "identified",
+        "impact": "major",
+        "created_at": "2026-01-10T12:00:00Z",
+        "updated_at": "2026-01-10T12:30:00Z",
+        "incident_updates": [
+            {
+                "id": "upd2",
+                "status": "identified",
+                "body": "The cause has been identified.",
+                "created_at": "2026-01-10T12:30:00Z"
+            },
+            {
+                "id": "upd1",
+                "status": "investigating",
+                "body": "We are looking into login failures.",
+                "created_at": "2026-01-10T12:00:00Z"
+            }
+        ]
+    }"#;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 10, 12, minute, 0).unwrap()
+    }
+
+    fn incident(id: &str) -> incidents::Model {
+        incidents::Model {
+            id: id.to_string(),
+            title: "Login issues".to_string(),
+            impact: "major".to_string(),
+            status: "identified".to_string(),
+            started_at: at(0),
+            resolved_at: None,
+            created_at: at(0),
+            updated_at: at(30),
+        }
+    }
+
+    fn update(id: &str, incident_id: &str, minute: u32) -> incident_updates::Model {
+        incident_updates::Model {
+            id: id.to_string(),
+            incident_id: incident_id.to_string(),
+            body: String::new(),
+            status: "identified".to_string(),
+            published_at: at(minute),
+            created_at: at(minute),
+            updated_at: at(minute),
+        }
+    }
+
+    fn change(
+        incident_id: &str,
+        previous_status: Option<&str>,
+        latest: Option<incident_updates::Model>,
+    ) -> IncidentChange {
+        IncidentChange {
+            incident: incident(incident_id),
+            previous_status: previous_status.map(str::to_string),
+            latest_update: latest,
+        }
+    }
+
+    fn ids(updates: &[incident_updates::Model]) -> Vec<&str> {
+        updates.iter().map(|update| update.id.as_str()).collect()
+    }
+
+    async fn store(db: &DatabaseConnection, incident: &Incident) -> Vec<incident_updates::Model> {
+        upsert_incident(db, incident).await.unwrap();
+        let mut inserted = Vec::new();
+        for update in &incident.incident_updates {
+            if let Some(update) = upsert_incident_update(db, &incident.id, update)
+                .await
+                .unwrap()
+            {
+                inserted.push(update);
+            }
+        }
+        inserted
+    }
+
+    #[tokio::test]
+    async fn test_updates_inserted_once() {
+        let db = connect_in_memory().await;
+        let fixture: Incident = serde_json::from_str(FIXTURE).unwrap();
+
+        let first = store(&db, &fixture).await;
+        let second = store(&db, &fixture).await;
+
+        assert_eq!(ids(&first), vec!["upd2", "upd1"]);
+        assert!(second.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_previous_update_status() {
+        let db = connect_in_memory().await;
+        let fixture: Incident = serde_json::from_str(FIXTURE).unwrap();
+        let inserted = store(&db, &fixture).await;
+
+        let newest = inserted.iter().find(|update| update.id == "upd2").unwrap();
+        let oldest = inserted.iter().find(|update| update.id == "upd1").unwrap();
+        assert_eq!(
+            previous_update_status(&db, newest)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("investigating")
+        );
+        assert_eq!(previous_update_status(&db, oldest).await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_stream_updates_of_unchanged_incident() {
+        let new_updates = vec![update("upd3", "inc1", 40), update("upd4", "inc1", 50)];
+
+        let streamed = updates_to_stream(new_updates, &[]);
+
+        assert_eq!(ids(&streamed), vec!["upd3", "upd4"]);
+    }
+
+    #[test]
+    fn test_no_stream_for_new_incident() {
+        let new_updates = vec![update("upd1", "inc1", 0), update("upd2", "inc1", 30)];
+        let changes = [change("inc1", None, Some(update("upd2", "inc1", 30)))];
+
+        assert!(updates_to_stream(new_updates, &changes).is_empty());
+    }
+
+    #[test]
+    fn test_status_change_carries_latest_update() {
+        let new_updates = vec![update("upd3", "inc1", 40), update("upd4", "inc1", 50)];
+        let changes = [change(
+            "inc1",
+            Some("investigating"),
+            Some(update("upd4", "inc1", 50)),
+        )];
+
+        let streamed = updates_to_stream(new_updates, &changes);
+
+        assert_eq!(ids(&streamed), vec!["upd3"]);
+    }
+
+    #[test]
+    fn test_changes_of_other_incidents_ignored() {
+        let new_updates = vec![update("upd3", "inc1", 40)];
+        let changes = [change("inc2", None, Some(update("upd9", "inc2", 40)))];
+
+        let streamed = updates_to_stream(new_updates, &changes);
+
+        assert_eq!(ids(&streamed), vec!["upd3"]);
+    }
 }
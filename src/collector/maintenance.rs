@@ -7,16 +7,29 @@ use tracing::{debug, info};
 
 use crate::entity::maintenances;
 
-use super::client::{Result, fetch_json, status_api_url};
+use super::client::{Result, status_api_url};
 use super::models::{Maintenance as ApiMaintenance, MaintenancesResponse};
+use super::schema::{SchemaMonitor, fetch_checked};
 
 /// Poll /scheduled-maintenances/upcoming.json and /scheduled-maintenances/active.json
-pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
-    let upcoming_url = status_api_url("/scheduled-maintenances/upcoming.json");
-    let active_url = status_api_url("/scheduled-maintenances/active.json");
-
-    let upcoming: MaintenancesResponse = fetch_json(client, &upcoming_url).await?;
-    let active: MaintenancesResponse = fetch_json(client, &active_url).await?;
+pub async fn poll(client: &Client, db: &DatabaseConnection, monitor: &SchemaMonitor) -> Result<()> {
+    let upcoming_endpoint = "/scheduled-maintenances/upcoming.json";
+    let active_endpoint = "/scheduled-maintenances/active.json";
+
+    let upcoming: MaintenancesResponse = fetch_checked(
+        client,
+        &status_api_url(upcoming_endpoint),
+        upcoming_endpoint,
+        monitor,
+    )
+    .await?;
+    let active: MaintenancesResponse = fetch_checked(
+        client,
+        &status_api_url(active_endpoint),
+        active_endpoint,
+        monitor,
+    )
+    .await?;
 
     let now = Utc::now();
 
@@ -85,7 +98,7 @@ async fn upsert_maintenance(db: &DatabaseConnection, m: &ApiMaintenance) -> Resu
             if should_update(&existing, m) {
                 let mut active: maintenances::ActiveModel = existing.into();
                 active.title = Set(m.name.clone());
-                active.status = Set(m.status.clone());
+                active.status = Set(m.status.to_string());
                 active.scheduled_for = Set(m.scheduled_for);
                 active.scheduled_until = Set(m.scheduled_until);
                 active.updated_at = Set(m.updated_at);
@@ -97,7 +110,7 @@ async fn upsert_maintenance(db: &DatabaseConnection, m: &ApiMaintenance) -> Resu
             let active = maintenances::ActiveModel {
                 id: Set(m.id.clone()),
                 title: Set(m.name.clone()),
-                status: Set(m.status.clone()),
+                status: Set(m.status.to_string()),
                 scheduled_for: Set(m.scheduled_for),
                 scheduled_until: Set(m.scheduled_until),
                 created_at: Set(m.created_at),
@@ -112,7 +125,7 @@ async fn upsert_maintenance(db: &DatabaseConnection, m: &ApiMaintenance) -> Resu
 }
 
 fn should_update(existing: &maintenances::Model, incoming: &ApiMaintenance) -> bool {
-    existing.status != incoming.status
+    existing.status != incoming.status.as_str()
         || existing.scheduled_for != incoming.scheduled_for
         || existing.scheduled_until != incoming.scheduled_until
 }
@@ -1,4 +1,7 @@
-use chrono::{DateTime, TimeZone, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, DurationRound, TimeZone, Utc};
 use reqwest::Client;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
@@ -6,32 +9,84 @@ use sea_orm::{
 };
 use tracing::{debug, warn};
 
-use crate::entity::metric_logs;
+use crate::cache::MetricCache;
+use crate::entity::{bot_config, metric_logs};
+use crate::repository::{LogRepository, Repositories};
 
-use super::client::{Result, fetch_json, metrics_api_url};
+use super::client::{ErrorKind, Result, fetch_json, metrics_api_url};
 use super::models::{CLOUDFRONT_METRICS, MetricDefinition, MetricsResponse};
 
 /// Default interval for CloudFront metrics (60 seconds)
 const METRIC_INTERVAL_SEC: i64 = 60;
 
+/// `interval_sec` of rows written by [`downsample_metrics`]
+pub const DOWNSAMPLED_INTERVAL_SEC: i64 = 60 * 60;
+
+/// Config key for the age in hours after which metric rows are downsampled
+pub const DOWNSAMPLE_AFTER_KEY: &str = "metrics_downsample_after_hours";
+
+/// Default downsampling age in hours (used if config missing)
+pub const DEFAULT_DOWNSAMPLE_AFTER_HOURS: i64 = 24;
+
+/// Shortest downsampling age (keeps the 24h dashboard at full resolution)
+const MIN_DOWNSAMPLE_AFTER_HOURS: i64 = 24;
+
+/// Longest downsampling age
+const MAX_DOWNSAMPLE_AFTER_HOURS: i64 = 30 * 24;
+
+/// Most hours downsampled per call, so a large backlog is spread over several runs
+const MAX_DOWNSAMPLE_HOURS_PER_RUN: i64 = 7 * 24;
+
+/// Result of one [`downsample_metrics`] call
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DownsampleSummary {
+    /// Rows at poll resolution that were replaced
+    pub raw_rows: u64,
+    /// Hourly rows written in their place
+    pub hourly_rows: usize,
+}
+
 /// Poll all CloudFront metrics endpoints
-pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
+///
+/// One failing metric doesn't stop the others. Returns the first decode failure
+/// (so the operator is notified), or the first error if every metric failed.
+/// Cached chart series of metrics that got new points are dropped.
+pub async fn poll(client: &Client, db: &DatabaseConnection, cache: &MetricCache) -> Result<()> {
+    let mut first_error = None;
+    let mut decode_error = None;
+    let mut any_succeeded = false;
+
     for metric in CLOUDFRONT_METRICS {
-        if let Err(e) = poll_metric(client, db, metric).await {
-            warn!(
-                metric = %metric.name,
-                error = %e,
-                "Failed to poll metric, skipping"
-            );
+        match poll_metric(client, db, cache, metric).await {
+            Ok(()) => any_succeeded = true,
+            Err(e) => {
+                warn!(
+                    metric = %metric.name,
+                    error = %e,
+                    "Failed to poll metric, skipping"
+                );
+                if e.kind() == ErrorKind::Decode {
+                    decode_error.get_or_insert(e);
+                } else {
+                    first_error.get_or_insert(e);
+                }
+            }
         }
     }
 
-    Ok(())
+    if let Some(e) = decode_error {
+        return Err(e);
+    }
+    match first_error {
+        Some(e) if !any_succeeded => Err(e),
+        _ => Ok(()),
+    }
 }
 
 async fn poll_metric(
     client: &Client,
     db: &DatabaseConnection,
+    cache: &MetricCache,
     metric: &MetricDefinition,
 ) -> Result<()> {
     let url = metrics_api_url(metric.endpoint);
@@ -74,6 +129,7 @@ async fn poll_metric(
     }
 
     if inserted_count > 0 {
+        cache.invalidate(metric.name).await;
         debug!(
             metric = %metric.name,
             count = inserted_count,
@@ -100,3 +156,91 @@ async fn get_latest_timestamp(
 
     Ok(result)
 }
+
+/// Get the downsampling age from database, falls back to default if missing
+pub async fn get_downsample_after_hours(db: &DatabaseConnection) -> i64 {
+    bot_config::Entity::find_by_id(DOWNSAMPLE_AFTER_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .filter(|hours| (MIN_DOWNSAMPLE_AFTER_HOURS..=MAX_DOWNSAMPLE_AFTER_HOURS).contains(hours))
+        .unwrap_or(DEFAULT_DOWNSAMPLE_AFTER_HOURS)
+}
+
+/// Replace metric rows older than the configured age with hourly averages
+///
+/// Rows are grouped by metric and UTC hour; each group becomes one row
+/// timestamped at the hour boundary with `interval_sec` set to
+/// [`DOWNSAMPLED_INTERVAL_SEC`], so later calls skip it. Only whole hours
+/// before the cutoff are touched, starting from the oldest, and at most
+/// `MAX_DOWNSAMPLE_HOURS_PER_RUN` per call. Each call's delete and insert run
+/// in one transaction.
+pub async fn downsample_metrics(
+    db: &Arc<DatabaseConnection>,
+) -> std::result::Result<DownsampleSummary, sea_orm::DbErr> {
+    let after_hours = get_downsample_after_hours(db).await;
+    let cutoff = floor_hour(Utc::now() - Duration::hours(after_hours));
+    let logs = LogRepository::new(db.clone());
+
+    let Some(oldest) = logs
+        .oldest_metric_finer_than(DOWNSAMPLED_INTERVAL_SEC, cutoff)
+        .await?
+    else {
+        return Ok(DownsampleSummary::default());
+    };
+    let start = floor_hour(oldest);
+    let end = (start + Duration::hours(MAX_DOWNSAMPLE_HOURS_PER_RUN)).min(cutoff);
+
+    let rows = logs
+        .metrics_finer_than(DOWNSAMPLED_INTERVAL_SEC, start, end)
+        .await?;
+
+    // (metric, hour) -> (unit, sum, count)
+    let mut groups: BTreeMap<(String, DateTime<Utc>), (String, f64, u32)> = BTreeMap::new();
+    for row in rows {
+        let group = groups
+            .entry((row.metric_name, floor_hour(row.timestamp)))
+            .or_insert((row.unit, 0.0, 0));
+        group.1 += row.value;
+        group.2 += 1;
+    }
+
+    let now = Utc::now();
+    let hourly: Vec<metric_logs::ActiveModel> = groups
+        .into_iter()
+        .map(
+            |((metric_name, hour), (unit, sum, count))| metric_logs::ActiveModel {
+                metric_name: Set(metric_name),
+                value: Set(sum / f64::from(count)),
+                unit: Set(unit),
+                interval_sec: Set(DOWNSAMPLED_INTERVAL_SEC),
+                timestamp: Set(hour),
+                created_at: Set(now),
+                ..Default::default()
+            },
+        )
+        .collect();
+    let hourly_rows = hourly.len();
+
+    let raw_rows = Repositories::new(db.clone())
+        .transaction(move |tx| {
+            Box::pin(async move {
+                tx.logs()
+                    .replace_metrics_finer_than(DOWNSAMPLED_INTERVAL_SEC, start, end, hourly)
+                    .await
+            })
+        })
+        .await?;
+
+    Ok(DownsampleSummary {
+        raw_rows,
+        hourly_rows,
+    })
+}
+
+/// Start of the UTC hour containing `at`
+fn floor_hour(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.duration_trunc(Duration::hours(1)).unwrap_or(at)
+}
@@ -1,23 +1,83 @@
+pub mod backoff;
+pub mod builds;
 pub mod client;
 pub mod config;
+pub mod health;
 pub mod incident;
 pub mod maintenance;
 pub mod metrics;
 pub mod models;
+pub mod schema;
 pub mod status;
 
+use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::Utc;
 use reqwest::Client;
 use sea_orm::DatabaseConnection;
 use tokio::sync::watch;
-use tokio::time::{Interval, MissedTickBehavior, interval};
-use tracing::{debug, error, info};
+use tokio::time::{Instant, Interval, MissedTickBehavior, interval, interval_at};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::alerts::delivery::Notifier;
+use crate::bot::maintenance_mode::MaintenanceMode;
+use crate::cache::MetricCache;
+use crate::leader::Leadership;
+
+use backoff::Backoff;
+use client::{CollectorError, FetchError};
 
 pub use config::{CollectorConfigRx, CollectorConfigTx};
+pub use health::CollectorHealth;
+pub use schema::SchemaMonitor;
+
+/// Consecutive failed polls after which a poller is logged as degraded
+const DEGRADED_AFTER_FAILURES: u32 = 5;
+
+/// Shared handles used by every poll loop
+struct PollContext<'a> {
+    monitor: &'a SchemaMonitor,
+    health: &'a CollectorHealth,
+    maintenance: &'a MaintenanceMode,
+    leadership: &'a Leadership,
+    shutdown: &'a CancellationToken,
+}
+
+/// State the collector shares with the rest of the bot
+pub struct CollectorHandles {
+    pub monitor: Arc<SchemaMonitor>,
+    pub health: Arc<CollectorHealth>,
+    pub maintenance: Arc<MaintenanceMode>,
+    pub leadership: Arc<Leadership>,
+    /// Delivery handles for component status and incident notices
+    pub notifier: Notifier,
+    /// Chart series dropped when new metric points are stored
+    pub metric_cache: Arc<MetricCache>,
+    /// Cancelled on shutdown; each poll loop exits after its current poll
+    pub shutdown: CancellationToken,
+}
 
 /// Start the data collector with all pollers running concurrently
-pub async fn start(client: Client, db: DatabaseConnection, config: CollectorConfigRx) {
+///
+/// Pollers only poll while this instance holds the collector lease, and
+/// return once the shutdown token is cancelled.
+pub async fn start(
+    client: Client,
+    db: DatabaseConnection,
+    config: CollectorConfigRx,
+    handles: CollectorHandles,
+) {
+    let CollectorHandles {
+        monitor,
+        health,
+        maintenance,
+        leadership,
+        notifier,
+        metric_cache,
+        shutdown,
+    } = handles;
     info!("Starting data collector...");
     info!(
         status = config.status.borrow().as_secs(),
@@ -27,42 +87,111 @@ pub async fn start(client: Client, db: DatabaseConnection, config: CollectorConf
         "Polling intervals (seconds)"
     );
 
+    // Every instance is a follower until its first lease write; wait for it
+    // so the leader doesn't skip its first polls
+    tokio::select! {
+        _ = shutdown.cancelled() => {
+            info!("Data collector stopped");
+            return;
+        }
+        _ = leadership.first_round() => {}
+    }
+
+    let poll_ctx = PollContext {
+        monitor: &monitor,
+        health: &health,
+        maintenance: &maintenance,
+        leadership: &leadership,
+        shutdown: &shutdown,
+    };
+
     tokio::join!(
-        poll_loop_dynamic("status", config.status.clone(), || {
-            status::poll(&client, &db)
+        poll_loop_dynamic("status", config.status.clone(), &poll_ctx, || {
+            status::poll(&client, &db, &monitor, &notifier)
         }),
-        poll_loop_dynamic("incident", config.incident.clone(), || {
-            incident::poll(&client, &db)
+        poll_loop_dynamic("incident", config.incident.clone(), &poll_ctx, || {
+            incident::poll(&client, &db, &monitor, &notifier)
         }),
-        poll_loop_dynamic("maintenance", config.maintenance.clone(), || {
-            maintenance::poll(&client, &db)
+        poll_loop_dynamic("maintenance", config.maintenance.clone(), &poll_ctx, || {
+            maintenance::poll(&client, &db, &monitor)
         }),
-        poll_loop_dynamic("metrics", config.metrics.clone(), || {
-            metrics::poll(&client, &db)
+        poll_loop_dynamic("metrics", config.metrics.clone(), &poll_ctx, || {
+            metrics::poll(&client, &db, &metric_cache)
         }),
     );
+    info!("Data collector stopped");
 }
 
 /// Poll loop with dynamic interval from watch channel
+///
+/// Failed polls delay the next tick according to `Backoff`; decode failures
+/// are also reported to the schema monitor so the operator is notified.
+/// Ticks are skipped while the bot is in maintenance mode or another instance
+/// holds the collector lease; a poll in progress when the lease is lost is
+/// allowed to finish. The loop exits on shutdown, after any poll in progress.
 async fn poll_loop_dynamic<F, Fut>(
     name: &'static str,
     mut interval_rx: watch::Receiver<Duration>,
+    poll_ctx: &PollContext<'_>,
     poll_fn: F,
 ) where
     F: Fn() -> Fut,
     Fut: std::future::Future<Output = client::Result<()>>,
 {
     let mut ticker = create_interval(*interval_rx.borrow());
+    let mut backoff = Backoff::default();
 
     loop {
         tokio::select! {
+            _ = poll_ctx.shutdown.cancelled() => {
+                debug!(poller = name, "Poll loop stopped");
+                break;
+            }
             _ = ticker.tick() => {
+                if poll_ctx.maintenance.is_enabled() {
+                    debug!(poller = name, "Maintenance mode, skipping poll");
+                    continue;
+                }
+                if !poll_ctx.leadership.is_leader() {
+                    debug!(poller = name, "Not the leader, skipping poll");
+                    continue;
+                }
                 match poll_fn().await {
                     Ok(()) => {
                         debug!(poller = name, "Polled");
+                        backoff.on_success();
+                        let failures = poll_ctx.health.record_success(name, Utc::now());
+                        if failures >= DEGRADED_AFTER_FAILURES {
+                            info!(poller = name, failures, "Poller recovered");
+                        }
                     }
                     Err(e) => {
-                        error!(poller = name, error = %e, "Poll failed");
+                        let period = *interval_rx.borrow();
+                        let kind = e.kind();
+                        let delay = backoff.on_failure(kind, e.retry_after(), period);
+                        error!(
+                            poller = name,
+                            kind = %kind,
+                            error = %e,
+                            retry_in_secs = delay.as_secs(),
+                            "Poll failed"
+                        );
+
+                        if let CollectorError::Fetch(FetchError::Decode { path, source }) = &e {
+                            poll_ctx
+                                .monitor
+                                .report(name, format!("decode failed at `{}`: {}", path, source))
+                                .await;
+                        }
+
+                        let failures =
+                            poll_ctx.health.record_failure(name, Utc::now(), kind, delay);
+                        if failures == DEGRADED_AFTER_FAILURES {
+                            warn!(poller = name, failures, kind = %kind, "Poller degraded");
+                        }
+                        if delay > period {
+                            ticker = create_interval_at(Instant::now() + delay, period);
+                        }
                     }
                 }
             }
@@ -84,3 +213,65 @@ fn create_interval(duration: Duration) -> Interval {
     ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
     ticker
 }
+
+fn create_interval_at(start: Instant, duration: Duration) -> Interval {
+    let mut ticker = interval_at(start, duration);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    ticker
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use serenity::all::Http;
+
+    use crate::bot::maintenance_mode::MaintenanceState;
+    use crate::database::connect_in_memory;
+
+    const TICK: Duration = Duration::from_millis(10);
+
+    #[tokio::test]
+    async fn test_poll_loop_pauses_during_maintenance() {
+        let db = connect_in_memory().await;
+        let monitor = SchemaMonitor::new(Arc::new(Http::new("")));
+        let health = CollectorHealth::new();
+        let maintenance = MaintenanceMode::new(MaintenanceState {
+            enabled: true,
+            message: None,
+        });
+        let leadership = Leadership::new("a".to_string());
+        leadership.set(true);
+        let shutdown = CancellationToken::new();
+        let poll_ctx = PollContext {
+            monitor: &monitor,
+            health: &health,
+            maintenance: &maintenance,
+            leadership: &leadership,
+            shutdown: &shutdown,
+        };
+        let (_interval_tx, interval_rx) = watch::channel(TICK);
+        let polls = AtomicUsize::new(0);
+
+        let driver = async {
+            tokio::time::sleep(TICK * 10).await;
+            let during = polls.load(Ordering::SeqCst);
+            maintenance
+                .set(&db, MaintenanceState::default())
+                .await
+                .unwrap();
+            tokio::time::sleep(TICK * 10).await;
+            shutdown.cancel();
+            during
+        };
+        let poll_loop = poll_loop_dynamic("status", interval_rx, &poll_ctx, || {
+            polls.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }
+        });
+        let (during, ()) = tokio::join!(driver, poll_loop);
+
+        assert_eq!(during, 0);
+        assert!(polls.load(Ordering::SeqCst) > 0);
+    }
+}
@@ -1,5 +1,101 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use serde_json::Value;
+
+// =============================================================================
+// String-backed Enums
+// =============================================================================
+
+/// Declare a string-backed enum that keeps unrecognized values
+///
+/// Statuspage adds enum values without notice. Unknown strings map to
+/// `Unknown(raw)` instead of failing the whole poll, and `as_str` returns the
+/// raw value so it is stored unchanged.
+macro_rules! string_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+        #[serde(from = "String")]
+        pub enum $name {
+            $($variant,)+
+            /// Value not recognized by this version (raw string preserved)
+            Unknown(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(raw) => raw,
+                }
+            }
+
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, Self::Unknown(_))
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(raw: String) -> Self {
+                match raw.as_str() {
+                    $($value => Self::$variant,)+
+                    _ => Self::Unknown(raw),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+string_enum!(
+    /// Overall page indicator and incident impact
+    Impact {
+        None => "none",
+        Minor => "minor",
+        Major => "major",
+        Critical => "critical",
+        Maintenance => "maintenance",
+    }
+);
+
+string_enum!(
+    /// Component status
+    ComponentStatus {
+        Operational => "operational",
+        DegradedPerformance => "degraded_performance",
+        PartialOutage => "partial_outage",
+        MajorOutage => "major_outage",
+        UnderMaintenance => "under_maintenance",
+    }
+);
+
+string_enum!(
+    /// Incident and incident update status
+    IncidentStatus {
+        Investigating => "investigating",
+        Identified => "identified",
+        Monitoring => "monitoring",
+        Resolved => "resolved",
+        Postmortem => "postmortem",
+    }
+);
+
+string_enum!(
+    /// Scheduled maintenance status
+    MaintenanceStatus {
+        Scheduled => "scheduled",
+        InProgress => "in_progress",
+        Verifying => "verifying",
+        Completed => "completed",
+    }
+);
 
 // =============================================================================
 // VRChat Status API (Atlassian Statuspage) Response Types
@@ -12,6 +108,9 @@ pub struct SummaryResponse {
     pub page: PageInfo,
     pub status: StatusInfo,
     pub components: Vec<Component>,
+    /// Top-level keys not modeled above (schema drift detection)
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,8 +120,7 @@ pub struct PageInfo {
 
 #[derive(Debug, Deserialize)]
 pub struct StatusInfo {
-    /// none | minor | major | critical
-    pub indicator: String,
+    pub indicator: Impact,
     pub description: String,
 }
 
@@ -30,24 +128,27 @@ pub struct StatusInfo {
 pub struct Component {
     pub id: String,
     pub name: String,
-    /// operational | degraded_performance | partial_outage | major_outage
-    pub status: String,
+    pub status: ComponentStatus,
+    /// Parent group component, if this component belongs to one
+    #[serde(default)]
+    pub group_id: Option<String>,
 }
 
 /// Response from /incidents/unresolved.json
 #[derive(Debug, Deserialize)]
 pub struct UnresolvedIncidentsResponse {
     pub incidents: Vec<Incident>,
+    /// Top-level keys not modeled above (schema drift detection)
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Incident {
     pub id: String,
     pub name: String,
-    /// investigating | identified | monitoring | resolved
-    pub status: String,
-    /// none | minor | major | critical
-    pub impact: String,
+    pub status: IncidentStatus,
+    pub impact: Impact,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub incident_updates: Vec<IncidentUpdate>,
@@ -56,7 +157,7 @@ pub struct Incident {
 #[derive(Debug, Deserialize)]
 pub struct IncidentUpdate {
     pub id: String,
-    pub status: String,
+    pub status: IncidentStatus,
     pub body: String,
     pub created_at: DateTime<Utc>,
 }
@@ -65,14 +166,16 @@ pub struct IncidentUpdate {
 #[derive(Debug, Deserialize)]
 pub struct MaintenancesResponse {
     pub scheduled_maintenances: Vec<Maintenance>,
+    /// Top-level keys not modeled above (schema drift detection)
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Maintenance {
     pub id: String,
     pub name: String,
-    /// scheduled | in_progress | completed
-    pub status: String,
+    pub status: MaintenanceStatus,
     pub scheduled_for: DateTime<Utc>,
     pub scheduled_until: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
@@ -141,3 +244,21 @@ pub const CLOUDFRONT_METRICS: &[MetricDefinition] = &[
         unit: "count",
     },
 ];
+
+// =============================================================================
+// VRChat API Response Types
+// Base URL: https://api.vrchat.cloud/api/1
+// =============================================================================
+
+/// Build fields of the public VRChat API config (/config)
+///
+/// The endpoint returns many more fields; only the build identity is kept.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteConfig {
+    /// Client build identifier, e.g. "public-standalone-1580-release"
+    pub build_version_tag: String,
+    /// Server deployment group ("blue"/"green")
+    #[serde(default)]
+    pub deployment_group: Option<String>,
+}
@@ -0,0 +1,603 @@
+//! Status page schema drift detection
+//!
+//! Statuspage occasionally renames fields or adds enum values. Each checked
+//! poll fetches the raw JSON, parses it into the typed response, and compares
+//! the typed result against a lenient extraction of critical fields. Unknown
+//! keys, unknown enum values, and mismatches are reported once per distinct
+//! issue: logged, kept in a small ring buffer for `/admin show`, and sent to
+//! the bot owner by DM. Typed parse failures surface as `FetchError::Decode`
+//! and are reported by the poll loop.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serenity::all::Http;
+use tracing::{error, info, warn};
+
+use crate::bot::owner;
+
+use super::client::{Result, decode, fetch_body};
+use super::models::{MaintenancesResponse, SummaryResponse, UnresolvedIncidentsResponse};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Number of recent issues kept for display
+const MAX_RECENT_ISSUES: usize = 20;
+
+/// Distinct issues remembered before the seen set is reset
+const MAX_SEEN_ISSUES: usize = 1000;
+
+// =============================================================================
+// Monitor
+// =============================================================================
+
+/// Schema issue observed during a poll
+#[derive(Debug, Clone)]
+pub struct SchemaIssue {
+    pub at: DateTime<Utc>,
+    pub endpoint: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    /// `endpoint: detail` keys already reported
+    seen: HashSet<String>,
+    recent: VecDeque<SchemaIssue>,
+}
+
+/// Collects schema issues and notifies the bot owner
+pub struct SchemaMonitor {
+    http: Arc<Http>,
+    state: Mutex<MonitorState>,
+}
+
+impl SchemaMonitor {
+    pub fn new(http: Arc<Http>) -> Self {
+        Self {
+            http,
+            state: Mutex::new(MonitorState::default()),
+        }
+    }
+
+    /// Report an issue; only the first occurrence of each distinct issue is surfaced
+    pub async fn report(&self, endpoint: &str, detail: String) {
+        if !self.record(endpoint, &detail) {
+            return;
+        }
+
+        warn!(endpoint, detail = %detail, "Status page schema issue detected");
+
+        let content = format!(
+            "**Status page schema change detected**\n`{}`: {}\n\
+             Polling continues; check `src/collector/models.rs` if data looks wrong.",
+            endpoint, detail
+        );
+        match owner::dm_owner(&self.http, content).await {
+            Ok(Some(owner_id)) => info!(owner_id = %owner_id, "Sent schema issue notice to owner"),
+            Ok(None) => warn!("Application has no owner, skipping schema issue notice"),
+            Err(e) => error!(error = %e, "Failed to DM owner about schema issue"),
+        }
+    }
+
+    /// Most recent distinct issues, oldest first
+    pub fn recent(&self) -> Vec<SchemaIssue> {
+        let state = self.state.lock().expect("schema monitor mutex poisoned");
+        state.recent.iter().cloned().collect()
+    }
+
+    /// Remember an issue; returns true if it had not been seen before
+    fn record(&self, endpoint: &str, detail: &str) -> bool {
+        let mut state = self.state.lock().expect("schema monitor mutex poisoned");
+
+        if state.seen.len() >= MAX_SEEN_ISSUES {
+            state.seen.clear();
+        }
+        if !state.seen.insert(format!("{}: {}", endpoint, detail)) {
+            return false;
+        }
+
+        if state.recent.len() >= MAX_RECENT_ISSUES {
+            state.recent.pop_front();
+        }
+        state.recent.push_back(SchemaIssue {
+            at: Utc::now(),
+            endpoint: endpoint.to_string(),
+            detail: detail.to_string(),
+        });
+        true
+    }
+}
+
+// =============================================================================
+// Checked Fetch
+// =============================================================================
+
+/// Response type that can be compared against its raw JSON
+pub trait SchemaCheck: DeserializeOwned {
+    /// Top-level keys that are known but intentionally not modeled
+    const IGNORED_KEYS: &'static [&'static str];
+
+    /// Unknown top-level keys and unrecognized enum values in the typed parse
+    fn unknown_values(&self) -> Vec<String>;
+
+    /// Critical fields as seen by the typed parse
+    fn critical_fields(&self) -> Vec<String>;
+
+    /// Critical fields extracted leniently from the raw JSON
+    fn critical_fields_raw(raw: &Value) -> Vec<String>;
+}
+
+/// Fetch a status page endpoint and check it for schema drift
+///
+/// A typed parse failure is returned as `FetchError::Decode`; everything else
+/// is reported and the typed response is returned.
+pub async fn fetch_checked<T: SchemaCheck>(
+    client: &Client,
+    url: &str,
+    endpoint: &str,
+    monitor: &SchemaMonitor,
+) -> Result<T> {
+    let body = fetch_body(client, url).await?;
+    let raw: Value = decode(&body)?;
+    let typed: T = decode(&body)?;
+
+    for issue in find_issues(&typed, &raw) {
+        monitor.report(endpoint, issue).await;
+    }
+
+    Ok(typed)
+}
+
+/// Compare a typed response against its raw JSON
+pub fn find_issues<T: SchemaCheck>(typed: &T, raw: &Value) -> Vec<String> {
+    let mut issues = typed.unknown_values();
+
+    let mut typed_fields = typed.critical_fields();
+    let mut raw_fields = T::critical_fields_raw(raw);
+    typed_fields.sort();
+    raw_fields.sort();
+    if typed_fields != raw_fields {
+        issues.push(format!(
+            "critical field mismatch: typed {:?}, raw {:?}",
+            typed_fields, raw_fields
+        ));
+    }
+
+    issues
+}
+
+fn unknown_keys(extra: &HashMap<String, Value>, ignored: &[&str]) -> Vec<String> {
+    let mut keys: Vec<_> = extra
+        .keys()
+        .filter(|key| !ignored.contains(&key.as_str()))
+        .map(|key| format!("unknown field `{}`", key))
+        .collect();
+    keys.sort();
+    keys
+}
+
+fn unknown_value(field: &str, value: &str) -> String {
+    format!("unknown {} value `{}`", field, value)
+}
+
+/// Collect string values at `array[*].key` from raw JSON
+fn raw_strings(raw: &Value, array: &str, key: &str, label: &str) -> Vec<String> {
+    raw.get(array)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get(key).and_then(Value::as_str))
+                .map(|value| format!("{}={}", label, value))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// =============================================================================
+// Response Checks
+// =============================================================================
+
+impl SchemaCheck for SummaryResponse {
+    const IGNORED_KEYS: &'static [&'static str] = &["incidents", "scheduled_maintenances"];
+
+    fn unknown_values(&self) -> Vec<String> {
+        let mut issues = unknown_keys(&self.extra, Self::IGNORED_KEYS);
+        if self.status.indicator.is_unknown() {
+            issues.push(unknown_value("indicator", self.status.indicator.as_str()));
+        }
+        for component in self.components.iter().filter(|c| c.status.is_unknown()) {
+            issues.push(unknown_value("component status", component.status.as_str()));
+        }
+        issues
+    }
+
+    fn critical_fields(&self) -> Vec<String> {
+        let mut fields = vec![format!("indicator={}", self.status.indicator)];
+        fields.extend(
+            self.components
+                .iter()
+                .map(|c| format!("component={}", c.id)),
+        );
+        fields
+    }
+
+    fn critical_fields_raw(raw: &Value) -> Vec<String> {
+        let mut fields: Vec<String> = raw
+            .pointer("/status/indicator")
+            .and_then(Value::as_str)
+            .map(|indicator| format!("indicator={}", indicator))
+            .into_iter()
+            .collect();
+        fields.extend(raw_strings(raw, "components", "id", "component"));
+        fields
+    }
+}
+
+impl SchemaCheck for UnresolvedIncidentsResponse {
+    const IGNORED_KEYS: &'static [&'static str] = &["page"];
+
+    fn unknown_values(&self) -> Vec<String> {
+        let mut issues = unknown_keys(&self.extra, Self::IGNORED_KEYS);
+        for incident in &self.incidents {
+            if incident.impact.is_unknown() {
+                issues.push(unknown_value("impact", incident.impact.as_str()));
+            }
+            if incident.status.is_unknown() {
+                issues.push(unknown_value("incident status", incident.status.as_str()));
+            }
+            for update in incident
+                .incident_updates
+                .iter()
+                .filter(|u| u.status.is_unknown())
+            {
+                issues.push(unknown_value(
+                    "incident update status",
+                    update.status.as_str(),
+                ));
+            }
+        }
+        issues
+    }
+
+    fn critical_fields(&self) -> Vec<String> {
+        self.incidents
+            .iter()
+            .map(|i| format!("incident={}", i.id))
+            .collect()
+    }
+
+    fn critical_fields_raw(raw: &Value) -> Vec<String> {
+        raw_strings(raw, "incidents", "id", "incident")
+    }
+}
+
+impl SchemaCheck for MaintenancesResponse {
+    const IGNORED_KEYS: &'static [&'static str] = &["page"];
+
+    fn unknown_values(&self) -> Vec<String> {
+        let mut issues = unknown_keys(&self.extra, Self::IGNORED_KEYS);
+        for maintenance in self
+            .scheduled_maintenances
+            .iter()
+            .filter(|m| m.status.is_unknown())
+        {
+            issues.push(unknown_value(
+                "maintenance status",
+                maintenance.status.as_str(),
+            ));
+        }
+        issues
+    }
+
+    fn critical_fields(&self) -> Vec<String> {
+        self.scheduled_maintenances
+            .iter()
+            .map(|m| format!("maintenance={}", m.id))
+            .collect()
+    }
+
+    fn critical_fields_raw(raw: &Value) -> Vec<String> {
+        raw_strings(raw, "scheduled_maintenances", "id", "maintenance")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collector::client::FetchError;
+    use serde_json::json;
+
+    const SUMMARY: &str = r#"{
+        "page": { "id": "p1", "name": "VRChat", "updated_at": "2026-01-10T12:00:00Z" },
+        "status": { "indicator": "minor", "description": "Minor Service Outage" },
+        "components": [
+            { "id": "c1", "name": "Authentication / Login", "status": "operational" },
+            { "id": "c2", "name": "API", "status": "degraded_performance", "group_id": "g1" }
+        ],
+        "incidents": [],
+        "scheduled_maintenances": []
+    }"#;
+
+    const INCIDENTS: &str = r#"{
+        "page": { "id": "p1" },
+        "incidents": [
+            {
+                "id": "inc1",
+                "name": "Login issues",
+                "status": "identified",
+                "impact": "major",
+                "created_at": "2026-01-10T12:00:00Z",
+                "updated_at": "2026-01-10T12:30:00Z",
+                "incident_updates": [
+                    {
+                        "id": "upd1",
+                        "status": "investigating",
+                        "body": "We are looking into login failures.",
+                        "created_at": "2026-01-10T12:00:00Z"
+                    }
+                ]
+            }
+        ]
+    }"#;
+
+    const MAINTENANCES: &str = r#"{
+        "page": { "id": "p1" },
+        "scheduled_maintenances": [
+            {
+                "id": "m1",
+                "name": "Database upgrade",
+                "status": "scheduled",
+                "scheduled_for": "2026-01-12T00:00:00Z",
+                "scheduled_until": "2026-01-12T02:00:00Z",
+                "created_at": "2026-01-10T12:00:00Z",
+                "updated_at": "2026-01-10T12:00:00Z"
+            }
+        ]
+    }"#;
+
+    fn fixture(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    /// Parse `raw` the way a checked poll does and return the reported issues
+    fn check<T: SchemaCheck>(raw: &Value) -> std::result::Result<Vec<String>, FetchError> {
+        let typed: T = decode(&serde_json::to_vec(raw).unwrap())?;
+        Ok(find_issues(&typed, raw))
+    }
+
+    fn decode_error_path<T: SchemaCheck + std::fmt::Debug>(raw: &Value) -> String {
+        match check::<T>(raw) {
+            Err(FetchError::Decode { path, .. }) => path,
+            other => panic!("expected a decode error, got {other:?}"),
+        }
+    }
+
+    fn monitor() -> SchemaMonitor {
+        SchemaMonitor::new(Arc::new(Http::new("")))
+    }
+
+    #[test]
+    fn test_fixtures_have_no_issues() {
+        assert_eq!(
+            check::<SummaryResponse>(&fixture(SUMMARY)).unwrap(),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check::<UnresolvedIncidentsResponse>(&fixture(INCIDENTS)).unwrap(),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check::<MaintenancesResponse>(&fixture(MAINTENANCES)).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_extra_top_level_field_reported() {
+        let mut raw = fixture(SUMMARY);
+        raw["banner"] = json!({ "text": "Hello" });
+        raw["affected_regions"] = json!(["us-east"]);
+
+        let issues = check::<SummaryResponse>(&raw).unwrap();
+
+        assert_eq!(
+            issues,
+            vec!["unknown field `affected_regions`", "unknown field `banner`"]
+        );
+    }
+
+    #[test]
+    fn test_extra_nested_fields_ignored() {
+        let mut raw = fixture(INCIDENTS);
+        raw["incidents"][0]["metadata"] = json!({ "jira": { "key": "OPS-1" } });
+        raw["incidents"][0]["incident_updates"][0]["affected_components"] =
+            json!([{ "code": "c1", "old_status": "operational" }]);
+
+        assert_eq!(
+            check::<UnresolvedIncidentsResponse>(&raw).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_new_enum_values_reported_with_raw_string() {
+        let mut summary = fixture(SUMMARY);
+        summary["status"]["indicator"] = json!("catastrophic");
+        summary["components"][1]["status"] = json!("slightly_on_fire");
+
+        let issues = check::<SummaryResponse>(&summary).unwrap();
+
+        assert_eq!(
+            issues,
+            vec![
+                "unknown indicator value `catastrophic`",
+                "unknown component status value `slightly_on_fire`",
+            ]
+        );
+
+        let mut incidents = fixture(INCIDENTS);
+        incidents["incidents"][0]["impact"] = json!("severe");
+        incidents["incidents"][0]["status"] = json!("mitigated");
+        incidents["incidents"][0]["incident_updates"][0]["status"] = json!("mitigated");
+
+        let issues = check::<UnresolvedIncidentsResponse>(&incidents).unwrap();
+
+        assert_eq!(
+            issues,
+            vec![
+                "unknown impact value `severe`",
+                "unknown incident status value `mitigated`",
+                "unknown incident update status value `mitigated`",
+            ]
+        );
+
+        let mut maintenances = fixture(MAINTENANCES);
+        maintenances["scheduled_maintenances"][0]["status"] = json!("postponed");
+
+        assert_eq!(
+            check::<MaintenancesResponse>(&maintenances).unwrap(),
+            vec!["unknown maintenance status value `postponed`"]
+        );
+    }
+
+    #[test]
+    fn test_missing_required_field_fails_decode() {
+        let mut raw = fixture(SUMMARY);
+        raw["components"][1].as_object_mut().unwrap().remove("name");
+
+        match check::<SummaryResponse>(&raw) {
+            Err(FetchError::Decode { path, source }) => {
+                assert_eq!(path, "components[1]");
+                assert!(
+                    source.to_string().contains("missing field `name`"),
+                    "{source}"
+                );
+            }
+            other => panic!("expected a decode error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_missing_optional_field_parses() {
+        let mut raw = fixture(SUMMARY);
+        raw["components"][1]
+            .as_object_mut()
+            .unwrap()
+            .remove("group_id");
+
+        assert_eq!(
+            check::<SummaryResponse>(&raw).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_renamed_field_fails_decode() {
+        let mut raw = fixture(INCIDENTS);
+        let incident = raw["incidents"][0].as_object_mut().unwrap();
+        let id = incident.remove("id").unwrap();
+        incident.insert("incident_id".into(), id);
+
+        let path = decode_error_path::<UnresolvedIncidentsResponse>(&raw);
+
+        assert!(path.starts_with("incidents[0]"), "{path}");
+    }
+
+    #[test]
+    fn test_wrong_type_fails_decode() {
+        let cases = [
+            ("/status/indicator", json!(3), "status.indicator"),
+            ("/components/0/id", json!(1), "components[0].id"),
+            ("/components", json!({ "c1": "operational" }), "components"),
+            ("/page/updated_at", json!("yesterday"), "page.updated_at"),
+        ];
+        for (pointer, value, expected) in cases {
+            let mut raw = fixture(SUMMARY);
+            *raw.pointer_mut(pointer).unwrap() = value;
+
+            assert_eq!(
+                decode_error_path::<SummaryResponse>(&raw),
+                expected,
+                "{pointer}"
+            );
+        }
+
+        let mut raw = fixture(MAINTENANCES);
+        raw["scheduled_maintenances"][0]["scheduled_for"] = json!(null);
+        assert_eq!(
+            decode_error_path::<MaintenancesResponse>(&raw),
+            "scheduled_maintenances[0].scheduled_for"
+        );
+    }
+
+    #[test]
+    fn test_critical_field_mismatch_reported() {
+        let typed: SummaryResponse = serde_json::from_str(SUMMARY).unwrap();
+        let mut raw = fixture(SUMMARY);
+        raw["status"]["indicator"] = json!("major");
+
+        let issues = find_issues(&typed, &raw);
+
+        assert_eq!(issues.len(), 1);
+        assert!(
+            issues[0].starts_with("critical field mismatch"),
+            "{issues:?}"
+        );
+        assert!(issues[0].contains("indicator=minor"));
+        assert!(issues[0].contains("indicator=major"));
+    }
+
+    #[test]
+    fn test_critical_fields_missing_from_raw_reported() {
+        let typed: UnresolvedIncidentsResponse = serde_json::from_str(INCIDENTS).unwrap();
+        let raw = json!({ "incidents": [{ "identifier": "inc1" }] });
+
+        let issues = find_issues(&typed, &raw);
+
+        assert_eq!(
+            issues,
+            vec![r#"critical field mismatch: typed ["incident=inc1"], raw []"#]
+        );
+    }
+
+    #[test]
+    fn test_monitor_records_each_issue_once() {
+        let monitor = monitor();
+
+        assert!(monitor.record("summary", "unknown field `banner`"));
+        assert!(!monitor.record("summary", "unknown field `banner`"));
+        assert!(monitor.record("incidents", "unknown field `banner`"));
+
+        let recent: Vec<_> = monitor
+            .recent()
+            .into_iter()
+            .map(|issue| issue.endpoint)
+            .collect();
+        assert_eq!(recent, vec!["summary", "incidents"]);
+    }
+
+    #[test]
+    fn test_monitor_keeps_most_recent_issues() {
+        let monitor = monitor();
+
+        for i in 0..MAX_RECENT_ISSUES + 5 {
+            monitor.record("summary", &format!("issue {i}"));
+        }
+
+        let recent = monitor.recent();
+        assert_eq!(recent.len(), MAX_RECENT_ISSUES);
+        assert_eq!(recent[0].detail, "issue 5");
+        assert_eq!(
+            recent.last().unwrap().detail,
+            format!("issue {}", MAX_RECENT_ISSUES + 4)
+        );
+    }
+}
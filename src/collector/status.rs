@@ -1,17 +1,33 @@
-use chrono::Utc;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use reqwest::Client;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
-use tracing::debug;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use tracing::{debug, info};
 
-use crate::entity::{component_logs, status_logs};
+use crate::alerts::component::{self, ComponentChange};
+use crate::alerts::delivery::Notifier;
+use crate::entity::{component_logs, status_logs, status_transitions};
 
-use super::client::{Result, fetch_json, status_api_url};
+use super::client::{Result, status_api_url};
 use super::models::SummaryResponse;
+use super::schema::{SchemaMonitor, fetch_checked};
 
 /// Poll /summary.json and store status and component logs
-pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
-    let url = status_api_url("/summary.json");
-    let response: SummaryResponse = fetch_json(client, &url).await?;
+///
+/// A new overall indicator is recorded in `status_transitions`. Components whose status got worse, or recovered, since the previous
+/// snapshot are handed to `alerts::component` for notices.
+pub async fn poll(
+    client: &Client,
+    db: &DatabaseConnection,
+    monitor: &SchemaMonitor,
+    notifier: &Notifier,
+) -> Result<()> {
+    let endpoint = "/summary.json";
+    let url = status_api_url(endpoint);
+    let response: SummaryResponse = fetch_checked(client, &url, endpoint, monitor).await?;
 
     let source_timestamp = response.page.updated_at;
     let now = Utc::now();
@@ -25,7 +41,7 @@ pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
     if existing_status.is_none() {
         // Insert new status log
         let status_log = status_logs::ActiveModel {
-            indicator: Set(response.status.indicator.clone()),
+            indicator: Set(response.status.indicator.to_string()),
             description: Set(response.status.description.clone()),
             source_timestamp: Set(source_timestamp),
             created_at: Set(now),
@@ -36,11 +52,15 @@ pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
             indicator = %response.status.indicator,
             "Inserted new status log"
         );
+
+        record_transition(db, &response.status.indicator.to_string(), source_timestamp).await?;
     } else {
         debug!("Status log already exists for timestamp, skipping");
     }
 
     // Process components
+    let previous = previous_statuses(db, source_timestamp).await?;
+    let mut changes = Vec::new();
     for component in response.components {
         let existing_component = component_logs::Entity::find()
             .filter(component_logs::Column::ComponentId.eq(&component.id))
@@ -52,7 +72,8 @@ pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
             let component_log = component_logs::ActiveModel {
                 component_id: Set(component.id.clone()),
                 name: Set(component.name.clone()),
-                status: Set(component.status.clone()),
+                status: Set(component.status.to_string()),
+                group_id: Set(component.group_id.clone()),
                 source_timestamp: Set(source_timestamp),
                 created_at: Set(now),
                 ..Default::default()
@@ -64,8 +85,80 @@ pub async fn poll(client: &Client, db: &DatabaseConnection) -> Result<()> {
                 status = %component.status,
                 "Inserted component log"
             );
+
+            let new_status = component.status.to_string();
+            if let Some(old_status) = previous.get(&component.id)
+                && let Some(kind) = component::classify(old_status, &new_status)
+            {
+                changes.push(ComponentChange {
+                    component_id: component.id.clone(),
+                    name: component.name.clone(),
+                    old_status: old_status.clone(),
+                    new_status,
+                    changed_at: source_timestamp,
+                    kind,
+                });
+            }
         }
     }
 
+    component::spawn_notices(notifier.clone(), db.clone(), changes);
+    Ok(())
+}
+
+/// Component statuses of the newest snapshot before `before`, by component ID
+async fn previous_statuses(
+    db: &DatabaseConnection,
+    before: DateTime<Utc>,
+) -> Result<HashMap<String, String>> {
+    let Some(latest) = component_logs::Entity::find()
+        .filter(component_logs::Column::SourceTimestamp.lt(before))
+        .order_by_desc(component_logs::Column::SourceTimestamp)
+        .one(db)
+        .await?
+    else {
+        return Ok(HashMap::new());
+    };
+
+    let rows = component_logs::Entity::find()
+        .filter(component_logs::Column::SourceTimestamp.eq(latest.source_timestamp))
+        .all(db)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.component_id, row.status))
+        .collect())
+}
+
+/// Record a transition if `indicator` differs from the previous snapshot's
+///
+/// The first snapshot ever stored has nothing to compare with and records
+/// nothing.
+async fn record_transition(
+    db: &DatabaseConnection,
+    indicator: &str,
+    source_timestamp: DateTime<Utc>,
+) -> Result<()> {
+    let previous = status_logs::Entity::find()
+        .filter(status_logs::Column::SourceTimestamp.lt(source_timestamp))
+        .order_by_desc(status_logs::Column::SourceTimestamp)
+        .one(db)
+        .await?;
+    let Some(previous) = previous.filter(|previous| previous.indicator != indicator) else {
+        return Ok(());
+    };
+
+    let transition = status_transitions::ActiveModel {
+        from_indicator: Set(previous.indicator.clone()),
+        to_indicator: Set(indicator.to_string()),
+        transitioned_at: Set(source_timestamp),
+        ..Default::default()
+    };
+    transition.insert(db).await?;
+    info!(
+        from = %previous.indicator,
+        to = indicator,
+        "Status indicator changed"
+    );
     Ok(())
 }
@@ -0,0 +1,363 @@
+//! /admin broadcast - owner announcement to every registered guild
+//!
+//! The command only shows a preview with Cancel/Send buttons; nothing goes
+//! out until the owner confirms. The preview's first embed is the
+//! announcement itself, so the Send button reads the text back from the
+//! message instead of keeping it in memory, and the `dms` choice travels in
+//! the button ID. Sends are spaced `SEND_INTERVAL` apart to stay clear of
+//! Discord rate limits, and the preview is edited with per-destination counts
+//! once every send has finished.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use rust_i18n::t;
+use sea_orm::DatabaseConnection;
+use serenity::all::{
+    ButtonStyle, ChannelId, Colour, ComponentInteraction, Context, CreateActionRow, CreateButton,
+    CreateEmbed, CreateEmbedFooter, CreateMessage, ResolvedOption, ResolvedValue, Timestamp,
+    UserId,
+};
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, warn};
+
+use crate::commands::registry::InlineReply;
+use crate::commands::shared::{
+    button_id_with_context, colors, defer_component_update, edit_component_embed,
+    edit_component_error, is_button, parse_button_context,
+};
+use crate::database;
+use crate::entity::{guild_configs, user_configs};
+use crate::i18n::{DEFAULT_LOCALE, normalize_locale, resolve_locale_component};
+use crate::repository::{GuildConfigRepository, UserConfigRepository};
+
+use super::config::is_owner_id;
+use super::embeds::{self, DeliveryCounts};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Longest announcement (well under the 4096-character embed description)
+pub const MAX_MESSAGE_LENGTH: u16 = 2000;
+
+/// Gap between two sends (10 per second)
+const SEND_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Module name for /admin buttons
+const MODULE: &str = "admin";
+
+/// Action name for the send button
+const ACTION_BROADCAST_CONFIRM: &str = "broadcast_confirm";
+
+/// Action name for the cancel button
+const ACTION_BROADCAST_CANCEL: &str = "broadcast_cancel";
+
+/// Set while a broadcast is being sent, so a second click can't send it twice
+static SENDING: AtomicBool = AtomicBool::new(false);
+
+// =============================================================================
+// Command Handler
+// =============================================================================
+
+/// Handle /admin broadcast <message> [dms] - show the preview and buttons
+pub async fn handle_broadcast(
+    db: &Arc<DatabaseConnection>,
+    options: &[ResolvedOption<'_>],
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let message = options.iter().find_map(|opt| match opt.value {
+        ResolvedValue::String(s) if opt.name == "message" => Some(s),
+        _ => None,
+    });
+    let dms = options
+        .iter()
+        .find_map(|opt| match opt.value {
+            ResolvedValue::Boolean(b) if opt.name == "dms" => Some(b),
+            _ => None,
+        })
+        .unwrap_or(false);
+
+    // Slash command options can't contain line breaks
+    let message = message.unwrap_or_default().replace("\\n", "\n");
+    let message = message.trim();
+    if message.is_empty() {
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.broadcast_empty", locale = locale),
+            locale,
+        )));
+    }
+
+    let guilds = match GuildConfigRepository::new(db.clone())
+        .list_all_enabled()
+        .await
+    {
+        Ok(guilds) => guilds.iter().filter(|g| g.channel_id.is_some()).count(),
+        Err(e) => {
+            error!(error = %e, "Failed to count broadcast guilds");
+            return Ok(Some(InlineReply::error(
+                &t!("errors.admin.destinations_failed", locale = locale),
+                locale,
+            )));
+        }
+    };
+    let users = if dms {
+        match UserConfigRepository::new(db.clone()).count_enabled().await {
+            Ok(count) => Some(count as usize),
+            Err(e) => {
+                error!(error = %e, "Failed to count broadcast users");
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.destinations_failed", locale = locale),
+                    locale,
+                )));
+            }
+        }
+    } else {
+        None
+    };
+
+    let flag = if dms { "1" } else { "0" };
+    let buttons = vec![
+        CreateButton::new(button_id_with_context(
+            MODULE,
+            ACTION_BROADCAST_CANCEL,
+            "dms",
+            flag,
+        ))
+        .label("Cancel")
+        .style(ButtonStyle::Secondary),
+        CreateButton::new(button_id_with_context(
+            MODULE,
+            ACTION_BROADCAST_CONFIRM,
+            "dms",
+            flag,
+        ))
+        .label("Send")
+        .style(ButtonStyle::Danger),
+    ];
+
+    let reply = InlineReply::embeds(vec![
+        announcement_embed(message, DEFAULT_LOCALE),
+        embeds::broadcast_confirm(guilds, users),
+    ])
+    .components(vec![CreateActionRow::Buttons(buttons)]);
+    Ok(Some(reply))
+}
+
+// =============================================================================
+// Button Handlers
+// =============================================================================
+
+/// Handle the Send and Cancel buttons of a broadcast preview
+pub async fn handle_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), serenity::Error> {
+    let custom_id = &interaction.data.custom_id;
+    let confirm = is_button(custom_id, MODULE, ACTION_BROADCAST_CONFIRM);
+    if !confirm && !is_button(custom_id, MODULE, ACTION_BROADCAST_CANCEL) {
+        return Ok(());
+    }
+
+    // Defer first to acknowledge within 3 seconds
+    defer_component_update(ctx, interaction).await?;
+
+    // Silent ignore for anyone but the owner, like the command itself
+    if !is_owner_id(ctx, interaction.user.id).await {
+        return Ok(());
+    }
+
+    if confirm {
+        handle_confirm(ctx, interaction).await
+    } else {
+        edit_component_embed(ctx, interaction, embeds::broadcast_cancelled()).await
+    }
+}
+
+/// Send the previewed announcement and report the counts
+async fn handle_confirm(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_component(ctx, interaction).await;
+    let dms = parse_button_context(&interaction.data.custom_id)
+        .is_some_and(|(context_type, flag)| context_type == "dms" && flag == "1");
+    let Some(message) = interaction
+        .message
+        .embeds
+        .first()
+        .and_then(|embed| embed.description.clone())
+    else {
+        return edit_component_error(
+            ctx,
+            interaction,
+            &t!("errors.admin.broadcast_missing", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    if SENDING.swap(true, Ordering::AcqRel) {
+        return edit_component_error(
+            ctx,
+            interaction,
+            &t!("errors.admin.broadcast_in_progress", locale = &locale),
+            &locale,
+        )
+        .await;
+    }
+
+    let db = database::get_db(ctx).await;
+    let result = match load_destinations(&db, dms).await {
+        Ok((guilds, users)) => {
+            let total = guilds.len() + users.len();
+            if let Err(e) =
+                edit_component_embed(ctx, interaction, embeds::broadcast_sending(total)).await
+            {
+                warn!(error = %e, "Failed to show broadcast progress");
+            }
+            Ok(send_all(ctx, &message, &guilds, &users, dms).await)
+        }
+        Err(e) => Err(e),
+    };
+    SENDING.store(false, Ordering::Release);
+
+    match result {
+        Ok((guilds, users)) => {
+            info!(
+                guilds_sent = guilds.sent,
+                guilds_failed = guilds.failed,
+                dms_sent = users.map(|c| c.sent),
+                dms_failed = users.map(|c| c.failed),
+                "Sent owner broadcast"
+            );
+            edit_component_embed(ctx, interaction, embeds::broadcast_result(guilds, users)).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to load broadcast destinations");
+            edit_component_error(
+                ctx,
+                interaction,
+                &t!("errors.admin.destinations_failed", locale = &locale),
+                &locale,
+            )
+            .await
+        }
+    }
+}
+
+// =============================================================================
+// Sending
+// =============================================================================
+
+/// Enabled guilds with an alert channel, and enabled users if `dms` is set
+async fn load_destinations(
+    db: &Arc<DatabaseConnection>,
+    dms: bool,
+) -> Result<(Vec<guild_configs::Model>, Vec<user_configs::Model>), sea_orm::DbErr> {
+    let guilds = GuildConfigRepository::new(db.clone())
+        .list_all_enabled()
+        .await?
+        .into_iter()
+        .filter(|g| g.channel_id.is_some())
+        .collect();
+    let users = if dms {
+        UserConfigRepository::new(db.clone())
+            .list_all_enabled()
+            .await?
+    } else {
+        Vec::new()
+    };
+    Ok((guilds, users))
+}
+
+/// Send the announcement to every destination, one per `SEND_INTERVAL`
+///
+/// Each recipient gets the title and footer in its configured language.
+async fn send_all(
+    ctx: &Context,
+    message: &str,
+    guilds: &[guild_configs::Model],
+    users: &[user_configs::Model],
+    dms: bool,
+) -> (DeliveryCounts, Option<DeliveryCounts>) {
+    let mut ticker = tokio::time::interval(SEND_INTERVAL);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut guild_counts = DeliveryCounts::default();
+    for guild in guilds {
+        let Some(channel_id) = guild
+            .channel_id
+            .as_deref()
+            .and_then(|id| id.parse::<u64>().ok())
+        else {
+            guild_counts.failed += 1;
+            continue;
+        };
+        ticker.tick().await;
+
+        let embed = announcement_embed(message, recipient_locale(guild.language.as_deref()));
+        match ChannelId::new(channel_id)
+            .send_message(&ctx.http, CreateMessage::new().embed(embed))
+            .await
+        {
+            Ok(_) => guild_counts.sent += 1,
+            Err(e) => {
+                warn!(guild_id = %guild.guild_id, channel_id, error = %e, "Failed to send broadcast to guild");
+                guild_counts.failed += 1;
+            }
+        }
+    }
+
+    if !dms {
+        return (guild_counts, None);
+    }
+
+    let mut user_counts = DeliveryCounts::default();
+    for user in users {
+        let Ok(user_id) = user.user_id.parse::<u64>() else {
+            user_counts.failed += 1;
+            continue;
+        };
+        ticker.tick().await;
+
+        let embed = announcement_embed(message, recipient_locale(user.language.as_deref()));
+        let result = match UserId::new(user_id).create_dm_channel(&ctx.http).await {
+            Ok(channel) => channel
+                .send_message(&ctx.http, CreateMessage::new().embed(embed))
+                .await
+                .map(|_| ()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => user_counts.sent += 1,
+            Err(e) => {
+                warn!(user_id = %user.user_id, error = %e, "Failed to send broadcast DM");
+                user_counts.failed += 1;
+            }
+        }
+    }
+
+    (guild_counts, Some(user_counts))
+}
+
+/// Locale of a stored language preference, default if unset or unsupported
+fn recipient_locale(language: Option<&str>) -> &'static str {
+    language
+        .and_then(normalize_locale)
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// The announcement as recipients see it
+fn announcement_embed(message: &str, locale: &str) -> CreateEmbed {
+    CreateEmbed::default()
+        .title(t!("embeds.broadcast.title", locale = locale))
+        .description(message)
+        .color(Colour::new(colors::BRAND))
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.broadcast.footer",
+            locale = locale
+        )))
+        .timestamp(Timestamp::now())
+}
@@ -1,19 +1,50 @@
 //! /admin command - Bot owner only administration
 
+use std::sync::Arc;
+
 use chrono::Utc;
+use rust_i18n::t;
 use serenity::all::{
     CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
-    CreateInteractionResponse, CreateInteractionResponseMessage, Permissions, ResolvedValue,
+    Permissions, ResolvedValue, UserId,
 };
-use tracing::error;
+use tracing::{error, info};
 
-use crate::collector::config::{DEFAULT_INTERVAL, PollerType, get_interval, validate_interval};
-use crate::commands::shared::respond_error;
+use crate::alerts::channel_check;
+use crate::alerts::reference::{
+    MAX_ALERT_EXPIRY_MINUTES, MIN_ALERT_EXPIRY_MINUTES, get_alert_expiry_minutes,
+    set_alert_expiry_minutes,
+};
+use crate::alerts::safeguard::{self, LimiterSnapshot};
+use crate::bot::maintenance_mode::{self, MaintenanceState};
+use crate::collector::config::{
+    DEFAULT_INTERVAL, MAX_INTERVAL, MIN_INTERVAL, PollerType, get_interval, validate_interval,
+};
+use crate::collector::health::PollerHealth;
+use crate::collector::schema::SchemaIssue;
+use crate::commands::registry::InlineReply;
+use crate::commands::report::{
+    MAX_REPORT_COOLDOWN, MIN_REPORT_COOLDOWN, get_report_cooldown, set_report_cooldown,
+};
 use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::maintenance::{
+    MAX_METRIC_RETENTION_DAYS, MIN_METRIC_RETENTION_DAYS, get_metric_retention_days,
+    set_metric_retention_days, validate_metric_retention,
+};
 use crate::repository::{GuildConfigRepository, UserConfigRepository};
+use crate::scheduler::JobStatus;
 use crate::state::AppStateKey;
+use crate::version::{self, MigrationStatus};
 
-use super::embeds;
+use super::{broadcast, correlate, embeds, stats};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Longest custom maintenance message (fits an embed field)
+const MAX_MAINTENANCE_MESSAGE_LENGTH: u16 = 500;
 
 // =============================================================================
 // Command Registration
@@ -29,6 +60,84 @@ pub fn register() -> CreateCommand {
             "show",
             "Display bot information and available commands",
         ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "stats",
+                "Chart alert volume over the last 30 days and summarize report and command activity",
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::String,
+                "guild_id",
+                "Limit to one guild (default: all guilds and users)",
+            ))
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "reporters",
+                "Add a chart of daily distinct reporters",
+            )),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "correlate",
+                "Chart community reports against an official incident timeline",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "incident_id",
+                    "Status page incident ID",
+                )
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "maintenance",
+                "Turn bot maintenance mode on or off",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "state",
+                    "Whether maintenance mode is on",
+                )
+                .required(true)
+                .add_string_choice("on", "on")
+                .add_string_choice("off", "off"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "message",
+                    "Message shown to users while maintenance mode is on",
+                )
+                .max_length(MAX_MAINTENANCE_MESSAGE_LENGTH),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "broadcast",
+                "Send an announcement to every registered guild, after confirmation",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "message",
+                    "Announcement text (\\n for a line break)",
+                )
+                .required(true)
+                .max_length(broadcast::MAX_MESSAGE_LENGTH),
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "dms",
+                "Also DM users registered for alerts (default: false)",
+            )),
+        )
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::SubCommandGroup,
@@ -73,6 +182,62 @@ pub fn register() -> CreateCommand {
                 CommandOptionType::SubCommand,
                 "reset",
                 "Reset all polling intervals to default (60s)",
+            ))
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "cooldown",
+                    "Update the per-user /report cooldown",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "minutes",
+                        "Cooldown in minutes (1-60)",
+                    )
+                    .required(true)
+                    .min_int_value(MIN_REPORT_COOLDOWN as u64)
+                    .max_int_value(MAX_REPORT_COOLDOWN as u64),
+                ),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "alert-expiry",
+                    "Update how long a threshold alert blocks repeats of its type",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "minutes",
+                        "Expiry in minutes (15-240)",
+                    )
+                    .required(true)
+                    .min_int_value(MIN_ALERT_EXPIRY_MINUTES as u64)
+                    .max_int_value(MAX_ALERT_EXPIRY_MINUTES as u64),
+                ),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "retention",
+                    "Update how long metric and status logs are kept",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "days",
+                        "Retention in days (7-365)",
+                    )
+                    .required(true)
+                    .min_int_value(MIN_METRIC_RETENTION_DAYS as u64)
+                    .max_int_value(MAX_METRIC_RETENTION_DAYS as u64),
+                ),
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "validate-channels",
+                "Check every guild's alert channel now and disable unreachable ones",
             )),
         )
 }
@@ -81,46 +246,122 @@ pub fn register() -> CreateCommand {
 // Command Handler
 // =============================================================================
 
-/// /admin command handler (owner-only)
-pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+/// /admin command handler (owner-only, inline)
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<Option<InlineReply>, serenity::Error> {
     // Check if user is bot owner (silent ignore if not)
     if !is_owner(ctx, interaction).await {
-        return Ok(());
+        return Ok(None);
     }
 
     let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
 
     // Parse subcommand
     let options = &interaction.data.options();
     let Some(first_opt) = options.first() else {
-        return Ok(());
+        return Ok(None);
     };
 
     match first_opt.name {
-        "show" => handle_admin_show(ctx, interaction).await,
+        "show" => handle_admin_show(ctx).await,
+        "stats" => {
+            let ResolvedValue::SubCommand(options) = &first_opt.value else {
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.invalid_command", locale = &locale),
+                    &locale,
+                )));
+            };
+            stats::handle_stats(&db, options, &locale).await
+        }
+        "correlate" => {
+            let ResolvedValue::SubCommand(options) = &first_opt.value else {
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.invalid_command", locale = &locale),
+                    &locale,
+                )));
+            };
+            correlate::handle_correlate(&db, options, &locale).await
+        }
+        "maintenance" => {
+            let ResolvedValue::SubCommand(options) = &first_opt.value else {
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.invalid_command", locale = &locale),
+                    &locale,
+                )));
+            };
+            handle_maintenance(ctx, &db, options, &locale).await
+        }
+        "broadcast" => {
+            let ResolvedValue::SubCommand(options) = &first_opt.value else {
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.invalid_command", locale = &locale),
+                    &locale,
+                )));
+            };
+            broadcast::handle_broadcast(&db, options, &locale).await
+        }
         "config" => {
             let ResolvedValue::SubCommandGroup(subcommands) = &first_opt.value else {
-                return respond_error(ctx, interaction, "Invalid command structure", "en").await;
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.invalid_command", locale = &locale),
+                    &locale,
+                )));
             };
 
             let Some(subcommand) = subcommands.first() else {
-                return respond_error(ctx, interaction, "Missing subcommand", "en").await;
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.missing_subcommand", locale = &locale),
+                    &locale,
+                )));
             };
 
             match subcommand.name {
-                "show" => handle_config_show(ctx, interaction, &db).await,
+                "show" => handle_config_show(&db).await,
                 "set" => {
                     let ResolvedValue::SubCommand(options) = &subcommand.value else {
-                        return respond_error(ctx, interaction, "Invalid command structure", "en")
-                            .await;
+                        return Ok(Some(InlineReply::error(
+                            &t!("errors.admin.invalid_command", locale = &locale),
+                            &locale,
+                        )));
                     };
-                    handle_config_set(ctx, interaction, &db, options).await
+                    handle_config_set(&db, options, &locale).await
                 }
-                "reset" => handle_config_reset(ctx, interaction, &db).await,
-                _ => Ok(()),
+                "reset" => handle_config_reset(&db, &locale).await,
+                "cooldown" => {
+                    let ResolvedValue::SubCommand(options) = &subcommand.value else {
+                        return Ok(Some(InlineReply::error(
+                            &t!("errors.admin.invalid_command", locale = &locale),
+                            &locale,
+                        )));
+                    };
+                    handle_config_cooldown(&db, options, &locale).await
+                }
+                "alert-expiry" => {
+                    let ResolvedValue::SubCommand(options) = &subcommand.value else {
+                        return Ok(Some(InlineReply::error(
+                            &t!("errors.admin.invalid_command", locale = &locale),
+                            &locale,
+                        )));
+                    };
+                    handle_config_alert_expiry(&db, options, &locale).await
+                }
+                "retention" => {
+                    let ResolvedValue::SubCommand(options) = &subcommand.value else {
+                        return Ok(Some(InlineReply::error(
+                            &t!("errors.admin.invalid_command", locale = &locale),
+                            &locale,
+                        )));
+                    };
+                    handle_config_retention(&db, options, &locale).await
+                }
+                "validate-channels" => handle_config_validate_channels(ctx, &db, &locale).await,
+                _ => Ok(None),
             }
         }
-        _ => Ok(()),
+        _ => Ok(None),
     }
 }
 
@@ -130,11 +371,16 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
 
 /// Check if the user is the bot owner
 async fn is_owner(ctx: &Context, interaction: &CommandInteraction) -> bool {
+    is_owner_id(ctx, interaction.user.id).await
+}
+
+/// Check if a user ID is the bot owner (also used for /admin buttons)
+pub(super) async fn is_owner_id(ctx: &Context, user_id: UserId) -> bool {
     match ctx.http.get_current_application_info().await {
         Ok(app_info) => app_info
             .owner
             .as_ref()
-            .is_some_and(|owner| owner.id == interaction.user.id),
+            .is_some_and(|owner| owner.id == user_id),
         Err(e) => {
             error!(error = %e, "Failed to get application info for owner check");
             false
@@ -147,20 +393,31 @@ async fn is_owner(ctx: &Context, interaction: &CommandInteraction) -> bool {
 // =============================================================================
 
 /// Handle /admin show - display bot info and command summary
-async fn handle_admin_show(
-    ctx: &Context,
-    interaction: &CommandInteraction,
-) -> Result<(), serenity::Error> {
+async fn handle_admin_show(ctx: &Context) -> Result<Option<InlineReply>, serenity::Error> {
     let db = database::get_db(ctx).await;
 
-    // Get uptime from AppState
-    let uptime = {
+    // Get uptime, alert limiter, schema issues, poller health, and job status from AppState
+    let (uptime, alert_limiter, schema_issues, collector_health, scheduled_jobs, maintenance) = {
         let data = ctx.data.read().await;
         let state = data.get::<AppStateKey>().expect("AppState not found");
-        let started_at = state.read().await.started_at;
-        format_uptime(started_at)
+        let state = state.read().await;
+        (
+            format_uptime(state.started_at, Utc::now()),
+            state.alert_limiter.clone(),
+            format_schema_issues(&state.schema_monitor.recent()),
+            format_collector_health(&state.collector_health.snapshot()),
+            format_scheduled_jobs(&state.scheduler_status.snapshot()),
+            format_maintenance(&state.maintenance.state()),
+        )
     };
 
+    // Alert delivery safeguard state
+    let max_per_hour = safeguard::load_max_per_hour(&db).await;
+    let alert_safeguard = format_safeguard(
+        &alert_limiter.snapshot(Utc::now(), max_per_hour),
+        max_per_hour,
+    );
+
     // Get counts
     let guild_count = ctx.cache.guild_count() as u64;
     let registered_guilds = GuildConfigRepository::new(db.clone())
@@ -172,6 +429,15 @@ async fn handle_admin_show(
         .await
         .unwrap_or(0);
 
+    // Schema migration status
+    let migrations = match version::migration_status(&db).await {
+        Ok(status) => format_migrations(&status),
+        Err(e) => {
+            error!(error = %e, "Failed to read migration status");
+            "Error".to_string()
+        }
+    };
+
     // Get polling intervals
     let format_interval = |result: Result<u64, _>| match result {
         Ok(secs) => format!("{}s", secs),
@@ -183,27 +449,31 @@ async fn handle_admin_show(
     let maintenance_interval = format_interval(get_interval(&db, PollerType::Maintenance).await);
     let metrics_interval = format_interval(get_interval(&db, PollerType::Metrics).await);
 
-    let embed = embeds::admin_show(
-        env!("CARGO_PKG_VERSION"),
-        &uptime,
+    let embed = embeds::admin_show(&embeds::AdminShowInfo {
+        version: version::VERSION,
+        git_hash: version::GIT_HASH,
+        migrations: &migrations,
+        uptime: &uptime,
         guild_count,
         registered_guilds,
         registered_users,
-        &status_interval,
-        &incident_interval,
-        &maintenance_interval,
-        &metrics_interval,
-    );
+        status_interval: &status_interval,
+        incident_interval: &incident_interval,
+        maintenance_interval: &maintenance_interval,
+        metrics_interval: &metrics_interval,
+        alert_safeguard: &alert_safeguard,
+        schema_issues: &schema_issues,
+        collector_health: &collector_health,
+        scheduled_jobs: &scheduled_jobs,
+        maintenance: &maintenance,
+    });
 
-    let response = CreateInteractionResponseMessage::new().embed(embed);
-    interaction
-        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
-        .await
+    Ok(Some(InlineReply::embed(embed)))
 }
 
-/// Format uptime duration as human-readable string
-fn format_uptime(started_at: chrono::DateTime<Utc>) -> String {
-    let duration = Utc::now() - started_at;
+/// Format the time from `started_at` to `now` as a human-readable string
+fn format_uptime(started_at: chrono::DateTime<Utc>, now: chrono::DateTime<Utc>) -> String {
+    let duration = now - started_at;
     let days = duration.num_days();
     let hours = duration.num_hours() % 24;
     let minutes = duration.num_minutes() % 60;
@@ -217,16 +487,192 @@ fn format_uptime(started_at: chrono::DateTime<Utc>) -> String {
     }
 }
 
+/// Format migration status as human-readable string
+fn format_migrations(status: &MigrationStatus) -> String {
+    let latest = status.latest.as_deref().unwrap_or("none");
+    if status.is_pending() {
+        format!(
+            "{}/{} applied (PENDING)\nLatest: `{}`",
+            status.applied, status.available, latest
+        )
+    } else {
+        format!(
+            "{}/{} applied\nLatest: `{}`",
+            status.applied, status.available, latest
+        )
+    }
+}
+
+/// Format alert safeguard state as human-readable string
+fn format_safeguard(snapshot: &LimiterSnapshot, max_per_hour: u64) -> String {
+    match snapshot.suppression {
+        Some(suppression) => format!(
+            "SUPPRESSED since <t:{}:R> ({} skipped)\nDeliveries: {}/{} in last hour",
+            suppression.since.timestamp(),
+            suppression.skipped,
+            snapshot.delivered_last_hour,
+            max_per_hour
+        ),
+        None => format!(
+            "Normal\nDeliveries: {}/{} in last hour",
+            snapshot.delivered_last_hour, max_per_hour
+        ),
+    }
+}
+
+/// Format recent status page schema issues as human-readable string
+fn format_schema_issues(issues: &[SchemaIssue]) -> String {
+    let Some(latest) = issues.last() else {
+        return "None detected".to_string();
+    };
+
+    let mut detail = latest.detail.clone();
+    if detail.len() > 200 {
+        let cut = (0..=200)
+            .rev()
+            .find(|&i| detail.is_char_boundary(i))
+            .unwrap_or(0);
+        detail.truncate(cut);
+        detail.push_str("...");
+    }
+
+    format!(
+        "{} recent\nLatest <t:{}:R> `{}`: {}",
+        issues.len(),
+        latest.at.timestamp(),
+        latest.endpoint,
+        detail
+    )
+}
+
+/// Format per-poller health as human-readable string
+fn format_collector_health(pollers: &[(&str, PollerHealth)]) -> String {
+    if pollers.is_empty() {
+        return "No polls yet".to_string();
+    }
+
+    pollers
+        .iter()
+        .map(|(name, health)| match health.last_failure {
+            Some(failure) if health.is_failing() => {
+                let retry = health
+                    .retry_at
+                    .map(|at| format!(", retry <t:{}:R>", at.timestamp()))
+                    .unwrap_or_default();
+                format!(
+                    "{}: FAILING x{} ({}) <t:{}:R>{}",
+                    name,
+                    health.consecutive_failures,
+                    failure.kind,
+                    failure.at.timestamp(),
+                    retry
+                )
+            }
+            _ => match health.last_success {
+                Some(at) => format!("{}: OK <t:{}:R>", name, at.timestamp()),
+                None => format!("{}: OK", name),
+            },
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format maintenance mode state as human-readable string
+fn format_maintenance(state: &MaintenanceState) -> String {
+    match (state.enabled, &state.message) {
+        (false, _) => "Off".to_string(),
+        (true, None) => "ON".to_string(),
+        (true, Some(message)) => format!("ON: {}", message),
+    }
+}
+
+/// Format scheduled job status as human-readable string
+fn format_scheduled_jobs(jobs: &[(&str, JobStatus)]) -> String {
+    if jobs.is_empty() {
+        return "None registered".to_string();
+    }
+
+    jobs.iter()
+        .map(|(name, status)| {
+            let last = match (status.last_run, &status.last_error) {
+                (None, _) => "not run yet".to_string(),
+                (Some(at), None) => format!("OK <t:{}:R>", at.timestamp()),
+                (Some(at), Some(error)) => {
+                    format!("FAILED <t:{}:R> ({})", at.timestamp(), error)
+                }
+            };
+            let next = status
+                .next_run
+                .map(|at| format!(", next <t:{}:R>", at.timestamp()))
+                .unwrap_or_default();
+            format!("{} ({}): {}{}", name, status.schedule, last, next)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// =============================================================================
+// Maintenance Handler
+// =============================================================================
+
+/// Handle /admin maintenance <on|off> [message]
+async fn handle_maintenance<'a>(
+    ctx: &Context,
+    db: &sea_orm::DatabaseConnection,
+    options: &[serenity::all::ResolvedOption<'a>],
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let enabled = options.iter().find_map(|opt| match (opt.name, &opt.value) {
+        ("state", ResolvedValue::String(s)) => Some(*s == "on"),
+        _ => None,
+    });
+    let message = options.iter().find_map(|opt| match (opt.name, &opt.value) {
+        ("message", ResolvedValue::String(s)) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        _ => None,
+    });
+
+    let Some(enabled) = enabled else {
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.missing_options", locale = locale),
+            locale,
+        )));
+    };
+
+    let maintenance = {
+        let data = ctx.data.read().await;
+        let state = data.get::<AppStateKey>().expect("AppState not found");
+        state.read().await.maintenance.clone()
+    };
+
+    // The message only applies while maintenance mode is on
+    let state = MaintenanceState {
+        enabled,
+        message: message.filter(|_| enabled),
+    };
+    if let Err(e) = maintenance.set(db, state.clone()).await {
+        error!(error = %e, "Failed to save maintenance mode");
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.maintenance_save_failed", locale = locale),
+            locale,
+        )));
+    }
+
+    ctx.set_activity(Some(maintenance_mode::activity(enabled)));
+    info!(enabled, message = ?state.message, "Maintenance mode updated");
+
+    Ok(Some(InlineReply::embed(embeds::maintenance_updated(
+        &state,
+    ))))
+}
+
 // =============================================================================
 // Config Handlers
 // =============================================================================
 
 /// Handle /admin config show
 async fn handle_config_show(
-    ctx: &Context,
-    interaction: &CommandInteraction,
     db: &sea_orm::DatabaseConnection,
-) -> Result<(), serenity::Error> {
+) -> Result<Option<InlineReply>, serenity::Error> {
     // Load current intervals from database
     let status = get_interval(db, PollerType::Status).await;
     let incident = get_interval(db, PollerType::Incident).await;
@@ -243,21 +689,20 @@ async fn handle_config_show(
         &format_interval(incident),
         &format_interval(maintenance),
         &format_interval(metrics),
+        get_report_cooldown(db).await,
+        get_alert_expiry_minutes(db).await,
+        get_metric_retention_days(db).await,
     );
 
-    let response = CreateInteractionResponseMessage::new().embed(embed);
-    interaction
-        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
-        .await
+    Ok(Some(InlineReply::embed(embed)))
 }
 
 /// Handle /admin config set <poller> <seconds>
 async fn handle_config_set<'a>(
-    ctx: &Context,
-    interaction: &CommandInteraction,
     db: &sea_orm::DatabaseConnection,
     options: &[serenity::all::ResolvedOption<'a>],
-) -> Result<(), serenity::Error> {
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
     // Parse options
     let poller_str = options.iter().find_map(|opt| {
         if opt.name == "poller"
@@ -278,51 +723,266 @@ async fn handle_config_set<'a>(
     });
 
     let (Some(poller_str), Some(seconds)) = (poller_str, seconds) else {
-        return respond_error(ctx, interaction, "Missing required options", "en").await;
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.missing_options", locale = locale),
+            locale,
+        )));
     };
 
     let Some(poller) = PollerType::from_str(poller_str) else {
-        return respond_error(ctx, interaction, "Invalid poller type", "en").await;
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.invalid_poller", locale = locale),
+            locale,
+        )));
     };
 
     // Validate interval
-    if let Err(msg) = validate_interval(seconds) {
-        return respond_error(ctx, interaction, &msg, "en").await;
+    if validate_interval(seconds).is_err() {
+        return Ok(Some(InlineReply::error(
+            &t!(
+                "errors.admin.interval_out_of_range",
+                min = MIN_INTERVAL,
+                max = MAX_INTERVAL,
+                locale = locale
+            ),
+            locale,
+        )));
     }
 
     // Update interval in database
     if let Err(e) = crate::collector::config::set_interval(db, poller, seconds).await {
         error!(error = %e, "Failed to update polling interval");
-        return respond_error(ctx, interaction, "Failed to save configuration", "en").await;
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.save_failed", locale = locale),
+            locale,
+        )));
     }
 
     let embed = embeds::config_updated(poller.as_str(), seconds);
 
-    let response = CreateInteractionResponseMessage::new().embed(embed);
-    interaction
-        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
-        .await
+    Ok(Some(InlineReply::embed(embed)))
+}
+
+/// Handle /admin config cooldown <minutes>
+async fn handle_config_cooldown(
+    db: &sea_orm::DatabaseConnection,
+    options: &[serenity::all::ResolvedOption<'_>],
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let Some(minutes) = options.iter().find_map(|opt| {
+        if opt.name == "minutes"
+            && let ResolvedValue::Integer(i) = opt.value
+        {
+            return Some(i);
+        }
+        None
+    }) else {
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.missing_options", locale = locale),
+            locale,
+        )));
+    };
+
+    if !(MIN_REPORT_COOLDOWN..=MAX_REPORT_COOLDOWN).contains(&minutes) {
+        return Ok(Some(InlineReply::error(
+            &t!(
+                "errors.admin.cooldown_out_of_range",
+                min = MIN_REPORT_COOLDOWN,
+                max = MAX_REPORT_COOLDOWN,
+                locale = locale
+            ),
+            locale,
+        )));
+    }
+
+    if let Err(e) = set_report_cooldown(db, minutes).await {
+        error!(error = %e, "Failed to update report cooldown");
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.save_failed", locale = locale),
+            locale,
+        )));
+    }
+    info!(minutes, "Updated report cooldown");
+
+    Ok(Some(InlineReply::embed(embeds::cooldown_updated(minutes))))
+}
+
+/// Handle /admin config alert-expiry <minutes>
+async fn handle_config_alert_expiry(
+    db: &sea_orm::DatabaseConnection,
+    options: &[serenity::all::ResolvedOption<'_>],
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let Some(minutes) = options.iter().find_map(|opt| {
+        if opt.name == "minutes"
+            && let ResolvedValue::Integer(i) = opt.value
+        {
+            return Some(i);
+        }
+        None
+    }) else {
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.missing_options", locale = locale),
+            locale,
+        )));
+    };
+
+    if !(MIN_ALERT_EXPIRY_MINUTES..=MAX_ALERT_EXPIRY_MINUTES).contains(&minutes) {
+        return Ok(Some(InlineReply::error(
+            &t!(
+                "errors.admin.alert_expiry_out_of_range",
+                min = MIN_ALERT_EXPIRY_MINUTES,
+                max = MAX_ALERT_EXPIRY_MINUTES,
+                locale = locale
+            ),
+            locale,
+        )));
+    }
+
+    if let Err(e) = set_alert_expiry_minutes(db, minutes).await {
+        error!(error = %e, "Failed to update alert expiry");
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.save_failed", locale = locale),
+            locale,
+        )));
+    }
+    info!(minutes, "Updated alert expiry");
+
+    Ok(Some(InlineReply::embed(embeds::alert_expiry_updated(
+        minutes,
+    ))))
+}
+
+/// Handle /admin config retention <days>
+async fn handle_config_retention(
+    db: &sea_orm::DatabaseConnection,
+    options: &[serenity::all::ResolvedOption<'_>],
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let Some(days) = options.iter().find_map(|opt| {
+        if opt.name == "days"
+            && let ResolvedValue::Integer(i) = opt.value
+        {
+            return Some(i);
+        }
+        None
+    }) else {
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.missing_options", locale = locale),
+            locale,
+        )));
+    };
+
+    if validate_metric_retention(days).is_err() {
+        return Ok(Some(InlineReply::error(
+            &t!(
+                "errors.admin.retention_out_of_range",
+                min = MIN_METRIC_RETENTION_DAYS,
+                max = MAX_METRIC_RETENTION_DAYS,
+                locale = locale
+            ),
+            locale,
+        )));
+    }
+
+    if let Err(e) = set_metric_retention_days(db, days).await {
+        error!(error = %e, "Failed to update metric retention");
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.save_failed", locale = locale),
+            locale,
+        )));
+    }
+    info!(days, "Updated metric retention");
+
+    Ok(Some(InlineReply::embed(embeds::retention_updated(days))))
+}
+
+/// Handle /admin config validate-channels
+async fn handle_config_validate_channels(
+    ctx: &Context,
+    db: &Arc<sea_orm::DatabaseConnection>,
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    match channel_check::validate_channels(&ctx.http, db).await {
+        Ok(summary) => {
+            info!(
+                checked = summary.checked,
+                invalidated = summary.invalidated.len(),
+                "Validated alert channels on demand"
+            );
+            Ok(Some(InlineReply::embed(embeds::channels_validated(
+                &summary,
+            ))))
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to validate alert channels");
+            Ok(Some(InlineReply::error(
+                &t!("errors.admin.validate_channels_failed", locale = locale),
+                locale,
+            )))
+        }
+    }
 }
 
 /// Handle /admin config reset
 async fn handle_config_reset(
-    ctx: &Context,
-    interaction: &CommandInteraction,
     db: &sea_orm::DatabaseConnection,
-) -> Result<(), serenity::Error> {
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
     // Reset all pollers to default
     for poller in PollerType::all() {
         if let Err(e) = crate::collector::config::set_interval(db, *poller, DEFAULT_INTERVAL).await
         {
             error!(error = %e, poller = ?poller, "Failed to reset polling interval");
-            return respond_error(ctx, interaction, "Failed to reset configuration", "en").await;
+            return Ok(Some(InlineReply::error(
+                &t!("errors.admin.reset_failed", locale = locale),
+                locale,
+            )));
         }
     }
 
     let embed = embeds::config_reset(DEFAULT_INTERVAL);
 
-    let response = CreateInteractionResponseMessage::new().embed(embed);
-    interaction
-        .create_response(&ctx.http, CreateInteractionResponse::Message(response))
-        .await
+    Ok(Some(InlineReply::embed(embed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn uptime(elapsed: Duration) -> String {
+        let started_at = Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap();
+        format_uptime(started_at, started_at + elapsed)
+    }
+
+    #[test]
+    fn test_uptime_minutes() {
+        assert_eq!(uptime(Duration::zero()), "0m");
+        assert_eq!(uptime(Duration::seconds(59)), "0m");
+        assert_eq!(uptime(Duration::minutes(59)), "59m");
+    }
+
+    #[test]
+    fn test_uptime_hour_boundary() {
+        assert_eq!(uptime(Duration::minutes(60)), "1h 0m");
+        assert_eq!(uptime(Duration::minutes(61)), "1h 1m");
+        assert_eq!(
+            uptime(Duration::hours(23) + Duration::minutes(59)),
+            "23h 59m"
+        );
+    }
+
+    #[test]
+    fn test_uptime_day_boundary() {
+        assert_eq!(uptime(Duration::hours(24)), "1d 0h 0m");
+        assert_eq!(
+            uptime(Duration::days(1) + Duration::hours(1) + Duration::minutes(1)),
+            "1d 1h 1m"
+        );
+        assert_eq!(
+            uptime(Duration::days(45) + Duration::hours(23) + Duration::minutes(59)),
+            "45d 23h 59m"
+        );
+    }
 }
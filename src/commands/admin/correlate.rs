@@ -0,0 +1,412 @@
+//! /admin correlate - community reports against an official incident timeline
+//!
+//! Reports of every type are counted in 10-minute buckets from two hours
+//! before the incident started until it was resolved (or now), and charted
+//! with vertical markers for the start, each published update, and the
+//! resolution. The summary compares the first bucket above the pre-incident
+//! baseline with the official start time.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use rust_i18n::t;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serenity::all::{CreateAttachment, ResolvedOption, ResolvedValue};
+use tracing::{error, warn};
+
+use crate::commands::registry::InlineReply;
+use crate::entity::{incident_updates, incidents};
+use crate::repository::ReportRepository;
+use crate::visualization::query::MetricData;
+use crate::visualization::theme::{GRAPH_COLOR, GREEN, ORANGE, RED};
+use crate::visualization::{ChartMarker, generate_marked_chart};
+
+use super::embeds;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Width of one report bucket
+pub const BUCKET_MINUTES: i64 = 10;
+
+/// Time charted before the official start
+pub const LEAD_WINDOW_HOURS: i64 = 2;
+
+/// Longest time charted after the official start (long incidents are cut off)
+const MAX_INCIDENT_HOURS: i64 = 24;
+
+/// Buckets at the start of the range that define normal report volume
+///
+/// One hour, well before the official start, so an early spike does not
+/// raise its own baseline.
+const BASELINE_BUCKETS: usize = 6;
+
+/// Lowest baseline, so a few stray reports after a silent hour are not a spike
+const MIN_BASELINE: f64 = 2.0;
+
+/// Longest accepted incident ID (status page IDs are 12 characters)
+const MAX_INCIDENT_ID_LENGTH: usize = 64;
+
+/// Attachment filename of the correlation chart
+const CHART_FILENAME: &str = "correlation.png";
+
+// =============================================================================
+// Buckets and Lead Time
+// =============================================================================
+
+/// Whether an incident ID could be a status page ID
+pub fn is_valid_incident_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_INCIDENT_ID_LENGTH
+        && id.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Start times of the buckets covering `[start, end)`, at least one
+pub fn bucket_starts(start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let width = Duration::minutes(BUCKET_MINUTES);
+    let mut starts = vec![start];
+    let mut next = start + width;
+    while next < end {
+        starts.push(next);
+        next += width;
+    }
+    starts
+}
+
+/// Number of timestamps in each bucket; timestamps outside the buckets are ignored
+pub fn bucket_counts(timestamps: &[DateTime<Utc>], starts: &[DateTime<Utc>]) -> Vec<u64> {
+    let mut counts = vec![0; starts.len()];
+    let Some(first) = starts.first() else {
+        return counts;
+    };
+
+    for ts in timestamps {
+        let offset = (*ts - *first).num_minutes();
+        if offset < 0 {
+            continue;
+        }
+        if let Some(count) = counts.get_mut((offset / BUCKET_MINUTES) as usize) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+/// Reports per bucket a spike has to exceed
+///
+/// Twice the average of the first [`BASELINE_BUCKETS`] buckets, but at
+/// least [`MIN_BASELINE`].
+pub fn spike_baseline(counts: &[u64]) -> f64 {
+    let window = &counts[..counts.len().min(BASELINE_BUCKETS)];
+    if window.is_empty() {
+        return MIN_BASELINE;
+    }
+    let average = window.iter().sum::<u64>() as f64 / window.len() as f64;
+    (average * 2.0).max(MIN_BASELINE)
+}
+
+/// Index of the first bucket after the baseline window that exceeds `baseline`
+pub fn first_spike(counts: &[u64], baseline: f64) -> Option<usize> {
+    counts
+        .iter()
+        .enumerate()
+        .skip(BASELINE_BUCKETS)
+        .find(|(_, count)| **count as f64 > baseline)
+        .map(|(index, _)| index)
+}
+
+/// Minutes the report spike preceded the official start (negative if it followed)
+pub fn lead_minutes(spike_start: DateTime<Utc>, started_at: DateTime<Utc>) -> i64 {
+    (started_at - spike_start).num_minutes()
+}
+
+/// One-line comparison of the report spike with the official start
+pub fn lead_summary(lead: Option<i64>, baseline: f64) -> String {
+    match lead {
+        Some(minutes) if minutes > 0 => format!(
+            "Community reports preceded the official incident by {} minutes.",
+            minutes
+        ),
+        Some(minutes) if minutes < 0 => format!(
+            "Community reports followed the official incident by {} minutes.",
+            -minutes
+        ),
+        Some(_) => "Community reports spiked when the official incident started.".to_string(),
+        None => format!(
+            "No report spike above the baseline of {:.1} reports per {} minutes.",
+            baseline, BUCKET_MINUTES
+        ),
+    }
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Handle /admin correlate <incident_id>
+pub async fn handle_correlate(
+    db: &DatabaseConnection,
+    options: &[ResolvedOption<'_>],
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let Some(incident_id) = options.iter().find_map(|opt| match (opt.name, &opt.value) {
+        ("incident_id", ResolvedValue::String(s)) => Some(s.trim()),
+        _ => None,
+    }) else {
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.missing_options", locale = locale),
+            locale,
+        )));
+    };
+    if !is_valid_incident_id(incident_id) {
+        return Ok(Some(InlineReply::error(
+            &t!("errors.admin.invalid_incident_id", locale = locale),
+            locale,
+        )));
+    }
+
+    let incident = match incidents::Entity::find_by_id(incident_id).one(db).await {
+        Ok(Some(incident)) => incident,
+        Ok(None) => {
+            return Ok(Some(InlineReply::error(
+                &t!(
+                    "errors.admin.unknown_incident",
+                    id = incident_id,
+                    locale = locale
+                ),
+                locale,
+            )));
+        }
+        Err(e) => {
+            error!(error = %e, incident_id, "Failed to load incident");
+            return Ok(Some(InlineReply::error(
+                &t!("errors.admin.incident_load_failed", locale = locale),
+                locale,
+            )));
+        }
+    };
+    let updates = match incident_updates::Entity::find()
+        .filter(incident_updates::Column::IncidentId.eq(incident_id))
+        .order_by_asc(incident_updates::Column::PublishedAt)
+        .all(db)
+        .await
+    {
+        Ok(updates) => updates,
+        Err(e) => {
+            error!(error = %e, incident_id, "Failed to load incident updates");
+            return Ok(Some(InlineReply::error(
+                &t!("errors.admin.incident_updates_failed", locale = locale),
+                locale,
+            )));
+        }
+    };
+
+    let start = incident.started_at - Duration::hours(LEAD_WINDOW_HOURS);
+    let cutoff = incident.started_at + Duration::hours(MAX_INCIDENT_HOURS);
+    let end = incident.resolved_at.unwrap_or_else(Utc::now);
+    let truncated = end > cutoff;
+    let end = end
+        .min(cutoff)
+        .max(incident.started_at + Duration::minutes(BUCKET_MINUTES));
+
+    let timestamps = match ReportRepository::new(Arc::new(db.clone()))
+        .created_between(start, end)
+        .await
+    {
+        Ok(timestamps) => timestamps,
+        Err(e) => {
+            error!(error = %e, incident_id, "Failed to load reports for correlation");
+            return Ok(Some(InlineReply::error(
+                &t!("errors.admin.reports_load_failed", locale = locale),
+                locale,
+            )));
+        }
+    };
+
+    let starts = bucket_starts(start, end);
+    let counts = bucket_counts(&timestamps, &starts);
+    let baseline = spike_baseline(&counts);
+    let spike_start = first_spike(&counts, baseline).map(|index| starts[index]);
+    let lead = spike_start.map(|at| lead_minutes(at, incident.started_at));
+
+    let mut markers = vec![ChartMarker {
+        at: incident.started_at,
+        label: "Start".to_string(),
+        color: RED,
+    }];
+    markers.extend(updates.iter().map(|update| ChartMarker {
+        at: update.published_at,
+        label: update.status.clone(),
+        color: ORANGE,
+    }));
+    if let Some(resolved_at) = incident.resolved_at {
+        markers.push(ChartMarker {
+            at: resolved_at,
+            label: "Resolved".to_string(),
+            color: GREEN,
+        });
+    }
+
+    let data = MetricData {
+        timestamps: starts,
+        values: counts.iter().map(|count| *count as f64).collect(),
+        unit: "reports".to_string(),
+    };
+    // Admin output is English only
+    let chart = generate_marked_chart(
+        &format!("Reports per {} Minutes", BUCKET_MINUTES),
+        &data,
+        GRAPH_COLOR,
+        &markers,
+        "en",
+    )
+    .inspect_err(|e| warn!(error = %e, "Failed to render correlation chart"))
+    .ok();
+
+    let embed = embeds::correlation(&embeds::CorrelationInfo {
+        incident: &incident,
+        updates: updates.len(),
+        reports: timestamps.len() as u64,
+        peak: counts.iter().copied().max().unwrap_or(0),
+        spike_start,
+        summary: &lead_summary(lead, baseline),
+        truncated,
+        chart_filename: chart.as_ref().map(|_| CHART_FILENAME),
+    });
+
+    let mut reply = InlineReply::embed(embed);
+    if let Some(png) = chart {
+        reply = reply.attachment(CreateAttachment::bytes(png, CHART_FILENAME));
+    }
+    Ok(Some(reply))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn range_start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2028, 3, 5, 10, 0, 0).unwrap()
+    }
+
+    /// Official start, [`LEAD_WINDOW_HOURS`] after the range start
+    fn started_at() -> DateTime<Utc> {
+        range_start() + Duration::hours(LEAD_WINDOW_HOURS)
+    }
+
+    /// Lead time of the first spike in `counts`, as the handler computes it
+    fn lead(counts: &[u64]) -> (Option<i64>, f64) {
+        let starts = bucket_starts(range_start(), started_at() + Duration::hours(1));
+        let baseline = spike_baseline(counts);
+        let lead = first_spike(counts, baseline).map(|i| lead_minutes(starts[i], started_at()));
+        (lead, baseline)
+    }
+
+    /// A quiet hour of one report per bucket, then `rest`
+    fn series(rest: &[u64]) -> Vec<u64> {
+        let mut counts = vec![1; BASELINE_BUCKETS];
+        counts.extend_from_slice(rest);
+        counts
+    }
+
+    #[test]
+    fn test_spike_before_official_start() {
+        // Bucket 9 starts 30 minutes before the official start at bucket 12
+        let (lead, baseline) = lead(&series(&[1, 2, 1, 5, 8, 9, 9]));
+
+        assert_eq!(baseline, MIN_BASELINE);
+        assert_eq!(lead, Some(30));
+        assert_eq!(
+            lead_summary(lead, baseline),
+            "Community reports preceded the official incident by 30 minutes."
+        );
+    }
+
+    #[test]
+    fn test_spike_after_official_start() {
+        let (lead, baseline) = lead(&series(&[1, 0, 2, 1, 1, 2, 1, 1, 6, 9]));
+
+        assert_eq!(lead, Some(-20));
+        assert_eq!(
+            lead_summary(lead, baseline),
+            "Community reports followed the official incident by 20 minutes."
+        );
+    }
+
+    #[test]
+    fn test_spike_at_official_start() {
+        let (lead, baseline) = lead(&series(&[1, 1, 1, 1, 1, 1, 4]));
+
+        assert_eq!(lead, Some(0));
+        assert_eq!(
+            lead_summary(lead, baseline),
+            "Community reports spiked when the official incident started."
+        );
+    }
+
+    #[test]
+    fn test_no_spike_above_baseline() {
+        // A busy baseline hour raises the bar above later volume
+        let mut counts = vec![3; BASELINE_BUCKETS];
+        counts.extend_from_slice(&[5, 6, 4, 6, 6, 5]);
+        let (lead, baseline) = lead(&counts);
+
+        assert_eq!(baseline, 6.0);
+        assert_eq!(lead, None);
+        assert_eq!(
+            lead_summary(lead, baseline),
+            "No report spike above the baseline of 6.0 reports per 10 minutes."
+        );
+    }
+
+    #[test]
+    fn test_spike_inside_baseline_window_is_ignored() {
+        let (lead, _) = lead(&[9, 0, 0, 0, 0, 0, 1, 1]);
+
+        assert_eq!(lead, None);
+    }
+
+    #[test]
+    fn test_empty_series() {
+        assert_eq!(spike_baseline(&[]), MIN_BASELINE);
+        assert_eq!(first_spike(&[], MIN_BASELINE), None);
+        assert_eq!(bucket_counts(&[range_start()], &[]), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_bucket_counts_ignore_outside_timestamps() {
+        let starts = bucket_starts(range_start(), range_start() + Duration::minutes(25));
+        let timestamps = [
+            range_start() - Duration::minutes(1),
+            range_start(),
+            range_start() + Duration::minutes(9),
+            range_start() + Duration::minutes(20),
+            range_start() + Duration::minutes(30),
+        ];
+
+        assert_eq!(starts.len(), 3);
+        assert_eq!(bucket_counts(&timestamps, &starts), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn test_incident_id_validation() {
+        assert!(is_valid_incident_id("2dkp7k0yl1nw"));
+        assert!(is_valid_incident_id("ABC123"));
+        assert!(is_valid_incident_id(&"a".repeat(MAX_INCIDENT_ID_LENGTH)));
+
+        for id in [
+            "",
+            "abc-123",
+            "abc 123",
+            "abc/../x",
+            "abc'; --",
+            "ｉｄ",
+            "é",
+            &"a".repeat(MAX_INCIDENT_ID_LENGTH + 1),
+        ] {
+            assert!(!is_valid_incident_id(id), "{id:?}");
+        }
+    }
+}
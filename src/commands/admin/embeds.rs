@@ -1,8 +1,12 @@
 //! Embed builders for /admin command responses
 
+use chrono::{DateTime, Utc};
 use serenity::all::{Colour, CreateEmbed, CreateEmbedFooter, Timestamp};
 
-use crate::commands::shared::colors;
+use crate::alerts::channel_check::ChannelCheckSummary;
+use crate::bot::maintenance_mode::MaintenanceState;
+use crate::commands::shared::{colors, incident_types};
+use crate::entity::incidents;
 
 /// Build embed showing current polling intervals
 pub fn show_intervals(
@@ -10,6 +14,9 @@ pub fn show_intervals(
     incident: &str,
     maintenance: &str,
     metrics: &str,
+    report_cooldown: i64,
+    alert_expiry: i64,
+    metric_retention_days: i64,
 ) -> CreateEmbed {
     CreateEmbed::default()
         .title("Polling Intervals")
@@ -18,7 +25,16 @@ pub fn show_intervals(
         .field("Incident", incident, true)
         .field("Maintenance", maintenance, true)
         .field("Metrics", metrics, true)
-        .footer(CreateEmbedFooter::new("Use /admin config set to change"))
+        .field("Report Cooldown", format!("{}m", report_cooldown), true)
+        .field("Alert Expiry", format!("{}m", alert_expiry), true)
+        .field(
+            "Metric Retention",
+            format!("{}d", metric_retention_days),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(
+            "Use /admin config set, cooldown, alert-expiry, or retention to change",
+        ))
 }
 
 /// Build embed for successful config update
@@ -32,6 +48,79 @@ pub fn config_updated(poller: &str, seconds: u64) -> CreateEmbed {
         .timestamp(Timestamp::now())
 }
 
+/// Build embed for a report cooldown change
+pub fn cooldown_updated(minutes: i64) -> CreateEmbed {
+    CreateEmbed::default()
+        .title("Configuration Updated")
+        .description("Per-user /report cooldown has been changed.")
+        .color(Colour::new(colors::SUCCESS))
+        .field("New Cooldown", format!("{}m", minutes), true)
+        .timestamp(Timestamp::now())
+}
+
+/// Build embed for an alert expiry change
+pub fn alert_expiry_updated(minutes: i64) -> CreateEmbed {
+    CreateEmbed::default()
+        .title("Configuration Updated")
+        .description("Threshold alert expiry has been changed. It applies from the next alert.")
+        .color(Colour::new(colors::SUCCESS))
+        .field("New Expiry", format!("{}m", minutes), true)
+        .timestamp(Timestamp::now())
+}
+
+/// Build embed for a metric retention change
+pub fn retention_updated(days: i64) -> CreateEmbed {
+    CreateEmbed::default()
+        .title("Configuration Updated")
+        .description("Metric, status, and component logs older than this are pruned daily.")
+        .color(Colour::new(colors::SUCCESS))
+        .field("New Retention", format!("{}d", days), true)
+        .timestamp(Timestamp::now())
+}
+
+/// Most disabled guilds listed by ID in the channel check result
+const MAX_LISTED_INVALID_GUILDS: usize = 20;
+
+/// Build embed for an on-demand alert channel check
+pub fn channels_validated(summary: &ChannelCheckSummary) -> CreateEmbed {
+    let colour = if summary.invalidated.is_empty() {
+        colors::SUCCESS
+    } else {
+        colors::WARNING
+    };
+
+    let mut embed = CreateEmbed::default()
+        .title("Alert Channels Checked")
+        .description("Guilds whose alert channel is deleted or inaccessible have been disabled.")
+        .color(Colour::new(colour))
+        .field("Checked", summary.checked.to_string(), true)
+        .field("Disabled", summary.invalidated.len().to_string(), true)
+        .field(
+            "Transient Failures",
+            summary.transient_failures.to_string(),
+            true,
+        )
+        .timestamp(Timestamp::now());
+
+    if !summary.invalidated.is_empty() {
+        let mut lines: Vec<String> = summary
+            .invalidated
+            .iter()
+            .take(MAX_LISTED_INVALID_GUILDS)
+            .map(|guild_id| format!("`{}`", guild_id))
+            .collect();
+        let hidden = summary
+            .invalidated
+            .len()
+            .saturating_sub(MAX_LISTED_INVALID_GUILDS);
+        if hidden > 0 {
+            lines.push(format!("...and {} more", hidden));
+        }
+        embed = embed.field("Disabled Guilds", lines.join("\n"), false);
+    }
+    embed
+}
+
 /// Build embed for successful config reset
 pub fn config_reset(default_interval: u64) -> CreateEmbed {
     let default_str = format!("{}s", default_interval);
@@ -47,38 +136,154 @@ pub fn config_reset(default_interval: u64) -> CreateEmbed {
         .timestamp(Timestamp::now())
 }
 
+/// Build embed for a maintenance mode change
+pub fn maintenance_updated(state: &MaintenanceState) -> CreateEmbed {
+    let embed = if state.enabled {
+        CreateEmbed::default()
+            .title("Maintenance Mode On")
+            .description(
+                "Commands other than /admin reply with a maintenance notice. \
+                 Alerts and collector polls are paused.",
+            )
+            .color(Colour::new(colors::WARNING))
+    } else {
+        CreateEmbed::default()
+            .title("Maintenance Mode Off")
+            .description("Commands, alerts, and collector polls have resumed.")
+            .color(Colour::new(colors::SUCCESS))
+    };
+
+    match &state.message {
+        Some(message) => embed.field("Message", message, false),
+        None => embed,
+    }
+    .timestamp(Timestamp::now())
+}
+
+/// Successful and failed sends to one kind of destination
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeliveryCounts {
+    pub sent: usize,
+    pub failed: usize,
+}
+
+/// Build the confirmation shown under a broadcast preview
+pub fn broadcast_confirm(guilds: usize, users: Option<usize>) -> CreateEmbed {
+    CreateEmbed::default()
+        .title("Send Broadcast?")
+        .description(
+            "The announcement above goes to every registered guild's alert channel, \
+             titled in each recipient's language. Sent messages can't be recalled.",
+        )
+        .color(Colour::new(colors::WARNING))
+        .field("Guild Channels", guilds.to_string(), true)
+        .field(
+            "User DMs",
+            users.map_or("Not included".to_string(), |count| count.to_string()),
+            true,
+        )
+}
+
+/// Build embed shown while a broadcast is being sent
+pub fn broadcast_sending(destinations: usize) -> CreateEmbed {
+    CreateEmbed::default()
+        .title("Sending Broadcast")
+        .description(format!(
+            "Sending to {} destinations at 10 per second...",
+            destinations
+        ))
+        .color(Colour::new(colors::BRAND))
+}
+
+/// Build embed with the counts of a finished broadcast
+pub fn broadcast_result(guilds: DeliveryCounts, users: Option<DeliveryCounts>) -> CreateEmbed {
+    let failed = guilds.failed + users.map_or(0, |counts| counts.failed);
+    let colour = if failed == 0 {
+        colors::SUCCESS
+    } else {
+        colors::WARNING
+    };
+    let format_counts =
+        |counts: DeliveryCounts| format!("{} sent, {} failed", counts.sent, counts.failed);
+
+    CreateEmbed::default()
+        .title("Broadcast Sent")
+        .color(Colour::new(colour))
+        .field("Guild Channels", format_counts(guilds), true)
+        .field(
+            "User DMs",
+            users.map_or("Not included".to_string(), format_counts),
+            true,
+        )
+        .timestamp(Timestamp::now())
+}
+
+/// Build embed for a cancelled broadcast
+pub fn broadcast_cancelled() -> CreateEmbed {
+    CreateEmbed::default()
+        .title("Broadcast Cancelled")
+        .description("Nothing was sent.")
+        .color(Colour::new(colors::BRAND))
+}
+
+/// Bot information displayed by /admin show
+pub struct AdminShowInfo<'a> {
+    pub version: &'a str,
+    pub git_hash: &'a str,
+    pub migrations: &'a str,
+    pub uptime: &'a str,
+    pub guild_count: u64,
+    pub registered_guilds: u64,
+    pub registered_users: u64,
+    pub status_interval: &'a str,
+    pub incident_interval: &'a str,
+    pub maintenance_interval: &'a str,
+    pub metrics_interval: &'a str,
+    pub alert_safeguard: &'a str,
+    pub schema_issues: &'a str,
+    pub collector_health: &'a str,
+    pub scheduled_jobs: &'a str,
+    pub maintenance: &'a str,
+}
+
 /// Build embed for /admin show - bot info and command summary
-pub fn admin_show(
-    version: &str,
-    uptime: &str,
-    guild_count: u64,
-    registered_guilds: u64,
-    registered_users: u64,
-    status_interval: &str,
-    incident_interval: &str,
-    maintenance_interval: &str,
-    metrics_interval: &str,
-) -> CreateEmbed {
+pub fn admin_show(info: &AdminShowInfo<'_>) -> CreateEmbed {
     CreateEmbed::default()
         .title("VRCPulse Admin")
         .color(Colour::new(colors::BRAND))
-        .field("Version", version, true)
-        .field("Uptime", uptime, true)
-        .field("Guilds", guild_count.to_string(), true)
-        .field("Registered Guilds", registered_guilds.to_string(), true)
-        .field("Registered Users", registered_users.to_string(), true)
-        .field("\u{200b}", "\u{200b}", true) // Empty field for alignment
+        .field("Version", info.version, true)
+        .field("Uptime", info.uptime, true)
+        .field("Guilds", info.guild_count.to_string(), true)
+        .field(
+            "Registered Guilds",
+            info.registered_guilds.to_string(),
+            true,
+        )
+        .field("Registered Users", info.registered_users.to_string(), true)
+        .field("Git Commit", format!("`{}`", info.git_hash), true)
+        .field("Migrations", info.migrations, false)
+        .field("Maintenance Mode", info.maintenance, false)
         .field(
             "Polling Intervals",
             format!(
                 "Status: {}\nIncident: {}\nMaintenance: {}\nMetrics: {}",
-                status_interval, incident_interval, maintenance_interval, metrics_interval
+                info.status_interval,
+                info.incident_interval,
+                info.maintenance_interval,
+                info.metrics_interval
             ),
             false,
         )
+        .field("Alert Safeguard", info.alert_safeguard, false)
+        .field("Collector Health", info.collector_health, false)
+        .field("Scheduled Jobs", info.scheduled_jobs, false)
+        .field("Status Page Schema", info.schema_issues, false)
         .field(
             "Commands",
             "`/admin show` - Display bot information\n\
+             `/admin stats [guild_id] [reporters]` - Chart alert volume (30 days)\n\
+             `/admin correlate <incident_id>` - Chart reports against an incident\n\
+             `/admin maintenance <on|off> [message]` - Toggle maintenance mode\n\
              `/admin config show` - View polling intervals\n\
              `/admin config set <poller> <seconds>` - Update interval\n\
              `/admin config reset` - Reset all intervals to default",
@@ -86,3 +291,196 @@ pub fn admin_show(
         )
         .footer(CreateEmbedFooter::new("Owner-only commands"))
 }
+
+/// Build embed for /admin stats, with the alert chart as its image
+pub fn alert_stats(
+    scope: &str,
+    days: u32,
+    totals: &[(String, u64)],
+    reporters: Option<u64>,
+    chart_filename: Option<&str>,
+) -> CreateEmbed {
+    let total: u64 = totals.iter().map(|(_, count)| count).sum();
+    let by_type = if totals.is_empty() {
+        "None".to_string()
+    } else {
+        totals
+            .iter()
+            .map(|(alert_type, count)| format!("`{}`: {}", alert_type, count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let mut embed = CreateEmbed::default()
+        .title(format!("Alert Statistics ({} days)", days))
+        .color(Colour::new(colors::BRAND))
+        .field("Scope", scope, false)
+        .field("Alerts Sent", total.to_string(), true)
+        .field("By Type", by_type, true);
+    if let Some(reporters) = reporters {
+        embed = embed.field("Reporter-Days", reporters.to_string(), true);
+    }
+    if let Some(filename) = chart_filename {
+        embed = embed.image(format!("attachment://{}", filename));
+    }
+    embed
+        .footer(CreateEmbedFooter::new("Days are UTC"))
+        .timestamp(Timestamp::now())
+}
+
+/// Report and usage analytics displayed by /admin stats
+pub struct ActivityStats {
+    /// Reports per incident type in the last 24 hours, most reported first
+    pub reports_24h: Vec<(String, u64)>,
+    /// Reports per incident type in the last 7 days, most reported first
+    pub reports_7d: Vec<(String, u64)>,
+    /// Distinct reporting users in the last 7 days
+    pub reporters_7d: u64,
+    /// Threshold alerts delivered in the last 7 days (global scope only)
+    pub threshold_alerts_7d: Option<u64>,
+    /// Guilds by command invocations in the last 7 days (global scope only)
+    pub top_guilds: Option<Vec<(String, u64)>>,
+}
+
+/// Build embed for the report and usage section of /admin stats
+pub fn activity_stats(stats: &ActivityStats) -> CreateEmbed {
+    let format_types = |rows: &[(String, u64)]| {
+        let total: u64 = rows.iter().map(|(_, count)| count).sum();
+        let mut lines = vec![format!("**{}** total", total)];
+        lines.extend(rows.iter().map(|(incident_type, count)| {
+            format!("{}: {}", incident_types::display_name(incident_type), count)
+        }));
+        lines.join("\n")
+    };
+
+    let mut embed = CreateEmbed::default()
+        .title("Report Activity")
+        .color(Colour::new(colors::BRAND))
+        .field("Reports (24h)", format_types(&stats.reports_24h), true)
+        .field("Reports (7d)", format_types(&stats.reports_7d), true)
+        .field("Reporters (7d)", stats.reporters_7d.to_string(), true);
+    if let Some(count) = stats.threshold_alerts_7d {
+        embed = embed.field("Threshold Alerts (7d)", count.to_string(), true);
+    }
+    if let Some(guilds) = &stats.top_guilds {
+        let value = if guilds.is_empty() {
+            "None".to_string()
+        } else {
+            guilds
+                .iter()
+                .enumerate()
+                .map(|(i, (guild_id, count))| format!("{}. `{}`: {}", i + 1, guild_id, count))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        embed = embed.field("Most Active Guilds (7d commands)", value, false);
+    }
+    embed
+}
+
+/// Command usage displayed by /admin stats
+pub struct CommandUsage {
+    /// (command, subcommand, invocations), most used first
+    pub commands: Vec<(String, Option<String>, u64)>,
+    pub invocations: u64,
+    pub users: u64,
+    /// Distinct guilds (DMs and user installs without a guild are not counted)
+    pub guilds: u64,
+}
+
+/// Build embed for the command usage section of /admin stats
+pub fn command_usage(usage: &CommandUsage, days: i64) -> CreateEmbed {
+    let commands = if usage.commands.is_empty() {
+        "None".to_string()
+    } else {
+        usage
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(i, (command, subcommand, count))| {
+                let name = match subcommand {
+                    Some(subcommand) => format!("/{} {}", command, subcommand),
+                    None => format!("/{}", command),
+                };
+                format!("{}. `{}`: {}", i + 1, name, count)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::default()
+        .title(format!("Command Usage ({}d)", days))
+        .color(Colour::new(colors::BRAND))
+        .field("Invocations", usage.invocations.to_string(), true)
+        .field("Users", usage.users.to_string(), true)
+        .field("Guilds", usage.guilds.to_string(), true)
+        .field("Top Commands", commands, false)
+}
+
+/// Build an image-only embed for an additional /admin stats chart
+pub fn stats_chart(filename: &str) -> CreateEmbed {
+    CreateEmbed::default()
+        .color(Colour::new(colors::BRAND))
+        .image(format!("attachment://{}", filename))
+}
+
+/// Incident correlation displayed by /admin correlate
+pub struct CorrelationInfo<'a> {
+    pub incident: &'a incidents::Model,
+    /// Published updates, drawn as chart markers
+    pub updates: usize,
+    /// Reports in the charted range
+    pub reports: u64,
+    /// Highest report count in one bucket
+    pub peak: u64,
+    /// Start of the first bucket above the baseline
+    pub spike_start: Option<DateTime<Utc>>,
+    pub summary: &'a str,
+    /// Whether the range was cut off before the incident ended
+    pub truncated: bool,
+    pub chart_filename: Option<&'a str>,
+}
+
+/// Build embed for /admin correlate, with the timeline chart as its image
+pub fn correlation(info: &CorrelationInfo<'_>) -> CreateEmbed {
+    let incident = info.incident;
+    let resolved = match incident.resolved_at {
+        Some(at) => format!("<t:{}:f>", at.timestamp()),
+        None => "Unresolved".to_string(),
+    };
+    let spike = match info.spike_start {
+        Some(at) => format!("<t:{}:t>", at.timestamp()),
+        None => "None".to_string(),
+    };
+
+    let mut embed = CreateEmbed::default()
+        .title(format!("Incident Correlation: {}", incident.title))
+        .description(info.summary)
+        .color(Colour::new(colors::BRAND))
+        .field(
+            "Incident",
+            format!("`{}` ({})", incident.id, incident.impact),
+            false,
+        )
+        .field(
+            "Started",
+            format!("<t:{}:f>", incident.started_at.timestamp()),
+            true,
+        )
+        .field("Resolved", resolved, true)
+        .field("Updates", info.updates.to_string(), true)
+        .field("Reports", info.reports.to_string(), true)
+        .field("Peak per 10 min", info.peak.to_string(), true)
+        .field("First Spike", spike, true);
+    if let Some(filename) = info.chart_filename {
+        embed = embed.image(format!("attachment://{}", filename));
+    }
+    let footer = if info.truncated {
+        "All report types; chart cut off 24h after the start"
+    } else {
+        "All report types"
+    };
+    embed
+        .footer(CreateEmbedFooter::new(footer))
+        .timestamp(Timestamp::now())
+}
@@ -1,9 +1,7 @@
+mod broadcast;
 pub mod config;
+mod correlate;
 mod embeds;
+mod stats;
 
-use serenity::all::CreateCommand;
-
-/// Returns all admin slash command definitions
-pub fn all() -> Vec<CreateCommand> {
-    vec![config::register()]
-}
+pub use broadcast::handle_button;
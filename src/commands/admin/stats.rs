@@ -0,0 +1,369 @@
+//! /admin stats - alert volume over the last 30 days and report activity
+//!
+//! Daily `sent_alerts` counts are grouped by day and alert type in SQL, then
+//! filled out to one value per day here so every series shares the x-axis.
+//! A second embed summarizes recent reports by incident type, distinct
+//! reporters, and the guilds using the most commands, and a third the most
+//! used commands from `command_logs`, also grouped in SQL.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use rust_i18n::t;
+use sea_orm::DatabaseConnection;
+use serenity::all::{CreateAttachment, GuildId, ResolvedOption, ResolvedValue};
+use tracing::{error, warn};
+
+use crate::alerts::threshold::ALERT_TYPE_THRESHOLD;
+use crate::commands::registry::InlineReply;
+use crate::maintenance::REPORT_RETENTION_DAYS;
+use crate::repository::aggregates::stitch_daily;
+use crate::repository::{DailyCount, RecordRepository, ReportAggregateRepository};
+use crate::visualization::theme::SERIES_COLORS;
+use crate::visualization::{NamedSeries, generate_multi_series_chart};
+
+use super::embeds;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Days covered by the statistics, including today
+pub const STATS_DAYS: u32 = 30;
+
+/// Attachment filename of the alert volume chart
+const ALERT_CHART_FILENAME: &str = "alert_stats.png";
+
+/// Attachment filename of the daily reporters chart
+const REPORTER_CHART_FILENAME: &str = "reporters.png";
+
+/// Guilds listed by command usage
+const TOP_GUILDS: u64 = 5;
+
+/// Commands listed by usage
+const TOP_COMMANDS: u64 = 10;
+
+/// Days covered by the command usage embed
+const USAGE_DAYS: i64 = 7;
+
+// =============================================================================
+// Day Buckets
+// =============================================================================
+
+/// The `days` UTC dates ending with `today`, oldest first
+pub fn day_range(today: NaiveDate, days: u32) -> Vec<NaiveDate> {
+    let first = today - Duration::days(i64::from(days.saturating_sub(1)));
+    first.iter_days().take(days as usize).collect()
+}
+
+/// One dense series per key, with zero for days without rows
+///
+/// Series are ordered by key; rows outside `days` are ignored.
+pub fn fill_daily_series(rows: &[DailyCount], days: &[NaiveDate]) -> Vec<(String, Vec<u64>)> {
+    let mut series: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    for row in rows {
+        let Some(index) = days.iter().position(|day| *day == row.day) else {
+            continue;
+        };
+        series
+            .entry(row.key.as_str())
+            .or_insert_with(|| vec![0; days.len()])[index] += row.count;
+    }
+    series
+        .into_iter()
+        .map(|(key, values)| (key.to_string(), values))
+        .collect()
+}
+
+/// One value per day, with zero for days without rows
+pub fn fill_daily_values(rows: &[(NaiveDate, u64)], days: &[NaiveDate]) -> Vec<u64> {
+    days.iter()
+        .map(|day| {
+            rows.iter()
+                .filter(|(row_day, _)| row_day == day)
+                .map(|(_, count)| count)
+                .sum()
+        })
+        .collect()
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Handle /admin stats [guild_id] [reporters]
+pub async fn handle_stats(
+    db: &DatabaseConnection,
+    options: &[ResolvedOption<'_>],
+    locale: &str,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let guild = match options.iter().find_map(|opt| match (opt.name, &opt.value) {
+        ("guild_id", ResolvedValue::String(s)) => Some(s.trim()),
+        _ => None,
+    }) {
+        Some(raw) => match raw.parse::<u64>() {
+            Ok(id) if id != 0 => Some(GuildId::new(id)),
+            _ => {
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.invalid_guild_id", locale = locale),
+                    locale,
+                )));
+            }
+        },
+        None => None,
+    };
+    let with_reporters = options.iter().any(|opt| {
+        matches!(
+            (opt.name, &opt.value),
+            ("reporters", ResolvedValue::Boolean(true))
+        )
+    });
+
+    let days = day_range(Utc::now().date_naive(), STATS_DAYS);
+    let since = days[0].and_time(NaiveTime::MIN).and_utc();
+    let repo = RecordRepository::new(Arc::new(db.clone()));
+
+    let alert_rows = match repo.daily_sent_alerts(guild, since).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            error!(error = %e, "Failed to load alert statistics");
+            return Ok(Some(InlineReply::error(
+                &t!("errors.admin.alert_stats_failed", locale = locale),
+                locale,
+            )));
+        }
+    };
+    let alert_series = fill_daily_series(&alert_rows, &days);
+
+    let reporters = if with_reporters {
+        match load_reporters(db, &repo, guild, &days).await {
+            Ok(rows) => Some(fill_daily_values(&rows, &days)),
+            Err(e) => {
+                error!(error = %e, "Failed to load reporter statistics");
+                return Ok(Some(InlineReply::error(
+                    &t!("errors.admin.reporter_stats_failed", locale = locale),
+                    locale,
+                )));
+            }
+        }
+    } else {
+        None
+    };
+
+    let day_starts: Vec<_> = days
+        .iter()
+        .map(|day| day.and_time(NaiveTime::MIN).and_utc())
+        .collect();
+    let scope = match guild {
+        Some(id) => format!("Guild {}", id),
+        None => "All guilds and users".to_string(),
+    };
+
+    // Admin output is English only
+    let alert_chart = generate_multi_series_chart(
+        &format!("Alerts Sent per Day ({}d)", STATS_DAYS),
+        &day_starts,
+        &named_series(&alert_series),
+        "en",
+    );
+    let reporter_chart = reporters.as_ref().map(|values| {
+        generate_multi_series_chart(
+            &format!("Distinct Reporters per Day ({}d)", STATS_DAYS),
+            &day_starts,
+            &named_series(&[("reporters".to_string(), values.clone())]),
+            "en",
+        )
+    });
+
+    let totals: Vec<(String, u64)> = alert_series
+        .iter()
+        .map(|(key, values)| (key.clone(), values.iter().sum()))
+        .collect();
+    let reporter_total = reporters.as_ref().map(|values| values.iter().sum());
+
+    let alert_png = alert_chart
+        .inspect_err(|e| warn!(error = %e, "Failed to render alert statistics chart"))
+        .ok();
+    let reporter_png = reporter_chart.and_then(|chart| {
+        chart
+            .inspect_err(|e| warn!(error = %e, "Failed to render reporter statistics chart"))
+            .ok()
+    });
+
+    let mut stats_embeds = vec![embeds::alert_stats(
+        &scope,
+        STATS_DAYS,
+        &totals,
+        reporter_total,
+        alert_png.as_ref().map(|_| ALERT_CHART_FILENAME),
+    )];
+    if reporter_png.is_some() {
+        stats_embeds.push(embeds::stats_chart(REPORTER_CHART_FILENAME));
+    }
+    // Activity is supplementary; the alert statistics are still shown without it
+    match load_activity(&repo, guild).await {
+        Ok(activity) => stats_embeds.push(embeds::activity_stats(&activity)),
+        Err(e) => warn!(error = %e, "Failed to load report activity statistics"),
+    }
+    match load_command_usage(&repo, guild).await {
+        Ok(usage) => stats_embeds.push(embeds::command_usage(&usage, USAGE_DAYS)),
+        Err(e) => warn!(error = %e, "Failed to load command usage statistics"),
+    }
+
+    let mut reply = InlineReply::embeds(stats_embeds);
+    if let Some(png) = alert_png {
+        reply = reply.attachment(CreateAttachment::bytes(png, ALERT_CHART_FILENAME));
+    }
+    if let Some(png) = reporter_png {
+        reply = reply.attachment(CreateAttachment::bytes(png, REPORTER_CHART_FILENAME));
+    }
+    Ok(Some(reply))
+}
+
+/// Report counts, reporters, and command usage for the activity embed
+///
+/// Threshold alert and guild command counts are only loaded for the global
+/// scope.
+async fn load_activity(
+    repo: &RecordRepository,
+    guild: Option<GuildId>,
+) -> Result<embeds::ActivityStats, sea_orm::DbErr> {
+    let now = Utc::now();
+    let day_ago = now - Duration::hours(24);
+    let week_ago = now - Duration::days(7);
+
+    let (threshold_alerts_7d, top_guilds) = match guild {
+        Some(_) => (None, None),
+        None => (
+            Some(
+                repo.count_sent_alerts_since(&[ALERT_TYPE_THRESHOLD], week_ago)
+                    .await?,
+            ),
+            Some(repo.top_command_guilds(week_ago, TOP_GUILDS).await?),
+        ),
+    };
+
+    Ok(embeds::ActivityStats {
+        reports_24h: repo.report_counts_by_type(guild, day_ago).await?,
+        reports_7d: repo.report_counts_by_type(guild, week_ago).await?,
+        reporters_7d: repo.count_distinct_reporters(guild, week_ago).await?,
+        threshold_alerts_7d,
+        top_guilds,
+    })
+}
+
+/// Most used commands and usage totals over the last `USAGE_DAYS`
+async fn load_command_usage(
+    repo: &RecordRepository,
+    guild: Option<GuildId>,
+) -> Result<embeds::CommandUsage, sea_orm::DbErr> {
+    let since = Utc::now() - Duration::days(USAGE_DAYS);
+    let (invocations, users, guilds) = repo.command_usage_totals(guild, since).await?;
+
+    Ok(embeds::CommandUsage {
+        commands: repo.top_commands(guild, since, TOP_COMMANDS).await?,
+        invocations,
+        users,
+        guilds,
+    })
+}
+
+/// Distinct reporters per day, reading aggregates for days past raw retention
+///
+/// Aggregates have no guild IDs, so a per-guild series only covers raw days.
+async fn load_reporters(
+    db: &DatabaseConnection,
+    repo: &RecordRepository,
+    guild: Option<GuildId>,
+    days: &[NaiveDate],
+) -> Result<Vec<(NaiveDate, u64)>, sea_orm::DbErr> {
+    let since = days[0].and_time(NaiveTime::MIN).and_utc();
+    let raw = repo.daily_distinct_reporters(guild, since).await?;
+
+    let raw_from = Utc::now().date_naive() - Duration::days(REPORT_RETENTION_DAYS);
+    if guild.is_some() || days[0] >= raw_from {
+        return Ok(raw);
+    }
+    let aggregated = ReportAggregateRepository::new(Arc::new(db.clone()))
+        .daily_reporters_between(days[0], raw_from)
+        .await?;
+    Ok(stitch_daily(&aggregated, &raw, raw_from))
+}
+
+/// Chart series with colors assigned in order
+fn named_series(series: &[(String, Vec<u64>)]) -> Vec<NamedSeries> {
+    series
+        .iter()
+        .zip(SERIES_COLORS.iter().cycle())
+        .map(|((name, values), color)| NamedSeries {
+            name: name.clone(),
+            color: *color,
+            values: values.iter().map(|v| *v as f64).collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn count(day: NaiveDate, key: &str, count: u64) -> DailyCount {
+        DailyCount {
+            day,
+            key: key.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn test_day_range_crosses_month_boundaries() {
+        let days = day_range(day(2028, 3, 2), 4);
+
+        assert_eq!(
+            days,
+            vec![
+                day(2028, 2, 28),
+                day(2028, 2, 29),
+                day(2028, 3, 1),
+                day(2028, 3, 2)
+            ]
+        );
+        assert_eq!(day_range(day(2028, 3, 2), 30).len(), 30);
+        assert_eq!(day_range(day(2028, 3, 2), 0), Vec::<NaiveDate>::new());
+    }
+
+    #[test]
+    fn test_fill_daily_series() {
+        let days = day_range(day(2026, 1, 2), 3);
+        let rows = [
+            count(day(2026, 1, 2), "threshold", 4),
+            count(day(2025, 12, 31), "threshold", 1),
+            count(day(2026, 1, 1), "incident_opened", 2),
+            // Outside the range
+            count(day(2025, 12, 30), "threshold", 9),
+        ];
+
+        let series = fill_daily_series(&rows, &days);
+
+        assert_eq!(
+            series,
+            vec![
+                ("incident_opened".to_string(), vec![0, 2, 0]),
+                ("threshold".to_string(), vec![1, 0, 4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_daily_values() {
+        let days = day_range(day(2026, 1, 2), 3);
+        let rows = [(day(2026, 1, 2), 3), (day(2025, 12, 1), 7)];
+
+        assert_eq!(fill_daily_values(&rows, &days), vec![0, 0, 3]);
+    }
+}
@@ -0,0 +1,118 @@
+//! Duration parsing for /config silence
+
+use chrono::Duration;
+
+/// Longest silence an admin can schedule
+pub const MAX_SILENCE_DAYS: i64 = 7;
+
+/// Why a duration string was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DurationError {
+    /// Not `<number><unit>` with unit `m`, `h`, or `d`
+    Invalid,
+    /// Zero length
+    Zero,
+    /// Longer than `MAX_SILENCE_DAYS`
+    TooLong,
+}
+
+/// Parse a silence duration such as `90m`, `2h`, or `1d`
+///
+/// Units are case-insensitive and surrounding whitespace is ignored.
+pub fn parse_silence_duration(input: &str) -> Result<Duration, DurationError> {
+    let input = input.trim().to_ascii_lowercase();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or(DurationError::Invalid)?;
+    let (amount, unit) = input.split_at(split);
+
+    let amount: i64 = amount.parse().map_err(|_| DurationError::Invalid)?;
+    let minutes_per_unit = match unit {
+        "m" => 1,
+        "h" => 60,
+        "d" => 60 * 24,
+        _ => return Err(DurationError::Invalid),
+    };
+
+    let minutes = amount
+        .checked_mul(minutes_per_unit)
+        .ok_or(DurationError::TooLong)?;
+    if minutes == 0 {
+        return Err(DurationError::Zero);
+    }
+    if minutes > MAX_SILENCE_DAYS * 24 * 60 {
+        return Err(DurationError::TooLong);
+    }
+
+    Ok(Duration::minutes(minutes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_durations() {
+        let cases = [
+            ("90m", Duration::minutes(90)),
+            ("2h", Duration::hours(2)),
+            ("1d", Duration::days(1)),
+            ("1m", Duration::minutes(1)),
+            ("2H", Duration::hours(2)),
+            ("  3d\n", Duration::days(3)),
+            ("007h", Duration::hours(7)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_silence_duration(input), Ok(expected), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_durations() {
+        let cases = [
+            "", "h", "2", "2w", "2s", "-1h", "+1h", "1.5h", "2 h", "2hh", "h2", "2h30m", "１h",
+        ];
+        for input in cases {
+            assert_eq!(
+                parse_silence_duration(input),
+                Err(DurationError::Invalid),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_zero_duration() {
+        for input in ["0m", "0h", "0d", "000m"] {
+            assert_eq!(
+                parse_silence_duration(input),
+                Err(DurationError::Zero),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_cap() {
+        let max = Duration::days(MAX_SILENCE_DAYS);
+
+        assert_eq!(parse_silence_duration("7d"), Ok(max));
+        assert_eq!(parse_silence_duration("168h"), Ok(max));
+        assert_eq!(parse_silence_duration("10080m"), Ok(max));
+        assert_eq!(
+            parse_silence_duration("10081m"),
+            Err(DurationError::TooLong)
+        );
+        assert_eq!(parse_silence_duration("8d"), Err(DurationError::TooLong));
+        // Overflows the minute count rather than wrapping
+        assert_eq!(
+            parse_silence_duration("9223372036854775807d"),
+            Err(DurationError::TooLong)
+        );
+        // Doesn't fit an i64 at all
+        assert_eq!(
+            parse_silence_duration("99999999999999999999m"),
+            Err(DurationError::Invalid)
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Alert subscription embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::{CreateEmbed, CreateEmbedFooter};
+
+use crate::alerts::FILTERABLE_ALERT_TYPES;
+use crate::commands::shared::{embeds, incident_types};
+
+/// Build embed listing which incident types the guild receives alerts for
+pub fn alert_types_current(muted: &[&str], locale: &str) -> CreateEmbed {
+    embeds::info_embed(
+        t!("embeds.config.alerts.current.title", locale = locale),
+        format_type_lines(muted, locale),
+    )
+    .footer(CreateEmbedFooter::new(t!(
+        "embeds.config.alerts.current.footer",
+        locale = locale
+    )))
+}
+
+/// Build embed confirming an alert type was turned on or off
+pub fn alert_types_updated(
+    incident_type: &str,
+    enabled: bool,
+    muted: &[&str],
+    locale: &str,
+) -> CreateEmbed {
+    let key = if enabled {
+        "embeds.config.alerts.updated.description_on"
+    } else {
+        "embeds.config.alerts.updated.description_off"
+    };
+    let summary = t!(
+        key,
+        name = incident_types::display_name_localized(incident_type, locale),
+        locale = locale
+    );
+
+    embeds::success_embed(
+        t!("embeds.config.alerts.updated.title", locale = locale),
+        format!("{}\n\n{}", summary, format_type_lines(muted, locale)),
+    )
+}
+
+/// Summary for /config show: "All", "None", or the enabled types
+pub fn format_alert_types(muted: &[&str], locale: &str) -> String {
+    let enabled: Vec<String> = incident_types::INCIDENT_TYPE_KEYS
+        .iter()
+        .filter(|key| !muted.contains(key))
+        .map(|key| incident_types::display_name_localized(key, locale))
+        .collect();
+
+    if enabled.len() == incident_types::INCIDENT_TYPE_KEYS.len() {
+        t!("embeds.config.alerts.all", locale = locale).to_string()
+    } else if enabled.is_empty() {
+        t!("embeds.config.alerts.none", locale = locale).to_string()
+    } else {
+        enabled.join(", ")
+    }
+}
+
+/// Build embed confirming an alert category was turned on or off
+pub fn alert_filter_updated(
+    alert_type: &str,
+    enabled: bool,
+    disabled: &[String],
+    locale: &str,
+) -> CreateEmbed {
+    let key = if enabled {
+        "embeds.config.alerts.updated.description_on"
+    } else {
+        "embeds.config.alerts.updated.description_off"
+    };
+    let summary = t!(
+        key,
+        name = category_display_name(alert_type, locale),
+        locale = locale
+    );
+    embeds::success_embed(
+        t!("embeds.config.alerts.updated.title", locale = locale),
+        format!("{}\n\n{}", summary, format_category_lines(disabled, locale)),
+    )
+}
+
+/// Build embed listing which alert categories a user receives by DM
+pub fn notifications_current(disabled: &[String], locale: &str) -> CreateEmbed {
+    embeds::info_embed(
+        t!("embeds.config.notifications.current.title", locale = locale),
+        format_category_lines(disabled, locale),
+    )
+    .footer(CreateEmbedFooter::new(t!(
+        "embeds.config.notifications.current.footer",
+        locale = locale
+    )))
+}
+
+/// Build embed confirming a user turned an alert category on or off
+pub fn notifications_updated(
+    alert_type: &str,
+    enabled: bool,
+    disabled: &[String],
+    locale: &str,
+) -> CreateEmbed {
+    let key = if enabled {
+        "embeds.config.notifications.updated.description_on"
+    } else {
+        "embeds.config.notifications.updated.description_off"
+    };
+    let summary = t!(
+        key,
+        name = category_display_name(alert_type, locale),
+        locale = locale
+    );
+
+    embeds::success_embed(
+        t!("embeds.config.notifications.updated.title", locale = locale),
+        format!("{}\n\n{}", summary, format_category_lines(disabled, locale)),
+    )
+}
+
+/// Summary for /config show: "All" or the enabled alert categories
+pub fn format_alert_categories(disabled: &[String], locale: &str) -> String {
+    let enabled: Vec<String> = FILTERABLE_ALERT_TYPES
+        .iter()
+        .filter(|key| !disabled.iter().any(|d| d == *key))
+        .map(|key| category_display_name(key, locale))
+        .collect();
+
+    if enabled.len() == FILTERABLE_ALERT_TYPES.len() {
+        t!("embeds.config.alerts.all", locale = locale).to_string()
+    } else if enabled.is_empty() {
+        t!("embeds.config.alerts.none_categories", locale = locale).to_string()
+    } else {
+        enabled.join(", ")
+    }
+}
+
+/// Localized name of an alert category
+fn category_display_name(alert_type: &str, locale: &str) -> String {
+    match alert_type {
+        "threshold" => t!("embeds.config.alerts.category_threshold", locale = locale).to_string(),
+        "maintenance" => {
+            t!("embeds.config.alerts.category_maintenance", locale = locale).to_string()
+        }
+        "component" => t!("embeds.config.alerts.category_component", locale = locale).to_string(),
+        "incident_opened" => t!(
+            "embeds.config.alerts.category_incident_opened",
+            locale = locale
+        )
+        .to_string(),
+        "incident_resolved" => t!(
+            "embeds.config.alerts.category_incident_resolved",
+            locale = locale
+        )
+        .to_string(),
+        _ => alert_type.to_string(),
+    }
+}
+
+/// One "category: On/Off" line per filterable alert category
+fn format_category_lines(disabled: &[String], locale: &str) -> String {
+    FILTERABLE_ALERT_TYPES
+        .iter()
+        .map(|key| {
+            let off = disabled.iter().any(|d| d == key);
+            format_line(&category_display_name(key, locale), off, locale)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One "type - On/Off" line per incident type
+fn format_type_lines(muted: &[&str], locale: &str) -> String {
+    incident_types::INCIDENT_TYPE_KEYS
+        .iter()
+        .map(|key| {
+            format_line(
+                &incident_types::display_name_localized(key, locale),
+                muted.contains(key),
+                locale,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// "name: On" or "name: Off"
+fn format_line(name: &str, off: bool, locale: &str) -> String {
+    let key = if off {
+        "embeds.config.alerts.line_off"
+    } else {
+        "embeds.config.alerts.line_on"
+    };
+    t!(key, name = name, locale = locale).to_string()
+}
@@ -0,0 +1,102 @@
+//! Data summary embed builder for /config command
+
+use chrono::{DateTime, Utc};
+use rust_i18n::t;
+use serenity::all::{CreateEmbed, CreateEmbedFooter};
+
+use crate::commands::shared::embeds;
+
+use super::super::export::{DataExport, ExportSubject};
+
+/// Build embed summarizing the data stored for a guild or user
+pub fn data_summary(export: &DataExport, locale: &str) -> CreateEmbed {
+    let (title, description) = match export.subject {
+        ExportSubject::Guild { .. } => (
+            t!("embeds.config.data.title_guild", locale = locale),
+            t!("embeds.config.data.description_guild", locale = locale),
+        ),
+        ExportSubject::User { .. } => (
+            t!("embeds.config.data.title_user", locale = locale),
+            t!("embeds.config.data.description_user", locale = locale),
+        ),
+    };
+
+    let config_display = export
+        .config
+        .as_ref()
+        .map(|config| {
+            config
+                .field_names()
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_else(|| t!("embeds.config.data.config_none", locale = locale).to_string());
+
+    let records = &export.records;
+    let settings = &export.settings;
+    let setting_counts = match export.subject {
+        ExportSubject::Guild { .. } => vec![
+            ("embeds.config.data.field_silences", settings.silences.len()),
+            (
+                "embeds.config.data.field_alert_channels",
+                settings.alert_channels.len(),
+            ),
+            (
+                "embeds.config.data.field_alert_filters",
+                settings.alert_filters.len(),
+            ),
+            ("embeds.config.data.field_webhooks", settings.webhooks.len()),
+        ],
+        ExportSubject::User { .. } => vec![(
+            "embeds.config.data.field_notification_filters",
+            settings.notification_filters.len(),
+        )],
+    };
+
+    let embed = embeds::info_embed(title, description).field(
+        t!("embeds.config.data.field_config", locale = locale),
+        config_display,
+        false,
+    );
+    setting_counts
+        .into_iter()
+        .fold(embed, |embed, (key, count)| {
+            embed.field(t!(key, locale = locale), count.to_string(), true)
+        })
+        .field(
+            t!("embeds.config.data.field_reports", locale = locale),
+            records.user_reports.count.to_string(),
+            true,
+        )
+        .field(
+            t!("embeds.config.data.field_alerts", locale = locale),
+            records.sent_alerts.count.to_string(),
+            true,
+        )
+        .field(
+            t!("embeds.config.data.field_commands", locale = locale),
+            records.command_logs.count.to_string(),
+            true,
+        )
+        .field(
+            t!("embeds.config.data.field_oldest", locale = locale),
+            format_timestamp(records.oldest(), locale),
+            true,
+        )
+        .field(
+            t!("embeds.config.data.field_newest", locale = locale),
+            format_timestamp(records.newest(), locale),
+            true,
+        )
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.config.data.footer",
+            locale = locale
+        )))
+}
+
+fn format_timestamp(ts: Option<DateTime<Utc>>, locale: &str) -> String {
+    ts.map(|ts| format!("<t:{}:f>", ts.timestamp()))
+        .unwrap_or_else(|| t!("embeds.config.data.no_records", locale = locale).to_string())
+}
@@ -0,0 +1,50 @@
+//! Weekly digest embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::{CreateEmbed, CreateEmbedFooter};
+
+use crate::commands::shared::embeds;
+
+/// Build embed showing current weekly digest setting
+pub fn digest_current(enabled: bool, locale: &str) -> CreateEmbed {
+    let description = if enabled {
+        t!(
+            "embeds.config.digest.current.description_on",
+            locale = locale
+        )
+    } else {
+        t!(
+            "embeds.config.digest.current.description_off",
+            locale = locale
+        )
+    };
+
+    embeds::info_embed(
+        t!("embeds.config.digest.current.title", locale = locale),
+        description,
+    )
+    .footer(CreateEmbedFooter::new(t!(
+        "embeds.config.digest.current.footer",
+        locale = locale
+    )))
+}
+
+/// Build embed confirming weekly digest update
+pub fn digest_updated(enabled: bool, locale: &str) -> CreateEmbed {
+    let description = if enabled {
+        t!(
+            "embeds.config.digest.updated.description_on",
+            locale = locale
+        )
+    } else {
+        t!(
+            "embeds.config.digest.updated.description_off",
+            locale = locale
+        )
+    };
+
+    embeds::success_embed(
+        t!("embeds.config.digest.updated.title", locale = locale),
+        description,
+    )
+}
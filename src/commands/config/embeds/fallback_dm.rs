@@ -0,0 +1,50 @@
+//! Owner DM fallback embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::{CreateEmbed, CreateEmbedFooter};
+
+use crate::commands::shared::embeds;
+
+/// Build embed showing current owner DM fallback setting
+pub fn fallback_dm_current(enabled: bool, locale: &str) -> CreateEmbed {
+    let description = if enabled {
+        t!(
+            "embeds.config.fallbackdm.current.description_on",
+            locale = locale
+        )
+    } else {
+        t!(
+            "embeds.config.fallbackdm.current.description_off",
+            locale = locale
+        )
+    };
+
+    embeds::info_embed(
+        t!("embeds.config.fallbackdm.current.title", locale = locale),
+        description,
+    )
+    .footer(CreateEmbedFooter::new(t!(
+        "embeds.config.fallbackdm.current.footer",
+        locale = locale
+    )))
+}
+
+/// Build embed confirming owner DM fallback update
+pub fn fallback_dm_updated(enabled: bool, locale: &str) -> CreateEmbed {
+    let description = if enabled {
+        t!(
+            "embeds.config.fallbackdm.updated.description_on",
+            locale = locale
+        )
+    } else {
+        t!(
+            "embeds.config.fallbackdm.updated.description_off",
+            locale = locale
+        )
+    };
+
+    embeds::success_embed(
+        t!("embeds.config.fallbackdm.updated.title", locale = locale),
+        description,
+    )
+}
@@ -1,14 +1,49 @@
 //! Guild-related embed builders for /config command
 
+use chrono::Utc;
 use rust_i18n::t;
 use serenity::all::{Colour, CreateEmbed, CreateEmbedFooter};
 
 use crate::commands::shared::colors;
-use crate::entity::guild_configs;
+use crate::commands::shared::incident_types;
+use crate::commands::shared::sparkline::sparkline;
+use crate::entity::{alert_silences, guild_alert_channels, guild_configs, guild_webhooks};
+
+use super::{
+    format_alert_categories, format_alert_types, format_mention_role, format_quiet_hours,
+    format_report_role, format_silences, format_threshold, format_webhooks,
+};
 use crate::i18n::get_language_display_name;
+use crate::repository::{RegistrationStatus, channels, config, registration};
+
+/// Per-guild activity shown in /config show
+pub struct GuildStats {
+    /// Alerts delivered in the last 30 days
+    pub alerts_30d: u64,
+    /// Member reports submitted in the last 30 days
+    pub reports_30d: u64,
+    /// Alerts per week, oldest first
+    pub weekly_alerts: Vec<u64>,
+}
+
+/// Extra alert destinations shown in /config show
+pub struct GuildDestinations<'a> {
+    /// Enabled per-type alert channels
+    pub alert_channels: &'a [guild_alert_channels::Model],
+    /// Enabled alert webhooks
+    pub webhooks: &'a [guild_webhooks::Model],
+}
 
 /// Build embed for active guild configuration
-pub fn show_guild_active(config: &guild_configs::Model, locale: &str) -> CreateEmbed {
+pub fn show_guild_active(
+    config: &guild_configs::Model,
+    stats: Option<&GuildStats>,
+    silences: &[alert_silences::Model],
+    destinations: &GuildDestinations<'_>,
+    disabled_alerts: &[String],
+    global_threshold: Option<i64>,
+    locale: &str,
+) -> CreateEmbed {
     let channel_display = config
         .channel_id
         .as_ref()
@@ -23,7 +58,32 @@ pub fn show_guild_active(config: &guild_configs::Model, locale: &str) -> CreateE
 
     let language_display = get_language_display_name(config.language.as_deref(), locale);
 
-    CreateEmbed::default()
+    let fallback_dm_display = if config.fallback_dm {
+        t!(
+            "embeds.config.show.guild_active.field_fallback_dm_on",
+            locale = locale
+        )
+    } else {
+        t!(
+            "embeds.config.show.guild_active.field_fallback_dm_off",
+            locale = locale
+        )
+    };
+
+    // Paused guilds show the resume time; expired pauses read as active
+    let status_display = match registration::guild(Some(config), Utc::now()) {
+        RegistrationStatus::Paused { until } => t!(
+            "embeds.config.show.guild_active.field_status_paused",
+            locale = locale,
+            until = format!("<t:{}:f>", until.timestamp())
+        ),
+        _ => t!(
+            "embeds.config.show.guild_active.field_status_value",
+            locale = locale
+        ),
+    };
+
+    let mut embed = CreateEmbed::default()
         .title(t!("embeds.config.show.guild_active.title", locale = locale))
         .color(Colour::new(colors::BRAND))
         .field(
@@ -31,10 +91,7 @@ pub fn show_guild_active(config: &guild_configs::Model, locale: &str) -> CreateE
                 "embeds.config.show.guild_active.field_status",
                 locale = locale
             ),
-            t!(
-                "embeds.config.show.guild_active.field_status_value",
-                locale = locale
-            ),
+            status_display,
             true,
         )
         .field(
@@ -53,6 +110,62 @@ pub fn show_guild_active(config: &guild_configs::Model, locale: &str) -> CreateE
             language_display,
             true,
         )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_fallback_dm",
+                locale = locale
+            ),
+            fallback_dm_display,
+            true,
+        )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_mention_role",
+                locale = locale
+            ),
+            format_mention_role(config::mention_role(config), locale),
+            true,
+        )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_quiet_hours",
+                locale = locale
+            ),
+            format_quiet_hours(config::quiet_hours(config), locale),
+            true,
+        )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_threshold",
+                locale = locale
+            ),
+            format_threshold(config.alert_threshold, global_threshold, locale),
+            true,
+        )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_alert_types",
+                locale = locale
+            ),
+            format_alert_types(&config::muted_incident_types(config), locale),
+            true,
+        )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_alert_categories",
+                locale = locale
+            ),
+            format_alert_categories(disabled_alerts, locale),
+            true,
+        )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_report_role",
+                locale = locale
+            ),
+            format_report_role(config::report_role(config), locale),
+            true,
+        )
         .field(
             t!(
                 "embeds.config.show.guild_active.field_registered",
@@ -64,7 +177,77 @@ pub fn show_guild_active(config: &guild_configs::Model, locale: &str) -> CreateE
         .footer(CreateEmbedFooter::new(t!(
             "embeds.config.show.guild_active.footer",
             locale = locale
-        )))
+        )));
+
+    if !silences.is_empty() {
+        embed = embed.field(
+            t!(
+                "embeds.config.show.guild_active.field_silences",
+                locale = locale
+            ),
+            format_silences(silences, locale),
+            false,
+        );
+    }
+
+    if !destinations.alert_channels.is_empty() {
+        let lines: Vec<String> = destinations
+            .alert_channels
+            .iter()
+            .map(|row| {
+                let types: Vec<String> = channels::channel_types(row)
+                    .into_iter()
+                    .map(|key| incident_types::display_name_localized(key, locale))
+                    .collect();
+                format!("<#{}>: {}", row.channel_id, types.join(", "))
+            })
+            .collect();
+        embed = embed.field(
+            t!(
+                "embeds.config.show.guild_active.field_alert_channels",
+                locale = locale
+            ),
+            lines.join("\n"),
+            false,
+        );
+    }
+
+    if !destinations.webhooks.is_empty() {
+        embed = embed.field(
+            t!(
+                "embeds.config.show.guild_active.field_webhooks",
+                locale = locale
+            ),
+            format_webhooks(destinations.webhooks, locale),
+            false,
+        );
+    }
+
+    let Some(stats) = stats else {
+        return embed;
+    };
+
+    embed
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_alerts_30d",
+                locale = locale
+            ),
+            format!(
+                "{}\n`{}`",
+                stats.alerts_30d,
+                sparkline(&stats.weekly_alerts)
+            ),
+            true,
+        )
+        .field(
+            t!(
+                "embeds.config.show.guild_active.field_reports_30d",
+                locale = locale
+            ),
+            stats.reports_30d.to_string(),
+            true,
+        )
 }
 
 /// Build embed for disabled guild configuration
@@ -82,17 +265,19 @@ pub fn show_guild_disabled(config: &guild_configs::Model, locale: &str) -> Creat
         });
 
     let time = format!("<t:{}:R>", config.updated_at.timestamp());
+    // Disabled by the channel check rather than by an admin
+    let description_key = if config.channel_invalid {
+        "embeds.config.show.guild_disabled.description_channel_invalid"
+    } else {
+        "embeds.config.show.guild_disabled.description"
+    };
 
     CreateEmbed::default()
         .title(t!(
             "embeds.config.show.guild_disabled.title",
             locale = locale
         ))
-        .description(t!(
-            "embeds.config.show.guild_disabled.description",
-            locale = locale,
-            time = time
-        ))
+        .description(t!(description_key, locale = locale, time = time))
         .color(Colour::new(colors::WARNING))
         .field(
             t!(
@@ -3,15 +3,18 @@
 use rust_i18n::t;
 use serenity::all::{CreateEmbed, CreateEmbedFooter};
 
-use crate::commands::shared::embeds;
-use crate::i18n::get_language_display_name;
+use crate::commands::shared::{embeds, text};
+use crate::i18n::{PREVIEW_KEYS, SUPPORTED_LOCALES, get_language_display_name};
 
-/// Build embed showing current language setting
+/// Longest preview sample shown per string
+const PREVIEW_SAMPLE_CHARS: usize = 80;
+
+/// Build embed showing current language setting, with a preview of every locale
 pub fn language_current(current: Option<&str>, is_guild: bool, locale: &str) -> CreateEmbed {
     let display_name = get_language_display_name(current, locale);
     let context = if is_guild { "server" } else { "account" };
 
-    embeds::info_embed(
+    let embed = embeds::info_embed(
         t!("embeds.config.language.current.title", locale = locale),
         t!(
             "embeds.config.language.current.description",
@@ -30,11 +33,39 @@ pub fn language_current(current: Option<&str>, is_guild: bool, locale: &str) ->
             locale = locale
         ),
         false,
-    )
-    .footer(CreateEmbedFooter::new(t!(
-        "embeds.config.language.current.footer",
-        locale = locale
-    )))
+    );
+
+    SUPPORTED_LOCALES
+        .iter()
+        .fold(embed, |embed, sample_locale| {
+            embed.field(
+                t!(
+                    "embeds.config.language.current.field_preview",
+                    locale = locale,
+                    language = get_language_display_name(Some(sample_locale), locale)
+                ),
+                preview_samples(sample_locale),
+                true,
+            )
+        })
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.config.language.current.footer",
+            locale = locale
+        )))
+}
+
+/// The preview strings in one locale, one per line
+fn preview_samples(locale: &str) -> String {
+    PREVIEW_KEYS
+        .iter()
+        .map(|key| {
+            format!(
+                "- {}",
+                text::truncate(&t!(*key, locale = locale), PREVIEW_SAMPLE_CHARS)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Build embed confirming language update
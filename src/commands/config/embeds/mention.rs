@@ -0,0 +1,34 @@
+//! Alert role mention embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::{CreateEmbed, Mentionable, RoleId};
+
+use crate::commands::shared::embeds;
+
+/// Build embed confirming the alert role was set or cleared
+pub fn mention_updated(role_id: Option<RoleId>, locale: &str) -> CreateEmbed {
+    let description = match role_id {
+        Some(role_id) => t!(
+            "embeds.config.mention.updated.description_set",
+            role = role_id.mention().to_string(),
+            locale = locale
+        ),
+        None => t!(
+            "embeds.config.mention.updated.description_cleared",
+            locale = locale
+        ),
+    };
+
+    embeds::success_embed(
+        t!("embeds.config.mention.updated.title", locale = locale),
+        description,
+    )
+}
+
+/// Alert role for /config show, or "None"
+pub fn format_mention_role(role_id: Option<RoleId>, locale: &str) -> String {
+    match role_id {
+        Some(role_id) => role_id.mention().to_string(),
+        None => t!("embeds.config.mention.none", locale = locale).to_string(),
+    }
+}
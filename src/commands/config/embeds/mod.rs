@@ -1,13 +1,39 @@
 //! Embed builders for /config command responses
 
+mod alerts;
+mod data;
+mod digest;
+mod fallback_dm;
 mod guild;
 mod language;
+mod mention;
+mod quiet_hours;
+mod report_role;
+mod silence;
+mod threshold;
 mod unregister;
 mod user;
+mod webhook;
 
-pub use guild::{show_guild_active, show_guild_disabled, show_guild_intro};
+pub use alerts::{
+    alert_filter_updated, alert_types_current, alert_types_updated, format_alert_categories,
+    format_alert_types, notifications_current, notifications_updated,
+};
+pub use data::data_summary;
+pub use digest::{digest_current, digest_updated};
+pub use fallback_dm::{fallback_dm_current, fallback_dm_updated};
+pub use guild::{
+    GuildDestinations, GuildStats, show_guild_active, show_guild_disabled, show_guild_intro,
+};
 pub use language::{language_current, language_updated};
+pub use mention::{format_mention_role, mention_updated};
+pub use quiet_hours::{format_quiet_hours, quiet_hours_updated};
+pub use report_role::{format_report_role, report_role_updated};
+pub use silence::{format_silences, silence_cleared, silence_set};
+pub use threshold::{format_threshold, threshold_updated};
 pub use unregister::{
-    unregister_cancelled, unregister_confirm, unregister_error, unregister_success,
+    UnregisterTarget, unregister_cancelled, unregister_confirm, unregister_error,
+    unregister_paused, unregister_success,
 };
 pub use user::{show_user_active, show_user_disabled, show_user_intro};
+pub use webhook::{format_webhooks, webhook_added, webhook_removed};
@@ -0,0 +1,40 @@
+//! Quiet hours embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::CreateEmbed;
+
+use crate::commands::shared::embeds;
+
+/// Build embed confirming the quiet window was set or cleared
+pub fn quiet_hours_updated(window: Option<(u32, u32)>, locale: &str) -> CreateEmbed {
+    let description = match window {
+        Some(window) => t!(
+            "embeds.config.quiet_hours.updated.description_set",
+            window = format_quiet_hours(Some(window), locale),
+            locale = locale
+        ),
+        None => t!(
+            "embeds.config.quiet_hours.updated.description_cleared",
+            locale = locale
+        ),
+    };
+
+    embeds::success_embed(
+        t!("embeds.config.quiet_hours.updated.title", locale = locale),
+        description,
+    )
+}
+
+/// Quiet window for /config show, or "None"
+pub fn format_quiet_hours(window: Option<(u32, u32)>, locale: &str) -> String {
+    match window {
+        Some((start, end)) => t!(
+            "embeds.config.quiet_hours.window",
+            start = format!("{:02}", start),
+            end = format!("{:02}", end),
+            locale = locale
+        )
+        .to_string(),
+        None => t!("embeds.config.quiet_hours.none", locale = locale).to_string(),
+    }
+}
@@ -0,0 +1,34 @@
+//! Report role restriction embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::{CreateEmbed, Mentionable, RoleId};
+
+use crate::commands::shared::embeds;
+
+/// Build embed confirming the report role was set or cleared
+pub fn report_role_updated(role_id: Option<RoleId>, locale: &str) -> CreateEmbed {
+    let description = match role_id {
+        Some(role_id) => t!(
+            "embeds.config.report_role.updated.description_set",
+            role = role_id.mention().to_string(),
+            locale = locale
+        ),
+        None => t!(
+            "embeds.config.report_role.updated.description_cleared",
+            locale = locale
+        ),
+    };
+
+    embeds::success_embed(
+        t!("embeds.config.report_role.updated.title", locale = locale),
+        description,
+    )
+}
+
+/// Report role for /config show, or "Everyone"
+pub fn format_report_role(role_id: Option<RoleId>, locale: &str) -> String {
+    match role_id {
+        Some(role_id) => role_id.mention().to_string(),
+        None => t!("embeds.config.report_role.everyone", locale = locale).to_string(),
+    }
+}
@@ -0,0 +1,70 @@
+//! Alert silence embed builders for /config command
+
+use chrono::{DateTime, Utc};
+use rust_i18n::t;
+use serenity::all::CreateEmbed;
+
+use crate::commands::shared::{embeds, incident_types};
+use crate::entity::alert_silences;
+
+/// Build embed confirming a new silence
+pub fn silence_set(incident_type: Option<&str>, until: DateTime<Utc>, locale: &str) -> CreateEmbed {
+    embeds::success_embed(
+        t!("embeds.config.silence.set.title", locale = locale),
+        t!(
+            "embeds.config.silence.set.description",
+            scope = scope_display(incident_type, locale),
+            until = format!("<t:{}:f>", until.timestamp()),
+            relative = format!("<t:{}:R>", until.timestamp()),
+            locale = locale
+        ),
+    )
+}
+
+/// Build embed confirming silences were cleared
+pub fn silence_cleared(count: u64, locale: &str) -> CreateEmbed {
+    if count == 0 {
+        return embeds::info_embed(
+            t!("embeds.config.silence.cleared.title", locale = locale),
+            t!(
+                "embeds.config.silence.cleared.description_none",
+                locale = locale
+            ),
+        );
+    }
+
+    embeds::success_embed(
+        t!("embeds.config.silence.cleared.title", locale = locale),
+        t!(
+            "embeds.config.silence.cleared.description",
+            count = count,
+            locale = locale
+        ),
+    )
+}
+
+/// Format active silences for /config show, one per line
+pub fn format_silences(silences: &[alert_silences::Model], locale: &str) -> String {
+    silences
+        .iter()
+        .map(|silence| {
+            t!(
+                "embeds.config.silence.show_line",
+                scope = scope_display(silence.incident_type.as_deref(), locale),
+                until = format!("<t:{}:R>", silence.until.timestamp()),
+                user = format!("<@{}>", silence.created_by),
+                locale = locale
+            )
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Incident type display name, or "all types" for an unscoped silence
+fn scope_display(incident_type: Option<&str>, locale: &str) -> String {
+    match incident_type {
+        Some(key) => incident_types::display_name_localized(key, locale),
+        None => t!("embeds.config.silence.all_types", locale = locale).to_string(),
+    }
+}
@@ -0,0 +1,53 @@
+//! Report threshold override embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::CreateEmbed;
+
+use crate::commands::shared::embeds;
+
+/// Build embed confirming a threshold override change
+pub fn threshold_updated(threshold: Option<i64>, global: Option<i64>, locale: &str) -> CreateEmbed {
+    let description = match (threshold, global) {
+        (Some(value), _) => t!(
+            "embeds.config.threshold.updated.description_set",
+            value = value,
+            locale = locale
+        )
+        .to_string(),
+        (None, Some(global)) => t!(
+            "embeds.config.threshold.updated.description_reset",
+            global = global,
+            locale = locale
+        )
+        .to_string(),
+        (None, None) => t!(
+            "embeds.config.threshold.updated.description_reset_unknown",
+            locale = locale
+        )
+        .to_string(),
+    };
+
+    embeds::success_embed(
+        t!("embeds.config.threshold.updated.title", locale = locale),
+        description,
+    )
+}
+
+/// Threshold line for /config show: the override or the global default
+pub fn format_threshold(threshold: Option<i64>, global: Option<i64>, locale: &str) -> String {
+    match (threshold, global) {
+        (Some(value), _) => t!(
+            "embeds.config.threshold.show_override",
+            value = value,
+            locale = locale
+        )
+        .to_string(),
+        (None, Some(global)) => t!(
+            "embeds.config.threshold.show_global",
+            value = global,
+            locale = locale
+        )
+        .to_string(),
+        (None, None) => t!("embeds.config.threshold.show_unknown", locale = locale).to_string(),
+    }
+}
@@ -1,37 +1,142 @@
 //! Unregister flow embed builders for /config command
 
+use chrono::{DateTime, Utc};
 use rust_i18n::t;
 use serenity::all::{CreateEmbed, CreateEmbedFooter};
 
 use crate::commands::shared::embeds;
+use crate::entity::{guild_configs, user_configs};
+use crate::i18n::get_language_display_name;
+
+/// Registration being unregistered, with its live settings
+pub enum UnregisterTarget<'a> {
+    Guild {
+        name: &'a str,
+        config: &'a guild_configs::Model,
+    },
+    User {
+        config: &'a user_configs::Model,
+    },
+}
 
 /// Build confirmation embed for unregister action
-pub fn unregister_confirm(name: &str, is_guild: bool, locale: &str) -> CreateEmbed {
-    let description = if is_guild {
-        t!(
-            "embeds.config.unregister.confirm.description_guild",
-            locale = locale,
-            name = name
-        )
-        .to_string()
-    } else {
-        t!(
-            "embeds.config.unregister.confirm.description_user",
-            locale = locale
-        )
-        .to_string()
+///
+/// Lists what stops and which settings are kept for a later `/config setup`.
+pub fn unregister_confirm(target: &UnregisterTarget<'_>, locale: &str) -> CreateEmbed {
+    let (description, stops, kept) = match target {
+        UnregisterTarget::Guild { name, config } => {
+            let stops = match &config.channel_id {
+                Some(channel_id) => t!(
+                    "embeds.config.unregister.confirm.stops_guild",
+                    locale = locale,
+                    channel = format!("<#{}>", channel_id)
+                ),
+                None => t!(
+                    "embeds.config.unregister.confirm.stops_guild_no_channel",
+                    locale = locale
+                ),
+            };
+            let fallback_dm = if config.fallback_dm {
+                t!(
+                    "embeds.config.show.guild_active.field_fallback_dm_on",
+                    locale = locale
+                )
+            } else {
+                t!(
+                    "embeds.config.show.guild_active.field_fallback_dm_off",
+                    locale = locale
+                )
+            };
+            let kept = vec![
+                kept_language(config.language.as_deref(), locale),
+                t!(
+                    "embeds.config.unregister.confirm.kept_fallback_dm",
+                    locale = locale,
+                    state = fallback_dm
+                )
+                .to_string(),
+            ];
+            let description = t!(
+                "embeds.config.unregister.confirm.description_guild",
+                locale = locale,
+                name = name
+            );
+            (description, stops, kept)
+        }
+        UnregisterTarget::User { config } => (
+            t!(
+                "embeds.config.unregister.confirm.description_user",
+                locale = locale
+            ),
+            t!(
+                "embeds.config.unregister.confirm.stops_user",
+                locale = locale
+            ),
+            vec![kept_language(config.language.as_deref(), locale)],
+        ),
     };
 
+    let kept = kept
+        .iter()
+        .map(|line| format!("- {}", line))
+        .chain(std::iter::once(
+            t!(
+                "embeds.config.unregister.confirm.kept_note",
+                locale = locale
+            )
+            .to_string(),
+        ))
+        .collect::<Vec<_>>()
+        .join("\n");
+
     embeds::warning_embed(
         t!("embeds.config.unregister.confirm.title", locale = locale),
         description,
     )
+    .field(
+        t!(
+            "embeds.config.unregister.confirm.field_stops",
+            locale = locale
+        ),
+        format!("- {}", stops),
+        false,
+    )
+    .field(
+        t!(
+            "embeds.config.unregister.confirm.field_kept",
+            locale = locale
+        ),
+        kept,
+        false,
+    )
     .footer(CreateEmbedFooter::new(t!(
         "embeds.config.unregister.confirm.footer",
         locale = locale
     )))
 }
 
+fn kept_language(language: Option<&str>, locale: &str) -> String {
+    t!(
+        "embeds.config.unregister.confirm.kept_language",
+        locale = locale,
+        language = get_language_display_name(language, locale)
+    )
+    .to_string()
+}
+
+/// Build success embed after pausing alerts
+pub fn unregister_paused(until: DateTime<Utc>, locale: &str) -> CreateEmbed {
+    embeds::success_embed(
+        t!("embeds.config.unregister.paused.title", locale = locale),
+        t!(
+            "embeds.config.unregister.paused.description",
+            locale = locale,
+            until = format!("<t:{}:f>", until.timestamp()),
+            relative = format!("<t:{}:R>", until.timestamp())
+        ),
+    )
+}
+
 /// Build success embed after unregistering
 pub fn unregister_success(locale: &str) -> CreateEmbed {
     embeds::success_embed(
@@ -6,6 +6,7 @@ use serenity::all::{Colour, CreateEmbed, CreateEmbedFooter};
 use crate::commands::shared::colors;
 use crate::entity::user_configs;
 use crate::i18n::get_language_display_name;
+use crate::repository::config::DISABLED_REASON_DM_UNREACHABLE;
 
 /// Build embed for active user configuration
 pub fn show_user_active(config: &user_configs::Model, locale: &str) -> CreateEmbed {
@@ -61,17 +62,20 @@ pub fn show_user_active(config: &user_configs::Model, locale: &str) -> CreateEmb
 /// Build embed for disabled user configuration
 pub fn show_user_disabled(config: &user_configs::Model, locale: &str) -> CreateEmbed {
     let time = format!("<t:{}:R>", config.updated_at.timestamp());
+    // Disabled by the bot after a DM was refused, not by the user
+    let description_key = match config.disabled_reason.as_deref() {
+        Some(DISABLED_REASON_DM_UNREACHABLE) => {
+            "embeds.config.show.user_disabled.description_dm_unreachable"
+        }
+        _ => "embeds.config.show.user_disabled.description",
+    };
 
     CreateEmbed::default()
         .title(t!(
             "embeds.config.show.user_disabled.title",
             locale = locale
         ))
-        .description(t!(
-            "embeds.config.show.user_disabled.description",
-            locale = locale,
-            time = time
-        ))
+        .description(t!(description_key, locale = locale, time = time))
         .color(Colour::new(colors::WARNING))
         .field(
             t!(
@@ -0,0 +1,80 @@
+//! Alert webhook embed builders for /config command
+
+use rust_i18n::t;
+use serenity::all::CreateEmbed;
+
+use crate::commands::shared::{embeds, incident_types};
+use crate::entity::guild_webhooks;
+use crate::repository::webhooks;
+
+/// Build embed confirming a new webhook, with its signing secret
+///
+/// The secret is only ever shown here, in an ephemeral reply.
+pub fn webhook_added(webhook: &guild_webhooks::Model, locale: &str) -> CreateEmbed {
+    embeds::success_embed(
+        t!("embeds.config.webhook.added.title", locale = locale),
+        t!(
+            "embeds.config.webhook.added.description",
+            id = webhook.id,
+            locale = locale
+        ),
+    )
+    .field(
+        t!("embeds.config.webhook.added.field_url", locale = locale),
+        format!("`{}`", webhook.url),
+        false,
+    )
+    .field(
+        t!("embeds.config.webhook.added.field_types", locale = locale),
+        format_webhook_types(webhook, locale),
+        false,
+    )
+    .field(
+        t!("embeds.config.webhook.added.field_secret", locale = locale),
+        format!("||`{}`||", webhook.secret),
+        false,
+    )
+}
+
+/// Build embed confirming a webhook was removed
+pub fn webhook_removed(id: i64, locale: &str) -> CreateEmbed {
+    embeds::success_embed(
+        t!("embeds.config.webhook.removed.title", locale = locale),
+        t!(
+            "embeds.config.webhook.removed.description",
+            id = id,
+            locale = locale
+        ),
+    )
+}
+
+/// One line per webhook for /config show: ID, host, and incident types
+///
+/// Only the host is shown, since URLs often carry a token in the path.
+pub fn format_webhooks(rows: &[guild_webhooks::Model], locale: &str) -> String {
+    rows.iter()
+        .map(|row| {
+            let host = webhooks::webhook_host(row).unwrap_or_else(|| "?".to_string());
+            format!(
+                "`#{}` {}: {}",
+                row.id,
+                host,
+                format_webhook_types(row, locale)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Incident types a webhook receives, or "All types"
+fn format_webhook_types(row: &guild_webhooks::Model, locale: &str) -> String {
+    let types = webhooks::webhook_types(row);
+    if types.is_empty() {
+        return t!("embeds.config.webhook.all_types", locale = locale).to_string();
+    }
+    types
+        .into_iter()
+        .map(|key| incident_types::display_name_localized(key, locale))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,423 @@
+//! JSON export for /config data
+//!
+//! Contains the caller's own config row, alert settings rows, and aggregate
+//! record counts; report contents and other users' data are never included.
+//! Webhooks are exported by host only, without the URL path or secret.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{IdenStatic, Iterable};
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{
+    alert_silences, guild_alert_channels, guild_alert_filters, guild_configs, guild_webhooks,
+    user_configs, user_notification_filters,
+};
+use crate::repository::RecordStats;
+use crate::repository::webhooks;
+
+/// Full data export for a guild or user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataExport {
+    pub generated_at: DateTime<Utc>,
+    pub subject: ExportSubject,
+    /// Stored config row, if the subject ever registered
+    pub config: Option<ExportConfig>,
+    /// Alert settings rows beyond the config row
+    #[serde(default)]
+    pub settings: ExportSettings,
+    pub records: ExportRecords,
+}
+
+/// Whose data the export describes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportSubject {
+    Guild { id: String },
+    User { id: String },
+}
+
+/// Stored config row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExportConfig {
+    Guild(guild_configs::Model),
+    User(user_configs::Model),
+}
+
+/// Stored alert settings rows
+///
+/// Guild exports fill the guild tables and user exports fill
+/// `notification_filters`; the other lists stay empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSettings {
+    /// `alert_silences`, including the user IDs that created them
+    pub silences: Vec<alert_silences::Model>,
+    pub alert_channels: Vec<guild_alert_channels::Model>,
+    pub alert_filters: Vec<guild_alert_filters::Model>,
+    pub webhooks: Vec<ExportWebhook>,
+    pub notification_filters: Vec<user_notification_filters::Model>,
+}
+
+/// `guild_webhooks` row without the URL path or signing secret
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportWebhook {
+    pub id: i64,
+    pub host: Option<String>,
+    pub alert_types: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&guild_webhooks::Model> for ExportWebhook {
+    fn from(row: &guild_webhooks::Model) -> Self {
+        Self {
+            id: row.id,
+            host: webhooks::webhook_host(row),
+            alert_types: row.alert_types.clone(),
+            enabled: row.enabled,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Aggregate counts per table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportRecords {
+    pub user_reports: ExportStats,
+    pub sent_alerts: ExportStats,
+    pub command_logs: ExportStats,
+}
+
+/// Aggregate statistics for one table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportStats {
+    pub count: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+}
+
+impl From<RecordStats> for ExportStats {
+    fn from(stats: RecordStats) -> Self {
+        Self {
+            count: stats.count,
+            oldest: stats.oldest,
+            newest: stats.newest,
+        }
+    }
+}
+
+impl DataExport {
+    /// Serialize as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl ExportConfig {
+    /// Names of the columns stored in the config row
+    pub fn field_names(&self) -> Vec<String> {
+        match self {
+            Self::Guild(_) => guild_configs::Column::iter()
+                .map(|c| c.as_str().to_string())
+                .collect(),
+            Self::User(_) => user_configs::Column::iter()
+                .map(|c| c.as_str().to_string())
+                .collect(),
+        }
+    }
+}
+
+impl ExportRecords {
+    fn all(&self) -> [&ExportStats; 3] {
+        [&self.user_reports, &self.sent_alerts, &self.command_logs]
+    }
+
+    /// Oldest timestamp across all tables
+    pub fn oldest(&self) -> Option<DateTime<Utc>> {
+        self.all().iter().filter_map(|s| s.oldest).min()
+    }
+
+    /// Newest timestamp across all tables
+    pub fn newest(&self) -> Option<DateTime<Utc>> {
+        self.all().iter().filter_map(|s| s.newest).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    fn guild_config() -> guild_configs::Model {
+        guild_configs::Model {
+            guild_id: "1".into(),
+            channel_id: Some("2".into()),
+            enabled: true,
+            language: None,
+            fallback_dm: true,
+            paused_until: Some(at(12)),
+            muted_incident_types: None,
+            alert_threshold: Some(5),
+            report_role_id: None,
+            mention_role_id: Some("3".into()),
+            digest_enabled: false,
+            alert_quiet_start: None,
+            alert_quiet_end: None,
+            channel_invalid: false,
+            consecutive_send_failures: 0,
+            created_at: at(1),
+            updated_at: at(2),
+        }
+    }
+
+    fn user_config() -> user_configs::Model {
+        user_configs::Model {
+            user_id: "4".into(),
+            enabled: false,
+            language: Some("ko".into()),
+            disabled_reason: None,
+            created_at: at(1),
+            updated_at: at(2),
+        }
+    }
+
+    fn webhook() -> guild_webhooks::Model {
+        guild_webhooks::Model {
+            id: 7,
+            guild_id: "1".into(),
+            url: "https://hooks.example.com/token/abc123".into(),
+            secret: "s3cret".into(),
+            alert_types: "login".into(),
+            enabled: true,
+            created_at: at(1),
+            updated_at: at(2),
+        }
+    }
+
+    fn guild_settings() -> ExportSettings {
+        ExportSettings {
+            silences: vec![alert_silences::Model {
+                id: 1,
+                guild_id: "1".into(),
+                incident_type: None,
+                until: at(20),
+                created_by: "5".into(),
+                created_at: at(4),
+            }],
+            alert_channels: vec![guild_alert_channels::Model {
+                id: 2,
+                guild_id: "1".into(),
+                channel_id: "6".into(),
+                incident_types: "login".into(),
+                enabled: true,
+                created_at: at(1),
+                updated_at: at(2),
+            }],
+            alert_filters: vec![guild_alert_filters::Model {
+                guild_id: "1".into(),
+                alert_type: "component".into(),
+                enabled: false,
+                updated_at: at(3),
+            }],
+            webhooks: vec![(&webhook()).into()],
+            notification_filters: Vec::new(),
+        }
+    }
+
+    fn user_settings() -> ExportSettings {
+        ExportSettings {
+            notification_filters: vec![user_notification_filters::Model {
+                user_id: "4".into(),
+                alert_type: "maintenance".into(),
+                enabled: false,
+                updated_at: at(3),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn records() -> ExportRecords {
+        ExportRecords {
+            user_reports: ExportStats {
+                count: 3,
+                oldest: Some(at(3)),
+                newest: Some(at(9)),
+            },
+            sent_alerts: ExportStats {
+                count: 1,
+                oldest: Some(at(5)),
+                newest: Some(at(5)),
+            },
+            command_logs: ExportStats::default(),
+        }
+    }
+
+    fn round_trip(export: &DataExport) -> DataExport {
+        serde_json::from_str(&export.to_json().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_guild_export_round_trip() {
+        let export = DataExport {
+            generated_at: at(10),
+            subject: ExportSubject::Guild { id: "1".into() },
+            config: Some(ExportConfig::Guild(guild_config())),
+            settings: guild_settings(),
+            records: records(),
+        };
+
+        assert_eq!(round_trip(&export), export);
+    }
+
+    #[test]
+    fn test_user_export_round_trip() {
+        let export = DataExport {
+            generated_at: at(10),
+            subject: ExportSubject::User { id: "4".into() },
+            config: Some(ExportConfig::User(user_config())),
+            settings: user_settings(),
+            records: records(),
+        };
+
+        let parsed = round_trip(&export);
+
+        // Untagged, so the user row must not be read back as a guild row
+        assert!(matches!(parsed.config, Some(ExportConfig::User(_))));
+        assert_eq!(parsed, export);
+    }
+
+    #[test]
+    fn test_unregistered_export_round_trip() {
+        let export = DataExport {
+            generated_at: at(10),
+            subject: ExportSubject::Guild { id: "1".into() },
+            config: None,
+            settings: ExportSettings::default(),
+            records: ExportRecords::default(),
+        };
+
+        assert_eq!(round_trip(&export), export);
+    }
+
+    #[test]
+    fn test_export_json_shape() {
+        let export = DataExport {
+            generated_at: at(10),
+            subject: ExportSubject::User { id: "4".into() },
+            config: None,
+            settings: ExportSettings::default(),
+            records: ExportRecords::default(),
+        };
+
+        let value = serde_json::to_value(&export).unwrap();
+
+        assert_eq!(value["subject"], json!({ "type": "user", "id": "4" }));
+        assert_eq!(value["config"], json!(null));
+        assert_eq!(
+            value["records"]["command_logs"],
+            json!({ "count": 0, "oldest": null, "newest": null })
+        );
+        assert_eq!(value["generated_at"], json!("2026-03-01T10:00:00Z"));
+    }
+
+    #[test]
+    fn test_absent_optional_fields_read_as_none() {
+        let value = json!({
+            "generated_at": "2026-03-01T10:00:00Z",
+            "subject": { "type": "guild", "id": "1" },
+            "records": {
+                "user_reports": { "count": 2, "oldest": "2026-03-01T03:00:00Z" },
+                "sent_alerts": { "count": 0 },
+                "command_logs": { "count": 0, "newest": null }
+            }
+        });
+
+        let export: DataExport = serde_json::from_value(value).unwrap();
+
+        assert_eq!(export.config, None);
+        assert_eq!(export.settings, ExportSettings::default());
+        assert_eq!(export.records.user_reports.oldest, Some(at(3)));
+        assert_eq!(export.records.user_reports.newest, None);
+        assert_eq!(export.records.sent_alerts, ExportStats::default());
+        assert_eq!(export.records.command_logs, ExportStats::default());
+    }
+
+    #[test]
+    fn test_guild_export_json_settings_sections() {
+        let export = DataExport {
+            generated_at: at(10),
+            subject: ExportSubject::Guild { id: "1".into() },
+            config: None,
+            settings: guild_settings(),
+            records: ExportRecords::default(),
+        };
+
+        let value = serde_json::to_value(&export).unwrap();
+        let settings = &value["settings"];
+
+        assert_eq!(settings["silences"][0]["created_by"], json!("5"));
+        assert_eq!(settings["alert_channels"][0]["channel_id"], json!("6"));
+        assert_eq!(
+            settings["alert_filters"][0]["alert_type"],
+            json!("component")
+        );
+        assert_eq!(settings["notification_filters"], json!([]));
+        assert_eq!(
+            settings["webhooks"],
+            json!([{
+                "id": 7,
+                "host": "hooks.example.com",
+                "alert_types": "login",
+                "enabled": true,
+                "created_at": "2026-03-01T01:00:00Z",
+                "updated_at": "2026-03-01T02:00:00Z"
+            }])
+        );
+        // Neither the URL path nor the signing secret leaves the database
+        let json = export.to_json().unwrap();
+        assert!(!json.contains("abc123"));
+        assert!(!json.contains("s3cret"));
+    }
+
+    #[test]
+    fn test_user_export_json_settings_sections() {
+        let export = DataExport {
+            generated_at: at(10),
+            subject: ExportSubject::User { id: "4".into() },
+            config: None,
+            settings: user_settings(),
+            records: ExportRecords::default(),
+        };
+
+        let value = serde_json::to_value(&export).unwrap();
+        let settings = &value["settings"];
+
+        assert_eq!(
+            settings["notification_filters"],
+            json!([{
+                "user_id": "4",
+                "alert_type": "maintenance",
+                "enabled": false,
+                "updated_at": "2026-03-01T03:00:00Z"
+            }])
+        );
+        assert_eq!(settings["silences"], json!([]));
+        assert_eq!(settings["webhooks"], json!([]));
+    }
+
+    #[test]
+    fn test_records_oldest_and_newest_across_tables() {
+        assert_eq!(records().oldest(), Some(at(3)));
+        assert_eq!(records().newest(), Some(at(9)));
+        assert_eq!(ExportRecords::default().oldest(), None);
+        assert_eq!(ExportRecords::default().newest(), None);
+    }
+}
@@ -0,0 +1,195 @@
+//! Alert subscription handlers for /config alerts
+//!
+//! `types` picks incident types for threshold alerts; `enable`/`disable`
+//! turn whole alert categories on or off.
+
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context};
+
+use crate::alerts::FILTERABLE_ALERT_TYPES;
+use crate::commands::shared::{edit_embed, edit_error, incident_types};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::{AlertFilterRepository, GuildConfigRepository, config};
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+
+/// Handle /config alerts types
+///
+/// Without a type, shows the current subscriptions. With a type, sets it to
+/// `mode` ("on"/"off"), or flips it when no mode is given.
+pub async fn handle_alert_types(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    incident_type: Option<String>,
+    mode: Option<String>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Subscriptions apply to guild alert channels only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.alerts.error_user_context", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    let repo = GuildConfigRepository::new(db.clone());
+
+    // Check if registered
+    let Some(existing) = repo.get(guild_id).await else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+    let muted = config::muted_incident_types(&existing);
+
+    // If no type specified, show current subscriptions
+    let Some(incident_type) = incident_type else {
+        if mode.is_some() {
+            return edit_error(
+                ctx,
+                interaction,
+                &t!("errors.missing_option", option = "type", locale = &locale),
+                &locale,
+            )
+            .await;
+        }
+        let embed = embeds::alert_types_current(&muted, &locale);
+        return edit_embed(ctx, interaction, embed).await;
+    };
+
+    let enabled = match mode.as_deref() {
+        Some(mode) => mode == "on",
+        None => muted.contains(&incident_type.as_str()),
+    };
+    let updated = apply_subscription(&muted, &incident_type, enabled);
+
+    match repo.update_muted_incident_types(guild_id, &updated).await {
+        Ok(_) => {
+            info!(guild_id = %guild_id, incident_type, enabled, "Updated guild alert types");
+            let embed = embeds::alert_types_updated(&incident_type, enabled, &updated, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update guild alert types");
+            edit_error(
+                ctx,
+                interaction,
+                &t!("embeds.config.alerts.error_update_failed", locale = &locale),
+                &locale,
+            )
+            .await
+        }
+    }
+}
+
+/// Handle /config alerts enable|disable <alert_type>
+pub async fn handle_alert_filter(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    alert_type: String,
+    enabled: bool,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Alert categories apply to guild alert channels only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.alerts.error_user_context", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    // Check if registered
+    if GuildConfigRepository::new(db.clone())
+        .get(guild_id)
+        .await
+        .is_none()
+    {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    if !FILTERABLE_ALERT_TYPES.contains(&alert_type.as_str()) {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.alerts.error_unknown_category",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    let repo = AlertFilterRepository::new(db);
+    let result = match repo.set_enabled(guild_id, &alert_type, enabled).await {
+        Ok(()) => repo.disabled_types(guild_id).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(disabled) => {
+            info!(guild_id = %guild_id, alert_type, enabled, "Updated guild alert filter");
+            let embed = embeds::alert_filter_updated(&alert_type, enabled, &disabled, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update guild alert filter");
+            edit_error(
+                ctx,
+                interaction,
+                &t!("embeds.config.alerts.error_update_failed", locale = &locale),
+                &locale,
+            )
+            .await
+        }
+    }
+}
+
+/// Muted types after turning `incident_type` on or off, in canonical order
+///
+/// Stored types that are no longer incident types are dropped.
+fn apply_subscription(muted: &[&str], incident_type: &str, enabled: bool) -> Vec<&'static str> {
+    incident_types::INCIDENT_TYPE_KEYS
+        .iter()
+        .copied()
+        .filter(|key| {
+            if *key == incident_type {
+                !enabled
+            } else {
+                muted.contains(key)
+            }
+        })
+        .collect()
+}
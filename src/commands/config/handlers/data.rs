@@ -0,0 +1,213 @@
+//! Data summary handler for /config command
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::DatabaseConnection;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context, CreateAttachment, EditInteractionResponse};
+
+use crate::commands::shared::edit_error;
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::{
+    AlertChannelRepository, AlertFilterRepository, GuildConfigRepository, RecordOwner,
+    RecordRepository, SilenceRepository, UserConfigRepository, WebhookRepository,
+};
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+use super::super::export::{
+    DataExport, ExportConfig, ExportRecords, ExportSettings, ExportSubject,
+};
+
+/// Handle /config data
+///
+/// Responds ephemerally with a summary embed and a JSON export attachment.
+pub async fn handle_data(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    let (owner, subject, config) = match config_context {
+        ConfigContext::Guild(guild_id) => (
+            RecordOwner::Guild(guild_id),
+            ExportSubject::Guild {
+                id: guild_id.to_string(),
+            },
+            GuildConfigRepository::new(db.clone())
+                .get(guild_id)
+                .await
+                .map(ExportConfig::Guild),
+        ),
+        ConfigContext::User(user_id) => (
+            RecordOwner::User(user_id),
+            ExportSubject::User {
+                id: user_id.to_string(),
+            },
+            UserConfigRepository::new(db.clone())
+                .get(user_id)
+                .await
+                .map(ExportConfig::User),
+        ),
+    };
+
+    let (settings, records) = match load_stored(&db, owner).await {
+        Ok(stored) => stored,
+        Err(e) => {
+            error!(error = %e, "Failed to load data summary");
+            return edit_error(
+                ctx,
+                interaction,
+                &t!("embeds.config.data.error_failed", locale = &locale),
+                &locale,
+            )
+            .await;
+        }
+    };
+
+    let export = DataExport {
+        generated_at: Utc::now(),
+        subject,
+        config,
+        settings,
+        records,
+    };
+
+    let json = match export.to_json() {
+        Ok(json) => json,
+        Err(e) => {
+            error!(error = %e, "Failed to serialize data export");
+            return edit_error(
+                ctx,
+                interaction,
+                &t!("embeds.config.data.error_failed", locale = &locale),
+                &locale,
+            )
+            .await;
+        }
+    };
+
+    let filename = match &export.subject {
+        ExportSubject::Guild { id } => format!("vrcpulse-guild-{}.json", id),
+        ExportSubject::User { id } => format!("vrcpulse-user-{}.json", id),
+    };
+
+    info!(owner = ?owner, "Generated data summary");
+
+    let response = EditInteractionResponse::new()
+        .embed(embeds::data_summary(&export, &locale))
+        .new_attachment(CreateAttachment::bytes(json.into_bytes(), filename));
+    interaction.edit_response(&ctx.http, response).await?;
+    Ok(())
+}
+
+/// Settings rows and record counts owned by `owner`
+async fn load_stored(
+    db: &Arc<DatabaseConnection>,
+    owner: RecordOwner,
+) -> Result<(ExportSettings, ExportRecords), sea_orm::DbErr> {
+    let settings = load_settings(db, owner).await?;
+    let records = load_records(&RecordRepository::new(db.clone()), owner).await?;
+    Ok((settings, records))
+}
+
+async fn load_records(
+    repo: &RecordRepository,
+    owner: RecordOwner,
+) -> Result<ExportRecords, sea_orm::DbErr> {
+    Ok(ExportRecords {
+        user_reports: repo.report_stats(owner).await?.into(),
+        sent_alerts: repo.sent_alert_stats(owner).await?.into(),
+        command_logs: repo.command_log_stats(owner).await?.into(),
+    })
+}
+
+async fn load_settings(
+    db: &Arc<DatabaseConnection>,
+    owner: RecordOwner,
+) -> Result<ExportSettings, sea_orm::DbErr> {
+    match owner {
+        RecordOwner::Guild(guild_id) => Ok(ExportSettings {
+            silences: SilenceRepository::new(db.clone()).list(guild_id).await?,
+            alert_channels: AlertChannelRepository::new(db.clone())
+                .list(guild_id)
+                .await?,
+            alert_filters: AlertFilterRepository::new(db.clone())
+                .list(guild_id)
+                .await?,
+            webhooks: WebhookRepository::new(db.clone())
+                .list(guild_id)
+                .await?
+                .iter()
+                .map(Into::into)
+                .collect(),
+            ..Default::default()
+        }),
+        RecordOwner::User(user_id) => Ok(ExportSettings {
+            notification_filters: AlertFilterRepository::new(db.clone())
+                .list_user(user_id)
+                .await?,
+            ..Default::default()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::Duration;
+    use serenity::all::{ChannelId, GuildId, UserId};
+
+    const GUILD: GuildId = GuildId::new(1);
+    const OTHER_GUILD: GuildId = GuildId::new(2);
+    const USER: UserId = UserId::new(3);
+
+    #[tokio::test]
+    async fn test_settings_cover_only_the_owner() {
+        let db = Arc::new(connect_in_memory().await);
+        for guild_id in [GUILD, OTHER_GUILD] {
+            SilenceRepository::new(db.clone())
+                .create(guild_id, None, Utc::now() + Duration::hours(1), USER)
+                .await
+                .unwrap();
+            AlertChannelRepository::new(db.clone())
+                .add_type(guild_id, ChannelId::new(4), "login")
+                .await
+                .unwrap();
+            AlertFilterRepository::new(db.clone())
+                .set_enabled(guild_id, "component", false)
+                .await
+                .unwrap();
+            WebhookRepository::new(db.clone())
+                .add(guild_id, "https://hooks.example.com/token", "secret", "")
+                .await
+                .unwrap();
+        }
+        AlertFilterRepository::new(db.clone())
+            .set_user_enabled(USER, "maintenance", false)
+            .await
+            .unwrap();
+
+        let guild = load_settings(&db, RecordOwner::Guild(GUILD)).await.unwrap();
+        assert_eq!(guild.silences.len(), 1);
+        assert_eq!(guild.silences[0].created_by, USER.to_string());
+        assert_eq!(guild.alert_channels.len(), 1);
+        assert_eq!(guild.alert_filters.len(), 1);
+        assert_eq!(guild.webhooks.len(), 1);
+        assert_eq!(guild.webhooks[0].host.as_deref(), Some("hooks.example.com"));
+        assert!(guild.notification_filters.is_empty());
+
+        let user = load_settings(&db, RecordOwner::User(USER)).await.unwrap();
+        assert_eq!(user.notification_filters.len(), 1);
+        assert_eq!(user.notification_filters[0].alert_type, "maintenance");
+        assert!(user.silences.is_empty());
+        assert!(user.webhooks.is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+//! Weekly digest handler for /config command
+
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context};
+
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::GuildConfigRepository;
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+
+/// Handle /config digest
+pub async fn handle_digest(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    mode: Option<String>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Digests are posted to guild alert channels only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.digest.error_user_context", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    let repo = GuildConfigRepository::new(db.clone());
+
+    // Check if registered
+    let Some(existing) = repo.get(guild_id).await else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    // If no mode specified, show current setting
+    let Some(mode) = mode else {
+        let embed = embeds::digest_current(existing.digest_enabled, &locale);
+        return edit_embed(ctx, interaction, embed).await;
+    };
+
+    let enabled = mode == "on";
+    match repo.update_digest_enabled(guild_id, enabled).await {
+        Ok(_) => {
+            info!(guild_id = %guild_id, enabled, "Updated guild weekly digest");
+            let embed = embeds::digest_updated(enabled, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update guild weekly digest");
+            edit_error(
+                ctx,
+                interaction,
+                &t!("embeds.config.digest.error_update_failed", locale = &locale),
+                &locale,
+            )
+            .await
+        }
+    }
+}
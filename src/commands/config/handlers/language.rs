@@ -5,10 +5,10 @@ use tracing::{error, info};
 
 use serenity::all::{CommandInteraction, Context};
 
-use crate::commands::shared::{defer, edit_embed, edit_error};
+use crate::commands::shared::{edit_embed, edit_error};
 use crate::database;
 use crate::i18n::resolve_locale_async;
-use crate::repository::{GuildConfigRepository, UserConfigRepository};
+use crate::repository::{GuildConfigRepository, LanguageError, UserConfigRepository};
 
 use super::super::context::ConfigContext;
 use super::super::embeds;
@@ -20,9 +20,6 @@ pub async fn handle_language(
     config_context: ConfigContext,
     language_code: Option<String>,
 ) -> Result<(), serenity::Error> {
-    // Defer response since we do database operations
-    defer(ctx, interaction).await?;
-
     let db = database::get_db(ctx).await;
     let locale = resolve_locale_async(ctx, interaction).await;
 
@@ -58,14 +55,28 @@ pub async fn handle_language(
                 return edit_embed(ctx, interaction, embed).await;
             }
 
-            // Update language - use the NEW language for the response
-            let response_locale = language.as_deref().unwrap_or(&locale);
-            match repo.update_language(guild_id, language.clone()).await {
-                Ok(_) => {
+            // Update language - use the NEW (normalized) language for the response
+            match repo.update_language(guild_id, language).await {
+                Ok(updated) => {
+                    let language = updated.language;
                     info!(guild_id = %guild_id, language = ?language, "Updated guild language");
+                    let response_locale = language.as_deref().unwrap_or(&locale);
                     let embed = embeds::language_updated(language.as_deref(), response_locale);
                     edit_embed(ctx, interaction, embed).await
                 }
+                Err(LanguageError::Unsupported(code)) => {
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!(
+                            "embeds.config.setup.error_language_unsupported",
+                            locale = &locale,
+                            code = code
+                        ),
+                        &locale,
+                    )
+                    .await
+                }
                 Err(e) => {
                     error!(error = %e, "Failed to update guild language");
                     edit_error(
@@ -106,14 +117,28 @@ pub async fn handle_language(
                 return edit_embed(ctx, interaction, embed).await;
             }
 
-            // Update language - use the NEW language for the response
-            let response_locale = language.as_deref().unwrap_or(&locale);
-            match repo.update_language(user_id, language.clone()).await {
-                Ok(_) => {
+            // Update language - use the NEW (normalized) language for the response
+            match repo.update_language(user_id, language).await {
+                Ok(updated) => {
+                    let language = updated.language;
                     info!(user_id = %user_id, language = ?language, "Updated user language");
+                    let response_locale = language.as_deref().unwrap_or(&locale);
                     let embed = embeds::language_updated(language.as_deref(), response_locale);
                     edit_embed(ctx, interaction, embed).await
                 }
+                Err(LanguageError::Unsupported(code)) => {
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!(
+                            "embeds.config.setup.error_language_unsupported",
+                            locale = &locale,
+                            code = code
+                        ),
+                        &locale,
+                    )
+                    .await
+                }
                 Err(e) => {
                     error!(error = %e, "Failed to update user language");
                     edit_error(
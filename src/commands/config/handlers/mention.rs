@@ -0,0 +1,84 @@
+//! Alert role mention handler for /config command
+
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context, Role};
+
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::GuildConfigRepository;
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+use super::super::validation::validate_mention_role;
+
+/// Handle /config mention [role]
+///
+/// With a role, threshold alerts ping it; without one, the mention is cleared.
+pub async fn handle_mention(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    role: Option<&Role>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Role mentions apply to guild alert channels only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.mention.error_user_context", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    let repo = GuildConfigRepository::new(db.clone());
+
+    // Check if registered
+    if repo.get(guild_id).await.is_none() {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    // Validate the role can be pinged
+    if let Some(role) = role
+        && let Err(e) = validate_mention_role(ctx, guild_id, role).await
+    {
+        return edit_error(ctx, interaction, &t!(e.key(), locale = &locale), &locale).await;
+    }
+
+    let role_id = role.map(|role| role.id);
+    match repo.update_mention_role(guild_id, role_id).await {
+        Ok(_) => {
+            info!(guild_id = %guild_id, role_id = ?role_id, "Updated guild alert mention role");
+            let embed = embeds::mention_updated(role_id, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update guild alert mention role");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.mention.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
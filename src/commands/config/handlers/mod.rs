@@ -1,14 +1,38 @@
 //! Handler functions for /config subcommands
 
+mod alerts;
+mod data;
+mod digest;
+mod fallback_dm;
 mod language;
+mod mention;
+mod notifications;
+mod quiet_hours;
+mod report_role;
 mod setup;
 mod show;
+mod silence;
+mod threshold;
 mod unregister;
+mod webhook;
 
+pub use alerts::{handle_alert_filter, handle_alert_types};
+pub use data::handle_data;
+pub use digest::handle_digest;
+pub use fallback_dm::handle_fallback_dm;
 pub use language::handle_language;
+pub use mention::handle_mention;
+pub use notifications::handle_notifications;
+pub use quiet_hours::handle_quiet_hours;
+pub use report_role::handle_report_role;
 pub use setup::handle_setup;
 pub use show::handle_show;
-pub use unregister::{handle_unregister, handle_unregister_cancel, handle_unregister_confirm};
+pub use silence::handle_silence;
+pub use threshold::{MAX_GUILD_THRESHOLD, handle_threshold};
+pub use unregister::{
+    handle_unregister, handle_unregister_cancel, handle_unregister_confirm, handle_unregister_pause,
+};
+pub use webhook::{MAX_WEBHOOK_URL_LENGTH, handle_webhook_add, handle_webhook_remove};
 
 use crate::commands::shared::is_button;
 
@@ -25,6 +49,9 @@ const ACTION_UNREGISTER_CONFIRM: &str = "unregister_confirm";
 /// Action name for unregister cancel button
 const ACTION_UNREGISTER_CANCEL: &str = "unregister_cancel";
 
+/// Action name for the pause button offered instead of unregistering
+const ACTION_UNREGISTER_PAUSE: &str = "unregister_pause";
+
 /// Generate button ID for unregister confirmation
 pub fn unregister_confirm_button_id(context_type: &str, id: impl ToString) -> String {
     crate::commands::shared::button_id_with_context(
@@ -45,6 +72,16 @@ pub fn unregister_cancel_button_id(context_type: &str, id: impl ToString) -> Str
     )
 }
 
+/// Generate button ID for pausing instead of unregistering
+pub fn unregister_pause_button_id(context_type: &str, id: impl ToString) -> String {
+    crate::commands::shared::button_id_with_context(
+        MODULE,
+        ACTION_UNREGISTER_PAUSE,
+        context_type,
+        id,
+    )
+}
+
 /// Check if button ID matches unregister confirmation
 pub fn is_confirm_button(custom_id: &str) -> bool {
     is_button(custom_id, MODULE, ACTION_UNREGISTER_CONFIRM)
@@ -54,3 +91,8 @@ pub fn is_confirm_button(custom_id: &str) -> bool {
 pub fn is_cancel_button(custom_id: &str) -> bool {
     is_button(custom_id, MODULE, ACTION_UNREGISTER_CANCEL)
 }
+
+/// Check if button ID matches unregister pause
+pub fn is_pause_button(custom_id: &str) -> bool {
+    is_button(custom_id, MODULE, ACTION_UNREGISTER_PAUSE)
+}
@@ -0,0 +1,127 @@
+//! DM notification handler for /config notifications
+//!
+//! The user-install counterpart of `/config alerts enable|disable`: turns
+//! whole alert categories on or off for a user's DMs.
+
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context};
+
+use crate::alerts::FILTERABLE_ALERT_TYPES;
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::{AlertFilterRepository, UserConfigRepository};
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+
+/// Handle /config notifications show|set
+///
+/// Without a change, shows the current categories. With one, sets the
+/// category to the given state.
+pub async fn handle_notifications(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    change: Option<(String, bool)>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Servers choose categories with /config alerts
+    let ConfigContext::User(user_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.notifications.error_guild_context",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    // Check if registered
+    if UserConfigRepository::new(db.clone())
+        .get(user_id)
+        .await
+        .is_none()
+    {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_user",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    let repo = AlertFilterRepository::new(db);
+
+    let Some((alert_type, enabled)) = change else {
+        return match repo.user_disabled_types(user_id).await {
+            Ok(disabled) => {
+                let embed = embeds::notifications_current(&disabled, &locale);
+                edit_embed(ctx, interaction, embed).await
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to load user notification filters");
+                edit_error(
+                    ctx,
+                    interaction,
+                    &t!(
+                        "embeds.config.notifications.error_load_failed",
+                        locale = &locale
+                    ),
+                    &locale,
+                )
+                .await
+            }
+        };
+    };
+
+    if !FILTERABLE_ALERT_TYPES.contains(&alert_type.as_str()) {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.alerts.error_unknown_category",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    let result = match repo.set_user_enabled(user_id, &alert_type, enabled).await {
+        Ok(()) => repo.user_disabled_types(user_id).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(disabled) => {
+            info!(user_id = %user_id, alert_type, enabled, "Updated user notification filter");
+            let embed = embeds::notifications_updated(&alert_type, enabled, &disabled, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update user notification filter");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.notifications.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Quiet hours handler for /config command
+
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context};
+
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::GuildConfigRepository;
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+
+/// Handle /config quiet-hours set|clear
+///
+/// With a `(start, end)` window, threshold alerts raised between those UTC
+/// hours are recorded but not posted; without one, the window is cleared.
+pub async fn handle_quiet_hours(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    window: Option<(u32, u32)>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Quiet hours apply to guild alert channels only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.quiet_hours.error_user_context",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    // An empty window would never hold anything back
+    if let Some((start, end)) = window
+        && start == end
+    {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.quiet_hours.error_same_hour",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    let repo = GuildConfigRepository::new(db.clone());
+
+    // Check if registered
+    if repo.get(guild_id).await.is_none() {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    match repo.update_quiet_hours(guild_id, window).await {
+        Ok(_) => {
+            info!(guild_id = %guild_id, window = ?window, "Updated guild quiet hours");
+            let embed = embeds::quiet_hours_updated(window, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update guild quiet hours");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.quiet_hours.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Report role restriction handler for /config command
+
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context, Role};
+
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::GuildConfigRepository;
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+
+/// Handle /config reportrole [role]
+///
+/// With a role, only members with it may submit /report in the guild;
+/// without one, the restriction is cleared.
+pub async fn handle_report_role(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    role: Option<&Role>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Report roles apply to guild reports only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.report_role.error_user_context",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    let repo = GuildConfigRepository::new(db.clone());
+
+    // Check if registered
+    if repo.get(guild_id).await.is_none() {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    // @everyone shares the guild's ID and would restrict nothing
+    if role.is_some_and(|role| role.id.get() == guild_id.get()) {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.report_role.error_everyone", locale = &locale),
+            &locale,
+        )
+        .await;
+    }
+
+    let role_id = role.map(|role| role.id);
+    match repo.update_report_role(guild_id, role_id).await {
+        Ok(_) => {
+            info!(guild_id = %guild_id, role_id = ?role_id, "Updated guild report role");
+            let embed = embeds::report_role_updated(role_id, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update guild report role");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.report_role.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
@@ -1,26 +1,35 @@
 //! Setup handler for /config command
 
+use chrono::Utc;
 use rust_i18n::t;
 use serenity::all::{ChannelId, CommandInteraction, Context};
 use tracing::{error, info};
 
-use crate::commands::shared::{defer, edit_error, edit_info, edit_success};
+use crate::alerts;
+use crate::commands::shared::{edit_error, edit_info, edit_success, incident_types};
 use crate::database;
 use crate::i18n::resolve_locale_async;
-use crate::repository::{GuildConfigRepository, UserConfigRepository};
+use crate::repository::{
+    AlertChannelRepository, GuildConfigRepository, UserConfigRepository, config, registration,
+};
 
 use super::super::context::ConfigContext;
-use super::super::validation::validate_channel_permissions;
+use super::super::validation::{ChannelAdvice, validate_channel_permissions};
 
 /// Handle /config setup
+///
+/// With `incident_type`, routes that type to the channel instead of changing
+/// the main alert channel.
 pub async fn handle_setup(
     ctx: &Context,
     interaction: &CommandInteraction,
     config_context: ConfigContext,
     channel_id: Option<ChannelId>,
+    incident_type: Option<String>,
 ) -> Result<(), serenity::Error> {
-    // Defer response since we do database operations
-    defer(ctx, interaction).await?;
+    if let Some(incident_type) = incident_type {
+        return handle_route(ctx, interaction, config_context, channel_id, &incident_type).await;
+    }
 
     let db = database::get_db(ctx).await;
     let locale = resolve_locale_async(ctx, interaction).await;
@@ -42,20 +51,53 @@ pub async fn handle_setup(
             };
 
             // Validate channel permissions
-            if let Err(msg) = validate_channel_permissions(ctx, channel_id).await {
-                return edit_error(ctx, interaction, &msg, &locale).await;
-            }
+            let advice = match validate_channel_permissions(ctx, channel_id).await {
+                Ok(advice) => advice,
+                Err(e) => {
+                    return edit_error(ctx, interaction, &t!(e.key(), locale = &locale), &locale)
+                        .await;
+                }
+            };
 
-            let repo = GuildConfigRepository::new(db);
+            let repo = GuildConfigRepository::new(db.clone());
 
             // Check if already registered and enabled
             let existing = repo.get(guild_id).await;
             if let Some(ref config) = existing
                 && config.enabled
             {
+                // Running setup again ends a pause early
+                let paused = config::is_paused(config, Utc::now());
+                if paused {
+                    if let Err(e) = repo.resume(guild_id).await {
+                        error!(error = %e, "Failed to resume paused guild");
+                        return edit_error(
+                            ctx,
+                            interaction,
+                            &t!("embeds.config.setup.error_update_failed", locale = &locale),
+                            &locale,
+                        )
+                        .await;
+                    }
+                    info!(guild_id = %guild_id, "Resumed paused guild alerts via setup");
+                }
+
                 // Already registered - update channel if different
                 if config.channel_id.as_ref() == Some(&channel_id.to_string()) {
                     let channel = format!("<#{}>", channel_id);
+                    if paused {
+                        return edit_success(
+                            ctx,
+                            interaction,
+                            &t!("embeds.config.setup.resumed.title", locale = &locale),
+                            &t!(
+                                "embeds.config.setup.resumed.description",
+                                locale = &locale,
+                                channel = channel
+                            ),
+                        )
+                        .await;
+                    }
                     return edit_info(
                         ctx,
                         interaction,
@@ -83,6 +125,11 @@ pub async fn handle_setup(
                         .await;
                     }
                     let channel = format!("<#{}>", channel_id);
+                    let description = t!(
+                        "embeds.config.setup.channel_updated.description",
+                        locale = &locale,
+                        channel = channel
+                    );
                     return edit_success(
                         ctx,
                         interaction,
@@ -90,11 +137,7 @@ pub async fn handle_setup(
                             "embeds.config.setup.channel_updated.title",
                             locale = &locale
                         ),
-                        &t!(
-                            "embeds.config.setup.channel_updated.description",
-                            locale = &locale,
-                            channel = channel
-                        ),
+                        &with_channel_advice(description.to_string(), advice, &locale),
                     )
                     .await;
                 }
@@ -111,17 +154,22 @@ pub async fn handle_setup(
                 Ok(_) => {
                     info!(guild_id = %guild_id, channel_id = %channel_id, "Guild registered for alerts");
                     let channel = format!("<#{}>", channel_id);
-                    edit_success(
+                    let description = t!(
+                        "embeds.config.setup.success.description_guild",
+                        locale = &locale,
+                        channel = channel
+                    );
+                    let reply = edit_success(
                         ctx,
                         interaction,
                         &t!("embeds.config.setup.success.title", locale = &locale),
-                        &t!(
-                            "embeds.config.setup.success.description_guild",
-                            locale = &locale,
-                            channel = channel
-                        ),
+                        &with_channel_advice(description.to_string(), advice, &locale),
                     )
-                    .await
+                    .await;
+
+                    // Registered mid-incident: send the ongoing alert after the confirmation
+                    alerts::threshold::replay_to_new_guild(ctx, &db, guild_id).await;
+                    reply
                 }
                 Err(e) => {
                     error!(error = %e, "Failed to create guild config");
@@ -199,3 +247,131 @@ pub async fn handle_setup(
         }
     }
 }
+
+/// Handle /config setup with `for`: route one incident type to a channel
+///
+/// Choosing the main alert channel removes the type from any extra channel,
+/// so it falls back to the main channel again.
+async fn handle_route(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    channel_id: Option<ChannelId>,
+    incident_type: &str,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.setup.error_route_user", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    let Some(channel_id) = channel_id else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_channel_required",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    let advice = match validate_channel_permissions(ctx, channel_id).await {
+        Ok(advice) => advice,
+        Err(e) => {
+            return edit_error(ctx, interaction, &t!(e.key(), locale = &locale), &locale).await;
+        }
+    };
+
+    // Extra channels only apply to a registered guild
+    let Some(guild_config) = GuildConfigRepository::new(db.clone())
+        .get(guild_id)
+        .await
+        .filter(|c| registration::guild(Some(c), Utc::now()).is_registered())
+    else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_route_not_registered",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    let repo = AlertChannelRepository::new(db);
+    let is_main = guild_config.channel_id.as_deref() == Some(channel_id.to_string().as_str());
+    let result = if is_main {
+        repo.remove_type(guild_id, incident_type).await.map(|_| ())
+    } else {
+        repo.add_type(guild_id, channel_id, incident_type)
+            .await
+            .map(|_| ())
+    };
+
+    if let Err(e) = result {
+        error!(error = %e, "Failed to update guild alert channel");
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.setup.error_update_failed", locale = &locale),
+            &locale,
+        )
+        .await;
+    }
+
+    info!(
+        guild_id = %guild_id,
+        channel_id = %channel_id,
+        incident_type,
+        main = is_main,
+        "Routed incident type to alert channel"
+    );
+    let channel = format!("<#{}>", channel_id);
+    let type_name = incident_types::display_name_localized(incident_type, &locale);
+    let description = if is_main {
+        t!(
+            "embeds.config.setup.routed.description_main",
+            locale = &locale,
+            incident_type = type_name,
+            channel = channel
+        )
+    } else {
+        t!(
+            "embeds.config.setup.routed.description",
+            locale = &locale,
+            incident_type = type_name,
+            channel = channel
+        )
+    };
+    edit_success(
+        ctx,
+        interaction,
+        &t!("embeds.config.setup.routed.title", locale = &locale),
+        &with_channel_advice(description.to_string(), advice, &locale),
+    )
+    .await
+}
+
+/// Append the read-only channel tip when regular members can post in the channel
+fn with_channel_advice(mut description: String, advice: ChannelAdvice, locale: &str) -> String {
+    if advice.everyone_can_send {
+        description.push_str("\n\n");
+        description.push_str(&t!(
+            "embeds.config.setup.warning_everyone_can_send",
+            locale = locale
+        ));
+    }
+    description
+}
@@ -1,14 +1,27 @@
 //! Show handler for /config command
 
-use serenity::all::{CommandInteraction, Context};
+use chrono::{Duration, Utc};
+use serenity::all::{CommandInteraction, Context, GuildId};
+use tracing::warn;
 
-use crate::commands::shared::{defer, edit_embed};
+use crate::alerts::threshold::get_config_value;
+use crate::commands::shared::edit_embed;
+use crate::commands::shared::sparkline::weekly_counts;
 use crate::database;
 use crate::i18n::resolve_locale_async;
-use crate::repository::{GuildConfigRepository, UserConfigRepository};
+use crate::repository::{
+    AlertChannelRepository, AlertFilterRepository, GuildConfigRepository, RecordRepository,
+    SilenceRepository, UserConfigRepository, WebhookRepository, registration,
+};
 
 use super::super::context::ConfigContext;
-use super::super::embeds;
+use super::super::embeds::{self, GuildDestinations, GuildStats};
+
+/// Window for alert and report counts
+const STATS_DAYS: i64 = 30;
+
+/// Number of weeks in the alert trend sparkline
+const TREND_WEEKS: usize = 8;
 
 /// Handle /config show
 pub async fn handle_show(
@@ -16,17 +29,57 @@ pub async fn handle_show(
     interaction: &CommandInteraction,
     config_context: ConfigContext,
 ) -> Result<(), serenity::Error> {
-    // Defer response since we do database operations
-    defer(ctx, interaction).await?;
-
     let db = database::get_db(ctx).await;
     let locale = resolve_locale_async(ctx, interaction).await;
 
     let embed = match config_context {
         ConfigContext::Guild(guild_id) => {
-            let repo = GuildConfigRepository::new(db);
+            let repo = GuildConfigRepository::new(db.clone());
             match repo.get(guild_id).await {
-                Some(c) if c.enabled => embeds::show_guild_active(&c, &locale),
+                Some(c) if registration::guild(Some(&c), Utc::now()).is_registered() => {
+                    let stats =
+                        load_guild_stats(&RecordRepository::new(db.clone()), guild_id).await;
+                    let silences = SilenceRepository::new(db.clone())
+                        .active(guild_id, Utc::now())
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!(guild_id = %guild_id, error = %e, "Failed to load alert silences for /config show");
+                            vec![]
+                        });
+                    let disabled_alerts = AlertFilterRepository::new(db.clone())
+                        .disabled_types(guild_id)
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!(guild_id = %guild_id, error = %e, "Failed to load alert filters for /config show");
+                            vec![]
+                        });
+                    let alert_channels = AlertChannelRepository::new(db.clone())
+                        .list_enabled(&guild_id.to_string())
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!(guild_id = %guild_id, error = %e, "Failed to load alert channels for /config show");
+                            vec![]
+                        });
+                    let webhooks = WebhookRepository::new(db.clone())
+                        .list_enabled(&guild_id.to_string())
+                        .await
+                        .unwrap_or_else(|e| {
+                            warn!(guild_id = %guild_id, error = %e, "Failed to load webhooks for /config show");
+                            vec![]
+                        });
+                    embeds::show_guild_active(
+                        &c,
+                        stats.as_ref(),
+                        &silences,
+                        &GuildDestinations {
+                            alert_channels: &alert_channels,
+                            webhooks: &webhooks,
+                        },
+                        &disabled_alerts,
+                        get_config_value(&db, "report_threshold").await,
+                        &locale,
+                    )
+                }
                 Some(c) => embeds::show_guild_disabled(&c, &locale),
                 None => embeds::show_guild_intro(&locale),
             }
@@ -34,7 +87,9 @@ pub async fn handle_show(
         ConfigContext::User(user_id) => {
             let repo = UserConfigRepository::new(db);
             match repo.get(user_id).await {
-                Some(c) if c.enabled => embeds::show_user_active(&c, &locale),
+                Some(c) if registration::user(Some(&c)).is_registered() => {
+                    embeds::show_user_active(&c, &locale)
+                }
                 Some(c) => embeds::show_user_disabled(&c, &locale),
                 None => embeds::show_user_intro(&locale),
             }
@@ -43,3 +98,30 @@ pub async fn handle_show(
 
     edit_embed(ctx, interaction, embed).await
 }
+
+/// Load 30-day activity and the weekly alert trend; `None` if any query fails
+async fn load_guild_stats(repo: &RecordRepository, guild_id: GuildId) -> Option<GuildStats> {
+    let now = Utc::now();
+    let month_ago = now - Duration::days(STATS_DAYS);
+    let trend_start = now - Duration::weeks(TREND_WEEKS as i64);
+
+    let result = async {
+        let alerts_30d = repo.count_guild_alerts_since(guild_id, month_ago).await?;
+        let reports_30d = repo.count_guild_reports_since(guild_id, month_ago).await?;
+        let alert_times = repo.guild_alert_times_since(guild_id, trend_start).await?;
+        Ok::<_, sea_orm::DbErr>(GuildStats {
+            alerts_30d,
+            reports_30d,
+            weekly_alerts: weekly_counts(&alert_times, now, TREND_WEEKS),
+        })
+    }
+    .await;
+
+    match result {
+        Ok(stats) => Some(stats),
+        Err(e) => {
+            warn!(guild_id = %guild_id, error = %e, "Failed to load guild stats for /config show");
+            None
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Alert silence handler for /config command
+
+use chrono::Utc;
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context};
+
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::{GuildConfigRepository, SilenceRepository};
+
+use super::super::context::ConfigContext;
+use super::super::duration::{DurationError, MAX_SILENCE_DAYS, parse_silence_duration};
+use super::super::embeds;
+
+/// Duration value that clears all silences
+const SILENCE_OFF: &str = "off";
+
+/// Handle /config silence
+pub async fn handle_silence(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    duration: String,
+    incident_type: Option<String>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Silences apply to guild alert channels only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.silence.error_user_context", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    // Check if registered
+    if GuildConfigRepository::new(db.clone())
+        .get(guild_id)
+        .await
+        .is_none()
+    {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    let repo = SilenceRepository::new(db);
+
+    if duration.trim().eq_ignore_ascii_case(SILENCE_OFF) {
+        return match repo.clear(guild_id).await {
+            Ok(count) => {
+                info!(guild_id = %guild_id, count, "Cleared alert silences");
+                edit_embed(ctx, interaction, embeds::silence_cleared(count, &locale)).await
+            }
+            Err(e) => {
+                error!(error = %e, "Failed to clear alert silences");
+                edit_error(
+                    ctx,
+                    interaction,
+                    &t!(
+                        "embeds.config.silence.error_update_failed",
+                        locale = &locale
+                    ),
+                    &locale,
+                )
+                .await
+            }
+        };
+    }
+
+    let length = match parse_silence_duration(&duration) {
+        Ok(length) => length,
+        Err(e) => {
+            let message = match e {
+                DurationError::TooLong => t!(
+                    "embeds.config.silence.error_too_long",
+                    days = MAX_SILENCE_DAYS,
+                    locale = &locale
+                ),
+                DurationError::Invalid | DurationError::Zero => {
+                    t!(
+                        "embeds.config.silence.error_invalid_duration",
+                        locale = &locale
+                    )
+                }
+            };
+            return edit_error(ctx, interaction, &message, &locale).await;
+        }
+    };
+
+    let until = Utc::now() + length;
+    match repo
+        .create(guild_id, incident_type.clone(), until, interaction.user.id)
+        .await
+    {
+        Ok(_) => {
+            info!(
+                guild_id = %guild_id,
+                incident_type = incident_type.as_deref().unwrap_or("all"),
+                until = %until,
+                "Created alert silence"
+            );
+            let embed = embeds::silence_set(incident_type.as_deref(), until, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to create alert silence");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.silence.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
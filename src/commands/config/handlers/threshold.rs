@@ -0,0 +1,102 @@
+//! Report threshold override handler for /config command
+
+use rust_i18n::t;
+use tracing::{error, info};
+
+use serenity::all::{CommandInteraction, Context};
+
+use crate::alerts::threshold::get_config_value;
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::GuildConfigRepository;
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+
+/// Highest accepted threshold override
+pub const MAX_GUILD_THRESHOLD: i64 = 100;
+
+/// Handle /config threshold [value]
+///
+/// A value of 1-100 sets the override; 0 or no value resets the guild to
+/// the global threshold.
+pub async fn handle_threshold(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    value: Option<i64>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Threshold overrides apply to guild alert channels only
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.threshold.error_user_context",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    let repo = GuildConfigRepository::new(db.clone());
+
+    // Check if registered
+    if repo.get(guild_id).await.is_none() {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    let threshold = match value {
+        None | Some(0) => None,
+        Some(n) if (1..=MAX_GUILD_THRESHOLD).contains(&n) => Some(n),
+        Some(_) => {
+            return edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.threshold.error_out_of_range",
+                    max = MAX_GUILD_THRESHOLD,
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await;
+        }
+    };
+
+    match repo.update_alert_threshold(guild_id, threshold).await {
+        Ok(_) => {
+            info!(guild_id = %guild_id, ?threshold, "Updated guild report threshold");
+            let global = get_config_value(&db, "report_threshold").await;
+            let embed = embeds::threshold_updated(threshold, global, &locale);
+            edit_embed(ctx, interaction, embed).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to update guild report threshold");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.threshold.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
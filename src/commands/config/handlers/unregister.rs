@@ -1,23 +1,29 @@
 //! Unregister handler for /config command
 
+use chrono::{Duration, Utc};
 use rust_i18n::t;
 use serenity::all::{
     ButtonStyle, CommandInteraction, ComponentInteraction, Context, CreateActionRow, CreateButton,
 };
-use tracing::error;
+use tracing::{error, info};
 
 use crate::commands::shared::{
-    defer_component_update, defer_ephemeral, edit_component_embed, edit_component_error,
-    edit_embed_components, edit_error, parse_button_context,
+    defer_component_update, edit_component_embed, edit_component_error, edit_embed_components,
+    edit_error, parse_button_context,
 };
 use crate::database;
 use crate::i18n::{resolve_locale_async, resolve_locale_component};
-use crate::repository::{GuildConfigRepository, UserConfigRepository};
+use crate::repository::{GuildConfigRepository, Repositories, UserConfigRepository, registration};
 
 use super::super::context::ConfigContext;
 use super::super::embeds;
 use super::super::validation::{AdminCheckResult, validate_guild_admin};
-use super::{unregister_cancel_button_id, unregister_confirm_button_id};
+use super::{
+    unregister_cancel_button_id, unregister_confirm_button_id, unregister_pause_button_id,
+};
+
+/// How long the pause button stops guild alerts
+const PAUSE_DAYS: i64 = 7;
 
 /// Handle /config unregister - show confirmation buttons
 pub async fn handle_unregister(
@@ -25,34 +31,29 @@ pub async fn handle_unregister(
     interaction: &CommandInteraction,
     config_context: ConfigContext,
 ) -> Result<(), serenity::Error> {
-    // Defer ephemeral since we do database operations and response should be private
-    defer_ephemeral(ctx, interaction).await?;
-
     let db = database::get_db(ctx).await;
     let locale = resolve_locale_async(ctx, interaction).await;
 
-    // Check if registered
-    let is_registered = match &config_context {
+    // Load the live config (registered only) to list what stops and what is kept
+    let (guild_config, user_config) = match &config_context {
         ConfigContext::Guild(guild_id) => {
             let repo = GuildConfigRepository::new(db.clone());
-            repo.get(*guild_id).await.is_some_and(|c| c.enabled)
+            let config = repo
+                .get(*guild_id)
+                .await
+                .filter(|c| registration::guild(Some(c), Utc::now()).is_registered());
+            (config, None)
         }
         ConfigContext::User(user_id) => {
             let repo = UserConfigRepository::new(db);
-            repo.get(*user_id).await.is_some_and(|c| c.enabled)
+            let config = repo
+                .get(*user_id)
+                .await
+                .filter(|c| registration::user(Some(c)).is_registered());
+            (None, config)
         }
     };
 
-    if !is_registered {
-        return edit_error(
-            ctx,
-            interaction,
-            &t!("embeds.config.errors.not_registered", locale = &locale),
-            &locale,
-        )
-        .await;
-    }
-
     // Get name for confirmation message
     let name = match &config_context {
         ConfigContext::Guild(guild_id) => interaction
@@ -62,8 +63,23 @@ pub async fn handle_unregister(
         ConfigContext::User(_) => interaction.user.name.clone(),
     };
 
-    let is_guild = matches!(config_context, ConfigContext::Guild(_));
-    let embed = embeds::unregister_confirm(&name, is_guild, &locale);
+    let target = match (&guild_config, &user_config) {
+        (Some(config), _) => embeds::UnregisterTarget::Guild {
+            name: &name,
+            config,
+        },
+        (_, Some(config)) => embeds::UnregisterTarget::User { config },
+        (None, None) => {
+            return edit_error(
+                ctx,
+                interaction,
+                &t!("embeds.config.errors.not_registered", locale = &locale),
+                &locale,
+            )
+            .await;
+        }
+    };
+    let embed = embeds::unregister_confirm(&target, &locale);
 
     // Generate button IDs with context
     let (context_type, context_id) = match &config_context {
@@ -71,16 +87,32 @@ pub async fn handle_unregister(
         ConfigContext::User(user_id) => ("user", user_id.to_string()),
     };
 
-    let buttons = CreateActionRow::Buttons(vec![
+    let mut buttons = vec![
         CreateButton::new(unregister_cancel_button_id(context_type, &context_id))
             .label(t!("buttons.cancel", locale = &locale))
             .style(ButtonStyle::Secondary),
+    ];
+    // Pausing only applies to guild channel alerts
+    if guild_config.is_some() {
+        buttons.push(
+            CreateButton::new(unregister_pause_button_id(context_type, &context_id))
+                .label(t!("buttons.pause_7d", locale = &locale))
+                .style(ButtonStyle::Primary),
+        );
+    }
+    buttons.push(
         CreateButton::new(unregister_confirm_button_id(context_type, &context_id))
             .label(t!("buttons.yes_unregister", locale = &locale))
             .style(ButtonStyle::Danger),
-    ]);
+    );
 
-    edit_embed_components(ctx, interaction, embed, vec![buttons]).await
+    edit_embed_components(
+        ctx,
+        interaction,
+        embed,
+        vec![CreateActionRow::Buttons(buttons)],
+    )
+    .await
 }
 
 /// Handle unregister confirmation button
@@ -94,84 +126,27 @@ pub async fn handle_unregister_confirm(
     let db = database::get_db(ctx).await;
     let locale = resolve_locale_component(ctx, interaction).await;
 
-    // Parse context from button custom_id using shared utility
-    let config_context = parse_button_context(&interaction.data.custom_id)
-        .and_then(|(context_type, id_str)| parse_config_context(context_type, id_str));
-
-    // SECURITY: Validate the user has permission to perform this action
-    let validated_context = match config_context {
-        Some(ConfigContext::Guild(guild_id)) => {
-            // User must have ADMINISTRATOR permission in this guild
-            match validate_guild_admin(ctx, guild_id, interaction.user.id).await {
-                AdminCheckResult::IsAdmin => Some(ConfigContext::Guild(guild_id)),
-                AdminCheckResult::NotAdmin => {
-                    return edit_component_error(
-                        ctx,
-                        interaction,
-                        &t!("embeds.config.errors.no_permission", locale = &locale),
-                        &locale,
-                    )
-                    .await;
-                }
-                AdminCheckResult::CouldNotVerify(reason) => {
-                    error!(
-                        guild_id = %guild_id,
-                        user_id = %interaction.user.id,
-                        reason = %reason,
-                        "Could not verify admin permissions"
-                    );
-                    return edit_component_error(
-                        ctx,
-                        interaction,
-                        &t!("embeds.config.errors.could_not_verify", locale = &locale),
-                        &locale,
-                    )
-                    .await;
-                }
-            }
-        }
-        Some(ConfigContext::User(user_id)) => {
-            // User can only unregister their own account
-            if user_id != interaction.user.id {
-                return edit_component_error(
-                    ctx,
-                    interaction,
-                    &t!("embeds.config.errors.only_own_account", locale = &locale),
-                    &locale,
-                )
-                .await;
-            }
-            Some(ConfigContext::User(user_id))
-        }
-        None => None,
+    let Some(validated_context) = validate_button_context(ctx, interaction, &locale).await? else {
+        return Ok(());
     };
 
     let result: Result<(), sea_orm::DbErr> = match validated_context {
-        Some(ConfigContext::Guild(guild_id)) => {
-            let repo = GuildConfigRepository::new(db);
-            repo.disable(guild_id).await.map(|_| ())
+        ConfigContext::Guild(guild_id) => {
+            // Disable and clear silences together so a failure leaves neither half done
+            Repositories::new(db)
+                .transaction(move |tx| {
+                    Box::pin(async move {
+                        tx.guild_configs().disable(guild_id).await?;
+                        tx.silences().clear(guild_id).await?;
+                        Ok(())
+                    })
+                })
+                .await
         }
-        Some(ConfigContext::User(user_id)) => {
+        ConfigContext::User(user_id) => {
             let repo = UserConfigRepository::new(db);
             repo.disable(user_id).await.map(|_| ())
         }
-        None => {
-            // Context parsing failed - don't fall back to insecure behavior
-            error!(
-                "Failed to parse button context: {}",
-                interaction.data.custom_id
-            );
-            return edit_component_error(
-                ctx,
-                interaction,
-                &t!(
-                    "embeds.config.errors.invalid_button_state",
-                    locale = &locale
-                ),
-                &locale,
-            )
-            .await;
-        }
     };
 
     let embed = match result {
@@ -185,6 +160,50 @@ pub async fn handle_unregister_confirm(
     edit_component_embed(ctx, interaction, embed).await
 }
 
+/// Handle pause button - stop guild alerts for `PAUSE_DAYS` instead of unregistering
+pub async fn handle_unregister_pause(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), serenity::Error> {
+    // Defer first to acknowledge within 3 seconds
+    defer_component_update(ctx, interaction).await?;
+
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_component(ctx, interaction).await;
+
+    let Some(validated_context) = validate_button_context(ctx, interaction, &locale).await? else {
+        return Ok(());
+    };
+
+    // The pause button is only offered for guilds
+    let ConfigContext::Guild(guild_id) = validated_context else {
+        return edit_component_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.errors.invalid_button_state",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    };
+
+    let until = Utc::now() + Duration::days(PAUSE_DAYS);
+    let embed = match GuildConfigRepository::new(db).pause(guild_id, until).await {
+        Ok(_) => {
+            info!(guild_id = %guild_id, until = %until, "Paused guild alerts");
+            embeds::unregister_paused(until, &locale)
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to pause guild alerts");
+            embeds::unregister_error(&locale)
+        }
+    };
+
+    edit_component_embed(ctx, interaction, embed).await
+}
+
 /// Handle unregister cancel button
 pub async fn handle_unregister_cancel(
     ctx: &Context,
@@ -202,6 +221,58 @@ pub async fn handle_unregister_cancel(
 // Helper Functions
 // =============================================================================
 
+/// Parse the button's context and check the clicking user may act on it
+///
+/// Sends the error response and returns `None` if the context is invalid or
+/// the user lacks permission.
+async fn validate_button_context(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    locale: &str,
+) -> Result<Option<ConfigContext>, serenity::Error> {
+    // Parse context from button custom_id using shared utility
+    let config_context = parse_button_context(&interaction.data.custom_id)
+        .and_then(|(context_type, id_str)| parse_config_context(context_type, id_str));
+
+    // SECURITY: Validate the user has permission to perform this action
+    let error_key = match config_context {
+        Some(ConfigContext::Guild(guild_id)) => {
+            // User must have ADMINISTRATOR permission in this guild
+            match validate_guild_admin(ctx, guild_id, interaction.user.id).await {
+                AdminCheckResult::IsAdmin => return Ok(config_context),
+                AdminCheckResult::NotAdmin => "embeds.config.errors.no_permission",
+                AdminCheckResult::CouldNotVerify(reason) => {
+                    error!(
+                        guild_id = %guild_id,
+                        user_id = %interaction.user.id,
+                        reason = %reason,
+                        "Could not verify admin permissions"
+                    );
+                    "embeds.config.errors.could_not_verify"
+                }
+            }
+        }
+        Some(ConfigContext::User(user_id)) => {
+            // User can only act on their own account
+            if user_id == interaction.user.id {
+                return Ok(config_context);
+            }
+            "embeds.config.errors.only_own_account"
+        }
+        None => {
+            // Context parsing failed - don't fall back to insecure behavior
+            error!(
+                "Failed to parse button context: {}",
+                interaction.data.custom_id
+            );
+            "embeds.config.errors.invalid_button_state"
+        }
+    };
+
+    edit_component_error(ctx, interaction, &t!(error_key, locale = locale), locale).await?;
+    Ok(None)
+}
+
 /// Parse ConfigContext from context type and ID string
 fn parse_config_context(context_type: &str, id_str: &str) -> Option<ConfigContext> {
     use serenity::all::{GuildId, UserId};
@@ -0,0 +1,210 @@
+//! Alert webhook handlers for /config command
+
+use rust_i18n::t;
+use serenity::all::{CommandInteraction, Context};
+use tracing::{error, info};
+
+use crate::alerts::webhook::{self, generate_secret};
+use crate::commands::shared::{edit_embed, edit_error};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::repository::{GuildConfigRepository, WebhookRepository};
+
+use super::super::context::ConfigContext;
+use super::super::embeds;
+
+/// Most webhooks a guild can register
+pub const MAX_WEBHOOKS_PER_GUILD: u64 = 5;
+
+/// Longest accepted webhook URL
+pub const MAX_WEBHOOK_URL_LENGTH: u16 = 2000;
+
+/// Handle /config webhook add <url> [type]
+///
+/// Generates a signing secret and shows it once in the (ephemeral) reply.
+pub async fn handle_webhook_add(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    url: String,
+    incident_type: Option<String>,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    // Webhooks are tied to a guild registration
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.webhook.error_user_context", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    if GuildConfigRepository::new(db.clone())
+        .get(guild_id)
+        .await
+        .is_none()
+    {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!(
+                "embeds.config.setup.error_language_not_registered_guild",
+                locale = &locale
+            ),
+            &locale,
+        )
+        .await;
+    }
+
+    // HTTPS only, so payloads and signatures aren't sent in the clear
+    let url = url.trim();
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.webhook.error_invalid_url", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+    if parsed.scheme() != "https" || parsed.host_str().is_none() {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.webhook.error_invalid_url", locale = &locale),
+            &locale,
+        )
+        .await;
+    }
+
+    // Only public addresses, so a guild can't make the bot post to its own
+    // host or internal network (loopback, private ranges, cloud metadata)
+    if !webhook::is_allowed_url(&parsed) || !webhook::resolves_to_public(&parsed).await {
+        info!(guild_id = %guild_id, host = ?parsed.host_str(), "Rejected non-public webhook URL");
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.webhook.error_private_url", locale = &locale),
+            &locale,
+        )
+        .await;
+    }
+
+    let repo = WebhookRepository::new(db.clone());
+    match repo.count(guild_id).await {
+        Ok(count) if count >= MAX_WEBHOOKS_PER_GUILD => {
+            return edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.webhook.error_limit",
+                    max = MAX_WEBHOOKS_PER_GUILD,
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to count guild webhooks");
+            return edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.webhook.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await;
+        }
+    }
+
+    let alert_types = incident_type.unwrap_or_default();
+    match repo
+        .add(guild_id, url, &generate_secret(), &alert_types)
+        .await
+    {
+        Ok(webhook) => {
+            info!(
+                guild_id = %guild_id,
+                webhook_id = webhook.id,
+                alert_types = %webhook.alert_types,
+                "Added guild alert webhook"
+            );
+            edit_embed(ctx, interaction, embeds::webhook_added(&webhook, &locale)).await
+        }
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to add guild webhook");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.webhook.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
+
+/// Handle /config webhook remove <id>
+pub async fn handle_webhook_remove(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    config_context: ConfigContext,
+    id: i64,
+) -> Result<(), serenity::Error> {
+    let db = database::get_db(ctx).await;
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    let ConfigContext::Guild(guild_id) = config_context else {
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("embeds.config.webhook.error_user_context", locale = &locale),
+            &locale,
+        )
+        .await;
+    };
+
+    match WebhookRepository::new(db).remove(guild_id, id).await {
+        Ok(true) => {
+            info!(guild_id = %guild_id, webhook_id = id, "Removed guild alert webhook");
+            edit_embed(ctx, interaction, embeds::webhook_removed(id, &locale)).await
+        }
+        Ok(false) => {
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.webhook.error_not_found",
+                    id = id,
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to remove guild webhook");
+            edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.config.webhook.error_update_failed",
+                    locale = &locale
+                ),
+                &locale,
+            )
+            .await
+        }
+    }
+}
@@ -1,7 +1,9 @@
 //! /config command - Guild and user registration for VRCPulse alerts
 
 mod context;
+mod duration;
 mod embeds;
+mod export;
 mod handlers;
 mod validation;
 
@@ -11,12 +13,17 @@ use serenity::all::{
     CreateCommand, CreateCommandOption, Permissions, ResolvedValue,
 };
 
-use crate::commands::shared::respond_error;
+use crate::alerts::FILTERABLE_ALERT_TYPES;
+use crate::commands::shared::{edit_error, incident_types};
 use crate::i18n::resolve_locale;
 use context::determine_context;
 use handlers::{
-    handle_language, handle_setup, handle_show, handle_unregister, handle_unregister_cancel,
-    handle_unregister_confirm, is_cancel_button, is_confirm_button,
+    MAX_GUILD_THRESHOLD, MAX_WEBHOOK_URL_LENGTH, handle_alert_filter, handle_alert_types,
+    handle_data, handle_digest, handle_fallback_dm, handle_language, handle_mention,
+    handle_notifications, handle_quiet_hours, handle_report_role, handle_setup, handle_show,
+    handle_silence, handle_threshold, handle_unregister, handle_unregister_cancel,
+    handle_unregister_confirm, handle_unregister_pause, handle_webhook_add, handle_webhook_remove,
+    is_cancel_button, is_confirm_button, is_pause_button,
 };
 
 // =============================================================================
@@ -25,6 +32,73 @@ use handlers::{
 
 /// /config command definition
 pub fn register() -> CreateCommand {
+    let mut silence_type_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "type",
+        t!("commands.config.silence.option_type"),
+    )
+    .name_localized("ko", "유형")
+    .description_localized(
+        "ko",
+        t!("commands.config.silence.option_type", locale = "ko"),
+    )
+    .required(false);
+
+    let mut alerts_type_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "type",
+        t!("commands.config.alerts.types.option_type"),
+    )
+    .name_localized("ko", "유형")
+    .description_localized(
+        "ko",
+        t!("commands.config.alerts.types.option_type", locale = "ko"),
+    )
+    .required(false);
+
+    let mut webhook_type_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "type",
+        t!("commands.config.webhook.add.option_type"),
+    )
+    .name_localized("ko", "유형")
+    .description_localized(
+        "ko",
+        t!("commands.config.webhook.add.option_type", locale = "ko"),
+    )
+    .required(false);
+
+    let mut setup_for_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "for",
+        t!("commands.config.setup.option_for"),
+    )
+    .name_localized("ko", "유형")
+    .description_localized("ko", t!("commands.config.setup.option_for", locale = "ko"))
+    .required(false);
+
+    for key in incident_types::INCIDENT_TYPE_KEYS {
+        let display_en = incident_types::display_name(key);
+        let display_ko = incident_types::display_name_localized(key, "ko");
+        setup_for_option = setup_for_option.add_string_choice_localized(
+            display_en.clone(),
+            *key,
+            [("ko", display_ko.clone())],
+        );
+        silence_type_option = silence_type_option.add_string_choice_localized(
+            display_en.clone(),
+            *key,
+            [("ko", display_ko.clone())],
+        );
+        webhook_type_option = webhook_type_option.add_string_choice_localized(
+            display_en.clone(),
+            *key,
+            [("ko", display_ko.clone())],
+        );
+        alerts_type_option =
+            alerts_type_option.add_string_choice_localized(display_en, *key, [("ko", display_ko)]);
+    }
+
     CreateCommand::new("config")
         .description(t!("commands.config.description"))
         .name_localized("ko", t!("commands.config.name", locale = "ko"))
@@ -51,7 +125,8 @@ pub fn register() -> CreateCommand {
                 )
                 .channel_types(vec![ChannelType::Text, ChannelType::News])
                 .required(false),
-            ),
+            )
+            .add_sub_option(setup_for_option),
         )
         .add_option(
             CreateCommandOption::new(
@@ -99,24 +174,489 @@ pub fn register() -> CreateCommand {
                 .required(false)
                 .add_string_choice("English", "en")
                 .add_string_choice("한국어 (Korean)", "ko")
+                .add_string_choice("日本語 (Japanese)", "ja")
                 .add_string_choice("Auto-detect (Discord)", "auto"),
             ),
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "data",
+                t!("commands.config.data.description"),
+            )
+            .name_localized("ko", t!("commands.config.data.name", locale = "ko"))
+            .description_localized("ko", t!("commands.config.data.description", locale = "ko")),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "fallbackdm",
+                t!("commands.config.fallbackdm.description"),
+            )
+            .name_localized("ko", t!("commands.config.fallbackdm.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.config.fallbackdm.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "mode",
+                    t!("commands.config.fallbackdm.option_mode"),
+                )
+                .name_localized("ko", "모드")
+                .description_localized(
+                    "ko",
+                    t!("commands.config.fallbackdm.option_mode", locale = "ko"),
+                )
+                .required(false)
+                .add_string_choice("On", "on")
+                .add_string_choice("Off", "off"),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "digest",
+                t!("commands.config.digest.description"),
+            )
+            .name_localized("ko", t!("commands.config.digest.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.config.digest.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "mode",
+                    t!("commands.config.digest.option_mode"),
+                )
+                .name_localized("ko", "모드")
+                .description_localized(
+                    "ko",
+                    t!("commands.config.digest.option_mode", locale = "ko"),
+                )
+                .required(false)
+                .add_string_choice("On", "on")
+                .add_string_choice("Off", "off"),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "silence",
+                t!("commands.config.silence.description"),
+            )
+            .name_localized("ko", t!("commands.config.silence.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.config.silence.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "duration",
+                    t!("commands.config.silence.option_duration"),
+                )
+                .name_localized("ko", "기간")
+                .description_localized(
+                    "ko",
+                    t!("commands.config.silence.option_duration", locale = "ko"),
+                )
+                .required(true),
+            )
+            .add_sub_option(silence_type_option),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "mention",
+                t!("commands.config.mention.description"),
+            )
+            .name_localized("ko", t!("commands.config.mention.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.config.mention.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Role,
+                    "role",
+                    t!("commands.config.mention.option_role"),
+                )
+                .name_localized("ko", "역할")
+                .description_localized(
+                    "ko",
+                    t!("commands.config.mention.option_role", locale = "ko"),
+                )
+                .required(false),
+            ),
+        )
+        .add_option(quiet_hours_group())
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "threshold",
+                t!("commands.config.threshold.description"),
+            )
+            .name_localized("ko", t!("commands.config.threshold.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.config.threshold.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "value",
+                    t!("commands.config.threshold.option_value"),
+                )
+                .name_localized("ko", "값")
+                .description_localized(
+                    "ko",
+                    t!("commands.config.threshold.option_value", locale = "ko"),
+                )
+                .min_int_value(0)
+                .max_int_value(MAX_GUILD_THRESHOLD as u64)
+                .required(false),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommandGroup,
+                "alerts",
+                t!("commands.config.alerts.description"),
+            )
+            .name_localized("ko", t!("commands.config.alerts.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.config.alerts.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "types",
+                    t!("commands.config.alerts.types.description"),
+                )
+                .name_localized("ko", t!("commands.config.alerts.types.name", locale = "ko"))
+                .description_localized(
+                    "ko",
+                    t!("commands.config.alerts.types.description", locale = "ko"),
+                )
+                .add_sub_option(alerts_type_option)
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "mode",
+                        t!("commands.config.alerts.types.option_mode"),
+                    )
+                    .name_localized("ko", "모드")
+                    .description_localized(
+                        "ko",
+                        t!("commands.config.alerts.types.option_mode", locale = "ko"),
+                    )
+                    .required(false)
+                    .add_string_choice("On", "on")
+                    .add_string_choice("Off", "off"),
+                ),
+            )
+            .add_sub_option(alert_filter_subcommand("enable"))
+            .add_sub_option(alert_filter_subcommand("disable")),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "reportrole",
+                t!("commands.config.report_role.description"),
+            )
+            .name_localized("ko", t!("commands.config.report_role.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.config.report_role.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Role,
+                    "role",
+                    t!("commands.config.report_role.option_role"),
+                )
+                .name_localized("ko", "역할")
+                .description_localized(
+                    "ko",
+                    t!("commands.config.report_role.option_role", locale = "ko"),
+                )
+                .required(false),
+            ),
+        )
+        .add_option(notifications_group())
+        .add_option(webhook_group(webhook_type_option))
+}
+
+/// /config quiet-hours set|clear definition
+fn quiet_hours_group() -> CreateCommandOption {
+    let hour_option = |name: &str, ko_name: &str| {
+        let key = format!("commands.config.quiet_hours.option_{}", name);
+        CreateCommandOption::new(CommandOptionType::Integer, name, t!(&key))
+            .name_localized("ko", ko_name)
+            .description_localized("ko", t!(&key, locale = "ko"))
+            .min_int_value(0)
+            .max_int_value(23)
+            .required(true)
+    };
+
+    CreateCommandOption::new(
+        CommandOptionType::SubCommandGroup,
+        "quiet-hours",
+        t!("commands.config.quiet_hours.description"),
+    )
+    .name_localized("ko", t!("commands.config.quiet_hours.name", locale = "ko"))
+    .description_localized(
+        "ko",
+        t!("commands.config.quiet_hours.description", locale = "ko"),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "set",
+            t!("commands.config.quiet_hours.set.description"),
+        )
+        .name_localized(
+            "ko",
+            t!("commands.config.quiet_hours.set.name", locale = "ko"),
+        )
+        .description_localized(
+            "ko",
+            t!("commands.config.quiet_hours.set.description", locale = "ko"),
+        )
+        .add_sub_option(hour_option("start", "시작"))
+        .add_sub_option(hour_option("end", "종료")),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "clear",
+            t!("commands.config.quiet_hours.clear.description"),
+        )
+        .name_localized(
+            "ko",
+            t!("commands.config.quiet_hours.clear.name", locale = "ko"),
+        )
+        .description_localized(
+            "ko",
+            t!(
+                "commands.config.quiet_hours.clear.description",
+                locale = "ko"
+            ),
+        ),
+    )
+}
+
+/// /config webhook add|remove definition
+fn webhook_group(type_option: CreateCommandOption) -> CreateCommandOption {
+    CreateCommandOption::new(
+        CommandOptionType::SubCommandGroup,
+        "webhook",
+        t!("commands.config.webhook.description"),
+    )
+    .name_localized("ko", t!("commands.config.webhook.name", locale = "ko"))
+    .description_localized(
+        "ko",
+        t!("commands.config.webhook.description", locale = "ko"),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "add",
+            t!("commands.config.webhook.add.description"),
+        )
+        .name_localized("ko", t!("commands.config.webhook.add.name", locale = "ko"))
+        .description_localized(
+            "ko",
+            t!("commands.config.webhook.add.description", locale = "ko"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "url",
+                t!("commands.config.webhook.add.option_url"),
+            )
+            .name_localized("ko", "주소")
+            .description_localized(
+                "ko",
+                t!("commands.config.webhook.add.option_url", locale = "ko"),
+            )
+            .max_length(MAX_WEBHOOK_URL_LENGTH)
+            .required(true),
+        )
+        .add_sub_option(type_option),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "remove",
+            t!("commands.config.webhook.remove.description"),
+        )
+        .name_localized(
+            "ko",
+            t!("commands.config.webhook.remove.name", locale = "ko"),
+        )
+        .description_localized(
+            "ko",
+            t!("commands.config.webhook.remove.description", locale = "ko"),
+        )
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Integer,
+                "id",
+                t!("commands.config.webhook.remove.option_id"),
+            )
+            .name_localized("ko", "번호")
+            .description_localized(
+                "ko",
+                t!("commands.config.webhook.remove.option_id", locale = "ko"),
+            )
+            .min_int_value(1)
+            .required(true),
+        ),
+    )
+}
+
+/// /config notifications show|set definition
+fn notifications_group() -> CreateCommandOption {
+    let mut type_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "type",
+        t!("commands.config.notifications.option_type"),
+    )
+    .name_localized("ko", "유형")
+    .description_localized(
+        "ko",
+        t!("commands.config.notifications.option_type", locale = "ko"),
+    )
+    .required(true);
+    for key in FILTERABLE_ALERT_TYPES {
+        let name_key = format!("commands.config.alerts.choice_{}", key);
+        type_option = type_option.add_string_choice_localized(
+            t!(&name_key),
+            *key,
+            [("ko", t!(&name_key, locale = "ko"))],
+        );
+    }
+
+    CreateCommandOption::new(
+        CommandOptionType::SubCommandGroup,
+        "notifications",
+        t!("commands.config.notifications.description"),
+    )
+    .name_localized(
+        "ko",
+        t!("commands.config.notifications.name", locale = "ko"),
+    )
+    .description_localized(
+        "ko",
+        t!("commands.config.notifications.description", locale = "ko"),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "show",
+            t!("commands.config.notifications.show.description"),
+        )
+        .name_localized(
+            "ko",
+            t!("commands.config.notifications.show.name", locale = "ko"),
+        )
+        .description_localized(
+            "ko",
+            t!(
+                "commands.config.notifications.show.description",
+                locale = "ko"
+            ),
+        ),
+    )
+    .add_sub_option(
+        CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "set",
+            t!("commands.config.notifications.set.description"),
+        )
+        .name_localized(
+            "ko",
+            t!("commands.config.notifications.set.name", locale = "ko"),
+        )
+        .description_localized(
+            "ko",
+            t!(
+                "commands.config.notifications.set.description",
+                locale = "ko"
+            ),
+        )
+        .add_sub_option(type_option)
+        .add_sub_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "enabled",
+                t!("commands.config.notifications.option_enabled"),
+            )
+            .name_localized("ko", "사용")
+            .description_localized(
+                "ko",
+                t!(
+                    "commands.config.notifications.option_enabled",
+                    locale = "ko"
+                ),
+            )
+            .required(true),
+        ),
+    )
+}
+
+/// /config alerts enable|disable definition
+fn alert_filter_subcommand(action: &str) -> CreateCommandOption {
+    let mut alert_type_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "alert_type",
+        t!("commands.config.alerts.option_alert_type"),
+    )
+    .name_localized("ko", "알림종류")
+    .description_localized(
+        "ko",
+        t!("commands.config.alerts.option_alert_type", locale = "ko"),
+    )
+    .required(true);
+    for key in FILTERABLE_ALERT_TYPES {
+        let name_key = format!("commands.config.alerts.choice_{}", key);
+        alert_type_option = alert_type_option.add_string_choice_localized(
+            t!(&name_key),
+            *key,
+            [("ko", t!(&name_key, locale = "ko"))],
+        );
+    }
+
+    let name_key = format!("commands.config.alerts.{}.name", action);
+    let description_key = format!("commands.config.alerts.{}.description", action);
+    CreateCommandOption::new(CommandOptionType::SubCommand, action, t!(&description_key))
+        .name_localized("ko", t!(&name_key, locale = "ko"))
+        .description_localized("ko", t!(&description_key, locale = "ko"))
+        .add_sub_option(alert_type_option)
 }
 
 // =============================================================================
 // Command Handler
 // =============================================================================
 
-/// /config command handler
+/// /config command handler (deferred by the dispatcher)
 pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
     let options = &interaction.data.options();
-    // Use sync locale resolution for error messages (before defer)
-    // Each handler will call resolve_locale_async after deferring for full DB lookup
-    let locale = resolve_locale(interaction);
 
     let Some(subcommand) = options.first() else {
-        return respond_error(ctx, interaction, "Missing subcommand", &locale).await;
+        let locale = resolve_locale(interaction);
+        return edit_error(
+            ctx,
+            interaction,
+            &t!("errors.missing_subcommand", locale = &locale),
+            &locale,
+        )
+        .await;
     };
 
     // Determine context: guild or user install
@@ -124,22 +664,33 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
 
     match subcommand.name {
         "setup" => {
-            let channel_id = if let ResolvedValue::SubCommand(opts) = &subcommand.value {
-                opts.iter().find_map(|opt| {
-                    if opt.name == "channel"
-                        && let ResolvedValue::Channel(ch) = opt.value
-                    {
-                        return Some(ch.id);
-                    }
-                    None
-                })
-            } else {
-                None
-            };
-            handle_setup(ctx, interaction, config_context, channel_id).await
+            let (channel_id, incident_type) =
+                if let ResolvedValue::SubCommand(opts) = &subcommand.value {
+                    let channel_id = opts.iter().find_map(|opt| {
+                        if opt.name == "channel"
+                            && let ResolvedValue::Channel(ch) = opt.value
+                        {
+                            return Some(ch.id);
+                        }
+                        None
+                    });
+                    let incident_type = opts.iter().find_map(|opt| {
+                        if opt.name == "for"
+                            && let ResolvedValue::String(key) = opt.value
+                        {
+                            return Some(key.to_string());
+                        }
+                        None
+                    });
+                    (channel_id, incident_type)
+                } else {
+                    (None, None)
+                };
+            handle_setup(ctx, interaction, config_context, channel_id, incident_type).await
         }
         "show" => handle_show(ctx, interaction, config_context).await,
         "unregister" => handle_unregister(ctx, interaction, config_context).await,
+        "data" => handle_data(ctx, interaction, config_context).await,
         "language" => {
             let language_code = if let ResolvedValue::SubCommand(opts) = &subcommand.value {
                 opts.iter().find_map(|opt| {
@@ -155,7 +706,356 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
             };
             handle_language(ctx, interaction, config_context, language_code).await
         }
-        _ => respond_error(ctx, interaction, "Unknown subcommand", &locale).await,
+        "fallbackdm" => {
+            let mode = if let ResolvedValue::SubCommand(opts) = &subcommand.value {
+                opts.iter().find_map(|opt| {
+                    if opt.name == "mode"
+                        && let ResolvedValue::String(mode) = opt.value
+                    {
+                        return Some(mode.to_string());
+                    }
+                    None
+                })
+            } else {
+                None
+            };
+            handle_fallback_dm(ctx, interaction, config_context, mode).await
+        }
+        "digest" => {
+            let mode = if let ResolvedValue::SubCommand(opts) = &subcommand.value {
+                opts.iter().find_map(|opt| {
+                    if opt.name == "mode"
+                        && let ResolvedValue::String(mode) = opt.value
+                    {
+                        return Some(mode.to_string());
+                    }
+                    None
+                })
+            } else {
+                None
+            };
+            handle_digest(ctx, interaction, config_context, mode).await
+        }
+        "mention" => {
+            let role = if let ResolvedValue::SubCommand(opts) = &subcommand.value {
+                opts.iter().find_map(|opt| {
+                    if opt.name == "role"
+                        && let ResolvedValue::Role(role) = opt.value
+                    {
+                        return Some(role);
+                    }
+                    None
+                })
+            } else {
+                None
+            };
+            handle_mention(ctx, interaction, config_context, role).await
+        }
+        "threshold" => {
+            let value = if let ResolvedValue::SubCommand(opts) = &subcommand.value {
+                opts.iter().find_map(|opt| {
+                    if opt.name == "value"
+                        && let ResolvedValue::Integer(value) = opt.value
+                    {
+                        return Some(value);
+                    }
+                    None
+                })
+            } else {
+                None
+            };
+            handle_threshold(ctx, interaction, config_context, value).await
+        }
+        "alerts" => {
+            // Subcommand group: /config alerts <types|enable|disable>
+            let (action, opts) = match &subcommand.value {
+                ResolvedValue::SubCommandGroup(group) => match group.first() {
+                    Some(action) => match &action.value {
+                        ResolvedValue::SubCommand(opts) => (action.name, opts.as_slice()),
+                        _ => (action.name, &[][..]),
+                    },
+                    None => ("", &[][..]),
+                },
+                _ => ("", &[][..]),
+            };
+
+            let mut incident_type = None;
+            let mut mode = None;
+            let mut alert_type = None;
+            for opt in opts {
+                match (opt.name, &opt.value) {
+                    ("type", ResolvedValue::String(value)) => {
+                        incident_type = Some(value.to_string())
+                    }
+                    ("mode", ResolvedValue::String(value)) => mode = Some(value.to_string()),
+                    ("alert_type", ResolvedValue::String(value)) => {
+                        alert_type = Some(value.to_string())
+                    }
+                    _ => {}
+                }
+            }
+
+            match (action, alert_type) {
+                ("types", _) => {
+                    handle_alert_types(ctx, interaction, config_context, incident_type, mode).await
+                }
+                ("enable" | "disable", Some(alert_type)) => {
+                    let enabled = action == "enable";
+                    handle_alert_filter(ctx, interaction, config_context, alert_type, enabled).await
+                }
+                ("enable" | "disable", None) => {
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!(
+                            "errors.missing_option",
+                            option = "alert_type",
+                            locale = &locale
+                        ),
+                        &locale,
+                    )
+                    .await
+                }
+                _ => {
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!("errors.unknown_subcommand", locale = &locale),
+                        &locale,
+                    )
+                    .await
+                }
+            }
+        }
+        "notifications" => {
+            // Subcommand group: /config notifications <show|set>
+            let (action, opts) = match &subcommand.value {
+                ResolvedValue::SubCommandGroup(group) => match group.first() {
+                    Some(action) => match &action.value {
+                        ResolvedValue::SubCommand(opts) => (action.name, opts.as_slice()),
+                        _ => (action.name, &[][..]),
+                    },
+                    None => ("", &[][..]),
+                },
+                _ => ("", &[][..]),
+            };
+
+            let mut alert_type = None;
+            let mut enabled = None;
+            for opt in opts {
+                match (opt.name, &opt.value) {
+                    ("type", ResolvedValue::String(value)) => alert_type = Some(value.to_string()),
+                    ("enabled", ResolvedValue::Boolean(value)) => enabled = Some(*value),
+                    _ => {}
+                }
+            }
+
+            match (action, alert_type, enabled) {
+                ("show", _, _) => {
+                    handle_notifications(ctx, interaction, config_context, None).await
+                }
+                ("set", Some(alert_type), Some(enabled)) => {
+                    handle_notifications(
+                        ctx,
+                        interaction,
+                        config_context,
+                        Some((alert_type, enabled)),
+                    )
+                    .await
+                }
+                ("set", alert_type, _) => {
+                    let option = if alert_type.is_none() {
+                        "type"
+                    } else {
+                        "enabled"
+                    };
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!("errors.missing_option", option = option, locale = &locale),
+                        &locale,
+                    )
+                    .await
+                }
+                _ => {
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!("errors.unknown_subcommand", locale = &locale),
+                        &locale,
+                    )
+                    .await
+                }
+            }
+        }
+        "quiet-hours" => {
+            // Subcommand group: /config quiet-hours <set|clear>
+            let (action, opts) = match &subcommand.value {
+                ResolvedValue::SubCommandGroup(group) => match group.first() {
+                    Some(action) => match &action.value {
+                        ResolvedValue::SubCommand(opts) => (action.name, opts.as_slice()),
+                        _ => (action.name, &[][..]),
+                    },
+                    None => ("", &[][..]),
+                },
+                _ => ("", &[][..]),
+            };
+
+            let hour = |name: &str| {
+                opts.iter().find_map(|opt| match (opt.name, &opt.value) {
+                    (n, ResolvedValue::Integer(value)) if n == name => {
+                        u32::try_from(*value).ok().filter(|h| *h < 24)
+                    }
+                    _ => None,
+                })
+            };
+
+            match (action, hour("start"), hour("end")) {
+                ("clear", _, _) => handle_quiet_hours(ctx, interaction, config_context, None).await,
+                ("set", Some(start), Some(end)) => {
+                    handle_quiet_hours(ctx, interaction, config_context, Some((start, end))).await
+                }
+                ("set", start, _) => {
+                    let option = if start.is_none() { "start" } else { "end" };
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!("errors.missing_option", option = option, locale = &locale),
+                        &locale,
+                    )
+                    .await
+                }
+                _ => {
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!("errors.unknown_subcommand", locale = &locale),
+                        &locale,
+                    )
+                    .await
+                }
+            }
+        }
+        "webhook" => {
+            // Subcommand group: /config webhook <add|remove>
+            let (action, opts) = match &subcommand.value {
+                ResolvedValue::SubCommandGroup(group) => match group.first() {
+                    Some(action) => match &action.value {
+                        ResolvedValue::SubCommand(opts) => (action.name, opts.as_slice()),
+                        _ => (action.name, &[][..]),
+                    },
+                    None => ("", &[][..]),
+                },
+                _ => ("", &[][..]),
+            };
+
+            let mut url = None;
+            let mut incident_type = None;
+            let mut id = None;
+            for opt in opts {
+                match (opt.name, &opt.value) {
+                    ("url", ResolvedValue::String(value)) => url = Some(value.to_string()),
+                    ("type", ResolvedValue::String(value)) => {
+                        incident_type = Some(value.to_string())
+                    }
+                    ("id", ResolvedValue::Integer(value)) => id = Some(*value),
+                    _ => {}
+                }
+            }
+
+            match (action, url, id) {
+                ("add", Some(url), _) => {
+                    handle_webhook_add(ctx, interaction, config_context, url, incident_type).await
+                }
+                ("remove", _, Some(id)) => {
+                    handle_webhook_remove(ctx, interaction, config_context, id).await
+                }
+                ("add" | "remove", _, _) => {
+                    let option = if action == "add" { "url" } else { "id" };
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!("errors.missing_option", option = option, locale = &locale),
+                        &locale,
+                    )
+                    .await
+                }
+                _ => {
+                    let locale = resolve_locale(interaction);
+                    edit_error(
+                        ctx,
+                        interaction,
+                        &t!("errors.unknown_subcommand", locale = &locale),
+                        &locale,
+                    )
+                    .await
+                }
+            }
+        }
+        "silence" => {
+            let mut duration = None;
+            let mut incident_type = None;
+            if let ResolvedValue::SubCommand(opts) = &subcommand.value {
+                for opt in opts {
+                    match (opt.name, &opt.value) {
+                        ("duration", ResolvedValue::String(value)) => {
+                            duration = Some(value.to_string())
+                        }
+                        ("type", ResolvedValue::String(value)) => {
+                            incident_type = Some(value.to_string())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            let Some(duration) = duration else {
+                let locale = resolve_locale(interaction);
+                return edit_error(
+                    ctx,
+                    interaction,
+                    &t!(
+                        "errors.missing_option",
+                        option = "duration",
+                        locale = &locale
+                    ),
+                    &locale,
+                )
+                .await;
+            };
+            handle_silence(ctx, interaction, config_context, duration, incident_type).await
+        }
+        "reportrole" => {
+            let role = if let ResolvedValue::SubCommand(opts) = &subcommand.value {
+                opts.iter().find_map(|opt| {
+                    if opt.name == "role"
+                        && let ResolvedValue::Role(role) = opt.value
+                    {
+                        return Some(role);
+                    }
+                    None
+                })
+            } else {
+                None
+            };
+            handle_report_role(ctx, interaction, config_context, role).await
+        }
+        _ => {
+            let locale = resolve_locale(interaction);
+            edit_error(
+                ctx,
+                interaction,
+                &t!("errors.unknown_subcommand", locale = &locale),
+                &locale,
+            )
+            .await
+        }
     }
 }
 
@@ -163,7 +1063,7 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
 // Button Handler
 // =============================================================================
 
-/// Handle button interactions for unregister confirmation
+/// Handle button interactions for unregister confirmation and pause
 pub async fn handle_button(
     ctx: &Context,
     interaction: &ComponentInteraction,
@@ -174,6 +1074,8 @@ pub async fn handle_button(
         handle_unregister_confirm(ctx, interaction).await
     } else if is_cancel_button(custom_id) {
         handle_unregister_cancel(ctx, interaction).await
+    } else if is_pause_button(custom_id) {
+        handle_unregister_pause(ctx, interaction).await
     } else {
         Ok(())
     }
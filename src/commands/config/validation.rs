@@ -1,76 +1,178 @@
 //! Permission validation for config commands
 
-use serenity::all::{ChannelId, Context, GuildChannel, GuildId, Permissions, UserId};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serenity::all::{
+    ChannelId, Context, GuildChannel, GuildId, PermissionOverwrite, PermissionOverwriteType,
+    Permissions, Role, RoleId, UserId,
+};
+use tokio::time::timeout;
 use tracing::error;
 
+/// Upper bound for HTTP fallbacks when guild data is not cached
+const HTTP_FALLBACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reason a channel or role was rejected, shown to the admin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    ChannelInaccessible,
+    NotGuildChannel,
+    GuildInaccessible,
+    ChannelPermissionsUnverified,
+    MissingSendMessages,
+    MissingEmbedLinks,
+    EveryoneRole,
+    RoleNotInGuild,
+    GuildPermissionsUnverified,
+    RoleNotMentionable,
+}
+
+impl ValidationError {
+    /// Locale key of the message, translated by the handler
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::ChannelInaccessible => "errors.validation.channel_inaccessible",
+            Self::NotGuildChannel => "errors.validation.not_guild_channel",
+            Self::GuildInaccessible => "errors.validation.guild_inaccessible",
+            Self::ChannelPermissionsUnverified => {
+                "errors.validation.channel_permissions_unverified"
+            }
+            Self::MissingSendMessages => "errors.validation.missing_send_messages",
+            Self::MissingEmbedLinks => "errors.validation.missing_embed_links",
+            Self::EveryoneRole => "errors.validation.everyone_role",
+            Self::RoleNotInGuild => "errors.validation.role_not_in_guild",
+            Self::GuildPermissionsUnverified => "errors.validation.guild_permissions_unverified",
+            Self::RoleNotMentionable => "errors.validation.role_not_mentionable",
+        }
+    }
+}
+
 // =============================================================================
 // Channel Validation
 // =============================================================================
 
+/// Advisory findings for a channel that passed validation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChannelAdvice {
+    /// Regular members (@everyone) can send messages, so alerts may get
+    /// buried in chat
+    pub everyone_can_send: bool,
+}
+
 /// Validate bot has required permissions in the target channel
+///
+/// On success, also reports whether @everyone can post there; that is only
+/// advice for the admin and never blocks setup.
 pub async fn validate_channel_permissions(
     ctx: &Context,
     channel_id: ChannelId,
-) -> Result<(), String> {
+) -> Result<ChannelAdvice, ValidationError> {
     // Get channel
     let channel = channel_id
         .to_channel(&ctx.http)
         .await
-        .map_err(|_| "Could not access that channel. Please check it exists and I can see it.")?;
+        .map_err(|_| ValidationError::ChannelInaccessible)?;
 
-    let guild_channel = channel
-        .guild()
-        .ok_or("That doesn't appear to be a server channel.")?;
+    let guild_channel = channel.guild().ok_or(ValidationError::NotGuildChannel)?;
 
     // Get bot's permissions in the channel
     let bot_id = ctx.cache.current_user().id;
-    let permissions = get_channel_permissions(ctx, &guild_channel, bot_id).await?;
+    let guild = fetch_guild_roles(ctx, guild_channel.guild_id)
+        .await
+        .map_err(|_| ValidationError::GuildInaccessible)?;
+    let permissions = get_channel_permissions(ctx, &guild, &guild_channel, bot_id).await?;
 
     // Check required permissions
     if !permissions.send_messages() {
-        return Err(
-            "I don't have permission to send messages in that channel. Please give me the **Send Messages** permission."
-                .to_string(),
-        );
+        return Err(ValidationError::MissingSendMessages);
     }
 
     if !permissions.embed_links() {
-        return Err(
-            "I don't have permission to send embeds in that channel. Please give me the **Embed Links** permission."
-                .to_string(),
-        );
+        return Err(ValidationError::MissingEmbedLinks);
     }
 
-    Ok(())
+    let everyone = everyone_permissions(
+        guild_channel.guild_id,
+        &guild.roles,
+        &guild_channel.permission_overwrites,
+    );
+    Ok(ChannelAdvice {
+        everyone_can_send: everyone.view_channel() && everyone.send_messages(),
+    })
 }
 
-/// Get bot's permissions in a channel
+/// Get a user's effective permissions in a channel
+///
+/// Uses cached member data when available, otherwise falls back to HTTP.
 async fn get_channel_permissions(
     ctx: &Context,
+    guild: &GuildRoles,
     channel: &GuildChannel,
     user_id: UserId,
-) -> Result<Permissions, String> {
+) -> Result<Permissions, ValidationError> {
     let guild_id = channel.guild_id;
 
-    // Try to get from cache first
-    if let Some(guild) = ctx.cache.guild(guild_id)
-        && let Some(member) = guild.members.get(&user_id)
-    {
-        return Ok(guild.user_permissions_in(channel, member));
+    let member_roles = fetch_member_roles(ctx, guild_id, user_id)
+        .await
+        .map_err(|_| ValidationError::ChannelPermissionsUnverified)?;
+
+    Ok(compute_permissions(
+        guild_id,
+        user_id,
+        &member_roles,
+        &guild.roles,
+        &channel.permission_overwrites,
+        guild.owner_id,
+    ))
+}
+
+// =============================================================================
+// Role Validation
+// =============================================================================
+
+/// Validate that alerts can ping `role` in a guild
+///
+/// The role must belong to the guild and not be @everyone, and it must be
+/// mentionable unless the bot has Mention Everyone (which can ping any role).
+pub async fn validate_mention_role(
+    ctx: &Context,
+    guild_id: GuildId,
+    role: &Role,
+) -> Result<(), ValidationError> {
+    // @everyone role shares the guild's ID
+    if role.id.get() == guild_id.get() {
+        return Err(ValidationError::EveryoneRole);
     }
 
-    // Fallback: fetch member
-    let member = guild_id
-        .member(&ctx.http, user_id)
+    let guild = fetch_guild_roles(ctx, guild_id)
         .await
-        .map_err(|_| "Could not verify my permissions in that channel.")?;
+        .map_err(|_| ValidationError::GuildInaccessible)?;
+    if !guild.roles.contains_key(&role.id) {
+        return Err(ValidationError::RoleNotInGuild);
+    }
 
-    let guild = ctx
-        .cache
-        .guild(guild_id)
-        .ok_or("Could not access guild information.")?;
+    if role.mentionable {
+        return Ok(());
+    }
 
-    Ok(guild.user_permissions_in(channel, &member))
+    let bot_id = ctx.cache.current_user().id;
+    let member_roles = fetch_member_roles(ctx, guild_id, bot_id)
+        .await
+        .map_err(|_| ValidationError::GuildPermissionsUnverified)?;
+    let permissions = compute_permissions(
+        guild_id,
+        bot_id,
+        &member_roles,
+        &guild.roles,
+        &[],
+        guild.owner_id,
+    );
+    if permissions.mention_everyone() {
+        return Ok(());
+    }
+
+    Err(ValidationError::RoleNotMentionable)
 }
 
 // =============================================================================
@@ -88,39 +190,451 @@ pub enum AdminCheckResult {
 }
 
 /// Validate that a user has ADMINISTRATOR permission in a guild
+///
+/// Works with or without a populated cache: missing guild or member data is
+/// fetched over HTTP (bounded by a timeout).
 pub async fn validate_guild_admin(
     ctx: &Context,
     guild_id: GuildId,
     user_id: UserId,
 ) -> AdminCheckResult {
-    // Try cache first
+    let guild = match fetch_guild_roles(ctx, guild_id).await {
+        Ok(guild) => guild,
+        Err(e) => {
+            error!(guild_id = %guild_id, error = %e, "Failed to fetch guild roles for admin check");
+            return AdminCheckResult::CouldNotVerify(e);
+        }
+    };
+
+    let member_roles = match fetch_member_roles(ctx, guild_id, user_id).await {
+        Ok(roles) => roles,
+        Err(e) => {
+            error!(guild_id = %guild_id, user_id = %user_id, error = %e, "Failed to fetch member for admin check");
+            return AdminCheckResult::CouldNotVerify(e);
+        }
+    };
+
+    let perms = compute_permissions(
+        guild_id,
+        user_id,
+        &member_roles,
+        &guild.roles,
+        &[],
+        guild.owner_id,
+    );
+
+    if perms.administrator() {
+        AdminCheckResult::IsAdmin
+    } else {
+        AdminCheckResult::NotAdmin
+    }
+}
+
+// =============================================================================
+// Guild Data (cache-optional)
+// =============================================================================
+
+/// Guild data needed to compute permissions
+struct GuildRoles {
+    owner_id: UserId,
+    /// Base permissions of every role in the guild (includes @everyone)
+    roles: HashMap<RoleId, Permissions>,
+}
+
+/// Get guild owner and role permissions from cache, falling back to HTTP
+async fn fetch_guild_roles(ctx: &Context, guild_id: GuildId) -> Result<GuildRoles, String> {
+    if let Some(guild) = ctx.cache.guild(guild_id) {
+        return Ok(GuildRoles {
+            owner_id: guild.owner_id,
+            roles: role_permissions(&guild.roles),
+        });
+    }
+
+    let guild = timeout(HTTP_FALLBACK_TIMEOUT, guild_id.to_partial_guild(&ctx.http))
+        .await
+        .map_err(|_| "Timed out fetching guild".to_string())?
+        .map_err(|e| format!("API error: {}", e))?;
+
+    Ok(GuildRoles {
+        owner_id: guild.owner_id,
+        roles: role_permissions(&guild.roles),
+    })
+}
+
+/// Get a member's role IDs from cache, falling back to HTTP
+async fn fetch_member_roles(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Vec<RoleId>, String> {
     if let Some(guild) = ctx.cache.guild(guild_id)
         && let Some(member) = guild.members.get(&user_id)
     {
-        let perms = guild.member_permissions(member);
-        return if perms.administrator() {
-            AdminCheckResult::IsAdmin
-        } else {
-            AdminCheckResult::NotAdmin
-        };
-    }
-
-    // Fallback: fetch member and check permissions
-    match guild_id.member(&ctx.http, user_id).await {
-        Ok(member) => {
-            if let Some(guild) = ctx.cache.guild(guild_id) {
-                let perms = guild.member_permissions(&member);
-                return if perms.administrator() {
-                    AdminCheckResult::IsAdmin
-                } else {
-                    AdminCheckResult::NotAdmin
-                };
-            }
-            AdminCheckResult::CouldNotVerify("Guild not in cache after member fetch".to_string())
+        return Ok(member.roles.clone());
+    }
+
+    let member = timeout(HTTP_FALLBACK_TIMEOUT, guild_id.member(&ctx.http, user_id))
+        .await
+        .map_err(|_| "Timed out fetching member".to_string())?
+        .map_err(|e| format!("API error: {}", e))?;
+
+    Ok(member.roles)
+}
+
+fn role_permissions(roles: &HashMap<RoleId, Role>) -> HashMap<RoleId, Permissions> {
+    roles
+        .iter()
+        .map(|(id, role)| (*id, role.permissions))
+        .collect()
+}
+
+// =============================================================================
+// Permission Computation
+// =============================================================================
+
+/// Compute a member's effective permissions
+///
+/// Follows Discord's resolution order:
+/// 1. Guild owner has all permissions
+/// 2. Base = @everyone role | member's roles; ADMINISTRATOR grants all
+/// 3. Channel overwrites: @everyone, then member's roles (combined), then member
+///
+/// Pass empty `overwrites` to compute guild-level permissions.
+pub fn compute_permissions(
+    guild_id: GuildId,
+    user_id: UserId,
+    member_roles: &[RoleId],
+    guild_roles: &HashMap<RoleId, Permissions>,
+    overwrites: &[PermissionOverwrite],
+    owner_id: UserId,
+) -> Permissions {
+    if user_id == owner_id {
+        return Permissions::all();
+    }
+
+    // @everyone role shares the guild's ID
+    let everyone_role = RoleId::new(guild_id.get());
+
+    let mut perms = guild_roles
+        .get(&everyone_role)
+        .copied()
+        .unwrap_or_else(Permissions::empty);
+    for role_id in member_roles {
+        if let Some(role_perms) = guild_roles.get(role_id) {
+            perms |= *role_perms;
         }
-        Err(e) => {
-            error!(guild_id = %guild_id, user_id = %user_id, error = %e, "Failed to fetch member for admin check");
-            AdminCheckResult::CouldNotVerify(format!("API error: {}", e))
+    }
+
+    if perms.administrator() {
+        return Permissions::all();
+    }
+
+    // @everyone overwrite
+    if let Some(overwrite) = overwrites
+        .iter()
+        .find(|o| o.kind == PermissionOverwriteType::Role(everyone_role))
+    {
+        perms &= !overwrite.deny;
+        perms |= overwrite.allow;
+    }
+
+    // Role overwrites (denies and allows combined across all member roles)
+    let mut role_allow = Permissions::empty();
+    let mut role_deny = Permissions::empty();
+    for overwrite in overwrites {
+        if let PermissionOverwriteType::Role(role_id) = overwrite.kind
+            && role_id != everyone_role
+            && member_roles.contains(&role_id)
+        {
+            role_allow |= overwrite.allow;
+            role_deny |= overwrite.deny;
         }
     }
+    perms &= !role_deny;
+    perms |= role_allow;
+
+    // Member overwrite
+    if let Some(overwrite) = overwrites
+        .iter()
+        .find(|o| o.kind == PermissionOverwriteType::Member(user_id))
+    {
+        perms &= !overwrite.deny;
+        perms |= overwrite.allow;
+    }
+
+    perms
+}
+
+/// Permissions of a member with no roles (@everyone) in a channel
+///
+/// The @everyone role's base permissions with the channel's @everyone
+/// overwrite applied. Pass empty `overwrites` for guild-level permissions.
+pub fn everyone_permissions(
+    guild_id: GuildId,
+    guild_roles: &HashMap<RoleId, Permissions>,
+    overwrites: &[PermissionOverwrite],
+) -> Permissions {
+    // @everyone role shares the guild's ID
+    let everyone_role = RoleId::new(guild_id.get());
+
+    let mut perms = guild_roles
+        .get(&everyone_role)
+        .copied()
+        .unwrap_or_else(Permissions::empty);
+    if perms.administrator() {
+        return Permissions::all();
+    }
+
+    if let Some(overwrite) = overwrites
+        .iter()
+        .find(|o| o.kind == PermissionOverwriteType::Role(everyone_role))
+    {
+        perms &= !overwrite.deny;
+        perms |= overwrite.allow;
+    }
+    perms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const EVERYONE: RoleId = RoleId::new(1);
+    const MODS: RoleId = RoleId::new(10);
+    const ADMINS: RoleId = RoleId::new(11);
+    const MUTED: RoleId = RoleId::new(12);
+    const OWNER: UserId = UserId::new(100);
+    const USER: UserId = UserId::new(200);
+
+    fn roles() -> HashMap<RoleId, Permissions> {
+        HashMap::from([
+            (
+                EVERYONE,
+                Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            ),
+            (MODS, Permissions::MANAGE_GUILD | Permissions::EMBED_LINKS),
+            (ADMINS, Permissions::ADMINISTRATOR),
+            (MUTED, Permissions::empty()),
+        ])
+    }
+
+    fn overwrite(
+        kind: PermissionOverwriteType,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> PermissionOverwrite {
+        PermissionOverwrite { allow, deny, kind }
+    }
+
+    #[test]
+    fn test_compute_permissions() {
+        let none = Permissions::empty();
+        let base = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+        let cases = [
+            (
+                "owner without roles",
+                OWNER,
+                vec![],
+                vec![],
+                Permissions::all(),
+            ),
+            (
+                "owner ignores channel deny",
+                OWNER,
+                vec![],
+                vec![overwrite(
+                    PermissionOverwriteType::Member(OWNER),
+                    none,
+                    Permissions::all(),
+                )],
+                Permissions::all(),
+            ),
+            ("everyone only", USER, vec![], vec![], base),
+            (
+                "roles are combined",
+                USER,
+                vec![MODS, MUTED],
+                vec![],
+                base | Permissions::MANAGE_GUILD | Permissions::EMBED_LINKS,
+            ),
+            (
+                "administrator role",
+                USER,
+                vec![ADMINS],
+                vec![],
+                Permissions::all(),
+            ),
+            (
+                "administrator ignores channel deny",
+                USER,
+                vec![ADMINS],
+                vec![overwrite(
+                    PermissionOverwriteType::Role(EVERYONE),
+                    none,
+                    Permissions::all(),
+                )],
+                Permissions::all(),
+            ),
+            (
+                "everyone overwrite denies",
+                USER,
+                vec![],
+                vec![overwrite(
+                    PermissionOverwriteType::Role(EVERYONE),
+                    none,
+                    Permissions::SEND_MESSAGES,
+                )],
+                Permissions::VIEW_CHANNEL,
+            ),
+            (
+                "role overwrite allows over everyone deny",
+                USER,
+                vec![MODS],
+                vec![
+                    overwrite(
+                        PermissionOverwriteType::Role(EVERYONE),
+                        none,
+                        Permissions::SEND_MESSAGES,
+                    ),
+                    overwrite(
+                        PermissionOverwriteType::Role(MODS),
+                        Permissions::SEND_MESSAGES,
+                        none,
+                    ),
+                ],
+                base | Permissions::MANAGE_GUILD | Permissions::EMBED_LINKS,
+            ),
+            (
+                "channel role deny overrides role allow",
+                USER,
+                vec![MODS],
+                vec![overwrite(
+                    PermissionOverwriteType::Role(MODS),
+                    none,
+                    Permissions::EMBED_LINKS,
+                )],
+                base | Permissions::MANAGE_GUILD,
+            ),
+            (
+                "role allow wins over another role's deny",
+                USER,
+                vec![MODS, MUTED],
+                vec![
+                    overwrite(
+                        PermissionOverwriteType::Role(MUTED),
+                        none,
+                        Permissions::SEND_MESSAGES,
+                    ),
+                    overwrite(
+                        PermissionOverwriteType::Role(MODS),
+                        Permissions::SEND_MESSAGES,
+                        none,
+                    ),
+                ],
+                base | Permissions::MANAGE_GUILD | Permissions::EMBED_LINKS,
+            ),
+            (
+                "overwrites for roles the member lacks are ignored",
+                USER,
+                vec![],
+                vec![overwrite(
+                    PermissionOverwriteType::Role(MUTED),
+                    none,
+                    Permissions::VIEW_CHANNEL,
+                )],
+                base,
+            ),
+            (
+                "member overwrite applies last",
+                USER,
+                vec![MODS],
+                vec![
+                    overwrite(
+                        PermissionOverwriteType::Role(MODS),
+                        Permissions::ATTACH_FILES,
+                        none,
+                    ),
+                    overwrite(
+                        PermissionOverwriteType::Member(USER),
+                        none,
+                        Permissions::ATTACH_FILES | Permissions::SEND_MESSAGES,
+                    ),
+                ],
+                Permissions::VIEW_CHANNEL | Permissions::MANAGE_GUILD | Permissions::EMBED_LINKS,
+            ),
+            (
+                "other member's overwrite is ignored",
+                USER,
+                vec![],
+                vec![overwrite(
+                    PermissionOverwriteType::Member(OWNER),
+                    none,
+                    Permissions::VIEW_CHANNEL,
+                )],
+                base,
+            ),
+        ];
+
+        let guild_roles = roles();
+        for (name, user, member_roles, overwrites, expected) in cases {
+            let perms =
+                compute_permissions(GUILD, user, &member_roles, &guild_roles, &overwrites, OWNER);
+            assert_eq!(perms, expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_compute_permissions_missing_everyone_role() {
+        let guild_roles = HashMap::from([(MODS, Permissions::EMBED_LINKS)]);
+
+        let perms = compute_permissions(GUILD, USER, &[MODS], &guild_roles, &[], OWNER);
+
+        assert_eq!(perms, Permissions::EMBED_LINKS);
+    }
+
+    #[test]
+    fn test_validation_errors_translated_in_every_locale() {
+        let keys = [
+            ValidationError::ChannelInaccessible,
+            ValidationError::NotGuildChannel,
+            ValidationError::GuildInaccessible,
+            ValidationError::ChannelPermissionsUnverified,
+            ValidationError::MissingSendMessages,
+            ValidationError::MissingEmbedLinks,
+            ValidationError::EveryoneRole,
+            ValidationError::RoleNotInGuild,
+            ValidationError::GuildPermissionsUnverified,
+            ValidationError::RoleNotMentionable,
+        ]
+        .map(ValidationError::key);
+
+        assert_eq!(crate::i18n::untranslated(&keys), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_everyone_permissions() {
+        let guild_roles = roles();
+        let overwrites = [
+            overwrite(
+                PermissionOverwriteType::Role(EVERYONE),
+                Permissions::EMBED_LINKS,
+                Permissions::SEND_MESSAGES,
+            ),
+            overwrite(
+                PermissionOverwriteType::Role(MODS),
+                Permissions::SEND_MESSAGES,
+                Permissions::empty(),
+            ),
+        ];
+
+        assert_eq!(
+            everyone_permissions(GUILD, &guild_roles, &[]),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+        );
+        assert_eq!(
+            everyone_permissions(GUILD, &guild_roles, &overwrites),
+            Permissions::VIEW_CHANNEL | Permissions::EMBED_LINKS
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use rust_i18n::t;
 use serenity::all::{CommandInteraction, Context, CreateCommand};
 
-use crate::commands::shared::defer;
+use crate::commands::registry::InlineReply;
 use crate::i18n::resolve_locale_async;
 
 /// /hello command definition
@@ -12,19 +12,16 @@ pub fn register() -> CreateCommand {
         .description_localized("ko", t!("commands.hello.description", locale = "ko"))
 }
 
-/// /hello command handler
-pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
-    // Defer first to acknowledge within 3 seconds, then do DB lookup for locale
-    defer::defer(ctx, interaction).await?;
-
+/// /hello command handler (inline)
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<Option<InlineReply>, serenity::Error> {
     let locale = resolve_locale_async(ctx, interaction).await;
 
-    let response = serenity::builder::EditInteractionResponse::new().content(t!(
+    Ok(Some(InlineReply::content(t!(
         "embeds.hello.message",
         locale = &locale,
         user = &interaction.user.name
-    ));
-
-    interaction.edit_response(&ctx.http, response).await?;
-    Ok(())
+    ))))
 }
@@ -0,0 +1,307 @@
+//! /incidents - currently active VRChat incidents
+//!
+//! Lists unresolved incidents recorded by the collector, newest first, with
+//! their impact, status, start time, and latest update. More than
+//! [`PAGE_SIZE`] incidents are split into pages with Previous/Next buttons.
+//! Resolved incidents are under `/status history`.
+
+use std::collections::HashMap;
+
+use rust_i18n::t;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use serenity::all::{
+    ButtonStyle, Colour, CommandInteraction, ComponentInteraction, Context, CreateActionRow,
+    CreateButton, CreateCommand, CreateEmbed, CreateEmbedFooter, Timestamp,
+};
+use tracing::error;
+
+use crate::commands::shared::components::label;
+use crate::commands::shared::{
+    button_id_with_context, colors, defer, defer_component_update, edit_component_embed,
+    edit_component_embed_components, embeds, is_button, links, parse_button_context, text,
+};
+use crate::commands::status::history::{impact_style, update_chars};
+use crate::database;
+use crate::entity::{incident_updates, incidents};
+use crate::i18n::{resolve_locale_async, resolve_locale_component};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Incidents listed per page
+const PAGE_SIZE: u64 = 5;
+
+/// Longest incident title shown
+const TITLE_CHARS: usize = 100;
+
+/// Module name for /incidents buttons
+const MODULE: &str = "incidents";
+
+/// Action name for the page buttons
+const ACTION_PAGE: &str = "page";
+
+/// Button context type carrying the target page
+const CONTEXT_PAGE: &str = "page";
+
+/// One page of active incidents with their latest updates
+struct IncidentPage {
+    incidents: Vec<(incidents::Model, Option<incident_updates::Model>)>,
+    /// 0-based page shown, clamped to the last page
+    page: u64,
+    pages: u64,
+    total: u64,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// /incidents command definition
+pub fn register() -> CreateCommand {
+    CreateCommand::new("incidents")
+        .description(t!("commands.incidents.description"))
+        .name_localized("ko", t!("commands.incidents.name", locale = "ko"))
+        .description_localized("ko", t!("commands.incidents.description", locale = "ko"))
+}
+
+/// Handle /incidents (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+    let db = database::get_db(ctx).await;
+
+    match load_active(&db, 0).await {
+        Ok(page) => {
+            let (embed, buttons) = render_page(&page, &locale);
+            defer::edit_embed_components(ctx, interaction, embed, buttons).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to load active incidents");
+            defer::edit_embed(ctx, interaction, error_embed(&locale)).await
+        }
+    }
+}
+
+/// Check if button ID matches an /incidents page button
+pub fn is_page_button(custom_id: &str) -> bool {
+    is_button(custom_id, MODULE, ACTION_PAGE)
+}
+
+/// Handle a Previous/Next click by re-rendering the requested page
+pub async fn handle_page_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), serenity::Error> {
+    defer_component_update(ctx, interaction).await?;
+
+    let locale = resolve_locale_component(ctx, interaction).await;
+    let page = parse_button_context(&interaction.data.custom_id)
+        .filter(|(context_type, _)| *context_type == CONTEXT_PAGE)
+        .and_then(|(_, page)| page.parse::<u64>().ok())
+        .unwrap_or(0);
+    let db = database::get_db(ctx).await;
+
+    match load_active(&db, page).await {
+        Ok(page) => {
+            let (embed, buttons) = render_page(&page, &locale);
+            edit_component_embed_components(ctx, interaction, embed, buttons).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to load active incidents");
+            edit_component_embed(ctx, interaction, error_embed(&locale)).await
+        }
+    }
+}
+
+fn error_embed(locale: &str) -> CreateEmbed {
+    embeds::localized_error_embed(
+        t!("embeds.incidents.error_description", locale = locale),
+        locale,
+    )
+}
+
+// =============================================================================
+// Data
+// =============================================================================
+
+/// Unresolved incidents on 0-based `page`, newest first, each with its
+/// latest update
+///
+/// A page past the end shows the last page.
+async fn load_active(db: &DatabaseConnection, page: u64) -> Result<IncidentPage, DbErr> {
+    let query = incidents::Entity::find()
+        .filter(incidents::Column::ResolvedAt.is_null())
+        .order_by_desc(incidents::Column::StartedAt);
+
+    let total = query.clone().count(db).await?;
+    let pages = total.div_ceil(PAGE_SIZE).max(1);
+    let page = page.min(pages - 1);
+    let incidents = query
+        .offset(page * PAGE_SIZE)
+        .limit(PAGE_SIZE)
+        .all(db)
+        .await?;
+    if incidents.is_empty() {
+        return Ok(IncidentPage {
+            incidents: Vec::new(),
+            page,
+            pages,
+            total,
+        });
+    }
+
+    let ids: Vec<&str> = incidents
+        .iter()
+        .map(|incident| incident.id.as_str())
+        .collect();
+    let updates = incident_updates::Entity::find()
+        .filter(incident_updates::Column::IncidentId.is_in(ids))
+        .order_by_desc(incident_updates::Column::PublishedAt)
+        .all(db)
+        .await?;
+
+    // Newest first, so the first update seen per incident is the latest
+    let mut latest: HashMap<String, incident_updates::Model> = HashMap::new();
+    for update in updates {
+        latest.entry(update.incident_id.clone()).or_insert(update);
+    }
+
+    Ok(IncidentPage {
+        incidents: incidents
+            .into_iter()
+            .map(|incident| {
+                let update = latest.remove(&incident.id);
+                (incident, update)
+            })
+            .collect(),
+        page,
+        pages,
+        total,
+    })
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+/// Embed and page buttons, colored by the most severe impact on the page
+fn render_page(page: &IncidentPage, locale: &str) -> (CreateEmbed, Vec<CreateActionRow>) {
+    if page.total == 0 {
+        let embed = embeds::success_embed(
+            t!("embeds.incidents.title", locale = locale),
+            t!("embeds.incidents.none", locale = locale),
+        );
+        return (embed, Vec::new());
+    }
+
+    let color = page
+        .incidents
+        .iter()
+        .max_by_key(|(incident, _)| impact_rank(&incident.impact))
+        .map_or(colors::BRAND, |(incident, _)| {
+            impact_style(&incident.impact).1
+        });
+    let body_chars = update_chars(page.incidents.len());
+
+    let embed = CreateEmbed::default()
+        .title(t!("embeds.incidents.title", locale = locale))
+        .description(t!(
+            "embeds.incidents.description",
+            count = page.total,
+            locale = locale
+        ))
+        .color(Colour::new(color))
+        .timestamp(Timestamp::now());
+    let embed = page
+        .incidents
+        .iter()
+        .fold(embed, |embed, (incident, update)| {
+            let (emoji, _) = impact_style(&incident.impact);
+            embed.field(
+                format!("{} {}", emoji, text::truncate(&incident.title, TITLE_CHARS)),
+                incident_value(incident, update.as_ref(), body_chars, locale),
+                false,
+            )
+        });
+
+    if page.pages == 1 {
+        let embed = embed.footer(CreateEmbedFooter::new(t!(
+            "embeds.history.footer",
+            locale = locale
+        )));
+        return (embed, Vec::new());
+    }
+
+    let embed = embed.footer(CreateEmbedFooter::new(t!(
+        "embeds.incidents.footer_page",
+        page = page.page + 1,
+        total = page.pages,
+        locale = locale
+    )));
+    let buttons = vec![
+        CreateButton::new(page_button_id(page.page.saturating_sub(1)))
+            .label(t!("embeds.incidents.button_previous", locale = locale))
+            .style(ButtonStyle::Secondary)
+            .disabled(page.page == 0),
+        CreateButton::new(page_button_id((page.page + 1).min(page.pages - 1)))
+            .label(t!("embeds.incidents.button_next", locale = locale))
+            .style(ButtonStyle::Secondary)
+            .disabled(page.page + 1 == page.pages),
+    ];
+    (embed, vec![CreateActionRow::Buttons(buttons)])
+}
+
+/// Generate button ID for a page of /incidents
+fn page_button_id(page: u64) -> String {
+    button_id_with_context(MODULE, ACTION_PAGE, CONTEXT_PAGE, page)
+}
+
+/// Rank of an impact level, so the embed takes the worst one
+fn impact_rank(impact: &str) -> u8 {
+    match impact {
+        "critical" => 3,
+        "major" => 2,
+        "minor" => 1,
+        _ => 0,
+    }
+}
+
+/// Impact, status, start, and latest update lines for one incident
+fn incident_value(
+    incident: &incidents::Model,
+    update: Option<&incident_updates::Model>,
+    body_chars: usize,
+    locale: &str,
+) -> String {
+    let impact = t!(
+        "embeds.incident.impact",
+        impact = label("embeds.incident.impact_level", &incident.impact, locale),
+        locale = locale
+    );
+    let status = t!(
+        "embeds.incidents.status",
+        status = label("embeds.incident.update_status", &incident.status, locale),
+        locale = locale
+    );
+    let started = t!(
+        "embeds.incidents.started",
+        time = format!("<t:{}:f>", incident.started_at.timestamp()),
+        locale = locale
+    );
+    let latest = match update {
+        Some(update) => format!(
+            "> {}",
+            text::truncate(&update.body.replace('\n', " "), body_chars)
+        ),
+        None => t!("embeds.history.no_updates", locale = locale).to_string(),
+    };
+    let link = t!(
+        "embeds.history.view_on_status_page",
+        url = links::incident_url(&incident.id),
+        locale = locale
+    );
+    format!("{}\n{}\n{}\n{}\n{}", impact, status, started, latest, link)
+}
@@ -0,0 +1,142 @@
+//! /maintenance - scheduled and in-progress VRChat maintenance windows
+//!
+//! Reads the `maintenances` table kept up to date by the collector
+//! (`collector::maintenance`). Unrelated to the bot's own maintenance mode
+//! (`/admin maintenance`).
+
+use rust_i18n::t;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serenity::all::{
+    Colour, CommandInteraction, Context, CreateCommand, CreateEmbed, CreateEmbedFooter, Timestamp,
+};
+use tracing::error;
+
+use crate::commands::registry::InlineReply;
+use crate::commands::shared::{colors, embeds, text};
+use crate::entity::maintenances;
+use crate::i18n::resolve_locale_async;
+use crate::state::AppStateKey;
+
+/// Maintenance statuses shown (completed windows are left out)
+const UPCOMING_STATUSES: &[&str] = &["scheduled", "in_progress"];
+
+/// Most windows listed (Discord allows 25 embed fields)
+const MAX_WINDOWS: u64 = 25;
+
+/// Longest maintenance title shown
+const TITLE_CHARS: usize = 200;
+
+/// /maintenance command definition
+pub fn register() -> CreateCommand {
+    CreateCommand::new("maintenance")
+        .description(t!("commands.maintenance.description"))
+        .name_localized("ko", t!("commands.maintenance.name", locale = "ko"))
+        .description_localized("ko", t!("commands.maintenance.description", locale = "ko"))
+}
+
+/// /maintenance command handler (inline)
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<Option<InlineReply>, serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    let db = {
+        let data = ctx.data.read().await;
+        let state = data
+            .get::<AppStateKey>()
+            .expect("AppState not found in TypeMap")
+            .read()
+            .await;
+        state.database.clone()
+    };
+
+    match load_upcoming(&db).await {
+        Ok(windows) if windows.is_empty() => Ok(Some(InlineReply::embed(embeds::info_embed(
+            t!("embeds.maintenance.title", locale = &locale),
+            t!("embeds.maintenance.none", locale = &locale),
+        )))),
+        Ok(windows) => Ok(Some(InlineReply::embed(maintenance_embed(
+            &windows, &locale,
+        )))),
+        Err(e) => {
+            error!(error = %e, "Failed to load maintenance windows");
+            Ok(Some(InlineReply::error(
+                &t!("embeds.maintenance.error_description", locale = &locale),
+                &locale,
+            )))
+        }
+    }
+}
+
+/// Scheduled and in-progress windows, earliest start first
+async fn load_upcoming(
+    db: &DatabaseConnection,
+) -> Result<Vec<maintenances::Model>, sea_orm::DbErr> {
+    maintenances::Entity::find()
+        .filter(maintenances::Column::Status.is_in(UPCOMING_STATUSES.iter().copied()))
+        .order_by_asc(maintenances::Column::ScheduledFor)
+        .limit(MAX_WINDOWS)
+        .all(db)
+        .await
+}
+
+/// One field per window; orange while a window is in progress
+fn maintenance_embed(windows: &[maintenances::Model], locale: &str) -> CreateEmbed {
+    let color = if windows.iter().any(|m| m.status == "in_progress") {
+        colors::MAJOR
+    } else {
+        colors::BRAND
+    };
+
+    let embed = CreateEmbed::default()
+        .title(t!("embeds.maintenance.title", locale = locale))
+        .color(Colour::new(color));
+
+    windows
+        .iter()
+        .fold(embed, |embed, window| {
+            embed.field(
+                text::truncate(&window.title, TITLE_CHARS),
+                window_value(window, locale),
+                false,
+            )
+        })
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.maintenance.footer",
+            locale = locale
+        )))
+        .timestamp(Timestamp::now())
+}
+
+/// Status, start, end, and duration lines for one window
+fn window_value(window: &maintenances::Model, locale: &str) -> String {
+    let status = match window.status.as_str() {
+        "in_progress" => t!("embeds.maintenance.status_in_progress", locale = locale),
+        _ => t!("embeds.maintenance.status_scheduled", locale = locale),
+    };
+    format!(
+        "{}\n{}\n{}\n{}",
+        status,
+        t!(
+            "embeds.maintenance.starts",
+            time = discord_time(window.scheduled_for.timestamp()),
+            locale = locale
+        ),
+        t!(
+            "embeds.maintenance.ends",
+            time = discord_time(window.scheduled_until.timestamp()),
+            locale = locale
+        ),
+        t!(
+            "embeds.maintenance.duration",
+            duration = text::format_duration(window.scheduled_until - window.scheduled_for, locale),
+            locale = locale
+        ),
+    )
+}
+
+/// Absolute and relative Discord timestamps, e.g. "<t:..:f> (<t:..:R>)"
+fn discord_time(unix: i64) -> String {
+    format!("<t:{unix}:f> (<t:{unix}:R>)")
+}
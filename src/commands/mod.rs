@@ -1,19 +1,129 @@
 pub mod admin;
 pub mod config;
 pub mod hello;
+pub mod incidents;
+pub mod maintenance;
+pub mod registry;
 pub mod report;
 pub mod shared;
 pub mod status;
 
-use serenity::all::{Command, Context, CreateCommand};
+use serenity::all::{Command, CommandInteraction, Context, CreateCommand};
 use tracing::info;
 
-/// Returns all slash command definitions
+use registry::{CommandHandler, CommandSpec, Defer};
+
+/// All command declarations, in registration order
+pub static COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "hello",
+        register: hello::register,
+        handler: CommandHandler::Inline {
+            defer: Defer::Public,
+            run: |ctx, interaction| Box::pin(hello::run(ctx, interaction)),
+        },
+        autocomplete: None,
+        dev_only: false,
+    },
+    CommandSpec {
+        name: "config",
+        register: config::register,
+        handler: CommandHandler::Deferred {
+            defer: Defer::Public,
+            ephemeral_subcommands: &["unregister", "data", "webhook"],
+            run: |ctx, interaction| Box::pin(config::run(ctx, interaction)),
+        },
+        autocomplete: None,
+        dev_only: false,
+    },
+    CommandSpec {
+        name: "report",
+        register: report::register,
+        handler: CommandHandler::Deferred {
+            defer: Defer::Ephemeral,
+            ephemeral_subcommands: &[],
+            run: |ctx, interaction| Box::pin(report::run(ctx, interaction)),
+        },
+        autocomplete: None,
+        dev_only: false,
+    },
+    CommandSpec {
+        name: "status",
+        register: status::register,
+        handler: CommandHandler::Deferred {
+            defer: Defer::Public,
+            ephemeral_subcommands: &[],
+            run: |ctx, interaction| Box::pin(status::run(ctx, interaction)),
+        },
+        autocomplete: Some(|ctx, interaction| Box::pin(status::autocomplete(ctx, interaction))),
+        dev_only: false,
+    },
+    CommandSpec {
+        name: "incidents",
+        register: incidents::register,
+        handler: CommandHandler::Deferred {
+            defer: Defer::Public,
+            ephemeral_subcommands: &[],
+            run: |ctx, interaction| Box::pin(incidents::run(ctx, interaction)),
+        },
+        autocomplete: None,
+        dev_only: false,
+    },
+    CommandSpec {
+        name: "maintenance",
+        register: maintenance::register,
+        handler: CommandHandler::Inline {
+            defer: Defer::Ephemeral,
+            run: |ctx, interaction| Box::pin(maintenance::run(ctx, interaction)),
+        },
+        autocomplete: None,
+        dev_only: false,
+    },
+    CommandSpec {
+        name: "admin",
+        register: admin::config::register,
+        handler: CommandHandler::Inline {
+            defer: Defer::Ephemeral,
+            run: |ctx, interaction| Box::pin(admin::config::run(ctx, interaction)),
+        },
+        autocomplete: None,
+        dev_only: true,
+    },
+];
+
+/// Returns all global slash command definitions
 pub fn all() -> Vec<CreateCommand> {
-    let mut commands = vec![hello::register(), config::register(), report::register()];
-    // commands.extend(admin::all());
-    commands.extend(status::all());
-    commands
+    COMMANDS
+        .iter()
+        .filter(|spec| !spec.dev_only)
+        .map(|spec| (spec.register)())
+        .collect()
+}
+
+/// Route a slash command to its handler
+///
+/// Unknown commands are ignored.
+pub async fn dispatch(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), serenity::Error> {
+    match registry::find(COMMANDS, &interaction.data.name) {
+        Some(spec) => registry::dispatch(ctx, interaction, spec).await,
+        None => Ok(()),
+    }
+}
+
+/// Route an autocomplete request to its command
+///
+/// Commands without an autocomplete handler are ignored.
+pub async fn autocomplete(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), serenity::Error> {
+    match registry::find(COMMANDS, &interaction.data.name).and_then(|spec| spec.autocomplete) {
+        Some(run) => run(ctx, interaction).await,
+        None => Ok(()),
+    }
 }
 
 /// Register global slash commands
@@ -0,0 +1,572 @@
+//! Declarative command registry and dispatcher
+//!
+//! Each command declares how it is acknowledged. The dispatcher performs the
+//! defer before calling deferred handlers, so handlers can't forget it, and
+//! answers inline handlers directly unless they exceed the latency budget, in
+//! which case it defers with the visibility the inline handler declares. An
+//! inline reply sent on time uses that visibility too, so it never depends on
+//! latency.
+//! When a handler fails before anything was shown, the dispatcher answers
+//! with a generic localized error instead of leaving the user waiting.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use rust_i18n::t;
+use serenity::all::{
+    CommandInteraction, Context, CreateActionRow, CreateAttachment, CreateCommand, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
+    MessageFlags,
+};
+use tracing::{debug, warn};
+
+use crate::i18n::resolve_locale_async;
+
+use super::shared::{defer, embeds};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// How long an inline handler may run before the dispatcher defers on its behalf
+///
+/// Discord requires an initial response within 3 seconds; this leaves headroom
+/// for the defer request itself.
+pub const INLINE_BUDGET: Duration = Duration::from_secs(2);
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Boxed future returned by command handlers
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Handler that edits a response the dispatcher has already deferred
+pub type DeferredFn =
+    for<'a> fn(&'a Context, &'a CommandInteraction) -> BoxFuture<'a, Result<(), serenity::Error>>;
+
+/// Handler that returns its response payload instead of sending it
+///
+/// `None` means the command is silently ignored (no response at all).
+pub type InlineFn = for<'a> fn(
+    &'a Context,
+    &'a CommandInteraction,
+) -> BoxFuture<'a, Result<Option<InlineReply>, serenity::Error>>;
+
+/// Handler that answers an autocomplete interaction itself
+pub type AutocompleteFn = DeferredFn;
+
+/// Visibility of a deferred response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Defer {
+    Public,
+    Ephemeral,
+}
+
+/// How the dispatcher acknowledges a command
+pub enum CommandHandler {
+    /// Dispatcher defers first; `ephemeral_subcommands` override `defer` to ephemeral
+    Deferred {
+        defer: Defer,
+        ephemeral_subcommands: &'static [&'static str],
+        run: DeferredFn,
+    },
+    /// Handler answers inline; dispatcher defers with `defer` if it exceeds
+    /// `INLINE_BUDGET`
+    ///
+    /// With [`Defer::Ephemeral`], every reply is ephemeral, on time or not. A
+    /// deferred response keeps its visibility, so handlers that can return an
+    /// ephemeral reply (e.g. [`InlineReply::error`]) must declare
+    /// [`Defer::Ephemeral`].
+    Inline { defer: Defer, run: InlineFn },
+}
+
+/// Slash command declaration
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub register: fn() -> CreateCommand,
+    pub handler: CommandHandler,
+    /// Answers autocomplete requests for options with `set_autocomplete`
+    pub autocomplete: Option<AutocompleteFn>,
+    /// Only registered to the test guild (never globally)
+    pub dev_only: bool,
+}
+
+impl CommandSpec {
+    /// Defer visibility for a given subcommand
+    ///
+    /// For inline handlers this is the visibility used if they exceed
+    /// `INLINE_BUDGET`.
+    pub fn defer_for(&self, subcommand: Option<&str>) -> Defer {
+        match &self.handler {
+            CommandHandler::Deferred {
+                defer,
+                ephemeral_subcommands,
+                ..
+            } => {
+                let ephemeral =
+                    subcommand.is_some_and(|name| ephemeral_subcommands.contains(&name));
+                if ephemeral { Defer::Ephemeral } else { *defer }
+            }
+            CommandHandler::Inline { defer, .. } => *defer,
+        }
+    }
+}
+
+/// What the watchdog did while an inline handler ran
+#[derive(Debug, PartialEq, Eq)]
+enum Watched<T> {
+    /// Finished within the budget; nothing was sent yet
+    OnTime(T),
+    /// Overran the budget, so the interaction was deferred
+    Deferred(T),
+}
+
+/// How far an interaction was acknowledged when its handler failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Acknowledgment {
+    /// No response sent yet
+    Pending,
+    /// Deferred; the handler may or may not have edited the response
+    Deferred,
+}
+
+/// Response payload returned by inline handlers
+#[derive(Debug, Clone, Default)]
+pub struct InlineReply {
+    content: Option<String>,
+    embeds: Vec<CreateEmbed>,
+    /// Files referenced by embeds as `attachment://<filename>`
+    attachments: Vec<CreateAttachment>,
+    /// Buttons under the reply, handled by the command's button handler
+    components: Vec<CreateActionRow>,
+    ephemeral: bool,
+}
+
+impl InlineReply {
+    /// Plain text reply
+    pub fn content(content: impl Into<String>) -> Self {
+        Self {
+            content: Some(content.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Single embed reply
+    pub fn embed(embed: CreateEmbed) -> Self {
+        Self {
+            embeds: vec![embed],
+            ..Default::default()
+        }
+    }
+
+    /// Ephemeral error embed reply
+    pub fn error(message: &str, locale: &str) -> Self {
+        Self {
+            embeds: vec![embeds::localized_error_embed(message, locale)],
+            ephemeral: true,
+            ..Default::default()
+        }
+    }
+
+    /// Multiple embeds in one reply
+    pub fn embeds(embeds: Vec<CreateEmbed>) -> Self {
+        Self {
+            embeds,
+            ..Default::default()
+        }
+    }
+
+    /// Attach a file (e.g. a chart image) to the reply
+    pub fn attachment(mut self, attachment: CreateAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Add action rows (e.g. confirm/cancel buttons) to the reply
+    pub fn components(mut self, components: Vec<CreateActionRow>) -> Self {
+        self.components = components;
+        self
+    }
+
+    /// Initial response, ephemeral if the reply or the declared `visibility` asks for it
+    fn into_message(self, visibility: Defer) -> CreateInteractionResponseMessage {
+        let mut message = CreateInteractionResponseMessage::new()
+            .embeds(self.embeds)
+            .add_files(self.attachments)
+            .ephemeral(self.ephemeral || visibility == Defer::Ephemeral);
+        if let Some(content) = self.content {
+            message = message.content(content);
+        }
+        if !self.components.is_empty() {
+            message = message.components(self.components);
+        }
+        message
+    }
+
+    fn into_edit(self) -> EditInteractionResponse {
+        let mut edit = EditInteractionResponse::new().embeds(self.embeds);
+        for attachment in self.attachments {
+            edit = edit.new_attachment(attachment);
+        }
+        if let Some(content) = self.content {
+            edit = edit.content(content);
+        }
+        if !self.components.is_empty() {
+            edit = edit.components(self.components);
+        }
+        edit
+    }
+}
+
+// =============================================================================
+// Dispatch
+// =============================================================================
+
+/// Find a command spec by name
+pub fn find<'a>(specs: &'a [CommandSpec], name: &str) -> Option<&'a CommandSpec> {
+    specs.iter().find(|spec| spec.name == name)
+}
+
+/// Acknowledge and run a command according to its spec
+pub async fn dispatch(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    spec: &CommandSpec,
+) -> Result<(), serenity::Error> {
+    let subcommand = interaction.data.options.first().map(|o| o.name.as_str());
+    let visibility = spec.defer_for(subcommand);
+    match &spec.handler {
+        CommandHandler::Deferred { run, .. } => {
+            defer_with(ctx, interaction, visibility).await?;
+            let result = run(ctx, interaction).await;
+            if result.is_err() {
+                send_fallback_error(ctx, interaction, Acknowledgment::Deferred).await;
+            }
+            result
+        }
+        CommandHandler::Inline { run, .. } => {
+            run_inline(ctx, interaction, spec.name, visibility, run).await
+        }
+    }
+}
+
+/// Defer an interaction with the given visibility
+async fn defer_with(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    visibility: Defer,
+) -> Result<(), serenity::Error> {
+    match visibility {
+        Defer::Public => defer::defer(ctx, interaction).await,
+        Defer::Ephemeral => defer::defer_ephemeral(ctx, interaction).await,
+    }
+}
+
+/// Run an inline handler under the latency budget
+///
+/// If the handler is still running after `INLINE_BUDGET`, defer with
+/// `visibility` and deliver its reply as an edit once it completes.
+async fn run_inline(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    name: &str,
+    visibility: Defer,
+    run: &InlineFn,
+) -> Result<(), serenity::Error> {
+    let overrun = async {
+        warn!(
+            command = name,
+            budget_ms = INLINE_BUDGET.as_millis() as u64,
+            ?visibility,
+            "Inline handler exceeded latency budget, deferring"
+        );
+        defer_with(ctx, interaction, visibility).await
+    };
+
+    match watch(run(ctx, interaction), INLINE_BUDGET, overrun).await? {
+        Watched::OnTime(Ok(Some(reply))) => {
+            interaction
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(reply.into_message(visibility)),
+                )
+                .await
+        }
+        Watched::OnTime(Ok(None)) => Ok(()),
+        Watched::OnTime(Err(e)) => {
+            send_fallback_error(ctx, interaction, Acknowledgment::Pending).await;
+            Err(e)
+        }
+        Watched::Deferred(Ok(Some(reply))) => {
+            interaction
+                .edit_response(&ctx.http, reply.into_edit())
+                .await?;
+            Ok(())
+        }
+        // Handler chose not to respond - remove the "thinking..." placeholder
+        Watched::Deferred(Ok(None)) => interaction.delete_response(&ctx.http).await,
+        Watched::Deferred(Err(e)) => {
+            // Inline handlers never send themselves, so the placeholder is still up
+            send_fallback_error(ctx, interaction, Acknowledgment::Deferred).await;
+            Err(e)
+        }
+    }
+}
+
+/// Await `handler`, running `overrun` once if it takes longer than `budget`
+///
+/// The handler keeps running while `overrun` acknowledges the interaction. If
+/// `overrun` fails, the handler is dropped and its error returned.
+async fn watch<T, E>(
+    handler: impl Future<Output = T>,
+    budget: Duration,
+    overrun: impl Future<Output = Result<(), E>>,
+) -> Result<Watched<T>, E> {
+    let mut handler = std::pin::pin!(handler);
+    match tokio::time::timeout(budget, &mut handler).await {
+        Ok(output) => Ok(Watched::OnTime(output)),
+        Err(_) => {
+            overrun.await?;
+            Ok(Watched::Deferred(handler.await))
+        }
+    }
+}
+
+/// Best-effort generic error after a handler returned `Err`
+///
+/// Answers only if the user would otherwise see nothing: a pending
+/// interaction gets an ephemeral reply, and a deferred one still showing the
+/// "thinking..." placeholder gets an edit. A response the handler already
+/// edited is left alone. Failures here are logged and dropped; the caller
+/// still reports the original error.
+async fn send_fallback_error(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    acknowledgment: Acknowledgment,
+) {
+    let locale = resolve_locale_async(ctx, interaction).await;
+    let embed = embeds::localized_error_embed(t!("errors.generic", locale = &locale), &locale);
+
+    let result = match acknowledgment {
+        Acknowledgment::Pending => {
+            let message = CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true);
+            interaction
+                .create_response(&ctx.http, CreateInteractionResponse::Message(message))
+                .await
+        }
+        Acknowledgment::Deferred => match interaction.get_response(&ctx.http).await {
+            Ok(message)
+                if message
+                    .flags
+                    .is_some_and(|flags| flags.contains(MessageFlags::LOADING)) =>
+            {
+                defer::edit_embed(ctx, interaction, embed).await
+            }
+            Ok(_) => return,
+            Err(e) => Err(e),
+        },
+    };
+
+    if let Err(e) = result {
+        debug!(error = %e, "Failed to send fallback error response");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::COMMANDS;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const BUDGET: Duration = Duration::from_millis(50);
+
+    fn deferred(defer: Defer, ephemeral_subcommands: &'static [&'static str]) -> CommandSpec {
+        CommandSpec {
+            name: "stub",
+            register: || CreateCommand::new("stub"),
+            handler: CommandHandler::Deferred {
+                defer,
+                ephemeral_subcommands,
+                run: |_, _| Box::pin(async { Ok(()) }),
+            },
+            autocomplete: None,
+            dev_only: false,
+        }
+    }
+
+    fn inline(defer: Defer) -> CommandSpec {
+        CommandSpec {
+            name: "stub",
+            register: || CreateCommand::new("stub"),
+            handler: CommandHandler::Inline {
+                defer,
+                run: |_, _| Box::pin(async { Ok(None) }),
+            },
+            autocomplete: None,
+            dev_only: false,
+        }
+    }
+
+    /// Stub handler that replies after `delay`
+    async fn stub_handler(delay: Duration) -> Result<Option<InlineReply>, serenity::Error> {
+        tokio::time::sleep(delay).await;
+        Ok(Some(InlineReply::content("done")))
+    }
+
+    /// Stub defer that counts its calls
+    async fn counting_defer(calls: &AtomicUsize) -> Result<(), &'static str> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_defer_for_deferred_spec() {
+        let spec = deferred(Defer::Public, &["withdraw"]);
+
+        assert_eq!(spec.defer_for(None), Defer::Public);
+        assert_eq!(spec.defer_for(Some("submit")), Defer::Public);
+        assert_eq!(spec.defer_for(Some("withdraw")), Defer::Ephemeral);
+        assert_eq!(
+            deferred(Defer::Ephemeral, &[]).defer_for(Some("submit")),
+            Defer::Ephemeral
+        );
+    }
+
+    #[test]
+    fn test_defer_for_inline_spec() {
+        assert_eq!(inline(Defer::Public).defer_for(None), Defer::Public);
+        assert_eq!(
+            inline(Defer::Ephemeral).defer_for(Some("any")),
+            Defer::Ephemeral
+        );
+    }
+
+    #[test]
+    fn test_find_by_name() {
+        assert_eq!(
+            find(COMMANDS, "report").map(|spec| spec.name),
+            Some("report")
+        );
+        assert!(find(COMMANDS, "unknown").is_none());
+    }
+
+    #[test]
+    fn test_command_names_unique_and_registered() {
+        let mut names = HashSet::new();
+        for spec in COMMANDS {
+            assert!(names.insert(spec.name), "duplicate command {}", spec.name);
+        }
+        let registered: HashSet<String> = crate::commands::all()
+            .into_iter()
+            .map(|command| {
+                serde_json::to_value(command).unwrap()["name"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        for spec in COMMANDS.iter().filter(|spec| !spec.dev_only) {
+            assert!(
+                registered.contains(spec.name),
+                "{} not registered",
+                spec.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_inline_handlers_with_error_replies_defer_ephemeral() {
+        for name in ["maintenance", "admin"] {
+            let spec = find(COMMANDS, name).unwrap();
+            assert!(matches!(spec.handler, CommandHandler::Inline { .. }));
+            assert_eq!(spec.defer_for(None), Defer::Ephemeral, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_on_time_reply_uses_declared_visibility() {
+        let ephemeral = |reply: InlineReply, visibility| {
+            let message = serde_json::to_value(reply.into_message(visibility)).unwrap();
+            message["flags"]
+                .as_u64()
+                .is_some_and(|flags| flags & MessageFlags::EPHEMERAL.bits() != 0)
+        };
+
+        assert!(ephemeral(InlineReply::content("done"), Defer::Ephemeral));
+        assert!(!ephemeral(InlineReply::content("done"), Defer::Public));
+        assert!(ephemeral(
+            InlineReply::error("failed", "en"),
+            Defer::Ephemeral
+        ));
+    }
+
+    #[test]
+    fn test_report_deferred_ephemerally() {
+        let spec = find(COMMANDS, "report").unwrap();
+
+        for subcommand in [None, Some("submit"), Some("withdraw")] {
+            assert_eq!(spec.defer_for(subcommand), Defer::Ephemeral);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_fast_handler_answers_inline() {
+        let calls = AtomicUsize::new(0);
+
+        let watched = watch(stub_handler(Duration::ZERO), BUDGET, counting_defer(&calls))
+            .await
+            .unwrap();
+
+        assert!(matches!(watched, Watched::OnTime(Ok(Some(_)))));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_watch_slow_handler_deferred_once() {
+        let calls = AtomicUsize::new(0);
+
+        let watched = watch(stub_handler(BUDGET * 4), BUDGET, counting_defer(&calls))
+            .await
+            .unwrap();
+
+        // The handler ran to completion after the defer and kept its reply
+        let Watched::Deferred(Ok(Some(reply))) = watched else {
+            panic!("slow handler should be deferred");
+        };
+        assert_eq!(reply.content.as_deref(), Some("done"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_watch_slow_handler_error_reported_after_defer() {
+        let slow_error = async {
+            tokio::time::sleep(BUDGET * 4).await;
+            Err::<Option<InlineReply>, _>(serenity::Error::Other("handler failed"))
+        };
+
+        let watched = watch(slow_error, BUDGET, async { Ok::<_, &str>(()) })
+            .await
+            .unwrap();
+
+        assert!(matches!(watched, Watched::Deferred(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn test_watch_failed_defer_drops_handler() {
+        let finished = AtomicUsize::new(0);
+        let handler = async {
+            tokio::time::sleep(BUDGET * 4).await;
+            finished.fetch_add(1, Ordering::SeqCst);
+        };
+
+        let result = watch(handler, BUDGET, async { Err("defer failed") }).await;
+
+        assert_eq!(result, Err("defer failed"));
+        assert_eq!(finished.load(Ordering::SeqCst), 0);
+    }
+}
@@ -1,28 +1,32 @@
 //! /report command - User incident reporting for VRChat issues
+//!
+//! `/report submit` files a report; `/report withdraw` retracts the user's
+//! latest report within the cooldown window so a mistake can be corrected.
+
+use std::sync::Arc;
 
 use chrono::{Duration, Utc};
 use rust_i18n::t;
-use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
-};
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
 use serenity::all::{
     CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
-    CreateEmbedFooter, ResolvedValue, Timestamp,
+    CreateEmbedFooter, GuildId, Mentionable, ResolvedOption, ResolvedValue, RoleId, Timestamp,
+    UserId,
+};
+use tracing::{error, info, warn};
+
+use crate::commands::shared::{defer, embeds, incident_types};
+use crate::database;
+use crate::entity::{bot_config, user_reports};
+use crate::i18n::resolve_locale_async;
+use crate::repository::{
+    GuildConfigRepository, ReportRepository, Repositories, config, registration,
 };
-use tracing::{error, info};
-
-use crate::commands::shared::{defer, embeds, incident_types, respond_error};
-use crate::entity::{bot_config, guild_configs, user_configs, user_reports};
-use crate::i18n::{resolve_locale, resolve_locale_async};
-use crate::state::AppStateKey;
 
 // =============================================================================
 // Constants
 // =============================================================================
 
-/// Duplicate report cooldown in minutes
-const DUPLICATE_COOLDOWN_MINUTES: i64 = 5;
-
 /// Maximum length for details field
 const MAX_DETAILS_LENGTH: usize = 500;
 
@@ -37,7 +41,7 @@ pub fn register() -> CreateCommand {
         "type",
         t!("commands.report.option_type"),
     )
-    .name_localized("ko", "유형")
+    .name_localized("ko", t!("commands.report.option_type_name", locale = "ko"))
     .description_localized("ko", t!("commands.report.option_type", locale = "ko"))
     .required(true);
 
@@ -56,24 +60,65 @@ pub fn register() -> CreateCommand {
         .description(t!("commands.report.description"))
         .name_localized("ko", t!("commands.report.name", locale = "ko"))
         .description_localized("ko", t!("commands.report.description", locale = "ko"))
-        .add_option(incident_type_option)
         .add_option(
             CreateCommandOption::new(
-                CommandOptionType::String,
-                "details",
-                t!("commands.report.option_details"),
+                CommandOptionType::SubCommand,
+                "submit",
+                t!("commands.report.submit.description"),
+            )
+            .name_localized("ko", t!("commands.report.submit.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.report.submit.description", locale = "ko"),
+            )
+            .add_sub_option(incident_type_option)
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "details",
+                    t!("commands.report.option_details"),
+                )
+                .name_localized(
+                    "ko",
+                    t!("commands.report.option_details_name", locale = "ko"),
+                )
+                .description_localized("ko", t!("commands.report.option_details", locale = "ko"))
+                .required(false),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "withdraw",
+                t!("commands.report.withdraw.description"),
             )
-            .name_localized("ko", "상세")
-            .description_localized("ko", t!("commands.report.option_details", locale = "ko"))
-            .required(false),
+            .name_localized("ko", t!("commands.report.withdraw.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.report.withdraw.description", locale = "ko"),
+            ),
         )
 }
 
-/// /report command handler
+/// /report command handler (deferred ephemerally by the dispatcher)
 pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
-    // Use sync locale for validation errors (before defer)
-    let sync_locale = resolve_locale(interaction);
-    let options = interaction.data.options();
+    match interaction.data.options.first().map(|o| o.name.as_str()) {
+        Some("withdraw") => run_withdraw(ctx, interaction).await,
+        _ => run_submit(ctx, interaction).await,
+    }
+}
+
+/// /report submit handler
+async fn run_submit(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+    let resolved = interaction.data.options();
+    let options: &[ResolvedOption<'_>] = match resolved.first().map(|opt| &opt.value) {
+        Some(ResolvedValue::SubCommand(opts)) => opts,
+        _ => &[],
+    };
 
     // Parse incident_type (required)
     let incident_type = options
@@ -88,11 +133,11 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         });
 
     let Some(incident_type) = incident_type else {
-        return respond_error(
+        return defer::edit_error(
             ctx,
             interaction,
-            &t!("errors.missing_incident_type", locale = &sync_locale),
-            &sync_locale,
+            &t!("errors.missing_incident_type", locale = &locale),
+            &locale,
         )
         .await;
     };
@@ -113,41 +158,44 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
     if let Some(ref d) = details
         && d.len() > MAX_DETAILS_LENGTH
     {
-        return respond_error(
+        return defer::edit_error(
             ctx,
             interaction,
             &t!(
                 "errors.details_too_long",
-                locale = &sync_locale,
+                locale = &locale,
                 max = MAX_DETAILS_LENGTH,
                 current = d.len()
             ),
-            &sync_locale,
+            &locale,
         )
         .await;
     }
 
-    // Defer response before DB operations
-    defer(ctx, interaction).await?;
-
-    // Now resolve locale with full DB lookup
-    let locale = resolve_locale_async(ctx, interaction).await;
-
-    // Get database
-    let data = ctx.data.read().await;
-    let state = data
-        .get::<AppStateKey>()
-        .expect("AppState not found in TypeMap");
-    let state = state.read().await;
-    let db = state.database.as_ref();
-
+    let db = database::get_db(ctx).await;
     let user_id = interaction.user.id;
     let guild_id = interaction.guild_id;
 
     // Check registration
-    match check_registration(db, guild_id, user_id).await {
-        RegistrationStatus::Registered => {}
-        RegistrationStatus::GuildNotRegistered => {
+    match check_registration(&db, guild_id, user_id).await {
+        ReportAccess::Allowed { report_role } => {
+            // Guild may restrict reports to a role; member roles come with the interaction
+            let member_roles = interaction
+                .member
+                .as_ref()
+                .map_or(&[][..], |member| member.roles.as_slice());
+            if !config::may_report(report_role, member_roles)
+                && let Some(role) = report_role
+            {
+                let message = t!(
+                    "embeds.report.error_missing_role",
+                    role = role.mention().to_string(),
+                    locale = &locale
+                );
+                return defer::edit_error(ctx, interaction, &message, &locale).await;
+            }
+        }
+        ReportAccess::GuildNotRegistered => {
             return defer::edit_error(
                 ctx,
                 interaction,
@@ -156,19 +204,29 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
             )
             .await;
         }
-        RegistrationStatus::UserNotRegistered => {
+        ReportAccess::UserNotRegistered => {
             return edit_user_intro(ctx, interaction, &locale).await;
         }
     }
 
     // Try to insert report first (atomic operation to prevent race condition)
-    match try_insert_report(db, guild_id, user_id, incident_type, details.clone()).await {
+    let cooldown = get_report_cooldown(&db).await;
+    match try_insert_report(
+        &db,
+        guild_id,
+        user_id,
+        incident_type,
+        details.clone(),
+        cooldown,
+    )
+    .await
+    {
         ReportInsertResult::Success => {
             // Report inserted successfully - continue to alert check
         }
         ReportInsertResult::CooldownActive(last_report_time) => {
             // User is in cooldown - show when they can report again
-            let can_report_at = last_report_time + Duration::minutes(DUPLICATE_COOLDOWN_MINUTES);
+            let can_report_at = last_report_time + Duration::minutes(cooldown);
             let time_text = format!("<t:{}:R>", can_report_at.timestamp());
             let embed = embeds::warning_embed(
                 t!("embeds.report.cooldown.title", locale = &locale),
@@ -192,18 +250,30 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         }
     }
 
-    // Check threshold and send alerts if needed
-    crate::alerts::check_and_send_alerts(ctx, db, incident_type).await;
+    // Check threshold and send alerts in the background, so a large fan-out
+    // doesn't hold up the confirmation
+    {
+        let ctx = ctx.clone();
+        let db = db.clone();
+        let incident_type = incident_type.to_string();
+        crate::state::background_tasks(&ctx)
+            .await
+            .spawn(async move {
+                crate::alerts::check_and_send_alerts(&ctx, &db, &incident_type).await;
+            });
+    }
 
     // Get count of similar reports
-    let interval = get_report_interval(db).await;
-    let similar_count = get_similar_report_count(db, incident_type, user_id, interval).await;
+    let interval = get_report_interval(&db).await;
+    let similar_count = get_similar_report_count(&db, incident_type, user_id, interval).await;
+    let trend = crate::alerts::threshold::trend_direction(&db, incident_type, interval).await;
 
     info!(
         user_id = %user_id,
         guild_id = ?guild_id,
         incident_type = incident_type,
         similar_count = similar_count,
+        trend = ?trend,
         "Report submitted"
     );
 
@@ -227,15 +297,32 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         )
         .to_string()
     };
+    // Show whether the issue is spreading once others have reported it too
+    let others_text = if similar_count == 0 {
+        others_text
+    } else {
+        format!("{} {}", others_text, trend.arrow())
+    };
+
+    let mut description = t!(
+        "embeds.report.success.description",
+        locale = &locale,
+        incident_type = display_name,
+        others_text = others_text
+    )
+    .to_string();
+    if let Some(url) = incident_types::link(ctx, &db, incident_type).await {
+        description.push_str("\n\n");
+        description.push_str(&t!(
+            "embeds.report.success.learn_more",
+            locale = &locale,
+            url = url
+        ));
+    }
 
     let embed = embeds::success_embed(
         t!("embeds.report.success.title", locale = &locale),
-        t!(
-            "embeds.report.success.description",
-            locale = &locale,
-            incident_type = display_name,
-            others_text = others_text
-        ),
+        description,
     )
     .footer(CreateEmbedFooter::new(t!(
         "embeds.report.success.footer",
@@ -246,46 +333,102 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
     defer::edit_embed(ctx, interaction, embed).await
 }
 
+/// /report withdraw handler
+async fn run_withdraw(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    let db = database::get_db(ctx).await;
+
+    let user_id = interaction.user.id;
+    let cooldown = get_report_cooldown(&db).await;
+    let report = match try_withdraw_report(&db, user_id, cooldown).await {
+        Ok(Some(report)) => report,
+        Ok(None) => {
+            return defer::edit_error(
+                ctx,
+                interaction,
+                &t!(
+                    "embeds.report.error_nothing_to_withdraw",
+                    locale = &locale,
+                    minutes = cooldown
+                ),
+                &locale,
+            )
+            .await;
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to withdraw report");
+            return defer::edit_error(
+                ctx,
+                interaction,
+                &t!("embeds.report.error_withdraw_failed", locale = &locale),
+                &locale,
+            )
+            .await;
+        }
+    };
+
+    info!(
+        user_id = %user_id,
+        report_id = report.id,
+        incident_type = %report.incident_type,
+        "Report withdrawn"
+    );
+
+    let embed = embeds::success_embed(
+        t!("embeds.report.withdrawn.title", locale = &locale),
+        t!(
+            "embeds.report.withdrawn.description",
+            locale = &locale,
+            incident_type = incident_types::display_name_localized(&report.incident_type, &locale)
+        ),
+    );
+    defer::edit_embed(ctx, interaction, embed).await
+}
+
 // =============================================================================
 // Registration Check
 // =============================================================================
 
-enum RegistrationStatus {
-    Registered,
+/// Whether the reporter's guild or user registration allows a report
+enum ReportAccess {
+    /// Registered; guild reports may require a role
+    Allowed {
+        report_role: Option<RoleId>,
+    },
     GuildNotRegistered,
     UserNotRegistered,
 }
 
+/// Paused guilds can still report; disabled ones and those needing a new
+/// `/config setup` can't
 async fn check_registration(
-    db: &DatabaseConnection,
-    guild_id: Option<serenity::all::GuildId>,
-    user_id: serenity::all::UserId,
-) -> RegistrationStatus {
+    db: &Arc<DatabaseConnection>,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+) -> ReportAccess {
     match guild_id {
+        // Guild context - check guild_configs
         Some(gid) => {
-            // Guild context - check guild_configs
-            let config = guild_configs::Entity::find_by_id(gid.to_string())
-                .one(db)
-                .await
-                .ok()
-                .flatten();
-
+            let config = GuildConfigRepository::new(db.clone()).get(gid).await;
             match config {
-                Some(c) if c.enabled => RegistrationStatus::Registered,
-                _ => RegistrationStatus::GuildNotRegistered,
+                Some(c) if registration::guild(Some(&c), Utc::now()).is_registered() => {
+                    ReportAccess::Allowed {
+                        report_role: config::report_role(&c),
+                    }
+                }
+                _ => ReportAccess::GuildNotRegistered,
             }
         }
+        // User install context - check user_configs
         None => {
-            // User install context - check user_configs
-            let config = user_configs::Entity::find_by_id(user_id.to_string())
-                .one(db)
-                .await
-                .ok()
-                .flatten();
-
-            match config {
-                Some(c) if c.enabled => RegistrationStatus::Registered,
-                _ => RegistrationStatus::UserNotRegistered,
+            if registration::user_status(db, user_id).await.is_registered() {
+                ReportAccess::Allowed { report_role: None }
+            } else {
+                ReportAccess::UserNotRegistered
             }
         }
     }
@@ -311,97 +454,95 @@ enum ReportInsertResult {
 /// 1. Check if user has recent report (optimistic check for better UX)
 /// 2. If no recent report, insert new report
 /// 3. After insert, verify no race condition occurred (multiple reports in window)
-/// 4. If race detected, the earliest report wins, duplicates are deleted
+/// 4. If race detected, the earliest report wins, the others are marked
+///    `duplicate` (never deleted)
 ///
-/// This ensures that even if two requests arrive simultaneously, only one
-/// report is recorded and the user sees proper cooldown messaging.
+/// All steps run in one transaction behind the write lock, so a failure
+/// part-way (e.g. while marking duplicates) rolls back the insert instead of
+/// leaving extra rows.
 async fn try_insert_report(
-    db: &DatabaseConnection,
-    guild_id: Option<serenity::all::GuildId>,
-    user_id: serenity::all::UserId,
+    db: &Arc<DatabaseConnection>,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
     incident_type: &str,
     content: Option<String>,
+    cooldown_minutes: i64,
 ) -> ReportInsertResult {
-    // First, check if there's an existing active report in the cooldown window
-    // This is still needed to get the exact timestamp for the error message
-    let cutoff = Utc::now() - Duration::minutes(DUPLICATE_COOLDOWN_MINUTES);
-
-    let existing = user_reports::Entity::find()
-        .filter(user_reports::Column::UserId.eq(user_id.to_string()))
-        .filter(user_reports::Column::Status.eq("active"))
-        .filter(user_reports::Column::CreatedAt.gt(cutoff))
-        .order_by_desc(user_reports::Column::CreatedAt)
-        .one(db)
-        .await;
+    let incident_type = incident_type.to_string();
 
-    match existing {
-        Ok(Some(report)) => {
-            // User already has a recent report - return cooldown
-            return ReportInsertResult::CooldownActive(report.created_at);
-        }
-        Ok(None) => {
-            // No recent report, proceed to insert
-        }
-        Err(e) => {
-            return ReportInsertResult::Error(e);
-        }
-    }
+    let result = Repositories::new(db.clone())
+        .transaction(move |tx| {
+            Box::pin(async move {
+                let reports = tx.reports();
 
-    // Try to insert the report
-    // We use a transaction to ensure atomicity
-    let report = user_reports::ActiveModel {
-        guild_id: Set(guild_id.map(|g| g.to_string())),
-        user_id: Set(user_id.to_string()),
-        incident_type: Set(incident_type.to_string()),
-        content: Set(content),
-        status: Set("active".to_string()),
-        created_at: Set(Utc::now()),
-        ..Default::default()
-    };
+                // Serialize concurrent submissions before the cooldown check
+                reports.lock_for_write().await?;
 
-    match report.insert(db).await {
-        Ok(inserted_report) => {
-            // Double-check: verify we're the only report in the window
-            // This handles the race condition where two requests pass the initial check
-            // Recalculate cutoff to avoid stale timestamp issues
-            let fresh_cutoff = Utc::now() - Duration::minutes(DUPLICATE_COOLDOWN_MINUTES);
-            let reports_in_window = user_reports::Entity::find()
-                .filter(user_reports::Column::UserId.eq(user_id.to_string()))
-                .filter(user_reports::Column::Status.eq("active"))
-                .filter(user_reports::Column::CreatedAt.gt(fresh_cutoff))
-                .order_by_asc(user_reports::Column::CreatedAt)
-                .order_by_asc(user_reports::Column::Id) // Tiebreaker for same-millisecond inserts
-                .all(db)
-                .await
-                .unwrap_or_else(|e| {
-                    tracing::error!(error = %e, "Failed to query reports for race detection");
-                    vec![]
-                });
-
-            if reports_in_window.len() > 1 {
-                // Race condition detected - multiple reports in the window
-                // The first one (by created_at) wins, others get deleted
-                let first_report = &reports_in_window[0];
-
-                if inserted_report.id != first_report.id {
-                    // We lost the race - delete our report and return cooldown
-                    let _ = user_reports::Entity::delete_by_id(inserted_report.id)
-                        .exec(db)
-                        .await;
-                    return ReportInsertResult::CooldownActive(first_report.created_at);
+                // Check for an existing active report in the cooldown window
+                // This is still needed to get the exact timestamp for the error message
+                let cutoff = Utc::now() - Duration::minutes(cooldown_minutes);
+                if let Some(report) = reports.latest_active_since(user_id, cutoff).await? {
+                    return Ok(ReportInsertResult::CooldownActive(report.created_at));
                 }
-                // We won the race - delete the others
-                for report in reports_in_window.iter().skip(1) {
-                    if report.id != inserted_report.id {
-                        let _ = user_reports::Entity::delete_by_id(report.id).exec(db).await;
-                    }
+
+                let inserted = reports
+                    .create(guild_id, user_id, &incident_type, content)
+                    .await?;
+
+                // Double-check: verify we're the only report in the window
+                // The write lock should make this a no-op; kept in case a
+                // writer bypasses the lock. Recalculate cutoff to avoid stale
+                // timestamp issues
+                let fresh_cutoff = Utc::now() - Duration::minutes(cooldown_minutes);
+
+                // The first one (by created_at) wins, others are kept as duplicates
+                if let Some(first_report) =
+                    reports.keep_earliest_since(user_id, fresh_cutoff).await?
+                    && first_report.id != inserted.id
+                {
+                    // We lost the race - our report is now a duplicate
+                    return Ok(ReportInsertResult::CooldownActive(first_report.created_at));
                 }
-            }
 
-            ReportInsertResult::Success
-        }
-        Err(e) => ReportInsertResult::Error(e),
-    }
+                Ok(ReportInsertResult::Success)
+            })
+        })
+        .await;
+
+    result.unwrap_or_else(ReportInsertResult::Error)
+}
+
+// =============================================================================
+// Withdraw Report
+// =============================================================================
+
+/// Withdraw the user's newest active report within the cooldown window
+///
+/// Returns the withdrawn report, or `None` if there is nothing to withdraw.
+/// The report keeps its row with `status = withdrawn`, so it stops counting
+/// and no longer holds the cooldown.
+async fn try_withdraw_report(
+    db: &Arc<DatabaseConnection>,
+    user_id: UserId,
+    cooldown_minutes: i64,
+) -> Result<Option<user_reports::Model>, sea_orm::DbErr> {
+    Repositories::new(db.clone())
+        .transaction(move |tx| {
+            Box::pin(async move {
+                let reports = tx.reports();
+
+                // Serialize with concurrent submissions from the same user
+                reports.lock_for_write().await?;
+
+                let cutoff = Utc::now() - Duration::minutes(cooldown_minutes);
+                let Some(report) = reports.latest_active_since(user_id, cutoff).await? else {
+                    return Ok(None);
+                };
+                reports.mark_withdrawn(report.id).await?;
+                Ok(Some(report))
+            })
+        })
+        .await
 }
 
 // =============================================================================
@@ -410,31 +551,19 @@ async fn try_insert_report(
 
 /// Count unique OTHER users who reported this incident type within the interval
 async fn get_similar_report_count(
-    db: &DatabaseConnection,
+    db: &Arc<DatabaseConnection>,
     incident_type: &str,
-    exclude_user_id: serenity::all::UserId,
+    exclude_user_id: UserId,
     interval_minutes: i64,
 ) -> i64 {
-    use sea_orm::{QuerySelect, sea_query::Expr};
-
     let cutoff = Utc::now() - Duration::minutes(interval_minutes);
-
-    // Count distinct users (excluding current user)
-    let result = user_reports::Entity::find()
-        .filter(user_reports::Column::IncidentType.eq(incident_type))
-        .filter(user_reports::Column::UserId.ne(exclude_user_id.to_string()))
-        .filter(user_reports::Column::CreatedAt.gt(cutoff))
-        .filter(user_reports::Column::Status.eq("active"))
-        .select_only()
-        .column_as(
-            Expr::col(user_reports::Column::UserId).count_distinct(),
-            "count",
-        )
-        .into_tuple::<i64>()
-        .one(db)
-        .await;
-
-    result.ok().flatten().unwrap_or(0)
+    ReportRepository::new(db.clone())
+        .count_other_reporters_since(incident_type, exclude_user_id, cutoff)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(error = %e, incident_type, "Failed to count similar reports");
+            0
+        })
 }
 
 // =============================================================================
@@ -461,6 +590,60 @@ async fn get_report_interval(db: &DatabaseConnection) -> i64 {
         })
 }
 
+/// `bot_config` key for the per-user report cooldown
+pub const REPORT_COOLDOWN_KEY: &str = "report_cooldown_minutes";
+
+/// Default per-user report cooldown in minutes (used if config missing)
+pub const DEFAULT_REPORT_COOLDOWN: i64 = 5;
+
+/// Shortest configurable report cooldown in minutes
+pub const MIN_REPORT_COOLDOWN: i64 = 1;
+
+/// Longest configurable report cooldown in minutes
+pub const MAX_REPORT_COOLDOWN: i64 = 60;
+
+/// Get the per-user report cooldown from database, falls back to default if missing
+///
+/// The key is optional (only written by /admin config cooldown), so a
+/// missing row is not logged.
+pub async fn get_report_cooldown(db: &DatabaseConnection) -> i64 {
+    bot_config::Entity::find_by_id(REPORT_COOLDOWN_KEY)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .filter(|minutes| (MIN_REPORT_COOLDOWN..=MAX_REPORT_COOLDOWN).contains(minutes))
+        .unwrap_or(DEFAULT_REPORT_COOLDOWN)
+}
+
+/// Store the per-user report cooldown
+pub async fn set_report_cooldown(
+    db: &DatabaseConnection,
+    minutes: i64,
+) -> Result<(), sea_orm::DbErr> {
+    match bot_config::Entity::find_by_id(REPORT_COOLDOWN_KEY)
+        .one(db)
+        .await?
+    {
+        Some(existing) => {
+            let mut active: bot_config::ActiveModel = existing.into();
+            active.value = Set(minutes.to_string());
+            active.updated_at = Set(Utc::now());
+            active.update(db).await?;
+        }
+        None => {
+            let config = bot_config::ActiveModel {
+                key: Set(REPORT_COOLDOWN_KEY.to_string()),
+                value: Set(minutes.to_string()),
+                updated_at: Set(Utc::now()),
+            };
+            config.insert(db).await?;
+        }
+    }
+    Ok(())
+}
+
 // =============================================================================
 // Edit Helpers (after defer - edit deferred response)
 // =============================================================================
@@ -494,3 +677,183 @@ async fn edit_user_intro(
 
     defer::edit_embed(ctx, interaction, embed).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use crate::entity::user_reports;
+    use crate::repository::reports::STATUS_DUPLICATE;
+    use sea_orm::EntityTrait;
+
+    const USER: UserId = UserId::new(1);
+
+    async fn insert(db: &Arc<DatabaseConnection>) -> ReportInsertResult {
+        try_insert_report(db, None, USER, "login", None, 5).await
+    }
+
+    async fn statuses(db: &Arc<DatabaseConnection>) -> Vec<String> {
+        user_reports::Entity::find()
+            .all(&**db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|report| report.status)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_second_report_in_cooldown_rejected() {
+        let db = Arc::new(connect_in_memory().await);
+
+        assert!(matches!(insert(&db).await, ReportInsertResult::Success));
+        assert!(matches!(
+            insert(&db).await,
+            ReportInsertResult::CooldownActive(_)
+        ));
+        assert_eq!(statuses(&db).await, vec!["active"]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_reports_keep_one_active() {
+        let db = Arc::new(connect_in_memory().await);
+
+        let (first, second) = tokio::join!(insert(&db), insert(&db));
+        let successes = [first, second]
+            .iter()
+            .filter(|result| matches!(result, ReportInsertResult::Success))
+            .count();
+
+        assert_eq!(successes, 1);
+        let statuses = statuses(&db).await;
+        assert_eq!(statuses.iter().filter(|s| *s == "active").count(), 1);
+        assert!(
+            statuses
+                .iter()
+                .all(|s| s == "active" || s == STATUS_DUPLICATE)
+        );
+    }
+
+    /// Korean translation of a key
+    fn korean(key: &str) -> Option<String> {
+        Some(t!(key, locale = "ko").to_string())
+    }
+
+    #[test]
+    fn test_registration_has_korean_localizations() {
+        let command = serde_json::to_value(register()).unwrap();
+        let ko_field = |value: &serde_json::Value, field: &str| {
+            value[field]["ko"].as_str().map(str::to_string)
+        };
+
+        assert_eq!(
+            ko_field(&command, "name_localizations"),
+            korean("commands.report.name")
+        );
+        assert_eq!(
+            ko_field(&command, "description_localizations"),
+            korean("commands.report.description")
+        );
+
+        let subcommands = command["options"].as_array().unwrap();
+        assert_eq!(subcommands.len(), 2);
+        for subcommand in subcommands {
+            let name = subcommand["name"].as_str().unwrap();
+            assert_eq!(
+                ko_field(subcommand, "name_localizations"),
+                korean(&format!("commands.report.{name}.name"))
+            );
+            assert_eq!(
+                ko_field(subcommand, "description_localizations"),
+                korean(&format!("commands.report.{name}.description"))
+            );
+        }
+
+        let options = subcommands[0]["options"].as_array().unwrap();
+        let option_names: Vec<_> = options
+            .iter()
+            .map(|o| o["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(option_names, ["type", "details"]);
+        for option in options {
+            let name = option["name"].as_str().unwrap();
+            assert_eq!(
+                ko_field(option, "name_localizations"),
+                korean(&format!("commands.report.option_{name}_name"))
+            );
+            assert_eq!(
+                ko_field(option, "description_localizations"),
+                korean(&format!("commands.report.option_{name}"))
+            );
+        }
+
+        let choices = options[0]["choices"].as_array().unwrap();
+        let values: Vec<_> = choices
+            .iter()
+            .map(|c| c["value"].as_str().unwrap())
+            .collect();
+        // Choice values stay the stable English keys; only the labels are localized
+        assert_eq!(values, incident_types::INCIDENT_TYPE_KEYS);
+        for choice in choices {
+            let key = choice["value"].as_str().unwrap();
+            assert_eq!(choice["name"], incident_types::display_name(key));
+            assert_eq!(
+                ko_field(choice, "name_localizations"),
+                Some(incident_types::display_name_localized(key, "ko"))
+            );
+            assert_ne!(ko_field(choice, "name_localizations").unwrap(), key);
+        }
+    }
+
+    #[test]
+    fn test_report_keys_translated_in_every_locale() {
+        let keys = [
+            "commands.report.name",
+            "commands.report.description",
+            "commands.report.option_type_name",
+            "commands.report.option_type",
+            "commands.report.option_details_name",
+            "commands.report.option_details",
+            "commands.report.submit.name",
+            "commands.report.submit.description",
+            "commands.report.withdraw.name",
+            "commands.report.withdraw.description",
+            "embeds.report.success.title",
+            "embeds.report.success.description",
+            "embeds.report.success.footer",
+            "embeds.report.success.learn_more",
+            "embeds.report.success.others_none",
+            "embeds.report.success.others_one",
+            "embeds.report.success.others_many",
+            "embeds.report.cooldown.title",
+            "embeds.report.cooldown.description",
+            "embeds.report.intro.title",
+            "embeds.report.intro.description",
+            "embeds.report.intro.field_commands",
+            "embeds.report.intro.field_commands_value",
+            "embeds.report.intro.field_getting_started",
+            "embeds.report.intro.field_getting_started_value",
+            "embeds.report.intro.footer",
+            "embeds.report.withdrawn.title",
+            "embeds.report.withdrawn.description",
+            "embeds.report.error_guild_not_registered",
+            "embeds.report.error_missing_role",
+            "embeds.report.error_insert_failed",
+            "embeds.report.error_nothing_to_withdraw",
+            "embeds.report.error_withdraw_failed",
+            "errors.missing_incident_type",
+            "errors.details_too_long",
+        ];
+        let incident_types: Vec<_> = incident_types::INCIDENT_TYPE_KEYS
+            .iter()
+            .map(|key| format!("incident_types.{key}"))
+            .collect();
+        let incident_types: Vec<&str> = incident_types.iter().map(String::as_str).collect();
+
+        assert_eq!(crate::i18n::untranslated(&keys), Vec::<String>::new());
+        assert_eq!(
+            crate::i18n::untranslated(&incident_types),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Display helpers for VRChat status page components
+
+use rust_i18n::t;
+
+/// Emoji for a component status
+pub fn status_emoji(status: &str) -> &'static str {
+    match status {
+        "operational" => "🟢",
+        "degraded_performance" => "🟡",
+        "partial_outage" => "🟠",
+        "major_outage" => "🔴",
+        "under_maintenance" => "🔵",
+        _ => "⚪",
+    }
+}
+
+/// Localized component name, or the status page name if not translated
+pub fn component_name(name: &str, locale: &str) -> String {
+    label("components", name, locale)
+}
+
+/// Localized label from `<prefix>.<value>`, or the raw value if untranslated
+///
+/// Used for status page values such as incident impact and status.
+pub fn label(prefix: &str, value: &str, locale: &str) -> String {
+    let key = format!("{}.{}", prefix, value);
+    let translated = t!(&key, locale = locale);
+    // If translation key doesn't exist, rust-i18n returns the key itself
+    if translated.contains(prefix) {
+        value.to_string()
+    } else {
+        translated.to_string()
+    }
+}
@@ -2,8 +2,9 @@
 //!
 //! Use these when your handler needs to do slow operations (database, API calls)
 //! before responding. Discord requires a response within 3 seconds, so defer first.
+//! Slash commands are deferred by the dispatcher (see `commands::registry`);
+//! handlers only call the `edit_*` functions.
 
-use rust_i18n::t;
 use serenity::all::{
     CommandInteraction, ComponentInteraction, Context, CreateEmbed, CreateInteractionResponse,
     EditInteractionResponse, Timestamp,
@@ -17,8 +18,8 @@ use super::embeds;
 
 /// Defer a command interaction response
 ///
-/// Call this first if your handler will take more than 3 seconds.
-/// Then use `edit_*` functions to send the actual response.
+/// Called by the dispatcher for commands declared as deferred.
+/// Handlers then use `edit_*` functions to send the actual response.
 pub async fn defer(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
     interaction.defer(&ctx.http).await
 }
@@ -62,8 +63,7 @@ pub async fn edit_error(
     message: &str,
     locale: &str,
 ) -> Result<(), serenity::Error> {
-    let title = t!("embeds.dashboard.error_title", locale = locale);
-    let embed = embeds::error_embed(title, message);
+    let embed = embeds::localized_error_embed(message, locale);
     edit_embed(ctx, interaction, embed).await
 }
 
@@ -123,6 +123,20 @@ pub async fn edit_component_embed(
     Ok(())
 }
 
+/// Edit a deferred component response with a custom embed and components
+pub async fn edit_component_embed_components(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    embed: CreateEmbed,
+    components: Vec<serenity::all::CreateActionRow>,
+) -> Result<(), serenity::Error> {
+    let response = EditInteractionResponse::new()
+        .embed(embed)
+        .components(components);
+    interaction.edit_response(&ctx.http, response).await?;
+    Ok(())
+}
+
 /// Edit a deferred component response with an error embed (removes components)
 pub async fn edit_component_error(
     ctx: &Context,
@@ -130,7 +144,6 @@ pub async fn edit_component_error(
     message: &str,
     locale: &str,
 ) -> Result<(), serenity::Error> {
-    let title = t!("embeds.dashboard.error_title", locale = locale);
-    let embed = embeds::error_embed(title, message);
+    let embed = embeds::localized_error_embed(message, locale);
     edit_component_embed(ctx, interaction, embed).await
 }
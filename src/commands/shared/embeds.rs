@@ -4,6 +4,7 @@
 //! Use these for simple title+description embeds. For complex embeds
 //! with fields, footers, or custom content, build the embed directly.
 
+use rust_i18n::t;
 use serenity::all::{Colour, CreateEmbed};
 
 use super::colors;
@@ -38,6 +39,14 @@ pub fn error_embed(title: impl Into<String>, description: impl Into<String>) ->
         .color(Colour::new(colors::ERROR))
 }
 
+/// Create an error embed with the localized "Error" title
+///
+/// `message` is already translated; every error response helper uses this so
+/// the title is the same across commands.
+pub fn localized_error_embed(message: impl Into<String>, locale: &str) -> CreateEmbed {
+    error_embed(t!("errors.title", locale = locale), message)
+}
+
 /// Create a warning embed (yellow)
 ///
 /// Use for warnings, confirmations before destructive actions.
@@ -1,6 +1,12 @@
-//! Shared utilities for incident type display names
+//! Shared utilities for incident type display names and documentation links
 
 use rust_i18n::t;
+use sea_orm::DatabaseConnection;
+use serenity::all::Context;
+
+use crate::state::AppStateKey;
+
+use super::links;
 
 /// Available incident type keys for reporting
 pub const INCIDENT_TYPE_KEYS: &[&str] = &["login", "instance", "api", "auth", "download", "other"];
@@ -29,3 +35,146 @@ pub fn display_name_localized(incident_type: &str, locale: &str) -> String {
         translated.to_string()
     }
 }
+
+// =============================================================================
+// Documentation Links
+// =============================================================================
+
+/// `bot_config` key prefix for per-type link overrides (e.g. `link.auth`)
+pub const LINK_KEY_PREFIX: &str = "link.";
+
+/// Built-in "Learn more" link for an incident type, if it has one
+fn builtin_link(incident_type: &str) -> Option<String> {
+    match incident_type {
+        "login" | "instance" | "api" | "download" => Some(links::status_page()),
+        "auth" => Some("https://help.vrchat.com/".to_string()),
+        _ => None,
+    }
+}
+
+/// `bot_config` key holding the link override for an incident type
+pub fn link_key(incident_type: &str) -> String {
+    format!("{}{}", LINK_KEY_PREFIX, incident_type)
+}
+
+/// Whether a configured link is an absolute `https` URL with a host
+pub fn is_valid_link(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .map(|url| url.scheme() == "https" && url.host_str().is_some())
+        .unwrap_or(false)
+}
+
+/// Resolve the "Learn more" link for an incident type
+///
+/// A valid `override_url` takes precedence over the built-in link; an empty
+/// override hides the link, and an invalid one is ignored. `None` means the
+/// line is omitted.
+pub fn resolve_link(incident_type: &str, override_url: Option<&str>) -> Option<String> {
+    match override_url.map(str::trim) {
+        Some("") => None,
+        Some(url) if is_valid_link(url) => Some(url.to_string()),
+        Some(url) => {
+            tracing::warn!(
+                incident_type,
+                url,
+                "Ignoring invalid link override (must be https)"
+            );
+            builtin_link(incident_type)
+        }
+        None => builtin_link(incident_type),
+    }
+}
+
+/// "Learn more" link for an incident type, with overrides from the cached
+/// config store (no query unless the snapshot has expired)
+pub async fn link(ctx: &Context, db: &DatabaseConnection, incident_type: &str) -> Option<String> {
+    let store = {
+        let data = ctx.data.read().await;
+        match data.get::<AppStateKey>() {
+            Some(state) => Some(state.read().await.config_store.clone()),
+            None => None,
+        }
+    };
+    let overrides = match store {
+        Some(store) => store.values(db).await,
+        None => Default::default(),
+    };
+    resolve_link(
+        incident_type,
+        overrides.get(&link_key(incident_type)).map(String::as_str),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HELP: &str = "https://help.vrchat.com/";
+    const CUSTOM: &str = "https://docs.example.com/vrchat-auth";
+
+    #[test]
+    fn test_link_key() {
+        assert_eq!(link_key("auth"), "link.auth");
+        assert!(link_key("login").starts_with(LINK_KEY_PREFIX));
+    }
+
+    #[test]
+    fn test_is_valid_link() {
+        let cases = [
+            ("https://help.vrchat.com/", true),
+            ("https://docs.example.com/a?b=c#d", true),
+            ("http://help.vrchat.com/", false),
+            ("ftp://example.com/file", false),
+            ("javascript:alert(1)", false),
+            ("https://", false),
+            ("help.vrchat.com", false),
+            ("/relative/path", false),
+            ("", false),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(is_valid_link(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_link_precedence() {
+        let status_page = links::status_page();
+        let cases = [
+            // No override: built-in link, or none for types without one
+            ("auth", None, Some(HELP)),
+            ("login", None, Some(status_page.as_str())),
+            ("other", None, None),
+            ("unknown", None, None),
+            // A valid override beats the built-in link
+            ("auth", Some(CUSTOM), Some(CUSTOM)),
+            ("other", Some(CUSTOM), Some(CUSTOM)),
+            (
+                "auth",
+                Some("  https://docs.example.com/vrchat-auth "),
+                Some(CUSTOM),
+            ),
+            // An empty override hides the line
+            ("auth", Some(""), None),
+            ("login", Some("   "), None),
+            // An invalid override is ignored
+            ("auth", Some("http://docs.example.com/"), Some(HELP)),
+            ("other", Some("not a url"), None),
+        ];
+        for (incident_type, override_url, expected) in cases {
+            assert_eq!(
+                resolve_link(incident_type, override_url).as_deref(),
+                expected,
+                "{incident_type} with {override_url:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_builtin_links_are_valid() {
+        for incident_type in INCIDENT_TYPE_KEYS {
+            if let Some(url) = builtin_link(incident_type) {
+                assert!(is_valid_link(&url), "{incident_type}: {url}");
+            }
+        }
+    }
+}
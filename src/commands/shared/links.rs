@@ -0,0 +1,65 @@
+//! Links to the official VRChat status page
+
+use crate::collector::client::status_page_url;
+
+/// Status page home
+pub fn status_page() -> String {
+    status_page_url("/")
+}
+
+/// Status page of an incident or scheduled maintenance
+///
+/// The ID is a single percent-encoded path segment, so an unexpected ID
+/// can't point the link anywhere else on the site.
+pub fn incident_url(id: &str) -> String {
+    let mut url = match reqwest::Url::parse(&status_page_url("/incidents/")) {
+        Ok(url) => url,
+        Err(_) => return status_page(),
+    };
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments.pop_if_empty().push(id);
+    }
+    url.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incident_url_plain_id() {
+        assert_eq!(
+            incident_url("abc123"),
+            "https://status.vrchat.com/incidents/abc123"
+        );
+    }
+
+    #[test]
+    fn test_incident_url_encodes_slashes() {
+        assert_eq!(
+            incident_url("../admin"),
+            "https://status.vrchat.com/incidents/..%2Fadmin"
+        );
+    }
+
+    #[test]
+    fn test_incident_url_encodes_query_and_fragment() {
+        assert_eq!(
+            incident_url("id?x=1#top"),
+            "https://status.vrchat.com/incidents/id%3Fx=1%23top"
+        );
+    }
+
+    #[test]
+    fn test_incident_url_encodes_spaces_and_unicode() {
+        assert_eq!(
+            incident_url("a b/é"),
+            "https://status.vrchat.com/incidents/a%20b%2F%C3%A9"
+        );
+    }
+
+    #[test]
+    fn test_status_page_home() {
+        assert_eq!(status_page(), "https://status.vrchat.com/");
+    }
+}
@@ -2,14 +2,17 @@
 
 pub mod button;
 pub mod colors;
+pub mod components;
 pub mod defer;
 pub mod embeds;
 pub mod incident_types;
+pub mod links;
 mod responses;
+pub mod sparkline;
+pub mod text;
 
 pub use button::{button_id_with_context, is_button, parse_button_context};
 pub use defer::{
-    defer, defer_component_update, defer_ephemeral, edit_component_embed, edit_component_error,
-    edit_embed, edit_embed_components, edit_error, edit_info, edit_success,
+    defer_component_update, edit_component_embed, edit_component_embed_components,
+    edit_component_error, edit_embed, edit_embed_components, edit_error, edit_info, edit_success,
 };
-pub use responses::respond_error;
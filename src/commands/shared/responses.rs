@@ -1,6 +1,5 @@
 //! Response utilities for Discord interactions
 
-use rust_i18n::t;
 use serenity::all::{
     CommandInteraction, ComponentInteraction, Context, CreateInteractionResponse,
     CreateInteractionResponseMessage, Timestamp,
@@ -45,14 +44,14 @@ pub async fn respond_info(
 }
 
 /// Send an error response to a command interaction (ephemeral)
+#[allow(dead_code)]
 pub async fn respond_error(
     ctx: &Context,
     interaction: &CommandInteraction,
     message: &str,
     locale: &str,
 ) -> Result<(), serenity::Error> {
-    let title = t!("embeds.dashboard.error_title", locale = locale);
-    let embed = embeds::error_embed(title, message);
+    let embed = embeds::localized_error_embed(message, locale);
 
     let response = CreateInteractionResponseMessage::new()
         .embed(embed)
@@ -74,8 +73,7 @@ pub async fn respond_button_error(
     message: &str,
     locale: &str,
 ) -> Result<(), serenity::Error> {
-    let title = t!("embeds.dashboard.error_title", locale = locale);
-    let embed = embeds::error_embed(title, message);
+    let embed = embeds::localized_error_embed(message, locale);
 
     let response = CreateInteractionResponseMessage::new()
         .embed(embed)
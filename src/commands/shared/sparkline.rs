@@ -0,0 +1,124 @@
+//! Text sparklines for compact trends in embeds
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Bar characters from lowest to highest
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Render values as a sparkline scaled to the largest value
+///
+/// Zero always renders as the lowest bar and any non-zero value as at least
+/// the second bar, so quiet weeks stay distinguishable from empty ones.
+pub fn sparkline(values: &[u64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    let top = (BARS.len() - 1) as u64;
+
+    values
+        .iter()
+        .map(|&value| {
+            let level = if max == 0 {
+                0
+            } else {
+                (value * top).div_ceil(max)
+            };
+            BARS[level as usize]
+        })
+        .collect()
+}
+
+/// Bucket timestamps into `weeks` consecutive 7-day windows ending at `now`
+///
+/// Returns counts oldest first. Timestamps outside the range are ignored.
+pub fn weekly_counts(timestamps: &[DateTime<Utc>], now: DateTime<Utc>, weeks: usize) -> Vec<u64> {
+    let mut counts = vec![0; weeks];
+    for ts in timestamps {
+        let age = now.signed_duration_since(*ts);
+        if age < Duration::zero() {
+            continue;
+        }
+        let weeks_ago = (age.num_seconds() / Duration::weeks(1).num_seconds()) as usize;
+        if weeks_ago < weeks {
+            counts[weeks - 1 - weeks_ago] += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_sparkline_empty() {
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_sparkline_single_point() {
+        assert_eq!(sparkline(&[0]), "▁");
+        assert_eq!(sparkline(&[1]), "█");
+        assert_eq!(sparkline(&[42]), "█");
+    }
+
+    #[test]
+    fn test_sparkline_flat() {
+        assert_eq!(sparkline(&[0, 0, 0, 0]), "▁▁▁▁");
+        assert_eq!(sparkline(&[5, 5, 5, 5]), "████");
+    }
+
+    #[test]
+    fn test_sparkline_scales_to_max() {
+        assert_eq!(sparkline(&[0, 1, 2, 3, 4, 5, 6, 7]), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[0, 7, 14]), "▁▅█");
+    }
+
+    #[test]
+    fn test_sparkline_spike_keeps_small_values_visible() {
+        assert_eq!(sparkline(&[1, 0, 0, 100, 0, 2, 0, 0]), "▂▁▁█▁▂▁▁");
+    }
+
+    #[test]
+    fn test_sparkline_one_char_per_value() {
+        let values: Vec<u64> = (0..8).map(|i| i * 3).collect();
+        assert_eq!(sparkline(&values).chars().count(), values.len());
+    }
+
+    #[test]
+    fn test_weekly_counts_empty() {
+        assert_eq!(weekly_counts(&[], now(), 4), vec![0, 0, 0, 0]);
+        assert_eq!(weekly_counts(&[now()], now(), 0), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_weekly_counts_buckets_oldest_first() {
+        let timestamps = [
+            now(),
+            now() - Duration::days(1),
+            now() - Duration::days(8),
+            now() - Duration::days(27),
+        ];
+
+        assert_eq!(weekly_counts(&timestamps, now(), 4), vec![1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_weekly_counts_boundaries() {
+        let week = Duration::weeks(1);
+        let timestamps = [
+            // Exactly one week ago starts the previous bucket
+            now() - week,
+            now() - week + Duration::seconds(1),
+            // Exactly `weeks` weeks ago is out of range
+            now() - week * 2,
+            // Future timestamps are ignored
+            now() + Duration::seconds(1),
+        ];
+
+        assert_eq!(weekly_counts(&timestamps, now(), 2), vec![1, 1]);
+    }
+}
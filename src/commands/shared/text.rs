@@ -0,0 +1,53 @@
+//! Text helpers for embed content
+
+use chrono::Duration;
+use rust_i18n::t;
+
+/// Shorten `text` to at most `max_chars` characters, marking the cut with "..."
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", kept.trim_end())
+}
+
+/// Duration in hours and minutes, e.g. "45m", "2h", or "2h 30m"
+///
+/// Negative durations read as zero.
+pub fn format_duration(duration: Duration, locale: &str) -> String {
+    let minutes = duration.num_minutes().max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, m) => t!("time.duration_minutes", m = m, locale = locale).to_string(),
+        (h, 0) => t!("time.duration_hours", h = h, locale = locale).to_string(),
+        (h, m) => t!("time.duration_hours_minutes", h = h, m = m, locale = locale).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        let cases = [
+            (Duration::minutes(-5), "0m"),
+            (Duration::seconds(59), "0m"),
+            (Duration::minutes(45), "45m"),
+            (Duration::hours(2), "2h"),
+            (Duration::minutes(150), "2h 30m"),
+            (Duration::hours(30), "30h"),
+        ];
+        for (duration, expected) in cases {
+            assert_eq!(format_duration(duration, "en"), expected, "{duration}");
+        }
+        assert_eq!(format_duration(Duration::minutes(150), "ko"), "2시간 30분");
+        assert_eq!(format_duration(Duration::minutes(150), "ja"), "2時間30分");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a longer sentence", 10), "a longe...");
+    }
+}
@@ -0,0 +1,292 @@
+//! /status community - aggregate, anonymized usage statistics
+//!
+//! Shows how many communities use the bot and how active reporting is. Only
+//! global totals are shown, never per-guild or per-user data. Results are
+//! cached in `AppState` for [`CACHE_TTL`] so repeated invocations don't hit
+//! the database.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::{DatabaseConnection, DbErr};
+use serenity::all::{
+    Colour, CommandInteraction, Context, CreateEmbed, CreateEmbedFooter, Timestamp,
+};
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::alerts::delivery::ALERT_TYPE_FALLBACK_DM;
+use crate::alerts::threshold::ALERT_TYPE_THRESHOLD;
+use crate::commands::shared::{colors, defer, embeds, incident_types};
+use crate::i18n::resolve_locale_async;
+use crate::repository::{
+    GuildConfigRepository, RecordRepository, ReportRepository, UserConfigRepository,
+};
+use crate::state::AppStateKey;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// How long computed statistics are reused
+pub const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Window for the report count
+const REPORT_WINDOW_HOURS: i64 = 24;
+
+/// Window for the alert count and busiest incident type
+const WEEK_DAYS: i64 = 7;
+
+// =============================================================================
+// Statistics
+// =============================================================================
+
+/// Aggregate usage statistics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommunityStats {
+    pub servers: u64,
+    pub users: u64,
+    /// Reports in the last 24 hours, all incident types
+    pub reports_24h: u64,
+    /// Threshold alert deliveries in the last 7 days
+    pub alerts_7d: u64,
+    /// Most reported incident type in the last 7 days, with its report count
+    pub busiest_type: Option<(String, u64)>,
+}
+
+/// Query all statistics
+async fn load_stats(db: &Arc<DatabaseConnection>) -> Result<CommunityStats, DbErr> {
+    let now = Utc::now();
+    let week_ago = now - chrono::Duration::days(WEEK_DAYS);
+
+    let reports = ReportRepository::new(db.clone());
+    Ok(CommunityStats {
+        servers: GuildConfigRepository::new(db.clone())
+            .count_enabled()
+            .await?,
+        users: UserConfigRepository::new(db.clone())
+            .count_enabled()
+            .await?,
+        reports_24h: reports
+            .count_since(now - chrono::Duration::hours(REPORT_WINDOW_HOURS))
+            .await?,
+        alerts_7d: RecordRepository::new(db.clone())
+            .count_sent_alerts_since(&[ALERT_TYPE_THRESHOLD, ALERT_TYPE_FALLBACK_DM], week_ago)
+            .await?,
+        busiest_type: reports.busiest_type_since(week_ago).await?,
+    })
+}
+
+// =============================================================================
+// Cache
+// =============================================================================
+
+/// Last computed statistics, shared through `AppState`
+///
+/// The lock is held while loading, so concurrent invocations after expiry
+/// wait for one load instead of each querying the database.
+#[derive(Debug, Default)]
+pub struct CommunityStatsCache {
+    entry: Mutex<Option<(Instant, CommunityStats)>>,
+}
+
+impl CommunityStatsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached statistics if younger than `ttl`, otherwise the result of `load`
+    ///
+    /// A failed load is not cached.
+    pub async fn get_or_load<F, Fut, E>(&self, ttl: Duration, load: F) -> Result<CommunityStats, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<CommunityStats, E>>,
+    {
+        let mut entry = self.entry.lock().await;
+        if let Some((loaded_at, stats)) = entry.as_ref()
+            && loaded_at.elapsed() < ttl
+        {
+            return Ok(stats.clone());
+        }
+
+        let stats = load().await?;
+        *entry = Some((Instant::now(), stats.clone()));
+        Ok(stats)
+    }
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Handle /status community (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    let (db, cache) = {
+        let data = ctx.data.read().await;
+        let state = data
+            .get::<AppStateKey>()
+            .expect("AppState not found in TypeMap")
+            .read()
+            .await;
+        (state.database.clone(), state.community_stats.clone())
+    };
+
+    let stats = match cache.get_or_load(CACHE_TTL, || load_stats(&db)).await {
+        Ok(stats) => stats,
+        Err(e) => {
+            error!(error = %e, "Failed to load community statistics");
+            let embed = embeds::localized_error_embed(
+                t!("embeds.community.error_description", locale = &locale),
+                &locale,
+            );
+            return defer::edit_embed(ctx, interaction, embed).await;
+        }
+    };
+
+    defer::edit_embed(ctx, interaction, community_embed(&stats, &locale)).await
+}
+
+/// Build the statistics embed
+fn community_embed(stats: &CommunityStats, locale: &str) -> CreateEmbed {
+    let busiest = match &stats.busiest_type {
+        Some((incident_type, count)) => t!(
+            "embeds.community.busiest_value",
+            incident_type = incident_types::display_name_localized(incident_type, locale),
+            count = count,
+            locale = locale
+        )
+        .to_string(),
+        None => t!("embeds.community.busiest_none", locale = locale).to_string(),
+    };
+
+    CreateEmbed::default()
+        .title(t!("embeds.community.title", locale = locale))
+        .description(t!("embeds.community.description", locale = locale))
+        .color(Colour::new(colors::BRAND))
+        .field(
+            t!("embeds.community.field_servers", locale = locale),
+            stats.servers.to_string(),
+            true,
+        )
+        .field(
+            t!("embeds.community.field_users", locale = locale),
+            stats.users.to_string(),
+            true,
+        )
+        .field("\u{200B}", "\u{200B}", true)
+        .field(
+            t!("embeds.community.field_reports_24h", locale = locale),
+            stats.reports_24h.to_string(),
+            true,
+        )
+        .field(
+            t!("embeds.community.field_alerts_7d", locale = locale),
+            stats.alerts_7d.to_string(),
+            true,
+        )
+        .field("\u{200B}", "\u{200B}", true)
+        .field(
+            t!("embeds.community.field_busiest", locale = locale),
+            busiest,
+            false,
+        )
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.community.footer",
+            locale = locale
+        )))
+        .timestamp(Timestamp::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn stats(servers: u64) -> CommunityStats {
+        CommunityStats {
+            servers,
+            users: 0,
+            reports_24h: 0,
+            alerts_7d: 0,
+            busiest_type: None,
+        }
+    }
+
+    /// Load through the cache, counting loader calls; each load returns the call number
+    async fn load(cache: &CommunityStatsCache, ttl: Duration, calls: &AtomicUsize) -> u64 {
+        cache
+            .get_or_load(ttl, || async {
+                let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                Ok::<_, DbErr>(stats(call as u64))
+            })
+            .await
+            .unwrap()
+            .servers
+    }
+
+    #[tokio::test]
+    async fn test_cache_loads_once_within_ttl() {
+        let cache = CommunityStatsCache::new();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..5 {
+            assert_eq!(load(&cache, CACHE_TTL, &calls).await, 1);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_reloads_after_ttl() {
+        let cache = CommunityStatsCache::new();
+        let calls = AtomicUsize::new(0);
+        let ttl = Duration::from_millis(50);
+
+        assert_eq!(load(&cache, ttl, &calls).await, 1);
+        assert_eq!(load(&cache, ttl, &calls).await, 1);
+        tokio::time::sleep(ttl).await;
+        assert_eq!(load(&cache, ttl, &calls).await, 2);
+        assert_eq!(load(&cache, ttl, &calls).await, 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_load_is_not_cached() {
+        let cache = CommunityStatsCache::new();
+        let calls = AtomicUsize::new(0);
+
+        let failed = cache
+            .get_or_load(CACHE_TTL, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err::<CommunityStats, _>(DbErr::Custom("unavailable".to_string()))
+            })
+            .await;
+
+        assert!(failed.is_err());
+        assert_eq!(load(&cache, CACHE_TTL, &calls).await, 2);
+        assert_eq!(load(&cache, CACHE_TTL, &calls).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_loads_share_one_query() {
+        let cache = Arc::new(CommunityStatsCache::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let cache = cache.clone();
+                let calls = calls.clone();
+                tokio::spawn(async move { load(&cache, CACHE_TTL, &calls).await })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), 1);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
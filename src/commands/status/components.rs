@@ -0,0 +1,341 @@
+//! /status components - every status page component with its current state
+//!
+//! Lists the latest `component_logs` row of each component, grouped under
+//! its parent group. Groups come from `component_logs.group_id`, falling back
+//! to the dashboard's hardcoded lists for rows collected before that column
+//! existed; components without a known group are listed under "Other". Each
+//! line shows how long the component has been in its current status. More
+//! than [`PAGE_SIZE`] components are split into pages with Previous/Next
+//! buttons.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use rust_i18n::t;
+use sea_orm::{DatabaseConnection, DbErr};
+use serenity::all::{
+    ButtonStyle, Colour, CommandInteraction, ComponentInteraction, Context, CreateActionRow,
+    CreateButton, CreateEmbed, CreateEmbedFooter, Timestamp,
+};
+use tracing::error;
+
+use crate::commands::shared::{
+    button_id_with_context, colors, defer, defer_component_update, edit_component_embed,
+    edit_component_embed_components, embeds, is_button, parse_button_context,
+};
+use crate::database;
+use crate::entity::component_logs;
+use crate::i18n::{resolve_locale_async, resolve_locale_component};
+use crate::repository::LogRepository;
+
+use crate::commands::shared::components::{component_name, status_emoji};
+
+use super::dashboard::known_group;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Components listed per page
+pub const PAGE_SIZE: usize = 20;
+
+/// Module name for status command buttons
+const MODULE: &str = "status";
+
+/// Action name for the page buttons
+const ACTION_COMPONENTS_PAGE: &str = "components_page";
+
+/// Button context type carrying the target page
+const CONTEXT_PAGE: &str = "page";
+
+// =============================================================================
+// Grouping
+// =============================================================================
+
+/// Components under one group, or ungrouped components if `group` is `None`
+#[derive(Debug, Clone)]
+pub struct ComponentSection {
+    pub group: Option<component_logs::Model>,
+    pub components: Vec<component_logs::Model>,
+}
+
+/// Group ID of a component, from the row or the hardcoded lists
+fn parent_id(component: &component_logs::Model) -> Option<String> {
+    component
+        .group_id
+        .clone()
+        .or_else(|| known_group(&component.component_id).map(str::to_string))
+}
+
+/// Arrange the latest component rows into sections
+///
+/// Groups and their components are sorted by name; the ungrouped section
+/// comes last.
+pub fn group_sections(rows: Vec<component_logs::Model>) -> Vec<ComponentSection> {
+    let group_ids: HashSet<String> = rows.iter().filter_map(parent_id).collect();
+    let (groups, mut members): (Vec<_>, Vec<_>) = rows
+        .into_iter()
+        .partition(|row| group_ids.contains(&row.component_id));
+
+    let mut sections: Vec<ComponentSection> = groups
+        .into_iter()
+        .map(|group| ComponentSection {
+            group: Some(group),
+            components: Vec::new(),
+        })
+        .collect();
+    sections.sort_by(|a, b| {
+        let name = |section: &ComponentSection| section.group.as_ref().map(|g| g.name.clone());
+        name(a).cmp(&name(b))
+    });
+
+    members.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut ungrouped = Vec::new();
+    for component in members {
+        let section = parent_id(&component).and_then(|group_id| {
+            sections.iter_mut().find(|section| {
+                section
+                    .group
+                    .as_ref()
+                    .is_some_and(|g| g.component_id == group_id)
+            })
+        });
+        match section {
+            Some(section) => section.components.push(component),
+            None => ungrouped.push(component),
+        }
+    }
+
+    sections.retain(|section| !section.components.is_empty());
+    if !ungrouped.is_empty() {
+        sections.push(ComponentSection {
+            group: None,
+            components: ungrouped,
+        });
+    }
+    sections
+}
+
+/// Number of pages needed for `count` components (at least one)
+pub fn page_count(count: usize) -> usize {
+    count.div_ceil(PAGE_SIZE).max(1)
+}
+
+// =============================================================================
+// Loading
+// =============================================================================
+
+/// Sections and the time each listed component entered its current status
+struct ComponentView {
+    sections: Vec<ComponentSection>,
+    since: HashMap<String, DateTime<Utc>>,
+}
+
+async fn load_view(db: &Arc<DatabaseConnection>) -> Result<ComponentView, DbErr> {
+    let repo = LogRepository::new(db.clone());
+    let sections = group_sections(repo.latest_components().await?);
+
+    let mut since = HashMap::new();
+    for component in sections.iter().flat_map(|s| &s.components) {
+        if let Some(at) = repo
+            .status_since(&component.component_id, &component.status)
+            .await?
+        {
+            since.insert(component.component_id.clone(), at);
+        }
+    }
+    Ok(ComponentView { sections, since })
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Handle /status components (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+    let db = database::get_db(ctx).await;
+
+    match load_view(&db).await {
+        Ok(view) => {
+            let (embed, buttons) = render_page(&view, 0, &locale);
+            defer::edit_embed_components(ctx, interaction, embed, buttons).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to load component statuses");
+            defer::edit_embed(ctx, interaction, error_embed(&locale)).await
+        }
+    }
+}
+
+/// Check if button ID matches a /status components page button
+pub fn is_page_button(custom_id: &str) -> bool {
+    is_button(custom_id, MODULE, ACTION_COMPONENTS_PAGE)
+}
+
+/// Handle a Previous/Next click by re-rendering the requested page
+pub async fn handle_page_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), serenity::Error> {
+    defer_component_update(ctx, interaction).await?;
+
+    let locale = resolve_locale_component(ctx, interaction).await;
+    let page = parse_button_context(&interaction.data.custom_id)
+        .filter(|(context_type, _)| *context_type == CONTEXT_PAGE)
+        .and_then(|(_, page)| page.parse::<usize>().ok())
+        .unwrap_or(0);
+    let db = database::get_db(ctx).await;
+
+    match load_view(&db).await {
+        Ok(view) => {
+            let (embed, buttons) = render_page(&view, page, &locale);
+            edit_component_embed_components(ctx, interaction, embed, buttons).await
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to load component statuses");
+            edit_component_embed(ctx, interaction, error_embed(&locale)).await
+        }
+    }
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+/// Embed and page buttons for `page` (clamped to the last page)
+fn render_page(
+    view: &ComponentView,
+    page: usize,
+    locale: &str,
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let entries: Vec<(Option<&component_logs::Model>, &component_logs::Model)> = view
+        .sections
+        .iter()
+        .flat_map(|section| {
+            section
+                .components
+                .iter()
+                .map(move |component| (section.group.as_ref(), component))
+        })
+        .collect();
+
+    let mut embed = CreateEmbed::default()
+        .title(t!("embeds.components.title", locale = locale))
+        .description(t!("embeds.components.description", locale = locale))
+        .color(Colour::new(colors::BRAND))
+        .timestamp(Timestamp::now());
+
+    if entries.is_empty() {
+        embed = embed.field(
+            "\u{200B}",
+            t!("embeds.components.no_data", locale = locale),
+            false,
+        );
+        return (embed, Vec::new());
+    }
+
+    let total = page_count(entries.len());
+    let page = page.min(total - 1);
+    let now = Utc::now();
+
+    // One field per run of components sharing a group on this page
+    let mut fields: Vec<(String, Vec<String>)> = Vec::new();
+    let mut current_group: Option<Option<&str>> = None;
+    for (group, component) in entries.iter().skip(page * PAGE_SIZE).take(PAGE_SIZE) {
+        let group_id = group.map(|g| g.component_id.as_str());
+        if current_group != Some(group_id) {
+            current_group = Some(group_id);
+            let title = match group {
+                Some(g) => format!(
+                    "{} {}",
+                    status_emoji(&g.status),
+                    component_name(&g.name, locale)
+                ),
+                None => t!("embeds.components.group_other", locale = locale).to_string(),
+            };
+            fields.push((title, Vec::new()));
+        }
+
+        let mut line = format!(
+            "{} {}",
+            status_emoji(&component.status),
+            component_name(&component.name, locale)
+        );
+        if let Some(since) = view.since.get(&component.component_id) {
+            line.push_str(" · ");
+            line.push_str(&format_elapsed(now - *since, locale));
+        }
+        if let Some((_, lines)) = fields.last_mut() {
+            lines.push(line);
+        }
+    }
+    for (title, lines) in fields {
+        embed = embed.field(title, lines.join("\n"), false);
+    }
+
+    if total == 1 {
+        return (embed, Vec::new());
+    }
+
+    embed = embed.footer(CreateEmbedFooter::new(t!(
+        "embeds.components.page",
+        page = page + 1,
+        total = total,
+        locale = locale
+    )));
+    let buttons = vec![
+        CreateButton::new(page_button_id(page.saturating_sub(1)))
+            .label(t!("embeds.components.button_previous", locale = locale))
+            .style(ButtonStyle::Secondary)
+            .disabled(page == 0),
+        CreateButton::new(page_button_id((page + 1).min(total - 1)))
+            .label(t!("embeds.components.button_next", locale = locale))
+            .style(ButtonStyle::Secondary)
+            .disabled(page + 1 == total),
+    ];
+    (embed, vec![CreateActionRow::Buttons(buttons)])
+}
+
+/// Generate button ID for a page of /status components
+fn page_button_id(page: usize) -> String {
+    button_id_with_context(MODULE, ACTION_COMPONENTS_PAGE, CONTEXT_PAGE, page)
+}
+
+/// Time in the current status, in days and hours down to minutes
+fn format_elapsed(elapsed: Duration, locale: &str) -> String {
+    let minutes = elapsed.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        t!(
+            "embeds.components.duration_days",
+            d = days,
+            h = hours,
+            locale = locale
+        )
+        .to_string()
+    } else if hours > 0 {
+        t!(
+            "embeds.components.duration_hours",
+            h = hours,
+            m = minutes,
+            locale = locale
+        )
+        .to_string()
+    } else {
+        t!(
+            "embeds.components.duration_minutes",
+            m = minutes,
+            locale = locale
+        )
+        .to_string()
+    }
+}
+
+fn error_embed(locale: &str) -> CreateEmbed {
+    embeds::localized_error_embed(
+        t!("embeds.components.error_description", locale = locale),
+        locale,
+    )
+}
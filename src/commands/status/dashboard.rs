@@ -1,34 +1,88 @@
-//! /status dashboard command
+//! /status dashboard subcommand
+//!
+//! The optional `timeframe` picks the charted window; without it the
+//! dashboard shows the last 12 hours. When the newest metric point is older
+//! than [`STALE_INTERVALS`] metrics polling intervals (e.g. the poller keeps
+//! failing), the footer warns that the data may be outdated and the embed is
+//! tinted yellow regardless of the VRChat indicator.
 
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Utc};
 use rust_i18n::t;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
 use serenity::all::{
-    Colour, CommandInteraction, Context, CreateAttachment, CreateCommand, CreateEmbed,
-    CreateEmbedFooter, Timestamp,
+    Colour, CommandInteraction, Context, CreateAttachment, CreateEmbed, CreateEmbedFooter,
+    ResolvedValue, Timestamp,
 };
-use tracing::error;
+use tracing::{error, warn};
 
-use crate::commands::shared::{colors, defer, embeds};
-use crate::entity::{component_logs, status_logs};
-use crate::i18n::resolve_locale_async;
+use crate::commands::shared::components::{component_name, status_emoji};
+use crate::commands::shared::{colors, defer, embeds, links};
+use crate::entity::{component_logs, metric_logs, status_logs};
+use crate::i18n::{relative_time, resolve_locale_async};
 use crate::state::AppStateKey;
 use crate::visualization::generate_dashboard;
+use crate::visualization::uptime::component_uptime;
+
+/// Selectable dashboard time ranges: option value and length in hours
+///
+/// Labels are `embeds.dashboard.timeframe.<value>`.
+pub const TIMEFRAMES: [(&str, i64); 6] = [
+    ("1h", 1),
+    ("6h", 6),
+    ("12h", 12),
+    ("24h", 24),
+    ("7d", 7 * 24),
+    ("30d", 30 * 24),
+];
 
-/// /status command definition
-pub fn register() -> CreateCommand {
-    CreateCommand::new("status")
-        .description(t!("commands.status.description"))
-        .name_localized("ko", t!("commands.status.name", locale = "ko"))
-        .description_localized("ko", t!("commands.status.description", locale = "ko"))
+/// Time range used when `timeframe` is omitted
+const DEFAULT_TIMEFRAME: &str = "12h";
+
+/// Days covered by the uptime field
+const UPTIME_DAYS: i64 = 30;
+
+/// Metrics polling intervals after which the newest point counts as stale
+const STALE_INTERVALS: u32 = 3;
+
+/// Age of the newest metric point if it is older than [`STALE_INTERVALS`]
+/// polling intervals, `None` while fresh or before any point is stored
+fn staleness(
+    latest: Option<DateTime<Utc>>,
+    interval: StdDuration,
+    now: DateTime<Utc>,
+) -> Option<chrono::Duration> {
+    let age = now - latest?;
+    let limit = chrono::Duration::from_std(interval * STALE_INTERVALS).ok()?;
+    (age > limit).then_some(age)
 }
 
-/// /status command handler
-pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
-    // Defer response since dashboard generation takes time
-    defer::defer(ctx, interaction).await?;
+/// Option value and length in hours of a timeframe, or the default if unknown
+pub(super) fn resolve_timeframe(value: Option<&str>) -> (&'static str, i64) {
+    let find = |wanted: &str| TIMEFRAMES.iter().find(|(key, _)| *key == wanted).copied();
+    value
+        .and_then(find)
+        .or_else(|| find(DEFAULT_TIMEFRAME))
+        .expect("default timeframe is listed")
+}
 
+/// Handle /status dashboard [timeframe] (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
     let locale = resolve_locale_async(ctx, interaction).await;
 
+    let options = interaction.data.options();
+    let timeframe = match options.first().map(|opt| &opt.value) {
+        Some(ResolvedValue::SubCommand(opts)) => {
+            opts.iter().find_map(|opt| match (opt.name, &opt.value) {
+                ("timeframe", ResolvedValue::String(s)) => Some(*s),
+                _ => None,
+            })
+        }
+        _ => None,
+    };
+    let (timeframe, hours) = resolve_timeframe(timeframe);
+
     // Get database from AppState
     let data = ctx.data.read().await;
     let state = data
@@ -45,9 +99,24 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         .ok()
         .flatten();
 
+    // Newest stored metric point, to warn when the charts stopped updating
+    let latest_metric = metric_logs::Entity::find()
+        .select_only()
+        .column(metric_logs::Column::CreatedAt)
+        .order_by_desc(metric_logs::Column::CreatedAt)
+        .into_tuple::<DateTime<Utc>>()
+        .one(db)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load latest metric time");
+            None
+        });
+    let metrics_interval = *state.collector_config.metrics.borrow();
+    let stale_for = staleness(latest_metric, metrics_interval, Utc::now());
+
     // Fetch latest component statuses (limit to recent data to avoid loading entire history)
     // We only need the most recent status for each component
-    use chrono::{Duration, Utc};
+    use chrono::Duration;
 
     let recent_cutoff = Utc::now() - Duration::hours(24);
     let components = component_logs::Entity::find()
@@ -64,8 +133,26 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         .filter(|c| seen_components.insert(c.component_id.clone()))
         .collect();
 
+    // Uptime of the two main groups, independent of the charted timeframe
+    let mut uptimes = Vec::new();
+    for (group_id, label_key) in [
+        (GROUP_API_WEBSITE, "embeds.dashboard.group_api_website"),
+        (
+            GROUP_REALTIME_NETWORKING,
+            "embeds.dashboard.group_realtime_networking",
+        ),
+    ] {
+        let uptime = component_uptime(db, group_id, Duration::days(UPTIME_DAYS))
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, group_id, "Failed to compute component uptime");
+                None
+            });
+        uptimes.push((label_key, uptime));
+    }
+
     // Generate dashboard
-    let result = generate_dashboard(db).await;
+    let result = generate_dashboard(db, &state.metric_cache, Duration::hours(hours), &locale).await;
 
     match result {
         Ok((png_bytes, stats)) => {
@@ -88,6 +175,13 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
                 ),
             };
 
+            // Outdated charts matter more than the reported indicator
+            let embed_color = if stale_for.is_some() {
+                colors::WARNING
+            } else {
+                embed_color
+            };
+
             // Format component statuses
             let component_fields = format_component_groups(&latest_components, &locale);
 
@@ -107,6 +201,7 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
 
             let mut embed = CreateEmbed::default()
                 .title(t!("embeds.dashboard.title", locale = &locale))
+                .url(links::status_page())
                 .color(Colour::new(embed_color))
                 .image("attachment://dashboard.png")
                 .field(
@@ -124,7 +219,11 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
                     format!("{:.4}%", stats.api_error_rate_avg),
                     true,
                 )
-                .field("\u{200B}", "\u{200B}", true)
+                .field(
+                    t!("embeds.dashboard.api_latency_p95", locale = &locale),
+                    format!("{:.0} ms", stats.api_latency_p95),
+                    true,
+                )
                 .field(
                     t!("embeds.dashboard.steam_auth", locale = &locale),
                     format!("{:.1}%", stats.steam_success_avg),
@@ -135,7 +234,16 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
                     format!("{:.1}%", stats.meta_success_avg),
                     true,
                 )
-                .field("\u{200B}", "\u{200B}", true);
+                .field(
+                    t!("embeds.dashboard.user_reports_24h", locale = &locale),
+                    stats.reports_24h.to_string(),
+                    true,
+                )
+                .field(
+                    t!("embeds.dashboard.uptime_30d", locale = &locale),
+                    format_uptimes(&uptimes, &locale),
+                    false,
+                );
 
             // Add component group fields
             for (name, value, inline) in component_fields {
@@ -143,9 +251,11 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
             }
 
             let embed = embed
-                .footer(CreateEmbedFooter::new(t!(
-                    "embeds.dashboard.footer_timeframe",
-                    locale = &locale
+                .footer(CreateEmbedFooter::new(format_footer(
+                    timeframe,
+                    stats.empty_charts.len(),
+                    stale_for,
+                    &locale,
                 )))
                 .timestamp(Timestamp::now());
 
@@ -160,9 +270,9 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
         Err(e) => {
             error!(error = %e, "Failed to generate dashboard");
 
-            let embed = embeds::error_embed(
-                t!("embeds.dashboard.error_title", locale = &locale),
+            let embed = embeds::localized_error_embed(
                 t!("embeds.dashboard.error_description", locale = &locale),
+                &locale,
             );
 
             defer::edit_embed(ctx, interaction, embed).await?;
@@ -172,6 +282,60 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(),
     Ok(())
 }
 
+/// Footer with the timeframe and how many charts have no data yet
+///
+/// Stale data replaces the footer with a warning naming the last update.
+fn format_footer(
+    timeframe: &str,
+    empty_charts: usize,
+    stale_for: Option<chrono::Duration>,
+    locale: &str,
+) -> String {
+    if let Some(age) = stale_for {
+        return t!(
+            "embeds.dashboard.footer_stale",
+            age = relative_time(age, locale),
+            locale = locale
+        )
+        .to_string();
+    }
+
+    let key = format!("embeds.dashboard.timeframe.{}", timeframe);
+    let timeframe = t!(&key, locale = locale);
+    match empty_charts {
+        0 => timeframe.to_string(),
+        1 => format!(
+            "{} · {}",
+            timeframe,
+            t!("embeds.dashboard.footer_empty_one", locale = locale)
+        ),
+        count => format!(
+            "{} · {}",
+            timeframe,
+            t!(
+                "embeds.dashboard.footer_empty_many",
+                count = count,
+                locale = locale
+            )
+        ),
+    }
+}
+
+/// One line per group: name and uptime percentage, or "No data"
+fn format_uptimes(uptimes: &[(&str, Option<f64>)], locale: &str) -> String {
+    uptimes
+        .iter()
+        .map(|(label_key, uptime)| {
+            let value = match uptime {
+                Some(fraction) => format!("{:.2}%", fraction * 100.0),
+                None => t!("embeds.dashboard.no_data", locale = locale).to_string(),
+            };
+            format!("{}: {}", t!(*label_key, locale = locale), value)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 // Component group IDs (hardcoded from VRChat status API)
 const GROUP_API_WEBSITE: &str = "64b3rr3cxgk5";
 const GROUP_REALTIME_NETWORKING: &str = "t1jm7fqqq43h";
@@ -191,6 +355,19 @@ const REALTIME_NETWORKING_CHILDREN: &[&str] = &[
     "3rv208r2qv7z", // Japan (Tokyo)
 ];
 
+/// Group a component belongs to in the hardcoded lists above
+///
+/// Used for rows collected before `component_logs.group_id` existed.
+pub(super) fn known_group(component_id: &str) -> Option<&'static str> {
+    if API_WEBSITE_CHILDREN.contains(&component_id) {
+        Some(GROUP_API_WEBSITE)
+    } else if REALTIME_NETWORKING_CHILDREN.contains(&component_id) {
+        Some(GROUP_REALTIME_NETWORKING)
+    } else {
+        None
+    }
+}
+
 /// Format component statuses into grouped embed fields
 fn format_component_groups(
     components: &[component_logs::Model],
@@ -215,36 +392,12 @@ fn format_component_groups(
         })
         .collect();
 
-    let format_status = |status: &str| -> &str {
-        match status {
-            "operational" => "🟢",
-            "degraded_performance" => "🟡",
-            "partial_outage" => "🟠",
-            "major_outage" => "🔴",
-            "under_maintenance" => "🔵",
-            _ => "⚪",
-        }
-    };
-
-    // Translate component name
-    let translate_component = |name: &str| -> String {
-        // Try to get localized name, fall back to original
-        let key = format!("components.{}", name);
-        let translated = t!(&key, locale = locale);
-        // If translation key doesn't exist, rust-i18n returns the key itself
-        if translated.contains("components.") {
-            name.to_string()
-        } else {
-            translated.to_string()
-        }
-    };
-
     let format_group = |children: &[&str]| -> String {
         children
             .iter()
             .filter_map(|id| component_map.get(id))
             .map(|(name, status)| {
-                format!("{} {}", format_status(status), translate_component(name))
+                format!("{} {}", status_emoji(status), component_name(name, locale))
             })
             .collect::<Vec<_>>()
             .join("\n")
@@ -253,11 +406,11 @@ fn format_component_groups(
     // Get group statuses
     let api_status = component_map
         .get(GROUP_API_WEBSITE)
-        .map(|(_, s)| format_status(s))
+        .map(|(_, s)| status_emoji(s))
         .unwrap_or("⚪");
     let network_status = component_map
         .get(GROUP_REALTIME_NETWORKING)
-        .map(|(_, s)| format_status(s))
+        .map(|(_, s)| status_emoji(s))
         .unwrap_or("⚪");
 
     vec![
@@ -284,3 +437,148 @@ fn format_component_groups(
         ),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    const INTERVAL: StdDuration = StdDuration::from_secs(60);
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_no_metrics_not_stale() {
+        assert_eq!(staleness(None, INTERVAL, now()), None);
+    }
+
+    #[test]
+    fn test_recent_point_not_stale() {
+        let latest = now() - Duration::seconds(90);
+
+        assert_eq!(staleness(Some(latest), INTERVAL, now()), None);
+    }
+
+    #[test]
+    fn test_stale_after_three_intervals() {
+        let at_limit = now() - Duration::minutes(3);
+        let past_limit = now() - Duration::minutes(3) - Duration::seconds(1);
+
+        assert_eq!(staleness(Some(at_limit), INTERVAL, now()), None);
+        assert_eq!(
+            staleness(Some(past_limit), INTERVAL, now()),
+            Some(Duration::seconds(181))
+        );
+    }
+
+    #[test]
+    fn test_reports_full_age() {
+        let latest = now() - Duration::minutes(47);
+
+        assert_eq!(
+            staleness(Some(latest), INTERVAL, now()),
+            Some(Duration::minutes(47))
+        );
+    }
+
+    #[test]
+    fn test_limit_scales_with_interval() {
+        let latest = now() - Duration::minutes(10);
+
+        assert_eq!(
+            staleness(Some(latest), StdDuration::from_secs(300), now()),
+            None
+        );
+        assert!(staleness(Some(latest), StdDuration::from_secs(120), now()).is_some());
+    }
+
+    #[test]
+    fn test_future_point_not_stale() {
+        let latest = now() + Duration::minutes(5);
+
+        assert_eq!(staleness(Some(latest), INTERVAL, now()), None);
+    }
+
+    #[test]
+    fn test_huge_interval_never_stale() {
+        let latest = now() - Duration::days(365);
+
+        assert_eq!(
+            staleness(Some(latest), StdDuration::from_secs(u64::MAX / 4), now()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_footer_without_empty_charts() {
+        assert_eq!(format_footer("12h", 0, None, "en"), "Last 12 hours");
+        assert_eq!(format_footer("7d", 0, None, "en"), "Last 7 days");
+    }
+
+    #[test]
+    fn test_footer_counts_empty_charts() {
+        assert_eq!(
+            format_footer("12h", 1, None, "en"),
+            "Last 12 hours · 1 metric has no data yet"
+        );
+        assert_eq!(
+            format_footer("1h", 4, None, "en"),
+            "Last hour · 4 metrics have no data yet"
+        );
+    }
+
+    #[test]
+    fn test_stale_footer_replaces_timeframe() {
+        let footer = format_footer("12h", 2, Some(Duration::minutes(45)), "en");
+
+        assert_eq!(footer, "⚠️ Data may be outdated — last update 45 min ago");
+    }
+
+    #[test]
+    fn test_footer_placeholders_substituted_in_every_locale() {
+        for locale in rust_i18n::available_locales!() {
+            for (timeframe, _) in TIMEFRAMES {
+                for empty in [0, 1, 2, 6] {
+                    let footer = format_footer(timeframe, empty, None, locale);
+
+                    assert!(!footer.contains("%{"), "{locale}: {footer}");
+                    assert!(
+                        !footer.contains("embeds."),
+                        "{locale}: missing key in {footer}"
+                    );
+                    if empty > 1 {
+                        assert!(footer.contains(&empty.to_string()), "{locale}: {footer}");
+                    }
+                }
+            }
+            let stale = format_footer("12h", 0, Some(Duration::hours(3)), locale);
+            assert!(!stale.contains("%{"), "{locale}: {stale}");
+            assert!(stale.contains('3'), "{locale}: {stale}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_placeholders_left_as_is() {
+        // An argument the template doesn't use is ignored, and a placeholder
+        // without an argument stays visible instead of vanishing
+        let unused = t!(
+            "embeds.dashboard.footer_empty_many",
+            count = 3,
+            n = 9,
+            locale = "en"
+        );
+        let missing = t!("embeds.dashboard.footer_empty_many", locale = "en");
+
+        assert_eq!(unused, "3 metrics have no data yet");
+        assert_eq!(missing, "%{count} metrics have no data yet");
+    }
+
+    #[test]
+    fn test_unknown_timeframe_uses_default() {
+        assert_eq!(resolve_timeframe(None), ("12h", 12));
+        assert_eq!(resolve_timeframe(Some("90m")), ("12h", 12));
+        assert_eq!(resolve_timeframe(Some("7d")), ("7d", 168));
+    }
+}
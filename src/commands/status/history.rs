@@ -0,0 +1,264 @@
+//! /status history - recent incidents from the VRChat status page
+//!
+//! Lists the latest incidents recorded by the collector, newest first, with
+//! their impact, start and resolution times, and the most recent update.
+//! With `resolved`, only resolved incidents are listed, most recently
+//! resolved first.
+
+use std::collections::HashMap;
+
+use rust_i18n::t;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serenity::all::{
+    Colour, CommandInteraction, Context, CreateEmbed, CreateEmbedFooter, ResolvedValue, Timestamp,
+};
+use tracing::error;
+
+use crate::commands::shared::{colors, defer, embeds, links, text};
+use crate::entity::{incident_updates, incidents};
+use crate::i18n::resolve_locale_async;
+use crate::state::AppStateKey;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Incidents shown when `count` is omitted
+pub const DEFAULT_COUNT: u64 = 5;
+
+/// Largest accepted `count`
+pub const MAX_COUNT: u64 = 20;
+
+/// Longest incident title shown
+const TITLE_CHARS: usize = 100;
+
+/// Longest update body shown
+const UPDATE_CHARS: usize = 200;
+
+/// Characters of update text shared by all incidents
+///
+/// Keeps a 20-incident embed, status page links included, under Discord's
+/// 6000-character total; each body gets an equal share, at most
+/// [`UPDATE_CHARS`].
+const UPDATE_BUDGET: usize = 1000;
+
+// =============================================================================
+// Formatting
+// =============================================================================
+
+/// Emoji and embed color for a status page impact level
+pub fn impact_style(impact: &str) -> (&'static str, u32) {
+    match impact {
+        "none" => ("🟢", colors::SUCCESS),
+        "minor" => ("🟡", colors::WARNING),
+        "major" => ("🟠", colors::MAJOR),
+        "critical" => ("🔴", colors::ERROR),
+        _ => ("⚪", colors::BRAND),
+    }
+}
+
+/// Longest update body for a list of `count` incidents
+pub fn update_chars(count: usize) -> usize {
+    (UPDATE_BUDGET / count.max(1)).min(UPDATE_CHARS)
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Handle /status history [count] [resolved] (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+    let count = requested_count(interaction);
+    let resolved_only = requested_resolved(interaction);
+
+    let db = {
+        let data = ctx.data.read().await;
+        let state = data
+            .get::<AppStateKey>()
+            .expect("AppState not found in TypeMap")
+            .read()
+            .await;
+        state.database.clone()
+    };
+
+    let history = match load_history(&db, count, resolved_only).await {
+        Ok(history) => history,
+        Err(e) => {
+            error!(error = %e, "Failed to load incident history");
+            let embed = embeds::localized_error_embed(
+                t!("embeds.history.error_description", locale = &locale),
+                &locale,
+            );
+            return defer::edit_embed(ctx, interaction, embed).await;
+        }
+    };
+
+    let embed = if history.is_empty() {
+        let description = if resolved_only {
+            "embeds.history.empty_resolved_description"
+        } else {
+            "embeds.history.empty_description"
+        };
+        embeds::info_embed(
+            t!("embeds.history.empty_title", locale = &locale),
+            t!(description, locale = &locale),
+        )
+    } else {
+        history_embed(&history, resolved_only, &locale)
+    };
+    defer::edit_embed(ctx, interaction, embed).await
+}
+
+/// `count` option, clamped to `1..=MAX_COUNT`
+fn requested_count(interaction: &CommandInteraction) -> u64 {
+    let options = interaction.data.options();
+    let count = options
+        .first()
+        .and_then(|subcommand| match &subcommand.value {
+            ResolvedValue::SubCommand(options) => options.iter().find_map(|opt| match opt.value {
+                ResolvedValue::Integer(n) if opt.name == "count" => Some(n),
+                _ => None,
+            }),
+            _ => None,
+        });
+    count.map_or(DEFAULT_COUNT, |n| (n.max(1) as u64).min(MAX_COUNT))
+}
+
+/// `resolved` option, false if omitted
+fn requested_resolved(interaction: &CommandInteraction) -> bool {
+    let options = interaction.data.options();
+    options
+        .first()
+        .is_some_and(|subcommand| match &subcommand.value {
+            ResolvedValue::SubCommand(options) => options.iter().any(|opt| {
+                opt.name == "resolved" && matches!(opt.value, ResolvedValue::Boolean(true))
+            }),
+            _ => false,
+        })
+}
+
+/// Latest incidents, each with its most recent update
+///
+/// Newest first by start time, or with `resolved_only` only resolved
+/// incidents by resolution time.
+async fn load_history(
+    db: &DatabaseConnection,
+    count: u64,
+    resolved_only: bool,
+) -> Result<Vec<(incidents::Model, Option<incident_updates::Model>)>, DbErr> {
+    let query = if resolved_only {
+        incidents::Entity::find()
+            .filter(incidents::Column::ResolvedAt.is_not_null())
+            .order_by_desc(incidents::Column::ResolvedAt)
+    } else {
+        incidents::Entity::find().order_by_desc(incidents::Column::StartedAt)
+    };
+    let incidents = query.limit(count).all(db).await?;
+    if incidents.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ids: Vec<&str> = incidents
+        .iter()
+        .map(|incident| incident.id.as_str())
+        .collect();
+    let updates = incident_updates::Entity::find()
+        .filter(incident_updates::Column::IncidentId.is_in(ids))
+        .order_by_desc(incident_updates::Column::PublishedAt)
+        .all(db)
+        .await?;
+
+    // Newest first, so the first update seen per incident is the latest
+    let mut latest: HashMap<String, incident_updates::Model> = HashMap::new();
+    for update in updates {
+        latest.entry(update.incident_id.clone()).or_insert(update);
+    }
+
+    Ok(incidents
+        .into_iter()
+        .map(|incident| {
+            let update = latest.remove(&incident.id);
+            (incident, update)
+        })
+        .collect())
+}
+
+/// Build the timeline embed, colored by the first incident's impact
+fn history_embed(
+    history: &[(incidents::Model, Option<incident_updates::Model>)],
+    resolved_only: bool,
+    locale: &str,
+) -> CreateEmbed {
+    let color = history.first().map_or(colors::BRAND, |(incident, _)| {
+        impact_style(&incident.impact).1
+    });
+    let body_chars = update_chars(history.len());
+
+    let embed = CreateEmbed::default()
+        .title(t!("embeds.history.title", locale = locale))
+        .description(t!(
+            if resolved_only {
+                "embeds.history.description_resolved"
+            } else {
+                "embeds.history.description"
+            },
+            count = history.len(),
+            locale = locale
+        ))
+        .color(Colour::new(color));
+
+    history
+        .iter()
+        .fold(embed, |embed, (incident, update)| {
+            let (emoji, _) = impact_style(&incident.impact);
+            embed.field(
+                format!("{} {}", emoji, text::truncate(&incident.title, TITLE_CHARS)),
+                incident_value(incident, update.as_ref(), body_chars, locale),
+                false,
+            )
+        })
+        .footer(CreateEmbedFooter::new(t!(
+            "embeds.history.footer",
+            locale = locale
+        )))
+        .timestamp(Timestamp::now())
+}
+
+/// Timeline lines for one incident
+fn incident_value(
+    incident: &incidents::Model,
+    update: Option<&incident_updates::Model>,
+    body_chars: usize,
+    locale: &str,
+) -> String {
+    let started = t!(
+        "embeds.history.started",
+        time = format!("<t:{}:f>", incident.started_at.timestamp()),
+        locale = locale
+    );
+    let resolved = match incident.resolved_at {
+        Some(resolved_at) => t!(
+            "embeds.history.resolved_lasted",
+            time = format!("<t:{}:R>", resolved_at.timestamp()),
+            duration = text::format_duration(resolved_at - incident.started_at, locale),
+            locale = locale
+        ),
+        None => t!("embeds.history.ongoing", locale = locale),
+    };
+    let latest = match update {
+        Some(update) => format!(
+            "> {}",
+            text::truncate(&update.body.replace('\n', " "), body_chars)
+        ),
+        None => t!("embeds.history.no_updates", locale = locale).to_string(),
+    };
+    let link = t!(
+        "embeds.history.view_on_status_page",
+        url = links::incident_url(&incident.id),
+        locale = locale
+    );
+    format!("{}\n{}\n{}\n{}", started, resolved, latest, link)
+}
@@ -0,0 +1,267 @@
+//! /status incident - details of one status page incident
+//!
+//! The `query` option autocompletes recorded incidents by title, with the
+//! incident ID as the value. Typing an ID or a title without picking a
+//! suggestion also works: an exact ID wins, otherwise the newest incident
+//! whose title contains the text is shown.
+
+use chrono::Utc;
+use rust_i18n::t;
+use sea_orm::DbErr;
+use serenity::all::{
+    Colour, CommandInteraction, Context, CreateAutocompleteResponse, CreateEmbed,
+    CreateEmbedFooter, CreateInteractionResponse, ResolvedValue, Timestamp,
+};
+use tracing::error;
+
+use crate::commands::shared::components::label;
+use crate::commands::shared::{defer, embeds, links, text};
+use crate::database;
+use crate::entity::{incident_updates, incidents};
+use crate::i18n::resolve_locale_async;
+use crate::repository::IncidentRepository;
+
+use super::history::impact_style;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Most suggestions Discord accepts
+const MAX_SUGGESTIONS: u64 = 25;
+
+/// Longest suggestion label Discord accepts
+const SUGGESTION_CHARS: usize = 100;
+
+/// Longest incident title in the embed title
+const TITLE_CHARS: usize = 200;
+
+/// Most updates shown; older ones are left out
+const MAX_UPDATES: usize = 20;
+
+/// Longest update body shown
+const UPDATE_CHARS: usize = 400;
+
+/// Characters of update text shared by the whole timeline
+///
+/// Keeps the embed under Discord's 6000-character total; each body gets an
+/// equal share, at most [`UPDATE_CHARS`].
+const UPDATE_BUDGET: usize = 4000;
+
+// =============================================================================
+// Autocomplete
+// =============================================================================
+
+/// Suggest incidents whose title contains the typed text
+pub async fn autocomplete(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), serenity::Error> {
+    let typed = interaction
+        .data
+        .autocomplete()
+        .map_or("", |option| option.value);
+
+    let db = database::get_db(ctx).await;
+    let incidents = match IncidentRepository::new(db)
+        .search(typed, MAX_SUGGESTIONS)
+        .await
+    {
+        Ok(incidents) => incidents,
+        Err(e) => {
+            error!(error = %e, "Failed to search incidents for autocomplete");
+            Vec::new()
+        }
+    };
+
+    let response = incidents
+        .iter()
+        .fold(CreateAutocompleteResponse::new(), |response, incident| {
+            response.add_string_choice(suggestion_label(incident), &incident.id)
+        });
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+        .await
+}
+
+/// Suggestion text: impact emoji, title, and start date
+fn suggestion_label(incident: &incidents::Model) -> String {
+    let (emoji, _) = impact_style(&incident.impact);
+    let date = incident.started_at.format(" · %Y-%m-%d").to_string();
+    let title_chars = SUGGESTION_CHARS - emoji.chars().count() - 1 - date.chars().count();
+    format!(
+        "{} {}{}",
+        emoji,
+        text::truncate(&incident.title, title_chars),
+        date
+    )
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Handle /status incident <query> (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+    let query = requested_query(interaction);
+
+    let db = database::get_db(ctx).await;
+    let repo = IncidentRepository::new(db);
+
+    let found = match find_incident(&repo, &query).await {
+        Ok(found) => found,
+        Err(e) => {
+            error!(error = %e, "Failed to load incident");
+            let embed = embeds::localized_error_embed(
+                t!("embeds.incident.error_description", locale = &locale),
+                &locale,
+            );
+            return defer::edit_embed(ctx, interaction, embed).await;
+        }
+    };
+
+    let embed = match found {
+        Some((incident, updates)) => incident_embed(&incident, &updates, &locale),
+        None => embeds::info_embed(
+            t!("embeds.incident.not_found_title", locale = &locale),
+            t!(
+                "embeds.incident.not_found_description",
+                query = text::truncate(&query, SUGGESTION_CHARS),
+                locale = &locale
+            ),
+        ),
+    };
+    defer::edit_embed(ctx, interaction, embed).await
+}
+
+/// `query` option (required)
+fn requested_query(interaction: &CommandInteraction) -> String {
+    let options = interaction.data.options();
+    options
+        .first()
+        .and_then(|subcommand| match &subcommand.value {
+            ResolvedValue::SubCommand(options) => options.iter().find_map(|opt| match opt.value {
+                ResolvedValue::String(value) if opt.name == "query" => Some(value.to_string()),
+                _ => None,
+            }),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Incident by exact ID, or else the newest one whose title matches
+async fn find_incident(
+    repo: &IncidentRepository,
+    query: &str,
+) -> Result<Option<(incidents::Model, Vec<incident_updates::Model>)>, DbErr> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(None);
+    }
+
+    let incident = match repo.get(query).await? {
+        Some(incident) => incident,
+        None => match repo.search(query, 1).await?.into_iter().next() {
+            Some(incident) => incident,
+            None => return Ok(None),
+        },
+    };
+    let updates = repo.updates(&incident.id).await?;
+    Ok(Some((incident, updates)))
+}
+
+// =============================================================================
+// Embed
+// =============================================================================
+
+/// Build the incident embed with its full update timeline, oldest first
+fn incident_embed(
+    incident: &incidents::Model,
+    updates: &[incident_updates::Model],
+    locale: &str,
+) -> CreateEmbed {
+    let (emoji, color) = impact_style(&incident.impact);
+
+    let resolved = match incident.resolved_at {
+        Some(resolved_at) => format!(
+            "{}\n{}",
+            t!(
+                "embeds.incident.resolved",
+                time = format!("<t:{}:f>", resolved_at.timestamp()),
+                locale = locale
+            ),
+            t!(
+                "embeds.incident.duration",
+                duration = text::format_duration(resolved_at - incident.started_at, locale),
+                locale = locale
+            )
+        ),
+        None => t!(
+            "embeds.incident.ongoing",
+            duration = text::format_duration(Utc::now() - incident.started_at, locale),
+            locale = locale
+        )
+        .to_string(),
+    };
+    let description = format!(
+        "{}\n{}\n{}",
+        t!(
+            "embeds.incident.impact",
+            impact = label("embeds.incident.impact_level", &incident.impact, locale),
+            locale = locale
+        ),
+        t!(
+            "embeds.incident.started",
+            time = format!("<t:{}:f>", incident.started_at.timestamp()),
+            locale = locale
+        ),
+        resolved
+    );
+
+    let mut embed = CreateEmbed::default()
+        .title(format!(
+            "{} {}",
+            emoji,
+            text::truncate(&incident.title, TITLE_CHARS)
+        ))
+        .url(links::incident_url(&incident.id))
+        .description(description)
+        .color(Colour::new(color));
+
+    // Newest updates win when the timeline is too long for one embed
+    let shown = &updates[updates.len().saturating_sub(MAX_UPDATES)..];
+    let body_chars = (UPDATE_BUDGET / shown.len().max(1)).min(UPDATE_CHARS);
+    if shown.is_empty() {
+        embed = embed.field(
+            t!("embeds.incident.timeline", locale = locale),
+            t!("embeds.history.no_updates", locale = locale),
+            false,
+        );
+    }
+    for update in shown {
+        embed = embed.field(
+            label("embeds.incident.update_status", &update.status, locale),
+            format!(
+                "<t:{}:f>\n> {}",
+                update.published_at.timestamp(),
+                text::truncate(&update.body.replace('\n', " "), body_chars)
+            ),
+            false,
+        );
+    }
+
+    let footer = if shown.len() < updates.len() {
+        t!(
+            "embeds.incident.footer_truncated",
+            shown = shown.len(),
+            total = updates.len(),
+            locale = locale
+        )
+    } else {
+        t!("embeds.history.footer", locale = locale)
+    };
+    embed
+        .footer(CreateEmbedFooter::new(footer))
+        .timestamp(Timestamp::now())
+}
@@ -0,0 +1,101 @@
+//! /status metric subcommand
+//!
+//! Charts one dashboard metric on its own at 1200x600, with current,
+//! minimum, average, and maximum values. The optional `timeframe` takes the
+//! same values as /status dashboard.
+
+use chrono::Duration;
+use rust_i18n::t;
+use serenity::all::{
+    Colour, CommandInteraction, Context, CreateAttachment, CreateEmbed, CreateEmbedFooter,
+    EditInteractionResponse, ResolvedValue, Timestamp,
+};
+use tracing::error;
+
+use crate::cache::get_metric_cache;
+use crate::commands::shared::{colors, defer, embeds};
+use crate::database;
+use crate::i18n::resolve_locale_async;
+use crate::visualization::metrics::{self, CHART_METRICS, ChartMetric};
+use crate::visualization::query::MetricData;
+use crate::visualization::single_chart::generate_single;
+
+use super::dashboard::resolve_timeframe;
+
+/// Handle /status metric <metric> [timeframe] (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+    let locale = resolve_locale_async(ctx, interaction).await;
+
+    let options = interaction.data.options();
+    let (metric, timeframe) = match options.first().map(|opt| &opt.value) {
+        Some(ResolvedValue::SubCommand(opts)) => {
+            let string = |name: &str| {
+                opts.iter().find_map(|opt| match &opt.value {
+                    ResolvedValue::String(s) if opt.name == name => Some(*s),
+                    _ => None,
+                })
+            };
+            (string("metric"), string("timeframe"))
+        }
+        _ => (None, None),
+    };
+    // The option is required with fixed choices; fall back to the first chart
+    let metric = metric.and_then(metrics::find).unwrap_or(&CHART_METRICS[0]);
+    let (timeframe, hours) = resolve_timeframe(timeframe);
+
+    let db = database::get_db(ctx).await;
+    let cache = get_metric_cache(ctx).await;
+    match generate_single(&db, &cache, metric, Duration::hours(hours), &locale).await {
+        Ok((png_bytes, data)) => {
+            let embed = metric_embed(metric, &data, timeframe, &locale);
+            let response = EditInteractionResponse::new()
+                .embed(embed)
+                .new_attachment(CreateAttachment::bytes(png_bytes, "metric.png"));
+            interaction.edit_response(&ctx.http, response).await?;
+            Ok(())
+        }
+        Err(e) => {
+            error!(error = %e, metric = metric.key, "Failed to generate metric chart");
+            let embed = embeds::localized_error_embed(
+                t!("embeds.metric.error_description", locale = &locale),
+                &locale,
+            );
+            defer::edit_embed(ctx, interaction, embed).await
+        }
+    }
+}
+
+/// Embed with the chart and its statistics
+fn metric_embed(
+    metric: &ChartMetric,
+    data: &MetricData,
+    timeframe: &str,
+    locale: &str,
+) -> CreateEmbed {
+    let name_key = format!("embeds.metric.names.{}", metric.key);
+    let name = t!(&name_key, locale = locale);
+    let timeframe_key = format!("embeds.dashboard.timeframe.{}", timeframe);
+    let timeframe_label = t!(&timeframe_key, locale = locale);
+
+    let mut embed = CreateEmbed::default()
+        .title(t!("embeds.metric.title", metric = name, locale = locale))
+        .color(Colour::new(colors::BRAND))
+        .image("attachment://metric.png")
+        .footer(CreateEmbedFooter::new(timeframe_label))
+        .timestamp(Timestamp::now());
+
+    let Some(current) = data.latest() else {
+        return embed.description(t!("embeds.metric.no_data", locale = locale));
+    };
+
+    let format = |value: f64| metric.value_format.format(value);
+    for (key, value) in [
+        ("embeds.metric.field_current", current),
+        ("embeds.metric.field_min", data.min()),
+        ("embeds.metric.field_avg", data.avg()),
+        ("embeds.metric.field_max", data.max()),
+    ] {
+        embed = embed.field(t!(key, locale = locale), format(value), true);
+    }
+    embed
+}
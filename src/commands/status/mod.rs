@@ -1,12 +1,256 @@
 //! Status commands module
+//!
+//! `/status dashboard` shows VRChat metrics; `/status metric` charts one of
+//! them in detail; `/status components` lists every status page component;
+//! `/status history` lists recent status page incidents; `/status incident`
+//! shows one incident with its full timeline; `/status community` shows
+//! aggregate VRCPulse usage.
 
+mod community;
+mod components;
 mod dashboard;
+pub mod history;
+mod incident;
+mod metric;
 
-use serenity::all::CreateCommand;
+pub use community::CommunityStatsCache;
 
-/// Returns all status command definitions
-pub fn all() -> Vec<CreateCommand> {
-    vec![dashboard::register()]
+use rust_i18n::t;
+use serenity::all::{
+    CommandInteraction, CommandOptionType, ComponentInteraction, Context, CreateCommand,
+    CreateCommandOption,
+};
+
+use dashboard::TIMEFRAMES;
+use history::MAX_COUNT;
+
+use crate::visualization::metrics::CHART_METRICS;
+
+/// /status command definition
+pub fn register() -> CreateCommand {
+    CreateCommand::new("status")
+        .description(t!("commands.status.description"))
+        .name_localized("ko", t!("commands.status.name", locale = "ko"))
+        .description_localized("ko", t!("commands.status.description", locale = "ko"))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "dashboard",
+                t!("commands.status.dashboard.description"),
+            )
+            .name_localized("ko", t!("commands.status.dashboard.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.status.dashboard.description", locale = "ko"),
+            )
+            .add_sub_option(timeframe_option()),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "metric",
+                t!("commands.status.metric.description"),
+            )
+            .name_localized("ko", t!("commands.status.metric.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.status.metric.description", locale = "ko"),
+            )
+            .add_sub_option(metric_option())
+            .add_sub_option(timeframe_option()),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "components",
+                t!("commands.status.components.description"),
+            )
+            .name_localized("ko", t!("commands.status.components.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.status.components.description", locale = "ko"),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "history",
+                t!("commands.status.history.description"),
+            )
+            .name_localized("ko", t!("commands.status.history.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.status.history.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "count",
+                    t!("commands.status.history.option_count"),
+                )
+                .name_localized(
+                    "ko",
+                    t!("commands.status.history.option_count_name", locale = "ko"),
+                )
+                .description_localized(
+                    "ko",
+                    t!("commands.status.history.option_count", locale = "ko"),
+                )
+                .min_int_value(1)
+                .max_int_value(MAX_COUNT)
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "resolved",
+                    t!("commands.status.history.option_resolved"),
+                )
+                .name_localized(
+                    "ko",
+                    t!(
+                        "commands.status.history.option_resolved_name",
+                        locale = "ko"
+                    ),
+                )
+                .description_localized(
+                    "ko",
+                    t!("commands.status.history.option_resolved", locale = "ko"),
+                )
+                .required(false),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "incident",
+                t!("commands.status.incident.description"),
+            )
+            .name_localized("ko", t!("commands.status.incident.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.status.incident.description", locale = "ko"),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "query",
+                    t!("commands.status.incident.option_query"),
+                )
+                .name_localized(
+                    "ko",
+                    t!("commands.status.incident.option_query_name", locale = "ko"),
+                )
+                .description_localized(
+                    "ko",
+                    t!("commands.status.incident.option_query", locale = "ko"),
+                )
+                .max_length(100)
+                .set_autocomplete(true)
+                .required(true),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "community",
+                t!("commands.status.community.description"),
+            )
+            .name_localized("ko", t!("commands.status.community.name", locale = "ko"))
+            .description_localized(
+                "ko",
+                t!("commands.status.community.description", locale = "ko"),
+            ),
+        )
+}
+
+/// /status command handler (deferred by the dispatcher)
+pub async fn run(ctx: &Context, interaction: &CommandInteraction) -> Result<(), serenity::Error> {
+    match interaction.data.options.first().map(|o| o.name.as_str()) {
+        Some("community") => community::run(ctx, interaction).await,
+        Some("components") => components::run(ctx, interaction).await,
+        Some("history") => history::run(ctx, interaction).await,
+        Some("incident") => incident::run(ctx, interaction).await,
+        Some("metric") => metric::run(ctx, interaction).await,
+        _ => dashboard::run(ctx, interaction).await,
+    }
+}
+
+/// /status autocomplete handler
+pub async fn autocomplete(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+) -> Result<(), serenity::Error> {
+    match interaction.data.options.first().map(|o| o.name.as_str()) {
+        Some("incident") => incident::autocomplete(ctx, interaction).await,
+        _ => Ok(()),
+    }
 }
 
-pub use dashboard::run;
+/// Handle button interactions for /status
+pub async fn handle_button(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+) -> Result<(), serenity::Error> {
+    if components::is_page_button(&interaction.data.custom_id) {
+        components::handle_page_button(ctx, interaction).await
+    } else {
+        Ok(())
+    }
+}
+
+/// Required `metric` option of /status metric
+fn metric_option() -> CreateCommandOption {
+    let mut option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "metric",
+        t!("commands.status.metric.option_metric"),
+    )
+    .name_localized(
+        "ko",
+        t!("commands.status.metric.option_metric_name", locale = "ko"),
+    )
+    .description_localized(
+        "ko",
+        t!("commands.status.metric.option_metric", locale = "ko"),
+    )
+    .required(true);
+
+    for metric in &CHART_METRICS {
+        let key = format!("embeds.metric.names.{}", metric.key);
+        option = option.add_string_choice_localized(
+            t!(&key),
+            metric.key,
+            [("ko", t!(&key, locale = "ko"))],
+        );
+    }
+    option
+}
+
+/// `timeframe` option of /status dashboard and /status metric
+fn timeframe_option() -> CreateCommandOption {
+    let mut option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "timeframe",
+        t!("commands.status.dashboard.option_timeframe"),
+    )
+    .name_localized(
+        "ko",
+        t!(
+            "commands.status.dashboard.option_timeframe_name",
+            locale = "ko"
+        ),
+    )
+    .description_localized(
+        "ko",
+        t!("commands.status.dashboard.option_timeframe", locale = "ko"),
+    )
+    .required(false);
+
+    for (value, _) in TIMEFRAMES {
+        let key = format!("embeds.dashboard.timeframe.{}", value);
+        option =
+            option.add_string_choice_localized(t!(&key), value, [("ko", t!(&key, locale = "ko"))]);
+    }
+    option
+}
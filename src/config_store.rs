@@ -0,0 +1,77 @@
+//! Cached read access to `bot_config`
+//!
+//! Hot paths (alert fan-out, report responses) read optional settings without
+//! querying the table each time. The whole table is loaded in one query and
+//! reused for [`CACHE_TTL`]; edits made directly in the database show up once
+//! the snapshot expires.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use sea_orm::{DatabaseConnection, EntityTrait};
+use tracing::warn;
+
+use crate::entity::bot_config;
+
+/// How long a loaded snapshot is reused
+pub const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// `bot_config` values keyed by `key`
+pub type ConfigValues = Arc<HashMap<String, String>>;
+
+struct Snapshot {
+    loaded_at: Instant,
+    values: ConfigValues,
+}
+
+/// Snapshot of `bot_config`, shared through `AppState`
+#[derive(Default)]
+pub struct ConfigStore {
+    snapshot: RwLock<Option<Snapshot>>,
+}
+
+impl ConfigStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current values, reloading the table when the snapshot has expired
+    ///
+    /// A failed reload keeps serving the previous snapshot (or nothing if
+    /// there is none), so callers fall back to their defaults.
+    pub async fn values(&self, db: &DatabaseConnection) -> ConfigValues {
+        if let Some(values) = self.fresh() {
+            return values;
+        }
+
+        match bot_config::Entity::find().all(db).await {
+            Ok(rows) => {
+                let values: ConfigValues =
+                    Arc::new(rows.into_iter().map(|row| (row.key, row.value)).collect());
+                *self.snapshot.write().expect("config store lock poisoned") = Some(Snapshot {
+                    loaded_at: Instant::now(),
+                    values: values.clone(),
+                });
+                values
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to load bot_config, using previous snapshot");
+                self.snapshot
+                    .read()
+                    .expect("config store lock poisoned")
+                    .as_ref()
+                    .map(|snapshot| snapshot.values.clone())
+                    .unwrap_or_default()
+            }
+        }
+    }
+
+    fn fresh(&self) -> Option<ConfigValues> {
+        let snapshot = self.snapshot.read().expect("config store lock poisoned");
+        snapshot
+            .as_ref()
+            .filter(|snapshot| snapshot.loaded_at.elapsed() < CACHE_TTL)
+            .map(|snapshot| snapshot.values.clone())
+    }
+}
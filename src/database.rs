@@ -26,3 +26,90 @@ pub async fn try_get_db(ctx: &Context) -> Option<Arc<DatabaseConnection>> {
     let state = data.get::<AppStateKey>()?;
     Some(state.read().await.database.clone())
 }
+
+/// Fresh in-memory database with every migration applied
+#[cfg(test)]
+pub async fn connect_in_memory() -> DatabaseConnection {
+    connect_in_memory_at(None).await
+}
+
+/// Fresh in-memory database with the first `migrations` applied (all if `None`)
+///
+/// For testing a data migration against rows written before it ran.
+#[cfg(test)]
+pub async fn connect_in_memory_at(migrations: Option<u32>) -> DatabaseConnection {
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ConnectOptions, Database};
+
+    let mut opts = ConnectOptions::new("sqlite::memory:");
+    // A single connection, so every query sees the same in-memory database
+    opts.max_connections(1)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("Failed to open in-memory database");
+    Migrator::up(&db, migrations)
+        .await
+        .expect("Failed to run migrations");
+    db
+}
+
+/// SQLite file in the temp directory with every migration applied, deleted
+/// on drop
+///
+/// Unlike [`connect_in_memory`], separate connections see the same data, like
+/// bot instances sharing one database.
+#[cfg(test)]
+pub struct TempDatabase {
+    path: std::path::PathBuf,
+}
+
+#[cfg(test)]
+impl TempDatabase {
+    pub async fn new() -> Self {
+        use migration::{Migrator, MigratorTrait};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "vrc-pulse-test-{}-{}.db",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = Self { path };
+        let db = file.connect().await;
+        Migrator::up(&db, None)
+            .await
+            .expect("Failed to run migrations");
+        file
+    }
+
+    /// Open a connection of its own, with the pragmas the bot uses
+    pub async fn connect(&self) -> DatabaseConnection {
+        use sea_orm::{ConnectOptions, ConnectionTrait, Database};
+
+        let mut opts = ConnectOptions::new(format!("sqlite://{}?mode=rwc", self.path.display()));
+        opts.max_connections(1)
+            .min_connections(1)
+            .sqlx_logging(false);
+        let db = Database::connect(opts)
+            .await
+            .expect("Failed to open temp database");
+        db.execute_unprepared("PRAGMA journal_mode=WAL; PRAGMA busy_timeout=5000;")
+            .await
+            .expect("Failed to set SQLite pragmas");
+        db
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
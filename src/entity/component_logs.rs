@@ -13,6 +13,7 @@ pub struct Model {
     pub status: String,
     pub source_timestamp: DateTimeUtc,
     pub created_at: DateTimeUtc,
+    pub group_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
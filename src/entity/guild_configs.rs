@@ -11,6 +11,17 @@ pub struct Model {
     pub channel_id: Option<String>,
     pub enabled: bool,
     pub language: Option<String>,
+    pub fallback_dm: bool,
+    pub paused_until: Option<DateTimeUtc>,
+    pub muted_incident_types: Option<String>,
+    pub alert_threshold: Option<i64>,
+    pub report_role_id: Option<String>,
+    pub mention_role_id: Option<String>,
+    pub digest_enabled: bool,
+    pub alert_quiet_start: Option<i32>,
+    pub alert_quiet_end: Option<i32>,
+    pub channel_invalid: bool,
+    pub consecutive_send_failures: i32,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
 }
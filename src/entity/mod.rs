@@ -2,15 +2,25 @@
 
 pub mod prelude;
 
+pub mod alert_payloads;
+pub mod alert_silences;
 pub mod bot_config;
 pub mod command_logs;
 pub mod component_logs;
+pub mod guild_alert_channels;
+pub mod guild_alert_filters;
 pub mod guild_configs;
+pub mod guild_webhooks;
 pub mod incident_updates;
 pub mod incidents;
+pub mod leases;
 pub mod maintenances;
 pub mod metric_logs;
+pub mod report_daily_aggregates;
 pub mod sent_alerts;
 pub mod status_logs;
+pub mod status_transitions;
 pub mod user_configs;
+pub mod user_notification_filters;
 pub mod user_reports;
+pub mod vrc_builds;
@@ -10,6 +10,7 @@ pub struct Model {
     pub user_id: String,
     pub enabled: bool,
     pub language: Option<String>,
+    pub disabled_reason: Option<String>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
 }
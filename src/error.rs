@@ -14,6 +14,10 @@ pub enum AppError {
     /// Discord client error
     #[error("Discord error: {0}")]
     Discord(#[from] serenity::Error),
+
+    /// Language preference write failure
+    #[error("Language error: {0}")]
+    Language(#[from] crate::repository::LanguageError),
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
@@ -0,0 +1,249 @@
+//! Cached guild and user language preferences
+//!
+//! Every interaction and every alert recipient resolves a locale, which
+//! would otherwise be one `guild_configs` or `user_configs` query each.
+//! Stored preferences (including "none set") are kept for [`CACHE_TTL`].
+//! The cache is process-wide rather than in `AppState` because alert
+//! fan-outs resolve locales with only a database connection at hand.
+//!
+//! Language writes in `GuildConfigRepository` and `UserConfigRepository`
+//! invalidate the entry; other instances pick up a change once it expires.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant};
+
+use serenity::all::{GuildId, UserId};
+
+/// How long a looked-up preference is reused
+pub const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Entries kept before expired ones are swept on insert
+const SWEEP_THRESHOLD: usize = 10_000;
+
+/// Whose preference an entry holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Owner {
+    Guild(GuildId),
+    User(UserId),
+}
+
+/// Stored `language` column values, `None` when unset
+#[derive(Default)]
+struct LocaleCache {
+    entries: RwLock<HashMap<Owner, (Option<String>, Instant)>>,
+}
+
+impl LocaleCache {
+    fn get(&self, owner: Owner) -> Option<Option<String>> {
+        let entries = self.entries.read().expect("locale cache lock poisoned");
+        entries
+            .get(&owner)
+            .filter(|(_, cached_at)| cached_at.elapsed() < CACHE_TTL)
+            .map(|(language, _)| language.clone())
+    }
+
+    fn insert(&self, owner: Owner, language: Option<String>) {
+        let mut entries = self.entries.write().expect("locale cache lock poisoned");
+        if entries.len() >= SWEEP_THRESHOLD {
+            entries.retain(|_, (_, cached_at)| cached_at.elapsed() < CACHE_TTL);
+        }
+        entries.insert(owner, (language, Instant::now()));
+    }
+
+    fn remove(&self, owner: Owner) {
+        self.entries
+            .write()
+            .expect("locale cache lock poisoned")
+            .remove(&owner);
+    }
+}
+
+static CACHE: LazyLock<LocaleCache> = LazyLock::new(LocaleCache::default);
+
+/// Cached guild preference: `None` on a miss, `Some(None)` if none is set
+pub(super) fn guild(guild_id: GuildId) -> Option<Option<String>> {
+    CACHE.get(Owner::Guild(guild_id))
+}
+
+/// Cached user preference: `None` on a miss, `Some(None)` if none is set
+pub(super) fn user(user_id: UserId) -> Option<Option<String>> {
+    CACHE.get(Owner::User(user_id))
+}
+
+pub(super) fn store_guild(guild_id: GuildId, language: Option<String>) {
+    CACHE.insert(Owner::Guild(guild_id), language);
+}
+
+pub(super) fn store_user(user_id: UserId, language: Option<String>) {
+    CACHE.insert(Owner::User(user_id), language);
+}
+
+/// Forget a guild's cached preference after its language changed
+pub fn invalidate_guild(guild_id: GuildId) {
+    CACHE.remove(Owner::Guild(guild_id));
+}
+
+/// Forget a user's cached preference after their language changed
+pub fn invalidate_user(user_id: UserId) {
+    CACHE.remove(Owner::User(user_id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use serenity::all::ChannelId;
+
+    use crate::database::connect_in_memory;
+    use crate::i18n::{resolve_guild_locale, resolve_user_locale};
+    use crate::repository::{GuildConfigRepository, UserConfigRepository};
+
+    fn expired() -> Instant {
+        Instant::now()
+            .checked_sub(CACHE_TTL)
+            .expect("monotonic clock is past the TTL")
+    }
+
+    #[test]
+    fn test_miss_then_hit() {
+        let cache = LocaleCache::default();
+        let owner = Owner::Guild(GuildId::new(1));
+        assert_eq!(cache.get(owner), None);
+
+        cache.insert(owner, Some("ko".to_string()));
+        assert_eq!(cache.get(owner), Some(Some("ko".to_string())));
+    }
+
+    #[test]
+    fn test_unset_preference_is_cached() {
+        let cache = LocaleCache::default();
+        let owner = Owner::User(UserId::new(1));
+
+        cache.insert(owner, None);
+        assert_eq!(cache.get(owner), Some(None));
+    }
+
+    #[test]
+    fn test_remove_invalidates() {
+        let cache = LocaleCache::default();
+        let owner = Owner::Guild(GuildId::new(1));
+        cache.insert(owner, Some("ko".to_string()));
+
+        cache.remove(owner);
+        assert_eq!(cache.get(owner), None);
+    }
+
+    #[test]
+    fn test_guild_and_user_entries_are_separate() {
+        let cache = LocaleCache::default();
+        cache.insert(Owner::Guild(GuildId::new(1)), Some("ko".to_string()));
+
+        assert_eq!(cache.get(Owner::User(UserId::new(1))), None);
+        cache.remove(Owner::User(UserId::new(1)));
+        assert_eq!(
+            cache.get(Owner::Guild(GuildId::new(1))),
+            Some(Some("ko".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_expired_entry_misses() {
+        let cache = LocaleCache::default();
+        let owner = Owner::Guild(GuildId::new(1));
+        cache
+            .entries
+            .write()
+            .unwrap()
+            .insert(owner, (Some("ko".to_string()), expired()));
+
+        assert_eq!(cache.get(owner), None);
+    }
+
+    #[test]
+    fn test_sweep_drops_expired_entries() {
+        let cache = LocaleCache::default();
+        {
+            let mut entries = cache.entries.write().unwrap();
+            for id in 1..=SWEEP_THRESHOLD as u64 {
+                entries.insert(Owner::User(UserId::new(id)), (None, expired()));
+            }
+        }
+
+        cache.insert(Owner::Guild(GuildId::new(1)), None);
+        assert_eq!(cache.entries.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_reads_with_invalidation() {
+        let cache = LocaleCache::default();
+        let owner = Owner::Guild(GuildId::new(1));
+        let other = Owner::Guild(GuildId::new(2));
+        cache.insert(owner, Some("ko".to_string()));
+        cache.insert(other, Some("ja".to_string()));
+        let invalidated = AtomicBool::new(false);
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..1_000 {
+                        // Before invalidation the entry is intact; after, it
+                        // may only be missing, never stale
+                        let seen_after = invalidated.load(Ordering::SeqCst);
+                        match cache.get(owner) {
+                            Some(language) => assert_eq!(language.as_deref(), Some("ko")),
+                            None => assert!(seen_after || invalidated.load(Ordering::SeqCst)),
+                        }
+                        assert_eq!(cache.get(other), Some(Some("ja".to_string())));
+                    }
+                });
+            }
+            scope.spawn(|| {
+                std::thread::yield_now();
+                invalidated.store(true, Ordering::SeqCst);
+                cache.remove(owner);
+            });
+        });
+
+        assert_eq!(cache.get(owner), None);
+        assert_eq!(cache.get(other), Some(Some("ja".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_guild_language_write_invalidates() {
+        let db = Arc::new(connect_in_memory().await);
+        let repo = GuildConfigRepository::new(db.clone());
+        // IDs unique to this test, since the process-wide cache is shared
+        let guild_id = GuildId::new(7_770_001);
+        repo.create(guild_id, ChannelId::new(1)).await.unwrap();
+
+        assert_eq!(resolve_guild_locale(&db, guild_id).await, "en");
+        assert_eq!(guild(guild_id), Some(None));
+
+        repo.update_language(guild_id, Some("ko".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(guild(guild_id), None);
+        assert_eq!(resolve_guild_locale(&db, guild_id).await, "ko");
+    }
+
+    #[tokio::test]
+    async fn test_user_language_write_invalidates() {
+        let db = Arc::new(connect_in_memory().await);
+        let repo = UserConfigRepository::new(db.clone());
+        let user_id = UserId::new(7_770_002);
+
+        assert_eq!(resolve_user_locale(&db, user_id).await, "en");
+        assert_eq!(user(user_id), Some(None));
+
+        repo.upsert_language(user_id, "ja").await.unwrap();
+        assert_eq!(resolve_user_locale(&db, user_id).await, "ja");
+
+        repo.update_language(user_id, Some("ko".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resolve_user_locale(&db, user_id).await, "ko");
+    }
+}
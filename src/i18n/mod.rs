@@ -7,6 +7,14 @@
 //! 2. User preference (from user_configs.language)
 //! 3. Discord locale (from interaction)
 //! 4. Default: "en"
+//!
+//! Stored preferences are cached for a few minutes (see [`cache`]).
+
+mod cache;
+mod relative_time;
+
+pub use cache::{invalidate_guild, invalidate_user};
+pub use relative_time::relative_time;
 
 use sea_orm::{DatabaseConnection, EntityTrait};
 use serenity::all::{CommandInteraction, ComponentInteraction, Context, GuildId, UserId};
@@ -17,14 +25,50 @@ use crate::state::AppStateKey;
 /// Default locale
 pub const DEFAULT_LOCALE: &str = "en";
 
+/// Locales that may be stored in the `language` columns
+pub const SUPPORTED_LOCALES: &[&str] = &["en", "ko", "ja"];
+
+/// Keys rendered in every locale by the `/config language` preview
+///
+/// An alert title, the dashboard title, and a button label, so admins see
+/// what members will read before switching.
+pub const PREVIEW_KEYS: &[&str] = &[
+    "embeds.alerts.threshold.title",
+    "embeds.dashboard.title",
+    "buttons.confirm",
+];
+
+/// Whether a stored language code is one we have translations for
+pub fn is_supported(code: &str) -> bool {
+    SUPPORTED_LOCALES.contains(&code)
+}
+
+/// Normalize a language code to a supported locale
+///
+/// Trims, lowercases, and reduces region variants to their primary subtag
+/// ("en-US", "en_GB" -> "en", " KO " -> "ko"). Returns `None` for codes
+/// we do not support.
+pub fn normalize_locale(code: &str) -> Option<&'static str> {
+    let code = code.trim().to_lowercase();
+    let primary = code.split(['-', '_']).next().unwrap_or_default();
+    SUPPORTED_LOCALES
+        .iter()
+        .copied()
+        .find(|supported| *supported == primary)
+}
+
 /// Convert Discord locale to our locale format
 ///
-/// Discord sends: "ko", "en-US", "en-GB", "ja", etc.
-/// We support: "en", "ko"
+/// Discord sends: "ko", "ja", "en-US", "en-GB", etc.
+/// We support: "en", "ko", "ja"
 ///
-/// Returns "ko" if Korean, "en" otherwise.
+/// Returns the matching supported locale, "en" otherwise.
 fn to_locale(discord_locale: &str) -> &'static str {
-    if discord_locale == "ko" { "ko" } else { "en" }
+    match discord_locale {
+        "ko" => "ko",
+        "ja" => "ja",
+        _ => "en",
+    }
 }
 
 /// Resolve the locale for a command interaction (sync version)
@@ -148,6 +192,7 @@ pub async fn resolve_user_locale_by_id(db: &DatabaseConnection, user_id: &str) -
 /// Returns a human-readable name for the language code in the user's locale.
 /// - `Some("en")` -> "English" (or localized equivalent)
 /// - `Some("ko")` -> "Korean" (or localized equivalent)
+/// - `Some("ja")` -> "Japanese" (or localized equivalent)
 /// - `None` -> "Auto-detect" (or localized equivalent)
 pub fn get_language_display_name(code: Option<&str>, locale: &str) -> String {
     use rust_i18n::t;
@@ -155,6 +200,7 @@ pub fn get_language_display_name(code: Option<&str>, locale: &str) -> String {
     match code {
         Some("en") => t!("embeds.config.language.names.en", locale = locale).to_string(),
         Some("ko") => t!("embeds.config.language.names.ko", locale = locale).to_string(),
+        Some("ja") => t!("embeds.config.language.names.ja", locale = locale).to_string(),
         None => t!("embeds.config.language.names.auto", locale = locale).to_string(),
         Some(other) => other.to_string(),
     }
@@ -171,19 +217,133 @@ async fn get_db(ctx: &Context) -> Option<std::sync::Arc<DatabaseConnection>> {
 }
 
 async fn get_guild_language(db: &DatabaseConnection, guild_id: GuildId) -> Option<String> {
-    guild_configs::Entity::find_by_id(guild_id.to_string())
-        .one(db)
-        .await
-        .ok()
-        .flatten()
-        .and_then(|c| c.language)
+    let language = match cache::guild(guild_id) {
+        Some(language) => language,
+        None => {
+            // Errors are not cached, so the next lookup retries
+            let row = guild_configs::Entity::find_by_id(guild_id.to_string())
+                .one(db)
+                .await
+                .ok()?;
+            let language = row.and_then(|c| c.language);
+            cache::store_guild(guild_id, language.clone());
+            language
+        }
+    };
+    language.filter(|lang| is_supported(lang))
 }
 
 async fn get_user_language(db: &DatabaseConnection, user_id: UserId) -> Option<String> {
-    user_configs::Entity::find_by_id(user_id.to_string())
-        .one(db)
-        .await
-        .ok()
-        .flatten()
-        .and_then(|c| c.language)
+    let language = match cache::user(user_id) {
+        Some(language) => language,
+        None => {
+            let row = user_configs::Entity::find_by_id(user_id.to_string())
+                .one(db)
+                .await
+                .ok()?;
+            let language = row.and_then(|c| c.language);
+            cache::store_user(user_id, language.clone());
+            language
+        }
+    };
+    language.filter(|lang| is_supported(lang))
+}
+
+/// Keys with no translation in some supported locale, as `locale: key`
+///
+/// No fallback locale is configured, so a missing key renders as the key
+/// itself.
+#[cfg(test)]
+pub fn untranslated(keys: &[&str]) -> Vec<String> {
+    SUPPORTED_LOCALES
+        .iter()
+        .flat_map(|locale| keys.iter().map(move |key| (*locale, *key)))
+        .filter(|&(locale, key)| rust_i18n::t!(key, locale = locale) == key)
+        .map(|(locale, key)| format!("{locale}: {key}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_locale() {
+        let cases = [
+            ("en", Some("en")),
+            ("ko", Some("ko")),
+            ("ja", Some("ja")),
+            ("EN", Some("en")),
+            (" ko\n", Some("ko")),
+            ("en-US", Some("en")),
+            ("en_GB", Some("en")),
+            ("ja-JP", Some("ja")),
+            ("ko-kr-x-private", Some("ko")),
+            ("", None),
+            ("   ", None),
+            ("fr", None),
+            ("zh-KO", None),
+            ("english", None),
+            ("-en", None),
+            ("e n", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize_locale(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_normalized_locales_are_supported() {
+        for code in SUPPORTED_LOCALES {
+            assert_eq!(normalize_locale(code), Some(*code));
+            assert!(is_supported(code));
+        }
+        assert!(!is_supported("en-US"));
+        assert!(!is_supported("EN"));
+    }
+
+    #[test]
+    fn test_error_keys_translated_in_every_locale() {
+        let keys = [
+            "errors.title",
+            "errors.generic",
+            "errors.missing_subcommand",
+            "errors.unknown_subcommand",
+            "errors.missing_option",
+            "errors.admin.invalid_command",
+            "errors.admin.missing_options",
+            "errors.admin.invalid_poller",
+            "errors.admin.interval_out_of_range",
+            "errors.admin.cooldown_out_of_range",
+            "errors.admin.alert_expiry_out_of_range",
+            "errors.admin.retention_out_of_range",
+            "errors.admin.save_failed",
+            "errors.admin.reset_failed",
+            "errors.admin.maintenance_save_failed",
+            "errors.admin.validate_channels_failed",
+            "errors.admin.invalid_guild_id",
+            "errors.admin.alert_stats_failed",
+            "errors.admin.reporter_stats_failed",
+            "errors.admin.invalid_incident_id",
+            "errors.admin.unknown_incident",
+            "errors.admin.incident_load_failed",
+            "errors.admin.incident_updates_failed",
+            "errors.admin.reports_load_failed",
+            "errors.admin.broadcast_empty",
+            "errors.admin.broadcast_missing",
+            "errors.admin.broadcast_in_progress",
+            "errors.admin.destinations_failed",
+        ];
+        assert_eq!(untranslated(&keys), Vec::<String>::new());
+        // The check itself catches a missing key
+        assert_eq!(
+            untranslated(&["errors.no_such_key"]).len(),
+            SUPPORTED_LOCALES.len()
+        );
+    }
+
+    #[test]
+    fn test_preview_keys_translated_in_every_locale() {
+        assert_eq!(untranslated(PREVIEW_KEYS), Vec::<String>::new());
+    }
 }
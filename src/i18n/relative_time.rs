@@ -0,0 +1,139 @@
+//! Localized relative times ("5 min ago", "2 hours ago")
+
+use chrono::{DateTime, Duration, Utc};
+use rust_i18n::t;
+
+/// Ages at or above this are shown as an absolute date instead
+const MAX_RELATIVE_DAYS: i64 = 7;
+
+/// Localized relative time for an age
+///
+/// Under a minute (including negative ages from clock skew) is "just now";
+/// then whole minutes, hours, and days. Ages of 7 days or more fall back to
+/// the absolute date.
+pub fn relative_time(age: Duration, locale: &str) -> String {
+    relative_time_at(age, Utc::now(), locale)
+}
+
+/// [`relative_time`] with an explicit current time for the absolute fallback
+pub fn relative_time_at(age: Duration, now: DateTime<Utc>, locale: &str) -> String {
+    match RelativeUnit::of(age) {
+        RelativeUnit::JustNow => t!("time.just_now", locale = locale).to_string(),
+        RelativeUnit::Minutes(1) => t!("time.min_ago_one", locale = locale).to_string(),
+        RelativeUnit::Minutes(n) => t!("time.min_ago_many", n = n, locale = locale).to_string(),
+        RelativeUnit::Hours(1) => t!("time.hour_ago_one", locale = locale).to_string(),
+        RelativeUnit::Hours(n) => t!("time.hour_ago_many", n = n, locale = locale).to_string(),
+        RelativeUnit::Days(1) => t!("time.day_ago_one", locale = locale).to_string(),
+        RelativeUnit::Days(n) => t!("time.day_ago_many", n = n, locale = locale).to_string(),
+        RelativeUnit::Date => (now - age).format(absolute_date_format(locale)).to_string(),
+    }
+}
+
+/// Largest whole unit an age is shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RelativeUnit {
+    JustNow,
+    Minutes(i64),
+    Hours(i64),
+    Days(i64),
+    /// Too old for a relative time
+    Date,
+}
+
+impl RelativeUnit {
+    fn of(age: Duration) -> Self {
+        if age.num_days() >= MAX_RELATIVE_DAYS {
+            Self::Date
+        } else if age.num_days() >= 1 {
+            Self::Days(age.num_days())
+        } else if age.num_hours() >= 1 {
+            Self::Hours(age.num_hours())
+        } else if age.num_minutes() >= 1 {
+            Self::Minutes(age.num_minutes())
+        } else {
+            Self::JustNow
+        }
+    }
+}
+
+/// Date format for ages past the relative range
+fn absolute_date_format(locale: &str) -> &'static str {
+    match locale {
+        "ko" => "%Y년 %-m월 %-d일",
+        "ja" => "%Y年%-m月%-d日",
+        _ => "%b %-d, %Y",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_unit_boundaries() {
+        let cases = [
+            (Duration::seconds(-90), RelativeUnit::JustNow),
+            (Duration::zero(), RelativeUnit::JustNow),
+            (Duration::seconds(59), RelativeUnit::JustNow),
+            (Duration::seconds(60), RelativeUnit::Minutes(1)),
+            (Duration::seconds(119), RelativeUnit::Minutes(1)),
+            (Duration::minutes(59), RelativeUnit::Minutes(59)),
+            (Duration::minutes(60), RelativeUnit::Hours(1)),
+            (Duration::minutes(23 * 60 + 59), RelativeUnit::Hours(23)),
+            (Duration::hours(24), RelativeUnit::Days(1)),
+            (Duration::hours(47), RelativeUnit::Days(1)),
+            (
+                Duration::days(7) - Duration::seconds(1),
+                RelativeUnit::Days(6),
+            ),
+            (Duration::days(7), RelativeUnit::Date),
+            (Duration::days(400), RelativeUnit::Date),
+        ];
+        for (age, expected) in cases {
+            assert_eq!(RelativeUnit::of(age), expected, "{age}");
+        }
+    }
+
+    #[test]
+    fn test_relative_time_per_locale() {
+        let cases = [
+            (Duration::seconds(-5), ["Just now", "방금", "たった今"]),
+            (Duration::seconds(59), ["Just now", "방금", "たった今"]),
+            (Duration::seconds(60), ["1 min ago", "1분 전", "1分前"]),
+            (Duration::minutes(59), ["59 min ago", "59분 전", "59分前"]),
+            (Duration::minutes(60), ["1 hour ago", "1시간 전", "1時間前"]),
+            (
+                Duration::hours(23),
+                ["23 hours ago", "23시간 전", "23時間前"],
+            ),
+            (Duration::hours(24), ["1 day ago", "1일 전", "1日前"]),
+            (Duration::days(6), ["6 days ago", "6일 전", "6日前"]),
+            (
+                Duration::days(7),
+                ["Mar 8, 2026", "2026년 3월 8일", "2026年3月8日"],
+            ),
+        ];
+        for (age, expected) in cases {
+            for (locale, expected) in ["en", "ko", "ja"].into_iter().zip(expected) {
+                assert_eq!(
+                    relative_time_at(age, now(), locale),
+                    expected,
+                    "{age} in {locale}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_locale_uses_english_date() {
+        assert_eq!(
+            relative_time_at(Duration::days(30), now(), "fr"),
+            "Feb 13, 2026"
+        );
+    }
+}
@@ -0,0 +1,377 @@
+//! Leader election between bot instances sharing one database
+//!
+//! During a zero-downtime deploy the new instance starts before the old one
+//! stops. Both serve interactions, but only the holder of the "collector"
+//! lease runs the collector pollers and scheduled jobs, so polls and metric
+//! inserts are not doubled.
+//!
+//! Every instance tries to acquire or renew the lease every
+//! [`RENEW_INTERVAL`]. Clocks are handled conservatively: the holder stops
+//! acting as leader [`CLOCK_SKEW_MARGIN`] before its lease expires (measured
+//! on its own monotonic clock), and a follower only takes over once the
+//! stored expiry is [`CLOCK_SKEW_MARGIN`] in the past. Losing the lease never
+//! interrupts work in progress; poll loops and the scheduler check
+//! [`Leadership::is_leader`] before each run. Every instance is a follower
+//! until its first election round finishes, so they wait for
+//! [`Leadership::first_round`] before their first run instead of skipping it.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::repository::LeaseRepository;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Lease guarding the collector and scheduled jobs
+pub const COLLECTOR_LEASE: &str = "collector";
+
+/// How often the lease is acquired or renewed
+pub const RENEW_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Lifetime of a lease after each renewal
+///
+/// Three renew intervals, so two failed renewals in a row keep the lease.
+pub const LEASE_TTL: Duration = Duration::from_secs(90);
+
+/// Largest assumed clock difference between instances
+pub const CLOCK_SKEW_MARGIN: Duration = Duration::from_secs(10);
+
+// =============================================================================
+// Leadership
+// =============================================================================
+
+/// Whether this instance currently holds the collector lease
+#[derive(Debug)]
+pub struct Leadership {
+    instance_id: String,
+    leader: AtomicBool,
+    /// Whether the first election round has finished
+    elected: watch::Sender<bool>,
+}
+
+impl Leadership {
+    /// Start as a follower until the lease is acquired
+    pub fn new(instance_id: String) -> Self {
+        Self {
+            instance_id,
+            leader: AtomicBool::new(false),
+            elected: watch::Sender::new(false),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader.load(Ordering::Relaxed)
+    }
+
+    /// Wait until the first election round has decided whether this
+    /// instance leads
+    pub async fn first_round(&self) {
+        // The sender lives as long as `self`, so waiting can't fail
+        let _ = self.elected.subscribe().wait_for(|elected| *elected).await;
+    }
+
+    /// Update the flag, returning the previous value
+    pub(crate) fn set(&self, leader: bool) -> bool {
+        self.leader.swap(leader, Ordering::Relaxed)
+    }
+
+    /// Mark the first election round as finished
+    fn finish_round(&self) {
+        self.elected
+            .send_if_modified(|elected| !std::mem::replace(elected, true));
+    }
+}
+
+/// Identifier of this process, unique across restarts and hosts
+pub fn instance_id() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "local".to_string());
+    format!(
+        "{}-{}-{}",
+        host,
+        std::process::id(),
+        Utc::now().timestamp_millis()
+    )
+}
+
+/// Until when a lease renewed at `renewed_at` may be treated as held locally
+pub fn held_until(renewed_at: Instant) -> Instant {
+    renewed_at + LEASE_TTL.saturating_sub(CLOCK_SKEW_MARGIN)
+}
+
+// =============================================================================
+// Lease Loop
+// =============================================================================
+
+/// Acquire and renew the collector lease until `shutdown` is cancelled
+///
+/// On shutdown the lease is released, so a new instance takes over without
+/// waiting for it to expire. A failed renewal keeps the current state until
+/// the locally tracked expiry passes, so a brief database hiccup does not
+/// hand leadership back and forth.
+pub async fn run(
+    db: Arc<DatabaseConnection>,
+    leadership: Arc<Leadership>,
+    shutdown: CancellationToken,
+) {
+    let repo = LeaseRepository::new(db.clone());
+    let ttl = chrono::Duration::from_std(LEASE_TTL).expect("lease TTL fits");
+    let margin = chrono::Duration::from_std(CLOCK_SKEW_MARGIN).expect("skew margin fits");
+    let mut lease_until: Option<Instant> = None;
+
+    info!(
+        instance = leadership.instance_id(),
+        "Starting leader election"
+    );
+
+    loop {
+        // Measure validity from before the write, in case it is slow
+        let attempt_started = Instant::now();
+        let now = Utc::now();
+        match repo
+            .try_acquire(
+                COLLECTOR_LEASE,
+                leadership.instance_id(),
+                now + ttl,
+                now - margin,
+                now,
+            )
+            .await
+        {
+            Ok(true) => lease_until = Some(held_until(attempt_started)),
+            Ok(false) => lease_until = None,
+            Err(e) => warn!(error = %e, "Failed to renew collector lease"),
+        }
+
+        let leader = lease_until.is_some_and(|until| Instant::now() < until);
+        match (leadership.set(leader), leader) {
+            (false, true) => info!(
+                instance = leadership.instance_id(),
+                "Acquired collector lease, running collector and scheduled jobs"
+            ),
+            (true, false) => warn!(
+                instance = leadership.instance_id(),
+                "Lost collector lease, pausing collector and scheduled jobs"
+            ),
+            _ => debug!(leader, "Collector lease checked"),
+        }
+        leadership.finish_round();
+
+        // Wake up early if the lease would lapse before the next renewal
+        let wait = lease_until
+            .map(|until| until.saturating_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+            .map_or(RENEW_INTERVAL, |remaining| remaining.min(RENEW_INTERVAL));
+
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(wait) => {}
+        }
+    }
+
+    release(db, &leadership).await;
+}
+
+/// Step down and delete the lease if this instance holds it
+async fn release(db: Arc<DatabaseConnection>, leadership: &Leadership) {
+    if !leadership.set(false) {
+        return;
+    }
+    match LeaseRepository::new(db)
+        .release(COLLECTOR_LEASE, leadership.instance_id())
+        .await
+    {
+        Ok(()) => info!("Released collector lease"),
+        Err(e) => warn!(error = %e, "Failed to release collector lease"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{TempDatabase, connect_in_memory};
+    use crate::entity::leases;
+    use sea_orm::EntityTrait;
+
+    async fn lease_holder(db: &DatabaseConnection) -> Option<String> {
+        leases::Entity::find_by_id(COLLECTOR_LEASE)
+            .one(db)
+            .await
+            .unwrap()
+            .map(|lease| lease.holder)
+    }
+
+    #[test]
+    fn test_held_until_leaves_skew_margin() {
+        let renewed_at = Instant::now();
+
+        assert_eq!(
+            held_until(renewed_at) - renewed_at,
+            LEASE_TTL - CLOCK_SKEW_MARGIN
+        );
+        assert!(held_until(renewed_at) - renewed_at > RENEW_INTERVAL);
+    }
+
+    #[test]
+    fn test_leadership_set_returns_previous() {
+        let leadership = Leadership::new("a".to_string());
+
+        assert!(!leadership.is_leader());
+        assert!(!leadership.set(true));
+        assert!(leadership.is_leader());
+        assert!(leadership.set(false));
+    }
+
+    #[tokio::test]
+    async fn test_run_acquires_and_releases_on_shutdown() {
+        let db = Arc::new(connect_in_memory().await);
+        let leadership = Arc::new(Leadership::new("a".to_string()));
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+
+        run(db.clone(), leadership.clone(), shutdown).await;
+
+        assert!(!leadership.is_leader());
+        assert!(
+            leases::Entity::find_by_id(COLLECTOR_LEASE)
+                .one(&*db)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_follows_live_holder() {
+        let db = Arc::new(connect_in_memory().await);
+        let now = Utc::now();
+        let ttl = chrono::Duration::from_std(LEASE_TTL).unwrap();
+        assert!(
+            LeaseRepository::new(db.clone())
+                .try_acquire(COLLECTOR_LEASE, "a", now + ttl, now, now)
+                .await
+                .unwrap()
+        );
+        let leadership = Arc::new(Leadership::new("b".to_string()));
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+
+        run(db.clone(), leadership.clone(), shutdown).await;
+
+        assert!(!leadership.is_leader());
+        let lease = leases::Entity::find_by_id(COLLECTOR_LEASE)
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lease.holder, "a");
+    }
+
+    #[tokio::test]
+    async fn test_first_round_waits_for_election() {
+        let db = Arc::new(connect_in_memory().await);
+        let leadership = Arc::new(Leadership::new("a".to_string()));
+        let shutdown = CancellationToken::new();
+
+        let waiting = tokio::spawn({
+            let leadership = leadership.clone();
+            async move {
+                leadership.first_round().await;
+                leadership.is_leader()
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        let election = tokio::spawn(run(db, leadership.clone(), shutdown.clone()));
+
+        // Leadership is already decided when the wait ends
+        assert!(waiting.await.unwrap());
+        shutdown.cancel();
+        election.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_two_instances_elect_one_leader() {
+        let file = TempDatabase::new().await;
+        let dbs = [
+            Arc::new(file.connect().await),
+            Arc::new(file.connect().await),
+        ];
+        let instances = [
+            Arc::new(Leadership::new("a".to_string())),
+            Arc::new(Leadership::new("b".to_string())),
+        ];
+        let tokens = [CancellationToken::new(), CancellationToken::new()];
+        let mut tasks: Vec<_> = (0..2)
+            .map(|i| tokio::spawn(run(dbs[i].clone(), instances[i].clone(), tokens[i].clone())))
+            .collect();
+        for instance in &instances {
+            instance.first_round().await;
+        }
+
+        let leaders: Vec<usize> = (0..2).filter(|&i| instances[i].is_leader()).collect();
+        assert_eq!(leaders.len(), 1, "exactly one instance leads");
+        let (leader, follower) = (leaders[0], 1 - leaders[0]);
+        assert_eq!(
+            lease_holder(&dbs[follower]).await.as_deref(),
+            Some(instances[leader].instance_id())
+        );
+
+        // The leader shuts down and releases the lease for the follower
+        tokens[leader].cancel();
+        tasks.remove(leader).await.unwrap();
+        assert!(!instances[leader].is_leader());
+        assert_eq!(lease_holder(&dbs[follower]).await, None);
+
+        tokens[follower].cancel();
+        tasks.pop().unwrap().await.unwrap();
+
+        // The follower's next round takes over without waiting for expiry
+        let successor = Arc::new(Leadership::new(
+            instances[follower].instance_id().to_string(),
+        ));
+        let token = CancellationToken::new();
+        let task = tokio::spawn(run(dbs[follower].clone(), successor.clone(), token.clone()));
+        successor.first_round().await;
+        assert!(successor.is_leader());
+        assert_eq!(
+            lease_holder(&dbs[leader]).await.as_deref(),
+            Some(successor.instance_id())
+        );
+        token.cancel();
+        task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_two_instances_race_for_lease() {
+        let file = TempDatabase::new().await;
+        let repos = [
+            LeaseRepository::new(Arc::new(file.connect().await)),
+            LeaseRepository::new(Arc::new(file.connect().await)),
+        ];
+        let now = Utc::now();
+        let ttl = chrono::Duration::from_std(LEASE_TTL).unwrap();
+
+        for round in 0..20 {
+            let name = format!("lease-{round}");
+            let (a, b) = tokio::join!(
+                repos[0].try_acquire(&name, "a", now + ttl, now, now),
+                repos[1].try_acquire(&name, "b", now + ttl, now, now),
+            );
+            assert!(a.unwrap() ^ b.unwrap(), "round {round} has one winner");
+        }
+    }
+}
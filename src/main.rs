@@ -1,16 +1,22 @@
 mod alerts;
 mod audit;
 mod bot;
+mod cache;
 mod collector;
 mod commands;
 mod config;
+mod config_store;
 mod database;
 mod entity;
 mod error;
 mod i18n;
+mod leader;
 mod logging;
+mod maintenance;
 mod repository;
+mod scheduler;
 mod state;
+mod version;
 mod visualization;
 
 // Initialize rust-i18n with locales from the `locales` directory
@@ -32,13 +38,17 @@ async fn main() -> Result<()> {
     info!("Starting VRCPulse...");
 
     // 3. Set up and configure the bot
-    let mut client = bot::setup(&config).await?;
+    let (mut client, shutdown) = bot::setup(&config).await?;
 
-    // 4. Start bot
+    // 4. Start bot (returns once a shutdown signal disconnects all shards)
     info!("Connecting to Discord...");
     if let Err(e) = client.start().await {
         error!("Client error: {:?}", e);
     }
+    info!("Discord client stopped");
+
+    // 5. Stop background tasks and close the database
+    shutdown.finish().await;
 
     Ok(())
 }
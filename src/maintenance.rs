@@ -0,0 +1,431 @@
+//! Periodic database maintenance
+//!
+//! Registers housekeeping jobs on the scheduler. A failed run is recorded in
+//! the job status and retried on the next tick.
+
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{NaiveTime, Utc};
+use chrono_tz::Tz;
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DatabaseConnection, EntityTrait, Set};
+use tracing::{debug, info};
+
+use crate::alerts::threshold::get_config_value;
+use crate::collector::metrics;
+use crate::commands::report::get_report_cooldown;
+use crate::entity::bot_config;
+use crate::repository::{
+    LogRepository, PayloadRepository, RecordRepository, ReportAggregateRepository,
+    ReportRepository, SilenceRepository,
+};
+use crate::scheduler::{JobResult, Schedule, Scheduler};
+
+/// How often expired silences and alert payloads are pruned
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How often reports past their window are marked expired
+const EXPIRE_REPORTS_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// How long stored alert payloads are kept (only the latest block is replayed)
+const PAYLOAD_RETENTION_HOURS: i64 = 24;
+
+/// Days raw user reports are kept; older days survive only as daily aggregates
+pub const REPORT_RETENTION_DAYS: i64 = 365;
+
+/// Config key for days collected metric and status logs are kept
+pub const METRIC_RETENTION_KEY: &str = "retention.metric_days";
+
+/// Default metric retention in days (used if config missing)
+pub const DEFAULT_METRIC_RETENTION_DAYS: i64 = 30;
+
+/// Shortest configurable metric retention (covers the 7-day dashboard)
+pub const MIN_METRIC_RETENTION_DAYS: i64 = 7;
+
+/// Longest configurable metric retention
+pub const MAX_METRIC_RETENTION_DAYS: i64 = 365;
+
+/// Config key for days command usage logs are kept
+pub const COMMAND_LOG_RETENTION_KEY: &str = "retention.command_log_days";
+
+/// Default command log retention in days (used if config missing)
+pub const DEFAULT_COMMAND_LOG_RETENTION_DAYS: i64 = 30;
+
+/// Shortest configurable command log retention (covers the 7-day usage stats)
+pub const MIN_COMMAND_LOG_RETENTION_DAYS: i64 = 7;
+
+/// Longest configurable command log retention (logs hold user IDs)
+pub const MAX_COMMAND_LOG_RETENTION_DAYS: i64 = 180;
+
+/// Most days summarized per run, so a large backlog is spread over several runs
+const MAX_AGGREGATE_DAYS_PER_RUN: u32 = 62;
+
+/// Daily report aggregation time (UTC, shortly after the day completes)
+const AGGREGATE_AT: NaiveTime = NaiveTime::from_hms_opt(0, 15, 0).expect("valid time");
+
+/// Daily metric downsampling time (UTC, before pruning)
+const DOWNSAMPLE_METRICS_AT: NaiveTime = NaiveTime::from_hms_opt(3, 15, 0).expect("valid time");
+
+/// Daily metric log pruning time (UTC, low traffic)
+const PRUNE_METRICS_AT: NaiveTime = NaiveTime::from_hms_opt(3, 30, 0).expect("valid time");
+
+/// Daily command log pruning time (UTC, after metric pruning)
+const PRUNE_COMMAND_LOGS_AT: NaiveTime = NaiveTime::from_hms_opt(3, 45, 0).expect("valid time");
+
+/// Daily query planner optimization time (UTC, low traffic)
+const OPTIMIZE_AT: NaiveTime = NaiveTime::from_hms_opt(4, 0, 0).expect("valid time");
+
+/// Register maintenance jobs
+pub fn register(scheduler: &mut Scheduler, db: Arc<DatabaseConnection>) {
+    scheduler.register(
+        "prune_silences",
+        Schedule::Interval(MAINTENANCE_INTERVAL),
+        {
+            let db = db.clone();
+            move || prune_expired_silences(db.clone())
+        },
+    );
+    scheduler.register(
+        "prune_alert_payloads",
+        Schedule::Interval(MAINTENANCE_INTERVAL),
+        {
+            let db = db.clone();
+            move || prune_alert_payloads(db.clone())
+        },
+    );
+    scheduler.register(
+        "expire_reports",
+        Schedule::Interval(EXPIRE_REPORTS_INTERVAL),
+        {
+            let db = db.clone();
+            move || expire_reports(db.clone())
+        },
+    );
+    scheduler.register(
+        "aggregate_reports",
+        Schedule::DailyAt {
+            time: AGGREGATE_AT,
+            tz: Tz::UTC,
+        },
+        {
+            let db = db.clone();
+            move || aggregate_reports(db.clone())
+        },
+    );
+    scheduler.register(
+        "downsample_metrics",
+        Schedule::DailyAt {
+            time: DOWNSAMPLE_METRICS_AT,
+            tz: Tz::UTC,
+        },
+        {
+            let db = db.clone();
+            move || downsample_metrics(db.clone())
+        },
+    );
+    scheduler.register(
+        "prune_metrics",
+        Schedule::DailyAt {
+            time: PRUNE_METRICS_AT,
+            tz: Tz::UTC,
+        },
+        {
+            let db = db.clone();
+            move || prune_metrics(db.clone())
+        },
+    );
+    scheduler.register(
+        "prune_command_logs",
+        Schedule::DailyAt {
+            time: PRUNE_COMMAND_LOGS_AT,
+            tz: Tz::UTC,
+        },
+        {
+            let db = db.clone();
+            move || prune_command_logs(db.clone())
+        },
+    );
+    scheduler.register(
+        "optimize_database",
+        Schedule::DailyAt {
+            time: OPTIMIZE_AT,
+            tz: Tz::UTC,
+        },
+        move || optimize_database(db.clone()),
+    );
+}
+
+/// Delete alert silences whose window has ended
+async fn prune_expired_silences(db: Arc<DatabaseConnection>) -> JobResult {
+    match SilenceRepository::new(db).prune_expired(Utc::now()).await? {
+        0 => debug!("No expired alert silences to prune"),
+        count => info!(count, "Pruned expired alert silences"),
+    }
+    Ok(())
+}
+
+/// Delete stored alert payloads past retention
+async fn prune_alert_payloads(db: Arc<DatabaseConnection>) -> JobResult {
+    let cutoff = Utc::now() - chrono::Duration::hours(PAYLOAD_RETENTION_HOURS);
+    match PayloadRepository::new(db).prune_before(cutoff).await? {
+        0 => debug!("No alert payloads to prune"),
+        count => info!(count, "Pruned alert payloads"),
+    }
+    Ok(())
+}
+
+/// Mark active reports past their window as expired
+///
+/// The window is `report_interval`, or the report cooldown if that is longer,
+/// so expiring a report never lifts a cooldown early.
+async fn expire_reports(db: Arc<DatabaseConnection>) -> JobResult {
+    let Some(interval) = get_config_value(&db, "report_interval").await else {
+        debug!("Missing config 'report_interval', not expiring reports");
+        return Ok(());
+    };
+    let window = interval.max(get_report_cooldown(&db).await);
+
+    let cutoff = Utc::now() - chrono::Duration::minutes(window);
+    match ReportRepository::new(db).expire_before(cutoff).await? {
+        0 => debug!("No user reports to expire"),
+        count => info!(count, "Marked user reports as expired"),
+    }
+    Ok(())
+}
+
+/// Summarize completed days of user reports, then prune raw rows past retention
+///
+/// Resumes after the aggregation cursor (or, before the first run that set
+/// it, the latest aggregated day or the oldest report). Raw rows are only
+/// pruned for days that have been aggregated.
+async fn aggregate_reports(db: Arc<DatabaseConnection>) -> JobResult {
+    let today = Utc::now().date_naive();
+    let aggregates = ReportAggregateRepository::new(db.clone());
+    let reports = ReportRepository::new(db);
+
+    let resume_after = match aggregates.aggregated_through().await? {
+        Some(through) => Some(through),
+        None => aggregates.latest_day().await?,
+    };
+    let mut day = match resume_after {
+        Some(latest) => latest + chrono::Duration::days(1),
+        None => match reports.oldest_created_at().await? {
+            Some(oldest) => oldest.date_naive(),
+            None => {
+                debug!("No user reports to aggregate");
+                return Ok(());
+            }
+        },
+    };
+
+    let mut aggregated = 0;
+    while day < today && aggregated < MAX_AGGREGATE_DAYS_PER_RUN {
+        aggregates.aggregate_day(day).await?;
+        day += chrono::Duration::days(1);
+        aggregated += 1;
+    }
+    if aggregated > 0 {
+        info!(days = aggregated, through = %(day - chrono::Duration::days(1)), "Aggregated user reports");
+    }
+
+    // `day` is the first day not yet aggregated
+    let cutoff = (today - chrono::Duration::days(REPORT_RETENTION_DAYS)).min(day);
+    match reports
+        .prune_before(cutoff.and_time(NaiveTime::MIN).and_utc())
+        .await?
+    {
+        0 => debug!("No user reports past retention"),
+        count => info!(count, before = %cutoff, "Pruned aggregated user reports"),
+    }
+    Ok(())
+}
+
+/// Get the metric retention from database, falls back to default if missing
+pub async fn get_metric_retention_days(db: &DatabaseConnection) -> i64 {
+    retention_days(
+        db,
+        METRIC_RETENTION_KEY,
+        DEFAULT_METRIC_RETENTION_DAYS,
+        MIN_METRIC_RETENTION_DAYS..=MAX_METRIC_RETENTION_DAYS,
+    )
+    .await
+}
+
+/// Get the command log retention from database, falls back to default if missing
+pub async fn get_command_log_retention_days(db: &DatabaseConnection) -> i64 {
+    retention_days(
+        db,
+        COMMAND_LOG_RETENTION_KEY,
+        DEFAULT_COMMAND_LOG_RETENTION_DAYS,
+        MIN_COMMAND_LOG_RETENTION_DAYS..=MAX_COMMAND_LOG_RETENTION_DAYS,
+    )
+    .await
+}
+
+/// Read a retention in days, ignoring values outside `range`
+async fn retention_days(
+    db: &DatabaseConnection,
+    key: &str,
+    default: i64,
+    range: RangeInclusive<i64>,
+) -> i64 {
+    bot_config::Entity::find_by_id(key)
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|c| c.value.parse().ok())
+        .filter(|days| range.contains(days))
+        .unwrap_or(default)
+}
+
+/// Store the metric retention
+pub async fn set_metric_retention_days(
+    db: &DatabaseConnection,
+    days: i64,
+) -> Result<(), sea_orm::DbErr> {
+    match bot_config::Entity::find_by_id(METRIC_RETENTION_KEY)
+        .one(db)
+        .await?
+    {
+        Some(existing) => {
+            let mut active: bot_config::ActiveModel = existing.into();
+            active.value = Set(days.to_string());
+            active.updated_at = Set(Utc::now());
+            active.update(db).await?;
+        }
+        None => {
+            let config = bot_config::ActiveModel {
+                key: Set(METRIC_RETENTION_KEY.to_string()),
+                value: Set(days.to_string()),
+                updated_at: Set(Utc::now()),
+            };
+            config.insert(db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Validate a metric retention in days
+pub fn validate_metric_retention(days: i64) -> Result<(), String> {
+    if !(MIN_METRIC_RETENTION_DAYS..=MAX_METRIC_RETENTION_DAYS).contains(&days) {
+        return Err(format!(
+            "Retention must be between {} and {} days",
+            MIN_METRIC_RETENTION_DAYS, MAX_METRIC_RETENTION_DAYS
+        ));
+    }
+    Ok(())
+}
+
+/// Replace old metric rows with hourly averages
+async fn downsample_metrics(db: Arc<DatabaseConnection>) -> JobResult {
+    let summary = metrics::downsample_metrics(&db).await?;
+    match summary.raw_rows {
+        0 => debug!("No metric logs to downsample"),
+        raw_rows => info!(
+            raw_rows,
+            hourly_rows = summary.hourly_rows,
+            "Downsampled metric logs to hourly averages"
+        ),
+    }
+    Ok(())
+}
+
+/// Delete collected metric, status, and component logs past retention
+async fn prune_metrics(db: Arc<DatabaseConnection>) -> JobResult {
+    let days = get_metric_retention_days(&db).await;
+    let cutoff = Utc::now() - chrono::Duration::days(days);
+    let logs = LogRepository::new(db);
+
+    let tables = [
+        ("metric_logs", logs.prune_metrics_before(cutoff).await?),
+        ("status_logs", logs.prune_statuses_before(cutoff).await?),
+        (
+            "component_logs",
+            logs.prune_components_before(cutoff).await?,
+        ),
+    ];
+    for (table, count) in tables {
+        match count {
+            0 => debug!(table, "No logs past retention"),
+            count => info!(table, count, days, "Pruned logs past retention"),
+        }
+    }
+    Ok(())
+}
+
+/// Delete command usage logs past retention
+async fn prune_command_logs(db: Arc<DatabaseConnection>) -> JobResult {
+    let days = get_command_log_retention_days(&db).await;
+    let cutoff = Utc::now() - chrono::Duration::days(days);
+    match RecordRepository::new(db)
+        .prune_command_logs_before(cutoff)
+        .await?
+    {
+        0 => debug!("No command logs past retention"),
+        count => info!(count, days, "Pruned command logs past retention"),
+    }
+    Ok(())
+}
+
+/// Refresh SQLite query planner statistics
+async fn optimize_database(db: Arc<DatabaseConnection>) -> JobResult {
+    db.execute_unprepared("PRAGMA optimize").await?;
+    debug!("Ran PRAGMA optimize");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use crate::entity::{report_daily_aggregates, user_reports};
+
+    async fn report(db: &DatabaseConnection, created_at: chrono::DateTime<Utc>) {
+        user_reports::ActiveModel {
+            user_id: Set("10".to_string()),
+            incident_type: Set("login".to_string()),
+            status: Set("active".to_string()),
+            created_at: Set(created_at),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_aggregation_continues_past_long_gap_without_reports() {
+        let db = Arc::new(connect_in_memory().await);
+        let now = Utc::now();
+        let today = now.date_naive();
+        // 130 empty days between the two reports, over two runs' worth
+        report(&db, now - chrono::Duration::days(150)).await;
+        report(&db, now - chrono::Duration::days(20)).await;
+
+        for _ in 0..3 {
+            aggregate_reports(db.clone()).await.unwrap();
+        }
+
+        let aggregates = ReportAggregateRepository::new(db.clone());
+        assert_eq!(
+            aggregates.aggregated_through().await.unwrap(),
+            Some(today - chrono::Duration::days(1))
+        );
+        let days: Vec<_> = report_daily_aggregates::Entity::find()
+            .all(&*db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.day)
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                today - chrono::Duration::days(150),
+                today - chrono::Duration::days(20)
+            ]
+        );
+    }
+}
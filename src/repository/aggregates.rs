@@ -0,0 +1,380 @@
+//! Repository for daily report aggregates
+//!
+//! Each completed UTC day of `user_reports` is summarized per incident type
+//! before the raw rows are pruned. Features that look further back than the
+//! raw retention window read aggregates for the old days and raw rows for
+//! the rest, joined with [`stitch_daily`].
+
+use chrono::{NaiveDate, NaiveTime, Utc};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::{bot_config, report_daily_aggregates, user_reports};
+
+use super::reports::UNCOUNTED_STATUSES;
+
+/// `bot_config` key holding the last aggregated day (`YYYY-MM-DD`)
+///
+/// Days without counted reports write no aggregate rows, so the cursor, not
+/// the latest aggregate row, tells where aggregation resumes.
+pub const AGGREGATED_THROUGH_KEY: &str = "reports.aggregated_through";
+
+/// Join per-day values from aggregates (days before `raw_from`) and raw rows
+/// (days from `raw_from` on), oldest first
+///
+/// Each day comes from exactly one source, so a day present in both is not
+/// counted twice.
+pub fn stitch_daily(
+    aggregated: &[(NaiveDate, u64)],
+    raw: &[(NaiveDate, u64)],
+    raw_from: NaiveDate,
+) -> Vec<(NaiveDate, u64)> {
+    let mut days: Vec<(NaiveDate, u64)> = aggregated
+        .iter()
+        .filter(|(day, _)| *day < raw_from)
+        .chain(raw.iter().filter(|(day, _)| *day >= raw_from))
+        .copied()
+        .collect();
+    days.sort_by_key(|(day, _)| *day);
+    days
+}
+
+/// Repository for report aggregate operations
+pub struct ReportAggregateRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> ReportAggregateRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Summarize one UTC day of raw reports, replacing earlier rows for that day
+    ///
+    /// Duplicates and withdrawn reports are excluded. The day's rows are
+    /// deleted and rewritten in one transaction, so a type with no counted
+    /// reports left loses its row and retries are safe. Also moves the
+    /// [`AGGREGATED_THROUGH_KEY`] cursor forward to `day`. Returns the number
+    /// of incident types written.
+    pub async fn aggregate_day(&self, day: NaiveDate) -> Result<u64, sea_orm::DbErr>
+    where
+        C::Target: TransactionTrait,
+    {
+        let start = day.and_time(NaiveTime::MIN).and_utc();
+        let end = start + chrono::Duration::days(1);
+        let txn = self.db.begin().await?;
+
+        let rows = user_reports::Entity::find()
+            .select_only()
+            .column(user_reports::Column::IncidentType)
+            .column_as(Expr::cust("COUNT(DISTINCT user_id)"), "distinct_reporters")
+            .column_as(user_reports::Column::Id.count(), "total_reports")
+            .column_as(Expr::cust("COUNT(DISTINCT guild_id)"), "guild_count")
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(start))
+            .filter(user_reports::Column::CreatedAt.lt(end))
+            .group_by(user_reports::Column::IncidentType)
+            .into_tuple::<(String, i64, i64, i64)>()
+            .all(&txn)
+            .await?;
+
+        report_daily_aggregates::Entity::delete_many()
+            .filter(report_daily_aggregates::Column::Day.eq(day))
+            .exec(&txn)
+            .await?;
+
+        let now = Utc::now();
+        let written = rows.len() as u64;
+        let models = rows.into_iter().map(
+            |(incident_type, distinct_reporters, total_reports, guild_count)| {
+                report_daily_aggregates::ActiveModel {
+                    day: Set(day),
+                    incident_type: Set(incident_type),
+                    distinct_reporters: Set(distinct_reporters),
+                    total_reports: Set(total_reports),
+                    guild_count: Set(guild_count),
+                    updated_at: Set(now),
+                }
+            },
+        );
+        report_daily_aggregates::Entity::insert_many(models)
+            .on_empty_do_nothing()
+            .exec_without_returning(&txn)
+            .await?;
+
+        if aggregated_through(&txn)
+            .await?
+            .is_none_or(|through| through < day)
+        {
+            let cursor = bot_config::ActiveModel {
+                key: Set(AGGREGATED_THROUGH_KEY.to_string()),
+                value: Set(day.to_string()),
+                updated_at: Set(now),
+            };
+            bot_config::Entity::insert(cursor)
+                .on_conflict(
+                    OnConflict::column(bot_config::Column::Key)
+                        .update_columns([bot_config::Column::Value, bot_config::Column::UpdatedAt])
+                        .to_owned(),
+                )
+                .exec_without_returning(&txn)
+                .await?;
+        }
+
+        txn.commit().await?;
+        Ok(written)
+    }
+
+    /// Last day summarized by [`Self::aggregate_day`], including days without reports
+    pub async fn aggregated_through(&self) -> Result<Option<NaiveDate>, sea_orm::DbErr> {
+        aggregated_through(&*self.db).await
+    }
+
+    /// Latest day with aggregate rows
+    pub async fn latest_day(&self) -> Result<Option<NaiveDate>, sea_orm::DbErr> {
+        report_daily_aggregates::Entity::find()
+            .select_only()
+            .column(report_daily_aggregates::Column::Day)
+            .order_by_desc(report_daily_aggregates::Column::Day)
+            .into_tuple::<NaiveDate>()
+            .one(&*self.db)
+            .await
+    }
+
+    /// Reporters per day in `[since, until)`, summed over incident types
+    ///
+    /// A user who reported several types on one day is counted once per
+    /// type; distinct users across types are not kept. Days without rows are
+    /// omitted.
+    pub async fn daily_reporters_between(
+        &self,
+        since: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<(NaiveDate, u64)>, sea_orm::DbErr> {
+        let rows = report_daily_aggregates::Entity::find()
+            .select_only()
+            .column(report_daily_aggregates::Column::Day)
+            .column_as(
+                report_daily_aggregates::Column::DistinctReporters.sum(),
+                "count",
+            )
+            .filter(report_daily_aggregates::Column::Day.gte(since))
+            .filter(report_daily_aggregates::Column::Day.lt(until))
+            .group_by(report_daily_aggregates::Column::Day)
+            .into_tuple::<(NaiveDate, i64)>()
+            .all(&*self.db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(day, count)| (day, count.max(0) as u64))
+            .collect())
+    }
+}
+
+/// Read the aggregation cursor, ignoring a value that is not a date
+async fn aggregated_through<C: ConnectionTrait>(
+    db: &C,
+) -> Result<Option<NaiveDate>, sea_orm::DbErr> {
+    Ok(bot_config::Entity::find_by_id(AGGREGATED_THROUGH_KEY)
+        .one(db)
+        .await?
+        .and_then(|config| config.value.parse().ok()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use crate::repository::RecordRepository;
+    use crate::repository::reports::{ReportRepository, STATUS_COUNTED, STATUS_DUPLICATE};
+    use chrono::{DateTime, TimeZone};
+    use sea_orm::ActiveModelTrait;
+
+    fn at(d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2028, 3, d, h, 0, 0).unwrap()
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2028, 3, d).unwrap()
+    }
+
+    async fn report(
+        db: &DatabaseConnection,
+        guild: Option<&str>,
+        user: &str,
+        incident_type: &str,
+        status: &str,
+        created_at: DateTime<Utc>,
+    ) {
+        user_reports::ActiveModel {
+            guild_id: Set(guild.map(str::to_string)),
+            user_id: Set(user.to_string()),
+            incident_type: Set(incident_type.to_string()),
+            status: Set(status.to_string()),
+            created_at: Set(created_at),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    /// Aggregate rows without `updated_at`, which changes on every run
+    async fn rows(db: &DatabaseConnection) -> Vec<(NaiveDate, String, i64, i64, i64)> {
+        report_daily_aggregates::Entity::find()
+            .order_by_asc(report_daily_aggregates::Column::Day)
+            .order_by_asc(report_daily_aggregates::Column::IncidentType)
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| {
+                (
+                    row.day,
+                    row.incident_type,
+                    row.distinct_reporters,
+                    row.total_reports,
+                    row.guild_count,
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_day_twice_writes_identical_single_rows() {
+        let db = Arc::new(connect_in_memory().await);
+        report(&db, Some("1"), "10", "login", "active", at(5, 0)).await;
+        report(&db, Some("1"), "10", "login", STATUS_COUNTED, at(5, 6)).await;
+        report(&db, Some("2"), "11", "login", "expired", at(5, 12)).await;
+        report(&db, None, "12", "instance", "active", at(5, 23)).await;
+        report(&db, Some("1"), "13", "login", STATUS_DUPLICATE, at(5, 8)).await;
+        // Neighbouring days stay out of the aggregate
+        report(&db, Some("3"), "14", "login", "active", at(4, 23)).await;
+        report(&db, Some("3"), "14", "login", "active", at(6, 0)).await;
+        let repo = ReportAggregateRepository::new(db.clone());
+
+        assert_eq!(repo.aggregate_day(day(5)).await.unwrap(), 2);
+        let first = rows(&db).await;
+        assert_eq!(repo.aggregate_day(day(5)).await.unwrap(), 2);
+
+        assert_eq!(rows(&db).await, first);
+        assert_eq!(
+            first,
+            vec![
+                (day(5), "instance".to_string(), 1, 1, 0),
+                (day(5), "login".to_string(), 2, 3, 2),
+            ]
+        );
+        assert_eq!(repo.latest_day().await.unwrap(), Some(day(5)));
+    }
+
+    #[tokio::test]
+    async fn test_rerun_drops_type_without_counted_reports() {
+        let db = Arc::new(connect_in_memory().await);
+        report(&db, Some("1"), "10", "login", "active", at(5, 0)).await;
+        report(&db, Some("1"), "11", "instance", "active", at(5, 1)).await;
+        let repo = ReportAggregateRepository::new(db.clone());
+        assert_eq!(repo.aggregate_day(day(5)).await.unwrap(), 2);
+
+        user_reports::Entity::update_many()
+            .col_expr(user_reports::Column::Status, Expr::value(STATUS_DUPLICATE))
+            .filter(user_reports::Column::IncidentType.eq("instance"))
+            .exec(&*db)
+            .await
+            .unwrap();
+        assert_eq!(repo.aggregate_day(day(5)).await.unwrap(), 1);
+
+        assert_eq!(
+            rows(&db).await,
+            vec![(day(5), "login".to_string(), 1, 1, 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cursor_advances_over_empty_days_only_forward() {
+        let db = Arc::new(connect_in_memory().await);
+        let repo = ReportAggregateRepository::new(db.clone());
+        assert_eq!(repo.aggregated_through().await.unwrap(), None);
+
+        assert_eq!(repo.aggregate_day(day(5)).await.unwrap(), 0);
+        assert_eq!(repo.aggregated_through().await.unwrap(), Some(day(5)));
+        assert_eq!(repo.latest_day().await.unwrap(), None);
+
+        // Re-running an earlier day keeps the cursor where it is
+        repo.aggregate_day(day(3)).await.unwrap();
+        assert_eq!(repo.aggregated_through().await.unwrap(), Some(day(5)));
+    }
+
+    #[tokio::test]
+    async fn test_stitched_series_matches_raw_series() {
+        let db = Arc::new(connect_in_memory().await);
+        // One type per user per day, so summing types counts each user once
+        for d in 1..=6 {
+            for user in 0..d {
+                let incident_type = if user % 2 == 0 { "login" } else { "instance" };
+                report(
+                    &db,
+                    Some("1"),
+                    &user.to_string(),
+                    incident_type,
+                    "active",
+                    at(d, user),
+                )
+                .await;
+            }
+        }
+        report(&db, Some("1"), "0", "login", "active", at(2, 20)).await;
+        let records = RecordRepository::new(db.clone());
+        let raw_before = records
+            .daily_distinct_reporters(None, at(1, 0))
+            .await
+            .unwrap();
+
+        // Summarize and prune the first three days, as the maintenance job does
+        let raw_from = day(4);
+        let aggregates = ReportAggregateRepository::new(db.clone());
+        for d in 1..=3 {
+            aggregates.aggregate_day(day(d)).await.unwrap();
+        }
+        ReportRepository::new(db.clone())
+            .prune_before(at(4, 0))
+            .await
+            .unwrap();
+        let aggregated = aggregates
+            .daily_reporters_between(day(1), raw_from)
+            .await
+            .unwrap();
+        let raw_after = records
+            .daily_distinct_reporters(None, at(1, 0))
+            .await
+            .unwrap();
+
+        assert_eq!(raw_after.first().map(|(d, _)| *d), Some(raw_from));
+        assert_eq!(stitch_daily(&aggregated, &raw_after, raw_from), raw_before);
+        assert_eq!(
+            raw_before,
+            (1..=6).map(|d| (day(d), d as u64)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_stitch_takes_each_day_from_one_source() {
+        let aggregated = [(day(1), 5), (day(2), 6), (day(3), 7)];
+        let raw = [(day(3), 9), (day(4), 1)];
+
+        assert_eq!(
+            stitch_daily(&aggregated, &raw, day(3)),
+            vec![(day(1), 5), (day(2), 6), (day(3), 9), (day(4), 1)]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Repository for observed VRChat client builds
+
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait, QueryOrder, QuerySelect, Set};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::vrc_builds;
+
+/// Repository for VRChat build operations
+pub struct BuildRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> BuildRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Record a build version if it has not been seen before
+    ///
+    /// Returns `true` if this is the first time the version was seen.
+    pub async fn record(
+        &self,
+        version: &str,
+        deployment_group: Option<String>,
+        seen_at: DateTime<Utc>,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let model = vrc_builds::ActiveModel {
+            version: Set(version.to_string()),
+            deployment_group: Set(deployment_group),
+            first_seen_at: Set(seen_at),
+            ..Default::default()
+        };
+        let inserted = vrc_builds::Entity::insert(model)
+            .on_conflict(
+                OnConflict::column(vrc_builds::Column::Version)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&*self.db)
+            .await?;
+        Ok(inserted > 0)
+    }
+
+    /// Most recently first-seen build, if it replaced an earlier one
+    ///
+    /// The first build ever recorded is the baseline observed when tracking
+    /// started, not a release, so it is never returned.
+    pub async fn latest_new_build(&self) -> Result<Option<vrc_builds::Model>, sea_orm::DbErr> {
+        let builds = vrc_builds::Entity::find()
+            .order_by_desc(vrc_builds::Column::FirstSeenAt)
+            .order_by_desc(vrc_builds::Column::Id)
+            .limit(2)
+            .all(&*self.db)
+            .await?;
+
+        if builds.len() < 2 {
+            return Ok(None);
+        }
+        Ok(builds.into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    async fn setup() -> BuildRepository {
+        BuildRepository::new(Arc::new(connect_in_memory().await))
+    }
+
+    #[tokio::test]
+    async fn test_record_first_seen_once() {
+        let repo = setup().await;
+
+        let first = repo.record("build-1580", Some("blue".into()), at(1)).await;
+        let again = repo.record("build-1580", Some("green".into()), at(5)).await;
+
+        assert!(first.unwrap());
+        assert!(!again.unwrap());
+        let all = vrc_builds::Entity::find().all(&*repo.db).await.unwrap();
+        assert_eq!(all.len(), 1);
+        // The first sighting is kept
+        assert_eq!(all[0].first_seen_at, at(1));
+        assert_eq!(all[0].deployment_group.as_deref(), Some("blue"));
+    }
+
+    #[tokio::test]
+    async fn test_baseline_build_is_not_new() {
+        let repo = setup().await;
+        assert_eq!(repo.latest_new_build().await.unwrap(), None);
+
+        repo.record("build-1580", None, at(1)).await.unwrap();
+
+        assert_eq!(repo.latest_new_build().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_latest_new_build() {
+        let repo = setup().await;
+        repo.record("build-1580", None, at(1)).await.unwrap();
+        repo.record("build-1581", None, at(3)).await.unwrap();
+        repo.record("build-1582", None, at(6)).await.unwrap();
+        // Seeing an older build again doesn't make it the latest
+        repo.record("build-1581", None, at(8)).await.unwrap();
+
+        let latest = repo.latest_new_build().await.unwrap().unwrap();
+
+        assert_eq!(latest.version, "build-1582");
+        assert_eq!(latest.first_seen_at, at(6));
+    }
+
+    #[tokio::test]
+    async fn test_latest_new_build_same_time_prefers_last_recorded() {
+        let repo = setup().await;
+        let seen = at(1);
+        repo.record("build-1580", None, seen).await.unwrap();
+        repo.record("build-1581", None, seen).await.unwrap();
+
+        let latest = repo.latest_new_build().await.unwrap().unwrap();
+
+        assert_eq!(latest.version, "build-1581");
+    }
+}
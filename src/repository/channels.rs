@@ -0,0 +1,167 @@
+//! Repository for per-type guild alert channels
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use serenity::all::{ChannelId, GuildId};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::guild_alert_channels;
+
+/// Repository for guild alert channel operations
+pub struct AlertChannelRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> AlertChannelRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Enabled extra channels of a guild, oldest first
+    pub async fn list_enabled(
+        &self,
+        guild_id: &str,
+    ) -> Result<Vec<guild_alert_channels::Model>, sea_orm::DbErr> {
+        guild_alert_channels::Entity::find()
+            .filter(guild_alert_channels::Column::GuildId.eq(guild_id))
+            .filter(guild_alert_channels::Column::Enabled.eq(true))
+            .order_by_asc(guild_alert_channels::Column::Id)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Every extra channel of a guild, including disabled ones, oldest first
+    pub async fn list(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Vec<guild_alert_channels::Model>, sea_orm::DbErr> {
+        guild_alert_channels::Entity::find()
+            .filter(guild_alert_channels::Column::GuildId.eq(guild_id.to_string()))
+            .order_by_asc(guild_alert_channels::Column::Id)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Route an incident type to a channel, adding the channel if needed
+    pub async fn add_type(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        incident_type: &str,
+    ) -> Result<guild_alert_channels::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let existing = guild_alert_channels::Entity::find()
+            .filter(guild_alert_channels::Column::GuildId.eq(guild_id.to_string()))
+            .filter(guild_alert_channels::Column::ChannelId.eq(channel_id.to_string()))
+            .one(&*self.db)
+            .await?;
+
+        match existing {
+            Some(row) => {
+                let mut types: Vec<&str> = channel_types(&row);
+                if !types.contains(&incident_type) {
+                    types.push(incident_type);
+                }
+                let incident_types = types.join(",");
+                let mut model: guild_alert_channels::ActiveModel = row.into();
+                model.incident_types = Set(incident_types);
+                model.enabled = Set(true);
+                model.updated_at = Set(now);
+                model.update(&*self.db).await
+            }
+            None => {
+                let model = guild_alert_channels::ActiveModel {
+                    guild_id: Set(guild_id.to_string()),
+                    channel_id: Set(channel_id.to_string()),
+                    incident_types: Set(incident_type.to_string()),
+                    enabled: Set(true),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                    ..Default::default()
+                };
+                model.insert(&*self.db).await
+            }
+        }
+    }
+
+    /// Stop routing an incident type to extra channels, returning how many
+    /// channels changed
+    ///
+    /// Channels left without types are deleted.
+    pub async fn remove_type(
+        &self,
+        guild_id: GuildId,
+        incident_type: &str,
+    ) -> Result<u64, sea_orm::DbErr> {
+        let rows = guild_alert_channels::Entity::find()
+            .filter(guild_alert_channels::Column::GuildId.eq(guild_id.to_string()))
+            .all(&*self.db)
+            .await?;
+
+        let mut changed = 0;
+        for row in rows {
+            let types: Vec<&str> = channel_types(&row)
+                .into_iter()
+                .filter(|t| *t != incident_type)
+                .collect();
+            if types.len() == channel_types(&row).len() {
+                continue;
+            }
+            changed += 1;
+
+            if types.is_empty() {
+                guild_alert_channels::Entity::delete_by_id(row.id)
+                    .exec(&*self.db)
+                    .await?;
+            } else {
+                let incident_types = types.join(",");
+                let mut model: guild_alert_channels::ActiveModel = row.into();
+                model.incident_types = Set(incident_types);
+                model.updated_at = Set(Utc::now());
+                model.update(&*self.db).await?;
+            }
+        }
+        Ok(changed)
+    }
+}
+
+/// Incident types routed to a channel, in stored order
+pub fn channel_types(row: &guild_alert_channels::Model) -> Vec<&str> {
+    row.incident_types
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Channels that should receive an alert of `incident_type`
+///
+/// Extra channels routed to the type take it; otherwise it goes to the main
+/// channel (`guild_configs.channel_id`). A guild without extra channels
+/// therefore gets every alert in its main channel, as before extra channels
+/// existed.
+pub fn route_channels<'a>(
+    main_channel: Option<&'a str>,
+    channels: &'a [guild_alert_channels::Model],
+    incident_type: &str,
+) -> Vec<&'a str> {
+    let routed: Vec<&str> = channels
+        .iter()
+        .filter(|row| row.enabled && channel_types(row).contains(&incident_type))
+        .map(|row| row.channel_id.as_str())
+        .collect();
+    if routed.is_empty() {
+        main_channel.into_iter().collect()
+    } else {
+        routed
+    }
+}
@@ -1,27 +1,62 @@
 //! Repository for guild and user configuration
 
-use chrono::Utc;
+use chrono::{DateTime, Timelike, Utc};
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
-    Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, Set,
 };
-use serenity::all::{ChannelId, GuildId, UserId};
+use serenity::all::{ChannelId, GuildId, RoleId, UserId};
+use std::ops::Deref;
 use std::sync::Arc;
+use thiserror::Error;
 
 use crate::entity::{guild_configs, user_configs};
+use crate::i18n::{self, SUPPORTED_LOCALES, normalize_locale};
+
+// =============================================================================
+// Language Validation
+// =============================================================================
+
+/// Failure while updating a language preference
+#[derive(Debug, Error)]
+pub enum LanguageError {
+    /// Code does not normalize to a supported locale
+    #[error("Unsupported language code: {0}")]
+    Unsupported(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sea_orm::DbErr),
+}
+
+/// Normalize a language preference before it is written
+///
+/// `None` (auto-detect) passes through unchanged.
+pub fn validate_language(language: Option<String>) -> Result<Option<String>, LanguageError> {
+    match language {
+        None => Ok(None),
+        Some(code) => normalize_locale(&code)
+            .map(|locale| Some(locale.to_string()))
+            .ok_or(LanguageError::Unsupported(code)),
+    }
+}
 
 // =============================================================================
 // Guild Config Repository
 // =============================================================================
 
 /// Repository for guild configuration operations
-pub struct GuildConfigRepository {
-    db: Arc<DatabaseConnection>,
+pub struct GuildConfigRepository<C = Arc<DatabaseConnection>> {
+    db: C,
 }
 
-impl GuildConfigRepository {
-    /// Create a new repository instance
-    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+impl<C> GuildConfigRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
         Self { db }
     }
 
@@ -46,6 +81,17 @@ impl GuildConfigRepository {
             channel_id: Set(Some(channel_id.to_string())),
             enabled: Set(true),
             language: Set(None),
+            fallback_dm: Set(false),
+            paused_until: Set(None),
+            muted_incident_types: Set(None),
+            alert_threshold: Set(None),
+            report_role_id: Set(None),
+            mention_role_id: Set(None),
+            digest_enabled: Set(false),
+            alert_quiet_start: Set(None),
+            alert_quiet_end: Set(None),
+            channel_invalid: Set(false),
+            consecutive_send_failures: Set(0),
             created_at: Set(now),
             updated_at: Set(now),
         };
@@ -53,11 +99,14 @@ impl GuildConfigRepository {
     }
 
     /// Update guild language preference
+    ///
+    /// The code is normalized first; unsupported codes are rejected.
     pub async fn update_language(
         &self,
         guild_id: GuildId,
         language: Option<String>,
-    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+    ) -> Result<guild_configs::Model, LanguageError> {
+        let language = validate_language(language)?;
         let now = Utc::now();
         let model = guild_configs::ActiveModel {
             guild_id: Set(guild_id.to_string()),
@@ -65,10 +114,218 @@ impl GuildConfigRepository {
             updated_at: Set(now),
             ..Default::default()
         };
+        let updated = model.update(&*self.db).await?;
+        i18n::invalidate_guild(guild_id);
+        Ok(updated)
+    }
+
+    /// Set a guild's language, creating a config if the guild has none
+    ///
+    /// A new config is created disabled and without a channel: remembering a
+    /// language does not register the guild for alerts.
+    pub async fn upsert_language(
+        &self,
+        guild_id: GuildId,
+        language: &str,
+    ) -> Result<guild_configs::Model, LanguageError> {
+        if self.get(guild_id).await.is_some() {
+            return self
+                .update_language(guild_id, Some(language.to_string()))
+                .await;
+        }
+
+        let language = validate_language(Some(language.to_string()))?;
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            channel_id: Set(None),
+            enabled: Set(false),
+            language: Set(language),
+            fallback_dm: Set(false),
+            paused_until: Set(None),
+            muted_incident_types: Set(None),
+            alert_threshold: Set(None),
+            report_role_id: Set(None),
+            mention_role_id: Set(None),
+            digest_enabled: Set(false),
+            alert_quiet_start: Set(None),
+            alert_quiet_end: Set(None),
+            channel_invalid: Set(false),
+            consecutive_send_failures: Set(0),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        let inserted = model.insert(&*self.db).await?;
+        i18n::invalidate_guild(guild_id);
+        Ok(inserted)
+    }
+
+    /// Configs whose stored language is not a supported locale
+    pub async fn find_invalid_languages(
+        &self,
+    ) -> Result<Vec<guild_configs::Model>, sea_orm::DbErr> {
+        guild_configs::Entity::find()
+            .filter(guild_configs::Column::Language.is_not_null())
+            .filter(guild_configs::Column::Language.is_not_in(SUPPORTED_LOCALES.iter().copied()))
+            .all(&*self.db)
+            .await
+    }
+
+    /// Update guild owner DM fallback preference
+    pub async fn update_fallback_dm(
+        &self,
+        guild_id: GuildId,
+        enabled: bool,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            fallback_dm: Set(enabled),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Set the role required to submit reports; `None` lets everyone report
+    pub async fn update_report_role(
+        &self,
+        guild_id: GuildId,
+        role_id: Option<RoleId>,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            report_role_id: Set(role_id.map(|id| id.to_string())),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Replace the incident types a guild receives no alerts for
+    ///
+    /// An empty list is stored as NULL, i.e. every type enabled.
+    pub async fn update_muted_incident_types(
+        &self,
+        guild_id: GuildId,
+        muted: &[&str],
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            muted_incident_types: Set(encode_incident_types(muted)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Set the guild's report threshold; `None` follows the global threshold
+    pub async fn update_alert_threshold(
+        &self,
+        guild_id: GuildId,
+        threshold: Option<i64>,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            alert_threshold: Set(threshold),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Set the role pinged by threshold alerts; `None` clears it
+    pub async fn update_mention_role(
+        &self,
+        guild_id: GuildId,
+        role_id: Option<RoleId>,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            mention_role_id: Set(role_id.map(|id| id.to_string())),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Set or clear the daily quiet window as `(start, end)` UTC hours
+    pub async fn update_quiet_hours(
+        &self,
+        guild_id: GuildId,
+        window: Option<(u32, u32)>,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            alert_quiet_start: Set(window.map(|(start, _)| start as i32)),
+            alert_quiet_end: Set(window.map(|(_, end)| end as i32)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Turn the weekly status digest on or off
+    pub async fn update_digest_enabled(
+        &self,
+        guild_id: GuildId,
+        enabled: bool,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            digest_enabled: Set(enabled),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Registered guilds with an alert channel that turned the weekly digest on
+    pub async fn list_digest_enabled(&self) -> Result<Vec<guild_configs::Model>, sea_orm::DbErr> {
+        guild_configs::Entity::find()
+            .filter(guild_configs::Column::Enabled.eq(true))
+            .filter(guild_configs::Column::ChannelId.is_not_null())
+            .filter(guild_configs::Column::DigestEnabled.eq(true))
+            .all(&*self.db)
+            .await
+    }
+
+    /// Pause alerts for a guild until `until`; they resume automatically
+    pub async fn pause(
+        &self,
+        guild_id: GuildId,
+        until: DateTime<Utc>,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            paused_until: Set(Some(until)),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Clear a pause before it expires
+    pub async fn resume(&self, guild_id: GuildId) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            paused_until: Set(None),
+            updated_at: Set(now),
+            ..Default::default()
+        };
         model.update(&*self.db).await
     }
 
-    /// Re-enable existing guild config with new channel
+    /// Re-enable existing guild config with new channel (clears any pause, the
+    /// invalid channel mark, and the delivery failure count)
     pub async fn reenable(
         &self,
         guild_id: GuildId,
@@ -79,6 +336,9 @@ impl GuildConfigRepository {
             guild_id: Set(guild_id.to_string()),
             channel_id: Set(Some(channel_id.to_string())),
             enabled: Set(true),
+            paused_until: Set(None),
+            channel_invalid: Set(false),
+            consecutive_send_failures: Set(0),
             updated_at: Set(now),
             ..Default::default()
         };
@@ -113,6 +373,62 @@ impl GuildConfigRepository {
         model.update(&*self.db).await
     }
 
+    /// Disable a guild whose alert channel can no longer be reached
+    ///
+    /// Like `disable`, but marks the channel as the reason so /config show
+    /// can explain it. `/config setup` clears the mark via `reenable`.
+    pub async fn mark_channel_invalid(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<guild_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_configs::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            enabled: Set(false),
+            channel_invalid: Set(true),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Count a failed alert delivery, returning the new consecutive count
+    pub async fn record_send_failure(&self, guild_id: GuildId) -> Result<i32, sea_orm::DbErr> {
+        guild_configs::Entity::update_many()
+            .col_expr(
+                guild_configs::Column::ConsecutiveSendFailures,
+                Expr::col(guild_configs::Column::ConsecutiveSendFailures).add(1),
+            )
+            .filter(guild_configs::Column::GuildId.eq(guild_id.to_string()))
+            .exec(&*self.db)
+            .await?;
+        Ok(self
+            .get(guild_id)
+            .await
+            .map_or(0, |config| config.consecutive_send_failures))
+    }
+
+    /// Reset the delivery failure count after a successful delivery
+    pub async fn reset_send_failures(&self, guild_id: GuildId) -> Result<(), sea_orm::DbErr> {
+        guild_configs::Entity::update_many()
+            .col_expr(
+                guild_configs::Column::ConsecutiveSendFailures,
+                Expr::value(0),
+            )
+            .filter(guild_configs::Column::GuildId.eq(guild_id.to_string()))
+            .exec(&*self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// All enabled guild configs, including paused ones
+    pub async fn list_all_enabled(&self) -> Result<Vec<guild_configs::Model>, sea_orm::DbErr> {
+        guild_configs::Entity::find()
+            .filter(guild_configs::Column::Enabled.eq(true))
+            .all(&*self.db)
+            .await
+    }
+
     /// Count enabled guild configs
     pub async fn count_enabled(&self) -> Result<u64, sea_orm::DbErr> {
         guild_configs::Entity::find()
@@ -122,18 +438,128 @@ impl GuildConfigRepository {
     }
 }
 
+/// Whether a guild's alerts are paused at `now`
+///
+/// Pauses expire on their own: once `paused_until` has passed the guild
+/// receives alerts again without any cleanup.
+pub fn is_paused(config: &guild_configs::Model, now: DateTime<Utc>) -> bool {
+    config.paused_until.is_some_and(|until| until > now)
+}
+
+/// Whether `count` reporters reach a guild's threshold
+///
+/// The guild's own threshold wins; guilds without one use `global`.
+pub fn meets_guild_threshold(config: &guild_configs::Model, count: i64, global: i64) -> bool {
+    count >= config.alert_threshold.unwrap_or(global)
+}
+
+/// A guild's quiet window as `(start, end)` UTC hours, if set
+///
+/// Rows with only one bound or an hour outside 0-23 count as unset.
+pub fn quiet_hours(config: &guild_configs::Model) -> Option<(u32, u32)> {
+    let hour = |value: Option<i32>| {
+        value
+            .and_then(|h| u32::try_from(h).ok())
+            .filter(|h| *h < 24)
+    };
+    Some((
+        hour(config.alert_quiet_start)?,
+        hour(config.alert_quiet_end)?,
+    ))
+}
+
+/// Whether `hour` falls in the quiet window starting at `start` and ending
+/// before `end`
+///
+/// Windows with `start > end` wrap past midnight (22-6 covers 22:00-05:59).
+/// An empty window (`start == end`) never matches.
+pub fn in_quiet_window(start: u32, end: u32, hour: u32) -> bool {
+    if start <= end {
+        (start..end).contains(&hour)
+    } else {
+        hour >= start || hour < end
+    }
+}
+
+/// Whether a guild's alerts are held back by its quiet hours at `now`
+pub fn is_quiet(config: &guild_configs::Model, now: DateTime<Utc>) -> bool {
+    quiet_hours(config).is_some_and(|(start, end)| in_quiet_window(start, end, now.hour()))
+}
+
+/// Role pinged by a guild's threshold alerts, if one is set
+pub fn mention_role(config: &guild_configs::Model) -> Option<RoleId> {
+    config
+        .mention_role_id
+        .as_deref()
+        .and_then(|id| id.parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .map(RoleId::new)
+}
+
+/// Incident types a guild has turned off, in stored order
+pub fn muted_incident_types(config: &guild_configs::Model) -> Vec<&str> {
+    config
+        .muted_incident_types
+        .as_deref()
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a guild receives alerts for an incident type
+///
+/// Types are on unless muted, so guilds that never used /config alerts get
+/// everything, including types added later.
+pub fn is_subscribed(config: &guild_configs::Model, incident_type: &str) -> bool {
+    !muted_incident_types(config).contains(&incident_type)
+}
+
+/// Stored form of a muted type list; `None` when nothing is muted
+pub fn encode_incident_types(types: &[&str]) -> Option<String> {
+    (!types.is_empty()).then(|| types.join(","))
+}
+
+/// Role a member needs to submit /report in a guild, if one is set
+pub fn report_role(config: &guild_configs::Model) -> Option<RoleId> {
+    config
+        .report_role_id
+        .as_deref()
+        .and_then(|id| id.parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .map(RoleId::new)
+}
+
+/// Whether a member with `member_roles` may report under `required`
+///
+/// No required role lets everyone report.
+pub fn may_report(required: Option<RoleId>, member_roles: &[RoleId]) -> bool {
+    required.is_none_or(|role| member_roles.contains(&role))
+}
+
 // =============================================================================
 // User Config Repository
 // =============================================================================
 
+/// `user_configs.disabled_reason` after Discord refused a DM alert
+pub const DISABLED_REASON_DM_UNREACHABLE: &str = "dm_unreachable";
+
 /// Repository for user configuration operations
-pub struct UserConfigRepository {
-    db: Arc<DatabaseConnection>,
+pub struct UserConfigRepository<C = Arc<DatabaseConnection>> {
+    db: C,
 }
 
-impl UserConfigRepository {
-    /// Create a new repository instance
-    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+impl<C> UserConfigRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
         Self { db }
     }
 
@@ -153,6 +579,7 @@ impl UserConfigRepository {
             user_id: Set(user_id.to_string()),
             enabled: Set(true),
             language: Set(None),
+            disabled_reason: Set(None),
             created_at: Set(now),
             updated_at: Set(now),
         };
@@ -160,11 +587,14 @@ impl UserConfigRepository {
     }
 
     /// Update user language preference
+    ///
+    /// The code is normalized first; unsupported codes are rejected.
     pub async fn update_language(
         &self,
         user_id: UserId,
         language: Option<String>,
-    ) -> Result<user_configs::Model, sea_orm::DbErr> {
+    ) -> Result<user_configs::Model, LanguageError> {
+        let language = validate_language(language)?;
         let now = Utc::now();
         let model = user_configs::ActiveModel {
             user_id: Set(user_id.to_string()),
@@ -172,15 +602,57 @@ impl UserConfigRepository {
             updated_at: Set(now),
             ..Default::default()
         };
-        model.update(&*self.db).await
+        let updated = model.update(&*self.db).await?;
+        i18n::invalidate_user(user_id);
+        Ok(updated)
+    }
+
+    /// Set a user's language, creating a config if the user has none
+    ///
+    /// A new config is created disabled: remembering a language does not
+    /// register the user for alerts.
+    pub async fn upsert_language(
+        &self,
+        user_id: UserId,
+        language: &str,
+    ) -> Result<user_configs::Model, LanguageError> {
+        if self.get(user_id).await.is_some() {
+            return self
+                .update_language(user_id, Some(language.to_string()))
+                .await;
+        }
+
+        let language = validate_language(Some(language.to_string()))?;
+        let now = Utc::now();
+        let model = user_configs::ActiveModel {
+            user_id: Set(user_id.to_string()),
+            enabled: Set(false),
+            language: Set(language),
+            disabled_reason: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+        let inserted = model.insert(&*self.db).await?;
+        i18n::invalidate_user(user_id);
+        Ok(inserted)
+    }
+
+    /// Configs whose stored language is not a supported locale
+    pub async fn find_invalid_languages(&self) -> Result<Vec<user_configs::Model>, sea_orm::DbErr> {
+        user_configs::Entity::find()
+            .filter(user_configs::Column::Language.is_not_null())
+            .filter(user_configs::Column::Language.is_not_in(SUPPORTED_LOCALES.iter().copied()))
+            .all(&*self.db)
+            .await
     }
 
-    /// Re-enable existing user config
+    /// Re-enable existing user config (clears any disable reason)
     pub async fn reenable(&self, user_id: UserId) -> Result<user_configs::Model, sea_orm::DbErr> {
         let now = Utc::now();
         let model = user_configs::ActiveModel {
             user_id: Set(user_id.to_string()),
             enabled: Set(true),
+            disabled_reason: Set(None),
             updated_at: Set(now),
             ..Default::default()
         };
@@ -193,12 +665,40 @@ impl UserConfigRepository {
         let model = user_configs::ActiveModel {
             user_id: Set(user_id.to_string()),
             enabled: Set(false),
+            disabled_reason: Set(None),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.update(&*self.db).await
+    }
+
+    /// Disable a user whose DMs Discord refuses to deliver
+    ///
+    /// Like `disable`, but records `DISABLED_REASON_DM_UNREACHABLE` so
+    /// /config show can explain it. `/config setup` clears it via `reenable`.
+    pub async fn mark_dm_unreachable(
+        &self,
+        user_id: UserId,
+    ) -> Result<user_configs::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = user_configs::ActiveModel {
+            user_id: Set(user_id.to_string()),
+            enabled: Set(false),
+            disabled_reason: Set(Some(DISABLED_REASON_DM_UNREACHABLE.to_string())),
             updated_at: Set(now),
             ..Default::default()
         };
         model.update(&*self.db).await
     }
 
+    /// List all enabled user configs
+    pub async fn list_all_enabled(&self) -> Result<Vec<user_configs::Model>, sea_orm::DbErr> {
+        user_configs::Entity::find()
+            .filter(user_configs::Column::Enabled.eq(true))
+            .all(&*self.db)
+            .await
+    }
+
     /// Count enabled user configs
     pub async fn count_enabled(&self) -> Result<u64, sea_orm::DbErr> {
         user_configs::Entity::find()
@@ -207,3 +707,254 @@ impl UserConfigRepository {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{connect_in_memory, connect_in_memory_at};
+    use sea_orm::IntoActiveModel;
+
+    const GUILD: GuildId = GuildId::new(1);
+    const USER: UserId = UserId::new(2);
+
+    /// Migrations applied before `m20260115_001_normalize_language_values`
+    const BEFORE_LANGUAGE_REPAIR: u32 = 5;
+
+    async fn setup() -> Arc<DatabaseConnection> {
+        let db = Arc::new(connect_in_memory().await);
+        GuildConfigRepository::new(db.clone())
+            .create(GUILD, ChannelId::new(3))
+            .await
+            .unwrap();
+        UserConfigRepository::new(db.clone())
+            .create(USER)
+            .await
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_validate_language() {
+        assert_eq!(validate_language(None).unwrap(), None);
+        assert_eq!(
+            validate_language(Some(" en-US ".into())).unwrap(),
+            Some("en".into())
+        );
+        assert_eq!(
+            validate_language(Some("KO".into())).unwrap(),
+            Some("ko".into())
+        );
+        for code in ["", "fr", "korean"] {
+            let error = validate_language(Some(code.into())).unwrap_err();
+            assert!(
+                matches!(&error, LanguageError::Unsupported(raw) if raw == code),
+                "{error}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_language_normalizes() {
+        let db = setup().await;
+        let guilds = GuildConfigRepository::new(db.clone());
+        let users = UserConfigRepository::new(db.clone());
+
+        let guild = guilds
+            .update_language(GUILD, Some("ja-JP".into()))
+            .await
+            .unwrap();
+        let user = users
+            .update_language(USER, Some(" KO".into()))
+            .await
+            .unwrap();
+
+        assert_eq!(guild.language.as_deref(), Some("ja"));
+        assert_eq!(user.language.as_deref(), Some("ko"));
+        assert_eq!(
+            guilds.get(GUILD).await.unwrap().language.as_deref(),
+            Some("ja")
+        );
+        assert_eq!(
+            users.get(USER).await.unwrap().language.as_deref(),
+            Some("ko")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_language_rejects_unsupported() {
+        let db = setup().await;
+        let guilds = GuildConfigRepository::new(db.clone());
+        let users = UserConfigRepository::new(db.clone());
+        guilds
+            .update_language(GUILD, Some("ko".into()))
+            .await
+            .unwrap();
+
+        let guild = guilds.update_language(GUILD, Some("fr".into())).await;
+        let user = users.update_language(USER, Some("en-AU".into())).await;
+        let user_empty = users.update_language(USER, Some(String::new())).await;
+
+        assert!(matches!(guild, Err(LanguageError::Unsupported(_))));
+        assert!(
+            user.is_ok(),
+            "region variants normalize to their primary subtag"
+        );
+        assert!(matches!(user_empty, Err(LanguageError::Unsupported(_))));
+        // Rejected writes leave the stored value alone
+        assert_eq!(
+            guilds.get(GUILD).await.unwrap().language.as_deref(),
+            Some("ko")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_language_clears() {
+        let db = setup().await;
+        let guilds = GuildConfigRepository::new(db.clone());
+        guilds
+            .update_language(GUILD, Some("ko".into()))
+            .await
+            .unwrap();
+
+        let guild = guilds.update_language(GUILD, None).await.unwrap();
+
+        assert_eq!(guild.language, None);
+    }
+
+    #[tokio::test]
+    async fn test_guild_upsert_language_creates_disabled_config() {
+        let db = Arc::new(connect_in_memory().await);
+        let guilds = GuildConfigRepository::new(db.clone());
+
+        let guild = guilds.upsert_language(GUILD, "KO").await.unwrap();
+
+        assert_eq!(guild.language.as_deref(), Some("ko"));
+        assert!(!guild.enabled);
+        assert_eq!(guild.channel_id, None);
+        assert!(matches!(
+            guilds.upsert_language(GuildId::new(9), "fr").await,
+            Err(LanguageError::Unsupported(_))
+        ));
+        assert!(guilds.get(GuildId::new(9)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_guild_upsert_language_keeps_registration() {
+        let db = setup().await;
+        let guilds = GuildConfigRepository::new(db.clone());
+
+        guilds.upsert_language(GUILD, "ko").await.unwrap();
+
+        let guild = guilds.get(GUILD).await.unwrap();
+        assert_eq!(guild.language.as_deref(), Some("ko"));
+        assert!(guild.enabled);
+        assert_eq!(guild.channel_id.as_deref(), Some("3"));
+        assert!(matches!(
+            guilds.upsert_language(GUILD, "fr").await,
+            Err(LanguageError::Unsupported(_))
+        ));
+        assert_eq!(
+            guilds.get(GUILD).await.unwrap().language.as_deref(),
+            Some("ko")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_invalid_languages() {
+        let db = setup().await;
+        // Bypass the repository, as a raw write or an old row would
+        let mut guild = GuildConfigRepository::new(db.clone())
+            .get(GUILD)
+            .await
+            .unwrap()
+            .into_active_model();
+        guild.language = Set(Some("en-US".into()));
+        guild.update(&*db).await.unwrap();
+        let other = UserId::new(4);
+        let users = UserConfigRepository::new(db.clone());
+        users.create(other).await.unwrap();
+        users
+            .update_language(other, Some("en".into()))
+            .await
+            .unwrap();
+
+        let guilds = GuildConfigRepository::new(db.clone())
+            .find_invalid_languages()
+            .await
+            .unwrap();
+        let users = users.find_invalid_languages().await.unwrap();
+
+        assert_eq!(guilds.len(), 1);
+        assert_eq!(guilds[0].language.as_deref(), Some("en-US"));
+        assert!(users.is_empty(), "NULL and supported codes are valid");
+    }
+
+    #[tokio::test]
+    async fn test_language_repair_migration() {
+        use migration::{Migrator, MigratorTrait};
+
+        let db = connect_in_memory_at(Some(BEFORE_LANGUAGE_REPAIR)).await;
+        let cases = [
+            ("1", Some("en")),
+            ("2", Some(" KO ")),
+            ("3", Some("en-US")),
+            ("4", Some("en_GB")),
+            ("5", Some("fr")),
+            ("6", Some("")),
+            ("7", None),
+        ];
+        for (id, language) in cases {
+            let language = language.map_or("NULL".to_string(), |code| format!("'{code}'"));
+            db.execute_unprepared(&format!(
+                "INSERT INTO guild_configs (guild_id, channel_id, enabled, language, created_at, updated_at) \
+                 VALUES ('{id}', '10', 1, {language}, '2026-01-01 00:00:00', '2026-01-01 00:00:00'); \
+                 INSERT INTO user_configs (user_id, enabled, language, created_at, updated_at) \
+                 VALUES ('{id}', 1, {language}, '2026-01-01 00:00:00', '2026-01-01 00:00:00');"
+            ))
+            .await
+            .unwrap();
+        }
+
+        Migrator::up(&db, None).await.unwrap();
+
+        let db = Arc::new(db);
+        let guilds = GuildConfigRepository::new(db.clone());
+        let users = UserConfigRepository::new(db.clone());
+        let expected = [
+            ("1", Some("en")),
+            ("2", Some("ko")),
+            ("3", Some("en")),
+            ("4", Some("en")),
+            ("5", None),
+            ("6", None),
+            ("7", None),
+        ];
+        for (id, language) in expected {
+            let id: u64 = id.parse().unwrap();
+            let guild = guilds.get(GuildId::new(id)).await.unwrap();
+            let user = users.get(UserId::new(id)).await.unwrap();
+            assert_eq!(guild.language.as_deref(), language, "guild {id}");
+            assert_eq!(user.language.as_deref(), language, "user {id}");
+        }
+        assert!(guilds.find_invalid_languages().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_is_paused() {
+        let db = setup().await;
+        let repo = GuildConfigRepository::new(db.clone());
+        let now = Utc::now();
+        let until = now + chrono::Duration::days(7);
+        let active = repo.get(GUILD).await.unwrap();
+
+        let paused = repo.pause(GUILD, until).await.unwrap();
+
+        assert!(!is_paused(&active, now));
+        assert!(is_paused(&paused, now));
+        assert!(is_paused(&paused, until - chrono::Duration::seconds(1)));
+        // Resumes on its own once `paused_until` is reached
+        assert!(!is_paused(&paused, until));
+        assert!(!is_paused(&paused, until + chrono::Duration::days(1)));
+        assert_eq!(repo.get(GUILD).await.unwrap().paused_until, Some(until));
+    }
+}
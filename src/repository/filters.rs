@@ -0,0 +1,162 @@
+//! Repository for per-guild and per-user alert category filters
+
+use chrono::Utc;
+use sea_orm::sea_query::{OnConflict, Query, SelectStatement};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serenity::all::{GuildId, UserId};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::{guild_alert_filters, user_notification_filters};
+
+/// Repository for alert filter operations
+pub struct AlertFilterRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> AlertFilterRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Turn an alert type on or off for a guild
+    pub async fn set_enabled(
+        &self,
+        guild_id: GuildId,
+        alert_type: &str,
+        enabled: bool,
+    ) -> Result<(), sea_orm::DbErr> {
+        let model = guild_alert_filters::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            alert_type: Set(alert_type.to_string()),
+            enabled: Set(enabled),
+            updated_at: Set(Utc::now()),
+        };
+        guild_alert_filters::Entity::insert(model)
+            .on_conflict(
+                OnConflict::columns([
+                    guild_alert_filters::Column::GuildId,
+                    guild_alert_filters::Column::AlertType,
+                ])
+                .update_columns([
+                    guild_alert_filters::Column::Enabled,
+                    guild_alert_filters::Column::UpdatedAt,
+                ])
+                .to_owned(),
+            )
+            .exec(&*self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Alert types a guild has turned off, alphabetically
+    pub async fn disabled_types(&self, guild_id: GuildId) -> Result<Vec<String>, sea_orm::DbErr> {
+        let rows = guild_alert_filters::Entity::find()
+            .filter(guild_alert_filters::Column::GuildId.eq(guild_id.to_string()))
+            .filter(guild_alert_filters::Column::Enabled.eq(false))
+            .order_by_asc(guild_alert_filters::Column::AlertType)
+            .all(&*self.db)
+            .await?;
+        Ok(rows.into_iter().map(|row| row.alert_type).collect())
+    }
+
+    /// Every filter row of a guild, alphabetically by alert type
+    pub async fn list(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Vec<guild_alert_filters::Model>, sea_orm::DbErr> {
+        guild_alert_filters::Entity::find()
+            .filter(guild_alert_filters::Column::GuildId.eq(guild_id.to_string()))
+            .order_by_asc(guild_alert_filters::Column::AlertType)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Turn an alert type on or off for a user's DMs
+    pub async fn set_user_enabled(
+        &self,
+        user_id: UserId,
+        alert_type: &str,
+        enabled: bool,
+    ) -> Result<(), sea_orm::DbErr> {
+        let model = user_notification_filters::ActiveModel {
+            user_id: Set(user_id.to_string()),
+            alert_type: Set(alert_type.to_string()),
+            enabled: Set(enabled),
+            updated_at: Set(Utc::now()),
+        };
+        user_notification_filters::Entity::insert(model)
+            .on_conflict(
+                OnConflict::columns([
+                    user_notification_filters::Column::UserId,
+                    user_notification_filters::Column::AlertType,
+                ])
+                .update_columns([
+                    user_notification_filters::Column::Enabled,
+                    user_notification_filters::Column::UpdatedAt,
+                ])
+                .to_owned(),
+            )
+            .exec(&*self.db)
+            .await?;
+        Ok(())
+    }
+
+    /// Alert types a user has turned off, alphabetically
+    pub async fn user_disabled_types(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<String>, sea_orm::DbErr> {
+        let rows = user_notification_filters::Entity::find()
+            .filter(user_notification_filters::Column::UserId.eq(user_id.to_string()))
+            .filter(user_notification_filters::Column::Enabled.eq(false))
+            .order_by_asc(user_notification_filters::Column::AlertType)
+            .all(&*self.db)
+            .await?;
+        Ok(rows.into_iter().map(|row| row.alert_type).collect())
+    }
+
+    /// Every filter row of a user, alphabetically by alert type
+    pub async fn list_user(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<user_notification_filters::Model>, sea_orm::DbErr> {
+        user_notification_filters::Entity::find()
+            .filter(user_notification_filters::Column::UserId.eq(user_id.to_string()))
+            .order_by_asc(user_notification_filters::Column::AlertType)
+            .all(&*self.db)
+            .await
+    }
+}
+
+/// Subquery selecting the guild IDs that turned `alert_type` off
+///
+/// Used as `guild_configs.guild_id NOT IN (...)`, so guilds without a row
+/// keep receiving the alert type.
+pub fn disabled_guilds(alert_type: &str) -> SelectStatement {
+    Query::select()
+        .column(guild_alert_filters::Column::GuildId)
+        .from(guild_alert_filters::Entity)
+        .and_where(guild_alert_filters::Column::AlertType.eq(alert_type))
+        .and_where(guild_alert_filters::Column::Enabled.eq(false))
+        .to_owned()
+}
+
+/// Subquery selecting the user IDs that turned `alert_type` off
+///
+/// Used as `user_configs.user_id NOT IN (...)`, like [`disabled_guilds`].
+pub fn disabled_users(alert_type: &str) -> SelectStatement {
+    Query::select()
+        .column(user_notification_filters::Column::UserId)
+        .from(user_notification_filters::Entity)
+        .and_where(user_notification_filters::Column::AlertType.eq(alert_type))
+        .and_where(user_notification_filters::Column::Enabled.eq(false))
+        .to_owned()
+}
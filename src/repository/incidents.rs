@@ -0,0 +1,64 @@
+//! Repository for status page incidents
+
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::{incident_updates, incidents};
+
+/// Repository for incident lookups
+pub struct IncidentRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> IncidentRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Incidents whose title contains `query`, newest first
+    ///
+    /// Matching is case-insensitive (SQLite `LIKE`); an empty query returns
+    /// the latest incidents.
+    pub async fn search(
+        &self,
+        query: &str,
+        limit: u64,
+    ) -> Result<Vec<incidents::Model>, sea_orm::DbErr> {
+        let mut select = incidents::Entity::find();
+        let query = query.trim();
+        if !query.is_empty() {
+            select = select.filter(incidents::Column::Title.contains(query));
+        }
+        select
+            .order_by_desc(incidents::Column::StartedAt)
+            .limit(limit)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Incident by status page ID
+    pub async fn get(&self, id: &str) -> Result<Option<incidents::Model>, sea_orm::DbErr> {
+        incidents::Entity::find_by_id(id).one(&*self.db).await
+    }
+
+    /// Every update of an incident, oldest first
+    pub async fn updates(
+        &self,
+        incident_id: &str,
+    ) -> Result<Vec<incident_updates::Model>, sea_orm::DbErr> {
+        incident_updates::Entity::find()
+            .filter(incident_updates::Column::IncidentId.eq(incident_id))
+            .order_by_asc(incident_updates::Column::PublishedAt)
+            .all(&*self.db)
+            .await
+    }
+}
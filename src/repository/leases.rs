@@ -0,0 +1,171 @@
+//! Repository for leader election leases
+//!
+//! Every write is a single conditional statement, so two instances racing
+//! for the same lease cannot both succeed.
+
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
+};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::leases;
+
+/// Repository for lease operations
+pub struct LeaseRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> LeaseRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Acquire or renew a lease until `expires_at`
+    ///
+    /// Succeeds if `holder` already holds the lease, the lease expired before
+    /// `stale_before`, or the lease does not exist yet. Returns whether
+    /// `holder` holds the lease afterwards.
+    pub async fn try_acquire(
+        &self,
+        name: &str,
+        holder: &str,
+        expires_at: DateTime<Utc>,
+        stale_before: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let updated = leases::Entity::update_many()
+            .col_expr(leases::Column::Holder, Expr::value(holder))
+            .col_expr(leases::Column::ExpiresAt, Expr::value(expires_at))
+            .col_expr(leases::Column::UpdatedAt, Expr::value(now))
+            .filter(leases::Column::Name.eq(name))
+            .filter(
+                Condition::any()
+                    .add(leases::Column::Holder.eq(holder))
+                    .add(leases::Column::ExpiresAt.lt(stale_before)),
+            )
+            .exec(&*self.db)
+            .await?;
+        if updated.rows_affected > 0 {
+            return Ok(true);
+        }
+
+        // No row matched: either another instance holds a live lease, or the
+        // lease was never created (or was released)
+        let model = leases::ActiveModel {
+            name: Set(name.to_string()),
+            holder: Set(holder.to_string()),
+            expires_at: Set(expires_at),
+            updated_at: Set(now),
+        };
+        let inserted = leases::Entity::insert(model)
+            .on_conflict(
+                OnConflict::column(leases::Column::Name)
+                    .do_nothing()
+                    .to_owned(),
+            )
+            .exec_without_returning(&*self.db)
+            .await?;
+        Ok(inserted > 0)
+    }
+
+    /// Give up a lease held by `holder`, so another instance can take over immediately
+    pub async fn release(&self, name: &str, holder: &str) -> Result<(), sea_orm::DbErr> {
+        leases::Entity::delete_many()
+            .filter(leases::Column::Name.eq(name))
+            .filter(leases::Column::Holder.eq(holder))
+            .exec(&*self.db)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::{Duration, TimeZone};
+
+    const LEASE: &str = "collector";
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap() + Duration::seconds(seconds)
+    }
+
+    /// Try to hold the lease for 90s at `now`, taking over leases that expired
+    /// more than 10s ago
+    async fn acquire(repo: &LeaseRepository, holder: &str, now: i64) -> bool {
+        repo.try_acquire(LEASE, holder, at(now + 90), at(now - 10), at(now))
+            .await
+            .unwrap()
+    }
+
+    async fn holder(repo: &LeaseRepository) -> Option<(String, DateTime<Utc>)> {
+        leases::Entity::find_by_id(LEASE)
+            .one(&*repo.db)
+            .await
+            .unwrap()
+            .map(|lease| (lease.holder, lease.expires_at))
+    }
+
+    #[tokio::test]
+    async fn test_first_holder_acquires() {
+        let repo = LeaseRepository::new(Arc::new(connect_in_memory().await));
+
+        assert!(acquire(&repo, "a", 0).await);
+        assert_eq!(holder(&repo).await, Some(("a".to_string(), at(90))));
+    }
+
+    #[tokio::test]
+    async fn test_second_holder_rejected_while_valid() {
+        let repo = LeaseRepository::new(Arc::new(connect_in_memory().await));
+        assert!(acquire(&repo, "a", 0).await);
+
+        assert!(!acquire(&repo, "b", 30).await);
+        // Expired, but not by more than the skew margin
+        assert!(!acquire(&repo, "b", 95).await);
+        assert_eq!(holder(&repo).await, Some(("a".to_string(), at(90))));
+    }
+
+    #[tokio::test]
+    async fn test_takeover_after_expiry() {
+        let repo = LeaseRepository::new(Arc::new(connect_in_memory().await));
+        assert!(acquire(&repo, "a", 0).await);
+
+        assert!(acquire(&repo, "b", 101).await);
+        assert_eq!(holder(&repo).await, Some(("b".to_string(), at(191))));
+        assert!(!acquire(&repo, "a", 102).await);
+    }
+
+    #[tokio::test]
+    async fn test_renew_by_holder() {
+        let repo = LeaseRepository::new(Arc::new(connect_in_memory().await));
+        assert!(acquire(&repo, "a", 0).await);
+
+        assert!(acquire(&repo, "a", 30).await);
+        assert_eq!(holder(&repo).await, Some(("a".to_string(), at(120))));
+        // The holder may renew even after its lease went stale
+        assert!(acquire(&repo, "a", 300).await);
+        assert_eq!(holder(&repo).await, Some(("a".to_string(), at(390))));
+    }
+
+    #[tokio::test]
+    async fn test_release_hands_over_immediately() {
+        let repo = LeaseRepository::new(Arc::new(connect_in_memory().await));
+        assert!(acquire(&repo, "a", 0).await);
+
+        repo.release(LEASE, "b").await.unwrap();
+        assert!(!acquire(&repo, "b", 10).await);
+
+        repo.release(LEASE, "a").await.unwrap();
+        assert_eq!(holder(&repo).await, None);
+        assert!(acquire(&repo, "b", 10).await);
+    }
+}
@@ -0,0 +1,300 @@
+//! Repository for collected status page time series
+
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, Func, Query, SelectStatement};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::{component_logs, metric_logs, status_logs};
+
+/// IDs of the latest row of each component
+fn latest_component_ids() -> SelectStatement {
+    Query::select()
+        .expr(Func::max(Expr::col(component_logs::Column::Id)))
+        .from(component_logs::Entity)
+        .group_by_col(component_logs::Column::ComponentId)
+        .to_owned()
+}
+
+/// Repository for metric, status, and component log operations
+pub struct LogRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> LogRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Delete metric data points measured before `cutoff`, returning the count
+    pub async fn prune_metrics_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sea_orm::DbErr> {
+        let result = metric_logs::Entity::delete_many()
+            .filter(metric_logs::Column::Timestamp.lt(cutoff))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Timestamp of the oldest metric row finer than `resolution_sec` measured
+    /// before `cutoff`
+    pub async fn oldest_metric_finer_than(
+        &self,
+        resolution_sec: i64,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, sea_orm::DbErr> {
+        metric_logs::Entity::find()
+            .filter(metric_logs::Column::IntervalSec.lt(resolution_sec))
+            .filter(metric_logs::Column::Timestamp.lt(cutoff))
+            .order_by_asc(metric_logs::Column::Timestamp)
+            .select_only()
+            .column(metric_logs::Column::Timestamp)
+            .into_tuple::<DateTime<Utc>>()
+            .one(&*self.db)
+            .await
+    }
+
+    /// Metric rows finer than `resolution_sec` measured in `[start, end)`
+    pub async fn metrics_finer_than(
+        &self,
+        resolution_sec: i64,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<metric_logs::Model>, sea_orm::DbErr> {
+        metric_logs::Entity::find()
+            .filter(metric_logs::Column::IntervalSec.lt(resolution_sec))
+            .filter(metric_logs::Column::Timestamp.gte(start))
+            .filter(metric_logs::Column::Timestamp.lt(end))
+            .order_by_asc(metric_logs::Column::Timestamp)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Replace the metric rows finer than `resolution_sec` in `[start, end)`
+    /// with `rows`, returning how many rows were deleted
+    ///
+    /// Run it in a transaction so a failed insert keeps the original rows.
+    pub async fn replace_metrics_finer_than(
+        &self,
+        resolution_sec: i64,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        rows: Vec<metric_logs::ActiveModel>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        let deleted = metric_logs::Entity::delete_many()
+            .filter(metric_logs::Column::IntervalSec.lt(resolution_sec))
+            .filter(metric_logs::Column::Timestamp.gte(start))
+            .filter(metric_logs::Column::Timestamp.lt(end))
+            .exec(&*self.db)
+            .await?;
+        if !rows.is_empty() {
+            metric_logs::Entity::insert_many(rows)
+                .exec(&*self.db)
+                .await?;
+        }
+        Ok(deleted.rows_affected)
+    }
+
+    /// Delete system status rows reported before `cutoff`, returning the count
+    ///
+    /// The latest row is kept however old it is, so the current status
+    /// survives a quiet status page.
+    pub async fn prune_statuses_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        let latest_id = Query::select()
+            .expr(Func::max(Expr::col(status_logs::Column::Id)))
+            .from(status_logs::Entity)
+            .to_owned();
+
+        let result = status_logs::Entity::delete_many()
+            .filter(status_logs::Column::SourceTimestamp.lt(cutoff))
+            .filter(status_logs::Column::Id.not_in_subquery(latest_id))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Delete component status rows reported before `cutoff`, returning the count
+    ///
+    /// The latest row of each component is kept however old it is, so
+    /// `latest_components` still knows every component's current status.
+    pub async fn prune_components_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        let result = component_logs::Entity::delete_many()
+            .filter(component_logs::Column::SourceTimestamp.lt(cutoff))
+            .filter(component_logs::Column::Id.not_in_subquery(latest_component_ids()))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Latest row of every component ever collected
+    pub async fn latest_components(&self) -> Result<Vec<component_logs::Model>, sea_orm::DbErr> {
+        component_logs::Entity::find()
+            .filter(component_logs::Column::Id.in_subquery(latest_component_ids()))
+            .all(&*self.db)
+            .await
+    }
+
+    /// When a component entered its current `status`
+    ///
+    /// Walks back to the last row with a different status and returns the
+    /// first row after it. If every retained row has `status`, returns the
+    /// oldest one, so the result is a lower bound after log pruning.
+    pub async fn status_since(
+        &self,
+        component_id: &str,
+        status: &str,
+    ) -> Result<Option<DateTime<Utc>>, sea_orm::DbErr> {
+        let last_other = component_logs::Entity::find()
+            .filter(component_logs::Column::ComponentId.eq(component_id))
+            .filter(component_logs::Column::Status.ne(status))
+            .order_by_desc(component_logs::Column::SourceTimestamp)
+            .one(&*self.db)
+            .await?;
+
+        let mut query = component_logs::Entity::find()
+            .filter(component_logs::Column::ComponentId.eq(component_id))
+            .filter(component_logs::Column::Status.eq(status));
+        if let Some(row) = last_other {
+            query = query.filter(component_logs::Column::SourceTimestamp.gt(row.source_timestamp));
+        }
+        Ok(query
+            .order_by_asc(component_logs::Column::SourceTimestamp)
+            .one(&*self.db)
+            .await?
+            .map(|row| row.source_timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::{Duration, TimeZone};
+    use sea_orm::{ActiveModelTrait, Set};
+
+    fn day(n: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap() + Duration::days(n)
+    }
+
+    async fn insert_status(db: &DatabaseConnection, indicator: &str, at: DateTime<Utc>) {
+        status_logs::ActiveModel {
+            indicator: Set(indicator.to_string()),
+            description: Set(String::new()),
+            source_timestamp: Set(at),
+            created_at: Set(at),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    async fn insert_component(db: &DatabaseConnection, id: &str, status: &str, at: DateTime<Utc>) {
+        component_logs::ActiveModel {
+            component_id: Set(id.to_string()),
+            name: Set(id.to_string()),
+            status: Set(status.to_string()),
+            source_timestamp: Set(at),
+            created_at: Set(at),
+            group_id: Set(None),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_prune_statuses_keeps_latest() {
+        let db = Arc::new(connect_in_memory().await);
+        insert_status(&db, "major", day(0)).await;
+        insert_status(&db, "none", day(1)).await;
+        let logs = LogRepository::new(db.clone());
+
+        let pruned = logs.prune_statuses_before(day(10)).await.unwrap();
+        let left = status_logs::Entity::find().all(&*db).await.unwrap();
+
+        assert_eq!(pruned, 1);
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].indicator, "none");
+    }
+
+    #[tokio::test]
+    async fn test_prune_statuses_before_cutoff_only() {
+        let db = Arc::new(connect_in_memory().await);
+        insert_status(&db, "major", day(0)).await;
+        insert_status(&db, "minor", day(5)).await;
+        insert_status(&db, "none", day(6)).await;
+        let logs = LogRepository::new(db.clone());
+
+        let pruned = logs.prune_statuses_before(day(3)).await.unwrap();
+
+        assert_eq!(pruned, 1);
+        assert_eq!(
+            status_logs::Entity::find().all(&*db).await.unwrap().len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_components_keeps_latest_per_component() {
+        let db = Arc::new(connect_in_memory().await);
+        insert_component(&db, "api", "major_outage", day(0)).await;
+        insert_component(&db, "api", "operational", day(1)).await;
+        insert_component(&db, "cdn", "operational", day(0)).await;
+        insert_component(&db, "web", "degraded_performance", day(2)).await;
+        insert_component(&db, "web", "operational", day(20)).await;
+        let logs = LogRepository::new(db.clone());
+
+        let pruned = logs.prune_components_before(day(10)).await.unwrap();
+        let mut latest: Vec<_> = logs
+            .latest_components()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| (row.component_id, row.status))
+            .collect();
+        latest.sort();
+
+        assert_eq!(pruned, 2);
+        assert_eq!(
+            latest,
+            vec![
+                ("api".to_string(), "operational".to_string()),
+                ("cdn".to_string(), "operational".to_string()),
+                ("web".to_string(), "operational".to_string()),
+            ]
+        );
+        assert_eq!(
+            component_logs::Entity::find()
+                .all(&*db)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prune_empty_tables() {
+        let db = Arc::new(connect_in_memory().await);
+        let logs = LogRepository::new(db);
+
+        assert_eq!(logs.prune_statuses_before(day(10)).await.unwrap(), 0);
+        assert_eq!(logs.prune_components_before(day(10)).await.unwrap(), 0);
+    }
+}
@@ -1,5 +1,224 @@
 //! Repository layer for database operations
+//!
+//! Repositories run on the shared connection pool by default. Flows with
+//! several dependent writes use [`Repositories::transaction`], which hands out
+//! repositories bound to one transaction so a failure rolls back every write.
 
+pub mod aggregates;
+pub mod builds;
+pub mod channels;
 pub mod config;
+pub mod filters;
+pub mod incidents;
+pub mod leases;
+pub mod logs;
+pub mod payloads;
+pub mod records;
+pub mod registration;
+pub mod reports;
+pub mod silences;
+pub mod webhooks;
 
-pub use config::{GuildConfigRepository, UserConfigRepository};
+pub use aggregates::ReportAggregateRepository;
+pub use builds::BuildRepository;
+pub use channels::AlertChannelRepository;
+pub use config::{GuildConfigRepository, LanguageError, UserConfigRepository};
+pub use filters::AlertFilterRepository;
+pub use incidents::IncidentRepository;
+pub use leases::LeaseRepository;
+pub use logs::LogRepository;
+pub use payloads::{AlertPayload, PayloadRepository};
+pub use records::{DailyCount, RecordOwner, RecordRepository, RecordStats};
+pub use registration::RegistrationStatus;
+pub use reports::ReportRepository;
+pub use silences::SilenceRepository;
+pub use webhooks::WebhookRepository;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use sea_orm::{DatabaseConnection, DatabaseTransaction, DbErr, TransactionTrait};
+use tracing::error;
+
+/// Future returned by a [`Repositories::transaction`] body
+pub type TransactionFuture<'t, T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send + 't>>;
+
+/// Entry point for multi-repository units of work
+pub struct Repositories {
+    db: Arc<DatabaseConnection>,
+}
+
+impl Repositories {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    /// Run `body` in a transaction
+    ///
+    /// Commits when `body` returns `Ok` and rolls back when it returns `Err`.
+    /// The body must own what it captures (clone IDs and strings into it).
+    pub async fn transaction<T, E, F>(&self, body: F) -> Result<T, E>
+    where
+        F: for<'t> FnOnce(Transaction<'t>) -> TransactionFuture<'t, T, E>,
+        E: From<DbErr>,
+    {
+        let txn = self.db.begin().await?;
+        let result = body(Transaction { txn: &txn }).await;
+
+        match result {
+            Ok(value) => {
+                txn.commit().await?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Err(rollback) = txn.rollback().await {
+                    error!(error = %rollback, "Failed to roll back transaction");
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Repositories bound to one open transaction
+#[derive(Clone, Copy)]
+pub struct Transaction<'t> {
+    txn: &'t DatabaseTransaction,
+}
+
+impl<'t> Transaction<'t> {
+    pub fn guild_configs(&self) -> GuildConfigRepository<&'t DatabaseTransaction> {
+        GuildConfigRepository::new(self.txn)
+    }
+
+    pub fn logs(&self) -> LogRepository<&'t DatabaseTransaction> {
+        LogRepository::new(self.txn)
+    }
+
+    pub fn reports(&self) -> ReportRepository<&'t DatabaseTransaction> {
+        ReportRepository::new(self.txn)
+    }
+
+    pub fn silences(&self) -> SilenceRepository<&'t DatabaseTransaction> {
+        SilenceRepository::new(self.txn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::{Duration, Utc};
+    use serenity::all::{ChannelId, GuildId, UserId};
+
+    const GUILD: GuildId = GuildId::new(1);
+    const USER: UserId = UserId::new(2);
+
+    /// Registered guild with one active silence
+    async fn setup() -> Arc<DatabaseConnection> {
+        let db = Arc::new(connect_in_memory().await);
+        GuildConfigRepository::new(db.clone())
+            .create(GUILD, ChannelId::new(3))
+            .await
+            .unwrap();
+        SilenceRepository::new(db.clone())
+            .create(GUILD, None, Utc::now() + Duration::hours(1), USER)
+            .await
+            .unwrap();
+        db
+    }
+
+    async fn silence_count(db: &Arc<DatabaseConnection>) -> usize {
+        SilenceRepository::new(db.clone())
+            .active(GUILD, Utc::now())
+            .await
+            .unwrap()
+            .len()
+    }
+
+    async fn report_count(db: &Arc<DatabaseConnection>) -> u64 {
+        ReportRepository::new(db.clone())
+            .count_since(Utc::now() - Duration::days(1))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_commit_keeps_every_write() {
+        let db = setup().await;
+
+        Repositories::new(db.clone())
+            .transaction(|tx| {
+                Box::pin(async move {
+                    tx.guild_configs().disable(GUILD).await?;
+                    tx.silences().clear(GUILD).await?;
+                    Ok::<_, DbErr>(())
+                })
+            })
+            .await
+            .unwrap();
+
+        let config = GuildConfigRepository::new(db.clone()).get(GUILD).await;
+        assert!(!config.unwrap().enabled);
+        assert_eq!(silence_count(&db).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_body_error_rolls_back_every_write() {
+        let db = setup().await;
+
+        let result = Repositories::new(db.clone())
+            .transaction(|tx| {
+                Box::pin(async move {
+                    tx.guild_configs().disable(GUILD).await?;
+                    tx.silences().clear(GUILD).await?;
+                    Err::<(), _>(DbErr::Custom("forced failure".to_string()))
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+        let config = GuildConfigRepository::new(db.clone()).get(GUILD).await;
+        assert!(config.unwrap().enabled);
+        assert_eq!(silence_count(&db).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_statement_rolls_back_earlier_writes() {
+        let db = setup().await;
+
+        let result = Repositories::new(db.clone())
+            .transaction(|tx| {
+                Box::pin(async move {
+                    tx.reports()
+                        .create(Some(GUILD), USER, "login", None)
+                        .await?;
+                    tx.silences().clear(GUILD).await?;
+                    // No config for this guild, so the update fails
+                    tx.guild_configs().disable(GuildId::new(99)).await?;
+                    Ok::<_, DbErr>(())
+                })
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(report_count(&db).await, 0);
+        assert_eq!(silence_count(&db).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_returns_body_value() {
+        let db = setup().await;
+
+        let report = Repositories::new(db.clone())
+            .transaction(|tx| {
+                Box::pin(async move { tx.reports().create(Some(GUILD), USER, "login", None).await })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(report.incident_type, "login");
+        assert_eq!(report_count(&db).await, 1);
+    }
+}
@@ -0,0 +1,237 @@
+//! Repository for stored threshold alert content
+
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::entity::alert_payloads;
+
+/// Content of one threshold alert fan-out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertPayload {
+    pub reference_id: String,
+    pub incident_type: String,
+    pub report_count: i64,
+    pub interval_minutes: i64,
+    /// Listed report timestamps, oldest first
+    pub recent_reports: Vec<DateTime<Utc>>,
+    pub recent_total: u64,
+    /// Communities among the reports, if known
+    pub communities: Option<u64>,
+    /// Time of the latest fan-out for this reference ID
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<alert_payloads::Model> for AlertPayload {
+    fn from(model: alert_payloads::Model) -> Self {
+        let recent_reports = serde_json::from_str(&model.recent_reports).unwrap_or_else(|e| {
+            warn!(
+                reference_id = %model.reference_id,
+                error = %e,
+                "Invalid stored alert report timestamps, listing none"
+            );
+            Vec::new()
+        });
+        Self {
+            reference_id: model.reference_id,
+            incident_type: model.incident_type,
+            report_count: model.report_count,
+            interval_minutes: model.interval_minutes,
+            recent_reports,
+            recent_total: model.recent_total.max(0) as u64,
+            communities: model.communities.map(|n| n.max(0) as u64),
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+/// Repository for alert payload operations
+pub struct PayloadRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> PayloadRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Store the content of a fan-out, replacing an earlier one for the same reference ID
+    pub async fn save(&self, payload: &AlertPayload) -> Result<(), sea_orm::DbErr> {
+        let recent_reports = serde_json::to_string(&payload.recent_reports)
+            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()))?;
+        let model = alert_payloads::ActiveModel {
+            reference_id: Set(payload.reference_id.clone()),
+            incident_type: Set(payload.incident_type.clone()),
+            report_count: Set(payload.report_count),
+            interval_minutes: Set(payload.interval_minutes),
+            recent_reports: Set(recent_reports),
+            recent_total: Set(payload.recent_total as i64),
+            communities: Set(payload.communities.map(|n| n as i64)),
+            created_at: Set(payload.updated_at),
+            updated_at: Set(payload.updated_at),
+        };
+        alert_payloads::Entity::insert(model)
+            .on_conflict(
+                OnConflict::column(alert_payloads::Column::ReferenceId)
+                    .update_columns([
+                        alert_payloads::Column::ReportCount,
+                        alert_payloads::Column::IntervalMinutes,
+                        alert_payloads::Column::RecentReports,
+                        alert_payloads::Column::RecentTotal,
+                        alert_payloads::Column::Communities,
+                        alert_payloads::Column::UpdatedAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(&*self.db)
+            .await
+            .map(|_| ())
+    }
+
+    /// Most recently sent payload with a fan-out at or after `since`
+    pub async fn latest_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Option<AlertPayload>, sea_orm::DbErr> {
+        let model = alert_payloads::Entity::find()
+            .filter(alert_payloads::Column::UpdatedAt.gte(since))
+            .order_by_desc(alert_payloads::Column::UpdatedAt)
+            .one(&*self.db)
+            .await?;
+        Ok(model.map(AlertPayload::from))
+    }
+
+    /// Delete payloads last sent before `cutoff`, returning the number removed
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sea_orm::DbErr> {
+        let result = alert_payloads::Entity::delete_many()
+            .filter(alert_payloads::Column::UpdatedAt.lt(cutoff))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::TimeZone;
+    use sea_orm::ActiveModelTrait;
+
+    fn at(h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2028, 3, 5, h, min, 0).unwrap()
+    }
+
+    fn payload(reference_id: &str, updated_at: DateTime<Utc>) -> AlertPayload {
+        AlertPayload {
+            reference_id: reference_id.to_string(),
+            incident_type: "login".to_string(),
+            report_count: 6,
+            interval_minutes: 60,
+            recent_reports: vec![at(9, 50), at(9, 55), at(10, 0)],
+            recent_total: 8,
+            communities: Some(3),
+            updated_at,
+        }
+    }
+
+    async fn repo() -> PayloadRepository {
+        PayloadRepository::new(Arc::new(connect_in_memory().await))
+    }
+
+    #[tokio::test]
+    async fn test_save_load_round_trip() {
+        let repo = repo().await;
+        let saved = payload("threshold_login_1", at(10, 0));
+        repo.save(&saved).await.unwrap();
+
+        assert_eq!(repo.latest_since(at(9, 0)).await.unwrap(), Some(saved));
+    }
+
+    #[tokio::test]
+    async fn test_save_replaces_same_reference_id() {
+        let repo = repo().await;
+        repo.save(&payload("threshold_login_1", at(10, 0)))
+            .await
+            .unwrap();
+        let mut updated = payload("threshold_login_1", at(10, 20));
+        updated.report_count = 9;
+        updated.communities = None;
+        repo.save(&updated).await.unwrap();
+
+        assert_eq!(repo.latest_since(at(9, 0)).await.unwrap(), Some(updated));
+        assert_eq!(
+            alert_payloads::Entity::find()
+                .all(&*repo.db)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_latest_since_picks_newest_fan_out() {
+        let repo = repo().await;
+        repo.save(&payload("threshold_login_1", at(10, 0)))
+            .await
+            .unwrap();
+        repo.save(&payload("threshold_api_1", at(10, 30)))
+            .await
+            .unwrap();
+
+        let latest = repo.latest_since(at(9, 0)).await.unwrap().unwrap();
+        assert_eq!(latest.reference_id, "threshold_api_1");
+        assert_eq!(repo.latest_since(at(10, 31)).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_prune_before() {
+        let repo = repo().await;
+        repo.save(&payload("threshold_login_1", at(10, 0)))
+            .await
+            .unwrap();
+        repo.save(&payload("threshold_api_1", at(12, 0)))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.prune_before(at(11, 0)).await.unwrap(), 1);
+        assert_eq!(
+            repo.latest_since(at(0, 0))
+                .await
+                .unwrap()
+                .map(|p| p.reference_id),
+            Some("threshold_api_1".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_stored_timestamps_list_none() {
+        let repo = repo().await;
+        repo.save(&payload("threshold_login_1", at(10, 0)))
+            .await
+            .unwrap();
+        let mut model: alert_payloads::ActiveModel = alert_payloads::Entity::find()
+            .one(&*repo.db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        model.recent_reports = Set("not json".to_string());
+        model.update(&*repo.db).await.unwrap();
+
+        let loaded = repo.latest_since(at(9, 0)).await.unwrap().unwrap();
+        assert!(loaded.recent_reports.is_empty());
+        assert_eq!(loaded.recent_total, 8);
+    }
+}
@@ -0,0 +1,591 @@
+//! Repository for record statistics, per guild, per user, or global
+//!
+//! Only aggregates are exposed here; record contents are never returned.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::sea_query::{Expr, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Select,
+};
+use serenity::all::{GuildId, UserId};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::{command_logs, sent_alerts, user_reports};
+
+use super::reports::UNCOUNTED_STATUSES;
+
+/// Owner of the records being summarized
+#[derive(Debug, Clone, Copy)]
+pub enum RecordOwner {
+    Guild(GuildId),
+    User(UserId),
+}
+
+/// Aggregate statistics for one table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecordStats {
+    pub count: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+}
+
+/// Row count for one UTC day and key (e.g. alert type)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub key: String,
+    pub count: u64,
+}
+
+/// Repository for record statistics
+pub struct RecordRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> RecordRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Statistics for user reports attributed to the owner
+    ///
+    /// Includes `duplicate` and `withdrawn` reports: this describes the records held, not the
+    /// reports that count toward alerts.
+    pub async fn report_stats(&self, owner: RecordOwner) -> Result<RecordStats, sea_orm::DbErr> {
+        let query = match owner {
+            RecordOwner::Guild(id) => user_reports::Entity::find()
+                .filter(user_reports::Column::GuildId.eq(id.to_string())),
+            RecordOwner::User(id) => {
+                user_reports::Entity::find().filter(user_reports::Column::UserId.eq(id.to_string()))
+            }
+        };
+        self.stats(query, user_reports::Column::CreatedAt).await
+    }
+
+    /// Statistics for alerts sent to the owner
+    pub async fn sent_alert_stats(
+        &self,
+        owner: RecordOwner,
+    ) -> Result<RecordStats, sea_orm::DbErr> {
+        let query =
+            match owner {
+                RecordOwner::Guild(id) => sent_alerts::Entity::find()
+                    .filter(sent_alerts::Column::GuildId.eq(id.to_string())),
+                RecordOwner::User(id) => sent_alerts::Entity::find()
+                    .filter(sent_alerts::Column::UserId.eq(id.to_string())),
+            };
+        self.stats(query, sent_alerts::Column::NotifiedAt).await
+    }
+
+    /// Statistics for commands logged for the owner
+    pub async fn command_log_stats(
+        &self,
+        owner: RecordOwner,
+    ) -> Result<RecordStats, sea_orm::DbErr> {
+        let query = match owner {
+            RecordOwner::Guild(id) => command_logs::Entity::find()
+                .filter(command_logs::Column::GuildId.eq(id.to_string())),
+            RecordOwner::User(id) => {
+                command_logs::Entity::find().filter(command_logs::Column::UserId.eq(id.to_string()))
+            }
+        };
+        self.stats(query, command_logs::Column::ExecutedAt).await
+    }
+
+    /// Count user reports from a guild since `since`
+    pub async fn count_guild_reports_since(
+        &self,
+        guild_id: GuildId,
+        since: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        user_reports::Entity::find()
+            .filter(user_reports::Column::GuildId.eq(guild_id.to_string()))
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since))
+            .count(&*self.db)
+            .await
+    }
+
+    /// Count alerts delivered to a guild since `since`
+    pub async fn count_guild_alerts_since(
+        &self,
+        guild_id: GuildId,
+        since: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        sent_alerts::Entity::find()
+            .filter(sent_alerts::Column::GuildId.eq(guild_id.to_string()))
+            .filter(sent_alerts::Column::NotifiedAt.gte(since))
+            .count(&*self.db)
+            .await
+    }
+
+    /// Count deliveries of the given alert types since `since`, across all owners
+    pub async fn count_sent_alerts_since(
+        &self,
+        alert_types: &[&str],
+        since: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        sent_alerts::Entity::find()
+            .filter(sent_alerts::Column::AlertType.is_in(alert_types.iter().copied()))
+            .filter(sent_alerts::Column::NotifiedAt.gte(since))
+            .count(&*self.db)
+            .await
+    }
+
+    /// Delivery timestamps of alerts sent to a guild since `since`
+    pub async fn guild_alert_times_since(
+        &self,
+        guild_id: GuildId,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, sea_orm::DbErr> {
+        sent_alerts::Entity::find()
+            .select_only()
+            .column(sent_alerts::Column::NotifiedAt)
+            .filter(sent_alerts::Column::GuildId.eq(guild_id.to_string()))
+            .filter(sent_alerts::Column::NotifiedAt.gte(since))
+            .into_tuple::<DateTime<Utc>>()
+            .all(&*self.db)
+            .await
+    }
+
+    /// Alerts sent per UTC day and alert type since `since`
+    ///
+    /// `guild` limits the counts to one guild; `None` counts every delivery.
+    /// Days without alerts are omitted.
+    pub async fn daily_sent_alerts(
+        &self,
+        guild: Option<GuildId>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DailyCount>, sea_orm::DbErr> {
+        let day = day_expr("notified_at");
+        let mut query = sent_alerts::Entity::find()
+            .select_only()
+            .column_as(day.clone(), "day")
+            .column(sent_alerts::Column::AlertType)
+            .column_as(sent_alerts::Column::Id.count(), "count")
+            .filter(sent_alerts::Column::NotifiedAt.gte(since));
+        if let Some(id) = guild {
+            query = query.filter(sent_alerts::Column::GuildId.eq(id.to_string()));
+        }
+
+        let rows = query
+            .group_by(day)
+            .group_by(sent_alerts::Column::AlertType)
+            .into_tuple::<(String, String, i64)>()
+            .all(&*self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(day, key, count)| {
+                Some(DailyCount {
+                    day: parse_day(&day)?,
+                    key,
+                    count: count.max(0) as u64,
+                })
+            })
+            .collect())
+    }
+
+    /// Distinct reporting users per UTC day since `since`
+    ///
+    /// `guild` limits the counts to reports made in one guild. Days without
+    /// reports are omitted.
+    pub async fn daily_distinct_reporters(
+        &self,
+        guild: Option<GuildId>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(NaiveDate, u64)>, sea_orm::DbErr> {
+        let day = day_expr("created_at");
+        let mut query = user_reports::Entity::find()
+            .select_only()
+            .column_as(day.clone(), "day")
+            .column_as(Expr::cust("COUNT(DISTINCT user_id)"), "count")
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since));
+        if let Some(id) = guild {
+            query = query.filter(user_reports::Column::GuildId.eq(id.to_string()));
+        }
+
+        let rows = query
+            .group_by(day)
+            .into_tuple::<(String, i64)>()
+            .all(&*self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(day, count)| Some((parse_day(&day)?, count.max(0) as u64)))
+            .collect())
+    }
+
+    /// User reports per incident type since `since`, most reported first
+    ///
+    /// `guild` limits the counts to reports made in one guild.
+    pub async fn report_counts_by_type(
+        &self,
+        guild: Option<GuildId>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(String, u64)>, sea_orm::DbErr> {
+        let mut query = user_reports::Entity::find()
+            .select_only()
+            .column(user_reports::Column::IncidentType)
+            .column_as(user_reports::Column::Id.count(), "count")
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since));
+        if let Some(id) = guild {
+            query = query.filter(user_reports::Column::GuildId.eq(id.to_string()));
+        }
+
+        let rows = query
+            .group_by(user_reports::Column::IncidentType)
+            .order_by_desc(Expr::cust("count"))
+            .into_tuple::<(String, i64)>()
+            .all(&*self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(key, count)| (key, count.max(0) as u64))
+            .collect())
+    }
+
+    /// Distinct reporting users since `since`
+    ///
+    /// `guild` limits the count to reports made in one guild.
+    pub async fn count_distinct_reporters(
+        &self,
+        guild: Option<GuildId>,
+        since: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        let mut query = user_reports::Entity::find()
+            .select_only()
+            .column_as(Expr::cust("COUNT(DISTINCT user_id)"), "count")
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since));
+        if let Some(id) = guild {
+            query = query.filter(user_reports::Column::GuildId.eq(id.to_string()));
+        }
+
+        let count = query.into_tuple::<i64>().one(&*self.db).await?;
+        Ok(count.unwrap_or(0).max(0) as u64)
+    }
+
+    /// Guilds with the most command invocations since `since`, most active
+    /// first, at most `limit`
+    pub async fn top_command_guilds(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<(String, u64)>, sea_orm::DbErr> {
+        let rows = command_logs::Entity::find()
+            .select_only()
+            .column(command_logs::Column::GuildId)
+            .column_as(command_logs::Column::Id.count(), "count")
+            .filter(command_logs::Column::GuildId.is_not_null())
+            .filter(command_logs::Column::ExecutedAt.gte(since))
+            .group_by(command_logs::Column::GuildId)
+            .order_by_desc(Expr::cust("count"))
+            .limit(limit)
+            .into_tuple::<(String, i64)>()
+            .all(&*self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(guild_id, count)| (guild_id, count.max(0) as u64))
+            .collect())
+    }
+
+    /// Most used commands since `since` as (command, subcommand, count),
+    /// most used first, at most `limit`
+    pub async fn top_commands(
+        &self,
+        guild: Option<GuildId>,
+        since: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<(String, Option<String>, u64)>, sea_orm::DbErr> {
+        let mut query = command_logs::Entity::find()
+            .select_only()
+            .column(command_logs::Column::CommandName)
+            .column(command_logs::Column::Subcommand)
+            .column_as(command_logs::Column::Id.count(), "count")
+            .filter(command_logs::Column::ExecutedAt.gte(since));
+        if let Some(id) = guild {
+            query = query.filter(command_logs::Column::GuildId.eq(id.to_string()));
+        }
+
+        let rows = query
+            .group_by(command_logs::Column::CommandName)
+            .group_by(command_logs::Column::Subcommand)
+            .order_by_desc(Expr::cust("count"))
+            .limit(limit)
+            .into_tuple::<(String, Option<String>, i64)>()
+            .all(&*self.db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(command, subcommand, count)| (command, subcommand, count.max(0) as u64))
+            .collect())
+    }
+
+    /// Command invocations, distinct users, and distinct guilds since `since`
+    pub async fn command_usage_totals(
+        &self,
+        guild: Option<GuildId>,
+        since: DateTime<Utc>,
+    ) -> Result<(u64, u64, u64), sea_orm::DbErr> {
+        let mut query = command_logs::Entity::find()
+            .select_only()
+            .column_as(command_logs::Column::Id.count(), "count")
+            .column_as(Expr::cust("COUNT(DISTINCT user_id)"), "users")
+            .column_as(Expr::cust("COUNT(DISTINCT guild_id)"), "guilds")
+            .filter(command_logs::Column::ExecutedAt.gte(since));
+        if let Some(id) = guild {
+            query = query.filter(command_logs::Column::GuildId.eq(id.to_string()));
+        }
+
+        let (count, users, guilds) = query
+            .into_tuple::<(i64, i64, i64)>()
+            .one(&*self.db)
+            .await?
+            .unwrap_or_default();
+        Ok((
+            count.max(0) as u64,
+            users.max(0) as u64,
+            guilds.max(0) as u64,
+        ))
+    }
+
+    /// Delete command logs executed before `cutoff`, returning the count
+    pub async fn prune_command_logs_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        let result = command_logs::Entity::delete_many()
+            .filter(command_logs::Column::ExecutedAt.lt(cutoff))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Count rows and find the oldest/newest timestamp for a filtered query
+    async fn stats<E>(
+        &self,
+        query: Select<E>,
+        time_column: E::Column,
+    ) -> Result<RecordStats, sea_orm::DbErr>
+    where
+        E: EntityTrait,
+        E::Model: Sync,
+    {
+        let count = query.clone().count(&*self.db).await?;
+        if count == 0 {
+            return Ok(RecordStats::default());
+        }
+
+        let oldest = query
+            .clone()
+            .select_only()
+            .column(time_column)
+            .order_by_asc(time_column)
+            .into_tuple::<DateTime<Utc>>()
+            .one(&*self.db)
+            .await?;
+        let newest = query
+            .select_only()
+            .column(time_column)
+            .order_by_desc(time_column)
+            .into_tuple::<DateTime<Utc>>()
+            .one(&*self.db)
+            .await?;
+
+        Ok(RecordStats {
+            count,
+            oldest,
+            newest,
+        })
+    }
+}
+
+/// UTC calendar day of a timestamp column
+///
+/// Timestamps are stored as UTC RFC 3339 text, so the day is the first ten
+/// characters (`YYYY-MM-DD`).
+fn day_expr(column: &str) -> SimpleExpr {
+    Expr::cust(format!("substr({}, 1, 10)", column))
+}
+
+fn parse_day(day: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use crate::repository::reports::{STATUS_COUNTED, STATUS_DUPLICATE, STATUS_WITHDRAWN};
+    use chrono::TimeZone;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    const GUILD: GuildId = GuildId::new(1);
+    const OTHER_GUILD: GuildId = GuildId::new(2);
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, sec).unwrap()
+    }
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    async fn sent(
+        db: &DatabaseConnection,
+        guild: Option<GuildId>,
+        alert_type: &str,
+        at: DateTime<Utc>,
+    ) {
+        sent_alerts::ActiveModel {
+            guild_id: Set(guild.map(|id| id.to_string())),
+            user_id: Set(guild.is_none().then(|| "9".to_string())),
+            alert_type: Set(alert_type.to_string()),
+            reference_id: Set(format!("{}_{}", alert_type, at.timestamp())),
+            notified_at: Set(at),
+            created_at: Set(at),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    async fn report(
+        db: &DatabaseConnection,
+        guild: GuildId,
+        user: &str,
+        status: &str,
+        at: DateTime<Utc>,
+    ) {
+        user_reports::ActiveModel {
+            guild_id: Set(Some(guild.to_string())),
+            user_id: Set(user.to_string()),
+            incident_type: Set("login".to_string()),
+            content: Set(None),
+            status: Set(status.to_string()),
+            created_at: Set(at),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    fn sorted(mut rows: Vec<DailyCount>) -> Vec<(NaiveDate, String, u64)> {
+        rows.sort_by(|a, b| (a.day, &a.key).cmp(&(b.day, &b.key)));
+        rows.into_iter().map(|r| (r.day, r.key, r.count)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_daily_sent_alerts_buckets_across_month_boundaries() {
+        let db = connect_in_memory().await;
+        sent(&db, Some(GUILD), "threshold", at(2028, 1, 31, 0, 0, 0)).await;
+        sent(&db, Some(GUILD), "threshold", at(2028, 1, 31, 23, 59, 59)).await;
+        sent(&db, Some(GUILD), "threshold", at(2028, 2, 1, 0, 0, 0)).await;
+        sent(
+            &db,
+            Some(GUILD),
+            "incident_opened",
+            at(2028, 2, 1, 12, 0, 0),
+        )
+        .await;
+        sent(&db, Some(GUILD), "threshold", at(2028, 2, 29, 23, 0, 0)).await;
+        sent(
+            &db,
+            Some(OTHER_GUILD),
+            "threshold",
+            at(2028, 3, 1, 0, 30, 0),
+        )
+        .await;
+        sent(&db, None, "threshold", at(2028, 3, 1, 1, 0, 0)).await;
+        // Before the window
+        sent(&db, Some(GUILD), "threshold", at(2028, 1, 30, 23, 59, 59)).await;
+        let repo = RecordRepository::new(Arc::new(db));
+
+        let all = repo
+            .daily_sent_alerts(None, at(2028, 1, 31, 0, 0, 0))
+            .await
+            .unwrap();
+        let guild = repo
+            .daily_sent_alerts(Some(GUILD), at(2028, 1, 31, 0, 0, 0))
+            .await
+            .unwrap();
+
+        let threshold = "threshold".to_string();
+        assert_eq!(
+            sorted(all),
+            vec![
+                (day(2028, 1, 31), threshold.clone(), 2),
+                (day(2028, 2, 1), "incident_opened".to_string(), 1),
+                (day(2028, 2, 1), threshold.clone(), 1),
+                (day(2028, 2, 29), threshold.clone(), 1),
+                (day(2028, 3, 1), threshold.clone(), 2),
+            ]
+        );
+        assert_eq!(
+            sorted(guild),
+            vec![
+                (day(2028, 1, 31), threshold.clone(), 2),
+                (day(2028, 2, 1), "incident_opened".to_string(), 1),
+                (day(2028, 2, 1), threshold.clone(), 1),
+                (day(2028, 2, 29), threshold, 1),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_daily_distinct_reporters() {
+        let db = connect_in_memory().await;
+        report(&db, GUILD, "10", STATUS_COUNTED, at(2026, 12, 31, 8, 0, 0)).await;
+        report(&db, GUILD, "10", STATUS_COUNTED, at(2026, 12, 31, 23, 0, 0)).await;
+        report(
+            &db,
+            GUILD,
+            "11",
+            STATUS_COUNTED,
+            at(2026, 12, 31, 23, 59, 59),
+        )
+        .await;
+        report(&db, GUILD, "10", STATUS_COUNTED, at(2027, 1, 1, 0, 0, 0)).await;
+        report(&db, GUILD, "12", STATUS_DUPLICATE, at(2027, 1, 1, 1, 0, 0)).await;
+        report(&db, GUILD, "13", STATUS_WITHDRAWN, at(2027, 1, 1, 2, 0, 0)).await;
+        report(
+            &db,
+            OTHER_GUILD,
+            "14",
+            STATUS_COUNTED,
+            at(2027, 1, 1, 3, 0, 0),
+        )
+        .await;
+        let repo = RecordRepository::new(Arc::new(db));
+        let since = at(2026, 12, 1, 0, 0, 0);
+
+        let mut all = repo.daily_distinct_reporters(None, since).await.unwrap();
+        let mut guild = repo
+            .daily_distinct_reporters(Some(GUILD), since)
+            .await
+            .unwrap();
+        all.sort();
+        guild.sort();
+
+        assert_eq!(all, vec![(day(2026, 12, 31), 2), (day(2027, 1, 1), 2)]);
+        assert_eq!(guild, vec![(day(2026, 12, 31), 2), (day(2027, 1, 1), 1)]);
+    }
+}
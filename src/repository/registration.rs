@@ -0,0 +1,323 @@
+//! Registration status of guilds and users
+//!
+//! Whether a guild or user is registered, paused, turned off, or needs a new
+//! `/config setup` depends on several `guild_configs` / `user_configs`
+//! columns. [`RegistrationStatus`] reads them in one place, and the alert
+//! recipient queries filter with [`guild_receives_alerts`] and
+//! [`user_receives_alerts`], which match the rows whose status is
+//! [`RegistrationStatus::Active`].
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, Condition, DatabaseConnection};
+use serenity::all::{GuildId, UserId};
+
+use crate::entity::{guild_configs, user_configs};
+
+use super::config::{self, DISABLED_REASON_DM_UNREACHABLE};
+use super::{GuildConfigRepository, UserConfigRepository};
+
+/// Registration of one guild or user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationStatus {
+    /// Registered and receiving alerts
+    Active,
+    /// Registered, but guild alerts are paused until the given time
+    Paused { until: DateTime<Utc> },
+    /// Turned off with `/config unregister` (or removed from the guild)
+    Disabled,
+    /// Turned off because alerts can't be delivered: the guild's alert
+    /// channel is gone or unset, or Discord refused the user's DMs
+    NeedsReconfigure,
+    /// No config row
+    NotRegistered,
+}
+
+impl RegistrationStatus {
+    /// Registered and not turned off, whether or not alerts are paused
+    pub fn is_registered(self) -> bool {
+        matches!(self, Self::Active | Self::Paused { .. })
+    }
+}
+
+/// Status of a guild from its config row at `now`
+pub fn guild(config: Option<&guild_configs::Model>, now: DateTime<Utc>) -> RegistrationStatus {
+    let Some(config) = config else {
+        return RegistrationStatus::NotRegistered;
+    };
+    match (config.enabled, config.paused_until) {
+        (false, _) if config.channel_invalid => RegistrationStatus::NeedsReconfigure,
+        (false, _) => RegistrationStatus::Disabled,
+        _ if config.channel_id.is_none() => RegistrationStatus::NeedsReconfigure,
+        (true, Some(until)) if config::is_paused(config, now) => {
+            RegistrationStatus::Paused { until }
+        }
+        (true, _) => RegistrationStatus::Active,
+    }
+}
+
+/// Status of a user from their config row
+pub fn user(config: Option<&user_configs::Model>) -> RegistrationStatus {
+    let Some(config) = config else {
+        return RegistrationStatus::NotRegistered;
+    };
+    if config.enabled {
+        RegistrationStatus::Active
+    } else if config.disabled_reason.as_deref() == Some(DISABLED_REASON_DM_UNREACHABLE) {
+        RegistrationStatus::NeedsReconfigure
+    } else {
+        RegistrationStatus::Disabled
+    }
+}
+
+/// Current status of a guild
+pub async fn guild_status(db: &Arc<DatabaseConnection>, guild_id: GuildId) -> RegistrationStatus {
+    let config = GuildConfigRepository::new(db.clone()).get(guild_id).await;
+    guild(config.as_ref(), Utc::now())
+}
+
+/// Current status of a user
+pub async fn user_status(db: &Arc<DatabaseConnection>, user_id: UserId) -> RegistrationStatus {
+    let config = UserConfigRepository::new(db.clone()).get(user_id).await;
+    user(config.as_ref())
+}
+
+/// `guild_configs` rows whose status is [`RegistrationStatus::Active`] at `now`
+pub fn guild_receives_alerts(now: DateTime<Utc>) -> Condition {
+    Condition::all()
+        .add(guild_configs::Column::Enabled.eq(true))
+        .add(guild_configs::Column::ChannelId.is_not_null())
+        .add(
+            Condition::any()
+                .add(guild_configs::Column::PausedUntil.is_null())
+                .add(guild_configs::Column::PausedUntil.lte(now)),
+        )
+}
+
+/// `user_configs` rows whose status is [`RegistrationStatus::Active`]
+pub fn user_receives_alerts() -> Condition {
+    Condition::all().add(user_configs::Column::Enabled.eq(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::{Duration, TimeZone};
+    use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, QueryFilter};
+    use std::collections::BTreeSet;
+
+    use RegistrationStatus::{Active, Disabled, NeedsReconfigure};
+
+    #[derive(Debug, Clone, Copy)]
+    enum Pause {
+        None,
+        Past,
+        Future,
+    }
+
+    /// (enabled, channel set, channel_invalid, pause, expected status)
+    ///
+    /// `Paused` is written as `None` here and filled in with the pause end.
+    const GUILD_CASES: [(bool, bool, bool, Pause, Option<RegistrationStatus>); 24] = [
+        (true, true, false, Pause::None, Some(Active)),
+        (true, true, false, Pause::Past, Some(Active)),
+        (true, true, false, Pause::Future, None),
+        (true, true, true, Pause::None, Some(Active)),
+        (true, true, true, Pause::Past, Some(Active)),
+        (true, true, true, Pause::Future, None),
+        (true, false, false, Pause::None, Some(NeedsReconfigure)),
+        (true, false, false, Pause::Past, Some(NeedsReconfigure)),
+        (true, false, false, Pause::Future, Some(NeedsReconfigure)),
+        (true, false, true, Pause::None, Some(NeedsReconfigure)),
+        (true, false, true, Pause::Past, Some(NeedsReconfigure)),
+        (true, false, true, Pause::Future, Some(NeedsReconfigure)),
+        (false, true, false, Pause::None, Some(Disabled)),
+        (false, true, false, Pause::Past, Some(Disabled)),
+        (false, true, false, Pause::Future, Some(Disabled)),
+        (false, true, true, Pause::None, Some(NeedsReconfigure)),
+        (false, true, true, Pause::Past, Some(NeedsReconfigure)),
+        (false, true, true, Pause::Future, Some(NeedsReconfigure)),
+        (false, false, false, Pause::None, Some(Disabled)),
+        (false, false, false, Pause::Past, Some(Disabled)),
+        (false, false, false, Pause::Future, Some(Disabled)),
+        (false, false, true, Pause::None, Some(NeedsReconfigure)),
+        (false, false, true, Pause::Past, Some(NeedsReconfigure)),
+        (false, false, true, Pause::Future, Some(NeedsReconfigure)),
+    ];
+
+    /// (enabled, disabled_reason, expected status)
+    const USER_CASES: [(bool, Option<&str>, RegistrationStatus); 6] = [
+        (true, None, Active),
+        (true, Some(DISABLED_REASON_DM_UNREACHABLE), Active),
+        (true, Some("other"), Active),
+        (false, None, Disabled),
+        (
+            false,
+            Some(DISABLED_REASON_DM_UNREACHABLE),
+            NeedsReconfigure,
+        ),
+        (false, Some("other"), Disabled),
+    ];
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 10, 12, 0, 0).unwrap()
+    }
+
+    fn pause_until(pause: Pause) -> Option<DateTime<Utc>> {
+        match pause {
+            Pause::None => None,
+            Pause::Past => Some(now() - Duration::hours(1)),
+            Pause::Future => Some(now() + Duration::hours(1)),
+        }
+    }
+
+    fn guild_config(
+        guild_id: usize,
+        enabled: bool,
+        channel: bool,
+        channel_invalid: bool,
+        pause: Pause,
+    ) -> guild_configs::Model {
+        guild_configs::Model {
+            guild_id: guild_id.to_string(),
+            channel_id: channel.then(|| "100".to_string()),
+            enabled,
+            language: None,
+            fallback_dm: false,
+            paused_until: pause_until(pause),
+            muted_incident_types: None,
+            alert_threshold: None,
+            report_role_id: None,
+            mention_role_id: None,
+            digest_enabled: false,
+            alert_quiet_start: None,
+            alert_quiet_end: None,
+            channel_invalid,
+            consecutive_send_failures: 0,
+            created_at: now(),
+            updated_at: now(),
+        }
+    }
+
+    fn user_config(user_id: usize, enabled: bool, reason: Option<&str>) -> user_configs::Model {
+        user_configs::Model {
+            user_id: user_id.to_string(),
+            enabled,
+            language: None,
+            disabled_reason: reason.map(str::to_string),
+            created_at: now(),
+            updated_at: now(),
+        }
+    }
+
+    fn expected_guild(expected: Option<RegistrationStatus>, pause: Pause) -> RegistrationStatus {
+        expected.unwrap_or_else(|| RegistrationStatus::Paused {
+            until: pause_until(pause).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_guild_status() {
+        for (i, (enabled, channel, invalid, pause, expected)) in GUILD_CASES.into_iter().enumerate()
+        {
+            let config = guild_config(i, enabled, channel, invalid, pause);
+            assert_eq!(
+                guild(Some(&config), now()),
+                expected_guild(expected, pause),
+                "case {i}: enabled={enabled} channel={channel} invalid={invalid} pause={pause:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_guild_without_config() {
+        assert_eq!(guild(None, now()), RegistrationStatus::NotRegistered);
+    }
+
+    #[test]
+    fn test_guild_pause_ends_at_until() {
+        let mut config = guild_config(0, true, true, false, Pause::None);
+        config.paused_until = Some(now());
+
+        assert_eq!(guild(Some(&config), now()), Active);
+    }
+
+    #[test]
+    fn test_user_status() {
+        for (enabled, reason, expected) in USER_CASES {
+            let config = user_config(0, enabled, reason);
+            assert_eq!(
+                user(Some(&config)),
+                expected,
+                "enabled={enabled} reason={reason:?}"
+            );
+        }
+        assert_eq!(user(None), RegistrationStatus::NotRegistered);
+    }
+
+    #[test]
+    fn test_is_registered() {
+        assert!(Active.is_registered());
+        assert!(RegistrationStatus::Paused { until: now() }.is_registered());
+        assert!(!Disabled.is_registered());
+        assert!(!NeedsReconfigure.is_registered());
+        assert!(!RegistrationStatus::NotRegistered.is_registered());
+    }
+
+    #[tokio::test]
+    async fn test_guild_receives_alerts_matches_active() {
+        let db = connect_in_memory().await;
+        let mut active = BTreeSet::new();
+        for (i, (enabled, channel, invalid, pause, expected)) in GUILD_CASES.into_iter().enumerate()
+        {
+            if expected == Some(Active) {
+                active.insert(i.to_string());
+            }
+            guild_config(i, enabled, channel, invalid, pause)
+                .into_active_model()
+                .insert(&db)
+                .await
+                .unwrap();
+        }
+
+        let receiving: BTreeSet<_> = guild_configs::Entity::find()
+            .filter(guild_receives_alerts(now()))
+            .all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|config| config.guild_id)
+            .collect();
+
+        assert_eq!(receiving, active);
+    }
+
+    #[tokio::test]
+    async fn test_user_receives_alerts_matches_active() {
+        let db = connect_in_memory().await;
+        let mut active = BTreeSet::new();
+        for (i, (enabled, reason, expected)) in USER_CASES.into_iter().enumerate() {
+            if expected == Active {
+                active.insert(i.to_string());
+            }
+            user_config(i, enabled, reason)
+                .into_active_model()
+                .insert(&db)
+                .await
+                .unwrap();
+        }
+
+        let receiving: BTreeSet<_> = user_configs::Entity::find()
+            .filter(user_receives_alerts())
+            .all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|config| config.user_id)
+            .collect();
+
+        assert_eq!(receiving, active);
+    }
+}
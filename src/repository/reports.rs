@@ -0,0 +1,547 @@
+//! Repository for user incident reports
+
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serenity::all::{GuildId, UserId};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::user_reports;
+
+/// `user_reports.status` for new reports that have not been part of an alert
+const STATUS_ACTIVE: &str = "active";
+
+/// `user_reports.status` for reports included in a threshold alert
+pub const STATUS_COUNTED: &str = "counted";
+
+/// `user_reports.status` for reports whose window passed without an alert
+pub const STATUS_EXPIRED: &str = "expired";
+
+/// Statuses that count toward thresholds and hold the cooldown
+///
+/// Counted reports keep counting until their window passes, so reports
+/// filed after an alert still see the full total.
+pub const COUNTING_STATUSES: [&str; 2] = [STATUS_ACTIVE, STATUS_COUNTED];
+
+/// `user_reports.status` for reports that lost a submission race
+///
+/// Kept for auditing; excluded from every count.
+pub const STATUS_DUPLICATE: &str = "duplicate";
+
+/// `user_reports.status` for reports retracted by the reporter with /report withdraw
+pub const STATUS_WITHDRAWN: &str = "withdrawn";
+
+/// Statuses kept in the table but excluded from statistics
+pub const UNCOUNTED_STATUSES: [&str; 2] = [STATUS_DUPLICATE, STATUS_WITHDRAWN];
+
+/// Repository for user report operations
+pub struct ReportRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> ReportRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Create an active report
+    pub async fn create(
+        &self,
+        guild_id: Option<GuildId>,
+        user_id: UserId,
+        incident_type: &str,
+        content: Option<String>,
+    ) -> Result<user_reports::Model, sea_orm::DbErr> {
+        let model = user_reports::ActiveModel {
+            guild_id: Set(guild_id.map(|g| g.to_string())),
+            user_id: Set(user_id.to_string()),
+            incident_type: Set(incident_type.to_string()),
+            content: Set(content),
+            status: Set(STATUS_ACTIVE.to_string()),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        };
+        model.insert(&*self.db).await
+    }
+
+    /// Take the database write lock for the rest of the transaction
+    ///
+    /// SQLite transactions start deferred, so two concurrent check-and-insert
+    /// transactions could both read before either writes. A no-op write as the
+    /// first statement acquires the lock up front, like `BEGIN IMMEDIATE`;
+    /// other writers wait on the busy timeout instead of racing.
+    pub async fn lock_for_write(&self) -> Result<(), sea_orm::DbErr> {
+        user_reports::Entity::update_many()
+            .col_expr(
+                user_reports::Column::Status,
+                Expr::col(user_reports::Column::Status).into(),
+            )
+            .filter(Expr::cust("0"))
+            .exec(&*self.db)
+            .await
+            .map(|_| ())
+    }
+
+    /// Newest active or counted report by a user created after `cutoff`
+    pub async fn latest_active_since(
+        &self,
+        user_id: UserId,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Option<user_reports::Model>, sea_orm::DbErr> {
+        user_reports::Entity::find()
+            .filter(user_reports::Column::UserId.eq(user_id.to_string()))
+            .filter(user_reports::Column::Status.is_in(COUNTING_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gt(cutoff))
+            .order_by_desc(user_reports::Column::CreatedAt)
+            .one(&*self.db)
+            .await
+    }
+
+    /// Active or counted reports by a user created after `cutoff`, oldest first
+    ///
+    /// Reports created in the same millisecond are ordered by ID.
+    pub async fn active_since(
+        &self,
+        user_id: UserId,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<user_reports::Model>, sea_orm::DbErr> {
+        user_reports::Entity::find()
+            .filter(user_reports::Column::UserId.eq(user_id.to_string()))
+            .filter(user_reports::Column::Status.is_in(COUNTING_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gt(cutoff))
+            .order_by_asc(user_reports::Column::CreatedAt)
+            .order_by_asc(user_reports::Column::Id)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Count reports created at or after `since`, excluding duplicates and withdrawn reports
+    pub async fn count_since(&self, since: DateTime<Utc>) -> Result<u64, sea_orm::DbErr> {
+        user_reports::Entity::find()
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since))
+            .count(&*self.db)
+            .await
+    }
+
+    /// Count distinct users other than `exclude` with an active or counted
+    /// report of a type created after `since`
+    pub async fn count_other_reporters_since(
+        &self,
+        incident_type: &str,
+        exclude: UserId,
+        since: DateTime<Utc>,
+    ) -> Result<i64, sea_orm::DbErr> {
+        let count = user_reports::Entity::find()
+            .filter(user_reports::Column::IncidentType.eq(incident_type))
+            .filter(user_reports::Column::UserId.ne(exclude.to_string()))
+            .filter(user_reports::Column::CreatedAt.gt(since))
+            .filter(user_reports::Column::Status.is_in(COUNTING_STATUSES))
+            .select_only()
+            .column_as(
+                Expr::col(user_reports::Column::UserId).count_distinct(),
+                "count",
+            )
+            .into_tuple::<i64>()
+            .one(&*self.db)
+            .await?;
+        Ok(count.unwrap_or(0))
+    }
+
+    /// Guild IDs of active or counted reports of a type created after `since`, one per guild
+    ///
+    /// User-install reports appear once as `None`.
+    pub async fn active_guilds_since(
+        &self,
+        incident_type: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Option<String>>, sea_orm::DbErr> {
+        user_reports::Entity::find()
+            .select_only()
+            .column(user_reports::Column::GuildId)
+            .filter(user_reports::Column::IncidentType.eq(incident_type))
+            .filter(user_reports::Column::Status.is_in(COUNTING_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gt(since))
+            .group_by(user_reports::Column::GuildId)
+            .into_tuple::<Option<String>>()
+            .all(&*self.db)
+            .await
+    }
+
+    /// Creation times of reports in `[since, until)`, all types, oldest first
+    ///
+    /// Duplicates and withdrawn reports are excluded.
+    pub async fn created_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, sea_orm::DbErr> {
+        user_reports::Entity::find()
+            .select_only()
+            .column(user_reports::Column::CreatedAt)
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since))
+            .filter(user_reports::Column::CreatedAt.lt(until))
+            .order_by_asc(user_reports::Column::CreatedAt)
+            .into_tuple::<DateTime<Utc>>()
+            .all(&*self.db)
+            .await
+    }
+
+    /// Most reported incident type since `since` with its report count
+    ///
+    /// Ties go to the alphabetically first type. Duplicates and withdrawn
+    /// reports are excluded.
+    pub async fn busiest_type_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Option<(String, u64)>, sea_orm::DbErr> {
+        let count = Expr::col(user_reports::Column::Id).count();
+        let row = user_reports::Entity::find()
+            .select_only()
+            .column(user_reports::Column::IncidentType)
+            .column_as(count.clone(), "count")
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since))
+            .group_by(user_reports::Column::IncidentType)
+            .order_by_desc(count)
+            .order_by_asc(user_reports::Column::IncidentType)
+            .into_tuple::<(String, i64)>()
+            .one(&*self.db)
+            .await?;
+        Ok(row.map(|(incident_type, count)| (incident_type, count.max(0) as u64)))
+    }
+
+    /// Most reported incident types since `since`, busiest first, at most `limit`
+    pub async fn top_types_since(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<(String, u64)>, sea_orm::DbErr> {
+        let count = Expr::col(user_reports::Column::Id).count();
+        let rows = user_reports::Entity::find()
+            .select_only()
+            .column(user_reports::Column::IncidentType)
+            .column_as(count.clone(), "count")
+            .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+            .filter(user_reports::Column::CreatedAt.gte(since))
+            .group_by(user_reports::Column::IncidentType)
+            .order_by_desc(count)
+            .order_by_asc(user_reports::Column::IncidentType)
+            .limit(limit)
+            .into_tuple::<(String, i64)>()
+            .all(&*self.db)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(incident_type, count)| (incident_type, count.max(0) as u64))
+            .collect())
+    }
+
+    /// Creation time of the oldest report, including uncounted ones
+    pub async fn oldest_created_at(&self) -> Result<Option<DateTime<Utc>>, sea_orm::DbErr> {
+        user_reports::Entity::find()
+            .select_only()
+            .column(user_reports::Column::CreatedAt)
+            .order_by_asc(user_reports::Column::CreatedAt)
+            .into_tuple::<DateTime<Utc>>()
+            .one(&*self.db)
+            .await
+    }
+
+    /// Delete reports created before `cutoff`, including uncounted ones, returning the number removed
+    pub async fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sea_orm::DbErr> {
+        let result = user_reports::Entity::delete_many()
+            .filter(user_reports::Column::CreatedAt.lt(cutoff))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Keep a user's earliest active or counted report created after
+    /// `cutoff` and mark the others as duplicates
+    ///
+    /// Returns the kept report. Losing reports keep their rows for auditing.
+    pub async fn keep_earliest_since(
+        &self,
+        user_id: UserId,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Option<user_reports::Model>, sea_orm::DbErr> {
+        let reports = self.active_since(user_id, cutoff).await?;
+        for report in reports.iter().skip(1) {
+            self.mark_duplicate(report.id).await?;
+        }
+        Ok(reports.into_iter().next())
+    }
+
+    /// Mark a report as a duplicate so it no longer counts
+    pub async fn mark_duplicate(&self, id: i64) -> Result<(), sea_orm::DbErr> {
+        user_reports::Entity::update_many()
+            .col_expr(user_reports::Column::Status, Expr::value(STATUS_DUPLICATE))
+            .filter(user_reports::Column::Id.eq(id))
+            .exec(&*self.db)
+            .await
+            .map(|_| ())
+    }
+
+    /// Mark active reports of a type created after `since` as counted, returning the number updated
+    pub async fn mark_counted(
+        &self,
+        incident_type: &str,
+        since: DateTime<Utc>,
+    ) -> Result<u64, sea_orm::DbErr> {
+        let result = user_reports::Entity::update_many()
+            .col_expr(user_reports::Column::Status, Expr::value(STATUS_COUNTED))
+            .filter(user_reports::Column::IncidentType.eq(incident_type))
+            .filter(user_reports::Column::Status.eq(STATUS_ACTIVE))
+            .filter(user_reports::Column::CreatedAt.gt(since))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Mark active reports created at or before `cutoff` as expired, returning the number updated
+    pub async fn expire_before(&self, cutoff: DateTime<Utc>) -> Result<u64, sea_orm::DbErr> {
+        let result = user_reports::Entity::update_many()
+            .col_expr(user_reports::Column::Status, Expr::value(STATUS_EXPIRED))
+            .filter(user_reports::Column::Status.eq(STATUS_ACTIVE))
+            .filter(user_reports::Column::CreatedAt.lte(cutoff))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Mark a report as withdrawn by its reporter so it no longer counts
+    pub async fn mark_withdrawn(&self, id: i64) -> Result<(), sea_orm::DbErr> {
+        user_reports::Entity::update_many()
+            .col_expr(user_reports::Column::Status, Expr::value(STATUS_WITHDRAWN))
+            .filter(user_reports::Column::Id.eq(id))
+            .exec(&*self.db)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use chrono::Duration;
+    use sea_orm::TransactionTrait;
+
+    const USER: UserId = UserId::new(1);
+    const OTHER: UserId = UserId::new(2);
+
+    async fn repo() -> ReportRepository {
+        ReportRepository::new(Arc::new(connect_in_memory().await))
+    }
+
+    async fn statuses(repo: &ReportRepository) -> Vec<(i64, String)> {
+        user_reports::Entity::find()
+            .order_by_asc(user_reports::Column::Id)
+            .all(&*repo.db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|report| (report.id, report.status))
+            .collect()
+    }
+
+    fn hour_ago() -> DateTime<Utc> {
+        Utc::now() - Duration::hours(1)
+    }
+
+    /// Insert an active report with a fixed creation time
+    async fn create_at(
+        repo: &ReportRepository,
+        user_id: UserId,
+        incident_type: &str,
+        created_at: DateTime<Utc>,
+    ) -> i64 {
+        user_reports::ActiveModel {
+            user_id: Set(user_id.to_string()),
+            incident_type: Set(incident_type.to_string()),
+            status: Set(STATUS_ACTIVE.to_string()),
+            created_at: Set(created_at),
+            ..Default::default()
+        }
+        .insert(&*repo.db)
+        .await
+        .unwrap()
+        .id
+    }
+
+    #[tokio::test]
+    async fn test_race_keeps_one_active_and_deletes_none() {
+        let repo = repo().await;
+        // Two reports landing in the same window, as a racing writer would leave them
+        let first = repo.create(None, USER, "login", None).await.unwrap();
+        let second = repo.create(None, USER, "login", None).await.unwrap();
+
+        let kept = repo.keep_earliest_since(USER, hour_ago()).await.unwrap();
+
+        assert_eq!(kept.map(|report| report.id), Some(first.id));
+        assert_eq!(
+            statuses(&repo).await,
+            vec![
+                (first.id, STATUS_ACTIVE.to_string()),
+                (second.id, STATUS_DUPLICATE.to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_race_resolution_leaves_other_users_alone() {
+        let repo = repo().await;
+        repo.create(None, USER, "login", None).await.unwrap();
+        repo.create(None, OTHER, "login", None).await.unwrap();
+
+        repo.keep_earliest_since(USER, hour_ago()).await.unwrap();
+
+        assert!(
+            statuses(&repo)
+                .await
+                .iter()
+                .all(|(_, status)| status == STATUS_ACTIVE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_race_resolution_without_reports() {
+        let repo = repo().await;
+
+        assert_eq!(
+            repo.keep_earliest_since(USER, hour_ago()).await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicates_excluded_from_counts() {
+        let repo = repo().await;
+        repo.create(None, OTHER, "login", None).await.unwrap();
+        let duplicate = repo.create(None, OTHER, "login", None).await.unwrap();
+        repo.mark_duplicate(duplicate.id).await.unwrap();
+
+        assert_eq!(repo.count_since(hour_ago()).await.unwrap(), 1);
+        assert_eq!(repo.active_since(OTHER, hour_ago()).await.unwrap().len(), 1);
+        assert_eq!(
+            repo.count_other_reporters_since("login", USER, hour_ago())
+                .await
+                .unwrap(),
+            1
+        );
+        assert_ne!(
+            repo.latest_active_since(OTHER, hour_ago())
+                .await
+                .unwrap()
+                .map(|report| report.id),
+            Some(duplicate.id)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lock_for_write_changes_nothing() {
+        let repo = repo().await;
+        let report = repo.create(None, USER, "login", None).await.unwrap();
+
+        let txn = repo.db.begin().await.unwrap();
+        ReportRepository::new(&txn).lock_for_write().await.unwrap();
+        txn.commit().await.unwrap();
+
+        assert_eq!(
+            statuses(&repo).await,
+            vec![(report.id, STATUS_ACTIVE.to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_counted_only_touches_active_reports_after_since() {
+        let repo = repo().await;
+        let since = hour_ago();
+        let before = create_at(&repo, USER, "login", since - Duration::minutes(5)).await;
+        let after = create_at(&repo, USER, "login", since + Duration::minutes(5)).await;
+        let other_type = create_at(&repo, OTHER, "instance", since + Duration::minutes(5)).await;
+        let duplicate = create_at(&repo, OTHER, "login", since + Duration::minutes(10)).await;
+        repo.mark_duplicate(duplicate).await.unwrap();
+
+        assert_eq!(repo.mark_counted("login", since).await.unwrap(), 1);
+        assert_eq!(
+            statuses(&repo).await,
+            vec![
+                (before, STATUS_ACTIVE.to_string()),
+                (after, STATUS_COUNTED.to_string()),
+                (other_type, STATUS_ACTIVE.to_string()),
+                (duplicate, STATUS_DUPLICATE.to_string()),
+            ]
+        );
+        // Already counted, so a second alert changes nothing
+        assert_eq!(repo.mark_counted("login", since).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_expire_before_only_touches_active_reports_up_to_cutoff() {
+        let repo = repo().await;
+        let cutoff = hour_ago();
+        let old = create_at(&repo, USER, "login", cutoff - Duration::minutes(5)).await;
+        let at_cutoff = create_at(&repo, OTHER, "login", cutoff).await;
+        let old_counted = create_at(&repo, OTHER, "instance", cutoff - Duration::minutes(5)).await;
+        repo.mark_counted("instance", cutoff - Duration::minutes(10))
+            .await
+            .unwrap();
+        let recent = create_at(&repo, USER, "instance", cutoff + Duration::minutes(5)).await;
+
+        assert_eq!(repo.expire_before(cutoff).await.unwrap(), 2);
+        assert_eq!(
+            statuses(&repo).await,
+            vec![
+                (old, STATUS_EXPIRED.to_string()),
+                (at_cutoff, STATUS_EXPIRED.to_string()),
+                (old_counted, STATUS_COUNTED.to_string()),
+                (recent, STATUS_ACTIVE.to_string()),
+            ]
+        );
+        assert_eq!(repo.expire_before(cutoff).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_reports_stop_counting_toward_thresholds() {
+        let repo = repo().await;
+        let window_start = Utc::now() - Duration::hours(2);
+        create_at(&repo, USER, "login", window_start + Duration::minutes(10)).await;
+        create_at(&repo, OTHER, "login", window_start + Duration::minutes(20)).await;
+        let third = UserId::new(3);
+        let exclude = UserId::new(99);
+        assert_eq!(
+            repo.count_other_reporters_since("login", exclude, window_start)
+                .await
+                .unwrap(),
+            2
+        );
+
+        repo.expire_before(hour_ago()).await.unwrap();
+        create_at(&repo, third, "login", Utc::now()).await;
+
+        assert_eq!(
+            repo.count_other_reporters_since("login", exclude, window_start)
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(
+            repo.latest_active_since(USER, window_start)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Repository for admin-scheduled alert silences
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, Set,
+};
+use serenity::all::{GuildId, UserId};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::alert_silences;
+
+/// Repository for alert silence operations
+pub struct SilenceRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> SilenceRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Create a silence for a guild; `incident_type` of `None` silences every type
+    pub async fn create(
+        &self,
+        guild_id: GuildId,
+        incident_type: Option<String>,
+        until: DateTime<Utc>,
+        created_by: UserId,
+    ) -> Result<alert_silences::Model, sea_orm::DbErr> {
+        let model = alert_silences::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            incident_type: Set(incident_type),
+            until: Set(until),
+            created_by: Set(created_by.to_string()),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        };
+        model.insert(&*self.db).await
+    }
+
+    /// Silences for a guild that are still active at `now`, soonest expiry first
+    pub async fn active(
+        &self,
+        guild_id: GuildId,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<alert_silences::Model>, sea_orm::DbErr> {
+        alert_silences::Entity::find()
+            .filter(alert_silences::Column::GuildId.eq(guild_id.to_string()))
+            .filter(alert_silences::Column::Until.gt(now))
+            .order_by_asc(alert_silences::Column::Until)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Whether alerts of `incident_type` are silenced for a guild at `now`
+    pub async fn is_silenced(
+        &self,
+        guild_id: GuildId,
+        incident_type: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let silences = self.active(guild_id, now).await?;
+        Ok(silences.iter().any(|s| covers(s, incident_type, now)))
+    }
+
+    /// Every stored silence for a guild, including expired ones not yet pruned
+    pub async fn list(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Vec<alert_silences::Model>, sea_orm::DbErr> {
+        alert_silences::Entity::find()
+            .filter(alert_silences::Column::GuildId.eq(guild_id.to_string()))
+            .order_by_asc(alert_silences::Column::Id)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Remove every silence for a guild, returning the number removed
+    pub async fn clear(&self, guild_id: GuildId) -> Result<u64, sea_orm::DbErr> {
+        let result = alert_silences::Entity::delete_many()
+            .filter(alert_silences::Column::GuildId.eq(guild_id.to_string()))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Delete silences that expired before `now`, returning the number removed
+    pub async fn prune_expired(&self, now: DateTime<Utc>) -> Result<u64, sea_orm::DbErr> {
+        let result = alert_silences::Entity::delete_many()
+            .filter(alert_silences::Column::Until.lte(now))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
+
+/// Whether a silence suppresses alerts of `incident_type` at `now`
+pub fn covers(silence: &alert_silences::Model, incident_type: &str, now: DateTime<Utc>) -> bool {
+    silence.until > now
+        && silence
+            .incident_type
+            .as_deref()
+            .is_none_or(|silenced| silenced == incident_type)
+}
@@ -0,0 +1,117 @@
+//! Repository for guild alert webhooks
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use serenity::all::GuildId;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::entity::guild_webhooks;
+
+/// Repository for guild webhook operations
+pub struct WebhookRepository<C = Arc<DatabaseConnection>> {
+    db: C,
+}
+
+impl<C> WebhookRepository<C>
+where
+    C: Deref,
+    C::Target: ConnectionTrait + Sized,
+{
+    /// Create a repository on the connection pool or an open transaction
+    pub fn new(db: C) -> Self {
+        Self { db }
+    }
+
+    /// Enabled webhooks of a guild, oldest first
+    pub async fn list_enabled(
+        &self,
+        guild_id: &str,
+    ) -> Result<Vec<guild_webhooks::Model>, sea_orm::DbErr> {
+        guild_webhooks::Entity::find()
+            .filter(guild_webhooks::Column::GuildId.eq(guild_id))
+            .filter(guild_webhooks::Column::Enabled.eq(true))
+            .order_by_asc(guild_webhooks::Column::Id)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Every webhook of a guild, including disabled ones, oldest first
+    pub async fn list(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Vec<guild_webhooks::Model>, sea_orm::DbErr> {
+        guild_webhooks::Entity::find()
+            .filter(guild_webhooks::Column::GuildId.eq(guild_id.to_string()))
+            .order_by_asc(guild_webhooks::Column::Id)
+            .all(&*self.db)
+            .await
+    }
+
+    /// Number of webhooks registered for a guild
+    pub async fn count(&self, guild_id: GuildId) -> Result<u64, sea_orm::DbErr> {
+        guild_webhooks::Entity::find()
+            .filter(guild_webhooks::Column::GuildId.eq(guild_id.to_string()))
+            .count(&*self.db)
+            .await
+    }
+
+    /// Register a webhook; `alert_types` is a comma-separated list, empty for every type
+    pub async fn add(
+        &self,
+        guild_id: GuildId,
+        url: &str,
+        secret: &str,
+        alert_types: &str,
+    ) -> Result<guild_webhooks::Model, sea_orm::DbErr> {
+        let now = Utc::now();
+        let model = guild_webhooks::ActiveModel {
+            guild_id: Set(guild_id.to_string()),
+            url: Set(url.to_string()),
+            secret: Set(secret.to_string()),
+            alert_types: Set(alert_types.to_string()),
+            enabled: Set(true),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        };
+        model.insert(&*self.db).await
+    }
+
+    /// Delete a guild's webhook by ID, returning whether it existed
+    ///
+    /// Scoped to the guild so one guild can't remove another's webhook.
+    pub async fn remove(&self, guild_id: GuildId, id: i64) -> Result<bool, sea_orm::DbErr> {
+        let result = guild_webhooks::Entity::delete_many()
+            .filter(guild_webhooks::Column::Id.eq(id))
+            .filter(guild_webhooks::Column::GuildId.eq(guild_id.to_string()))
+            .exec(&*self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
+
+/// Incident types a webhook receives, in stored order (empty for every type)
+pub fn webhook_types(row: &guild_webhooks::Model) -> Vec<&str> {
+    row.alert_types
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Host of a webhook URL, without the path that often carries a token
+pub fn webhook_host(row: &guild_webhooks::Model) -> Option<String> {
+    reqwest::Url::parse(&row.url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+}
+
+/// Whether a webhook should receive an alert of `incident_type`
+pub fn receives(row: &guild_webhooks::Model, incident_type: &str) -> bool {
+    let types = webhook_types(row);
+    row.enabled && (types.is_empty() || types.contains(&incident_type))
+}
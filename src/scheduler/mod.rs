@@ -0,0 +1,382 @@
+//! Background job scheduler
+//!
+//! Subsystems register periodic jobs on a [`Scheduler`] during setup instead of
+//! spawning their own timer loops. A single driver task runs due jobs one at a
+//! time, isolates panics, logs timings, records per-job status for
+//! `/admin show`, and stops when the shutdown token is cancelled. Jobs only
+//! run while this instance holds the collector lease; due jobs on a follower
+//! are skipped until their next slot. The driver waits for the first
+//! election round before its first tick, so the instance that is about to
+//! lead doesn't skip the jobs that are due at startup.
+//!
+//! The collector keeps its own poll loops: their intervals change at runtime
+//! and are driven by per-poller backoff.
+
+mod schedule;
+
+pub use schedule::Schedule;
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::leader::Leadership;
+
+/// Error returned by a job run
+pub type JobError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Result of a single job run
+pub type JobResult = Result<(), JobError>;
+
+type JobFuture = Pin<Box<dyn Future<Output = JobResult> + Send>>;
+type JobFn = Box<dyn Fn() -> JobFuture + Send + Sync>;
+
+/// A registered job
+struct Job {
+    name: &'static str,
+    schedule: Schedule,
+    run: JobFn,
+    next_due: DateTime<Utc>,
+}
+
+// =============================================================================
+// Status
+// =============================================================================
+
+/// Last known state of a job
+#[derive(Debug, Clone, Default)]
+pub struct JobStatus {
+    /// Human-readable schedule
+    pub schedule: String,
+    /// When the last run started
+    pub last_run: Option<DateTime<Utc>>,
+    pub last_duration: Option<Duration>,
+    /// Error or panic message from the last run, if it failed
+    pub last_error: Option<String>,
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+/// Status of all registered jobs, keyed by job name
+#[derive(Debug, Default)]
+pub struct SchedulerStatus {
+    jobs: Mutex<BTreeMap<&'static str, JobStatus>>,
+}
+
+impl SchedulerStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of all registered jobs, by name
+    pub fn snapshot(&self) -> Vec<(&'static str, JobStatus)> {
+        let jobs = self.jobs.lock().expect("scheduler status mutex poisoned");
+        jobs.iter()
+            .map(|(name, status)| (*name, status.clone()))
+            .collect()
+    }
+
+    fn update(&self, name: &'static str, f: impl FnOnce(&mut JobStatus)) {
+        let mut jobs = self.jobs.lock().expect("scheduler status mutex poisoned");
+        f(jobs.entry(name).or_default());
+    }
+}
+
+// =============================================================================
+// Scheduler
+// =============================================================================
+
+/// Registry of periodic jobs, consumed by [`Scheduler::run`]
+pub struct Scheduler {
+    jobs: Vec<Job>,
+    status: Arc<SchedulerStatus>,
+}
+
+impl Scheduler {
+    pub fn new(status: Arc<SchedulerStatus>) -> Self {
+        Self {
+            jobs: Vec::new(),
+            status,
+        }
+    }
+
+    /// Register a job
+    ///
+    /// Job names must be unique; they key the status shown in `/admin show`.
+    pub fn register<F, Fut>(&mut self, name: &'static str, schedule: Schedule, run: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = JobResult> + Send + 'static,
+    {
+        debug_assert!(
+            self.jobs.iter().all(|job| job.name != name),
+            "duplicate job name {name}"
+        );
+
+        let next_due = schedule.next_due(None, Utc::now());
+        self.status.update(name, |status| {
+            status.schedule = schedule.to_string();
+            status.next_run = Some(next_due);
+        });
+        self.jobs.push(Job {
+            name,
+            schedule,
+            run: Box::new(move || Box::pin(run())),
+            next_due,
+        });
+    }
+
+    /// Run due jobs until `shutdown` is cancelled
+    ///
+    /// Jobs run sequentially in registration order when due at the same time.
+    /// A job in progress is allowed to finish before the driver exits.
+    pub async fn run(mut self, shutdown: CancellationToken, leadership: Arc<Leadership>) {
+        if self.jobs.is_empty() {
+            return;
+        }
+        info!(jobs = self.jobs.len(), "Scheduler started");
+
+        // Every instance is a follower until its first lease write, and
+        // interval jobs are due right away
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                info!("Scheduler stopped");
+                return;
+            }
+            _ = leadership.first_round() => {}
+        }
+
+        loop {
+            let next_due = self
+                .jobs
+                .iter()
+                .map(|job| job.next_due)
+                .min()
+                .expect("scheduler has jobs");
+            let wait = (next_due - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(wait) => {}
+            }
+
+            for job in &mut self.jobs {
+                if shutdown.is_cancelled() {
+                    break;
+                }
+                if job.next_due > Utc::now() {
+                    continue;
+                }
+                if leadership.is_leader() {
+                    run_job(job, &self.status).await;
+                } else {
+                    skip_job(job, &self.status);
+                }
+            }
+        }
+
+        info!("Scheduler stopped");
+    }
+}
+
+/// Run a job once in its own task so a panic cannot take down the driver
+async fn run_job(job: &mut Job, status: &SchedulerStatus) {
+    let started_at = Utc::now();
+    let started = Instant::now();
+    debug!(job = job.name, "Running scheduled job");
+
+    let outcome = match tokio::spawn((job.run)()).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => {
+            error!(job = job.name, error = %e, "Scheduled job failed");
+            Some(e.to_string())
+        }
+        Err(e) if e.is_panic() => {
+            let message = panic_message(e.into_panic());
+            error!(job = job.name, panic = %message, "Scheduled job panicked");
+            Some(format!("panicked: {}", message))
+        }
+        Err(e) => {
+            warn!(job = job.name, error = %e, "Scheduled job was cancelled");
+            Some(e.to_string())
+        }
+    };
+
+    let elapsed = started.elapsed();
+    debug!(
+        job = job.name,
+        elapsed_ms = elapsed.as_millis() as u64,
+        "Scheduled job finished"
+    );
+
+    job.next_due = job.schedule.next_due(Some(started_at), Utc::now());
+    status.update(job.name, |status| {
+        status.next_run = Some(job.next_due);
+        status.last_run = Some(started_at);
+        status.last_duration = Some(elapsed);
+        status.last_error = outcome;
+    });
+}
+
+/// Move a due job to its next slot without running it (another instance is leader)
+fn skip_job(job: &mut Job, status: &SchedulerStatus) {
+    debug!(job = job.name, "Not the leader, skipping scheduled job");
+    job.next_due = job.schedule.next_due(Some(Utc::now()), Utc::now());
+    status.update(job.name, |status| status.next_run = Some(job.next_due));
+}
+
+/// Extract the message from a panic payload
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use crate::leader;
+    use crate::repository::LeaseRepository;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const HOURLY: Schedule = Schedule::Interval(Duration::from_secs(60 * 60));
+
+    /// Job that counts its runs and stops the driver after `stop_after` runs
+    fn counting_job(
+        runs: &Arc<AtomicUsize>,
+        shutdown: &CancellationToken,
+        stop_after: usize,
+    ) -> impl Fn() -> JobFuture + Send + Sync + 'static {
+        let runs = runs.clone();
+        let shutdown = shutdown.clone();
+        move || {
+            let runs = runs.clone();
+            let shutdown = shutdown.clone();
+            Box::pin(async move {
+                if runs.fetch_add(1, Ordering::SeqCst) + 1 >= stop_after {
+                    shutdown.cancel();
+                }
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_due_at_startup_runs_on_new_leader() {
+        let db = Arc::new(connect_in_memory().await);
+        let leadership = Arc::new(Leadership::new("a".to_string()));
+        let shutdown = CancellationToken::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut scheduler = Scheduler::new(Arc::new(SchedulerStatus::new()));
+        scheduler.register("job", HOURLY, counting_job(&runs, &shutdown, 1));
+
+        let driver = tokio::spawn(scheduler.run(shutdown.clone(), leadership.clone()));
+        // The scheduler is already waiting when the lease is first written
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let election = tokio::spawn(leader::run(db, leadership, shutdown.clone()));
+
+        tokio::time::timeout(Duration::from_secs(5), driver)
+            .await
+            .expect("job should run once the lease is acquired")
+            .unwrap();
+        election.await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_follower_skips_due_job() {
+        let db = Arc::new(connect_in_memory().await);
+        let now = Utc::now();
+        let ttl = chrono::Duration::from_std(leader::LEASE_TTL).unwrap();
+        LeaseRepository::new(db.clone())
+            .try_acquire(leader::COLLECTOR_LEASE, "other", now + ttl, now, now)
+            .await
+            .unwrap();
+        let leadership = Arc::new(Leadership::new("b".to_string()));
+        let shutdown = CancellationToken::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let status = Arc::new(SchedulerStatus::new());
+        let mut scheduler = Scheduler::new(status.clone());
+        scheduler.register("job", HOURLY, counting_job(&runs, &shutdown, 1));
+
+        let election = tokio::spawn(leader::run(db, leadership.clone(), shutdown.clone()));
+        let driver = tokio::spawn(scheduler.run(shutdown.clone(), leadership));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        shutdown.cancel();
+        driver.await.unwrap();
+        election.await.unwrap();
+
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        let (_, job) = status.snapshot().remove(0);
+        assert!(job.last_run.is_none());
+        assert!(job.next_run.unwrap() > now + chrono::Duration::minutes(59));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_does_not_stop_driver() {
+        let db = Arc::new(connect_in_memory().await);
+        let leadership = Arc::new(Leadership::new("a".to_string()));
+        let shutdown = CancellationToken::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let status = Arc::new(SchedulerStatus::new());
+        let mut scheduler = Scheduler::new(status.clone());
+        scheduler.register("panics", HOURLY, || async {
+            panic!("job blew up");
+        });
+        scheduler.register("normal", HOURLY, counting_job(&runs, &shutdown, 1));
+
+        let election = tokio::spawn(leader::run(db, leadership.clone(), shutdown.clone()));
+        let driver = tokio::spawn(scheduler.run(shutdown.clone(), leadership));
+        tokio::time::timeout(Duration::from_secs(5), driver)
+            .await
+            .expect("normal job should run after the panic")
+            .expect("driver should not panic");
+        election.await.unwrap();
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        let jobs: BTreeMap<_, _> = status.snapshot().into_iter().collect();
+        let panicked = &jobs["panics"];
+        assert!(panicked.last_run.is_some());
+        let error = panicked.last_error.as_deref().unwrap();
+        assert!(error.contains("panicked"), "{error}");
+        assert!(error.contains("job blew up"), "{error}");
+        assert!(jobs["normal"].last_error.is_none());
+        assert!(jobs["normal"].last_run.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failing_job_records_error() {
+        let db = Arc::new(connect_in_memory().await);
+        let leadership = Arc::new(Leadership::new("a".to_string()));
+        let shutdown = CancellationToken::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let status = Arc::new(SchedulerStatus::new());
+        let mut scheduler = Scheduler::new(status.clone());
+        scheduler.register("fails", HOURLY, || async { Err("disk full".into()) });
+        scheduler.register("normal", HOURLY, counting_job(&runs, &shutdown, 1));
+
+        let election = tokio::spawn(leader::run(db, leadership.clone(), shutdown.clone()));
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            scheduler.run(shutdown.clone(), leadership),
+        )
+        .await
+        .unwrap();
+        election.await.unwrap();
+
+        let jobs: BTreeMap<_, _> = status.snapshot().into_iter().collect();
+        assert_eq!(jobs["fails"].last_error.as_deref(), Some("disk full"));
+        assert!(jobs["fails"].next_run.unwrap() > jobs["fails"].last_run.unwrap());
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
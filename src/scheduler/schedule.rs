@@ -0,0 +1,243 @@
+//! Job schedules and next-due computation
+
+use std::fmt;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Longest DST gap we step over when the scheduled local time does not exist
+const MAX_DST_GAP_MINUTES: i64 = 3 * 60;
+
+/// When a job runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// Every `Duration`, starting immediately
+    Interval(Duration),
+    /// Once a day at a local wall-clock time
+    DailyAt { time: NaiveTime, tz: Tz },
+}
+
+impl Schedule {
+    /// Next time the job is due
+    ///
+    /// - `Interval`: immediately if never run, otherwise one interval after
+    ///   the last run.
+    /// - `DailyAt`: the first occurrence of the local time after the last run,
+    ///   or after `now` if never run.
+    ///
+    /// Missed runs are not replayed; an overdue job is due once, at `now`.
+    pub fn next_due(&self, last_run: Option<DateTime<Utc>>, now: DateTime<Utc>) -> DateTime<Utc> {
+        match *self {
+            Self::Interval(interval) => {
+                let Some(last_run) = last_run else {
+                    return now;
+                };
+                let interval =
+                    chrono::Duration::from_std(interval).unwrap_or(chrono::Duration::MAX);
+                last_run
+                    .checked_add_signed(interval)
+                    .map_or(now, |due| due.max(now))
+            }
+            Self::DailyAt { time, tz } => next_daily(time, tz, last_run.unwrap_or(now)).max(now),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Interval(interval) => write!(f, "every {}s", interval.as_secs()),
+            Self::DailyAt { time, tz } => write!(f, "daily {} {}", time.format("%H:%M"), tz),
+        }
+    }
+}
+
+/// First occurrence of `time` in `tz` strictly after `after`
+fn next_daily(time: NaiveTime, tz: Tz, after: DateTime<Utc>) -> DateTime<Utc> {
+    let today = after.with_timezone(&tz).date_naive();
+
+    // Today or tomorrow; the third day covers a DST gap shifting the time
+    today
+        .iter_days()
+        .take(3)
+        .map(|date| resolve_local(tz, date.and_time(time)))
+        .find(|due| *due > after)
+        .expect("a daily time recurs within three days")
+}
+
+/// Map a local wall-clock time to UTC across DST transitions
+///
+/// Ambiguous times (clocks going back) resolve to the first occurrence so the
+/// job runs once. Times inside a gap (clocks going forward) run at the first
+/// valid minute after the gap.
+fn resolve_local(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    (0..=MAX_DST_GAP_MINUTES)
+        .find_map(|minutes| {
+            tz.from_local_datetime(&(local + chrono::Duration::minutes(minutes)))
+                .earliest()
+        })
+        .expect("DST gaps are shorter than MAX_DST_GAP_MINUTES")
+        .with_timezone(&Utc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn daily(h: u32, m: u32, tz: Tz) -> Schedule {
+        Schedule::DailyAt {
+            time: NaiveTime::from_hms_opt(h, m, 0).unwrap(),
+            tz,
+        }
+    }
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn test_interval_never_run_is_due_now() {
+        let now = utc(2026, 1, 10, 12, 0);
+
+        assert_eq!(Schedule::Interval(HOUR).next_due(None, now), now);
+    }
+
+    #[test]
+    fn test_interval_after_last_run() {
+        let now = utc(2026, 1, 10, 12, 0);
+        let last_run = utc(2026, 1, 10, 11, 30);
+
+        assert_eq!(
+            Schedule::Interval(HOUR).next_due(Some(last_run), now),
+            utc(2026, 1, 10, 12, 30)
+        );
+    }
+
+    #[test]
+    fn test_interval_due_exactly_now() {
+        let now = utc(2026, 1, 10, 12, 0);
+        let last_run = utc(2026, 1, 10, 11, 0);
+
+        assert_eq!(Schedule::Interval(HOUR).next_due(Some(last_run), now), now);
+    }
+
+    #[test]
+    fn test_interval_overdue_runs_once_now() {
+        let now = utc(2026, 1, 10, 12, 0);
+        let last_run = utc(2026, 1, 9, 0, 0);
+
+        assert_eq!(Schedule::Interval(HOUR).next_due(Some(last_run), now), now);
+    }
+
+    #[test]
+    fn test_interval_overflow_falls_back_to_now() {
+        let now = utc(2026, 1, 10, 12, 0);
+
+        // Too long for chrono::Duration
+        assert_eq!(
+            Schedule::Interval(Duration::MAX).next_due(Some(now), now),
+            now
+        );
+        // Representable, but past the last DateTime
+        assert_eq!(
+            Schedule::Interval(HOUR).next_due(Some(DateTime::<Utc>::MAX_UTC), now),
+            now
+        );
+    }
+
+    #[test]
+    fn test_daily_later_today() {
+        let now = utc(2026, 1, 10, 1, 0);
+
+        assert_eq!(
+            daily(3, 30, Tz::UTC).next_due(None, now),
+            utc(2026, 1, 10, 3, 30)
+        );
+    }
+
+    #[test]
+    fn test_daily_passed_today_runs_tomorrow() {
+        let now = utc(2026, 1, 10, 4, 0);
+
+        assert_eq!(
+            daily(3, 30, Tz::UTC).next_due(None, now),
+            utc(2026, 1, 11, 3, 30)
+        );
+    }
+
+    #[test]
+    fn test_daily_at_exact_time_is_strictly_after() {
+        let last_run = utc(2026, 1, 10, 3, 30);
+
+        assert_eq!(
+            daily(3, 30, Tz::UTC).next_due(Some(last_run), last_run),
+            utc(2026, 1, 11, 3, 30)
+        );
+    }
+
+    #[test]
+    fn test_daily_missed_runs_not_replayed() {
+        let last_run = utc(2026, 1, 5, 3, 30);
+        let now = utc(2026, 1, 10, 12, 0);
+
+        assert_eq!(daily(3, 30, Tz::UTC).next_due(Some(last_run), now), now);
+    }
+
+    #[test]
+    fn test_daily_crosses_month_and_year() {
+        let now = utc(2026, 12, 31, 23, 59);
+
+        assert_eq!(
+            daily(0, 0, Tz::UTC).next_due(None, now),
+            utc(2027, 1, 1, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_daily_in_timezone() {
+        // 09:00 in Seoul is 00:00 UTC
+        let now = utc(2026, 1, 10, 1, 0);
+
+        assert_eq!(
+            daily(9, 0, Tz::Asia__Seoul).next_due(None, now),
+            utc(2026, 1, 11, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_daily_in_dst_gap_runs_after_gap() {
+        // 02:30 does not exist in New York on 2026-03-08; clocks jump to 03:00 EDT
+        let now = utc(2026, 3, 8, 5, 0);
+
+        assert_eq!(
+            daily(2, 30, Tz::America__New_York).next_due(None, now),
+            utc(2026, 3, 8, 7, 0)
+        );
+    }
+
+    #[test]
+    fn test_daily_in_dst_overlap_runs_once() {
+        // 01:30 happens twice in New York on 2026-11-01; the first is EDT
+        let now = utc(2026, 11, 1, 4, 0);
+        let schedule = daily(1, 30, Tz::America__New_York);
+
+        let first = schedule.next_due(None, now);
+        assert_eq!(first, utc(2026, 11, 1, 5, 30));
+        assert_eq!(
+            schedule.next_due(Some(first), first),
+            utc(2026, 11, 2, 6, 30)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Schedule::Interval(HOUR).to_string(), "every 3600s");
+        assert_eq!(
+            daily(3, 5, Tz::Asia__Seoul).to_string(),
+            "daily 03:05 Asia/Seoul"
+        );
+    }
+}
@@ -1,11 +1,27 @@
 use chrono::{DateTime, Utc};
 use sea_orm::DatabaseConnection;
-use serenity::all::GuildId;
-use std::collections::HashSet;
+use serenity::all::{GuildId, InteractionId};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tokio_util::task::TaskTracker;
 
-use crate::collector::CollectorConfigTx;
+use crate::alerts::context_chart::ChartCache;
+use crate::alerts::safeguard::DeliveryLimiter;
+use crate::bot::dedup::SeenInteractions;
+use crate::bot::maintenance_mode::MaintenanceMode;
+use crate::cache::MetricCache;
+use crate::collector::{CollectorConfigTx, CollectorHealth, SchemaMonitor};
+use crate::commands::status::CommunityStatsCache;
+use crate::config_store::ConfigStore;
+use crate::scheduler::SchedulerStatus;
+
+/// How long a pending intro send blocks other sends to the same guild
+///
+/// Normally cleared when the send finishes; the timeout only matters if the
+/// send task dies without clearing it.
+const INTRO_IN_FLIGHT_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// TypeMap key for AppState access
 pub struct AppStateKey;
@@ -14,6 +30,19 @@ impl serenity::prelude::TypeMapKey for AppStateKey {
     type Value = Arc<RwLock<AppState>>;
 }
 
+/// Tracker for tasks spawned off the request path (see
+/// [`AppState::background_tasks`])
+///
+/// Returns an untracked tracker if AppState is missing, so callers can spawn
+/// either way.
+pub async fn background_tasks(ctx: &serenity::all::Context) -> TaskTracker {
+    let data = ctx.data.read().await;
+    match data.get::<AppStateKey>() {
+        Some(state) => state.read().await.background_tasks.clone(),
+        None => TaskTracker::new(),
+    }
+}
+
 /// Application global state
 /// - Accessible via `TypeMap` in Serenity event handlers
 pub struct AppState {
@@ -23,33 +52,112 @@ pub struct AppState {
     pub collector_config: CollectorConfigTx,
     /// Bot startup timestamp
     pub started_at: DateTime<Utc>,
+    /// Rolling hourly counter of alert deliveries (alert storm safeguard)
+    pub alert_limiter: Arc<DeliveryLimiter>,
+    /// Rendered alert context charts, shared across one fan-out
+    pub alert_charts: Arc<ChartCache>,
+    /// Status page schema issues reported by the collector
+    pub schema_monitor: Arc<SchemaMonitor>,
+    /// Per-poller success/failure state reported by the collector
+    pub collector_health: Arc<CollectorHealth>,
+    /// Last run and next run of scheduled background jobs
+    pub scheduler_status: Arc<SchedulerStatus>,
+    /// Bot maintenance mode (cached copy of the `bot_config` flag)
+    pub maintenance: Arc<MaintenanceMode>,
+    /// Cached `bot_config` values for hot-path lookups
+    pub config_store: Arc<ConfigStore>,
+    /// Cached aggregate statistics for `/status community`
+    pub community_stats: Arc<CommunityStatsCache>,
+    /// Cached chart metric series (invalidated by the collector)
+    pub metric_cache: Arc<MetricCache>,
+    /// Alert fan-outs, webhook posts, and audit writes spawned off the
+    /// request path, drained on shutdown before the database closes
+    pub background_tasks: TaskTracker,
     /// Guilds awaiting intro message (failed to send on join)
     pending_intros: HashSet<GuildId>,
+    /// Pending intros currently being sent, with when the send started
+    intros_in_flight: HashMap<GuildId, Instant>,
     /// Guilds that have already received intro (prevents duplicate sends)
     intro_sent_guilds: HashSet<GuildId>,
+    /// Recently processed interactions (skips Discord redeliveries)
+    seen_interactions: SeenInteractions,
 }
 
 impl AppState {
     /// Create a new AppState instance
-    pub fn new(database: DatabaseConnection, collector_config: CollectorConfigTx) -> Self {
+    pub fn new(
+        database: DatabaseConnection,
+        collector_config: CollectorConfigTx,
+        schema_monitor: Arc<SchemaMonitor>,
+        collector_health: Arc<CollectorHealth>,
+        scheduler_status: Arc<SchedulerStatus>,
+        maintenance: Arc<MaintenanceMode>,
+    ) -> Self {
         Self {
             database: Arc::new(database),
             collector_config,
             started_at: Utc::now(),
+            alert_limiter: Arc::new(DeliveryLimiter::new()),
+            alert_charts: Arc::new(ChartCache::new()),
+            schema_monitor,
+            collector_health,
+            scheduler_status,
+            maintenance,
+            config_store: Arc::new(ConfigStore::new()),
+            community_stats: Arc::new(CommunityStatsCache::new()),
+            metric_cache: Arc::new(MetricCache::new()),
+            background_tasks: TaskTracker::new(),
             pending_intros: HashSet::new(),
+            intros_in_flight: HashMap::new(),
             intro_sent_guilds: HashSet::new(),
+            seen_interactions: SeenInteractions::default(),
         }
     }
 
+    /// AppState on a database, with defaults for everything else
+    #[cfg(test)]
+    pub async fn for_test(database: DatabaseConnection) -> Self {
+        use serenity::all::Http;
+
+        let (collector_config, _) = crate::collector::config::init(&database)
+            .await
+            .expect("Failed to load collector config");
+        Self::new(
+            database,
+            collector_config,
+            Arc::new(SchemaMonitor::new(Arc::new(Http::new("")))),
+            Arc::new(CollectorHealth::new()),
+            Arc::new(SchedulerStatus::new()),
+            Arc::new(MaintenanceMode::default()),
+        )
+    }
+
     /// Add a guild to the pending intros set
     pub fn add_pending_intro(&mut self, guild_id: GuildId) {
         self.pending_intros.insert(guild_id);
     }
 
-    /// Remove a guild from the pending intros set
-    /// Returns true if the guild was in the set
-    pub fn remove_pending_intro(&mut self, guild_id: GuildId) -> bool {
-        self.pending_intros.remove(&guild_id)
+    /// Take a guild's pending intro and mark its send as in flight
+    ///
+    /// Returns true for exactly one caller per pending intro: the guild must
+    /// be pending and no send may be in flight. Call `finish_pending_intro`
+    /// once the send completes.
+    pub fn take_pending_intro(&mut self, guild_id: GuildId, now: Instant) -> bool {
+        if let Some(started) = self.intros_in_flight.get(&guild_id)
+            && now.duration_since(*started) < INTRO_IN_FLIGHT_TIMEOUT
+        {
+            return false;
+        }
+        if !self.pending_intros.remove(&guild_id) {
+            return false;
+        }
+        self.intros_in_flight.insert(guild_id, now);
+        true
+    }
+
+    /// Clear the in-flight marker set by `take_pending_intro`
+    pub fn finish_pending_intro(&mut self, guild_id: GuildId) {
+        self.intros_in_flight.remove(&guild_id);
     }
 
     /// Check if intro was already sent to this guild and mark it as sent
@@ -58,4 +166,71 @@ impl AppState {
     pub fn try_mark_intro_sent(&mut self, guild_id: GuildId) -> bool {
         self.intro_sent_guilds.insert(guild_id)
     }
+
+    /// Check if this interaction was already processed and mark it as processed
+    /// Returns true if this is the first delivery (should dispatch)
+    /// Returns false if it is a redelivery (skip)
+    pub fn try_mark_interaction_seen(&mut self, interaction_id: InteractionId) -> bool {
+        self.seen_interactions
+            .try_mark(interaction_id, std::time::Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const GUILD: GuildId = GuildId::new(1);
+
+    /// The first-command path of the interaction handler: claim the pending
+    /// intro, send it outside the lock, then clear the in-flight marker
+    async fn first_command(state: Arc<RwLock<AppState>>, sends: Arc<AtomicUsize>) {
+        let claimed = state
+            .write()
+            .await
+            .take_pending_intro(GUILD, Instant::now());
+        if claimed {
+            sends.fetch_add(1, Ordering::SeqCst);
+            // Let the other commands run while the send is in flight
+            tokio::task::yield_now().await;
+            state.write().await.finish_pending_intro(GUILD);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_first_commands_send_one_intro() {
+        let state = Arc::new(RwLock::new(
+            AppState::for_test(connect_in_memory().await).await,
+        ));
+        state.write().await.add_pending_intro(GUILD);
+        let sends = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..20)
+            .map(|_| tokio::spawn(first_command(state.clone(), sends.clone())))
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        assert_eq!(sends.load(Ordering::SeqCst), 1);
+        assert!(state.read().await.intros_in_flight.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_intro_blocks_a_new_claim_until_finished() {
+        let mut state = AppState::for_test(connect_in_memory().await).await;
+        let now = Instant::now();
+        state.add_pending_intro(GUILD);
+        assert!(state.take_pending_intro(GUILD, now));
+
+        // A failed join-time send queues the intro again mid-send
+        state.add_pending_intro(GUILD);
+        assert!(!state.take_pending_intro(GUILD, now));
+        assert!(state.take_pending_intro(GUILD, now + INTRO_IN_FLIGHT_TIMEOUT));
+
+        state.finish_pending_intro(GUILD);
+        assert!(!state.take_pending_intro(GUILD, now));
+    }
 }
@@ -0,0 +1,226 @@
+//! Build and deployment information
+//!
+//! Assembles the startup banner and the migration status shown in
+//! `/admin show`, so operators can tell which build and schema are running.
+
+use std::path::PathBuf;
+
+use migration::{Migrator, MigratorTrait};
+use sea_orm::{DatabaseConnection, DbErr};
+
+/// Crate version from Cargo.toml
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Git commit hash embedded by build.rs ("unknown" if unavailable)
+pub const GIT_HASH: &str = env!("VRCPULSE_GIT_HASH");
+
+/// Applied vs available schema migrations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub applied: usize,
+    pub available: usize,
+    /// Name of the most recently applied migration
+    pub latest: Option<String>,
+}
+
+impl MigrationStatus {
+    /// Whether migrations exist that have not been applied yet
+    pub fn is_pending(&self) -> bool {
+        self.applied < self.available
+    }
+}
+
+/// Query the `seaql_migrations` table through the migrator
+pub async fn migration_status(db: &DatabaseConnection) -> Result<MigrationStatus, DbErr> {
+    let applied = Migrator::get_applied_migrations(db).await?;
+    Ok(MigrationStatus {
+        applied: applied.len(),
+        available: Migrator::migrations().len(),
+        latest: applied.last().map(|m| m.name().to_string()),
+    })
+}
+
+/// Inputs for the startup banner
+pub struct StartupInfo<'a> {
+    pub version: &'a str,
+    pub git_hash: &'a str,
+    /// `None` if the migration table could not be read
+    pub migrations: Option<&'a MigrationStatus>,
+    pub database_path: Option<&'a str>,
+    pub database_size: Option<u64>,
+    pub locales: &'a [&'a str],
+}
+
+/// Build the multi-line startup banner
+pub fn banner(info: &StartupInfo<'_>) -> String {
+    let migrations = match info.migrations {
+        Some(status) => {
+            let pending = if status.is_pending() {
+                " (PENDING)"
+            } else {
+                ""
+            };
+            format!(
+                "{}/{} applied{}, latest {}",
+                status.applied,
+                status.available,
+                pending,
+                status.latest.as_deref().unwrap_or("none")
+            )
+        }
+        None => "unknown".to_string(),
+    };
+
+    let database = match (info.database_path, info.database_size) {
+        (Some(path), Some(size)) => format!("{} ({})", path, format_bytes(size)),
+        (Some(path), None) => format!("{} (size unknown)", path),
+        (None, _) => "unknown".to_string(),
+    };
+
+    format!(
+        "VRCPulse {} ({})\n  migrations: {}\n  database:   {}\n  locales:    {}",
+        info.version,
+        info.git_hash,
+        migrations,
+        database,
+        info.locales.join(", ")
+    )
+}
+
+/// File path of a SQLite connection URL, if it refers to a file
+///
+/// Accepts `sqlite://path`, `sqlite:path`, and strips query parameters.
+/// In-memory databases return `None`.
+pub fn sqlite_path(database_url: &str) -> Option<PathBuf> {
+    let rest = database_url.strip_prefix("sqlite:")?;
+    let rest = rest.strip_prefix("//").unwrap_or(rest);
+    let path = rest.split('?').next().unwrap_or(rest);
+
+    if path.is_empty() || path == ":memory:" {
+        return None;
+    }
+    Some(PathBuf::from(path))
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+    const GIB: f64 = MIB * 1024.0;
+
+    let b = bytes as f64;
+    if b >= GIB {
+        format!("{:.1} GiB", b / GIB)
+    } else if b >= MIB {
+        format!("{:.1} MiB", b / MIB)
+    } else if b >= KIB {
+        format!("{:.1} KiB", b / KIB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connect_in_memory;
+
+    fn status(applied: usize, available: usize) -> MigrationStatus {
+        MigrationStatus {
+            applied,
+            available,
+            latest: Some("m20260209_001_split_incident_alert_types".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_banner_with_fixed_inputs() {
+        let migrations = status(31, 31);
+        let banner = banner(&StartupInfo {
+            version: "1.2.3",
+            git_hash: "0123456789ab",
+            migrations: Some(&migrations),
+            database_path: Some("data/vrcpulse.db"),
+            database_size: Some(3 * 1024 * 1024 + 512 * 1024),
+            locales: &["en", "ja", "ko"],
+        });
+
+        assert_eq!(
+            banner,
+            "VRCPulse 1.2.3 (0123456789ab)\n  \
+             migrations: 31/31 applied, latest m20260209_001_split_incident_alert_types\n  \
+             database:   data/vrcpulse.db (3.5 MiB)\n  \
+             locales:    en, ja, ko"
+        );
+    }
+
+    #[test]
+    fn test_banner_marks_pending_and_unknowns() {
+        let migrations = MigrationStatus {
+            latest: None,
+            ..status(0, 31)
+        };
+        let banner = banner(&StartupInfo {
+            version: "1.2.3",
+            git_hash: "unknown",
+            migrations: Some(&migrations),
+            database_path: Some("bot.db"),
+            database_size: None,
+            locales: &["en"],
+        });
+
+        assert!(banner.starts_with("VRCPulse 1.2.3 (unknown)\n"));
+        assert!(banner.contains("migrations: 0/31 applied (PENDING), latest none"));
+        assert!(banner.contains("database:   bot.db (size unknown)"));
+    }
+
+    #[test]
+    fn test_banner_without_migration_table_or_path() {
+        let banner = banner(&StartupInfo {
+            version: "1.2.3",
+            git_hash: "0123456789ab-dirty",
+            migrations: None,
+            database_path: None,
+            database_size: Some(10),
+            locales: &[],
+        });
+
+        assert!(banner.contains("(0123456789ab-dirty)"));
+        assert!(banner.contains("migrations: unknown\n"));
+        assert!(banner.contains("database:   unknown\n"));
+    }
+
+    #[test]
+    fn test_sqlite_path() {
+        let cases = [
+            ("sqlite://data/bot.db?mode=rwc", Some("data/bot.db")),
+            ("sqlite:bot.db", Some("bot.db")),
+            ("sqlite:///abs/bot.db", Some("/abs/bot.db")),
+            ("sqlite::memory:", None),
+            ("sqlite://", None),
+            ("postgres://localhost/bot", None),
+        ];
+        for (url, expected) in cases {
+            assert_eq!(sqlite_path(url), expected.map(PathBuf::from), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_format_bytes_units() {
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_bytes(2 * 1024 * 1024 * 1024), "2.0 GiB");
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_after_all_migrations() {
+        let db = connect_in_memory().await;
+
+        let status = migration_status(&db).await.unwrap();
+
+        assert_eq!(status.applied, Migrator::migrations().len());
+        assert!(!status.is_pending());
+        let newest = Migrator::migrations().last().map(|m| m.name().to_string());
+        assert_eq!(status.latest, newest);
+    }
+}
@@ -1,19 +1,37 @@
 //! Dashboard generation
 //!
-//! Generates a 6-chart dashboard PNG image.
+//! Generates a 6-chart dashboard PNG image (five status page metrics and the
+//! hourly user report volume), plus standalone single-metric, multi-series,
+//! and marked timeline charts.
+//!
+//! Metrics without data render a "no data" placeholder panel instead of a
+//! blank grid cell, and are listed in `DashboardStats::empty_charts`.
 
+use chrono::{DateTime, Duration, Utc};
 use plotters::backend::BitMapBackend;
-use plotters::chart::ChartBuilder;
-use plotters::drawing::IntoDrawingArea;
+use plotters::chart::{ChartBuilder, SeriesLabelPosition};
+use plotters::coord::Shift;
+use plotters::drawing::{DrawingArea, IntoDrawingArea};
+use plotters::element::{Circle, PathElement, Rectangle, Text};
 use plotters::series::{AreaSeries, LineSeries};
+use plotters::style::text_anchor::{HPos, Pos, VPos};
 use plotters::style::{Color, IntoFont, RGBColor};
+use rust_i18n::t;
 use sea_orm::DatabaseConnection;
 
-use crate::visualization::query::{MetricData, load_metric_as_percent, load_metric_downsampled};
+use crate::cache::{self, MetricCache};
+use crate::repository::RecordRepository;
+use crate::visualization::metrics::{
+    API_ERROR_RATE, API_LATENCY, META_AUTH, ONLINE_USERS, STEAM_AUTH,
+};
+use crate::visualization::query::{MetricData, load_report_counts};
 use crate::visualization::theme::*;
 
+/// Title of the user report volume chart
+pub const USER_REPORTS_TITLE: &str = "User Reports";
+
 /// Y-axis format for charts
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub enum YAxisFormat {
     /// Count format: 0, 50k, 100k
     Count,
@@ -31,29 +49,58 @@ pub struct DashboardStats {
     pub online_users_avg: f64,
     pub online_users_max: f64,
     pub api_error_rate_avg: f64,
+    /// 95th percentile of the downsampled API latency, in milliseconds
+    pub api_latency_p95: f64,
     pub steam_success_avg: f64,
     pub meta_success_avg: f64,
+    /// Distinct users who reported in the last 24 hours, whatever the range
+    pub reports_24h: u64,
+    /// Titles of charts drawn as "no data" placeholders
+    pub empty_charts: Vec<&'static str>,
 }
 
 /// Generate dashboard PNG and return bytes with stats
+///
+/// Every chart and statistic covers the last `range`. Series are read
+/// through `cache`. `locale` is used for the placeholder text of charts
+/// without data.
 pub async fn generate_dashboard(
     db: &DatabaseConnection,
+    cache: &MetricCache,
+    range: Duration,
+    locale: &str,
 ) -> Result<(Vec<u8>, DashboardStats), Box<dyn std::error::Error + Send + Sync>> {
-    // Load all 6 metrics
-    let online_users = load_metric_downsampled(db, "visits").await?;
-    let api_latency = load_metric_downsampled(db, "api_latency").await?;
-    let api_requests = load_metric_downsampled(db, "api_requests").await?;
-    let api_error_rate = load_metric_as_percent(db, "api_errors").await?;
-    let steam_success = load_metric_as_percent(db, "extauth_steam").await?;
-    let meta_success = load_metric_as_percent(db, "extauth_oculus").await?;
+    // Load the 5 metrics and the report volume
+    let ttl = cache::load_ttl(db).await;
+    let online_users = ONLINE_USERS.load_cached(db, cache, ttl, range).await?;
+    let api_latency = API_LATENCY.load_cached(db, cache, ttl, range).await?;
+    let user_reports = load_report_counts(db, range).await?;
+    let api_error_rate = API_ERROR_RATE.load_cached(db, cache, ttl, range).await?;
+    let steam_success = STEAM_AUTH.load_cached(db, cache, ttl, range).await?;
+    let meta_success = META_AUTH.load_cached(db, cache, ttl, range).await?;
+
+    // Chart titles and data, in grid order
+    let charts: [(&'static str, &MetricData); 6] = [
+        (ONLINE_USERS.title, &online_users),
+        (API_LATENCY.title, &api_latency),
+        (USER_REPORTS_TITLE, &user_reports),
+        (API_ERROR_RATE.title, &api_error_rate),
+        (STEAM_AUTH.title, &steam_success),
+        (META_AUTH.title, &meta_success),
+    ];
 
     // Calculate stats
     let stats = DashboardStats {
         online_users_avg: online_users.avg(),
         online_users_max: online_users.max(),
         api_error_rate_avg: api_error_rate.avg(),
+        api_latency_p95: api_latency.percentile(95.0),
         steam_success_avg: steam_success.avg(),
         meta_success_avg: meta_success.avg(),
+        reports_24h: RecordRepository::new(db)
+            .count_distinct_reporters(None, Utc::now() - Duration::hours(24))
+            .await?,
+        empty_charts: empty_chart_titles(&charts),
     };
 
     // Generate PNG in memory
@@ -70,49 +117,61 @@ pub async fn generate_dashboard(
         // Row 1: Online Users, API Latency
         draw_chart(
             &areas[0],
-            "Online Users",
-            &online_users,
-            GRAPH_COLOR,
+            charts[0].0,
+            charts[0].1,
+            ONLINE_USERS.color,
             YAxisFormat::Count,
+            &DASHBOARD_STYLE,
+            locale,
         )?;
         draw_chart(
             &areas[1],
-            "API Latency",
-            &api_latency,
-            GRAPH_COLOR,
+            charts[1].0,
+            charts[1].1,
+            API_LATENCY.color,
             YAxisFormat::Hidden,
+            &DASHBOARD_STYLE,
+            locale,
         )?;
 
-        // Row 2: API Requests, API Error Rate
+        // Row 2: User Reports, API Error Rate
         draw_chart(
             &areas[2],
-            "API Requests",
-            &api_requests,
-            GRAPH_COLOR,
-            YAxisFormat::Hidden,
+            charts[2].0,
+            charts[2].1,
+            YELLOW,
+            YAxisFormat::Count,
+            &DASHBOARD_STYLE,
+            locale,
         )?;
         draw_chart(
             &areas[3],
-            "API Error Rate",
-            &api_error_rate,
-            RED,
+            charts[3].0,
+            charts[3].1,
+            API_ERROR_RATE.color,
             YAxisFormat::PercentAuto,
+            &DASHBOARD_STYLE,
+            locale,
         )?;
 
         // Row 3: Steam Auth Success Rate, Meta Auth Success Rate
         draw_chart(
             &areas[4],
-            "Steam Auth Success Rate",
-            &steam_success,
-            GREEN,
+            charts[4].0,
+            charts[4].1,
+            STEAM_AUTH.color,
             YAxisFormat::Percent,
+            &DASHBOARD_STYLE,
+            locale,
         )?;
         draw_chart(
             &areas[5],
-            "Meta Auth Success Rate",
-            &meta_success,
-            GREEN,
+            charts[5].0,
+            charts[5].1,
+            META_AUTH.color,
             YAxisFormat::Percent,
+            &DASHBOARD_STYLE,
+            locale,
         )?;
 
         root.present()?;
@@ -124,16 +183,343 @@ pub async fn generate_dashboard(
     Ok((png_bytes, stats))
 }
 
-/// Draw a single chart
-fn draw_chart(
-    area: &plotters::drawing::DrawingArea<BitMapBackend, plotters::coord::Shift>,
+/// Render one metric as a standalone 600x300 PNG
+pub fn generate_single_chart(
     title: &str,
     data: &MetricData,
     color: RGBColor,
     y_format: YAxisFormat,
+    locale: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut buffer = vec![0u8; (SINGLE_CHART_WIDTH * SINGLE_CHART_HEIGHT * 3) as usize];
+
+    {
+        let root =
+            BitMapBackend::with_buffer(&mut buffer, (SINGLE_CHART_WIDTH, SINGLE_CHART_HEIGHT))
+                .into_drawing_area();
+        root.fill(&BG_COLOR)?;
+        let area = root.margin(8, 8, 8, 8);
+        draw_chart(
+            &area,
+            title,
+            data,
+            color,
+            y_format,
+            &SINGLE_CHART_STYLE,
+            locale,
+        )?;
+        root.present()?;
+    }
+
+    encode_png(&buffer, SINGLE_CHART_WIDTH, SINGLE_CHART_HEIGHT)
+}
+
+/// One named line in a multi-series chart
+#[derive(Debug, Clone)]
+pub struct NamedSeries {
+    /// Legend label
+    pub name: String,
+    pub color: RGBColor,
+    /// One value per shared timestamp
+    pub values: Vec<f64>,
+}
+
+/// Render daily series sharing one x-axis as a standalone 600x300 PNG
+///
+/// Each series is drawn as a line and listed in a legend. `days` are the
+/// bucket start times, labeled by date. Renders the "no data" placeholder
+/// when there are no days or no series.
+pub fn generate_multi_series_chart(
+    title: &str,
+    days: &[DateTime<Utc>],
+    series: &[NamedSeries],
+    locale: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut buffer = vec![0u8; (SINGLE_CHART_WIDTH * SINGLE_CHART_HEIGHT * 3) as usize];
+
+    {
+        let root =
+            BitMapBackend::with_buffer(&mut buffer, (SINGLE_CHART_WIDTH, SINGLE_CHART_HEIGHT))
+                .into_drawing_area();
+        root.fill(&BG_COLOR)?;
+        let area = root.margin(8, 8, 8, 8);
+        draw_multi_series(&area, title, days, series, &SINGLE_CHART_STYLE, locale)?;
+        root.present()?;
+    }
+
+    encode_png(&buffer, SINGLE_CHART_WIDTH, SINGLE_CHART_HEIGHT)
+}
+
+/// Draw lines for several series with a legend, or a placeholder panel
+fn draw_multi_series(
+    area: &DrawingArea<BitMapBackend, Shift>,
+    title: &str,
+    days: &[DateTime<Utc>],
+    series: &[NamedSeries],
+    style: &ChartStyle,
+    locale: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    if data.is_empty() {
-        return Ok(());
+    let points = series
+        .iter()
+        .map(|s| s.values.len())
+        .min()
+        .unwrap_or(0)
+        .min(days.len());
+    if points == 0 {
+        return draw_placeholder(area, title, style, locale);
+    }
+
+    let max_val = series
+        .iter()
+        .flat_map(|s| s.values[..points].iter().copied())
+        .fold(0.0, f64::max);
+    // Headroom above the highest line keeps the legend from covering it
+    let y_max = if max_val == 0.0 { 1.0 } else { max_val * 1.3 };
+
+    let layout = XLayout::new(points);
+    let date_format = date_label_format(locale);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(
+            title,
+            ("sans-serif", style.title_font_size)
+                .into_font()
+                .color(&TEXT_COLOR),
+        )
+        .margin(style.margin)
+        .x_label_area_size(style.x_label_area)
+        .y_label_area_size(style.y_label_area)
+        .build_cartesian_2d(0..layout.x_end, 0.0..y_max)?;
+
+    chart
+        .configure_mesh()
+        .x_labels(6)
+        .y_labels(5)
+        .x_label_formatter(&|x| {
+            layout
+                .index(*x)
+                .map(|i| days[i].format(date_format).to_string())
+                .unwrap_or_default()
+        })
+        .y_label_formatter(&|y| format!("{:.0}", y))
+        .x_label_style(
+            ("sans-serif", style.label_font_size)
+                .into_font()
+                .color(&MUTED_COLOR),
+        )
+        .y_label_style(
+            ("sans-serif", style.label_font_size)
+                .into_font()
+                .color(&MUTED_COLOR),
+        )
+        .axis_style(MUTED_COLOR)
+        .bold_line_style(MUTED_COLOR.mix(0.2))
+        .light_line_style(MUTED_COLOR.mix(0.1))
+        .draw()?;
+
+    for s in series {
+        let color = s.color;
+        let line = s.values[..points]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (layout.x(i), *v));
+
+        // A single point has no line, so mark it instead
+        let drawn = if points == 1 {
+            chart.draw_series(
+                line.map(|point| Circle::new(point, style.line_width * 2, color.filled())),
+            )?
+        } else {
+            chart.draw_series(LineSeries::new(line, color.stroke_width(style.line_width)))?
+        };
+
+        let swatch = style.label_font_size as i32;
+        drawn.label(s.name.as_str()).legend(move |(x, y)| {
+            Rectangle::new([(x, y - 2), (x + swatch, y + 2)], color.filled())
+        });
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .label_font(
+            ("sans-serif", style.label_font_size)
+                .into_font()
+                .color(&TEXT_COLOR),
+        )
+        .background_style(BG_COLOR.mix(0.8))
+        .border_style(MUTED_COLOR.mix(0.4))
+        .draw()?;
+
+    Ok(())
+}
+
+/// Vertical line marking a point in time on a timeline chart
+#[derive(Debug, Clone)]
+pub struct ChartMarker {
+    pub at: DateTime<Utc>,
+    /// Short text drawn next to the top of the line
+    pub label: String,
+    pub color: RGBColor,
+}
+
+/// Render a count series with vertical time markers as a standalone 600x300 PNG
+///
+/// The x-axis is proportional to time, so markers land at their exact
+/// minute rather than snapping to the nearest data point. Markers outside
+/// the series range are skipped. Renders the "no data" placeholder when the
+/// series is empty.
+pub fn generate_marked_chart(
+    title: &str,
+    data: &MetricData,
+    color: RGBColor,
+    markers: &[ChartMarker],
+    locale: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut buffer = vec![0u8; (SINGLE_CHART_WIDTH * SINGLE_CHART_HEIGHT * 3) as usize];
+
+    {
+        let root =
+            BitMapBackend::with_buffer(&mut buffer, (SINGLE_CHART_WIDTH, SINGLE_CHART_HEIGHT))
+                .into_drawing_area();
+        root.fill(&BG_COLOR)?;
+        let area = root.margin(8, 8, 8, 8);
+        draw_marked_chart(
+            &area,
+            title,
+            data,
+            color,
+            markers,
+            &SINGLE_CHART_STYLE,
+            locale,
+        )?;
+        root.present()?;
+    }
+
+    encode_png(&buffer, SINGLE_CHART_WIDTH, SINGLE_CHART_HEIGHT)
+}
+
+/// Draw a count line on a time-proportional x-axis with vertical markers
+fn draw_marked_chart(
+    area: &DrawingArea<BitMapBackend, Shift>,
+    title: &str,
+    data: &MetricData,
+    color: RGBColor,
+    markers: &[ChartMarker],
+    style: &ChartStyle,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let points = data.values.len().min(data.timestamps.len());
+    if points == 0 {
+        return draw_placeholder(area, title, style, locale);
+    }
+
+    // X coordinates are minutes since the first timestamp
+    let origin = data.timestamps[0];
+    let minutes = |at: DateTime<Utc>| (at - origin).num_seconds() as f64 / 60.0;
+    let x_end = minutes(data.timestamps[points - 1]).max(1.0);
+
+    let max_val = data.values[..points].iter().copied().fold(0.0, f64::max);
+    // Headroom above the line keeps marker labels readable
+    let y_max = if max_val == 0.0 { 1.0 } else { max_val * 1.4 };
+
+    let time_format = axis_label_format(locale, &data.timestamps[..points]);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(
+            title,
+            ("sans-serif", style.title_font_size)
+                .into_font()
+                .color(&TEXT_COLOR),
+        )
+        .margin(style.margin)
+        .x_label_area_size(style.x_label_area)
+        .y_label_area_size(style.y_label_area)
+        .build_cartesian_2d(0.0..x_end, 0.0..y_max)?;
+
+    chart
+        .configure_mesh()
+        .x_labels(6)
+        .y_labels(5)
+        .x_label_formatter(&|x| {
+            (origin + chrono::Duration::seconds((*x * 60.0) as i64))
+                .format(time_format)
+                .to_string()
+        })
+        .y_label_formatter(&|y| format!("{:.0}", y))
+        .x_label_style(
+            ("sans-serif", style.label_font_size)
+                .into_font()
+                .color(&MUTED_COLOR),
+        )
+        .y_label_style(
+            ("sans-serif", style.label_font_size)
+                .into_font()
+                .color(&MUTED_COLOR),
+        )
+        .axis_style(MUTED_COLOR)
+        .bold_line_style(MUTED_COLOR.mix(0.2))
+        .light_line_style(MUTED_COLOR.mix(0.1))
+        .draw()?;
+
+    let line = data.timestamps[..points]
+        .iter()
+        .zip(&data.values[..points])
+        .map(|(at, v)| (minutes(*at), *v));
+    if points == 1 {
+        chart.draw_series(
+            line.map(|point| Circle::new(point, style.line_width * 2, color.filled())),
+        )?;
+    } else {
+        chart.draw_series(LineSeries::new(line, color.stroke_width(style.line_width)))?;
+    }
+
+    // Labels step down so markers close together don't overlap completely
+    let visible = markers
+        .iter()
+        .map(|marker| (minutes(marker.at), marker))
+        .filter(|(x, _)| (0.0..=x_end).contains(x));
+    for (i, (x, marker)) in visible.enumerate() {
+        chart.draw_series(std::iter::once(PathElement::new(
+            vec![(x, 0.0), (x, y_max)],
+            marker.color.stroke_width(style.line_width),
+        )))?;
+
+        let label_y = y_max * (0.97 - 0.08 * (i % 4) as f64);
+        chart.draw_series(std::iter::once(Text::new(
+            marker.label.clone(),
+            (x, label_y),
+            ("sans-serif", style.label_font_size)
+                .into_font()
+                .color(&marker.color),
+        )))?;
+    }
+
+    Ok(())
+}
+
+/// Titles of charts whose data is empty, in grid order
+fn empty_chart_titles(charts: &[(&'static str, &MetricData)]) -> Vec<&'static str> {
+    charts
+        .iter()
+        .filter(|(_, data)| data.is_empty())
+        .map(|(title, _)| *title)
+        .collect()
+}
+
+/// Draw a single chart, or a placeholder panel if there is no data
+pub(super) fn draw_chart(
+    area: &DrawingArea<BitMapBackend, Shift>,
+    title: &str,
+    data: &MetricData,
+    color: RGBColor,
+    y_format: YAxisFormat,
+    style: &ChartStyle,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let points = data.values.len().min(data.timestamps.len());
+    if points == 0 {
+        return draw_placeholder(area, title, style, locale);
     }
 
     let max_val = data.max();
@@ -145,35 +531,30 @@ fn draw_chart(
             }
         };
 
-    let start_time = data.timestamps.first().unwrap();
-    let end_time = data.timestamps.last().unwrap();
+    let layout = XLayout::new(points);
+    let time_format = axis_label_format(locale, &data.timestamps[..points]);
 
     let mut chart = ChartBuilder::on(area)
         .caption(
             title,
-            ("sans-serif", TITLE_FONT_SIZE)
+            ("sans-serif", style.title_font_size)
                 .into_font()
                 .color(&TEXT_COLOR),
         )
-        .margin(20)
-        .x_label_area_size(70)
-        .y_label_area_size(120)
-        .build_cartesian_2d(0..data.values.len(), 0.0..y_max)?;
+        .margin(style.margin)
+        .x_label_area_size(style.x_label_area)
+        .y_label_area_size(style.y_label_area)
+        .build_cartesian_2d(0..layout.x_end, 0.0..y_max)?;
 
     chart
         .configure_mesh()
-        .x_labels(5)
+        .x_labels(style.x_labels)
         .y_labels(5)
         .x_label_formatter(&|x| {
-            if *x == 0 {
-                start_time.format("%H:%M").to_string()
-            } else if *x >= data.values.len() - 1 {
-                end_time.format("%H:%M").to_string()
-            } else if *x < data.timestamps.len() {
-                data.timestamps[*x].format("%H:%M").to_string()
-            } else {
-                String::new()
-            }
+            layout
+                .index(*x)
+                .map(|i| data.timestamps[i].format(time_format).to_string())
+                .unwrap_or_default()
         })
         .y_label_formatter(&move |y| match y_format {
             YAxisFormat::Count => {
@@ -196,12 +577,12 @@ fn draw_chart(
             YAxisFormat::Hidden => String::new(),
         })
         .x_label_style(
-            ("sans-serif", LABEL_FONT_SIZE)
+            ("sans-serif", style.label_font_size)
                 .into_font()
                 .color(&MUTED_COLOR),
         )
         .y_label_style(
-            ("sans-serif", LABEL_FONT_SIZE)
+            ("sans-serif", style.label_font_size)
                 .into_font()
                 .color(&MUTED_COLOR),
         )
@@ -210,24 +591,162 @@ fn draw_chart(
         .light_line_style(MUTED_COLOR.mix(0.1))
         .draw()?;
 
+    let series = || {
+        data.values[..points]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (layout.x(i), *v))
+    };
+
+    // A single point has no area or line, so mark it instead
+    if points == 1 {
+        chart.draw_series(
+            series().map(|point| Circle::new(point, style.line_width * 2, color.filled())),
+        )?;
+        return Ok(());
+    }
+
     // Draw area
-    chart.draw_series(AreaSeries::new(
-        data.values.iter().enumerate().map(|(i, v)| (i, *v)),
-        0.0,
-        color.mix(0.3),
-    ))?;
+    chart.draw_series(AreaSeries::new(series(), 0.0, color.mix(0.3)))?;
 
     // Draw line
     chart.draw_series(LineSeries::new(
-        data.values.iter().enumerate().map(|(i, v)| (i, *v)),
-        color.stroke_width(4),
+        series(),
+        color.stroke_width(style.line_width),
     ))?;
 
     Ok(())
 }
 
+/// Mapping between data indices and x coordinates
+///
+/// A single point is padded to the middle of a `0..2` range so it gets one
+/// centered label instead of a degenerate `0..1` axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct XLayout {
+    points: usize,
+    offset: usize,
+    /// Exclusive end of the x range
+    x_end: usize,
+}
+
+impl XLayout {
+    fn new(points: usize) -> Self {
+        if points == 1 {
+            Self {
+                points,
+                offset: 1,
+                x_end: 2,
+            }
+        } else {
+            Self {
+                points,
+                offset: 0,
+                x_end: points.max(1),
+            }
+        }
+    }
+
+    /// X coordinate of a data index
+    fn x(&self, index: usize) -> usize {
+        index + self.offset
+    }
+
+    /// Data index at an x coordinate, if a point is drawn there
+    fn index(&self, x: usize) -> Option<usize> {
+        x.checked_sub(self.offset).filter(|i| *i < self.points)
+    }
+}
+
+/// Longest series span (days) whose axis labels still include times
+const DATE_ONLY_SPAN_DAYS: i64 = 7;
+
+/// Whether a series covers more than one calendar day (UTC)
+fn spans_days(timestamps: &[DateTime<Utc>]) -> bool {
+    let mut dates = timestamps.iter().map(|ts| ts.date_naive());
+    match dates.next() {
+        Some(first) => dates.any(|date| date != first),
+        None => false,
+    }
+}
+
+/// X-axis label format for a locale
+///
+/// Times stay numeric; ranges crossing midnight add the date in the locale's
+/// usual order.
+fn time_label_format(locale: &str, spans_days: bool) -> &'static str {
+    match (locale, spans_days) {
+        (_, false) => "%H:%M",
+        ("ko", true) => "%m.%d %H:%M",
+        (_, true) => "%m/%d %H:%M",
+    }
+}
+
+/// X-axis label format for a series' timestamps
+///
+/// Series longer than [`DATE_ONLY_SPAN_DAYS`] show dates only, since times
+/// would repeat across too many days to read.
+fn axis_label_format(locale: &str, timestamps: &[DateTime<Utc>]) -> &'static str {
+    let span = match (timestamps.first(), timestamps.last()) {
+        (Some(first), Some(last)) => *last - *first,
+        _ => Duration::zero(),
+    };
+    if span > Duration::days(DATE_ONLY_SPAN_DAYS) {
+        date_label_format(locale)
+    } else {
+        time_label_format(locale, spans_days(timestamps))
+    }
+}
+
+/// X-axis label format for daily buckets in a locale
+fn date_label_format(locale: &str) -> &'static str {
+    match locale {
+        "ko" => "%m.%d",
+        _ => "%m/%d",
+    }
+}
+
+/// Draw the chart title over a bordered panel with centered "no data" text
+fn draw_placeholder(
+    area: &DrawingArea<BitMapBackend, Shift>,
+    title: &str,
+    style: &ChartStyle,
+    locale: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let margin = style.margin;
+    let panel = area.margin(margin, margin, margin, margin).titled(
+        title,
+        ("sans-serif", style.title_font_size)
+            .into_font()
+            .color(&TEXT_COLOR),
+    )?;
+
+    let (width, height) = panel.dim_in_pixel();
+    panel.draw(&Rectangle::new(
+        [(0, 0), (width as i32 - 1, height as i32 - 1)],
+        MUTED_COLOR.mix(0.3).stroke_width(2),
+    ))?;
+
+    let message = t!("embeds.dashboard.chart_no_data", locale = locale);
+    panel.draw(&Text::new(
+        message.as_ref(),
+        panel_center(width, height),
+        ("sans-serif", style.label_font_size * 3 / 2)
+            .into_font()
+            .color(&MUTED_COLOR)
+            .pos(Pos::new(HPos::Center, VPos::Center)),
+    ))?;
+
+    Ok(())
+}
+
+/// Pixel center of a panel, in the panel's own coordinates
+fn panel_center(width: u32, height: u32) -> (i32, i32) {
+    ((width / 2) as i32, (height / 2) as i32)
+}
+
 /// Encode raw RGB buffer to PNG
-fn encode_png(
+pub(super) fn encode_png(
     buffer: &[u8],
     width: u32,
     height: u32,
@@ -244,3 +763,461 @@ fn encode_png(
     }
     Ok(png_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use proptest::prelude::*;
+
+    const WIDTH: u32 = SINGLE_CHART_WIDTH;
+    const HEIGHT: u32 = SINGLE_CHART_HEIGHT;
+
+    fn metric(values: &[f64]) -> MetricData {
+        MetricData {
+            timestamps: vec![Utc::now(); values.len()],
+            values: values.to_vec(),
+            unit: String::new(),
+        }
+    }
+
+    /// Raw RGB pixels of a 600x300 "no data" placeholder
+    fn render_placeholder(locale: &str) -> Vec<u8> {
+        render_placeholder_with("API Error Rate", locale)
+    }
+
+    fn render_placeholder_with(title: &str, locale: &str) -> Vec<u8> {
+        let mut buffer = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+            root.fill(&BG_COLOR).unwrap();
+            let area = root.margin(8, 8, 8, 8);
+            draw_placeholder(&area, title, &SINGLE_CHART_STYLE, locale).unwrap();
+            root.present().unwrap();
+        }
+        buffer
+    }
+
+    /// Raw RGB pixels of a 600x300 red percentage chart
+    fn render_chart(data: &MetricData, locale: &str) -> Vec<u8> {
+        let mut buffer = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+            root.fill(&BG_COLOR).unwrap();
+            let area = root.margin(8, 8, 8, 8);
+            draw_chart(
+                &area,
+                "API Error Rate",
+                data,
+                RED,
+                YAxisFormat::Percent,
+                &SINGLE_CHART_STYLE,
+                locale,
+            )
+            .unwrap();
+            root.present().unwrap();
+        }
+        buffer
+    }
+
+    /// Raw RGB pixels of a 600x300 multi-series chart over `days` days
+    fn render_multi(days: usize, series: &[NamedSeries]) -> Vec<u8> {
+        let start = Utc.with_ymd_and_hms(2026, 1, 20, 0, 0, 0).unwrap();
+        let days: Vec<_> = (0..days as i64)
+            .map(|i| start + Duration::days(i))
+            .collect();
+        let mut buffer = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+            root.fill(&BG_COLOR).unwrap();
+            let area = root.margin(8, 8, 8, 8);
+            draw_multi_series(&area, "Alerts", &days, series, &SINGLE_CHART_STYLE, "en").unwrap();
+            root.present().unwrap();
+        }
+        buffer
+    }
+
+    fn named(name: &str, color: RGBColor, values: Vec<f64>) -> NamedSeries {
+        NamedSeries {
+            name: name.to_string(),
+            color,
+            values,
+        }
+    }
+
+    /// Bounding box of pixels drawn in exactly `color`
+    fn color_bounds(buffer: &[u8], color: RGBColor) -> Option<(u32, u32, u32, u32)> {
+        color_bounds_in(buffer, color, 0..WIDTH, 0..HEIGHT)
+    }
+
+    /// Bounding box of pixels drawn in exactly `color` within a region
+    fn color_bounds_in(
+        buffer: &[u8],
+        color: RGBColor,
+        xs: std::ops::Range<u32>,
+        ys: std::ops::Range<u32>,
+    ) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for y in ys {
+            for x in xs.clone() {
+                let i = ((y * WIDTH + x) * 3) as usize;
+                if buffer[i..i + 3] != [color.0, color.1, color.2] {
+                    continue;
+                }
+                bounds = Some(match bounds {
+                    None => (x, y, x, y),
+                    Some((l, t, r, b)) => (l.min(x), t.min(y), r.max(x), b.max(y)),
+                });
+            }
+        }
+        bounds
+    }
+
+    /// Times between 2000 and 2100, in any order
+    fn timestamps(len: usize) -> impl Strategy<Value = Vec<DateTime<Utc>>> {
+        proptest::collection::vec(
+            (946_684_800i64..4_102_444_800)
+                .prop_map(|secs| DateTime::from_timestamp(secs, 0).unwrap()),
+            len,
+        )
+    }
+
+    fn is_background(buffer: &[u8], x: u32, y: u32) -> bool {
+        let i = ((y * WIDTH + x) * 3) as usize;
+        buffer[i..i + 3] == [BG_COLOR.0, BG_COLOR.1, BG_COLOR.2]
+    }
+
+    /// Bounding box `(left, top, right, bottom)` of drawn pixels in a region
+    fn drawn_bounds(
+        buffer: &[u8],
+        xs: std::ops::Range<u32>,
+        ys: std::ops::Range<u32>,
+    ) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for y in ys {
+            for x in xs.clone() {
+                if is_background(buffer, x, y) {
+                    continue;
+                }
+                bounds = Some(match bounds {
+                    None => (x, y, x, y),
+                    Some((l, t, r, b)) => (l.min(x), t.min(y), r.max(x), b.max(y)),
+                });
+            }
+        }
+        bounds
+    }
+
+    #[test]
+    fn test_empty_chart_titles() {
+        let empty = metric(&[]);
+        let full = metric(&[1.0, 2.0]);
+        let charts = [
+            ("Online Users", &full),
+            ("API Latency", &empty),
+            ("API Requests", &full),
+            ("Meta Auth Success Rate", &empty),
+        ];
+
+        assert_eq!(
+            empty_chart_titles(&charts),
+            vec!["API Latency", "Meta Auth Success Rate"]
+        );
+        assert!(empty_chart_titles(&charts[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_panel_center() {
+        assert_eq!(panel_center(600, 300), (300, 150));
+        assert_eq!(panel_center(5, 3), (2, 1));
+        assert_eq!(panel_center(0, 0), (0, 0));
+    }
+
+    #[test]
+    fn test_placeholder_outer_margin_untouched() {
+        let buffer = render_placeholder("en");
+
+        for (x, y) in [
+            (0, 0),
+            (WIDTH - 1, 0),
+            (0, HEIGHT - 1),
+            (WIDTH - 1, HEIGHT - 1),
+        ] {
+            assert!(is_background(&buffer, x, y), "({x}, {y})");
+        }
+        // Nothing is drawn in the 8px root margin plus the style margin
+        let inset = 8 + SINGLE_CHART_STYLE.margin;
+        assert_eq!(drawn_bounds(&buffer, 0..WIDTH, 0..inset - 1), None);
+        assert_eq!(drawn_bounds(&buffer, 0..inset - 1, 0..HEIGHT), None);
+    }
+
+    #[test]
+    fn test_placeholder_draws_title_border_and_message() {
+        let buffer = render_placeholder("en");
+        let inset = 8 + SINGLE_CHART_STYLE.margin;
+
+        // Title along the top
+        let title = drawn_bounds(&buffer, inset..WIDTH - inset, inset..inset + 30);
+        assert!(title.is_some(), "title not drawn");
+
+        // Border down both sides of the panel, below the title
+        let row = HEIGHT * 3 / 4;
+        let left = drawn_bounds(&buffer, inset..inset + 6, row..row + 1);
+        let right = drawn_bounds(&buffer, WIDTH - inset - 6..WIDTH - inset, row..row + 1);
+        assert!(left.is_some(), "left border not drawn");
+        assert!(right.is_some(), "right border not drawn");
+
+        // Inside the border, only the message is drawn
+        let message = drawn_bounds(
+            &buffer,
+            inset + 6..WIDTH - inset - 6,
+            inset + 40..HEIGHT - inset - 6,
+        )
+        .expect("message not drawn");
+        let (l, t, r, b) = message;
+        assert!(r - l > 100, "message too narrow: {message:?}");
+        assert!(b - t < 40, "message too tall: {message:?}");
+    }
+
+    #[test]
+    fn test_placeholder_message_centered() {
+        let buffer = render_placeholder("en");
+        let inset = 8 + SINGLE_CHART_STYLE.margin;
+
+        let (l, t, r, b) = drawn_bounds(
+            &buffer,
+            inset + 6..WIDTH - inset - 6,
+            inset + 40..HEIGHT - inset - 6,
+        )
+        .unwrap();
+        let center_x = (l + r) / 2;
+        let center_y = (t + b) / 2;
+
+        assert!(center_x.abs_diff(WIDTH / 2) <= 4, "x center {center_x}");
+        // The panel starts below the title, so its center is a little lower
+        let panel_top = inset + SINGLE_CHART_STYLE.title_font_size;
+        let panel_center_y = (panel_top + HEIGHT - inset) / 2;
+        assert!(
+            center_y.abs_diff(panel_center_y) <= 12,
+            "y center {center_y}, panel {panel_center_y}"
+        );
+    }
+
+    #[test]
+    fn test_single_chart_without_data_renders() {
+        let png = generate_single_chart(
+            "API Error Rate",
+            &metric(&[]),
+            RED,
+            YAxisFormat::PercentAuto,
+            "en",
+        )
+        .unwrap();
+
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_x_layout_boundaries() {
+        for points in [0, 1, 2, 1000] {
+            let layout = XLayout::new(points);
+            let xs: Vec<usize> = (0..points).map(|i| layout.x(i)).collect();
+
+            assert!(
+                xs.windows(2).all(|w| w[0] < w[1]),
+                "{points}: not monotonic"
+            );
+            assert!(xs.iter().all(|x| *x < layout.x_end.max(1)), "{points}");
+            for (i, x) in xs.iter().enumerate() {
+                assert_eq!(layout.index(*x), Some(i), "{points}: x {x}");
+            }
+            assert_eq!(layout.index(layout.x_end), None, "{points}");
+        }
+    }
+
+    #[test]
+    fn test_x_layout_centers_single_point() {
+        let layout = XLayout::new(1);
+
+        // One label in the middle of a 0..2 axis, none at the edges
+        assert_eq!(layout.x(0), 1);
+        assert_eq!(layout.x_end, 2);
+        assert_eq!(layout.index(0), None);
+        assert_eq!(layout.index(1), Some(0));
+        assert_eq!(layout.index(2), None);
+    }
+
+    #[test]
+    fn test_axis_label_format() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 10, 0, 0).unwrap();
+        let cases = [
+            (vec![], "en", "%H:%M"),
+            (vec![start], "en", "%H:%M"),
+            (vec![start, start + Duration::hours(6)], "ko", "%H:%M"),
+            (
+                vec![start, start + Duration::hours(20)],
+                "en",
+                "%m/%d %H:%M",
+            ),
+            (
+                vec![start, start + Duration::hours(20)],
+                "ko",
+                "%m.%d %H:%M",
+            ),
+            (vec![start, start + Duration::days(7)], "ja", "%m/%d %H:%M"),
+            (vec![start, start + Duration::days(8)], "en", "%m/%d"),
+            (vec![start, start + Duration::days(30)], "ko", "%m.%d"),
+        ];
+        for (timestamps, locale, expected) in cases {
+            assert_eq!(
+                axis_label_format(locale, &timestamps),
+                expected,
+                "{locale}, {} points",
+                timestamps.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_single_point_golden() {
+        let data = MetricData {
+            timestamps: vec![Utc.with_ymd_and_hms(2026, 3, 1, 10, 0, 0).unwrap()],
+            values: vec![50.0],
+            unit: String::new(),
+        };
+
+        let buffer = render_chart(&data, "en");
+
+        // One small marker, centered in the plot area
+        let (l, t, r, b) = color_bounds(&buffer, RED).expect("marker not drawn");
+        let marker = SINGLE_CHART_STYLE.line_width * 2;
+        assert!(r - l <= marker * 2 + 1, "marker width {}", r - l);
+        assert!(b - t <= marker * 2 + 1, "marker height {}", b - t);
+        let inset = 8 + SINGLE_CHART_STYLE.margin;
+        let plot_left = inset + SINGLE_CHART_STYLE.y_label_area;
+        let plot_right = WIDTH - inset;
+        let plot_top = inset + SINGLE_CHART_STYLE.title_font_size;
+        let plot_bottom = HEIGHT - inset - SINGLE_CHART_STYLE.x_label_area;
+        let center_x = (l + r) / 2;
+        let center_y = (t + b) / 2;
+        assert!(
+            center_x.abs_diff((plot_left + plot_right) / 2) <= 6,
+            "x center {center_x}"
+        );
+        assert!(
+            center_y.abs_diff((plot_top + plot_bottom) / 2) <= 12,
+            "y center {center_y}"
+        );
+    }
+
+    #[test]
+    fn test_multi_series_golden() {
+        // Green climbs from 10 to 39; red stays near zero
+        let series = [
+            named("threshold", GREEN, (10..40).map(f64::from).collect()),
+            named("incident_opened", RED, vec![2.0; 30]),
+        ];
+
+        let buffer = render_multi(30, &series);
+
+        let inset = 8 + SINGLE_CHART_STYLE.margin;
+        let plot_left = inset + SINGLE_CHART_STYLE.y_label_area;
+        let plot_top = inset + SINGLE_CHART_STYLE.title_font_size;
+        let plot_bottom = HEIGHT - inset - SINGLE_CHART_STYLE.x_label_area;
+        // Lines: green reaches the upper right, red runs along the bottom
+        let (_, green_top, green_right, _) = color_bounds(&buffer, GREEN).expect("green");
+        assert!(
+            green_right > WIDTH - inset - 40,
+            "green ends at {green_right}"
+        );
+        assert!(green_top < plot_top + 60, "green peaks at {green_top}");
+        let red_line = color_bounds_in(&buffer, RED, plot_left + 150..WIDTH, 0..HEIGHT);
+        let (_, red_top, _, red_bottom) = red_line.expect("red line");
+        assert!(red_top > plot_bottom - 30, "red line at {red_top}");
+        assert!(red_bottom <= plot_bottom, "red line at {red_bottom}");
+        // Legend: both swatches in the upper left, where no line passes
+        let legend_xs = plot_left..plot_left + 150;
+        let legend_ys = plot_top..plot_top + 60;
+        for (name, color) in [("green", GREEN), ("red", RED)] {
+            let (l, t, r, b) =
+                color_bounds_in(&buffer, color, legend_xs.clone(), legend_ys.clone())
+                    .unwrap_or_else(|| panic!("{name} swatch not drawn"));
+            let width = r - l + 1;
+            assert!(
+                width.abs_diff(SINGLE_CHART_STYLE.label_font_size) <= 1,
+                "{name} swatch width {width}"
+            );
+            assert!(b - t <= 5, "{name} swatch height {}", b - t);
+        }
+    }
+
+    #[test]
+    fn test_multi_series_single_day_marks_points() {
+        let series = [named("threshold", GREEN, vec![3.0])];
+
+        let buffer = render_multi(1, &series);
+
+        let (l, _, r, _) =
+            color_bounds_in(&buffer, GREEN, 200..WIDTH, 0..HEIGHT).expect("marker not drawn");
+        assert!(r - l <= SINGLE_CHART_STYLE.line_width * 4 + 1, "{l}..{r}");
+    }
+
+    #[test]
+    fn test_multi_series_uses_shortest_length() {
+        let series = [
+            named("threshold", GREEN, vec![1.0; 30]),
+            named("incident_opened", RED, vec![1.0; 3]),
+        ];
+
+        let png = generate_multi_series_chart("Alerts", &[Utc::now(); 10], &series, "en");
+
+        assert!(png.unwrap().starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_multi_series_without_data_is_placeholder() {
+        let cases: [(usize, Vec<NamedSeries>); 3] = [
+            (30, vec![]),
+            (0, vec![named("threshold", GREEN, vec![1.0; 30])]),
+            (30, vec![named("threshold", GREEN, vec![])]),
+        ];
+        for (days, series) in cases {
+            let buffer = render_multi(days, &series);
+
+            assert_eq!(color_bounds(&buffer, GREEN), None, "{days} days");
+            assert_eq!(
+                buffer,
+                render_placeholder_with("Alerts", "en"),
+                "{days} days"
+            );
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn test_chart_renders_any_series_length(
+            len in prop::sample::select(vec![0usize, 1, 2, 1000]),
+            seed in timestamps(1000),
+            locale in prop::sample::select(vec!["en", "ko", "ja"]),
+        ) {
+            let data = MetricData {
+                timestamps: seed[..len].to_vec(),
+                values: (0..len).map(|i| (i % 7) as f64).collect(),
+                unit: String::new(),
+            };
+
+            let png = generate_single_chart(
+                "Online Users",
+                &data,
+                RED,
+                YAxisFormat::Count,
+                locale,
+            );
+
+            prop_assert!(png.is_ok());
+            prop_assert!(png.unwrap().starts_with(b"\x89PNG"));
+        }
+    }
+}
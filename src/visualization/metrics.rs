@@ -0,0 +1,159 @@
+//! Charted metrics and their database keys
+//!
+//! Each chartable metric is defined once here, with the `metric_logs` name it
+//! is stored under, so the dashboard and `/status metric` load the same data.
+
+use std::time::Duration as StdDuration;
+
+use chrono::Duration;
+use plotters::style::RGBColor;
+use sea_orm::DatabaseConnection;
+
+use crate::cache::MetricCache;
+use crate::visualization::dashboard::YAxisFormat;
+use crate::visualization::query::{MetricData, load_metric_as_percent, load_metric_downsampled};
+use crate::visualization::theme::{GRAPH_COLOR, GREEN, RED};
+
+/// How a metric's values are shown in text
+#[derive(Debug, Clone, Copy)]
+pub enum ValueFormat {
+    /// Counts, abbreviated from 1000 (e.g. `12.3k`)
+    Count,
+    /// Milliseconds (e.g. `85 ms`)
+    Milliseconds,
+    /// Percentage with a fixed number of decimals
+    Percent { decimals: usize },
+}
+
+impl ValueFormat {
+    /// Format a value for an embed field
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Self::Count if value >= 1000.0 => format!("{:.1}k", value / 1000.0),
+            Self::Count => format!("{:.0}", value),
+            Self::Milliseconds => format!("{:.0} ms", value),
+            Self::Percent { decimals } => format!("{:.*}%", decimals, value),
+        }
+    }
+}
+
+/// One chartable metric
+#[derive(Debug, Clone, Copy)]
+pub struct ChartMetric {
+    /// Option value, also used in `embeds.metric.names.<key>`
+    pub key: &'static str,
+    /// Name in `metric_logs.metric_name`
+    pub metric_name: &'static str,
+    /// Chart title
+    pub title: &'static str,
+    /// Stored as a 0-1 ratio and charted as a percentage
+    pub percent: bool,
+    pub color: RGBColor,
+    /// Y-axis labels when charted on its own
+    pub y_format: YAxisFormat,
+    pub value_format: ValueFormat,
+}
+
+impl ChartMetric {
+    /// Load the metric for the last `range`, downsampled (and as a percentage
+    /// for ratio metrics)
+    pub async fn load(
+        &self,
+        db: &DatabaseConnection,
+        range: Duration,
+    ) -> Result<MetricData, sea_orm::DbErr> {
+        if self.percent {
+            load_metric_as_percent(db, self.metric_name, range).await
+        } else {
+            load_metric_downsampled(db, self.metric_name, range).await
+        }
+    }
+
+    /// [`load`](Self::load) through the metric cache, reusing a series
+    /// loaded within `ttl`
+    pub async fn load_cached(
+        &self,
+        db: &DatabaseConnection,
+        cache: &MetricCache,
+        ttl: StdDuration,
+        range: Duration,
+    ) -> Result<MetricData, sea_orm::DbErr> {
+        let key = MetricCache::key(self.metric_name, self.percent, range);
+        cache.get_or_load(key, ttl, || self.load(db, range)).await
+    }
+}
+
+pub const ONLINE_USERS: ChartMetric = ChartMetric {
+    key: "online_users",
+    metric_name: "visits",
+    title: "Online Users",
+    percent: false,
+    color: GRAPH_COLOR,
+    y_format: YAxisFormat::Count,
+    value_format: ValueFormat::Count,
+};
+
+pub const API_LATENCY: ChartMetric = ChartMetric {
+    key: "api_latency",
+    metric_name: "api_latency",
+    title: "API Latency",
+    percent: false,
+    color: GRAPH_COLOR,
+    y_format: YAxisFormat::Count,
+    value_format: ValueFormat::Milliseconds,
+};
+
+pub const API_REQUESTS: ChartMetric = ChartMetric {
+    key: "api_requests",
+    metric_name: "api_requests",
+    title: "API Requests",
+    percent: false,
+    color: GRAPH_COLOR,
+    y_format: YAxisFormat::Count,
+    value_format: ValueFormat::Count,
+};
+
+pub const API_ERROR_RATE: ChartMetric = ChartMetric {
+    key: "api_error_rate",
+    metric_name: "api_errors",
+    title: "API Error Rate",
+    percent: true,
+    color: RED,
+    y_format: YAxisFormat::PercentAuto,
+    value_format: ValueFormat::Percent { decimals: 4 },
+};
+
+pub const STEAM_AUTH: ChartMetric = ChartMetric {
+    key: "steam_auth",
+    metric_name: "extauth_steam",
+    title: "Steam Auth Success Rate",
+    percent: true,
+    color: GREEN,
+    y_format: YAxisFormat::Percent,
+    value_format: ValueFormat::Percent { decimals: 1 },
+};
+
+pub const META_AUTH: ChartMetric = ChartMetric {
+    key: "meta_auth",
+    metric_name: "extauth_oculus",
+    title: "Meta Auth Success Rate",
+    percent: true,
+    color: GREEN,
+    y_format: YAxisFormat::Percent,
+    value_format: ValueFormat::Percent { decimals: 1 },
+};
+
+/// All charted metrics, in dashboard grid order
+pub const CHART_METRICS: [ChartMetric; 6] = [
+    ONLINE_USERS,
+    API_LATENCY,
+    API_REQUESTS,
+    API_ERROR_RATE,
+    STEAM_AUTH,
+    META_AUTH,
+];
+
+/// Find a metric by option value
+pub fn find(key: &str) -> Option<&'static ChartMetric> {
+    CHART_METRICS.iter().find(|metric| metric.key == key)
+}
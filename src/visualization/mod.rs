@@ -4,7 +4,13 @@
 //! stored in SQLite, for embedding in Discord messages.
 
 pub mod dashboard;
+pub mod metrics;
 pub mod query;
+pub mod single_chart;
 pub mod theme;
+pub mod uptime;
 
-pub use dashboard::generate_dashboard;
+pub use dashboard::{
+    ChartMarker, NamedSeries, YAxisFormat, generate_dashboard, generate_marked_chart,
+    generate_multi_series_chart, generate_single_chart,
+};
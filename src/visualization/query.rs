@@ -2,14 +2,18 @@
 //!
 //! Loads metric data from SQLite and performs downsampling.
 
-use chrono::{DateTime, Duration, Utc};
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use chrono::{DateTime, Duration, DurationRound, NaiveDateTime, Utc};
+use sea_orm::sea_query::{Expr, Func, SimpleExpr};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use crate::entity::metric_logs;
-use crate::visualization::theme::{DOWNSAMPLE_MINUTES, HOURS_RANGE};
+use crate::entity::{metric_logs, user_reports};
+use crate::repository::reports::UNCOUNTED_STATUSES;
+use crate::visualization::theme::{DASHBOARD_POINTS, DOWNSAMPLE_MINUTES};
 
 /// Metric data for chart rendering
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricData {
     pub timestamps: Vec<DateTime<Utc>>,
     pub values: Vec<f64>,
@@ -35,18 +39,50 @@ impl MetricData {
     pub fn max(&self) -> f64 {
         self.values.iter().cloned().fold(0.0_f64, f64::max)
     }
+
+    /// Get minimum value (0 for empty data, like `max`)
+    pub fn min(&self) -> f64 {
+        if self.values.is_empty() {
+            0.0
+        } else {
+            self.values.iter().cloned().fold(f64::INFINITY, f64::min)
+        }
+    }
+
+    /// Get the most recent value
+    pub fn latest(&self) -> Option<f64> {
+        self.values.last().copied()
+    }
+
+    /// Get the `p`-th percentile (0-100), interpolating between neighbors
+    ///
+    /// `p` is clamped to 0-100. Returns 0 for empty data, like `avg`.
+    pub fn percentile(&self, p: f64) -> f64 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+
+        let mut sorted = self.values.clone();
+        sorted.sort_by(f64::total_cmp);
+
+        let rank = p.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+    }
 }
 
-/// Load metric data from database
-pub async fn load_metric(
+/// Load every data point of a metric in `[start, end]`, oldest first
+pub async fn load_metric_raw(
     db: &DatabaseConnection,
     metric_name: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
 ) -> Result<MetricData, sea_orm::DbErr> {
-    let cutoff = Utc::now() - Duration::hours(HOURS_RANGE);
-
     let data: Vec<metric_logs::Model> = metric_logs::Entity::find()
         .filter(metric_logs::Column::MetricName.eq(metric_name))
-        .filter(metric_logs::Column::Timestamp.gte(cutoff))
+        .filter(metric_logs::Column::Timestamp.gte(start))
+        .filter(metric_logs::Column::Timestamp.lte(end))
         .order_by_asc(metric_logs::Column::Timestamp)
         .all(db)
         .await?;
@@ -62,13 +98,118 @@ pub async fn load_metric(
     })
 }
 
+/// Load a metric between `start` and `end`, downsampled when the window is large
+///
+/// Windows with more than [`DASHBOARD_POINTS`] raw points are averaged into
+/// buckets of [`downsample_interval`], so any window charts at about the same
+/// density. Smaller windows are returned as stored.
+pub async fn load_metric_range(
+    db: &DatabaseConnection,
+    metric_name: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<MetricData, sea_orm::DbErr> {
+    let data = load_metric_raw(db, metric_name, start, end).await?;
+    if data.values.len() <= DASHBOARD_POINTS as usize {
+        return Ok(data);
+    }
+    Ok(downsample_by(data, downsample_interval(end - start)))
+}
+
+/// Distinct reporting users per UTC hour over the last `window`, oldest first
+///
+/// Hours are grouped in SQL with `strftime`; hours without reports are
+/// filled in as zero, so the series is never empty. Duplicate and withdrawn
+/// reports are not counted. Points are timestamped at the start of the hour.
+pub async fn load_report_counts(
+    db: &DatabaseConnection,
+    window: Duration,
+) -> Result<MetricData, sea_orm::DbErr> {
+    let now = Utc::now();
+    let hour = Duration::hours(1);
+    let start = (now - window).duration_trunc(hour).unwrap_or(now - window);
+
+    let rows = user_reports::Entity::find()
+        .select_only()
+        .column_as(
+            Expr::cust("strftime('%Y-%m-%d %H:00:00', created_at)"),
+            "hour",
+        )
+        .column_as(Expr::cust("COUNT(DISTINCT user_id)"), "count")
+        .filter(user_reports::Column::Status.is_not_in(UNCOUNTED_STATUSES))
+        .filter(user_reports::Column::CreatedAt.gte(start))
+        .group_by(Expr::cust("hour"))
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await?;
+    let counts: HashMap<DateTime<Utc>, i64> = rows
+        .into_iter()
+        .filter_map(|(hour, count)| {
+            let at = NaiveDateTime::parse_from_str(&hour, "%Y-%m-%d %H:%M:%S").ok()?;
+            Some((at.and_utc(), count))
+        })
+        .collect();
+
+    let mut timestamps = Vec::new();
+    let mut values = Vec::new();
+    let mut at = start;
+    while at <= now {
+        timestamps.push(at);
+        values.push(counts.get(&at).copied().unwrap_or(0) as f64);
+        at += hour;
+    }
+
+    Ok(MetricData {
+        timestamps,
+        values,
+        unit: "reports".to_string(),
+    })
+}
+
+/// Average of a metric over the last `range`, computed in SQL
+///
+/// Returns `None` when there are no data points in the range.
+pub async fn average_metric(
+    db: &DatabaseConnection,
+    metric_name: &str,
+    range: Duration,
+) -> Result<Option<f64>, sea_orm::DbErr> {
+    let cutoff = Utc::now() - range;
+
+    let average = metric_logs::Entity::find()
+        .select_only()
+        .column_as(
+            SimpleExpr::from(Func::avg(Expr::col(metric_logs::Column::Value))),
+            "average",
+        )
+        .filter(metric_logs::Column::MetricName.eq(metric_name))
+        .filter(metric_logs::Column::Timestamp.gte(cutoff))
+        .into_tuple::<Option<f64>>()
+        .one(db)
+        .await?;
+
+    Ok(average.flatten())
+}
+
 /// Downsample data by averaging over intervals
 pub fn downsample(data: MetricData) -> MetricData {
+    downsample_by(data, Duration::minutes(DOWNSAMPLE_MINUTES))
+}
+
+/// Downsample bucket width for a dashboard time range
+///
+/// Keeps about [`DASHBOARD_POINTS`] points per chart (5 minutes for the
+/// 12-hour default), and never less than one minute.
+pub fn downsample_interval(range: Duration) -> Duration {
+    (range / DASHBOARD_POINTS).max(Duration::minutes(1))
+}
+
+/// Downsample data by averaging over buckets of `interval`
+pub fn downsample_by(data: MetricData, interval: Duration) -> MetricData {
     if data.values.is_empty() {
         return data;
     }
 
-    let interval = Duration::minutes(DOWNSAMPLE_MINUTES);
     let mut downsampled_timestamps = Vec::new();
     let mut downsampled_values = Vec::new();
 
@@ -110,20 +251,22 @@ pub fn to_percent(mut data: MetricData) -> MetricData {
     data
 }
 
-/// Load and process metric data (load + downsample)
+/// Load and process metric data for the last `range` (load + downsample)
 pub async fn load_metric_downsampled(
     db: &DatabaseConnection,
     metric_name: &str,
+    range: Duration,
 ) -> Result<MetricData, sea_orm::DbErr> {
-    let data = load_metric(db, metric_name).await?;
-    Ok(downsample(data))
+    let end = Utc::now();
+    load_metric_range(db, metric_name, end - range, end).await
 }
 
-/// Load and process metric as percentage (load + downsample + to_percent)
+/// Load and process metric as percentage for the last `range` (load + downsample + to_percent)
 pub async fn load_metric_as_percent(
     db: &DatabaseConnection,
     metric_name: &str,
+    range: Duration,
 ) -> Result<MetricData, sea_orm::DbErr> {
-    let data = load_metric(db, metric_name).await?;
-    Ok(to_percent(downsample(data)))
+    let data = load_metric_downsampled(db, metric_name, range).await?;
+    Ok(to_percent(data))
 }
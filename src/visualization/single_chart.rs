@@ -0,0 +1,52 @@
+//! Large single-metric chart for `/status metric`
+//!
+//! Draws one metric at 1200x600 with the dashboard's chart renderer, using
+//! larger fonts and more x-axis labels.
+
+use chrono::Duration;
+use plotters::backend::BitMapBackend;
+use plotters::drawing::IntoDrawingArea;
+use sea_orm::DatabaseConnection;
+
+use crate::cache::{self, MetricCache};
+use crate::visualization::dashboard::{draw_chart, encode_png};
+use crate::visualization::metrics::ChartMetric;
+use crate::visualization::query::MetricData;
+use crate::visualization::theme::*;
+
+/// Render `metric` over the last `range` as a 1200x600 PNG
+///
+/// Returns the image with the charted data, so callers can show statistics
+/// for exactly what was drawn. The series is read through `cache`. A metric
+/// without data renders the "no data" placeholder.
+pub async fn generate_single(
+    db: &DatabaseConnection,
+    cache: &MetricCache,
+    metric: &ChartMetric,
+    range: Duration,
+    locale: &str,
+) -> Result<(Vec<u8>, MetricData), Box<dyn std::error::Error + Send + Sync>> {
+    let ttl = cache::load_ttl(db).await;
+    let data = metric.load_cached(db, cache, ttl, range).await?;
+    let mut buffer = vec![0u8; (LARGE_CHART_WIDTH * LARGE_CHART_HEIGHT * 3) as usize];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (LARGE_CHART_WIDTH, LARGE_CHART_HEIGHT))
+            .into_drawing_area();
+        root.fill(&BG_COLOR)?;
+        let area = root.margin(16, 16, 16, 16);
+        draw_chart(
+            &area,
+            metric.title,
+            &data,
+            metric.color,
+            metric.y_format,
+            &LARGE_CHART_STYLE,
+            locale,
+        )?;
+        root.present()?;
+    }
+
+    let png = encode_png(&buffer, LARGE_CHART_WIDTH, LARGE_CHART_HEIGHT)?;
+    Ok((png, data))
+}
@@ -19,16 +19,73 @@ pub const MUTED_COLOR: RGBColor = RGBColor(0x94, 0x9B, 0xA4);
 /// Status colors
 pub const GREEN: RGBColor = RGBColor(0x57, 0xF2, 0x87);
 pub const RED: RGBColor = RGBColor(0xED, 0x42, 0x45);
-#[allow(dead_code)]
 pub const YELLOW: RGBColor = RGBColor(0xFE, 0xE7, 0x5C);
-#[allow(dead_code)]
 pub const ORANGE: RGBColor = RGBColor(0xF0, 0xB1, 0x32);
+pub const BLUE: RGBColor = RGBColor(0x58, 0x65, 0xF2);
+
+/// Colors assigned to named series in order (multi-series charts)
+pub const SERIES_COLORS: [RGBColor; 6] = [GRAPH_COLOR, GREEN, RED, ORANGE, BLUE, YELLOW];
 
 /// Dashboard configuration
 pub const IMAGE_SIZE: u32 = 2400;
-pub const HOURS_RANGE: i64 = 12;
 pub const DOWNSAMPLE_MINUTES: i64 = 5;
+/// Points per dashboard chart; the bucket width scales with the time range
+pub const DASHBOARD_POINTS: i32 = 144;
 
 /// Font sizes (scaled for high resolution)
 pub const TITLE_FONT_SIZE: u32 = 48;
 pub const LABEL_FONT_SIZE: u32 = 22;
+
+/// Single chart size (alert context images)
+pub const SINGLE_CHART_WIDTH: u32 = 600;
+pub const SINGLE_CHART_HEIGHT: u32 = 300;
+
+/// Large single-metric chart size (`/status metric`)
+pub const LARGE_CHART_WIDTH: u32 = 1200;
+pub const LARGE_CHART_HEIGHT: u32 = 600;
+
+/// Size-dependent chart layout
+#[derive(Debug, Clone, Copy)]
+pub struct ChartStyle {
+    pub title_font_size: u32,
+    pub label_font_size: u32,
+    pub margin: u32,
+    pub x_label_area: u32,
+    pub y_label_area: u32,
+    pub line_width: u32,
+    /// Approximate number of x-axis labels
+    pub x_labels: usize,
+}
+
+/// Layout for each chart in the 2400x2400 dashboard
+pub const DASHBOARD_STYLE: ChartStyle = ChartStyle {
+    title_font_size: TITLE_FONT_SIZE,
+    label_font_size: LABEL_FONT_SIZE,
+    margin: 20,
+    x_label_area: 70,
+    y_label_area: 120,
+    line_width: 4,
+    x_labels: 5,
+};
+
+/// Layout for a standalone 600x300 chart
+pub const SINGLE_CHART_STYLE: ChartStyle = ChartStyle {
+    title_font_size: 20,
+    label_font_size: 12,
+    margin: 8,
+    x_label_area: 28,
+    y_label_area: 56,
+    line_width: 2,
+    x_labels: 5,
+};
+
+/// Layout for a large 1200x600 single-metric chart (`/status metric`)
+pub const LARGE_CHART_STYLE: ChartStyle = ChartStyle {
+    title_font_size: 36,
+    label_font_size: 18,
+    margin: 16,
+    x_label_area: 44,
+    y_label_area: 96,
+    line_width: 3,
+    x_labels: 10,
+};
@@ -0,0 +1,177 @@
+//! Component uptime from status history
+//!
+//! `component_logs` only has a row when the status page reports a change of
+//! page state, so each row's status is assumed to hold until the next row.
+//! Time before a component's first known row in the window is not counted,
+//! which keeps components added partway through the window from being
+//! penalized.
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::entity::component_logs;
+
+/// Status counted as up
+const OPERATIONAL: &str = "operational";
+
+/// Fraction of known time in `[since, until]` spent operational
+///
+/// `prior` is the status of the last row before `since`, which holds at the
+/// start of the window. `rows` are `(source_timestamp, status)` pairs inside
+/// the window, oldest first. Returns `None` if no time in the window has a
+/// known status.
+pub fn uptime_fraction(
+    prior: Option<&str>,
+    rows: &[(DateTime<Utc>, String)],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Option<f64> {
+    let mut known = Duration::zero();
+    let mut up = Duration::zero();
+    let mut current: Option<(DateTime<Utc>, &str)> = prior.map(|status| (since, status));
+
+    let changes = rows
+        .iter()
+        .filter(|(at, _)| *at >= since && *at <= until)
+        .map(|(at, status)| (*at, status.as_str()));
+    for (at, status) in changes.chain(std::iter::once((until, ""))) {
+        if let Some((from, previous)) = current {
+            let span = at - from;
+            known += span;
+            if previous == OPERATIONAL {
+                up += span;
+            }
+        }
+        current = Some((at, status));
+    }
+
+    let known_ms = known.num_milliseconds();
+    (known_ms > 0).then(|| up.num_milliseconds() as f64 / known_ms as f64)
+}
+
+/// Fraction of the last `window` a component spent operational
+///
+/// `None` if the component has no status history covering the window.
+pub async fn component_uptime(
+    db: &DatabaseConnection,
+    component_id: &str,
+    window: Duration,
+) -> Result<Option<f64>, DbErr> {
+    let until = Utc::now();
+    let since = until - window;
+
+    let prior = component_logs::Entity::find()
+        .filter(component_logs::Column::ComponentId.eq(component_id))
+        .filter(component_logs::Column::SourceTimestamp.lt(since))
+        .order_by_desc(component_logs::Column::SourceTimestamp)
+        .one(db)
+        .await?;
+
+    let rows: Vec<(DateTime<Utc>, String)> = component_logs::Entity::find()
+        .filter(component_logs::Column::ComponentId.eq(component_id))
+        .filter(component_logs::Column::SourceTimestamp.gte(since))
+        .order_by_asc(component_logs::Column::SourceTimestamp)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|row| (row.source_timestamp, row.status))
+        .collect();
+
+    Ok(uptime_fraction(
+        prior.as_ref().map(|row| row.status.as_str()),
+        &rows,
+        since,
+        until,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn hour(h: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 10, 0, 0, 0).unwrap() + Duration::hours(h)
+    }
+
+    fn rows(changes: &[(i64, &str)]) -> Vec<(DateTime<Utc>, String)> {
+        changes
+            .iter()
+            .map(|(h, status)| (hour(*h), status.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_input_is_unknown() {
+        assert_eq!(uptime_fraction(None, &[], hour(0), hour(10)), None);
+    }
+
+    #[test]
+    fn test_empty_window_is_unknown() {
+        assert_eq!(
+            uptime_fraction(Some(OPERATIONAL), &[], hour(5), hour(5)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_single_prior_state_covers_window() {
+        assert_eq!(
+            uptime_fraction(Some(OPERATIONAL), &[], hour(0), hour(10)),
+            Some(1.0)
+        );
+        assert_eq!(
+            uptime_fraction(Some("major_outage"), &[], hour(0), hour(10)),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_single_row_in_window() {
+        let rows = rows(&[(4, OPERATIONAL)]);
+
+        // Time before the first known row is not counted
+        assert_eq!(uptime_fraction(None, &rows, hour(0), hour(10)), Some(1.0));
+    }
+
+    #[test]
+    fn test_changes_split_window() {
+        let rows = rows(&[(2, "major_outage"), (4, OPERATIONAL)]);
+
+        assert_eq!(
+            uptime_fraction(Some(OPERATIONAL), &rows, hour(0), hour(10)),
+            Some(0.8)
+        );
+    }
+
+    #[test]
+    fn test_partial_overlap_with_since() {
+        // The outage started before the window and ended inside it
+        let rows = rows(&[(-5, "major_outage"), (2, OPERATIONAL)]);
+
+        assert_eq!(
+            uptime_fraction(Some("major_outage"), &rows, hour(0), hour(10)),
+            Some(0.8)
+        );
+    }
+
+    #[test]
+    fn test_rows_after_until_ignored() {
+        let rows = rows(&[(5, "major_outage"), (12, OPERATIONAL)]);
+
+        assert_eq!(
+            uptime_fraction(Some(OPERATIONAL), &rows, hour(0), hour(10)),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn test_row_at_since_replaces_prior() {
+        let rows = rows(&[(0, OPERATIONAL)]);
+
+        assert_eq!(
+            uptime_fraction(Some("major_outage"), &rows, hour(0), hour(10)),
+            Some(1.0)
+        );
+    }
+}